@@ -0,0 +1,61 @@
+use std::process::Command;
+
+/// Build Script
+///
+/// Embeds build metadata for `/version` and the startup banner:
+/// - `GIT_SHA`: taken from the environment when set (e.g. a Docker build arg, since `.git` is
+///   not part of the image context), otherwise from `git rev-parse`.
+/// - `BUILD_TIMESTAMP`: taken from the environment when set, otherwise the RFC 3339 commit time
+///   of `HEAD`. It is not the wall-clock time of the build: this script only re-runs when the
+///   git refs below change, so a wall-clock time would go stale in incremental builds.
+///
+/// Nothing here may fail the build: when git is unavailable the variable is simply left
+/// unset and the binary reports "unknown".
+fn main() {
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+    println!("cargo:rerun-if-env-changed=BUILD_TIMESTAMP");
+
+    let sha = std::env::var("GIT_SHA")
+        .ok()
+        .filter(|sha| !sha.trim().is_empty())
+        .or_else(|| git(&["rev-parse", "--short=12", "HEAD"]));
+    if let Some(sha) = sha {
+        println!("cargo:rustc-env=GIT_SHA={}", sha.trim());
+    }
+
+    // Re-run when HEAD moves so the embedded hash and timestamp never go stale in local
+    // builds. `git gc` moves branch tips into `packed-refs`; a path that does not exist would
+    // re-run the script on every build, so it is only watched once it does.
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        println!("cargo:rerun-if-changed={}/refs", git_dir);
+        let packed_refs = std::path::Path::new(&git_dir).join("packed-refs");
+        if packed_refs.exists() {
+            println!("cargo:rerun-if-changed={}", packed_refs.display());
+        }
+    }
+
+    let built_at = std::env::var("BUILD_TIMESTAMP")
+        .ok()
+        .map(|at| at.trim().to_string())
+        .filter(|at| !at.is_empty())
+        .or_else(|| {
+            let committed = git(&["show", "-s", "--format=%cI", "HEAD"])?;
+            let committed = chrono::DateTime::parse_from_rfc3339(&committed).ok()?;
+            Some(committed.with_timezone(&chrono::Utc).to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        });
+    if let Some(built_at) = built_at {
+        println!("cargo:rustc-env=BUILD_TIMESTAMP={}", built_at);
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
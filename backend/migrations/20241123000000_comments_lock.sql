@@ -0,0 +1,5 @@
+-- 6. Discussion Lock
+-- Freezes new comments and likes on a project (e.g. after grading) while existing
+-- comments remain readable. Set by the project owner or an admin.
+ALTER TABLE public.projects
+    ADD COLUMN IF NOT EXISTS comments_locked BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,9 @@
+-- 7. Submission Readiness
+-- Supporting columns for the pre-review checklist:
+--   links         Free-form label -> URL map (repository, demo, etc.). Keys are user data.
+--   video_waived  Explicit opt-out for projects that genuinely have no demo video.
+--   submitted_at  Set when the owner submits a draft for review (NULL = still a draft).
+ALTER TABLE public.projects
+    ADD COLUMN IF NOT EXISTS links JSONB NOT NULL DEFAULT '{}'::jsonb,
+    ADD COLUMN IF NOT EXISTS video_waived BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS submitted_at TIMESTAMPTZ;
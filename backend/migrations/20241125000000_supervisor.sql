@@ -0,0 +1,6 @@
+-- 8. Supervisor
+-- Free-text name of the staff member supervising the project (NULL until assigned).
+ALTER TABLE public.projects
+    ADD COLUMN IF NOT EXISTS supervisor TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_projects_supervisor ON public.projects(supervisor);
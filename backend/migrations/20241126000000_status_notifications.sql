@@ -0,0 +1,10 @@
+-- 9. Status Change Notifications
+-- Admins can notify a project owner when visibility changes, optionally with a reason.
+ALTER TABLE public.notifications
+    ADD COLUMN IF NOT EXISTS message TEXT;
+
+ALTER TABLE public.notifications
+    DROP CONSTRAINT IF EXISTS notifications_type_check;
+
+ALTER TABLE public.notifications
+    ADD CONSTRAINT notifications_type_check CHECK (type IN ('like', 'comment', 'status_change'));
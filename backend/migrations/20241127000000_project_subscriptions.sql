@@ -0,0 +1,36 @@
+-- 10. Comment Subscriptions
+-- Explicit per-user preference for comment notifications on a project. Absence of a row means
+-- the default applies: the owner and everyone who has commented are subscribed.
+CREATE TABLE IF NOT EXISTS public.project_subscriptions (
+    project_id UUID NOT NULL REFERENCES public.projects(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES auth.users(id) ON DELETE CASCADE,
+    state TEXT NOT NULL CHECK (state IN ('subscribed', 'muted')),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, user_id)
+);
+
+-- Trigger Function: Handle Comments (replaces the owner-only version)
+-- Recipients are the owner, prior participants and explicit subscribers, minus anyone who
+-- muted the project and minus the commenter themselves.
+CREATE OR REPLACE FUNCTION public.handle_new_comment()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO public.notifications (user_id, actor_id, project_id, type)
+    SELECT r.user_id, NEW.user_id, NEW.project_id, 'comment'
+    FROM (
+        SELECT p.user_id FROM public.projects p WHERE p.id = NEW.project_id
+        UNION
+        SELECT c.user_id FROM public.project_comments c WHERE c.project_id = NEW.project_id
+        UNION
+        SELECT s.user_id FROM public.project_subscriptions s
+        WHERE s.project_id = NEW.project_id AND s.state = 'subscribed'
+    ) r
+    WHERE r.user_id <> NEW.user_id
+      AND NOT EXISTS (
+          SELECT 1 FROM public.project_subscriptions m
+          WHERE m.project_id = NEW.project_id AND m.user_id = r.user_id AND m.state = 'muted'
+      );
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,13 @@
+-- 11. Upload Tracking
+-- One row per presigned upload URL issued. Used to cap how many URLs a user may request and how
+-- many uploads may be left unconfirmed (issued, unexpired, not yet referenced by a project).
+CREATE TABLE IF NOT EXISTS public.uploads (
+    id UUID DEFAULT gen_random_uuid() PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES auth.users(id) ON DELETE CASCADE,
+    object_key TEXT NOT NULL UNIQUE,
+    purpose TEXT NOT NULL CHECK (purpose IN ('cover_image', 'video', 'report')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_uploads_user_created ON public.uploads(user_id, created_at);
@@ -0,0 +1,17 @@
+-- 12. Programmes
+-- Admin-managed lookup of degree programmes (e.g. 'CS' Computer Science). A project may belong
+-- to several programmes, which is how joint degrees are expressed.
+CREATE TABLE IF NOT EXISTS public.programmes (
+    code TEXT PRIMARY KEY CHECK (code ~ '^[A-Z0-9-]{2,16}$'),
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS public.project_programmes (
+    project_id UUID NOT NULL REFERENCES public.projects(id) ON DELETE CASCADE,
+    programme_code TEXT NOT NULL REFERENCES public.programmes(code),
+    PRIMARY KEY (project_id, programme_code)
+);
+
+-- Serves the `programme` filter on the public listing.
+CREATE INDEX idx_project_programmes_code ON public.project_programmes(programme_code);
@@ -0,0 +1,27 @@
+-- 13. Comment Reports and Auto-Hide
+-- Users report comments for moderation. Once a comment collects enough distinct pending
+-- reports it is hidden from public listings until a moderator restores it.
+ALTER TABLE public.project_comments
+    ADD COLUMN IF NOT EXISTS hidden_pending_review BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS public.comment_reports (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    comment_id BIGINT NOT NULL REFERENCES public.project_comments(id) ON DELETE CASCADE,
+    reporter_id UUID NOT NULL REFERENCES auth.users(id) ON DELETE CASCADE,
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Set when a moderator resolves the report (e.g. restores the comment).
+    resolved_at TIMESTAMPTZ
+);
+
+-- One pending report per user per comment; the auto-hide threshold counts distinct reporters.
+CREATE UNIQUE INDEX idx_comment_reports_pending
+    ON public.comment_reports(comment_id, reporter_id) WHERE resolved_at IS NULL;
+
+-- Moderators are notified when a comment is hidden.
+ALTER TABLE public.notifications
+    DROP CONSTRAINT IF EXISTS notifications_type_check;
+
+ALTER TABLE public.notifications
+    ADD CONSTRAINT notifications_type_check
+    CHECK (type IN ('like', 'comment', 'status_change', 'comment_hidden'));
@@ -0,0 +1,16 @@
+-- 14. Stale Draft Sweeps
+-- The maintenance runner sends one reminder for a draft left untouched for a while and, much
+-- later, flags it for the orphaned-upload cleanup. Each timestamp is set at most once.
+ALTER TABLE public.projects
+    ADD COLUMN IF NOT EXISTS draft_reminded_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS cleanup_flagged_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_projects_stale_drafts
+    ON public.projects(updated_at) WHERE submitted_at IS NULL AND is_public = false;
+
+ALTER TABLE public.notifications
+    DROP CONSTRAINT IF EXISTS notifications_type_check;
+
+ALTER TABLE public.notifications
+    ADD CONSTRAINT notifications_type_check
+    CHECK (type IN ('like', 'comment', 'status_change', 'comment_hidden', 'draft_reminder'));
@@ -0,0 +1,11 @@
+-- 15. Project Tags
+-- Free-form, owner-chosen labels (e.g. 'machine-learning'). The API lower-cases them and caps
+-- a project at 10 tags of at most 30 characters each.
+CREATE TABLE IF NOT EXISTS public.project_tags (
+    project_id UUID NOT NULL REFERENCES public.projects(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL CHECK (char_length(tag) BETWEEN 1 AND 30),
+    PRIMARY KEY (project_id, tag)
+);
+
+-- Serves the `tag` filter on the public listing and the GET /tags counts.
+CREATE INDEX idx_project_tags_tag ON public.project_tags(tag);
@@ -0,0 +1,10 @@
+-- 16. Settings
+-- Runtime-adjustable key/value settings (submission deadline, maintenance mode, feature flags).
+-- Which keys exist and what their values may be is registered in code (`settings::REGISTRY`);
+-- the table only stores the JSON value an admin last set.
+CREATE TABLE IF NOT EXISTS public.settings (
+    key TEXT PRIMARY KEY,
+    value JSONB NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_by UUID REFERENCES public.profiles(id) ON DELETE SET NULL
+);
@@ -0,0 +1,21 @@
+-- 17. Anonymous Project Feedback
+-- Visitors can leave feedback on a public project without an account. Only the owner (and
+-- admins) read it through the API; it is never part of a public response.
+CREATE TABLE IF NOT EXISTS public.project_feedback (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    project_id UUID NOT NULL REFERENCES public.projects(id) ON DELETE CASCADE,
+    message TEXT NOT NULL CHECK (char_length(message) BETWEEN 10 AND 1000),
+    -- Optional, so the owner can reply; never shown publicly.
+    contact_email TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_feedback_project
+    ON public.project_feedback(project_id, created_at DESC);
+
+ALTER TABLE public.notifications
+    DROP CONSTRAINT IF EXISTS notifications_type_check;
+
+ALTER TABLE public.notifications
+    ADD CONSTRAINT notifications_type_check
+    CHECK (type IN ('like', 'comment', 'status_change', 'comment_hidden', 'draft_reminder', 'feedback'));
@@ -0,0 +1,9 @@
+-- 18. Project Views
+-- A running count of public detail-page views, bumped in place by POST /projects/{id}/view.
+-- Not a per-visitor log: it is a popularity signal, not an audit trail.
+ALTER TABLE public.projects
+    ADD COLUMN IF NOT EXISTS view_count BIGINT NOT NULL DEFAULT 0;
+
+-- Serves the `most_viewed` listing order.
+CREATE INDEX IF NOT EXISTS idx_projects_view_count
+    ON public.projects(view_count DESC, created_at DESC) WHERE is_public = true;
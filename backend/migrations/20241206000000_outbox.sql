@@ -0,0 +1,48 @@
+-- 19. Notification Outbox
+-- Comment fan-out moves out of the `on_project_comment` trigger and into the application, so
+-- that a large one can be queued here instead of writing every notification inside the
+-- request transaction. The dispatcher expands a queued job in batches, in recipient order,
+-- and records its position in `cursor` in the same transaction as each batch.
+
+-- Recipients of a comment notification: the owner, prior participants and explicit
+-- subscribers, minus anyone who muted the project and minus the commenter themselves.
+-- Shared by the synchronous path, the recipient count and the dispatcher batches.
+CREATE OR REPLACE FUNCTION public.comment_recipients(p_project_id UUID, p_actor_id UUID)
+RETURNS TABLE (recipient_id UUID) AS $$
+    SELECT r.user_id
+    FROM (
+        SELECT p.user_id FROM public.projects p WHERE p.id = p_project_id
+        UNION
+        SELECT c.user_id FROM public.project_comments c WHERE c.project_id = p_project_id
+        UNION
+        SELECT s.user_id FROM public.project_subscriptions s
+        WHERE s.project_id = p_project_id AND s.state = 'subscribed'
+    ) r
+    WHERE r.user_id <> p_actor_id
+      AND NOT EXISTS (
+          SELECT 1 FROM public.project_subscriptions m
+          WHERE m.project_id = p_project_id AND m.user_id = r.user_id AND m.state = 'muted'
+      );
+$$ LANGUAGE sql STABLE;
+
+DROP TRIGGER IF EXISTS on_project_comment ON public.project_comments;
+DROP FUNCTION IF EXISTS public.handle_new_comment();
+
+CREATE TABLE IF NOT EXISTS public.outbox (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    kind TEXT NOT NULL CHECK (kind IN ('pending_fanout')),
+    payload JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'done')),
+    -- Recipients counted when the job was queued; `processed` counts notifications written.
+    total BIGINT NOT NULL DEFAULT 0,
+    processed BIGINT NOT NULL DEFAULT 0,
+    -- Last recipient handled. Batches resume strictly after it, so a retried batch never
+    -- notifies anyone twice.
+    cursor UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+-- The dispatcher always takes the oldest pending job.
+CREATE INDEX IF NOT EXISTS idx_outbox_pending ON public.outbox(id) WHERE status = 'pending';
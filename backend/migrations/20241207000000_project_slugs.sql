@@ -0,0 +1,36 @@
+-- 20. Project Slugs
+-- Readable detail-page addresses (GET /projects/slug/{slug}), e.g. `smart-greenhouse-2024`:
+-- the lower-cased title with runs of other characters turned into `-`, cut to 60 characters,
+-- then the cohort year, plus `-2`, `-3`, ... on collision. `projects.slug` is the current one.
+-- Renaming a project gives it a new slug; every slug it ever had stays in `project_slugs`, so
+-- links shared under an old title keep working and are never handed to another project.
+ALTER TABLE public.projects ADD COLUMN IF NOT EXISTS slug TEXT;
+
+WITH base AS (
+    SELECT id, created_at,
+           COALESCE(
+               NULLIF(RTRIM(LEFT(LTRIM(LOWER(REGEXP_REPLACE(title, '[^a-zA-Z0-9]+', '-', 'g')), '-'), 60), '-'), ''),
+               'project'
+           ) || '-' || year AS slug
+    FROM public.projects
+    WHERE slug IS NULL
+), numbered AS (
+    SELECT id, slug, ROW_NUMBER() OVER (PARTITION BY slug ORDER BY created_at, id) AS n FROM base
+)
+UPDATE public.projects p
+SET slug = CASE WHEN numbered.n = 1 THEN numbered.slug ELSE numbered.slug || '-' || numbered.n END
+FROM numbered
+WHERE numbered.id = p.id;
+
+ALTER TABLE public.projects ALTER COLUMN slug SET NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_slug ON public.projects(slug);
+
+CREATE TABLE IF NOT EXISTS public.project_slugs (
+    slug TEXT PRIMARY KEY,
+    project_id UUID NOT NULL REFERENCES public.projects(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO public.project_slugs (slug, project_id)
+SELECT slug, id FROM public.projects
+ON CONFLICT (slug) DO NOTHING;
@@ -0,0 +1,32 @@
+-- 21. Project Status History
+-- One row per visibility change of a project, written by a trigger so every path that flips
+-- `is_public` is recorded. Feeds the "you published ..." entries of GET /me/activity. Changes
+-- made before this migration were never recorded, so the history starts here.
+CREATE TABLE IF NOT EXISTS public.project_status_history (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    project_id UUID NOT NULL REFERENCES public.projects(id) ON DELETE CASCADE,
+    is_public BOOLEAN NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_status_history_project
+    ON public.project_status_history(project_id, changed_at DESC);
+
+CREATE OR REPLACE FUNCTION public.record_project_status()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO public.project_status_history (project_id, is_public) VALUES (NEW.id, NEW.is_public);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS on_project_status_change ON public.projects;
+CREATE TRIGGER on_project_status_change
+    AFTER UPDATE OF is_public ON public.projects
+    FOR EACH ROW
+    WHEN (OLD.is_public IS DISTINCT FROM NEW.is_public)
+    EXECUTE FUNCTION public.record_project_status();
+
+-- The activity feed reads a user's own comments newest first.
+CREATE INDEX IF NOT EXISTS idx_project_comments_user
+    ON public.project_comments(user_id, created_at DESC);
@@ -0,0 +1,42 @@
+-- 22. Project Status
+-- Replaces the single visibility flag with the review state of a project: `draft` (not yet
+-- submitted), `pending` (submitted, awaiting review), `approved` (published) or `rejected`.
+-- Existing rows map as: public -> approved, submitted -> pending, otherwise draft.
+-- `is_public` stays for one release as a column generated from `status`, so existing readers
+-- and the public-only filters keep working unchanged.
+ALTER TABLE public.projects ADD COLUMN IF NOT EXISTS status TEXT;
+
+UPDATE public.projects
+SET status = CASE
+    WHEN is_public THEN 'approved'
+    WHEN submitted_at IS NOT NULL THEN 'pending'
+    ELSE 'draft'
+END
+WHERE status IS NULL;
+
+ALTER TABLE public.projects ALTER COLUMN status SET DEFAULT 'draft';
+ALTER TABLE public.projects ALTER COLUMN status SET NOT NULL;
+ALTER TABLE public.projects ADD CONSTRAINT projects_status_check
+    CHECK (status IN ('draft', 'pending', 'approved', 'rejected'));
+
+-- The history trigger names `is_public` in its column list, so it has to go before the
+-- column does. The indexes on `is_public` are dropped with it and rebuilt below.
+DROP TRIGGER IF EXISTS on_project_status_change ON public.projects;
+
+ALTER TABLE public.projects DROP COLUMN is_public;
+ALTER TABLE public.projects
+    ADD COLUMN is_public BOOLEAN NOT NULL GENERATED ALWAYS AS (status = 'approved') STORED;
+
+CREATE INDEX IF NOT EXISTS idx_projects_public ON public.projects(is_public);
+CREATE INDEX IF NOT EXISTS idx_projects_stale_drafts
+    ON public.projects(updated_at) WHERE submitted_at IS NULL AND is_public = false;
+CREATE INDEX IF NOT EXISTS idx_projects_view_count
+    ON public.projects(view_count DESC, created_at DESC) WHERE is_public = true;
+
+-- A generated column is never a SET target, so the history trigger now watches `status`;
+-- it still only records changes that flip visibility.
+CREATE TRIGGER on_project_status_change
+    AFTER UPDATE OF status ON public.projects
+    FOR EACH ROW
+    WHEN (OLD.is_public IS DISTINCT FROM NEW.is_public)
+    EXECUTE FUNCTION public.record_project_status();
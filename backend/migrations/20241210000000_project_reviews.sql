@@ -0,0 +1,15 @@
+-- 23. Project Reviews
+-- One row per admin status decision (PUT /admin/projects/{id}/status): who made it, the status
+-- it set and the optional reason given to the owner. The newest row per project is shown to
+-- the owner on GET /me/projects, so a rejected student can see why.
+CREATE TABLE IF NOT EXISTS public.project_reviews (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    project_id UUID NOT NULL REFERENCES public.projects(id) ON DELETE CASCADE,
+    reviewer_id UUID NOT NULL REFERENCES auth.users(id) ON DELETE CASCADE,
+    status TEXT NOT NULL CHECK (status IN ('draft', 'pending', 'approved', 'rejected')),
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_reviews_project
+    ON public.project_reviews(project_id, created_at DESC, id DESC);
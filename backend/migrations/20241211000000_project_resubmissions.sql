@@ -0,0 +1,14 @@
+-- 24. Project Resubmissions
+-- Owners may put a rejected project back in the review queue (POST /projects/{id}/resubmit) a
+-- limited number of times; this counts the attempts. A resubmission is also written to
+-- `project_reviews` (status 'pending', no reason, the owner as reviewer) so the rejection
+-- reason stops being the latest review, and every admin gets a 'resubmission' notification.
+ALTER TABLE public.projects
+    ADD COLUMN IF NOT EXISTS resubmission_count INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE public.notifications
+    DROP CONSTRAINT IF EXISTS notifications_type_check;
+
+ALTER TABLE public.notifications
+    ADD CONSTRAINT notifications_type_check
+    CHECK (type IN ('like', 'comment', 'status_change', 'comment_hidden', 'draft_reminder', 'feedback', 'resubmission'));
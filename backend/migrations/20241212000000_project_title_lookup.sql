@@ -0,0 +1,6 @@
+-- 25. Duplicate Title Lookup
+-- POST /projects refuses a second project with the same title and year (compared trimmed,
+-- with runs of whitespace collapsed and case ignored) unless `force` is set. The expression
+-- must match the one in `PostgresRepository::find_project_by_title_year`.
+CREATE INDEX IF NOT EXISTS idx_projects_title_year
+    ON public.projects(year, lower(regexp_replace(btrim(title), '\s+', ' ', 'g')));
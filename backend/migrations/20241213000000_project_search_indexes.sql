@@ -0,0 +1,18 @@
+-- 26. Project Search Indexes
+-- GET /projects matches `search` with `ILIKE '%term%'` on title, abstract and author. A
+-- leading wildcard cannot use a btree, so each column gets a trigram GIN index; the three
+-- predicates are OR'ed, and the planner only combines them (BitmapOr) when every one is
+-- indexed. Terms shorter than three characters yield no trigram, so they still scan.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_projects_title_trgm
+    ON public.projects USING GIN (title gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_projects_abstract_trgm
+    ON public.projects USING GIN (abstract gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_projects_author_trgm
+    ON public.projects USING GIN (author gin_trgm_ops);
+
+-- The most common listing: one cohort year, newest first. The predicate matches the public
+-- listing's `status = 'approved'`, so it reads the index in order and stops at the page size.
+CREATE INDEX IF NOT EXISTS idx_projects_public_year_listing
+    ON public.projects(year, created_at DESC, id DESC) WHERE status = 'approved';
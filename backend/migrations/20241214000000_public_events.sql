@@ -0,0 +1,44 @@
+-- 27. Public Events
+-- Publish/unpublish feed read by GET /events/public, which the university portal polls to
+-- mirror approved projects. Written by triggers, so an event commits in the same transaction
+-- as the status change or delete that caused it. No foreign key: an unpublish event has to
+-- outlive the project it reports as deleted.
+CREATE TABLE IF NOT EXISTS public.public_events (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    kind TEXT NOT NULL CHECK (kind IN ('project.published', 'project.unpublished')),
+    project_id UUID NOT NULL,
+    -- clock_timestamp(), not NOW(): rows written by one bulk statement still get distinct
+    -- times, which the `since` watermark of the feed depends on.
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+
+CREATE INDEX IF NOT EXISTS idx_public_events_created
+    ON public.public_events(created_at, id);
+
+CREATE OR REPLACE FUNCTION public.record_public_event()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO public.public_events (kind, project_id) VALUES ('project.unpublished', OLD.id);
+        RETURN OLD;
+    END IF;
+    INSERT INTO public.public_events (kind, project_id)
+    VALUES (CASE WHEN NEW.is_public THEN 'project.published' ELSE 'project.unpublished' END, NEW.id);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Same condition as `on_project_status_change`: only changes that flip visibility.
+DROP TRIGGER IF EXISTS on_project_publication_change ON public.projects;
+CREATE TRIGGER on_project_publication_change
+    AFTER UPDATE OF status ON public.projects
+    FOR EACH ROW
+    WHEN (OLD.is_public IS DISTINCT FROM NEW.is_public)
+    EXECUTE FUNCTION public.record_public_event();
+
+DROP TRIGGER IF EXISTS on_public_project_delete ON public.projects;
+CREATE TRIGGER on_public_project_delete
+    AFTER DELETE ON public.projects
+    FOR EACH ROW
+    WHEN (OLD.is_public)
+    EXECUTE FUNCTION public.record_public_event();
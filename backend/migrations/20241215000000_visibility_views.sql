@@ -0,0 +1,19 @@
+-- 28. Visibility Views
+-- The canonical "what may the public see" rules, in one place. Public read paths select from
+-- these views instead of repeating the predicate; writes and owner/admin reads keep using the
+-- base tables. Both views are simple enough for the planner to inline, so the partial and
+-- trigram indexes on `projects` still apply.
+--
+-- `SELECT *` is expanded when a view is created: a column added to `projects` or
+-- `project_comments` later only appears here once the view is recreated.
+
+-- Approved projects: `status = 'approved'`, the same rule as the generated `is_public` column.
+CREATE OR REPLACE VIEW public.public_projects AS
+    SELECT * FROM public.projects WHERE status = 'approved';
+
+-- Comments anyone may read: on an approved project and not hidden by reports.
+CREATE OR REPLACE VIEW public.visible_comments AS
+    SELECT c.*
+    FROM public.project_comments c
+    JOIN public.projects p ON p.id = c.project_id
+    WHERE p.status = 'approved' AND NOT c.hidden_pending_review;
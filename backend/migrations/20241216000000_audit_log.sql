@@ -0,0 +1,33 @@
+-- 29. Audit Log
+-- Who did what to which record, for admin actions (GET /admin/audit). Rows are written after
+-- the action itself has succeeded; when that insert fails the entry is queued in the outbox
+-- as an 'audit_entry' job and moved here by the dispatcher, keeping the time it happened.
+-- Entries older than AUDIT_RETENTION_DAYS are purged by the maintenance runner. No foreign
+-- keys: an entry has to outlive the actor and the record it describes.
+CREATE TABLE IF NOT EXISTS public.audit_log (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    -- NULL for actions taken by the system itself (the retention purge).
+    actor_id UUID,
+    action TEXT NOT NULL,
+    target_type TEXT NOT NULL,
+    target_id TEXT,
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- "What did admin X do last Tuesday": one actor over a date range, newest first.
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor_created
+    ON public.audit_log(actor_id, created_at);
+-- The history of one record.
+CREATE INDEX IF NOT EXISTS idx_audit_log_target
+    ON public.audit_log(target_type, target_id);
+-- The unfiltered listing and the retention purge.
+CREATE INDEX IF NOT EXISTS idx_audit_log_created
+    ON public.audit_log(created_at);
+
+ALTER TABLE public.outbox
+    DROP CONSTRAINT IF EXISTS outbox_kind_check;
+
+ALTER TABLE public.outbox
+    ADD CONSTRAINT outbox_kind_check
+    CHECK (kind IN ('pending_fanout', 'audit_entry'));
@@ -0,0 +1,15 @@
+-- 30. Project Department
+-- The course a project belongs to (e.g. "CS", "EE", "Biomed"), for browsing the showcase per
+-- course. Values are checked against the DEPARTMENTS allowlist by the API, not here, so the
+-- list can change without a migration. NULL until the owner picks one.
+ALTER TABLE public.projects
+    ADD COLUMN IF NOT EXISTS department TEXT;
+
+-- The `department` filter of GET /projects and the counts of GET /departments.
+CREATE INDEX IF NOT EXISTS idx_projects_public_department
+    ON public.projects(department) WHERE status = 'approved';
+
+-- `SELECT *` was expanded when the view was created (migration 28); recreate it so the new
+-- column is visible through it. It is appended last, so the replacement is compatible.
+CREATE OR REPLACE VIEW public.public_projects AS
+    SELECT * FROM public.projects WHERE status = 'approved';
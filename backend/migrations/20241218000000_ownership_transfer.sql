@@ -0,0 +1,10 @@
+-- 31. Ownership Transfers
+-- An admin can move a project to another account (PUT /admin/projects/{id}/owner), e.g. when
+-- the wrong group member submitted it. Both the previous and the new owner are told with an
+-- 'ownership_change' notification, sent by the admin.
+ALTER TABLE public.notifications
+    DROP CONSTRAINT IF EXISTS notifications_type_check;
+
+ALTER TABLE public.notifications
+    ADD CONSTRAINT notifications_type_check
+    CHECK (type IN ('like', 'comment', 'status_change', 'comment_hidden', 'draft_reminder', 'feedback', 'resubmission', 'ownership_change'));
@@ -0,0 +1,14 @@
+-- 32. Project Technologies
+-- Languages, frameworks and tools a project uses (e.g. {rust,postgres}), for "all projects
+-- using X" searches. Stored lower-case, de-duplicated and sorted by the API. Unlike tags these
+-- are matched as a set (`@>`), so they live in an array rather than a join table.
+ALTER TABLE public.projects
+    ADD COLUMN IF NOT EXISTS technologies TEXT[] NOT NULL DEFAULT '{}';
+
+-- The `tech` containment filter of GET /projects and the counts of GET /technologies.
+CREATE INDEX IF NOT EXISTS idx_projects_public_technologies
+    ON public.projects USING GIN (technologies) WHERE status = 'approved';
+
+-- Recreate the view so the new column is visible through it (see migration 30).
+CREATE OR REPLACE VIEW public.public_projects AS
+    SELECT * FROM public.projects WHERE status = 'approved';
@@ -0,0 +1,6 @@
+-- 33. Like Notifications in the Application
+-- The owner's 'like' notification is now written by the repository, in the same transaction
+-- as the like (as comment notifications have been since the outbox, migration 19), so the
+-- `on_project_like` trigger goes. Self-likes still notify nobody.
+DROP TRIGGER IF EXISTS on_project_like ON public.project_likes;
+DROP FUNCTION IF EXISTS public.handle_new_like();
@@ -0,0 +1,12 @@
+-- 34. Comment Edits
+-- Authors may correct their own comments in place (PUT /comments/{id}) rather than deleting
+-- and reposting them at the end of the thread. NULL until the first edit; each edit moves it.
+ALTER TABLE public.project_comments
+    ADD COLUMN IF NOT EXISTS edited_at TIMESTAMPTZ;
+
+-- Recreate the view so the new column is visible through it (see migration 28).
+CREATE OR REPLACE VIEW public.visible_comments AS
+    SELECT c.*
+    FROM public.project_comments c
+    JOIN public.projects p ON p.id = c.project_id
+    WHERE p.status = 'approved' AND NOT c.hidden_pending_review;
@@ -0,0 +1,9 @@
+-- 35. Comment Mentions
+-- "@alice" in a comment sends the profile whose email starts "alice@" a 'mention'
+-- notification from the comment's author.
+ALTER TABLE public.notifications
+    DROP CONSTRAINT IF EXISTS notifications_type_check;
+
+ALTER TABLE public.notifications
+    ADD CONSTRAINT notifications_type_check
+    CHECK (type IN ('like', 'comment', 'status_change', 'comment_hidden', 'draft_reminder', 'feedback', 'resubmission', 'ownership_change', 'mention'));
@@ -0,0 +1,13 @@
+-- 36. Soft-Deleted Comments
+-- Deleting a comment stamps `deleted_at` instead of removing the row, so a thread can keep a
+-- "[deleted]" placeholder where later comments followed it. Deleted comments are no longer
+-- counted or readable through the view.
+ALTER TABLE public.project_comments
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- Recreate the view so the new column is visible through it and deleted rows are not.
+CREATE OR REPLACE VIEW public.visible_comments AS
+    SELECT c.*
+    FROM public.project_comments c
+    JOIN public.projects p ON p.id = c.project_id
+    WHERE p.status = 'approved' AND NOT c.hidden_pending_review AND c.deleted_at IS NULL;
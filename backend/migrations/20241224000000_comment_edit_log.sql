@@ -0,0 +1,13 @@
+-- 37. Comment Edit Log
+-- One row per edit of a comment, so edits count towards the author's comment rate limit like
+-- new comments do (`edited_at` only remembers the latest). Rows go with their comment.
+CREATE TABLE IF NOT EXISTS public.comment_edits (
+    id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    comment_id BIGINT NOT NULL REFERENCES public.project_comments(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES public.profiles(id) ON DELETE CASCADE,
+    edited_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- The rate limit reads a user's edits of the last day.
+CREATE INDEX IF NOT EXISTS idx_comment_edits_user
+    ON public.comment_edits(user_id, edited_at DESC);
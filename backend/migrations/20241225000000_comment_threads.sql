@@ -0,0 +1,58 @@
+-- 38. Comment Threads
+-- The comment thread a viewer may read, shared by the listing and both paging directions.
+-- The public part is `visible_comments` (migration 28). Beyond it, as in
+-- `get_project_authorized`, moderators read every comment, and a signed-in viewer also reads
+-- the thread of their own project and their own comments held for review.
+--
+-- A deleted comment stays as a placeholder (author, text and edit stamp blanked, the text
+-- replaced by `p_deleted_text`) while a comment the viewer can read follows it. Such a later
+-- comment already implies the viewer may read the project, so only the held-for-review rule is
+-- checked on the placeholder itself.
+CREATE OR REPLACE FUNCTION public.comment_thread(
+    p_project_id UUID,
+    p_moderator BOOLEAN,
+    p_viewer UUID,
+    p_deleted_text TEXT
+)
+RETURNS TABLE (
+    id BIGINT,
+    user_id UUID,
+    project_id UUID,
+    comment TEXT,
+    created_at TIMESTAMPTZ,
+    author_email TEXT,
+    hidden_pending_review BOOLEAN,
+    edited_at TIMESTAMPTZ,
+    edited BOOLEAN,
+    is_deleted BOOLEAN
+) AS $$
+    WITH readable AS (
+        SELECT v.id FROM public.visible_comments v WHERE v.project_id = p_project_id
+        UNION
+        SELECT c.id
+        FROM public.project_comments c
+        JOIN public.projects pr ON pr.id = c.project_id
+        WHERE c.project_id = p_project_id AND c.deleted_at IS NULL
+          AND (p_moderator
+               OR (pr.user_id = p_viewer AND (NOT c.hidden_pending_review OR c.user_id = p_viewer))
+               OR (pr.status = 'approved' AND c.user_id = p_viewer))
+    )
+    SELECT
+        c.id,
+        CASE WHEN c.deleted_at IS NULL THEN c.user_id END,
+        c.project_id,
+        CASE WHEN c.deleted_at IS NULL THEN c.comment ELSE p_deleted_text END,
+        c.created_at,
+        CASE WHEN c.deleted_at IS NULL THEN p.email END,
+        c.hidden_pending_review AND c.deleted_at IS NULL,
+        CASE WHEN c.deleted_at IS NULL THEN c.edited_at END,
+        c.edited_at IS NOT NULL AND c.deleted_at IS NULL,
+        c.deleted_at IS NOT NULL
+    FROM public.project_comments c
+    JOIN public.profiles p ON p.id = c.user_id
+    WHERE c.project_id = p_project_id
+      AND (c.id IN (SELECT r.id FROM readable r)
+           OR (c.deleted_at IS NOT NULL
+               AND (NOT c.hidden_pending_review OR p_moderator OR c.user_id = p_viewer)
+               AND EXISTS (SELECT 1 FROM readable later WHERE later.id > c.id)));
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,17 @@
+use crate::{models::NewAuditEntry, repository::Repository};
+
+/// record
+///
+/// Writes `entry` to the audit log once the action it describes has succeeded. It never fails
+/// the action: when the insert fails the entry is queued in the outbox, where the dispatcher
+/// picks it up (`Repository::flush_audit_outbox`), and when that fails as well the entry is
+/// only logged.
+pub async fn record(repo: &dyn Repository, entry: NewAuditEntry) {
+    let Err(e) = repo.record_audit(&entry).await else {
+        return;
+    };
+    tracing::warn!(error = %e, action = %entry.action, "Audit insert failed; queueing the entry in the outbox");
+    if let Err(e) = repo.queue_audit(&entry).await {
+        tracing::error!(error = %e, ?entry, "Audit entry lost: the outbox insert failed too");
+    }
+}
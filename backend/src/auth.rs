@@ -1,16 +1,24 @@
 use axum::{
-    extract::{FromRef, FromRequestParts},
+    extract::{FromRef, FromRequestParts, OptionalFromRequestParts},
     http::{StatusCode, header, request::Parts},
 };
-use jsonwebtoken::{DecodingKey, Validation, decode, errors::ErrorKind};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, errors::ErrorKind};
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use uuid::Uuid;
 
 use crate::{
     config::{AppConfig, Env},
-    repository::RepositoryState,
+    repository::{
+        RepositoryState,
+        priority::{self, RequestClass},
+    },
 };
 
+/// Upper bound on the accepted Bearer token length (in bytes). Supabase access tokens are
+/// well under 2 KB; anything larger is rejected without attempting to decode it.
+const MAX_TOKEN_LEN: usize = 8 * 1024;
+
 /// Claims
 ///
 /// Represents the standard payload structure expected inside a JSON Web Token (JWT).
@@ -74,21 +82,23 @@ where
         // If the application is running in Env::Local, we allow authentication by
         // providing a known, valid UUID in the 'x-user-id' header.
         // This accelerates development but is guarded by the Env check.
-        if config.env == Env::Local {
-            if let Some(user_id_header) = parts.headers.get("x-user-id") {
-                if let Ok(id_str) = user_id_header.to_str() {
-                    // Attempt to parse the header value as a UUID.
-                    if let Ok(user_id) = Uuid::parse_str(id_str) {
-                        // Crucially, we verify that this UUID maps to an actual user/profile
-                        // in the local development database to ensure roles are correctly loaded.
-                        if let Some(user) = repo.get_user(user_id).await {
-                            return Ok(AuthUser {
-                                id: user.id,
-                                role: user.role,
-                            });
-                        }
-                    }
-                }
+        // Only a well-formed UUID ever reaches the repository; anything else (non-UTF-8 bytes,
+        // SQL-ish strings) is discarded here and falls through to the JWT flow.
+        if config.env == Env::Local
+            && let Some(user_id) = parts
+                .headers
+                .get("x-user-id")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|id_str| Uuid::parse_str(id_str).ok())
+        {
+            // Crucially, we verify that this UUID maps to an actual user/profile
+            // in the local development database to ensure roles are correctly loaded.
+            if let Some(user) = priority::with_class(RequestClass::Lookup, repo.get_user(user_id)).await {
+                priority::note_role(&user.role);
+                return Ok(AuthUser {
+                    id: user.id,
+                    role: user.role,
+                });
             }
         }
         // If Env is Production, or if the bypass failed (e.g., header was bad or user not found),
@@ -106,11 +116,19 @@ where
             .strip_prefix("Bearer ")
             .ok_or(StatusCode::UNAUTHORIZED)?;
 
+        // Cheap structural checks before any cryptographic work: a JWT is exactly three
+        // dot-separated segments and never legitimately approaches the header size limit.
+        if token.len() > MAX_TOKEN_LEN || token.split('.').count() != 3 {
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+
         // 4. JWT Decoding Setup
         let secret = &config.jwt_secret;
         let decoding_key = DecodingKey::from_secret(secret.as_bytes());
 
-        let mut validation = Validation::default();
+        // The algorithm is pinned explicitly: tokens declaring `alg=none` or any other
+        // HMAC variant (e.g. HS384) are rejected before the signature is even considered.
+        let mut validation = Validation::new(Algorithm::HS256);
 
         // Ensure expiration time validation is always active.
         validation.validate_exp = true;
@@ -134,13 +152,18 @@ where
         // 6. Database Lookup (Final Verification)
         // Check the database for the user's existence and retrieve their current role.
         // This prevents access if the user was deleted after the token was issued.
-        let user = repo
-            .get_user(user_id)
+        // This is the only repository call on the JWT path and it is only reached with a
+        // token whose signature, algorithm and expiry have all been verified above.
+        // The role is not known before it, and any signed-up user holds a valid token, so it
+        // runs as a `Lookup` (`repository::priority`): it may borrow a database permit reserved
+        // for admins, but only within a budget of its own that public traffic cannot exhaust.
+        let user = priority::with_class(RequestClass::Lookup, repo.get_user(user_id))
             .await
             // If the user is not found, the token is technically valid but the user is not active.
             .ok_or(StatusCode::UNAUTHORIZED)?;
 
-        // Success: Return the resolved identity.
+        // Success: Upgrade the request's database priority by role and return the identity.
+        priority::note_role(&user.role);
         Ok(AuthUser {
             id: user.id,
             role: user.role,
@@ -148,3 +171,26 @@ where
     }
 }
 
+
+/// Optional AuthUser
+///
+/// Lets public handlers take `Option<AuthUser>` to personalise a response when the caller is
+/// signed in. Missing *or invalid* credentials yield `None` rather than a 401, so an expired
+/// token never breaks a public page.
+impl<S> OptionalFromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+    RepositoryState: FromRef<S>,
+    AppConfig: FromRef<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        Ok(<AuthUser as FromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .ok())
+    }
+}
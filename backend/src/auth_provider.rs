@@ -0,0 +1,180 @@
+use axum::http::StatusCode;
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::{build_info, config::AppConfig, error::ApiError, metrics};
+
+/// Idle connections kept open per auth provider host.
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+/// How long an idle pooled connection is kept before being closed.
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Budget for establishing the TCP/TLS connection, within the total timeout.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// http_client
+///
+/// Builds the shared outbound `reqwest::Client` held in `AppState`. Every request is capped
+/// at `auth_provider_timeout` end to end, idle connections are pooled and reused, and the
+/// `User-Agent` identifies this build (`fyp-portal/<version> (<commit>)`).
+pub fn http_client(config: &AppConfig) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(config.auth_provider_timeout)
+        .connect_timeout(CONNECT_TIMEOUT.min(config.auth_provider_timeout))
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .user_agent(build_info::server_header())
+        .build()
+        .expect("FATAL: Failed to build the outbound HTTP client.")
+}
+
+/// Outcome
+///
+/// How an auth provider call ended; the `outcome` label of `auth_provider_requests_total`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    /// The provider answered with a non-2xx status (e.g. email already registered).
+    Rejected,
+    Timeout,
+    /// Connection failure or an unreadable response.
+    Error,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Success => "success",
+            Outcome::Rejected => "rejected",
+            Outcome::Timeout => "timeout",
+            Outcome::Error => "error",
+        }
+    }
+}
+
+/// AuthProviderError
+///
+/// Why a call to the external auth provider failed. Converts into the `ApiError` returned to
+/// the client: timeouts are 504 so a slow provider is distinguishable from our own faults.
+#[derive(Debug)]
+pub enum AuthProviderError {
+    /// `SUPABASE_URL` / `SUPABASE_KEY` are not set.
+    NotConfigured,
+    Rejected(StatusCode),
+    Timeout,
+    Unavailable(String),
+}
+
+impl From<AuthProviderError> for ApiError {
+    fn from(err: AuthProviderError) -> Self {
+        match err {
+            AuthProviderError::NotConfigured => ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "auth_provider_unconfigured",
+                "Registration is not available.",
+            ),
+            AuthProviderError::Rejected(_) => ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "registration_rejected",
+                "The auth provider rejected the registration.",
+            ),
+            AuthProviderError::Timeout => ApiError::new(
+                StatusCode::GATEWAY_TIMEOUT,
+                "auth_provider_timeout",
+                "The auth provider did not respond in time.",
+            ),
+            AuthProviderError::Unavailable(_) => ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "auth_provider_error",
+                "The auth provider could not be reached.",
+            ),
+        }
+    }
+}
+
+/// Minimal view of the `/auth/v1/signup` response: the new `auth.users.id`.
+#[derive(Deserialize)]
+struct SignupResponse {
+    id: Uuid,
+}
+
+/// signup
+///
+/// Creates the account with Supabase Auth and returns its user id, which becomes the
+/// primary key of the local profile.
+pub async fn signup(
+    client: &reqwest::Client,
+    config: &AppConfig,
+    email: &str,
+    password: &str,
+) -> Result<Uuid, AuthProviderError> {
+    let (Some(base_url), Some(key)) = (&config.supabase_url, &config.supabase_key) else {
+        return Err(AuthProviderError::NotConfigured);
+    };
+    let request = client
+        .post(format!("{}/auth/v1/signup", base_url))
+        .header("apikey", key)
+        .json(&serde_json::json!({ "email": email, "password": password }));
+
+    instrumented("signup", async {
+        let response = request.send().await.map_err(classify)?;
+        if !response.status().is_success() {
+            return Err(AuthProviderError::Rejected(response.status()));
+        }
+        let user = response.json::<SignupResponse>().await.map_err(classify)?;
+        Ok(user.id)
+    })
+    .await
+}
+
+/// Distinguishes timeouts (total or connect) from other transport and decoding failures.
+fn classify(err: reqwest::Error) -> AuthProviderError {
+    if err.is_timeout() {
+        AuthProviderError::Timeout
+    } else {
+        AuthProviderError::Unavailable(err.without_url().to_string())
+    }
+}
+
+/// instrumented
+///
+/// Runs one auth provider call inside an `auth_provider_request` span that records the
+/// operation, outcome, upstream status and latency, and counts it in
+/// `auth_provider_requests_total{outcome}`.
+async fn instrumented<T>(
+    operation: &'static str,
+    call: impl Future<Output = Result<T, AuthProviderError>>,
+) -> Result<T, AuthProviderError> {
+    let span = tracing::info_span!(
+        "auth_provider_request",
+        operation,
+        outcome = tracing::field::Empty,
+        status = tracing::field::Empty,
+        latency_ms = tracing::field::Empty,
+    );
+    let started = Instant::now();
+    let result = call.instrument(span.clone()).await;
+
+    let outcome = match &result {
+        Ok(_) => Outcome::Success,
+        Err(AuthProviderError::Rejected(status)) => {
+            span.record("status", status.as_u16());
+            Outcome::Rejected
+        }
+        Err(AuthProviderError::Timeout) => Outcome::Timeout,
+        Err(_) => Outcome::Error,
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+    span.record("outcome", outcome.as_str());
+    span.record("latency_ms", latency_ms);
+    metrics::AUTH_PROVIDER_REQUESTS.inc(outcome.as_str());
+
+    span.in_scope(|| match &result {
+        Err(err @ (AuthProviderError::Timeout | AuthProviderError::Unavailable(_))) => {
+            tracing::error!(latency_ms, "Auth provider {} failed: {:?}", operation, err);
+        }
+        _ => tracing::info!(latency_ms, "Auth provider {} {}", operation, outcome.as_str()),
+    });
+    result
+}
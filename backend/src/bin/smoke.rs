@@ -0,0 +1,65 @@
+use fyp_portal::smoke::{self, SmokeOptions, WRITABLE_ENV};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: smoke --base-url <url> [--env <name>] [--allow-writes]
+
+Runs the post-deploy smoke test against a live environment and exits non-zero on the
+first failing step.
+
+  --base-url <url>  API root, e.g. https://api.example.com
+  --env <name>      name of the target environment (default: production)
+  --allow-writes    also register a throwaway user, create and delete a draft and request a
+                    presigned upload; only honoured with --env staging
+
+SMOKE_JWT_SECRET signs the throwaway user's token; without it the local x-user-id header
+is used, which only a local deployment accepts.";
+
+/// parse_args
+///
+/// Reads the command line into `SmokeOptions`; Err carries the message to print with the usage.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<SmokeOptions, String> {
+    let mut base_url = None;
+    let mut env = "production".to_string();
+    let mut allow_writes = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--base-url" => base_url = Some(args.next().ok_or("--base-url needs a value")?),
+            "--env" => env = args.next().ok_or("--env needs a value")?,
+            "--allow-writes" => allow_writes = true,
+            "-h" | "--help" => return Err(String::new()),
+            other => return Err(format!("unknown argument: {other}")),
+        }
+    }
+    Ok(SmokeOptions {
+        base_url: base_url.ok_or("--base-url is required")?,
+        env,
+        allow_writes,
+        jwt_secret: std::env::var("SMOKE_JWT_SECRET").ok().filter(|v| !v.is_empty()),
+    })
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            if !message.is_empty() {
+                eprintln!("{message}");
+            }
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    if options.allow_writes && !options.writes_enabled() {
+        eprintln!("--allow-writes ignored: only --env {WRITABLE_ENV} may be written to");
+    }
+
+    let report = smoke::run(&options).await;
+    println!("{report}");
+    if report.passed() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
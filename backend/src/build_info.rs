@@ -0,0 +1,56 @@
+use crate::{config::AppConfig, models::VersionInfo};
+
+/// Placeholder reported when a piece of build metadata was not available at compile time.
+pub const UNKNOWN: &str = "unknown";
+
+/// Crate version from Cargo.toml.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Short git commit hash, set by build.rs (or the `GIT_SHA` build environment variable).
+pub const COMMIT: &str = or_unknown(option_env!("GIT_SHA"));
+
+/// RFC 3339 timestamp set by build.rs: the `BUILD_TIMESTAMP` build environment variable, or the
+/// commit time of the checked-out `HEAD`.
+pub const BUILT_AT: &str = or_unknown(option_env!("BUILD_TIMESTAMP"));
+
+/// or_unknown
+///
+/// Resolves optional compile-time metadata, so builds without git (e.g. from a source
+/// tarball) still compile and simply report "unknown".
+pub const fn or_unknown(value: Option<&'static str>) -> &'static str {
+    match value {
+        Some(value) => value,
+        None => UNKNOWN,
+    }
+}
+
+/// server_header
+///
+/// Value of the `Server` response header, e.g. `fyp-portal/0.1.0 (3f2a9c1d0b7e)`.
+pub fn server_header() -> String {
+    format!("{}/{} ({})", env!("CARGO_PKG_NAME"), VERSION, COMMIT)
+}
+
+/// response_server_header
+///
+/// The `Server` header sent on responses: `server_header`, or just the crate name when
+/// `version_admin_only` hides the build from non-admins.
+pub fn response_server_header(config: &AppConfig) -> String {
+    if config.version_admin_only {
+        env!("CARGO_PKG_NAME").to_string()
+    } else {
+        server_header()
+    }
+}
+
+/// version_info
+///
+/// The payload returned by `GET /version`.
+pub fn version_info(config: &AppConfig) -> VersionInfo {
+    VersionInfo {
+        version: VERSION.to_string(),
+        commit: COMMIT.to_string(),
+        built_at: BUILT_AT.to_string(),
+        env: config.env.as_str().to_string(),
+    }
+}
@@ -0,0 +1,62 @@
+use axum::http::StatusCode;
+use serde::Deserialize;
+
+use crate::{config::AppConfig, error::ApiError};
+
+/// Minimal view of a siteverify response. Turnstile, hCaptcha and reCAPTCHA all report the
+/// verdict as `success`.
+#[derive(Deserialize)]
+struct VerifyResponse {
+    success: bool,
+}
+
+/// verify
+///
+/// Checks a CAPTCHA token with the provider's siteverify endpoint. A no-op unless
+/// `captcha_secret` is configured, so local setups and tests need no provider. A missing or
+/// rejected token is a 400 `captcha_failed`; an unreachable provider is a 503
+/// `captcha_unavailable` rather than letting the request through.
+pub async fn verify(
+    client: &reqwest::Client,
+    config: &AppConfig,
+    token: Option<&str>,
+) -> Result<(), ApiError> {
+    let Some(secret) = &config.captcha_secret else {
+        return Ok(());
+    };
+    let failed = || {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "captcha_failed",
+            "CAPTCHA verification failed. Please try again.",
+        )
+    };
+    let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) else {
+        return Err(failed());
+    };
+
+    let verdict = async {
+        client
+            .post(&config.captcha_verify_url)
+            .form(&[("secret", secret.as_str()), ("response", token)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<VerifyResponse>()
+            .await
+    }
+    .await;
+
+    match verdict {
+        Ok(VerifyResponse { success: true }) => Ok(()),
+        Ok(VerifyResponse { success: false }) => Err(failed()),
+        Err(e) => {
+            tracing::warn!(error = %e.without_url(), "CAPTCHA provider unreachable");
+            Err(ApiError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "captcha_unavailable",
+                "CAPTCHA verification is temporarily unavailable.",
+            ))
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use axum::{
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::{Map, Value};
+
+use crate::error::ApiError;
+
+/// Keys whose values are free-form, user-controlled JSON (JSONB columns, error details).
+/// The key itself is still re-cased, but nothing nested beneath it is touched.
+const PRESERVED_KEYS: [&str; 4] = ["links", "crop", "metadata", "details"];
+
+/// Paths that must never be rewritten (the OpenAPI document has its own casing rules).
+const EXEMPT_PREFIXES: [&str; 2] = ["/swagger-ui", "/api-docs"];
+
+/// Upper bound for buffering a response body during re-casing.
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// wants_camel_case
+///
+/// A client opts into camelCase either with the `X-Api-Case: camel` header or the
+/// `?case=camel` query parameter. Anything else keeps the default snake_case output.
+fn wants_camel_case(request: &Request) -> bool {
+    let header_match = request
+        .headers()
+        .get("x-api-case")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("camel"));
+
+    let query_match = request.uri().query().is_some_and(|query| {
+        query
+            .split('&')
+            .any(|pair| pair.eq_ignore_ascii_case("case=camel"))
+    });
+
+    header_match || query_match
+}
+
+/// snake_to_camel
+///
+/// Converts a single `snake_case` key to `camelCase` (`abstract_text` -> `abstractText`).
+/// Keys without underscores are returned unchanged.
+pub fn snake_to_camel(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    let mut upper_next = false;
+    for (i, ch) in key.chars().enumerate() {
+        if ch == '_' && i > 0 {
+            upper_next = true;
+        } else if upper_next {
+            out.extend(ch.to_uppercase());
+            upper_next = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// to_camel_case_keys
+///
+/// Recursively re-cases every object key in a JSON document, leaving the contents of
+/// `PRESERVED_KEYS` verbatim so user data stored in free-form fields is never mangled.
+pub fn to_camel_case_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = if PRESERVED_KEYS.contains(&key.as_str()) {
+                        value
+                    } else {
+                        to_camel_case_keys(value)
+                    };
+                    (snake_to_camel(&key), value)
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(to_camel_case_keys).collect()),
+        other => other,
+    }
+}
+
+/// json_casing
+///
+/// Middleware implementing the optional camelCase response mode. Handlers always serialize
+/// snake_case (matching the models); when the client asks for camelCase, the already
+/// serialized JSON body is parsed, re-cased and re-emitted with a corrected Content-Length.
+/// Non-JSON responses (CSV, HTML, images, NDJSON streams) pass through untouched. A body that
+/// cannot be buffered (over `MAX_BODY_BYTES`, or failing mid-stream) becomes a 500 rather than
+/// a success with an empty body.
+pub async fn json_casing(request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    if EXEMPT_PREFIXES.iter().any(|prefix| path.starts_with(prefix)) || !wants_camel_case(&request)
+    {
+        return next.run(request).await;
+    }
+
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!("json_casing: failed to buffer response body: {:?}", e);
+            return ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "casing_failed",
+                "The response could not be converted to camelCase; retry without `case=camel`.",
+            )
+            .into_response();
+        }
+    };
+
+    let rewritten = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(value) => serde_json::to_vec(&to_camel_case_keys(value)).unwrap_or_else(|_| bytes.to_vec()),
+        // Not actually JSON despite the header; return the original bytes unchanged.
+        Err(_) => bytes.to_vec(),
+    };
+
+    parts
+        .headers
+        .insert(header::CONTENT_LENGTH, HeaderValue::from(rewritten.len()));
+    Response::from_parts(parts, Body::from(rewritten))
+}
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+
+/// Clock
+///
+/// The source of "now" for time-based background work, so tests can move time forward
+/// instead of waiting for it.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// SystemClock
+///
+/// The wall clock, used in production.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// MockClock
+///
+/// A clock that only moves when told to. Starts at the real current time, so rows written
+/// by a repository (which stamps them with its own clock) line up with it.
+#[cfg(feature = "test-util")]
+pub struct MockClock {
+    now: std::sync::Mutex<DateTime<Utc>>,
+}
+
+#[cfg(feature = "test-util")]
+impl MockClock {
+    pub fn new() -> Self {
+        Self {
+            now: std::sync::Mutex::new(Utc::now()),
+        }
+    }
+
+    pub fn advance(&self, by: chrono::Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
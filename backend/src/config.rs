@@ -1,4 +1,7 @@
-use std::env;
+use crate::redact::{self, REDACTED};
+use crate::security_headers::SecurityHeaders;
+use chrono::Datelike;
+use std::{env, fmt, time::Duration};
 
 /// AppConfig
 ///
@@ -6,6 +9,8 @@ use std::env;
 /// immutable once loaded, ensuring consistency across all threads and services (e.g., Repository, Storage).
 /// It is pulled into the application state via FromRef, embodying the "immutable AppConfig"
 /// part of the Unified State Pattern.
+///
+/// `Debug` is implemented by hand so that dumping the config never prints credentials.
 #[derive(Clone)]
 pub struct AppConfig {
     // Database connection string (Postgres).
@@ -24,8 +29,89 @@ pub struct AppConfig {
     pub env: Env,
     // Secret key used to decode and validate incoming JWTs (Supabase-managed).
     pub jwt_secret: String,
+    // Per-client request budget for the global rate limiter (token bucket, refilled per minute).
+    pub rate_limit_per_minute: u32,
+    // Reverse proxies in front of the API that append to X-Forwarded-For. Rate limiting keys on
+    // the hop the outermost of them added; zero keys on the socket peer and ignores the header.
+    pub trusted_proxy_hops: usize,
+    // Size of the Postgres connection pool.
+    pub db_max_connections: u32,
+    // Pool connections only admin and moderator requests may use, so moderation stays
+    // responsive while public traffic saturates the rest. Always below `db_max_connections`.
+    pub db_admin_reserved: u32,
+    // Public origin of the frontend, used to build canonical links (e.g. Open Graph tags).
+    pub public_site_url: String,
+    // Public origin of this API as seen by clients, used for absolute links (e.g. pagination).
+    pub public_api_url: String,
+    // Words masked out of generated excerpts shown to third parties (link previews).
+    pub excerpt_blocked_words: Vec<String>,
+    // Departments a project may belong to, in their canonical spelling (e.g. "CS", "Biomed").
+    pub departments: Vec<String>,
+    // The cohort year currently being submitted; projects for other years fail the readiness check.
+    pub current_cohort_year: i32,
+    // Comment threads longer than this are returned newest-first unless `order` is given.
+    pub comments_newest_first_after: i64,
+    // Presigned upload URLs a non-admin may request per rolling hour.
+    pub max_presigns_per_hour: i64,
+    // Unconfirmed, unexpired uploads a non-admin may hold per purpose.
+    pub max_pending_uploads: i64,
+    // Restricts GET /version to admins and drops the version from `Server` (public by default).
+    pub version_admin_only: bool,
+    // Collapses duplicate slashes and strips trailing slashes before routing (on by default).
+    pub normalize_paths: bool,
+    // Supabase project URL used by the register proxy (unset disables registration).
+    pub supabase_url: Option<String>,
+    // Supabase anon/service key sent as `apikey` on auth provider calls.
+    pub supabase_key: Option<String>,
+    // Total time budget for a single auth provider call, including connect and body.
+    pub auth_provider_timeout: Duration,
+    // Distinct pending reports after which a comment is hidden pending moderator review.
+    pub auto_hide_report_threshold: i64,
+    // Days without edits after which a draft's owner gets a one-off reminder.
+    pub draft_reminder_days: i64,
+    // Days without edits after which a draft is flagged for the orphaned-upload cleanup.
+    pub draft_cleanup_days: i64,
+    // Days an audit log entry is kept before the maintenance runner purges it.
+    pub audit_retention_days: i64,
+    // Times an owner may put a rejected project back in the review queue.
+    pub max_resubmissions: i32,
+    // Projects a student may create per cohort year (admins are exempt).
+    pub max_projects_per_user_per_year: i64,
+    // Comments a non-admin may post per rolling minute and per rolling day, across all projects.
+    pub max_comments_per_minute: i64,
+    pub max_comments_per_day: i64,
+    // How often the background maintenance runner makes a pass.
+    pub maintenance_interval: Duration,
+    // How often the `settings` table is reloaded (changes made on this instance apply at once).
+    pub settings_refresh_interval: Duration,
+    // Anonymous feedback submissions allowed per client address per hour.
+    pub feedback_per_hour: u32,
+    // CAPTCHA provider secret; when unset, anonymous forms are not CAPTCHA-gated.
+    pub captcha_secret: Option<String>,
+    // The provider's token verification endpoint (Cloudflare Turnstile by default).
+    pub captcha_verify_url: String,
+    // Comment notifications above this many recipients are queued for the outbox dispatcher
+    // instead of being written in the request.
+    pub fanout_sync_threshold: i64,
+    // How often the outbox dispatcher looks for queued fan-out jobs.
+    pub outbox_dispatch_interval: Duration,
+    // Security headers added to every response (CSP, framing, HSTS in Production, ...).
+    pub security_headers: SecurityHeaders,
 }
 
+/// Default total timeout for calls to the external auth provider (Supabase).
+pub const DEFAULT_AUTH_PROVIDER_TIMEOUT: Duration = Duration::from_secs(10);
+/// Default pause between maintenance runner passes.
+pub const DEFAULT_MAINTENANCE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Default pause between reloads of the runtime settings.
+pub const DEFAULT_SETTINGS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// Default pause between outbox dispatcher passes.
+pub const DEFAULT_OUTBOX_DISPATCH_INTERVAL: Duration = Duration::from_secs(5);
+/// Default CAPTCHA verification endpoint (Cloudflare Turnstile).
+pub const DEFAULT_CAPTCHA_VERIFY_URL: &str = "https://challenges.cloudflare.com/turnstile/v0/siteverify";
+/// Departments accepted when DEPARTMENTS is unset.
+pub const DEFAULT_DEPARTMENTS: &[&str] = &["CS", "EE", "Biomed"];
+
 /// Env
 ///
 /// Defines the runtime context, used to switch between development utilities (MinIO, Bypass)
@@ -36,6 +122,118 @@ pub enum Env {
     Production,
 }
 
+impl Env {
+    /// The `APP_ENV` spelling of the environment, as reported by `/version`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Env::Local => "local",
+            Env::Production => "production",
+        }
+    }
+}
+
+impl fmt::Debug for AppConfig {
+    /// Secrets print as `[REDACTED]` and the database password is masked. The destructuring
+    /// makes a newly added field a compile error here, so it has to be classified explicitly.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            db_url,
+            s3_endpoint,
+            s3_region,
+            s3_key: _,
+            s3_secret: _,
+            s3_bucket,
+            env,
+            jwt_secret: _,
+            rate_limit_per_minute,
+            trusted_proxy_hops,
+            db_max_connections,
+            db_admin_reserved,
+            public_site_url,
+            public_api_url,
+            excerpt_blocked_words,
+            departments,
+            current_cohort_year,
+            comments_newest_first_after,
+            max_presigns_per_hour,
+            max_pending_uploads,
+            version_admin_only,
+            normalize_paths,
+            supabase_url,
+            supabase_key,
+            auth_provider_timeout,
+            auto_hide_report_threshold,
+            draft_reminder_days,
+            draft_cleanup_days,
+            audit_retention_days,
+            max_resubmissions,
+            max_projects_per_user_per_year,
+            max_comments_per_minute,
+            max_comments_per_day,
+            maintenance_interval,
+            settings_refresh_interval,
+            feedback_per_hour,
+            captcha_secret,
+            captcha_verify_url,
+            fanout_sync_threshold,
+            outbox_dispatch_interval,
+            security_headers,
+        } = self;
+        f.debug_struct("AppConfig")
+            .field("db_url", &redact::redact_dsn(db_url))
+            .field("s3_endpoint", s3_endpoint)
+            .field("s3_region", s3_region)
+            .field("s3_key", &format_args!("{REDACTED}"))
+            .field("s3_secret", &format_args!("{REDACTED}"))
+            .field("s3_bucket", s3_bucket)
+            .field("env", env)
+            .field("jwt_secret", &format_args!("{REDACTED}"))
+            .field("rate_limit_per_minute", rate_limit_per_minute)
+            .field("trusted_proxy_hops", trusted_proxy_hops)
+            .field("db_max_connections", db_max_connections)
+            .field("db_admin_reserved", db_admin_reserved)
+            .field("public_site_url", public_site_url)
+            .field("public_api_url", public_api_url)
+            .field("excerpt_blocked_words", excerpt_blocked_words)
+            .field("departments", departments)
+            .field("current_cohort_year", current_cohort_year)
+            .field("comments_newest_first_after", comments_newest_first_after)
+            .field("max_presigns_per_hour", max_presigns_per_hour)
+            .field("max_pending_uploads", max_pending_uploads)
+            .field("version_admin_only", version_admin_only)
+            .field("normalize_paths", normalize_paths)
+            .field("supabase_url", supabase_url)
+            .field("supabase_key", &supabase_key.as_ref().map(|_| Redacted))
+            .field("auth_provider_timeout", auth_provider_timeout)
+            .field("auto_hide_report_threshold", auto_hide_report_threshold)
+            .field("draft_reminder_days", draft_reminder_days)
+            .field("draft_cleanup_days", draft_cleanup_days)
+            .field("audit_retention_days", audit_retention_days)
+            .field("max_resubmissions", max_resubmissions)
+            .field("max_projects_per_user_per_year", max_projects_per_user_per_year)
+            .field("max_comments_per_minute", max_comments_per_minute)
+            .field("max_comments_per_day", max_comments_per_day)
+            .field("maintenance_interval", maintenance_interval)
+            .field("settings_refresh_interval", settings_refresh_interval)
+            .field("feedback_per_hour", feedback_per_hour)
+            .field("captcha_secret", &captcha_secret.as_ref().map(|_| Redacted))
+            .field("captcha_verify_url", captcha_verify_url)
+            .field("fanout_sync_threshold", fanout_sync_threshold)
+            .field("outbox_dispatch_interval", outbox_dispatch_interval)
+            .field("security_headers", security_headers)
+            .finish()
+    }
+}
+
+/// Debug stand-in for a secret that is set: prints `[REDACTED]` without quotes.
+struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
 impl Default for AppConfig {
     /// default
     ///
@@ -54,6 +252,39 @@ impl Default for AppConfig {
             s3_bucket: "fyp-test".to_string(),
             env: Env::Local,
             jwt_secret: "super-secure-test-secret-value-local".to_string(),
+            rate_limit_per_minute: 120,
+            trusted_proxy_hops: 0,
+            db_max_connections: 5,
+            db_admin_reserved: 1,
+            public_site_url: "http://localhost:3000".to_string(),
+            public_api_url: "http://localhost:3000".to_string(),
+            excerpt_blocked_words: vec![],
+            departments: DEFAULT_DEPARTMENTS.iter().map(|d| d.to_string()).collect(),
+            current_cohort_year: chrono::Utc::now().year(),
+            comments_newest_first_after: 50,
+            max_presigns_per_hour: 60,
+            max_pending_uploads: 5,
+            version_admin_only: false,
+            normalize_paths: true,
+            supabase_url: None,
+            supabase_key: None,
+            auth_provider_timeout: DEFAULT_AUTH_PROVIDER_TIMEOUT,
+            auto_hide_report_threshold: 3,
+            draft_reminder_days: 7,
+            draft_cleanup_days: 90,
+            audit_retention_days: 365,
+            max_resubmissions: 3,
+            max_projects_per_user_per_year: 3,
+            max_comments_per_minute: 5,
+            max_comments_per_day: 50,
+            maintenance_interval: DEFAULT_MAINTENANCE_INTERVAL,
+            settings_refresh_interval: DEFAULT_SETTINGS_REFRESH_INTERVAL,
+            feedback_per_hour: 5,
+            captcha_secret: None,
+            captcha_verify_url: DEFAULT_CAPTCHA_VERIFY_URL.to_string(),
+            fanout_sync_threshold: 500,
+            outbox_dispatch_interval: DEFAULT_OUTBOX_DISPATCH_INTERVAL,
+            security_headers: SecurityHeaders::default(),
         }
     }
 }
@@ -85,6 +316,193 @@ impl AppConfig {
                 .unwrap_or_else(|_| "super-secure-test-secret-value-local".to_string()),
         };
 
+        // Rate Limiting: Shared by both environments, overridable for load tests or busy events.
+        let rate_limit_per_minute = env::var("RATE_LIMIT_PER_MINUTE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(120);
+        let trusted_proxy_hops = env::var("TRUSTED_PROXY_HOPS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+
+        // Database Pool: Its size, and the connections held back for admin/moderator requests
+        // (at least one is always left to everyone else).
+        let db_max_connections = env::var("DB_MAX_CONNECTIONS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(5);
+        let db_admin_reserved = env::var("DB_ADMIN_RESERVED")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1)
+            .min(db_max_connections - 1);
+
+        // Link Previews: Canonical site origin and the excerpt word filter (comma-separated).
+        let public_site_url = env::var("PUBLIC_SITE_URL")
+            .unwrap_or_else(|_| "http://localhost:3000".to_string())
+            .trim_end_matches('/')
+            .to_string();
+        let excerpt_blocked_words = env::var("EXCERPT_BLOCKED_WORDS")
+            .map(|v| {
+                v.split(',')
+                    .map(|w| w.trim().to_lowercase())
+                    .filter(|w| !w.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Departments: the allowlist for `projects.department` (comma-separated, spelling kept).
+        let departments: Vec<String> = env::var("DEPARTMENTS")
+            .map(|v| {
+                v.split(',')
+                    .map(|d| d.trim().to_string())
+                    .filter(|d| !d.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .ok()
+            .filter(|list| !list.is_empty())
+            .unwrap_or_else(|| DEFAULT_DEPARTMENTS.iter().map(|d| d.to_string()).collect());
+
+        // API Links: Origin used for absolute URLs in responses (pagination links).
+        let public_api_url = env::var("PUBLIC_API_URL")
+            .unwrap_or_else(|_| "http://localhost:3000".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        // Submission Cohort: Defaults to the current calendar year.
+        let current_cohort_year = env::var("CURRENT_COHORT_YEAR")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(|| chrono::Utc::now().year());
+
+        // Comment Threads: Length above which the default order flips to newest-first.
+        let comments_newest_first_after = env::var("COMMENTS_NEWEST_FIRST_AFTER")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(50);
+
+        // Upload Guard: Caps on presigned URLs per hour and pending uploads per purpose.
+        let max_presigns_per_hour = env::var("MAX_PRESIGNS_PER_HOUR")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60);
+        let max_pending_uploads = env::var("MAX_PENDING_UPLOADS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+
+        // Build Metadata: GET /version and the version in `Server` are public unless
+        // VERSION_ADMIN_ONLY=true.
+        let version_admin_only = env::var("VERSION_ADMIN_ONLY")
+            .map(|v| v.eq_ignore_ascii_case("true") || v == "1")
+            .unwrap_or(false);
+
+        // Path Normalization: Opt out with NORMALIZE_PATHS=false (e.g. to debug proxy rewrites).
+        let normalize_paths = env::var("NORMALIZE_PATHS")
+            .map(|v| !(v.eq_ignore_ascii_case("false") || v == "0"))
+            .unwrap_or(true);
+
+        // Auth Provider: The register proxy target and its per-call timeout.
+        let supabase_url = env::var("SUPABASE_URL")
+            .ok()
+            .map(|v| v.trim_end_matches('/').to_string());
+        let supabase_key = env::var("SUPABASE_KEY").ok();
+        let auth_provider_timeout = env::var("AUTH_PROVIDER_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_AUTH_PROVIDER_TIMEOUT);
+
+        // Moderation: Reports from this many distinct users hide a comment until reviewed.
+        let auto_hide_report_threshold = env::var("AUTO_HIDE_REPORT_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(3);
+
+        // Review: How often a rejected project may be resubmitted.
+        let max_resubmissions = env::var("MAX_RESUBMISSIONS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n >= 0)
+            .unwrap_or(3);
+
+        // Submissions: Projects one student may create per cohort year.
+        let max_projects_per_user_per_year = env::var("MAX_PROJECTS_PER_USER_PER_YEAR")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(3);
+
+        // Comment Rate Limit: Comments one non-admin may post per rolling minute and day.
+        let max_comments_per_minute = env::var("MAX_COMMENTS_PER_MINUTE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(5);
+        let max_comments_per_day = env::var("MAX_COMMENTS_PER_DAY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(50);
+
+        // Maintenance: Stale-draft thresholds (days since last edit) and the runner's pass interval.
+        let draft_reminder_days = env::var("DRAFT_REMINDER_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(7);
+        let draft_cleanup_days = env::var("DRAFT_CLEANUP_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(90);
+        let audit_retention_days = env::var("AUDIT_RETENTION_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(365);
+        let maintenance_interval = env::var("MAINTENANCE_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_MAINTENANCE_INTERVAL);
+        let settings_refresh_interval = env::var("SETTINGS_REFRESH_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_SETTINGS_REFRESH_INTERVAL);
+
+        // Notification Fan-out: Recipients above which a fan-out is queued, and the dispatcher's interval.
+        let fanout_sync_threshold = env::var("FANOUT_SYNC_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(500);
+        let outbox_dispatch_interval = env::var("OUTBOX_DISPATCH_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_OUTBOX_DISPATCH_INTERVAL);
+
+        // Security Headers: Defaults suit both environments; SECURITY_* variables override them.
+        let security_headers = SecurityHeaders::from_env();
+
+        // Anonymous Feedback: Per-address hourly budget and the optional CAPTCHA gate.
+        let feedback_per_hour = env::var("FEEDBACK_PER_HOUR")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(5);
+        let captcha_secret = env::var("CAPTCHA_SECRET").ok().filter(|v| !v.is_empty());
+        let captcha_verify_url = env::var("CAPTCHA_VERIFY_URL")
+            .unwrap_or_else(|_| DEFAULT_CAPTCHA_VERIFY_URL.to_string());
+
         match env {
             Env::Local => Self {
                 env: Env::Local,
@@ -97,13 +515,49 @@ impl AppConfig {
                 s3_secret: "password".to_string(),
                 s3_bucket: "fyp-uploads".to_string(),
                 jwt_secret,
+                rate_limit_per_minute,
+                trusted_proxy_hops,
+                db_max_connections,
+                db_admin_reserved,
+                public_site_url,
+                public_api_url,
+                excerpt_blocked_words,
+                departments,
+                current_cohort_year,
+                comments_newest_first_after,
+                max_presigns_per_hour,
+                max_pending_uploads,
+                version_admin_only,
+                normalize_paths,
+                supabase_url,
+                supabase_key,
+                auth_provider_timeout,
+                auto_hide_report_threshold,
+                draft_reminder_days,
+                draft_cleanup_days,
+                audit_retention_days,
+                max_resubmissions,
+                max_projects_per_user_per_year,
+                max_comments_per_minute,
+                max_comments_per_day,
+                maintenance_interval,
+                settings_refresh_interval,
+                feedback_per_hour,
+                captcha_secret,
+                captcha_verify_url,
+                fanout_sync_threshold,
+                outbox_dispatch_interval,
+                security_headers,
             },
             Env::Production => {
                 // Production environment demands explicit setting of all infrastructure secrets.
-                let project_url =
-                    env::var("SUPABASE_URL").expect("FATAL: SUPABASE_URL required in prod");
+                let project_url = supabase_url
+                    .clone()
+                    .expect("FATAL: SUPABASE_URL required in prod");
                 // Construct the S3 endpoint specifically for Supabase's Storage API gateway.
                 let s3_endpoint = format!("{}/storage/v1/s3", project_url);
+                let s3_bucket =
+                    env::var("S3_BUCKET_NAME").unwrap_or_else(|_| "fyp-uploads".to_string());
 
                 Self {
                     env: Env::Production,
@@ -115,9 +569,41 @@ impl AppConfig {
                         .expect("FATAL: S3_ACCESS_KEY required in prod"),
                     s3_secret: env::var("S3_SECRET_KEY")
                         .expect("FATAL: S3_SECRET_KEY required in prod"),
-                    s3_bucket: env::var("S3_BUCKET_NAME")
-                        .unwrap_or_else(|_| "fyp-uploads".to_string()),
+                    s3_bucket,
                     jwt_secret,
+                    rate_limit_per_minute,
+                    trusted_proxy_hops,
+                    db_max_connections,
+                    db_admin_reserved,
+                    public_site_url,
+                    public_api_url,
+                    excerpt_blocked_words,
+                    departments,
+                    current_cohort_year,
+                    comments_newest_first_after,
+                    max_presigns_per_hour,
+                    max_pending_uploads,
+                    version_admin_only,
+                    normalize_paths,
+                    supabase_url,
+                    supabase_key,
+                    auth_provider_timeout,
+                    auto_hide_report_threshold,
+                    draft_reminder_days,
+                    draft_cleanup_days,
+                    audit_retention_days,
+                    max_resubmissions,
+                    max_projects_per_user_per_year,
+                    max_comments_per_minute,
+                    max_comments_per_day,
+                    maintenance_interval,
+                    settings_refresh_interval,
+                    feedback_per_hour,
+                    captcha_secret,
+                    captcha_verify_url,
+                    fanout_sync_threshold,
+                    outbox_dispatch_interval,
+                    security_headers,
                 }
             }
         }
@@ -0,0 +1,124 @@
+use crate::{
+    config::AppConfig,
+    links::UrlBuilder,
+    models::{EmbedDocument, Project},
+    og::{escape_html, excerpt},
+};
+use axum::http::{HeaderMap, HeaderValue, header};
+use serde::Deserialize;
+
+/// Maximum length (in characters) of the abstract excerpt shown on an embedded card.
+const EXCERPT_MAX_CHARS: usize = 160;
+/// Frame size advertised to oEmbed consumers, in CSS pixels.
+pub const EMBED_WIDTH: u32 = 400;
+pub const EMBED_HEIGHT: u32 = 360;
+
+/// Default Content-Security-Policy for embed responses (`security_headers.embed_csp`): no
+/// scripts, frames, forms or fetches; only inline styles and remote images.
+/// `frame-ancestors *` is what lets any site frame the card.
+pub const EMBED_CSP: &str = "default-src 'none'; img-src http: https:; style-src 'unsafe-inline'; base-uri 'none'; form-action 'none'; frame-ancestors *";
+
+/// EmbedFormat
+///
+/// `?format=` of GET /embed/projects/{id}: the HTML card (default) or its oEmbed document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbedFormat {
+    #[default]
+    Html,
+    Json,
+}
+
+/// The configured embed policy as a header map; empty when the policy is switched off.
+pub fn csp_headers(config: &AppConfig) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Ok(csp) = HeaderValue::from_str(&config.security_headers.embed_csp)
+        && !csp.is_empty()
+    {
+        headers.insert(header::CONTENT_SECURITY_POLICY, csp);
+    }
+    headers
+}
+
+fn canonical_url(project: &Project, config: &AppConfig) -> String {
+    format!(
+        "{}/projects/{}",
+        config.public_site_url.trim_end_matches('/'),
+        project.id
+    )
+}
+
+fn embed_url(project: &Project, config: &AppConfig) -> String {
+    UrlBuilder::new(config).absolute(&format!("/embed/projects/{}", project.id))
+}
+
+/// render_card
+///
+/// The embeddable project card as a standalone HTML document: cover thumbnail, title linking
+/// back to the project page, author and a short excerpt. Styles are inline attributes and
+/// there is no script, so it works under `EMBED_CSP`. Every interpolated field is escaped.
+/// `image` is the cover's `og::media_url`.
+pub fn render_card(project: &Project, config: &AppConfig, image: Option<&str>) -> String {
+    let title = escape_html(&project.title);
+    let author = escape_html(&project.author);
+    let summary = escape_html(&excerpt(
+        &project.abstract_text,
+        EXCERPT_MAX_CHARS,
+        &config.excerpt_blocked_words,
+    ));
+    let canonical = escape_html(&canonical_url(project, config));
+    let image = image
+        .map(|url| {
+            format!(
+                r#"<img src="{}" alt="" style="display:block;width:100%;height:160px;object-fit:cover">"#,
+                escape_html(url)
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        concat!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n",
+            "<meta name=\"robots\" content=\"noindex\">\n</head>\n",
+            "<body style=\"margin:0;font-family:system-ui,sans-serif;color:#1f2937\">\n",
+            "<article style=\"box-sizing:border-box;max-width:{width}px;border:1px solid #e5e7eb;border-radius:8px;overflow:hidden;background:#fff\">\n",
+            "{image}\n<div style=\"padding:12px 16px\">\n",
+            "<a href=\"{canonical}\" target=\"_blank\" rel=\"noopener\" style=\"font-size:18px;font-weight:600;color:#1d4ed8;text-decoration:none\">{title}</a>\n",
+            "<p style=\"margin:4px 0 8px;font-size:13px;color:#6b7280\">{author}</p>\n",
+            "<p style=\"margin:0;font-size:14px;line-height:1.4\">{summary}</p>\n",
+            "</div>\n</article>\n</body>\n</html>\n",
+        ),
+        title = title,
+        author = author,
+        summary = summary,
+        canonical = canonical,
+        image = image,
+        width = EMBED_WIDTH,
+    )
+}
+
+/// document
+///
+/// The oEmbed-style (`type: "rich"`) description of a project card. `html` is an iframe of
+/// the HTML card, so consumers never inline project text into their own pages. `image` is the
+/// cover's `og::media_url`.
+pub fn document(project: &Project, config: &AppConfig, image: Option<&str>) -> EmbedDocument {
+    let html = format!(
+        r#"<iframe src="{src}" width="{EMBED_WIDTH}" height="{EMBED_HEIGHT}" title="{title}" loading="lazy" style="border:0"></iframe>"#,
+        src = escape_html(&embed_url(project, config)),
+        title = escape_html(&project.title),
+    );
+    EmbedDocument {
+        embed_type: "rich".to_string(),
+        version: "1.0".to_string(),
+        title: project.title.clone(),
+        author_name: project.author.clone(),
+        provider_name: "FYP Portal".to_string(),
+        provider_url: config.public_site_url.clone(),
+        url: canonical_url(project, config),
+        thumbnail_url: image.map(str::to_string),
+        html,
+        width: EMBED_WIDTH,
+        height: EMBED_HEIGHT,
+    }
+}
@@ -0,0 +1,113 @@
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// ErrorResponse
+///
+/// The JSON body returned for every structured API error. `code` is a stable, machine-readable
+/// identifier the frontend can branch on; `message` is a human-readable explanation.
+/// `details` carries optional, error-specific context (e.g. rate limit counters, failed checks).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ErrorResponse {
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(type = "unknown")]
+    #[schema(value_type = Object)]
+    pub details: Option<serde_json::Value>,
+}
+
+/// FieldError
+///
+/// One failed check in a 422 `validation_failed` response, which lists every failing field of
+/// the payload in `details.errors` so a form can mark them all at once.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq)]
+#[ts(export)]
+pub struct FieldError {
+    // The payload field, e.g. `title`.
+    pub field: String,
+    // Stable identifier of the failed rule: `required`, `too_long` or `invalid_key`.
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, code: &str, message: impl Into<String>) -> Self {
+        Self { field: field.to_string(), code: code.to_string(), message: message.into() }
+    }
+}
+
+/// ApiError
+///
+/// Handler-level error type pairing an HTTP status with an `ErrorResponse` body.
+/// Handlers that need to explain *why* a request failed return `Result<_, ApiError>`
+/// instead of a bare `StatusCode`.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub body: ErrorResponse,
+    /// Seconds sent as `Retry-After`, for 429s raised by a handler.
+    pub retry_after: Option<u64>,
+}
+
+impl ApiError {
+    /// Creates an error with the given status, stable code and message.
+    pub fn new(status: StatusCode, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: ErrorResponse {
+                code: code.to_string(),
+                message: message.into(),
+                details: None,
+            },
+            retry_after: None,
+        }
+    }
+
+    /// 422 `validation_failed` listing `errors`, one per failing field.
+    pub fn validation(errors: Vec<FieldError>) -> Self {
+        Self::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "validation_failed",
+            "Some fields are invalid; see `details.errors`.",
+        )
+        .with_details(serde_json::json!({ "errors": errors }))
+    }
+
+    /// Attaches error-specific context to the response body.
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.body.details = Some(details);
+        self
+    }
+
+    /// Tells the client how many seconds to wait before retrying (at least one).
+    pub fn with_retry_after(mut self, seconds: u64) -> Self {
+        self.retry_after = Some(seconds.max(1));
+        self
+    }
+}
+
+/// ErrorCode
+///
+/// Response extension carrying the `ErrorResponse` code, so middleware (the error log) can
+/// tell errors apart without parsing the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorCode(pub String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let code = ErrorCode(self.body.code.clone());
+        let mut response = (self.status, Json(self.body)).into_response();
+        response.extensions_mut().insert(code);
+        if let Some(seconds) = self.retry_after {
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+        }
+        response
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    clock::{Clock, SystemClock},
+    error::ErrorCode,
+    models::{ErrorGroup, ErrorSample, ErrorSummary, ErrorWindow},
+};
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+/// Most samples kept; the oldest is dropped for every new one beyond this.
+pub const ERROR_LOG_CAPACITY: usize = 1_000;
+
+/// ErrorLog
+///
+/// Bounded, in-process record of the 5xx and 429 responses of this instance, for
+/// GET /admin/errors. Filled by the `record_errors` middleware; samples are stamped with the
+/// log's clock. Nothing is persisted, so a restart starts from an empty log.
+pub struct ErrorLog {
+    clock: Arc<dyn Clock>,
+    capacity: usize,
+    samples: Mutex<VecDeque<ErrorSample>>,
+}
+
+/// ErrorLogState
+///
+/// The shared handle to the error log held in `AppState`.
+pub type ErrorLogState = Arc<ErrorLog>;
+
+impl ErrorLog {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self::with_capacity(clock, ERROR_LOG_CAPACITY)
+    }
+
+    pub fn with_capacity(clock: Arc<dyn Clock>, capacity: usize) -> Self {
+        Self {
+            clock,
+            capacity,
+            samples: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Whether a response with this status is worth recording: server errors and rate limiting.
+    pub fn tracks(status: StatusCode) -> bool {
+        status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+    }
+
+    /// record
+    ///
+    /// Appends a sample stamped with the current time, evicting the oldest when full.
+    pub fn record(&self, route: String, status: StatusCode, code: String, request_id: Option<String>) {
+        let sample = ErrorSample {
+            at: self.clock.now(),
+            route,
+            status: status.as_u16(),
+            code,
+            request_id,
+        };
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() == self.capacity {
+            samples.pop_front();
+        }
+        if self.capacity > 0 {
+            samples.push_back(sample);
+        }
+    }
+
+    /// Number of samples currently held.
+    pub fn len(&self) -> usize {
+        self.samples.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// summary
+    ///
+    /// Counts per route, code and status within `window`, most frequent first (ties by route
+    /// and code), and at most `samples` of the newest errors in it.
+    pub fn summary(&self, window: ErrorWindow, samples: usize) -> ErrorSummary {
+        let since = self.clock.now() - window.duration();
+        let held = self.samples.lock().unwrap();
+        let in_window: Vec<&ErrorSample> = held.iter().filter(|s| s.at >= since).collect();
+
+        let mut groups: HashMap<(&str, &str, u16), ErrorGroup> = HashMap::new();
+        for sample in &in_window {
+            groups
+                .entry((&sample.route, &sample.code, sample.status))
+                .and_modify(|group| {
+                    group.count += 1;
+                    group.last_seen = group.last_seen.max(sample.at);
+                })
+                .or_insert_with(|| ErrorGroup {
+                    route: sample.route.clone(),
+                    code: sample.code.clone(),
+                    status: sample.status,
+                    count: 1,
+                    last_seen: sample.at,
+                });
+        }
+        let mut groups: Vec<ErrorGroup> = groups.into_values().collect();
+        groups.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.route.cmp(&b.route))
+                .then_with(|| a.code.cmp(&b.code))
+        });
+
+        ErrorSummary {
+            window,
+            since,
+            total: in_window.len() as u64,
+            groups,
+            recent: in_window.iter().rev().take(samples).map(|s| (*s).clone()).collect(),
+        }
+    }
+}
+
+impl Default for ErrorLog {
+    fn default() -> Self {
+        Self::new(Arc::new(SystemClock))
+    }
+}
+
+/// Stable code for a response that carries no `ErrorCode`, e.g. `internal_server_error`.
+fn status_code_name(status: StatusCode) -> String {
+    status
+        .canonical_reason()
+        .unwrap_or("unknown")
+        .to_ascii_lowercase()
+        .replace([' ', '-'], "_")
+}
+
+/// record_errors
+///
+/// Middleware feeding the error log. Runs inside the router, where the matched route template
+/// is known; requests that matched no route are recorded as `<unmatched>` rather than by path.
+/// The code comes from the `ApiError` that produced the response, if any.
+pub async fn record_errors(
+    State(log): State<ErrorLogState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = match request.extensions().get::<MatchedPath>() {
+        Some(path) => format!("{} {}", request.method(), path.as_str()),
+        None => format!("{} <unmatched>", request.method()),
+    };
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let response = next.run(request).await;
+    let status = response.status();
+    if ErrorLog::tracks(status) {
+        let code = response
+            .extensions()
+            .get::<ErrorCode>()
+            .map(|code| code.0.clone())
+            .unwrap_or_else(|| status_code_name(status));
+        log.record(route, status, code, request_id);
+    }
+    response
+}
@@ -0,0 +1,252 @@
+use axum::{
+    Json,
+    body::{Body, Bytes},
+    extract::FromRequestParts,
+    http::{HeaderValue, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use futures::{StreamExt, stream::BoxStream};
+use serde::Serialize;
+use std::convert::Infallible;
+
+use crate::models::{AuditEntry, ProjectExportRow, SupervisorStats};
+use crate::repository::RepositoryError;
+
+/// ResponseFormat
+///
+/// Content negotiation for admin listings. A request gets CSV when it sends
+/// `Accept: text/csv` or `?format=csv`; everything else gets the default JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Json,
+    Csv,
+}
+
+impl<S> FromRequestParts<S> for ResponseFormat
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query_csv = parts.uri.query().is_some_and(|query| {
+            query
+                .split('&')
+                .any(|pair| pair.eq_ignore_ascii_case("format=csv"))
+        });
+
+        let accept_csv = parts
+            .headers
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|accept| {
+                accept
+                    .split(',')
+                    .any(|media| media.trim().to_ascii_lowercase().starts_with("text/csv"))
+            });
+
+        Ok(if query_csv || accept_csv {
+            ResponseFormat::Csv
+        } else {
+            ResponseFormat::Json
+        })
+    }
+}
+
+/// CsvRecord
+///
+/// Implemented by row types that can be exported as CSV. `csv_fields` must return
+/// values in the same order as `HEADERS`.
+pub trait CsvRecord {
+    const HEADERS: &'static [&'static str];
+    fn csv_fields(&self) -> Vec<String>;
+}
+
+/// escape_field
+///
+/// RFC 4180 quoting (fields containing commas, quotes or newlines are wrapped in quotes
+/// with inner quotes doubled). Values starting with a spreadsheet formula character are
+/// prefixed with `'` so user-supplied text is never evaluated when the file is opened.
+pub fn escape_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// header_line
+///
+/// The header row for `T`, with its CRLF line ending.
+pub fn header_line<T: CsvRecord>() -> String {
+    let mut out = T::HEADERS.join(",");
+    out.push_str("\r\n");
+    out
+}
+
+/// csv_line
+///
+/// One escaped record, with its CRLF line ending. Used directly when streaming rows.
+pub fn csv_line<T: CsvRecord>(row: &T) -> String {
+    let fields: Vec<String> = row.csv_fields().iter().map(|f| escape_field(f)).collect();
+    let mut out = fields.join(",");
+    out.push_str("\r\n");
+    out
+}
+
+/// to_csv
+///
+/// Renders a header row followed by one line per record, using CRLF line endings.
+pub fn to_csv<T: CsvRecord>(rows: &[T]) -> String {
+    let mut out = header_line::<T>();
+    for row in rows {
+        out.push_str(&csv_line(row));
+    }
+    out
+}
+
+/// attachment
+///
+/// A `Content-Disposition` value offering the body as a download named `filename`.
+pub fn attachment(filename: &str) -> HeaderValue {
+    HeaderValue::from_str(&format!(
+        "attachment; filename=\"{}\"",
+        filename.replace(['"', '\\'], "")
+    ))
+    .unwrap_or_else(|_| HeaderValue::from_static("attachment"))
+}
+
+/// negotiate
+///
+/// Returns `rows` as JSON or as a downloadable CSV attachment named `filename`.
+pub fn negotiate<T>(format: ResponseFormat, filename: &str, rows: Vec<T>) -> Response
+where
+    T: Serialize + CsvRecord,
+{
+    match format {
+        ResponseFormat::Json => Json(rows).into_response(),
+        ResponseFormat::Csv => (
+            [
+                (
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("text/csv; charset=utf-8"),
+                ),
+                (header::CONTENT_DISPOSITION, attachment(filename)),
+            ],
+            to_csv(&rows),
+        )
+            .into_response(),
+    }
+}
+
+/// stream_csv
+///
+/// Streams `rows` as a downloadable CSV attachment named `filename`, one line per row, so the
+/// whole export is never held in memory. A database error ends the download early (the client
+/// sees a truncated file) because the status line has already been sent.
+pub fn stream_csv<T>(filename: &str, rows: BoxStream<'static, Result<T, RepositoryError>>) -> Response
+where
+    T: CsvRecord + Send + 'static,
+{
+    let header_row = futures::stream::once(std::future::ready(header_line::<T>()));
+    let logged_name = filename.to_string();
+    let lines = rows
+        .take_while(move |row| {
+            if let Err(e) = row {
+                tracing::error!("{}: aborting export after database error: {}", logged_name, e);
+            }
+            std::future::ready(row.is_ok())
+        })
+        .filter_map(|row| std::future::ready(row.ok()))
+        .map(|row| csv_line(&row));
+    let body = header_row
+        .chain(lines)
+        .map(|line| Ok::<_, Infallible>(Bytes::from(line)));
+
+    (
+        [
+            (header::CONTENT_TYPE, HeaderValue::from_static("text/csv; charset=utf-8")),
+            (header::CONTENT_DISPOSITION, attachment(filename)),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+// --- Record Implementations ---
+
+impl CsvRecord for SupervisorStats {
+    const HEADERS: &'static [&'static str] = &[
+        "supervisor",
+        "project_count",
+        "public_count",
+        "avg_likes",
+        "total_comments",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.supervisor.clone(),
+            self.project_count.to_string(),
+            self.public_count.to_string(),
+            format!("{:.2}", self.avg_likes),
+            self.total_comments.to_string(),
+        ]
+    }
+}
+
+impl CsvRecord for ProjectExportRow {
+    const HEADERS: &'static [&'static str] = &[
+        "id",
+        "title",
+        "author",
+        "year",
+        "status",
+        "like_count",
+        "created_at",
+        "owner_email",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.title.clone(),
+            self.author.clone(),
+            self.year.to_string(),
+            self.status.as_str().to_string(),
+            self.like_count.to_string(),
+            self.created_at.to_rfc3339(),
+            self.owner_email.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl CsvRecord for AuditEntry {
+    const HEADERS: &'static [&'static str] = &[
+        "id",
+        "created_at",
+        "actor_id",
+        "action",
+        "target_type",
+        "target_id",
+        "details",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.created_at.to_rfc3339(),
+            self.actor_id.map(|id| id.to_string()).unwrap_or_default(),
+            self.action.clone(),
+            self.target_type.clone(),
+            self.target_id.clone().unwrap_or_default(),
+            self.details.to_string(),
+        ]
+    }
+}
@@ -0,0 +1,253 @@
+use async_trait::async_trait;
+use axum::{
+    body::Bytes,
+    extract::{FromRef, FromRequest, FromRequestParts, Path, Request},
+    http::{StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthUser,
+    config::AppConfig,
+    error::ApiError,
+    models::{Project, ProjectStatus, UpdateProjectRequest, UpdateStatusRequest},
+    repository::{Repository, RepositoryState},
+};
+
+/// Largest accepted comment id: 2^53 - 1, the largest integer a JavaScript client can
+/// represent exactly. Anything above it cannot have been issued to a browser.
+pub const MAX_COMMENT_ID: i64 = (1 << 53) - 1;
+
+/// CommentId
+///
+/// A validated `project_comments.id` taken from the URL. Deserializes from the raw path
+/// segment, so `Path<CommentId>` (or `Path<(Uuid, CommentId)>`) rejects zero, negative,
+/// oversized and non-numeric values with 400 before any handler or query runs. Every route
+/// that takes a comment id must extract it through this type rather than `Path<i64>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "i64")]
+pub struct CommentId(i64);
+
+impl CommentId {
+    pub fn get(self) -> i64 {
+        self.0
+    }
+}
+
+impl TryFrom<i64> for CommentId {
+    type Error = String;
+
+    fn try_from(id: i64) -> Result<Self, Self::Error> {
+        if (1..=MAX_COMMENT_ID).contains(&id) {
+            Ok(CommentId(id))
+        } else {
+            Err(format!("comment id must be between 1 and {}", MAX_COMMENT_ID))
+        }
+    }
+}
+
+/// StatusUpdate
+///
+/// Body extractor for the admin status endpoint. Accepts the structured
+/// `UpdateStatusRequest` object, and — for older clients — a bare JSON boolean, which is
+/// treated as `{ "status": "approved" }` when true and `{ "status": "rejected" }` when false,
+/// and logged as deprecated. The legacy shape is tried first since it is the cheaper and less
+/// ambiguous parse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusUpdate(pub UpdateStatusRequest);
+
+impl<S> FromRequest<S> for StatusUpdate
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let bytes = Bytes::from_request(req, state).await.map_err(|e| {
+            ApiError::new(StatusCode::BAD_REQUEST, "invalid_body", e.body_text())
+        })?;
+
+        if let Ok(is_public) = serde_json::from_slice::<bool>(&bytes) {
+            tracing::warn!(
+                "deprecated: status update sent as a bare boolean; send UpdateStatusRequest {{ status, reason, notify_owner }} instead"
+            );
+            return Ok(StatusUpdate(UpdateStatusRequest {
+                status: ProjectStatus::from_visibility(is_public),
+                ..UpdateStatusRequest::default()
+            }));
+        }
+
+        serde_json::from_slice::<UpdateStatusRequest>(&bytes)
+            .map(StatusUpdate)
+            .map_err(|e| {
+                ApiError::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "invalid_body",
+                    format!("Expected UpdateStatusRequest or a boolean: {}", e),
+                )
+            })
+    }
+}
+
+/// Project fields fixed at creation. The owner cannot be handed over by an update.
+pub const IMMUTABLE_PROJECT_FIELDS: [&str; 1] = ["user_id"];
+
+/// ProjectPatch
+///
+/// Body extractor for PUT /projects/{id}. Parses an `UpdateProjectRequest`, but first rejects
+/// any payload naming an immutable field (`IMMUTABLE_PROJECT_FIELDS`) with 422
+/// `immutable_field`, rather than letting serde silently ignore it and the update "succeed".
+/// `author` is read as `author_name`, so neither spelling is ignored.
+#[derive(Debug, Clone)]
+pub struct ProjectPatch(pub UpdateProjectRequest);
+
+impl<S> FromRequest<S> for ProjectPatch
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let bytes = Bytes::from_request(req, state).await.map_err(|e| {
+            ApiError::new(StatusCode::BAD_REQUEST, "invalid_body", e.body_text())
+        })?;
+        let invalid = |e: serde_json::Error| {
+            ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "invalid_body",
+                format!("Expected UpdateProjectRequest: {}", e),
+            )
+        };
+
+        let mut body = serde_json::from_slice::<serde_json::Value>(&bytes).map_err(invalid)?;
+        if let Some(object) = body.as_object_mut() {
+            // `author` is the response spelling of `author_name`.
+            if !object.contains_key("author_name")
+                && let Some(author) = object.remove("author")
+            {
+                object.insert("author_name".to_string(), author);
+            }
+            let named: Vec<&str> = IMMUTABLE_PROJECT_FIELDS
+                .into_iter()
+                .filter(|field| object.contains_key(*field))
+                .collect();
+            if !named.is_empty() {
+                return Err(ApiError::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "immutable_field",
+                    "The owner cannot be changed after creation.",
+                )
+                .with_details(serde_json::json!({ "fields": named })));
+            }
+        }
+        serde_json::from_value(body).map(ProjectPatch).map_err(invalid)
+    }
+}
+
+/// OwnedResource
+///
+/// A resource addressed by the `{id}` path segment that its owner and admins may act on.
+/// Implemented by marker types (e.g. `ProjectId`) so `OwnerOrAdmin<R>` can be reused for
+/// other resources.
+#[async_trait]
+pub trait OwnedResource {
+    type Resource: Send;
+
+    /// Label used in error messages, e.g. "Project".
+    const NAME: &'static str;
+
+    /// Loads the resource as `user` may see it: anything for admins, otherwise only what is
+    /// visible to them. `None` means it does not exist *for this user*.
+    async fn load(repo: &dyn Repository, id: Uuid, user: &AuthUser) -> Option<Self::Resource>;
+
+    fn owner(resource: &Self::Resource) -> Uuid;
+}
+
+/// ProjectId
+///
+/// `OwnedResource` marker for `projects`. Non-admins can see public projects and their own.
+pub enum ProjectId {}
+
+#[async_trait]
+impl OwnedResource for ProjectId {
+    type Resource = Project;
+    const NAME: &'static str = "Project";
+
+    async fn load(repo: &dyn Repository, id: Uuid, user: &AuthUser) -> Option<Project> {
+        if user.role == "admin" {
+            repo.get_project(id).await
+        } else {
+            repo.get_project_authorized(id, user.id).await
+        }
+    }
+
+    fn owner(project: &Project) -> Uuid {
+        project.user_id
+    }
+}
+
+#[derive(Deserialize)]
+struct IdParam {
+    id: Uuid,
+}
+
+/// OwnerOrAdmin
+///
+/// Extractor for "owner or admin" routes: authenticates the caller, resolves `{id}` from the
+/// path, loads the resource once and hands both to the handler.
+///
+/// Rejections: 401 from `AuthUser`; 404 `not_found` when the resource does not exist or is
+/// not visible to the caller (so private projects are not revealed); 403 `forbidden` when it
+/// is visible but belongs to someone else.
+pub struct OwnerOrAdmin<R: OwnedResource> {
+    pub user: AuthUser,
+    pub resource: R::Resource,
+}
+
+impl<R: OwnedResource> OwnerOrAdmin<R> {
+    pub fn is_admin(&self) -> bool {
+        self.user.role == "admin"
+    }
+}
+
+impl<R, S> FromRequestParts<S> for OwnerOrAdmin<R>
+where
+    R: OwnedResource,
+    S: Send + Sync,
+    RepositoryState: FromRef<S>,
+    AppConfig: FromRef<S>,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        let Path(IdParam { id }) = Path::<IdParam>::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let repo = RepositoryState::from_ref(state);
+        let resource = R::load(repo.as_ref(), id, &user).await.ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                format!("{} not found.", R::NAME),
+            )
+            .into_response()
+        })?;
+
+        if user.role != "admin" && R::owner(&resource) != user.id {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "forbidden",
+                format!("Only the owner or an admin may modify this {}.", R::NAME.to_lowercase()),
+            )
+            .into_response());
+        }
+
+        Ok(OwnerOrAdmin { user, resource })
+    }
+}
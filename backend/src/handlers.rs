@@ -1,23 +1,69 @@
 use crate::{
     AppState,
+    audit,
     auth::AuthUser,
+    auth_provider,
+    build_info,
+    captcha,
+    error::{ApiError, ErrorResponse, FieldError},
+    export::{self, ResponseFormat},
+    extract::{CommentId, OwnerOrAdmin, ProjectId, ProjectPatch, StatusUpdate},
+    embed::{self, EmbedFormat},
+    maintenance::DraftReminders,
+    metrics, moderation, og, readiness, redact,
+    links::UrlBuilder,
+    pagination::PageRequest,
+    repository::{AdminCommentFilter, AuditFilter, CommentCursor, HiddenComments, ProjectListFilter, ProjectViewer},
+    sparse::{self, SparseJson, SparsePage},
+    storage::PRESIGN_TTL,
     models::{
-        self, AdminDashboardStats, Comment, CreateCommentRequest, CreateProjectRequest,
-        NotificationResponse, PresignedUrlRequest, PresignedUrlResponse, Project,
-        RegisterUserRequest, UpdateProjectRequest, User, UserProfile,
+        self, ActivityItem, AdminDashboardStats, AuditEntry, NewAuditEntry, BulkStatusRequest, BulkStatusResponse, ReassignOwnerRequest, ProjectStats, PendingReview, TechnologyCount, BulkUpdateResponse, ChangedSinceRequest, ChangedSinceResponse, Comment, CommentPage, NewNotification, CreateProgrammeRequest, DepartmentCount, DraftReminderReport, EmbedDocument, ErrorSummary, ErrorWindow, InvalidYearsReport, YearRemapRequest, CreateReportRequest, ReportedComment, AdminComment, DeepHealth, HealthStatus, Paginated, Programme, CommentOrder, CommentsLockRequest,
+        CreateCommentRequest, CreateFeedbackRequest, CreateProjectRequest, CreatedProject, LikeCountResponse, CommentCountResponse, LikeOutcome, NotificationResponse, ResubmitOutcome, CommentEditOutcome, OutboxJob, PostedComment, PresignedUrlRequest,
+        PresignedUrlResponse, MyProjectSummary, Project, ProjectDetail, ProjectListItem, ProjectLiker, ProjectFeedback, ProjectReview, ProjectUpdateResult, ProjectSort, PublicEventsResponse, PublicStats, ReadinessReport, RegisterUserRequest, Setting, SubscriptionState, TagCount,
+        SupervisorStats, UpdateCommentRequest, UpdateProjectRequest, UpdateSettingRequest, UpdateStatusRequest, UploadPurpose, User,
+        UserProfile, VersionInfo, ViewResponse, VoteResponse, YearCount,
     },
 };
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    body::{Body, Bytes},
+    extract::{OriginalUri, Path, Query, State},
+    http::{HeaderValue, StatusCode, Uri, header},
+    response::{Html, IntoResponse, Response},
 };
+use futures::StreamExt;
 use serde::Deserialize;
 use uuid::Uuid;
 
 // --- Filter Structs ---
 
+/// CommentFilter
+///
+/// Query parameters for the public comment thread (GET /projects/{id}/comments).
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct CommentFilter {
+    /// `oldest` or `newest`. When omitted the order depends on the thread length, and pages
+    /// (see `get_comments`) go back from the newest comment but each reads oldest first.
+    pub order: Option<CommentOrder>,
+    /// Page size when paging (default 50). Values above 100 are capped at 100.
+    #[param(value_type = Option<i64>, minimum = 1)]
+    pub limit: Option<String>,
+    /// Only comments older than this: a comment id, or an RFC 3339 timestamp. Not with
+    /// `order=oldest`.
+    pub before: Option<String>,
+    /// Only comments newer than this, like `before`. Only with `order=oldest`.
+    pub after: Option<String>,
+}
+
+/// EmbedFilter
+///
+/// Query parameters for the embeddable project card (GET /embed/projects/{id}).
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct EmbedFilter {
+    /// `html` (default) for the card itself, `json` for its oEmbed document.
+    pub format: Option<EmbedFormat>,
+}
+
 /// ProjectFilter
 ///
 /// Defines the accepted query parameters for the public project listing endpoint (GET /projects).
@@ -26,210 +72,2353 @@ use uuid::Uuid;
 pub struct ProjectFilter {
     /// Optional filter for projects created in a specific year.
     pub year: Option<i32>,
-    /// Optional full-text search string for project title/abstract matching.
+    /// Optional search term, matched case-insensitively within title, abstract and author.
+    /// At least 2 characters once trimmed; a blank term is ignored.
+    pub search: Option<String>,
+    /// Optional programme code (e.g. `CS`); joint-degree projects match each of theirs.
+    pub programme: Option<String>,
+    /// Optional tag (e.g. `machine-learning`), matched case-insensitively.
+    pub tag: Option<String>,
+    /// Optional supervisor name (e.g. `Dr Jane Smith`), matched exactly but case-insensitively.
+    pub supervisor: Option<String>,
+    /// Optional author name (e.g. `Jane Doe`), matched exactly but case-insensitively, so
+    /// unlike `search` it does not match part of a name. Combines with `year` and `search`.
+    pub author: Option<String>,
+    /// Optional department (e.g. `EE`), matched case-insensitively. `GET /departments` lists
+    /// the ones in use.
+    pub department: Option<String>,
+    /// Optional comma-separated list of Project fields to return (e.g. `id,title,year`).
+    /// `id` is always included. `user_id` is not selectable on public listings.
+    pub fields: Option<String>,
+    /// 1-based page number (default 1).
+    #[param(value_type = Option<i64>, minimum = 1)]
+    pub page: Option<String>,
+    /// Projects per page (default 20, at most 100).
+    #[param(value_type = Option<i64>, minimum = 1, maximum = 100)]
+    pub per_page: Option<String>,
+    /// Opaque `next_cursor` from a previous page. Continues after that page's last project,
+    /// unaffected by projects published in between. Replaces `page`.
+    pub cursor: Option<String>,
+    /// Listing order: `newest` (default), `oldest`, `most_liked`, `most_viewed` or `title`.
+    /// `cursor` is only supported with `newest`.
+    #[param(value_type = Option<ProjectSort>)]
+    pub sort: Option<String>,
+}
+
+/// TechFilter
+///
+/// The repeatable `tech` parameter of GET /projects. `ProjectFilter` cannot hold a repeated
+/// key, so the values are read from the request URI (`tech_filter`); this struct only
+/// documents the parameter.
+#[derive(utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TechFilter {
+    /// Technology (e.g. `rust`), matched case-insensitively. Repeat it to require several:
+    /// `?tech=rust&tech=postgres` lists projects using both. `GET /technologies` lists the
+    /// ones in use.
+    pub tech: Option<Vec<String>>,
+}
+
+/// The `tech` values of `uri`'s query, trimmed, lower-cased and de-duplicated; blank ones are
+/// ignored. The query already parsed as a `ProjectFilter`, so it is well-formed.
+fn tech_filter(uri: &Uri) -> Vec<String> {
+    let Ok(Query(pairs)) = Query::<Vec<(String, String)>>::try_from_uri(uri) else {
+        return vec![];
+    };
+    let mut technologies: Vec<String> = pairs
+        .into_iter()
+        .filter(|(key, _)| key == "tech")
+        .map(|(_, value)| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect();
+    technologies.sort();
+    technologies.dedup();
+    technologies
+}
+
+/// Shortest accepted `?search=` term; a single character matches nearly every project.
+pub const MIN_SEARCH_CHARS: usize = 2;
+
+/// Trims `?search=`, dropping a blank term and rejecting one shorter than `MIN_SEARCH_CHARS`.
+fn parse_search(raw: Option<String>) -> Result<Option<String>, ApiError> {
+    let Some(term) = raw.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    if term.chars().count() < MIN_SEARCH_CHARS {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_search",
+            "`search` must be at least 2 characters.",
+        )
+        .with_details(serde_json::json!({ "field": "search", "min": MIN_SEARCH_CHARS })));
+    }
+    Ok(Some(term))
+}
+
+/// Parses `?sort=`. Taken as a string so an unknown value gets a JSON 422 listing the
+/// accepted ones, instead of the extractor's plain-text 400.
+fn parse_project_sort(raw: Option<&str>) -> Result<ProjectSort, ApiError> {
+    let Some(raw) = raw.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Ok(ProjectSort::default());
+    };
+    ProjectSort::ALL
+        .into_iter()
+        .find(|sort| sort.as_str() == raw)
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "invalid_sort",
+                "Unknown sort order.",
+            )
+            .with_details(serde_json::json!({
+                "field": "sort",
+                "valid": ProjectSort::ALL.map(|sort| sort.as_str()),
+            }))
+        })
+}
+
+/// FieldsFilter
+///
+/// Sparse fieldset selection for listings that take no other filters (e.g. GET /admin/projects).
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct FieldsFilter {
+    /// Optional comma-separated list of Project fields to return. `id` is always included.
+    pub fields: Option<String>,
+}
+
+/// FeaturedFilter
+///
+/// Optional query parameters for GET /projects/featured.
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct FeaturedFilter {
+    /// Number of projects (default 3). Values above 20 are capped at 20.
+    #[param(value_type = Option<i64>, minimum = 1)]
+    pub limit: Option<String>,
+    /// Only rank projects from this cohort year. Defaults to the most recent year that has public
+    /// projects, so earlier cohorts' favourites do not hold the homepage indefinitely.
+    pub year: Option<i32>,
+}
+
+/// PendingQueueFilter
+///
+/// Optional query parameters for GET /admin/projects/pending.
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct PendingQueueFilter {
+    /// Number of entries (default 50). Values above 200 are capped at 200.
+    #[param(value_type = Option<i64>, minimum = 1)]
+    pub limit: Option<String>,
+}
+
+/// RandomProjectQuery
+///
+/// Optional query parameters for GET /projects/random.
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct RandomProjectQuery {
+    /// Return the "project of the day": the same project for every caller until midnight UTC.
+    #[serde(default)]
+    pub daily: bool,
+}
+
+/// ReportFilter
+///
+/// Optional query parameters for admin reports. `format=csv` is handled by `ResponseFormat`.
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct ReportFilter {
+    /// Restrict the report to a single cohort year.
+    pub year: Option<i32>,
+}
+
+/// ErrorSummaryQuery
+///
+/// Query parameters for GET /admin/errors.
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct ErrorSummaryQuery {
+    /// `15m`, `1h` (default) or `24h`.
+    pub window: Option<ErrorWindow>,
+    /// How many of the newest samples to return (default 20, at most 100).
+    pub samples: Option<usize>,
+}
+
+/// PublicEventsQuery
+///
+/// Query parameters for GET /events/public.
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct PublicEventsQuery {
+    /// Only events after this time (RFC 3339), normally the previous response's `next_since`.
+    /// Omitted means from the start of the feed.
+    #[param(value_type = Option<String>)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// SubmitOptions
+///
+/// Query parameters for the draft submission endpoint (POST /projects/{id}/submit).
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct SubmitOptions {
+    /// Admin-only: submit even if the readiness checklist fails.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// SaveOptions
+///
+/// Query parameters for creating and updating a project (POST /projects, PUT /projects/{id}).
+#[derive(Deserialize, Default, utoipa::IntoParams)]
+pub struct SaveOptions {
+    /// Store an over-long abstract cut at a word boundary instead of rejecting it with 422.
+    #[serde(default)]
+    pub truncate: bool,
+}
+
+/// CohortFilter
+///
+/// Query parameters for admin bulk actions that target a whole cohort (e.g. POST /admin/projects/lock-comments).
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct CohortFilter {
+    /// The cohort year whose projects are affected.
+    pub year: i32,
+}
+
+/// ActivityFilter
+///
+/// Query parameters for the caller's activity feed (GET /me/activity).
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct ActivityFilter {
+    /// 1-based page number (default 1).
+    #[param(value_type = Option<i64>, minimum = 1)]
+    pub page: Option<String>,
+    /// Items per page (default 20, at most 100).
+    #[param(value_type = Option<i64>, minimum = 1, maximum = 100)]
+    pub per_page: Option<String>,
+}
+
+/// LikersFilter
+///
+/// Query parameters for the list of a project's likers (GET /projects/{id}/likes/users).
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct LikersFilter {
+    /// 1-based page number (default 1).
+    #[param(value_type = Option<i64>, minimum = 1)]
+    pub page: Option<String>,
+    /// Items per page (default 20, at most 100).
+    #[param(value_type = Option<i64>, minimum = 1, maximum = 100)]
+    pub per_page: Option<String>,
+}
+
+/// AuditQuery
+///
+/// Query parameters for the audit log (GET /admin/audit). `format=csv` is handled by
+/// `ResponseFormat`.
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct AuditQuery {
+    /// Only entries by this user.
+    pub actor_id: Option<Uuid>,
+    /// Only this action, e.g. `project.status`.
+    pub action: Option<String>,
+    /// Only entries about this kind of record, e.g. `project`.
+    pub target_type: Option<String>,
+    /// Entries at or after this time (RFC 3339).
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Entries before this time (RFC 3339).
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    /// 1-based page number (default 1). Ignored for CSV, which has every matching entry.
+    #[param(value_type = Option<i64>, minimum = 1)]
+    pub page: Option<String>,
+    /// Entries per page (default 20, at most 100).
+    #[param(value_type = Option<i64>, minimum = 1, maximum = 100)]
+    pub per_page: Option<String>,
+}
+
+/// AdminCommentQuery
+///
+/// Query parameters for the moderators' comment listing (GET /admin/comments).
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct AdminCommentQuery {
+    /// Only comments on this project.
+    pub project_id: Option<Uuid>,
+    /// Only comments by this user.
+    pub user_id: Option<Uuid>,
+    /// Only comments whose text contains this, ignoring case.
     pub search: Option<String>,
+    /// Page size (default 50). Values above 100 are capped at 100.
+    #[param(value_type = Option<i64>, minimum = 1)]
+    pub limit: Option<String>,
+    /// Number of matching comments to skip (default 0).
+    #[param(value_type = Option<i64>, minimum = 0)]
+    pub offset: Option<String>,
+}
+
+/// DryRunFilter
+///
+/// Query parameters for admin maintenance triggers (e.g. POST /admin/maintenance/draft-reminders).
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct DryRunFilter {
+    /// Report what would be done without writing anything (default false).
+    pub dry_run: Option<bool>,
+}
+
+// --- Handlers ---
+
+/// get_my_projects
+///
+/// [Authenticated Route] Lists all projects owned by the requesting user, newest first.
+/// This includes projects that are currently drafts, pending review or rejected (any `status`).
+/// Each entry adds the like count and `review_state` for the dashboard, and the latest admin
+/// review, if any, so the owner can see the reason.
+///
+/// *Note*: The user identity (`id`) is resolved securely via the `AuthUser` extractor.
+#[utoipa::path(
+    get,
+    path = "/me/projects",
+    responses((status = 200, description = "My Projects", body = [MyProjectSummary]))
+)]
+pub async fn get_my_projects(
+    AuthUser { id, .. }: AuthUser,
+    State(state): State<AppState>,
+) -> Json<Vec<MyProjectSummary>> {
+    let summaries = state.repo.get_my_project_summaries(id).await;
+    let mut reviews: std::collections::HashMap<Uuid, ProjectReview> =
+        state.repo.get_latest_reviews(id).await.into_iter().map(|r| (r.project_id, r)).collect();
+    let summaries = summaries
+        .into_iter()
+        .map(|summary| MyProjectSummary {
+            latest_review: reviews.remove(&summary.project.id),
+            ..summary
+        })
+        .collect();
+    Json(summaries)
+}
+
+/// get_my_activity
+///
+/// [Authenticated Route] The caller's activity feed for the profile page, newest first:
+/// comments they wrote, projects they liked and visibility changes of their own projects,
+/// each with the project's title. Covers the last `ACTIVITY_WINDOW_DAYS` (90) days.
+#[utoipa::path(
+    get,
+    path = "/me/activity",
+    params(ActivityFilter),
+    responses(
+        (status = 200, description = "A page of the caller's activity", body = Paginated<ActivityItem>),
+        (status = 422, description = "Invalid `page`/`per_page`", body = ErrorResponse)
+    )
+)]
+pub async fn get_my_activity(
+    AuthUser { id, .. }: AuthUser,
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Query(filter): Query<ActivityFilter>,
+) -> Result<Json<Paginated<ActivityItem>>, ApiError> {
+    let page = PageRequest::parse(filter.page.as_deref(), filter.per_page.as_deref(), None)?;
+    let (items, total_count) = state.repo.get_user_activity(id, page).await;
+    let links = UrlBuilder::new(&state.config).page_links(&uri, page.page, page.per_page, total_count);
+    Ok(Json(Paginated {
+        items,
+        total_count,
+        page: page.page,
+        per_page: page.per_page,
+        links,
+        next_cursor: None,
+    }))
+}
+
+/// build_readiness_report
+///
+/// Confirms the cover image in storage, then runs the checklist in `readiness`.
+/// A storage failure counts as "not confirmed" rather than failing the whole request.
+async fn build_readiness_report(state: &AppState, project: &Project) -> ReadinessReport {
+    let cover_in_storage = if project.cover_image.trim().is_empty() {
+        false
+    } else {
+        state
+            .storage
+            .object_exists(&project.cover_image)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("readiness: cover image lookup failed: {}", redact::redact_urls(&e));
+                false
+            })
+    };
+    readiness::evaluate(project, cover_in_storage, state.config.current_cohort_year)
+}
+
+/// get_project_readiness
+///
+/// [Authenticated Route] Returns the submission checklist for one of the user's own projects,
+/// so problems can be fixed before an admin spends a review cycle on them.
+///
+/// *Ownership*: Projects the user does not own are reported as 404.
+#[utoipa::path(
+    get,
+    path = "/me/projects/{id}/readiness",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Checklist", body = ReadinessReport),
+        (status = 404, description = "Not Found or Not Yours")
+    )
+)]
+pub async fn get_project_readiness(
+    AuthUser { id: user_id, .. }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReadinessReport>, StatusCode> {
+    let project = state
+        .repo
+        .get_project_authorized(id, user_id)
+        .await
+        .filter(|p| p.user_id == user_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(build_readiness_report(&state, &project).await))
+}
+
+/// get_project_stats
+///
+/// [Authenticated Route] Engagement numbers for one of the user's own projects: like, comment
+/// and view totals, plus likes per day over the last 30 days for a chart.
+///
+/// *Ownership*: Enforced in the repository query; projects the user does not own are reported
+/// as 404, for admins too.
+#[utoipa::path(
+    get,
+    path = "/me/projects/{id}/stats",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Engagement figures", body = ProjectStats),
+        (status = 404, description = "Not Found or Not Yours", body = ErrorResponse)
+    )
+)]
+pub async fn get_project_stats(
+    AuthUser { id: user_id, .. }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ProjectStats>, ApiError> {
+    state
+        .repo
+        .get_project_stats(id, user_id)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found."))
+}
+
+/// submit_project
+///
+/// [Authenticated Route] Moves a draft into the review queue.
+///
+/// *Readiness*: The checklist is re-evaluated server-side; any failed check refuses the
+/// submission with 422 `not_ready`, listing the failed checks in `details.checks`.
+/// Admins may bypass the checklist (and ownership) with `?force=true`.
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/submit",
+    params(("id" = Uuid, Path, description = "Project ID"), SubmitOptions),
+    responses(
+        (status = 200, description = "Submitted", body = Project),
+        (status = 403, description = "Force Requires Admin", body = ErrorResponse),
+        (status = 404, description = "Not Found or Not Yours", body = ErrorResponse),
+        (status = 409, description = "Already Submitted", body = ErrorResponse),
+        (status = 422, description = "Checklist Failed", body = ErrorResponse)
+    )
+)]
+pub async fn submit_project(
+    AuthUser {
+        id: user_id, role, ..
+    }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(options): Query<SubmitOptions>,
+) -> Result<Json<models::Project>, ApiError> {
+    let is_admin = role == "admin";
+    if options.force && !is_admin {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "force_requires_admin",
+            "Only admins may bypass the submission checklist.",
+        ));
+    }
+
+    let not_found = || ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found.");
+    let project = if is_admin {
+        state.repo.get_project(id).await
+    } else {
+        state
+            .repo
+            .get_project_authorized(id, user_id)
+            .await
+            .filter(|p| p.user_id == user_id)
+    }
+    .ok_or_else(not_found)?;
+
+    let already_submitted = || {
+        ApiError::new(
+            StatusCode::CONFLICT,
+            "already_submitted",
+            "This project has already been submitted for review.",
+        )
+    };
+    if project.is_public || project.submitted_at.is_some() {
+        return Err(already_submitted());
+    }
+
+    if !options.force {
+        let report = build_readiness_report(&state, &project).await;
+        if !report.ready {
+            let failed: Vec<_> = report.checks.into_iter().filter(|c| !c.passed).collect();
+            return Err(ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "not_ready",
+                "The project does not meet the submission checklist.",
+            )
+            .with_details(serde_json::json!({ "checks": failed })));
+        }
+    }
+
+    // Only a draft is moved; one submitted or decided since it was read above is a conflict.
+    state
+        .repo
+        .mark_project_submitted(id)
+        .await
+        .map(Json)
+        .ok_or_else(already_submitted)
+}
+
+/// resubmit_project
+///
+/// [Authenticated Route] Puts a rejected project back in the review queue after the owner has
+/// addressed the rejection. The project becomes `pending` again, the rejection reason is no
+/// longer shown as its latest review, and every admin is notified.
+///
+/// *Limits*: Only rejected projects can be resubmitted (409 `not_rejected` otherwise), at most
+/// `max_resubmissions` times (409 `resubmission_limit`).
+///
+/// *Ownership*: Only the owner may resubmit; other projects are reported as 404.
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/resubmit",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Resubmitted", body = Project),
+        (status = 404, description = "Not Found or Not Yours", body = ErrorResponse),
+        (status = 409, description = "Not Rejected, or Resubmission Limit Reached", body = ErrorResponse)
+    )
+)]
+pub async fn resubmit_project(
+    AuthUser { id: user_id, .. }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<models::Project>, ApiError> {
+    let max = state.config.max_resubmissions;
+    match state.repo.resubmit_project(id, user_id, max).await {
+        Some(ResubmitOutcome::Resubmitted(project)) => {
+            tracing::info!(project_id = %id, "Project resubmitted for review");
+            Ok(Json(*project))
+        }
+        Some(ResubmitOutcome::NotRejected(status)) => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "not_rejected",
+            "Only rejected projects can be resubmitted.",
+        )
+        .with_details(serde_json::json!({ "status": status }))),
+        Some(ResubmitOutcome::LimitReached) => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "resubmission_limit",
+            "This project has been resubmitted too many times.",
+        )
+        .with_details(serde_json::json!({ "max_resubmissions": max }))),
+        None => Err(ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found.")),
+    }
+}
+
+/// add_comment
+///
+/// [Authenticated Route] Posts a new comment on a project and notifies its participants.
+/// Up to `fanout_sync_threshold` recipients are notified before responding; above that, the
+/// notifications are queued for the outbox dispatcher and the response is a 202 carrying the
+/// `fanout_job_id` to follow at GET /admin/outbox/{id}.
+///
+/// *Validation*: The text is sanitized, tidied and checked by `normalize_comment` first (HTML
+/// markup and control characters are removed); a blank or over-long comment is a 422
+/// `validation_failed`.
+///
+/// *Rate Limit*: Non-admins may post `max_comments_per_minute` comments per rolling minute and
+/// `max_comments_per_day` per rolling day, across all projects (see `check_comment_rate`).
+///
+/// *Visibility*: Non-admins may comment on public projects and their own; any other project
+/// is a 404 `not_found`, like a missing one.
+///
+/// *Discussion Lock*: Refused with 403 `comments_locked` once the project's discussion is frozen.
+///
+/// *Mentions*: Users mentioned as `@prefix` each get a `mention` notification (see
+/// `notify_mentions`).
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/comments",
+    request_body = CreateCommentRequest,
+    responses(
+        (status = 201, description = "Comment Added", body = PostedComment),
+        (status = 202, description = "Comment Added, notifications queued", body = PostedComment),
+        (status = 403, description = "Discussion Locked", body = ErrorResponse),
+        (status = 404, description = "Project Not Found", body = ErrorResponse),
+        (status = 422, description = "Blank or longer than 2000 characters", body = ErrorResponse),
+        (status = 429, description = "Comment rate limit reached; see `Retry-After`", body = ErrorResponse),
+        (status = 500, description = "The comment could not be saved", body = ErrorResponse)
+    )
+)]
+pub async fn add_comment(
+    AuthUser { id: user_id, role }: AuthUser,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateCommentRequest>,
+) -> Result<(StatusCode, Json<PostedComment>), ApiError> {
+    let text = normalize_comment(&payload.text)?;
+    let not_found = || ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found.");
+    if role != "admin" {
+        check_comment_rate(&state, user_id).await?;
+        state.repo.get_project_authorized(project_id, user_id).await.ok_or_else(not_found)?;
+    }
+    // The insert itself refuses a locked discussion. Admins skip the visibility check, so a
+    // missing project is also only found out here.
+    let posted = match state.repo.add_comment(project_id, user_id, text, state.config.fanout_sync_threshold).await {
+        Ok(Some(posted)) => posted,
+        Ok(None) if state.repo.are_comments_locked(project_id).await => return Err(comments_locked_error()),
+        Ok(None) => return Err(not_found()),
+        Err(e) => {
+            tracing::error!("add_comment error: {}", e);
+            return Err(ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "comment_failed",
+                "The comment could not be saved; please try again.",
+            ));
+        }
+    };
+    notify_mentions(&state, &posted.comment).await;
+    let status = if posted.fanout_job_id.is_some() {
+        StatusCode::ACCEPTED
+    } else {
+        StatusCode::CREATED
+    };
+    Ok((status, Json(posted)))
+}
+
+/// notify_mentions
+///
+/// Sends each user `@`-mentioned in a new comment (see `moderation::extract_mentions`) one
+/// `mention` notification from its author, however often they are mentioned. Prefixes that
+/// match no profile, or more than one, are ignored rather than guessed at, and the author is
+/// never notified of their own mention. Only the first `MAX_MENTIONS_PER_COMMENT` distinct
+/// prefixes are looked up, since these notifications are written on the request path.
+async fn notify_mentions(state: &AppState, comment: &Comment) {
+    let mut prefixes = moderation::extract_mentions(&comment.comment);
+    prefixes.truncate(MAX_MENTIONS_PER_COMMENT);
+    let Some(author) = comment.user_id else { return };
+    if prefixes.is_empty() {
+        return;
+    }
+    let users = state.repo.get_users_by_email_prefix(&prefixes).await;
+    let local_part = |user: &User| user.email.split('@').next().unwrap_or_default().to_lowercase();
+    for user in &users {
+        let prefix = local_part(user);
+        let unique = users.iter().filter(|other| local_part(other) == prefix).count() == 1;
+        if unique && user.id != author {
+            let notification = NewNotification::new(user.id, author, comment.project_id, "mention");
+            state.repo.create_notification(notification).await;
+        }
+    }
+}
+
+/// Distinct `@` mentions per comment that notify anyone; later ones are plain text.
+pub const MAX_MENTIONS_PER_COMMENT: usize = 10;
+
+/// Longest comment, in characters, once `normalize_comment` has tidied it.
+pub const MAX_COMMENT_CHARS: usize = 2_000;
+
+/// Longest comment text, in bytes, that is sanitized at all: room for `MAX_COMMENT_CHARS`
+/// four-byte characters. Anything larger could never fit once tidied.
+pub const MAX_RAW_COMMENT_BYTES: usize = 4 * MAX_COMMENT_CHARS;
+
+/// normalize_comment
+///
+/// The stored form of a comment's text: put through `moderation::sanitize_comment` (markup and
+/// control characters, including the `\r` of Windows line endings, removed), trimmed, and with
+/// runs of blank lines cut to one (at most two newlines in a row). Blank or over
+/// `MAX_COMMENT_CHARS` results are a 422 `validation_failed` on `text`, so a comment that was
+/// nothing but markup is refused as blank. Input over `MAX_RAW_COMMENT_BYTES` is refused as
+/// too long before it is sanitized, which bounds the sanitizer's work per request.
+fn normalize_comment(raw: &str) -> Result<String, ApiError> {
+    let too_long = || {
+        ApiError::validation(vec![FieldError::new("text", "too_long", "The comment is longer than 2000 characters.")])
+    };
+    if raw.len() > MAX_RAW_COMMENT_BYTES {
+        return Err(too_long());
+    }
+    let mut text = String::with_capacity(raw.len());
+    let mut newlines = 0;
+    for c in moderation::sanitize_comment(raw).trim().chars() {
+        newlines = if c == '\n' { newlines + 1 } else { 0 };
+        if newlines <= 2 {
+            text.push(c);
+        }
+    }
+    if text.is_empty() {
+        return Err(ApiError::validation(vec![FieldError::new("text", "required", "The comment cannot be blank.")]));
+    }
+    if text.chars().count() > MAX_COMMENT_CHARS {
+        return Err(too_long());
+    }
+    Ok(text)
+}
+
+/// check_comment_rate
+///
+/// The per-user comment limit, counted over the user's comments and edits on every project:
+/// reaching `max_comments_per_minute` in the last minute or `max_comments_per_day` in the last
+/// day is a 429 `comment_rate_limited`. `Retry-After` is the time until the oldest comment or
+/// edit of the exhausted window (the longer one, if both are) leaves it.
+async fn check_comment_rate(state: &AppState, user_id: Uuid) -> Result<(), ApiError> {
+    let recent = state.repo.get_recent_comments(user_id).await;
+    let now = state.clock.now();
+    let windows = [
+        ("minute", chrono::Duration::minutes(1), recent.last_minute, recent.oldest_last_minute, state.config.max_comments_per_minute),
+        ("day", chrono::Duration::days(1), recent.last_day, recent.oldest_last_day, state.config.max_comments_per_day),
+    ];
+    let exhausted = windows
+        .into_iter()
+        .filter(|&(_, _, count, _, limit)| count >= limit)
+        .map(|(window, length, count, oldest, limit)| {
+            let wait = oldest.map_or(length, |oldest| oldest + length - now);
+            let seconds = (wait.num_milliseconds().max(0) as u64).div_ceil(1000);
+            (seconds, window, count, limit)
+        })
+        .max();
+    let Some((seconds, window, count, limit)) = exhausted else {
+        return Ok(());
+    };
+    Err(ApiError::new(
+        StatusCode::TOO_MANY_REQUESTS,
+        "comment_rate_limited",
+        "You are commenting too quickly. Please try again later.",
+    )
+    .with_details(serde_json::json!({ "window": window, "count": count, "limit": limit }))
+    .with_retry_after(seconds))
+}
+
+/// comments_locked_error
+///
+/// Shared 403 for every write path (comments, likes) blocked by the discussion lock.
+fn comments_locked_error() -> ApiError {
+    ApiError::new(
+        StatusCode::FORBIDDEN,
+        "comments_locked",
+        "Discussion on this project has been locked.",
+    )
+}
+
+/// set_comments_lock
+///
+/// [Authenticated Route] Locks or unlocks discussion on a project.
+///
+/// *RBAC/Ownership*: Admins may lock any project; everyone else is restricted to their own
+/// projects by the repository's ownership check.
+#[utoipa::path(
+    put,
+    path = "/projects/{id}/comments-lock",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = CommentsLockRequest,
+    responses(
+        (status = 200, description = "Updated", body = Project),
+        (status = 404, description = "Not Found or Not Yours")
+    )
+)]
+pub async fn set_comments_lock(
+    AuthUser {
+        id: user_id, role, ..
+    }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CommentsLockRequest>,
+) -> Result<Json<models::Project>, StatusCode> {
+    let updated = if role == "admin" {
+        state.repo.set_comments_locked_admin(id, payload.locked).await
+    } else {
+        state
+            .repo
+            .set_comments_locked(id, user_id, payload.locked)
+            .await
+    };
+    updated.map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+/// set_project_subscription
+///
+/// [Authenticated Route] Subscribes to or mutes comment notifications for a project.
+/// The owner and past commenters are subscribed by default; muting overrides that.
+#[utoipa::path(
+    put,
+    path = "/projects/{id}/subscription",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = SubscriptionState,
+    responses(
+        (status = 200, description = "Updated", body = SubscriptionState),
+        (status = 404, description = "Not Found")
+    )
+)]
+pub async fn set_project_subscription(
+    AuthUser { id: user_id, .. }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(subscription): Json<SubscriptionState>,
+) -> Result<Json<SubscriptionState>, StatusCode> {
+    // Private projects are only visible (and therefore followable) by their owner.
+    state
+        .repo
+        .get_project_authorized(id, user_id)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if !state.repo.set_subscription(id, user_id, subscription).await {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(subscription))
+}
+
+/// Page size of GET /projects/{id}/comments when `before` is given without `limit`.
+pub const DEFAULT_COMMENT_PAGE_LIMIT: i64 = 50;
+/// Largest `limit` served by GET /projects/{id}/comments; larger requests are capped to it.
+pub const MAX_COMMENT_PAGE_LIMIT: i64 = 100;
+
+/// get_comments
+///
+/// [Public Route] Retrieves all comments for a given project ID.
+/// The underlying repository method ensures the project is public before returning comments,
+/// except to its signed-in owner and to admins, who also read the threads of private projects
+/// (e.g. a reviewer's remarks on a project pending review). Anyone else gets an empty thread.
+///
+/// *Ordering*: An explicit `order` wins. Otherwise threads longer than
+/// `comments_newest_first_after` are returned newest-first so fresh replies are not buried,
+/// and shorter threads read oldest-first.
+///
+/// *Paging*: With `limit`, `before` or `after` one page of `limit` comments is returned, and
+/// `has_more` is set when more remain in the paging direction. `order=newest` pages back from
+/// the newest comment with `before`, newest first; `order=oldest` pages forward from the first
+/// with `after`, oldest first. Without `order` pages go back like `newest` but each reads oldest
+/// first, so the client can prepend it above the last. A cursor against the order is a 422
+/// `invalid_pagination`.
+///
+/// *Moderation*: Comments hidden pending review are omitted, except that a signed-in author
+/// still sees their own (with `hidden_pending_review` set) and admins see all of them.
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/comments",
+    params(("id" = Uuid, Path, description = "Project ID"), CommentFilter),
+    responses(
+        (status = 200, description = "Comments", body = CommentPage),
+        (status = 400, description = "Unknown order, or a cursor that is neither a comment id nor a timestamp"),
+        (status = 422, description = "Zero, negative or non-numeric limit, or a cursor against the order", body = ErrorResponse)
+    )
+)]
+pub async fn get_comments(
+    user: Option<AuthUser>,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    Query(filter): Query<CommentFilter>,
+) -> Result<Json<CommentPage>, ApiError> {
+    let hidden = match user {
+        Some(user) if user.role == "admin" => HiddenComments::Include,
+        Some(user) => HiddenComments::AuthoredBy(user.id),
+        None => HiddenComments::Exclude,
+    };
+    if filter.limit.is_some() || filter.before.is_some() || filter.after.is_some() {
+        // The cursor has to point the way the pages go, as `/projects` ties `cursor` to a sort.
+        let forwards = filter.order == Some(CommentOrder::Oldest);
+        let misplaced = match (forwards, &filter.before, &filter.after) {
+            (true, Some(_), _) => Some(("before", "`before` cannot be used with `order=oldest`; use `after`.")),
+            (false, _, Some(_)) => Some(("after", "`after` can only be used with `order=oldest`.")),
+            _ => None,
+        };
+        if let Some((field, message)) = misplaced {
+            return Err(ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_pagination", message)
+                .with_details(serde_json::json!({ "field": field })));
+        }
+        let limit = parse_limit(filter.limit.as_deref(), DEFAULT_COMMENT_PAGE_LIMIT, MAX_COMMENT_PAGE_LIMIT)?;
+        let cursor = if forwards { filter.after.as_deref() } else { filter.before.as_deref() };
+        let cursor = cursor.map(parse_comment_cursor).transpose()?;
+        // One extra row answers `has_more` without a count.
+        let mut comments = if forwards {
+            state.repo.get_comments_after(project_id, hidden, cursor, limit + 1).await
+        } else {
+            state.repo.get_comments_before(project_id, hidden, cursor, limit + 1).await
+        };
+        let has_more = comments.len() as i64 > limit;
+        comments.truncate(limit as usize);
+        if filter.order.is_none() {
+            comments.reverse();
+        }
+        return Ok(Json(CommentPage { comments, has_more }));
+    }
+
+    let order = match filter.order {
+        Some(order) => order,
+        None if state.repo.count_comments(project_id).await.unwrap_or(0)
+            > state.config.comments_newest_first_after =>
+        {
+            CommentOrder::Newest
+        }
+        None => CommentOrder::Oldest,
+    };
+    let comments = state.repo.get_comments(project_id, order, hidden).await;
+    Ok(Json(CommentPage { comments, has_more: false }))
+}
+
+/// `before` or `after` of GET /projects/{id}/comments: a comment id, or else an RFC 3339 timestamp.
+fn parse_comment_cursor(raw: &str) -> Result<CommentCursor, ApiError> {
+    let raw = raw.trim();
+    if let Ok(id) = raw.parse::<i64>() {
+        return Ok(CommentCursor::Id(id));
+    }
+    chrono::DateTime::parse_from_rfc3339(raw)
+        .map(|at| CommentCursor::Time(at.with_timezone(&chrono::Utc)))
+        .map_err(|_| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_cursor",
+                "The cursor must be a comment id or an RFC 3339 timestamp.",
+            )
+        })
+}
+
+/// delete_project
+///
+/// [Authenticated Route] Allows a user to delete their own project; admins may delete any.
+///
+/// *Authorization*: `OwnerOrAdmin` loads the project once and rejects strangers with 403
+/// (404 when the project is private to someone else). The repository's owner check still
+/// applies for non-admins as Defense-in-Depth.
+#[utoipa::path(
+    delete,
+    path = "/projects/{id}",
+    responses(
+        (status = 204, description = "Deleted"), 
+        (status = 403, description = "Not Owner", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse)
+    )
+)]
+pub async fn delete_project(
+    State(state): State<AppState>,
+    owned: OwnerOrAdmin<ProjectId>,
+) -> StatusCode {
+    let id = owned.resource.id;
+    let deleted = if owned.is_admin() {
+        state.repo.delete_project_admin(id).await
+    } else {
+        state.repo.delete_project(id, owned.user.id).await
+    };
+    // The row can still vanish between the extractor's load and the delete.
+    if deleted {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+/// update_project
+///
+/// [Authenticated Route] Allows a user to modify their own project details.
+/// `programme_codes`, when present, replaces the project's programmes and must name known
+/// programmes; `tags`, when present, replaces its tags. The response lists the fields that
+/// actually changed; values equal to the stored ones (after trimming the title and abstract)
+/// are not written, so a no-op update keeps `updated_at`.
+///
+/// *Abstract*: At most `MAX_ABSTRACT_CHARS`; see `fit_abstract` for the 422 and the
+/// `?truncate=true` alternative.
+///
+/// *Validation*: The fields present are checked as at creation (see `project_field_errors`).
+///
+/// *Author and year*: `author_name` and `year` correct a misspelled name or a wrong cohort; the year must be an allowed academic year, as at creation, and a new
+/// year gives the project a new slug. Only `user_id` is fixed: a payload naming it is rejected
+/// with 422 `immutable_field` (see `ProjectPatch`).
+///
+/// *Authorization*: `OwnerOrAdmin` — the owner or an admin. Admin edits are applied on behalf
+/// of the owner, so the repository's owner-scoped update is reused.
+#[utoipa::path(
+    put,
+    path = "/projects/{id}",
+    params(SaveOptions),
+    request_body = UpdateProjectRequest,
+    responses(
+        (status = 200, description = "Updated project and the fields that changed", body = ProjectUpdateResult),
+        (status = 403, description = "Not Owner", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 422, description = "Immutable field, invalid title, author or cover key, year outside the academic years, unknown programme code or department, invalid tags or technologies, or abstract too long", body = ErrorResponse)
+    )
+)]
+pub async fn update_project(
+    State(state): State<AppState>,
+    OwnerOrAdmin { resource: project, .. }: OwnerOrAdmin<ProjectId>,
+    Query(options): Query<SaveOptions>,
+    ProjectPatch(mut payload): ProjectPatch,
+) -> Result<Json<ProjectUpdateResult>, ApiError> {
+    let errors = project_field_errors(
+        payload.title.as_deref(),
+        payload.author_name.as_deref(),
+        payload.cover_image_key.as_deref(),
+    );
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+    let truncated = match &mut payload.abstract_text {
+        Some(text) => fit_abstract(text, options.truncate)?,
+        None => false,
+    };
+    if let Some(year) = payload.year {
+        validate_year(&state, year)?;
+    }
+    validate_programme_codes(&state, &mut payload.programme_codes).await?;
+    validate_department(&state, &mut payload.department)?;
+    if let Some(tags) = &mut payload.tags {
+        normalize_tags(tags)?;
+    }
+    if let Some(technologies) = &mut payload.technologies {
+        normalize_technologies(technologies)?;
+    }
+    match state.repo.update_project(project.id, project.user_id, payload).await {
+        Some(result) => Ok(Json(ProjectUpdateResult {
+            truncated,
+            abstract_length: result.project.abstract_text.chars().count(),
+            ..result
+        })),
+        // Deleted between the extractor's load and the update.
+        None => Err(ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found.")),
+    }
+}
+
+/// get_projects
+///
+/// [Public Route] Lists public projects with filtering and search capabilities, one page at
+/// a time (newest first). The envelope carries `total_count` and `next`/`prev` links that
+/// preserve the filters. Pages are addressed by number, or by `cursor` (keyset mode), which
+/// neither skips nor repeats rows when projects are approved mid-scroll.
+///
+/// *Security*: The repository method applies the `status = approved` filter **unconditionally**
+/// to prevent data leakage to anonymous users, ensuring Defense-in-Depth. Reports that are not
+/// public have their `report` key removed, as on every public project response.
+///
+/// Authentication is optional: signed-in callers also get `liked_by_me` on every item.
+#[utoipa::path(
+    get,
+    path = "/projects",
+    params(ProjectFilter, TechFilter),
+    responses(
+        (status = 200, description = "A page of filtered projects (only the selected fields when `fields` is set)", body = Paginated<ProjectListItem>),
+        (status = 400, description = "Malformed `cursor`", body = ErrorResponse),
+        (status = 422, description = "Unknown field in `fields`, unknown `sort`, `search` shorter than 2 characters, or invalid `page`/`per_page`", body = ErrorResponse)
+    )
+)]
+pub async fn get_projects(
+    user: Option<AuthUser>,
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Query(filter): Query<ProjectFilter>,
+) -> Result<SparsePage<ProjectListItem>, ApiError> {
+    // Anonymous endpoint: sensitive fields are not offered in the whitelist.
+    let mut selectable = sparse::project_fields(false);
+    selectable.push("liked_by_me".to_string());
+    let fields = sparse::parse_fields(filter.fields.as_deref(), &selectable)?;
+    let page = PageRequest::parse(filter.page.as_deref(), filter.per_page.as_deref(), filter.cursor.as_deref())?;
+    let sort = parse_project_sort(filter.sort.as_deref())?;
+    let search = parse_search(filter.search)?;
+    // Cursors encode a (created_at, id) position, which only means something in newest order.
+    if sort != ProjectSort::Newest && page.after.is_some() {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_pagination",
+            "`cursor` can only be used with `sort=newest`.",
+        )
+        .with_details(serde_json::json!({ "field": "cursor" })));
+    }
+    let list_filter = ProjectListFilter {
+        year: filter.year,
+        search,
+        programme: filter.programme.map(|code| code.trim().to_uppercase()),
+        tag: filter.tag.map(|tag| tag.trim().to_lowercase()),
+        supervisor: filter.supervisor.map(|name| name.trim().to_string()).filter(|name| !name.is_empty()),
+        author: filter.author.map(|name| name.trim().to_string()).filter(|name| !name.is_empty()),
+        department: filter.department.map(|name| name.trim().to_string()).filter(|name| !name.is_empty()),
+        technologies: tech_filter(&uri),
+        sort,
+    };
+    let (items, total_count) = state.repo.get_projects(list_filter, page).await;
+    let items: Vec<Project> = items.into_iter().map(Project::redact_for_public).collect();
+    let next_cursor = match sort {
+        ProjectSort::Newest => page.next_cursor(&items),
+        _ => None,
+    };
+    let urls = UrlBuilder::new(&state.config);
+    let links = match page.after {
+        Some(_) => urls.cursor_links(&uri, next_cursor.as_deref()),
+        None => urls.page_links(&uri, page.page, page.per_page, total_count),
+    };
+    let liked = match &user {
+        Some(user) => {
+            let ids: Vec<Uuid> = items.iter().map(|p| p.id).collect();
+            Some(state.repo.get_liked_project_ids(user.id, &ids).await)
+        }
+        None => None,
+    };
+    let items = items
+        .into_iter()
+        .map(|project| ProjectListItem {
+            liked_by_me: liked.as_ref().map(|ids| ids.contains(&project.id)),
+            project,
+        })
+        .collect();
+    Ok(SparsePage(
+        Paginated {
+            items,
+            total_count,
+            page: page.page,
+            per_page: page.per_page,
+            links,
+            next_cursor,
+        },
+        fields,
+    ))
+}
+
+/// get_project_details
+///
+/// [Public Route] Retrieves a single project's details by ID.
+/// Requires an existence and visibility check: anonymous callers only see public projects,
+/// signed-in owners also see their own drafts and hidden projects, and admins see everything.
+///
+/// The response embeds the owner's profile (`owner`: ID, email, avatar), which listings leave
+/// out; it is null if the owner has no profile row. Authentication is optional: signed-in
+/// callers additionally receive their comment `subscription` state and `liked_by_me`.
+///
+/// *Report*: The `report` key is null unless `report_is_public` is set or the caller is the
+/// owner or an admin (see `Project::redact_for_public`).
+#[utoipa::path(
+    get,
+    path = "/projects/{id}",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Found", body = ProjectDetail),
+        (status = 404, description = "Not Found or Private")
+    )
+)]
+pub async fn get_project_details(
+    user: Option<AuthUser>,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ProjectDetail>, StatusCode> {
+    project_detail(&state, user, id).await
+}
+
+/// get_project_by_slug
+///
+/// [Public Route] `get_project_details` addressed by slug (`/projects/slug/smart-greenhouse-2024`)
+/// instead of ID, with the same visibility rules and payload. Slugs a project had before a
+/// rename still resolve to it.
+#[utoipa::path(
+    get,
+    path = "/projects/slug/{slug}",
+    params(("slug" = String, Path, description = "Current or earlier project slug")),
+    responses(
+        (status = 200, description = "Found", body = ProjectDetail),
+        (status = 404, description = "Not Found or Private")
+    )
+)]
+pub async fn get_project_by_slug(
+    user: Option<AuthUser>,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<Json<ProjectDetail>, StatusCode> {
+    let id = state.repo.resolve_project_slug(&slug).await.ok_or(StatusCode::NOT_FOUND)?;
+    project_detail(&state, user, id).await
+}
+
+/// The body shared by the ID and slug detail routes.
+async fn project_detail(
+    state: &AppState,
+    user: Option<AuthUser>,
+    id: Uuid,
+) -> Result<Json<ProjectDetail>, StatusCode> {
+    let viewer = match &user {
+        None => ProjectViewer::Public,
+        Some(user) if user.role == "admin" => ProjectViewer::Admin,
+        Some(user) => ProjectViewer::User(user.id),
+    };
+    // If the project is not found OR not visible to the caller, it returns None.
+    let (project, owner) = state.repo.get_project_detail(id, viewer).await.ok_or(StatusCode::NOT_FOUND)?;
+    let project = match viewer {
+        ProjectViewer::Admin => project,
+        ProjectViewer::User(user_id) if user_id == project.user_id => project,
+        _ => project.redact_for_public(),
+    };
+    let (subscription, liked_by_me) = match user {
+        Some(user) => (
+            state.repo.get_subscription(id, user.id).await,
+            Some(!state.repo.get_liked_project_ids(user.id, &[id]).await.is_empty()),
+        ),
+        None => (None, None),
+    };
+    let programme_codes = state.repo.get_project_programmes(id).await;
+    Ok(Json(ProjectDetail {
+        project,
+        subscription,
+        programme_codes,
+        owner,
+        liked_by_me,
+    }))
+}
+
+/// record_view
+///
+/// [Public Route] Counts one view of a public project, for the "1.2k views" counter and the
+/// `most_viewed` listing order. Called by the detail page once per visit; anonymous callers
+/// are counted like anyone else, and the global rate limit bounds repeated calls.
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/view",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "View counted", body = ViewResponse),
+        (status = 404, description = "Not Found or Private", body = ErrorResponse)
+    )
+)]
+pub async fn record_view(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ViewResponse>, ApiError> {
+    state
+        .repo
+        .increment_view(id)
+        .await
+        .map(|view_count| Json(ViewResponse { view_count }))
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found."))
+}
+
+/// get_like_count
+///
+/// [Public Route] The current like total of a public project, so a client can refresh the
+/// counter after a vote without refetching the project. Hidden or missing projects 404.
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/likes",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Like total", body = LikeCountResponse),
+        (status = 404, description = "Not Found or Private", body = ErrorResponse)
+    )
+)]
+pub async fn get_like_count(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<LikeCountResponse>, ApiError> {
+    state
+        .repo
+        .get_like_count(id)
+        .await
+        .map(|count| Json(LikeCountResponse { count }))
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found."))
+}
+
+/// get_comment_count
+///
+/// [Public Route] How many comments a public project's thread shows, for the comment bubble on
+/// project cards without fetching the thread. Comments held for review are not counted. Hidden
+/// or missing projects 404.
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/comments/count",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Comment total", body = CommentCountResponse),
+        (status = 404, description = "Not Found or Private", body = ErrorResponse)
+    )
+)]
+pub async fn get_comment_count(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CommentCountResponse>, ApiError> {
+    state
+        .repo
+        .count_comments(id)
+        .await
+        .map(|count| Json(CommentCountResponse { count }))
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found."))
+}
+
+/// get_project_likers
+///
+/// [Authenticated Route] Who liked a project (id and email), most recent like first, one page
+/// at a time. Only the project's owner and admins may see it.
+///
+/// *Authorization*: `OwnerOrAdmin` rejects other users with 403 (404 when the project is
+/// private to someone else).
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/likes/users",
+    params(("id" = Uuid, Path, description = "Project ID"), LikersFilter),
+    responses(
+        (status = 200, description = "A page of the project's likers", body = Paginated<ProjectLiker>),
+        (status = 403, description = "Not Owner", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 422, description = "Invalid `page`/`per_page`", body = ErrorResponse)
+    )
+)]
+pub async fn get_project_likers(
+    State(state): State<AppState>,
+    OwnerOrAdmin { resource: project, .. }: OwnerOrAdmin<ProjectId>,
+    OriginalUri(uri): OriginalUri,
+    Query(filter): Query<LikersFilter>,
+) -> Result<Json<Paginated<ProjectLiker>>, ApiError> {
+    let page = PageRequest::parse(filter.page.as_deref(), filter.per_page.as_deref(), None)?;
+    let (items, total_count) = state.repo.get_project_likers(project.id, page).await;
+    let links = UrlBuilder::new(&state.config).page_links(&uri, page.page, page.per_page, total_count);
+    Ok(Json(Paginated {
+        items,
+        total_count,
+        page: page.page,
+        per_page: page.per_page,
+        links,
+        next_cursor: None,
+    }))
+}
+
+/// Most project ids a single changed-since check may name.
+pub const CHANGED_SINCE_MAX_IDS: usize = 200;
+/// Oldest cutoff a changed-since check may ask about; older timestamps are clamped to it.
+pub const CHANGED_SINCE_MAX_AGE: chrono::Duration = chrono::Duration::days(90);
+
+/// get_changed_projects
+///
+/// [Public Route] Tells a returning visitor which public projects changed (edited or
+/// commented on) since their last visit, so the listing can flag them.
+///
+/// `since` is clamped to `CHANGED_SINCE_MAX_AGE` ago to bound the scan; the response echoes
+/// the cutoff actually used.
+#[utoipa::path(
+    post,
+    path = "/projects/changed-since",
+    request_body = ChangedSinceRequest,
+    responses(
+        (status = 200, description = "Ids of changed public projects", body = ChangedSinceResponse),
+        (status = 422, description = "More than 200 ids", body = ErrorResponse)
+    )
+)]
+pub async fn get_changed_projects(
+    State(state): State<AppState>,
+    Json(req): Json<ChangedSinceRequest>,
+) -> Result<Json<ChangedSinceResponse>, ApiError> {
+    if let Some(ids) = &req.ids
+        && ids.len() > CHANGED_SINCE_MAX_IDS
+    {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "too_many_ids",
+            "At most 200 project ids may be checked at once.",
+        )
+        .with_details(serde_json::json!({ "count": ids.len(), "limit": CHANGED_SINCE_MAX_IDS })));
+    }
+
+    let since = req.since.max(chrono::Utc::now() - CHANGED_SINCE_MAX_AGE);
+    let ids = state.repo.get_changed_project_ids(since, req.ids).await;
+    Ok(Json(ChangedSinceResponse { since, ids }))
+}
+
+/// Most events one GET /events/public call returns.
+pub const PUBLIC_EVENTS_PAGE_SIZE: usize = 500;
+
+/// get_public_events
+///
+/// [Public Route] The publication feed for sites mirroring the showcase: `project.published`
+/// and `project.unpublished` events after `since`, oldest first, at most
+/// `PUBLIC_EVENTS_PAGE_SIZE` per call. Callers pass `next_since` back as `since` until
+/// `has_more` is false, then poll again later.
+///
+/// `since` is exclusive, so a full page is cut back to end on a timestamp boundary: events
+/// sharing the time of the first one left out are moved to the next page rather than skipped.
+#[utoipa::path(
+    get,
+    path = "/events/public",
+    params(PublicEventsQuery),
+    responses(
+        (status = 200, description = "Events in order with the watermark for the next call", body = PublicEventsResponse),
+        (status = 400, description = "`since` is not an RFC 3339 timestamp")
+    )
+)]
+pub async fn get_public_events(
+    State(state): State<AppState>,
+    Query(query): Query<PublicEventsQuery>,
+) -> impl IntoResponse {
+    let since = query.since.unwrap_or(chrono::DateTime::UNIX_EPOCH);
+    let mut events = state.repo.get_public_events(since, PUBLIC_EVENTS_PAGE_SIZE as i64 + 1).await;
+    for event in &mut events {
+        event.project = event.project.take().map(Project::redact_for_public);
+    }
+    let has_more = events.len() > PUBLIC_EVENTS_PAGE_SIZE;
+    if has_more {
+        let cut = events[PUBLIC_EVENTS_PAGE_SIZE].at;
+        events.truncate(PUBLIC_EVENTS_PAGE_SIZE);
+        // Unless the whole page shares that time, which no watermark could split anyway.
+        if let Some(last) = events.iter().rposition(|event| event.at != cut) {
+            events.truncate(last + 1);
+        }
+    }
+    let next_since = events.last().map_or(since, |event| event.at);
+    (
+        [(header::CACHE_CONTROL, "public, max-age=60")],
+        Json(PublicEventsResponse { events, next_since, has_more }),
+    )
+}
+
+/// get_public_stats
+///
+/// [Public Route] Headline counters for the landing page. Only public projects are counted;
+/// admin-only figures (pending reviews, user totals) stay behind `/admin/stats`.
+#[utoipa::path(
+    get,
+    path = "/stats/public",
+    responses((status = 200, description = "Public counters", body = PublicStats))
+)]
+pub async fn get_public_stats(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(state.repo.get_public_stats().await),
+    )
+}
+
+/// get_tags
+///
+/// [Public Route] Tags in use on public projects with their project counts, most used first,
+/// for the listing's tag filter.
+#[utoipa::path(
+    get,
+    path = "/tags",
+    responses((status = 200, description = "Tags with usage counts", body = [TagCount]))
+)]
+pub async fn get_tags(State(state): State<AppState>) -> Json<Vec<TagCount>> {
+    Json(state.repo.get_tag_counts().await)
+}
+
+/// get_departments
+///
+/// [Public Route] Departments that have public projects with their project counts, most
+/// projects first, for the listing's department filter.
+#[utoipa::path(
+    get,
+    path = "/departments",
+    responses((status = 200, description = "Departments with public project counts", body = [DepartmentCount]))
+)]
+pub async fn get_departments(State(state): State<AppState>) -> Json<Vec<DepartmentCount>> {
+    Json(state.repo.get_department_counts().await)
+}
+
+/// get_technologies
+///
+/// [Public Route] Technologies used by public projects with their project counts, most used
+/// first, for the listing's technology filter.
+#[utoipa::path(
+    get,
+    path = "/technologies",
+    responses((status = 200, description = "Technologies with public project counts", body = [TechnologyCount]))
+)]
+pub async fn get_technologies(State(state): State<AppState>) -> Json<Vec<TechnologyCount>> {
+    Json(state.repo.get_technology_counts().await)
+}
+
+/// get_project_years
+///
+/// [Public Route] Years that have public projects, newest first, with their project counts,
+/// for the listing's year filter.
+#[utoipa::path(
+    get,
+    path = "/projects/years",
+    responses((status = 200, description = "Years with public project counts", body = [YearCount]))
+)]
+pub async fn get_project_years(State(state): State<AppState>) -> Json<Vec<YearCount>> {
+    Json(state.repo.get_project_years().await)
+}
+
+/// get_project_archive
+///
+/// [Public Route] The "Class of {year}" archive: every approved project of the year, most
+/// liked first, in the same order as the featured list. A year without public projects is an
+/// empty list; a non-numeric year is rejected by the path extractor with a 400.
+#[utoipa::path(
+    get,
+    path = "/projects/archive/{year}",
+    params(("year" = i32, Path, description = "Cohort year")),
+    responses(
+        (status = 200, description = "The year's public projects, most liked first", body = [Project]),
+        (status = 400, description = "Non-numeric year")
+    )
+)]
+pub async fn get_project_archive(
+    State(state): State<AppState>,
+    Path(year): Path<i32>,
+) -> Json<Vec<Project>> {
+    Json(state.repo.get_year_archive(year).await.into_iter().map(Project::redact_for_public).collect())
+}
+
+/// Shortest and longest feedback message, in characters after trimming (matches the
+/// `project_feedback.message` CHECK constraint).
+pub const MIN_FEEDBACK_CHARS: usize = 10;
+pub const MAX_FEEDBACK_CHARS: usize = 1000;
+
+/// validate_feedback
+///
+/// Trims the message and contact address (a blank address counts as none) and rejects an
+/// out-of-bounds message or malformed address with 422 `invalid_feedback`.
+fn validate_feedback(payload: &CreateFeedbackRequest) -> Result<(String, Option<String>), ApiError> {
+    let invalid = |message: &str, details: serde_json::Value| {
+        ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_feedback", message).with_details(details)
+    };
+    let message = payload.message.trim();
+    let chars = message.chars().count();
+    if !(MIN_FEEDBACK_CHARS..=MAX_FEEDBACK_CHARS).contains(&chars) {
+        return Err(invalid(
+            "Feedback must be between 10 and 1000 characters.",
+            serde_json::json!({
+                "field": "message",
+                "min_chars": MIN_FEEDBACK_CHARS,
+                "max_chars": MAX_FEEDBACK_CHARS,
+            }),
+        ));
+    }
+    let contact_email = payload.contact_email.as_deref().map(str::trim).filter(|e| !e.is_empty());
+    if let Some(email) = contact_email {
+        let plausible = email.len() <= 254
+            && !email.chars().any(char::is_whitespace)
+            && email
+                .split_once('@')
+                .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.') && !domain.contains('@'));
+        if !plausible {
+            return Err(invalid(
+                "The contact email address is not valid.",
+                serde_json::json!({ "field": "contact_email" }),
+            ));
+        }
+    }
+    Ok((message.to_string(), contact_email.map(str::to_string)))
+}
+
+/// submit_feedback
+///
+/// [Public Route] Leaves anonymous feedback on a public project and notifies its owner.
+/// Rate limited per client address (FEEDBACK_PER_HOUR) and, when a CAPTCHA secret is
+/// configured, gated on `captcha_token`. Feedback is only readable by the owner and admins.
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/feedback",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = CreateFeedbackRequest,
+    responses(
+        (status = 201, description = "Feedback stored", body = ProjectFeedback),
+        (status = 400, description = "CAPTCHA failed", body = ErrorResponse),
+        (status = 404, description = "Not Found or Private", body = ErrorResponse),
+        (status = 422, description = "Invalid message or contact email", body = ErrorResponse),
+        (status = 429, description = "Too many submissions", body = ErrorResponse)
+    )
+)]
+pub async fn submit_feedback(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateFeedbackRequest>,
+) -> Result<(StatusCode, Json<ProjectFeedback>), ApiError> {
+    let (message, contact_email) = validate_feedback(&payload)?;
+    captcha::verify(&state.http, &state.config, payload.captcha_token.as_deref()).await?;
+    state
+        .repo
+        .add_feedback(id, &message, contact_email.as_deref())
+        .await
+        .map(|feedback| (StatusCode::CREATED, Json(feedback)))
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found."))
+}
+
+/// get_project_feedback
+///
+/// [Authenticated Route] The anonymous feedback left on one of the caller's projects, newest
+/// first.
+///
+/// *Authorization*: `OwnerOrAdmin` — the owner or an admin.
+#[utoipa::path(
+    get,
+    path = "/me/projects/{id}/feedback",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Feedback, newest first", body = [ProjectFeedback]),
+        (status = 403, description = "Not Owner", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse)
+    )
+)]
+pub async fn get_project_feedback(
+    State(state): State<AppState>,
+    owned: OwnerOrAdmin<ProjectId>,
+) -> Json<Vec<ProjectFeedback>> {
+    Json(state.repo.get_feedback(Some(owned.resource.id)).await)
+}
+
+/// get_all_feedback
+///
+/// [Admin Route] Anonymous feedback across every project, newest first.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
+#[utoipa::path(
+    get,
+    path = "/admin/feedback",
+    responses(
+        (status = 200, description = "Feedback, newest first", body = [ProjectFeedback]),
+        (status = 403, description = "Not Admin", body = ErrorResponse)
+    )
+)]
+pub async fn get_all_feedback(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ProjectFeedback>>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    Ok(Json(state.repo.get_feedback(None).await))
+}
+
+/// get_version
+///
+/// [Public Route] Reports the build metadata of the running binary, so issues can be traced
+/// to the exact deployed commit.
+///
+/// *Authorization*: Public by default; with `VERSION_ADMIN_ONLY=true` only admins may read it.
+#[utoipa::path(
+    get,
+    path = "/version",
+    responses(
+        (status = 200, description = "Build metadata", body = VersionInfo),
+        (status = 401, description = "Admin-only and not signed in"),
+        (status = 403, description = "Admin-only and not an admin")
+    )
+)]
+pub async fn get_version(
+    user: Option<AuthUser>,
+    State(state): State<AppState>,
+) -> Result<Json<VersionInfo>, StatusCode> {
+    if state.config.version_admin_only {
+        match user {
+            Some(user) if user.role == "admin" => {}
+            Some(_) => return Err(StatusCode::FORBIDDEN),
+            None => return Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+    Ok(Json(build_info::version_info(&state.config)))
+}
+
+/// get_project_og
+///
+/// [Public Route] Serves a minimal HTML document with Open Graph / Twitter card tags so that
+/// links shared in chat apps (Teams, WhatsApp) render a preview. The SPA shell itself carries
+/// no per-project metadata, so crawlers are pointed here.
+///
+/// *Security*: Only public projects produce metadata; missing or private projects receive an
+/// empty 404 shell. All interpolated fields are HTML-escaped. The cover is only signed when it
+/// is one of the owner's own cover uploads (see `og::cover_url`).
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/og",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Preview document", content_type = "text/html", body = String),
+        (status = 404, description = "Not Found or Private", content_type = "text/html", body = String)
+    )
+)]
+pub async fn get_project_og(State(state): State<AppState>, Path(id): Path<Uuid>) -> Response {
+    match state.repo.get_public_project(id).await {
+        Some(project) => {
+            let image = og::cover_url(state.repo.as_ref(), state.storage.as_ref(), &project).await;
+            (
+                // Previews are cheap to regenerate; let crawlers and CDNs cache them briefly.
+                [(header::CACHE_CONTROL, "public, max-age=300")],
+                Html(og::render_project(&project, &state.config, image.as_deref())),
+            )
+                .into_response()
+        }
+        None => (StatusCode::NOT_FOUND, Html(og::render_not_found())).into_response(),
+    }
+}
+
+/// get_project_embed
+///
+/// [Public Route] A project card that third-party pages (e.g. a supervisor's homepage) can
+/// frame, or with `?format=json` the oEmbed-style document describing it. Responses carry
+/// the configured embed policy (`EMBED_CSP` by default), which allows framing from any origin
+/// but no scripts.
+///
+/// *Security*: Only public projects are rendered; missing or private ones get the same 404 as
+/// the link preview. All interpolated fields are HTML-escaped, and the cover is signed under
+/// the same ownership check as the link preview.
+#[utoipa::path(
+    get,
+    path = "/embed/projects/{id}",
+    params(("id" = Uuid, Path, description = "Project ID"), EmbedFilter),
+    responses(
+        (status = 200, description = "Embeddable card (HTML) or its oEmbed document (JSON)", content(
+            (String = "text/html"),
+            (EmbedDocument = "application/json")
+        )),
+        (status = 404, description = "Not Found or Private", body = ErrorResponse)
+    )
+)]
+pub async fn get_project_embed(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(filter): Query<EmbedFilter>,
+) -> Response {
+    let format = filter.format.unwrap_or_default();
+    let csp = embed::csp_headers(&state.config);
+    let mut headers = csp.clone();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("public, max-age=300"));
+    let project = state.repo.get_public_project(id).await;
+    let image = match &project {
+        Some(project) => og::cover_url(state.repo.as_ref(), state.storage.as_ref(), project).await,
+        None => None,
+    };
+    match (project, format) {
+        (Some(project), EmbedFormat::Html) => {
+            (headers, Html(embed::render_card(&project, &state.config, image.as_deref()))).into_response()
+        }
+        (Some(project), EmbedFormat::Json) => {
+            (headers, Json(embed::document(&project, &state.config, image.as_deref()))).into_response()
+        }
+        (None, EmbedFormat::Html) => (
+            StatusCode::NOT_FOUND,
+            csp,
+            Html(og::render_not_found()),
+        )
+            .into_response(),
+        (None, EmbedFormat::Json) => {
+            ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found.").into_response()
+        }
+    }
+}
+
+/// Number of projects in the featured strip when no `limit` is given.
+pub const DEFAULT_FEATURED_LIMIT: i64 = 3;
+/// Largest `limit` served by GET /projects/featured; larger requests are capped to it.
+pub const MAX_FEATURED_LIMIT: i64 = 20;
+
+/// Parses `?limit=`, `default` when absent. Taken as a string so that non-numeric input gets
+/// the same JSON 422 as zero or negative values; values above `max` are capped rather than
+/// rejected.
+fn parse_limit(raw: Option<&str>, default: i64, max: i64) -> Result<i64, ApiError> {
+    let Some(raw) = raw.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Ok(default);
+    };
+    match raw.parse::<i64>() {
+        Ok(limit) if limit >= 1 => Ok(limit.min(max)),
+        _ => Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_limit",
+            "`limit` must be a positive integer.",
+        )
+        .with_details(serde_json::json!({ "field": "limit", "min": 1, "max": max }))),
+    }
+}
+
+/// parse_offset
+///
+/// Validates an optional `offset` query value: absent or blank means 0, anything but a
+/// non-negative integer is a 422 `invalid_offset`.
+fn parse_offset(raw: Option<&str>) -> Result<i64, ApiError> {
+    let Some(raw) = raw.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Ok(0);
+    };
+    match raw.parse::<i64>() {
+        Ok(offset) if offset >= 0 => Ok(offset),
+        _ => Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_offset",
+            "`offset` must be a non-negative integer.",
+        )
+        .with_details(serde_json::json!({ "field": "offset", "min": 0 }))),
+    }
+}
+
+/// The seed of the "project of the day": whole days since the Unix epoch, in UTC.
+pub fn spotlight_seed(now: chrono::DateTime<chrono::Utc>) -> i64 {
+    now.timestamp().div_euclid(86_400)
+}
+
+/// get_random_project
+///
+/// [Public Route] One random public project for the landing page's rotating spotlight. With
+/// `daily=true` the pick is seeded by the date, so every visitor sees the same project for the
+/// day (and it may be cached for a while); otherwise each call draws again.
+#[utoipa::path(
+    get,
+    path = "/projects/random",
+    params(RandomProjectQuery),
+    responses(
+        (status = 200, description = "A public project", body = Project),
+        (status = 404, description = "There are no public projects", body = ErrorResponse)
+    )
+)]
+pub async fn get_random_project(
+    State(state): State<AppState>,
+    Query(query): Query<RandomProjectQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (seed, cache) = if query.daily {
+        (Some(spotlight_seed(chrono::Utc::now())), "public, max-age=300")
+    } else {
+        (None, "no-store")
+    };
+    let project = state
+        .repo
+        .get_random_public_project(seed)
+        .await
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "not_found", "There are no public projects."))?;
+    Ok(([(header::CACHE_CONTROL, cache)], Json(project.redact_for_public())))
+}
+
+/// get_featured_projects
+///
+/// [Public Route] Retrieves the most popular projects, for the homepage strip and the "top
+/// projects" page. Only liked projects are featured once there are at least `limit` of them;
+/// until then (a new site, or a quiet year) unliked projects fill the list so it never looks
+/// empty.
+///
+/// Without `year` the ranking covers the latest cohort with public projects only, not all time.
+#[utoipa::path(
+    get,
+    path = "/projects/featured",
+    params(FeaturedFilter),
+    responses(
+        (status = 200, description = "Top projects", body = [Project]),
+        (status = 422, description = "Zero, negative or non-numeric limit", body = ErrorResponse)
+    )
+)]
+pub async fn get_featured_projects(
+    State(state): State<AppState>,
+    Query(filter): Query<FeaturedFilter>,
+) -> Result<Json<Vec<models::Project>>, ApiError> {
+    let limit = parse_limit(filter.limit.as_deref(), DEFAULT_FEATURED_LIMIT, MAX_FEATURED_LIMIT)?;
+    let year = match filter.year {
+        Some(year) => Some(year),
+        // Newest first; None only when nothing is public, and then there is nothing to rank.
+        None => state.repo.get_project_years().await.first().map(|y| y.year),
+    };
+    let mut featured = state.repo.get_top_projects(limit, 1, year).await;
+    if (featured.len() as i64) < limit {
+        tracing::debug!(
+            liked = featured.len(),
+            limit,
+            "Featured projects: too few liked projects, falling back to min_likes=0"
+        );
+        featured = state.repo.get_top_projects(limit, 0, year).await;
+    }
+    Ok(Json(featured.into_iter().map(Project::redact_for_public).collect()))
+}
+
+/// get_admin_projects
+///
+/// [Admin Route] Retrieves ALL projects in the system, regardless of their review `status`.
+///
+/// *Authorization*: Explicitly checks that the `role` resolved by `AuthUser` is "admin".
+#[utoipa::path(
+    get,
+    path = "/admin/projects",
+    params(FieldsFilter),
+    responses(
+        (status = 200, description = "All projects (only the selected fields when `fields` is set)", body = [Project]),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 422, description = "Unknown field in `fields`", body = ErrorResponse)
+    )
+)]
+pub async fn get_admin_projects(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+    Query(filter): Query<FieldsFilter>,
+) -> Result<SparseJson<Vec<models::Project>>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    let fields = sparse::parse_fields(filter.fields.as_deref(), &sparse::project_fields(true))?;
+    Ok(SparseJson(state.repo.get_all_projects().await, fields))
+}
+
+/// Number of queue entries returned by GET /admin/projects/pending when no `limit` is given.
+pub const DEFAULT_PENDING_LIMIT: i64 = 50;
+/// Largest `limit` served by GET /admin/projects/pending; larger requests are capped to it.
+pub const MAX_PENDING_LIMIT: i64 = 200;
+
+/// get_pending_reviews
+///
+/// [Admin Route] The review queue: projects awaiting review (status `pending`), longest
+/// waiting first, each with the submitter's email and `waiting_seconds`.
+///
+/// *Authorization*: Explicitly checks that the `role` resolved by `AuthUser` is "admin".
+#[utoipa::path(
+    get,
+    path = "/admin/projects/pending",
+    params(PendingQueueFilter),
+    responses(
+        (status = 200, description = "Pending projects, longest waiting first", body = [PendingReview]),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 422, description = "Zero, negative or non-numeric limit", body = ErrorResponse)
+    )
+)]
+pub async fn get_pending_reviews(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+    Query(filter): Query<PendingQueueFilter>,
+) -> Result<Json<Vec<PendingReview>>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    let limit = parse_limit(filter.limit.as_deref(), DEFAULT_PENDING_LIMIT, MAX_PENDING_LIMIT)?;
+    Ok(Json(state.repo.get_pending_reviews(limit).await))
+}
+
+/// stream_admin_projects
+///
+/// [Admin Route] Every project as newline-delimited JSON (one `Project` per line), written as
+/// rows arrive from the database instead of being buffered into one array. Meant for exports
+/// too large for `GET /admin/projects`.
+///
+/// If the database fails mid-way the error is logged and the body simply ends after the last
+/// complete line, so clients should treat a short read as incomplete. Keys are snake_case:
+/// the casing middleware only rewrites `application/json` bodies.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
+#[utoipa::path(
+    get,
+    path = "/admin/projects/stream",
+    responses(
+        (status = 200, description = "One project per line", content_type = "application/x-ndjson", body = Project),
+        (status = 403, description = "Not Admin", body = ErrorResponse)
+    )
+)]
+pub async fn stream_admin_projects(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Response, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+
+    let lines = state
+        .repo
+        .stream_all_projects()
+        .take_while(|row| {
+            if let Err(e) = row {
+                tracing::error!("stream_admin_projects: aborting after database error: {}", e);
+            }
+            std::future::ready(row.is_ok())
+        })
+        .filter_map(|row| std::future::ready(row.ok()))
+        .map(|project| {
+            let mut line = serde_json::to_vec(&project)?;
+            line.push(b'\n');
+            Ok::<_, serde_json::Error>(Bytes::from(line))
+        });
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+        .into_response())
 }
 
-/// SupabaseAuthResponse
+/// export_admin_projects_csv
 ///
-/// Minimal struct to deserialize the response from the external Supabase /auth/v1/signup endpoint,
-/// specifically capturing the newly created user's UUID.
-#[derive(Deserialize)]
-struct SupabaseAuthResponse {
-    id: Uuid,
-}
+/// [Admin Route] Every project, whatever its status, as a spreadsheet download: id, title,
+/// author, year, status, like count, creation time and the owner's email. Rows are written as
+/// they arrive from the database, like `stream_admin_projects`, and a database failure ends
+/// the file early after the last complete line. Fields are escaped by `export::escape_field`.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
+#[utoipa::path(
+    get,
+    path = "/admin/projects/export.csv",
+    responses(
+        (status = 200, description = "Header row, then one project per line", content_type = "text/csv"),
+        (status = 403, description = "Not Admin", body = ErrorResponse)
+    )
+)]
+pub async fn export_admin_projects_csv(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Response, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
 
-// --- Handlers ---
+    let filename = format!("projects-{}.csv", state.clock.now().format("%Y-%m-%d"));
+    Ok(export::stream_csv(&filename, state.repo.stream_project_export()))
+}
 
-/// get_my_projects
+/// get_programmes
 ///
-/// [Authenticated Route] Lists all projects owned by the requesting user.
-/// This includes projects that are currently hidden or pending review (`is_public=false`).
+/// [Admin Route] Lists the programme lookup used to tag projects.
 ///
-/// *Note*: The user identity (`id`) is resolved securely via the `AuthUser` extractor.
+/// *Authorization*: Explicitly checks that the `role` is "admin".
 #[utoipa::path(
     get,
-    path = "/me/projects",
-    responses((status = 200, description = "My Projects", body = [Project]))
+    path = "/admin/programmes",
+    responses(
+        (status = 200, description = "All programmes, by code", body = [Programme]),
+        (status = 403, description = "Not Admin", body = ErrorResponse)
+    )
 )]
-pub async fn get_my_projects(
-    AuthUser { id, .. }: AuthUser,
+pub async fn get_programmes(
+    AuthUser { role, .. }: AuthUser,
     State(state): State<AppState>,
-) -> Json<Vec<models::Project>> {
-    let projects = state.repo.get_my_projects(id).await;
-    Json(projects)
+) -> Result<Json<Vec<Programme>>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    Ok(Json(state.repo.get_programmes().await))
 }
 
-/// add_comment
+/// create_programme
+///
+/// [Admin Route] Adds a programme. Codes are upper-cased and must be 2-16 letters, digits
+/// or dashes; the name must not be blank.
 ///
-/// [Authenticated Route] Posts a new comment on a project.
-/// This operation **triggers the PostgreSQL notification trigger** (`handle_new_comment`)
-/// upon successful database insertion.
+/// *Authorization*: Explicitly checks that the `role` is "admin".
 #[utoipa::path(
     post,
-    path = "/projects/{id}/comments",
-    request_body = CreateCommentRequest,
-    responses((status = 201, description = "Comment Added", body = Comment))
+    path = "/admin/programmes",
+    request_body = CreateProgrammeRequest,
+    responses(
+        (status = 201, description = "Created", body = Programme),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 409, description = "Code already exists", body = ErrorResponse),
+        (status = 422, description = "Invalid code or name", body = ErrorResponse)
+    )
 )]
-pub async fn add_comment(
-    AuthUser { id: user_id, .. }: AuthUser,
+pub async fn create_programme(
+    AuthUser { id, role, .. }: AuthUser,
     State(state): State<AppState>,
-    Path(project_id): Path<Uuid>,
-    Json(payload): Json<CreateCommentRequest>,
-) -> Json<models::Comment> {
-    let comment = state
-        .repo
-        .add_comment(project_id, user_id, payload.text)
-        .await;
-    Json(comment)
+    Json(payload): Json<CreateProgrammeRequest>,
+) -> Result<(StatusCode, Json<Programme>), ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+
+    let code = payload.code.trim().to_uppercase();
+    let name = payload.name.trim();
+    let code_ok = (2..=16).contains(&code.len())
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !code_ok || name.is_empty() {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_programme",
+            "Programme codes are 2-16 letters, digits or dashes, and names must not be blank.",
+        ));
+    }
+
+    match state.repo.create_programme(&code, name).await {
+        Some(programme) => {
+            let entry = NewAuditEntry::new(Some(id), "programme.create", "programme", &programme.code)
+                .with_details(serde_json::json!({ "name": programme.name }));
+            audit::record(&*state.repo, entry).await;
+            Ok((StatusCode::CREATED, Json(programme)))
+        }
+        None => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "programme_exists",
+            "A programme with this code already exists.",
+        )
+        .with_details(serde_json::json!({ "code": code }))),
+    }
 }
 
-/// get_comments
+/// get_setting
 ///
-/// [Public Route] Retrieves all comments for a given project ID.
-/// The underlying repository method ensures the project is public before returning comments.
+/// [Admin Route] Reads one runtime setting (e.g. `maintenance_mode`, `flag.new_editor`) from
+/// this instance's snapshot. Keys never set report their default.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
 #[utoipa::path(
     get,
-    path = "/projects/{id}/comments",
-    responses((status = 200, description = "Comments", body = [Comment]))
+    path = "/admin/settings/{key}",
+    params(("key" = String, Path, description = "Setting key")),
+    responses(
+        (status = 200, description = "Current value", body = Setting),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 404, description = "Unknown setting", body = ErrorResponse)
+    )
 )]
-pub async fn get_comments(
+pub async fn get_setting(
+    AuthUser { role, .. }: AuthUser,
     State(state): State<AppState>,
-    Path(project_id): Path<Uuid>,
-) -> Json<Vec<models::Comment>> {
-    let comments = state.repo.get_comments(project_id).await;
-    Json(comments)
+    Path(key): Path<String>,
+) -> Result<Json<Setting>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    state.settings.get(&key).map(Json)
 }
 
-/// delete_project
+/// put_setting
 ///
-/// [Authenticated Route] Allows a user to delete their own project.
+/// [Admin Route] Sets one runtime setting. The value must pass the key's registered
+/// validation (e.g. an RFC 3339 timestamp for `submission_deadline`). The change applies on
+/// this instance immediately and on others at their next settings refresh.
 ///
-/// *Authorization*: The repository method enforces an **Owner-Only** check against the `user_id`
-/// provided by the `AuthUser` extractor. If the user is not the owner, the repository query
-/// will affect 0 rows, resulting in a 404 (or 403, depending on error mapping).
+/// *Authorization*: Explicitly checks that the `role` is "admin".
 #[utoipa::path(
-    delete,
-    path = "/projects/{id}",
+    put,
+    path = "/admin/settings/{key}",
+    params(("key" = String, Path, description = "Setting key")),
+    request_body = UpdateSettingRequest,
     responses(
-        (status = 204, description = "Deleted"), 
-        (status = 403, description = "Not Owner"),
-        (status = 404, description = "Not Found")
+        (status = 200, description = "Stored value", body = Setting),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 404, description = "Unknown setting", body = ErrorResponse),
+        (status = 422, description = "Value not valid for this setting", body = ErrorResponse)
     )
 )]
-pub async fn delete_project(
-    AuthUser { id: user_id, .. }: AuthUser,
+pub async fn put_setting(
+    AuthUser { id, role, .. }: AuthUser,
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-) -> StatusCode {
-    // If the repository returns false, it means either the project didn't exist,
-    // or the user wasn't the owner, hence 404 is a safe default response.
-    if state.repo.delete_project(id, user_id).await {
-        StatusCode::NO_CONTENT
-    } else {
-        StatusCode::NOT_FOUND
+    Path(key): Path<String>,
+    Json(payload): Json<UpdateSettingRequest>,
+) -> Result<Json<Setting>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
     }
+    let setting = state.settings.set(&*state.repo, &key, payload.value, id).await?;
+    let entry = NewAuditEntry::new(Some(id), "setting.put", "setting", &key)
+        .with_details(serde_json::json!({ "value": setting.value }));
+    audit::record(&*state.repo, entry).await;
+    Ok(Json(setting))
 }
 
-/// update_project
+/// lock_comments_for_cohort
 ///
-/// [Authenticated Route] Allows a user to modify their own project details.
+/// [Admin Route] Locks discussion on every project of a cohort year in one statement.
 ///
-/// *Authorization*: Enforces the **Owner-Only** check in the repository layer.
+/// *Authorization*: Explicitly checks that the `role` is "admin".
 #[utoipa::path(
-    put,
-    path = "/projects/{id}",
-    request_body = UpdateProjectRequest,
-    responses((status = 200, description = "Updated", body = Project))
+    post,
+    path = "/admin/projects/lock-comments",
+    params(CohortFilter),
+    responses(
+        (status = 200, description = "Projects locked", body = BulkUpdateResponse),
+        (status = 403, description = "Not Admin")
+    )
 )]
-pub async fn update_project(
-    AuthUser { id: user_id, .. }: AuthUser,
+pub async fn lock_comments_for_cohort(
+    AuthUser { id, role, .. }: AuthUser,
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    Json(payload): Json<UpdateProjectRequest>,
-) -> Result<Json<models::Project>, StatusCode> {
-    match state.repo.update_project(id, user_id, payload).await {
-        Some(project) => Ok(Json(project)),
-        // Returns 404 if the project is not found OR if the authenticated user is not the owner.
-        None => Err(StatusCode::NOT_FOUND),
+    Query(filter): Query<CohortFilter>,
+) -> Result<Json<BulkUpdateResponse>, StatusCode> {
+    if role != "admin" {
+        return Err(StatusCode::FORBIDDEN);
     }
+    let updated = state.repo.lock_comments_for_year(filter.year).await;
+    let entry = NewAuditEntry::new(Some(id), "project.lock_comments", "cohort", filter.year)
+        .with_details(serde_json::json!({ "updated": updated }));
+    audit::record(&*state.repo, entry).await;
+    Ok(Json(BulkUpdateResponse { updated }))
 }
 
-/// get_projects
+/// run_draft_reminders
 ///
-/// [Public Route] Lists public projects with filtering and search capabilities.
+/// [Admin Route] Runs the stale-draft maintenance pass now, or with `?dry_run=true` only
+/// reports which drafts it would remind or flag for cleanup. The background runner performs
+/// the same pass on its own schedule.
 ///
-/// *Security*: The repository method applies the `is_public=true` filter **unconditionally**
-/// to prevent data leakage to anonymous users, ensuring Defense-in-Depth.
+/// *Authorization*: Explicitly checks that the `role` is "admin".
 #[utoipa::path(
-    get,
-    path = "/projects",
-    params(ProjectFilter),
+    post,
+    path = "/admin/maintenance/draft-reminders",
+    params(DryRunFilter),
     responses(
-        (status = 200, description = "List filtered projects", body = [Project])
+        (status = 200, description = "Drafts reminded and flagged (or that would be)", body = DraftReminderReport),
+        (status = 403, description = "Not Admin")
     )
 )]
-pub async fn get_projects(
+pub async fn run_draft_reminders(
+    AuthUser { role, .. }: AuthUser,
     State(state): State<AppState>,
-    Query(filter): Query<ProjectFilter>,
-) -> Json<Vec<models::Project>> {
-    let projects = state.repo.get_projects(filter.year, filter.search).await;
-    Json(projects)
+    Query(filter): Query<DryRunFilter>,
+) -> Result<Json<DraftReminderReport>, StatusCode> {
+    if role != "admin" {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let reminders = DraftReminders::new(state.repo.clone(), &state.config);
+    Ok(Json(reminders.sweep(state.clock.now(), filter.dry_run.unwrap_or(false)).await))
 }
 
-/// get_project_details
+/// get_invalid_years
 ///
-/// [Public Route] Retrieves a single project's details by ID.
-/// Requires an existence and visibility check.
+/// [Admin Route] Lists every project whose year lies outside the configured academic years
+/// (the `academic_years` setting), such as typos from before years were validated.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
 #[utoipa::path(
     get,
-    path = "/projects/{id}",
-    params(("id" = Uuid, Path, description = "Project ID")),
-    responses((status = 200, description = "Found", body = Project))
+    path = "/admin/maintenance/invalid-years",
+    responses(
+        (status = 200, description = "The allowed range and the projects outside it", body = InvalidYearsReport),
+        (status = 403, description = "Not Admin")
+    )
 )]
-pub async fn get_project_details(
+pub async fn get_invalid_years(
+    AuthUser { role, .. }: AuthUser,
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-) -> Result<Json<models::Project>, StatusCode> {
-    match state.repo.get_project(id).await {
-        // If the project is not found OR is not public, it returns None.
-        Some(project) => Ok(Json(project)),
-        None => Err(StatusCode::NOT_FOUND),
+) -> Result<Json<InvalidYearsReport>, StatusCode> {
+    if role != "admin" {
+        return Err(StatusCode::FORBIDDEN);
     }
+    let allowed = state.settings.academic_years();
+    let projects = state.repo.get_projects_outside_years(allowed.min, allowed.max).await;
+    Ok(Json(InvalidYearsReport { allowed, projects }))
 }
 
-/// get_featured_projects
+/// remap_invalid_years
+///
+/// [Admin Route] Moves every project filed under year `from` to year `to`, giving each a slug
+/// for the new year. `to` must be an allowed academic year; `from` may be any other year.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
+#[utoipa::path(
+    post,
+    path = "/admin/maintenance/invalid-years/remap",
+    request_body = YearRemapRequest,
+    responses(
+        (status = 200, description = "Projects moved", body = BulkUpdateResponse),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 422, description = "Target year outside the academic years, or equal to `from`", body = ErrorResponse)
+    )
+)]
+pub async fn remap_invalid_years(
+    AuthUser { id, role, .. }: AuthUser,
+    State(state): State<AppState>,
+    Json(payload): Json<YearRemapRequest>,
+) -> Result<Json<BulkUpdateResponse>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    validate_year(&state, payload.to)?;
+    if payload.from == payload.to {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_remap",
+            "The source and target years are the same.",
+        ));
+    }
+    let updated = state.repo.remap_project_year(payload.from, payload.to).await;
+    let entry = NewAuditEntry::new(Some(id), "project.remap_year", "cohort", payload.from)
+        .with_details(serde_json::json!({ "to": payload.to, "updated": updated }));
+    audit::record(&*state.repo, entry).await;
+    Ok(Json(BulkUpdateResponse { updated }))
+}
+
+/// get_outbox_job
 ///
-/// [Public Route] Retrieves a small list of the most popular projects.
-/// The `limit` (3) is hardcoded in the repository call.
+/// [Admin Route] Reports the progress of a queued background job, such as the fan-out job id
+/// returned with a comment whose notifications were too many to send in the request.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
 #[utoipa::path(
     get,
-    path = "/projects/featured",
-    responses((status = 200, description = "Top projects", body = [Project]))
+    path = "/admin/outbox/{id}",
+    params(("id" = i64, Path, description = "Outbox job ID")),
+    responses(
+        (status = 200, description = "Job status and progress", body = OutboxJob),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 404, description = "No such job", body = ErrorResponse)
+    )
 )]
-pub async fn get_featured_projects(State(state): State<AppState>) -> Json<Vec<models::Project>> {
-    let featured = state.repo.get_top_projects(3).await;
-    Json(featured)
+pub async fn get_outbox_job(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Json<OutboxJob>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    state.repo.get_outbox_job(id).await.map(Json).ok_or_else(|| {
+        ApiError::new(StatusCode::NOT_FOUND, "not_found", "Outbox job not found.")
+    })
 }
 
-/// get_admin_projects
+/// get_supervisor_report
 ///
-/// [Admin Route] Retrieves ALL projects in the system, regardless of their `is_public` status.
+/// [Admin Route] Staff report of project counts, visibility and engagement per supervisor.
+/// Responds with JSON by default, or a CSV attachment for `Accept: text/csv` / `?format=csv`.
 ///
-/// *Authorization*: Explicitly checks that the `role` resolved by `AuthUser` is "admin".
+/// *Authorization*: Explicitly checks that the `role` is "admin".
 #[utoipa::path(
     get,
-    path = "/admin/projects",
-    responses((status = 200, description = "All projects", body = [Project]))
+    path = "/admin/reports/supervisors",
+    params(ReportFilter),
+    responses(
+        (status = 200, description = "Supervisor report", body = [SupervisorStats]),
+        (status = 200, description = "Supervisor report (CSV)", content_type = "text/csv", body = String),
+        (status = 403, description = "Not Admin")
+    )
 )]
-pub async fn get_admin_projects(
+pub async fn get_supervisor_report(
     AuthUser { role, .. }: AuthUser,
     State(state): State<AppState>,
-) -> Result<Json<Vec<models::Project>>, StatusCode> {
+    Query(filter): Query<ReportFilter>,
+    format: ResponseFormat,
+) -> Result<Response, StatusCode> {
     if role != "admin" {
         return Err(StatusCode::FORBIDDEN);
     }
-    Ok(Json(state.repo.get_all_projects().await))
+    let rows = state.repo.get_supervisor_report(filter.year).await;
+    let filename = match filter.year {
+        Some(year) => format!("supervisors-{}.csv", year),
+        None => "supervisors.csv".to_string(),
+    };
+    Ok(export::negotiate(format, &filename, rows))
 }
 
 /// get_me
@@ -253,11 +2442,7 @@ pub async fn get_me(AuthUser { id, role, .. }: AuthUser) -> Json<UserProfile> {
             id.simple().to_string().chars().take(4).collect::<String>()
         ),
         role,
-        // Using a DiceBear API for stable, unique avatar generation based on UUID.
-        avatar_url: Some(format!(
-            "https://api.dicebear.com/7.x/avataaars/svg?seed={}",
-            id
-        )),
+        avatar_url: Some(models::avatar_url(id)),
     })
 }
 
@@ -285,77 +2470,598 @@ pub async fn get_admin_stats(
 ///
 /// [Authenticated Route] Handles the submission of a new project.
 /// The `user_id` is automatically taken from the authenticated session, ensuring data integrity.
+/// Any `programme_codes` must name known programmes, a `department` must be one of the
+/// configured departments, and `year` must be an allowed academic year (a corrected year is
+/// checked the same way by `update_project`).
+///
+/// *Duplicates*: A project with the same title (ignoring case and extra whitespace) and year,
+/// by anyone, refuses the request with 409 `duplicate_project` naming the existing project in
+/// `details.project_id`, since group partners tend to submit the same project twice. `force`
+/// skips the check for genuinely distinct projects that share a title.
+///
+/// *Validation*: The title, author name and cover key are checked first, and every failure
+/// is listed in one 422 `validation_failed` (see `project_field_errors`).
+///
+/// *Limit*: A student may own at most `max_projects_per_user_per_year` projects of one year,
+/// whatever their status; one more is refused with 409 `project_limit`. Admins are exempt.
+///
+/// *Abstract*: At most `MAX_ABSTRACT_CHARS`; see `fit_abstract` for the 422 and the
+/// `?truncate=true` alternative.
 #[utoipa::path(
     post,
     path = "/projects",
+    params(SaveOptions),
     request_body = CreateProjectRequest,
-    responses((status = 200, description = "Created", body = Project))
+    responses(
+        (status = 200, description = "Created", body = CreatedProject),
+        (status = 409, description = "Same title and year as an existing project, or the caller's project limit for the year reached", body = ErrorResponse),
+        (status = 422, description = "Invalid title, author or cover key, year outside the academic years, unknown programme code or department, invalid tags or technologies, or abstract too long", body = ErrorResponse)
+    )
 )]
 pub async fn create_project(
-    AuthUser { id, .. }: AuthUser,
+    AuthUser { id, role, .. }: AuthUser,
     State(state): State<AppState>,
-    Json(payload): Json<models::CreateProjectRequest>,
-) -> Json<models::Project> {
+    Query(options): Query<SaveOptions>,
+    Json(mut payload): Json<models::CreateProjectRequest>,
+) -> Result<Json<CreatedProject>, ApiError> {
+    let errors = project_field_errors(
+        Some(&payload.title),
+        Some(&payload.author_name),
+        Some(&payload.cover_image_key),
+    );
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+    let truncated = fit_abstract(&mut payload.abstract_text, options.truncate)?;
+    validate_year(&state, payload.year)?;
+    validate_programme_codes(&state, &mut payload.programme_codes).await?;
+    validate_department(&state, &mut payload.department)?;
+    normalize_tags(&mut payload.tags)?;
+    normalize_technologies(&mut payload.technologies)?;
+    if role != "admin" {
+        let limit = state.config.max_projects_per_user_per_year;
+        let count = state.repo.count_user_projects_in_year(id, payload.year).await;
+        if count >= limit {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                "project_limit",
+                "You have already created the maximum number of projects for this year. Edit or delete one of them instead.",
+            )
+            .with_details(serde_json::json!({ "year": payload.year, "limit": limit, "count": count })));
+        }
+    }
+    if !payload.force
+        && let Some(existing) = state.repo.find_project_by_title_year(&payload.title, payload.year).await
+    {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "duplicate_project",
+            "A project with this title already exists for this year.",
+        )
+        .with_details(serde_json::json!({ "project_id": existing })));
+    }
     let project = state.repo.create_project(payload, id).await;
-    Json(project)
+    Ok(Json(CreatedProject {
+        truncated,
+        abstract_length: project.abstract_text.chars().count(),
+        project,
+    }))
+}
+
+/// Longest abstract, in characters, a project may store (once trimmed).
+pub const MAX_ABSTRACT_CHARS: usize = 5_000;
+
+/// Longest project title, in characters (once trimmed).
+pub const MAX_TITLE_CHARS: usize = 200;
+
+/// Prefix of every object key handed out by the presigned upload flow (`uploads/{uuid}.{ext}`).
+pub const UPLOAD_KEY_PREFIX: &str = "uploads/";
+
+/// project_field_errors
+///
+/// The per-field checks of a project payload: a title that is not blank and at most
+/// `MAX_TITLE_CHARS` once trimmed, an author name that is not blank, and a cover key from the
+/// upload flow (`UPLOAD_KEY_PREFIX`). Fields an update leaves out (`None`) are not checked.
+/// Returns every failure, for `ApiError::validation`.
+fn project_field_errors(title: Option<&str>, author_name: Option<&str>, cover_image_key: Option<&str>) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if let Some(title) = title.map(str::trim) {
+        if title.is_empty() {
+            errors.push(FieldError::new("title", "required", "The title cannot be blank."));
+        } else if title.chars().count() > MAX_TITLE_CHARS {
+            errors.push(FieldError::new("title", "too_long", "The title is longer than 200 characters."));
+        }
+    }
+    if author_name.is_some_and(|name| name.trim().is_empty()) {
+        errors.push(FieldError::new("author_name", "required", "The author name cannot be blank."));
+    }
+    if cover_image_key.is_some_and(|key| key.strip_prefix(UPLOAD_KEY_PREFIX).is_none_or(str::is_empty)) {
+        errors.push(FieldError::new(
+            "cover_image_key",
+            "invalid_key",
+            "The cover image must be a key returned by POST /upload/presigned.",
+        ));
+    }
+    errors
+}
+
+/// fit_abstract
+///
+/// Enforces `MAX_ABSTRACT_CHARS` on a payload's abstract. An over-long one is rejected with
+/// 422 `abstract_too_long`, whose details give the `length`, the `excess_chars` and where
+/// truncating would cut it (`truncate_at`, in characters). With `truncate` the abstract is
+/// replaced by that cut instead, so a submission at the deadline is kept rather than lost.
+/// Returns whether it was cut.
+fn fit_abstract(text: &mut String, truncate: bool) -> Result<bool, ApiError> {
+    let trimmed = text.trim();
+    let length = trimmed.chars().count();
+    if length <= MAX_ABSTRACT_CHARS {
+        return Ok(false);
+    }
+    let cut = cut_at_word(trimmed, MAX_ABSTRACT_CHARS);
+    if !truncate {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "abstract_too_long",
+            "The abstract is longer than 5000 characters. Shorten it, or resend with ?truncate=true to store it cut at a word boundary.",
+        )
+        .with_details(serde_json::json!({
+            "field": "abstract_text",
+            "max": MAX_ABSTRACT_CHARS,
+            "length": length,
+            "excess_chars": length - MAX_ABSTRACT_CHARS,
+            "truncate_at": cut.chars().count(),
+        })));
+    }
+    *text = cut.to_string();
+    Ok(true)
+}
+
+/// cut_at_word
+///
+/// The longest prefix of `text` with at most `max_chars` characters that ends at a word
+/// boundary, without trailing whitespace; a single word longer than that is cut mid-word.
+/// Counts characters rather than bytes, so multi-byte text is never split inside a character.
+fn cut_at_word(text: &str, max_chars: usize) -> &str {
+    let Some((end, next)) = text.char_indices().nth(max_chars) else {
+        return text;
+    };
+    let prefix = &text[..end];
+    if next.is_whitespace() {
+        return prefix.trim_end();
+    }
+    match prefix.rfind(char::is_whitespace) {
+        Some(space) => prefix[..space].trim_end(),
+        None => prefix,
+    }
+}
+
+/// validate_year
+///
+/// Rejects a year outside the `academic_years` setting with 422 `invalid_year`, naming the
+/// allowed range. Reads the live settings snapshot, so a changed range applies at once.
+fn validate_year(state: &AppState, year: i32) -> Result<(), ApiError> {
+    let allowed = state.settings.academic_years();
+    if allowed.contains(year) {
+        return Ok(());
+    }
+    Err(ApiError::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "invalid_year",
+        "The year is outside the allowed academic years.",
+    )
+    .with_details(serde_json::json!({ "year": year, "min": allowed.min, "max": allowed.max })))
+}
+
+/// validate_department
+///
+/// Checks a payload's department against the `DEPARTMENTS` allowlist, ignoring case, and
+/// stores the configured spelling so the listing filter and `GET /departments` see one value
+/// per department. A blank department counts as absent; anything else unlisted is rejected
+/// with 422 `unknown_department`, naming the allowed values.
+fn validate_department(state: &AppState, department: &mut Option<String>) -> Result<(), ApiError> {
+    let Some(name) = department.as_deref().map(str::trim).filter(|name| !name.is_empty()) else {
+        *department = None;
+        return Ok(());
+    };
+    match state.config.departments.iter().find(|allowed| allowed.eq_ignore_ascii_case(name)) {
+        Some(allowed) => {
+            *department = Some(allowed.clone());
+            Ok(())
+        }
+        None => Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "unknown_department",
+            "The department is not one of the configured departments.",
+        )
+        .with_details(serde_json::json!({ "department": name, "allowed": state.config.departments }))),
+    }
+}
+
+/// validate_programme_codes
+///
+/// Normalizes the programme codes of a project payload in place (trimmed, upper-cased,
+/// de-duplicated) and rejects codes missing from the lookup with 422 `unknown_programme`.
+async fn validate_programme_codes(
+    state: &AppState,
+    codes: &mut Option<Vec<String>>,
+) -> Result<(), ApiError> {
+    let Some(codes) = codes else {
+        return Ok(());
+    };
+    let mut normalized: Vec<String> = Vec::with_capacity(codes.len());
+    for code in codes.iter().map(|c| c.trim().to_uppercase()) {
+        if !normalized.contains(&code) {
+            normalized.push(code);
+        }
+    }
+    *codes = normalized;
+
+    let unknown = if codes.is_empty() {
+        vec![]
+    } else {
+        state.repo.unknown_programme_codes(codes).await
+    };
+    if !unknown.is_empty() {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "unknown_programme",
+            "One or more programme codes do not exist.",
+        )
+        .with_details(serde_json::json!({ "unknown": unknown })));
+    }
+    Ok(())
+}
+
+/// Most tags a single project may carry.
+pub const MAX_PROJECT_TAGS: usize = 10;
+/// Longest tag, in characters (matches the `project_tags.tag` CHECK constraint).
+pub const MAX_TAG_CHARS: usize = 30;
+
+/// normalize_tags
+///
+/// Normalizes a project payload's tags in place (trimmed, lower-cased, de-duplicated) and
+/// rejects empty or over-long tags, or more than `MAX_PROJECT_TAGS`, with 422 `invalid_tags`.
+fn normalize_tags(tags: &mut Vec<String>) -> Result<(), ApiError> {
+    let invalid = |message: &str, details: serde_json::Value| {
+        ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_tags", message).with_details(details)
+    };
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags.iter().map(|t| t.trim().to_lowercase()) {
+        if tag.is_empty() || tag.chars().count() > MAX_TAG_CHARS {
+            return Err(invalid(
+                "Tags must be between 1 and 30 characters.",
+                serde_json::json!({ "tag": tag, "max_chars": MAX_TAG_CHARS }),
+            ));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    if normalized.len() > MAX_PROJECT_TAGS {
+        return Err(invalid(
+            "A project can have at most 10 tags.",
+            serde_json::json!({ "count": normalized.len(), "limit": MAX_PROJECT_TAGS }),
+        ));
+    }
+    *tags = normalized;
+    Ok(())
+}
+
+/// Most technologies a single project may list.
+pub const MAX_PROJECT_TECHNOLOGIES: usize = 15;
+/// Longest technology name, in characters.
+pub const MAX_TECHNOLOGY_CHARS: usize = 40;
+
+/// normalize_technologies
+///
+/// Normalizes a project payload's technologies in place (trimmed, lower-cased, de-duplicated
+/// and sorted) and rejects empty or over-long names, or more than `MAX_PROJECT_TECHNOLOGIES`,
+/// with 422 `invalid_technologies`.
+fn normalize_technologies(technologies: &mut Vec<String>) -> Result<(), ApiError> {
+    let invalid = |message: &str, details: serde_json::Value| {
+        ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_technologies", message).with_details(details)
+    };
+    let mut normalized: Vec<String> = Vec::with_capacity(technologies.len());
+    for technology in technologies.iter().map(|t| t.trim().to_lowercase()) {
+        if technology.is_empty() || technology.chars().count() > MAX_TECHNOLOGY_CHARS {
+            return Err(invalid(
+                "Technologies must be between 1 and 40 characters.",
+                serde_json::json!({ "technology": technology, "max_chars": MAX_TECHNOLOGY_CHARS }),
+            ));
+        }
+        normalized.push(technology);
+    }
+    normalized.sort();
+    normalized.dedup();
+    if normalized.len() > MAX_PROJECT_TECHNOLOGIES {
+        return Err(invalid(
+            "A project can list at most 15 technologies.",
+            serde_json::json!({ "count": normalized.len(), "limit": MAX_PROJECT_TECHNOLOGIES }),
+        ));
+    }
+    *technologies = normalized;
+    Ok(())
+}
+
+/// vote_project
+///
+/// [Authenticated Route] Records a 'like' from the user for a project.
+///
+/// *Idempotency*: The repository method uses the composite primary key on `project_likes`
+/// to enforce the **one-vote-per-user-per-project** rule. A repeated vote is a 409 Conflict
+/// with the same body as a new one, `already_liked: true`, so either way the client learns
+/// the current like state without a second request.
+///
+/// *Discussion Lock*: Likes are reactions, so they are refused with 403 `comments_locked` too.
+///
+/// *Own Project*: Self-likes would inflate the featured ranking, so the owner (admins too) is
+/// refused with 403 `own_project`. The repository enforces this, not just this handler.
+///
+/// *Visibility*: Only public projects can be liked. Hidden, pending and draft projects are
+/// 404, even for their owner, so likes never accrue before approval.
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/vote",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Voted", body = VoteResponse),
+        (status = 403, description = "Discussion Locked, or the caller owns the project", body = ErrorResponse),
+        (status = 404, description = "Not Found or Private", body = ErrorResponse),
+        (status = 409, description = "Already voted; current like state", body = VoteResponse)
+    )
+)]
+pub async fn vote_project(
+    AuthUser { id, .. }: AuthUser,
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<VoteResponse>), ApiError> {
+    if state.repo.are_comments_locked(project_id).await {
+        return Err(comments_locked_error());
+    }
+
+    let like = models::Like {
+        user_id: id,
+        project_id,
+    };
+
+    match state.repo.like_project(like).await {
+        Some(outcome @ LikeOutcome::Created(_)) => Ok((StatusCode::OK, Json(outcome.into()))),
+        Some(outcome @ LikeOutcome::AlreadyExists(_)) => Ok((StatusCode::CONFLICT, Json(outcome.into()))),
+        Some(LikeOutcome::OwnProject(_)) => Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "own_project",
+            "You cannot like your own project.",
+        )),
+        None => Err(ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found.")),
+    }
 }
 
-/// vote_project
+/// unvote_project
 ///
-/// [Authenticated Route] Records a 'like' from the user for a project.
+/// [Authenticated Route] Takes back the user's 'like' on a project (the frontend's heart
+/// toggle). The owner's "like" notification for it is removed with it.
 ///
-/// *Idempotency*: The repository method uses the composite primary key on `project_likes`
-/// to enforce the **one-vote-per-user-per-project** rule, returning a 409 Conflict if violated.
+/// Returns 204 when a like was removed and 404 when the user had not liked the project (or it
+/// does not exist). Unlike voting, this is allowed on a locked discussion.
 #[utoipa::path(
-    post,
+    delete,
     path = "/projects/{id}/vote",
     params(("id" = Uuid, Path, description = "Project ID")),
     responses(
-        (status = 200, description = "Voted"),
-        (status = 409, description = "Duplicate")
+        (status = 204, description = "Like removed"),
+        (status = 404, description = "Not liked")
     )
 )]
-pub async fn vote_project(
+pub async fn unvote_project(
     AuthUser { id, .. }: AuthUser,
     State(state): State<AppState>,
     Path(project_id): Path<Uuid>,
-) -> Result<StatusCode, StatusCode> {
+) -> StatusCode {
     let like = models::Like {
         user_id: id,
         project_id,
     };
-
-    match state.repo.like_project(like).await {
-        true => Ok(StatusCode::OK),
-        false => Err(StatusCode::CONFLICT),
+    if state.repo.unlike_project(like).await {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
     }
 }
 
 /// update_project_status
 ///
-/// [Admin Route] Endpoint for an administrator to publish or hide a project.
+/// [Admin Route] Endpoint for an administrator to move a project through review: approving
+/// publishes it, any other status hides it.
 ///
 /// *RBAC*: Strict enforcement of the "admin" role before calling the repository.
+///
+/// *Review*: Every change is recorded in `project_reviews` with the admin and the optional
+/// `reason`; the owner sees the latest one on GET /me/projects.
+///
+/// *Notification*: When a reason is given, or with `notify_owner`, the owner receives a
+/// `status_change` notification carrying the reason. A bare boolean body is still accepted
+/// (deprecated).
 #[utoipa::path(
     put,
-    path = "/projects/{id}/status",
+    path = "/admin/projects/{id}/status",
     params(("id" = Uuid, Path, description = "Project ID")),
-    request_body = bool,
-    responses((status = 200, description = "Updated", body = Project))
+    request_body = UpdateStatusRequest,
+    responses(
+        (status = 200, description = "Updated", body = Project),
+        (status = 403, description = "Not Admin"),
+        (status = 404, description = "Not Found")
+    )
 )]
 pub async fn update_project_status(
-    AuthUser { role, id: _user_id }: AuthUser,
+    AuthUser { role, id: admin_id }: AuthUser,
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(is_public): Json<bool>,
+    StatusUpdate(payload): StatusUpdate,
 ) -> Result<Json<models::Project>, StatusCode> {
     if role != "admin" {
         return Err(StatusCode::FORBIDDEN);
     }
-    match state.repo.set_project_status(id, is_public).await {
-        Some(project) => Ok(Json(project)),
-        None => Err(StatusCode::NOT_FOUND),
+    let reason = payload
+        .reason
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    let project = state
+        .repo
+        .set_project_status(id, payload.status)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    tracing::info!(
+        project_id = %id,
+        status = payload.status.as_str(),
+        reason = reason.as_deref().unwrap_or(""),
+        "project status changed"
+    );
+
+    state
+        .repo
+        .record_project_review(id, admin_id, payload.status, reason.clone())
+        .await;
+    let entry = NewAuditEntry::new(Some(admin_id), "project.status", "project", id)
+        .with_details(serde_json::json!({ "status": payload.status, "reason": reason }));
+    audit::record(&*state.repo, entry).await;
+
+    if payload.notify_owner || reason.is_some() {
+        state
+            .repo
+            .notify_status_change(id, admin_id, reason)
+            .await;
+    }
+
+    Ok(Json(project))
+}
+
+/// Most projects a single bulk status change may name.
+pub const BULK_STATUS_MAX_IDS: usize = 200;
+
+/// bulk_update_project_status
+///
+/// [Admin Route] Publishes (`is_public: true`, status `approved`) or hides (`rejected`) many
+/// projects in one statement, e.g. the whole review queue on showcase day. Repeated ids count
+/// once; unknown ids are reported back rather than failing the batch.
+///
+/// *RBAC*: Strict enforcement of the "admin" role before calling the repository.
+///
+/// *Review*: Each project whose status changed gets a review without a reason, as on the
+/// single-project endpoint; owners are not notified.
+#[utoipa::path(
+    post,
+    path = "/admin/projects/bulk_status",
+    request_body = BulkStatusRequest,
+    responses(
+        (status = 200, description = "Projects changed and ids not found", body = BulkStatusResponse),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 422, description = "Too many ids", body = ErrorResponse)
+    )
+)]
+pub async fn bulk_update_project_status(
+    AuthUser { role, id: admin_id }: AuthUser,
+    State(state): State<AppState>,
+    Json(payload): Json<BulkStatusRequest>,
+) -> Result<Json<BulkStatusResponse>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    let mut seen = std::collections::HashSet::new();
+    let ids: Vec<Uuid> = payload.ids.into_iter().filter(|id| seen.insert(*id)).collect();
+    if ids.len() > BULK_STATUS_MAX_IDS {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "too_many_ids",
+            "At most 200 projects may be updated at once.",
+        )
+        .with_details(serde_json::json!({ "count": ids.len(), "limit": BULK_STATUS_MAX_IDS })));
+    }
+
+    let outcome = state.repo.set_projects_status_bulk(&ids, payload.is_public).await;
+    let status = models::ProjectStatus::from_visibility(payload.is_public);
+    tracing::info!(
+        status = status.as_str(),
+        requested = ids.len(),
+        changed = outcome.changed.len(),
+        not_found = outcome.not_found.len(),
+        "bulk project status change"
+    );
+    for &id in &outcome.changed {
+        state.repo.record_project_review(id, admin_id, status, None).await;
+        let entry = NewAuditEntry::new(Some(admin_id), "project.status", "project", id)
+            .with_details(serde_json::json!({ "status": status, "bulk": true }));
+        audit::record(&*state.repo, entry).await;
+    }
+
+    Ok(Json(BulkStatusResponse {
+        updated: outcome.changed.len() as i64,
+        not_found: outcome.not_found,
+    }))
+}
+
+/// reassign_project_owner
+///
+/// [Admin Route] Moves a project to another account, e.g. when a group's project was
+/// submitted by the wrong member. Reassigning to the current owner changes nothing.
+///
+/// *RBAC*: Strict enforcement of the "admin" role before calling the repository.
+///
+/// *Notification*: The previous and the new owner each receive an `ownership_change`
+/// notification; the transfer is recorded in the audit log.
+#[utoipa::path(
+    put,
+    path = "/admin/projects/{id}/owner",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = ReassignOwnerRequest,
+    responses(
+        (status = 200, description = "Reassigned", body = Project),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 404, description = "Project or target user not found", body = ErrorResponse)
+    )
+)]
+pub async fn reassign_project_owner(
+    AuthUser { role, id: admin_id }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ReassignOwnerRequest>,
+) -> Result<Json<models::Project>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
     }
+    let outcome = state
+        .repo
+        .reassign_project_owner(id, payload.new_owner_id, admin_id)
+        .await
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "not_found", "Project not found."))?;
+    let (project, previous_owner) = match outcome {
+        models::OwnerTransferOutcome::Transferred { project, previous_owner } => (*project, previous_owner),
+        models::OwnerTransferOutcome::UnknownOwner => {
+            return Err(ApiError::new(StatusCode::NOT_FOUND, "not_found", "User not found.")
+                .with_details(serde_json::json!({ "field": "new_owner_id" })));
+        }
+    };
+
+    tracing::info!(
+        project_id = %id,
+        from = %previous_owner,
+        to = %payload.new_owner_id,
+        "project owner reassigned"
+    );
+    let entry = NewAuditEntry::new(Some(admin_id), "project.owner", "project", id)
+        .with_details(serde_json::json!({ "from": previous_owner, "to": payload.new_owner_id }));
+    audit::record(&*state.repo, entry).await;
+
+    Ok(Json(project))
 }
 
 /// register_user
@@ -365,48 +3071,31 @@ pub async fn update_project_status(
 /// *Flow*: Calls Supabase's signup endpoint, retrieves the `auth.users.id` (UUID), and then
 /// uses that ID to create the corresponding record in the application's local `public.profiles` table.
 /// This ensures primary key synchronization between the external Auth system and our local schema.
+///
+/// The call goes through the shared, time-capped client in `AppState`; a provider that does
+/// not answer within `AUTH_PROVIDER_TIMEOUT_MS` yields 504 rather than hanging the request.
 #[utoipa::path(
     post,
     path = "/register",
     request_body = RegisterUserRequest,
-    responses((status = 200, description = "Registered", body = User))
+    responses(
+        (status = 200, description = "Registered", body = User),
+        (status = 400, description = "Rejected by the auth provider", body = ErrorResponse),
+        (status = 504, description = "Auth provider timed out", body = ErrorResponse)
+    )
 )]
 pub async fn register_user(
     State(state): State<AppState>,
     Json(payload): Json<RegisterUserRequest>,
-) -> Result<Json<User>, StatusCode> {
-    let supabase_url =
-        std::env::var("SUPABASE_URL").map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let supabase_key =
-        std::env::var("SUPABASE_KEY").map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    // Step 1: Call external Auth provider (Supabase)
-    let client = reqwest::Client::new();
-    let auth_url = format!("{}/auth/v1/signup", supabase_url);
-
-    let response = client
-        .post(auth_url)
-        .header("apikey", supabase_key)
-        .header("Content-Type", "application/json")
-        .json(&serde_json::json!({ "email": payload.email, "password": payload.password }))
-        .send()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    if !response.status().is_success() {
-        // If Supabase rejects the user (e.g., email already exists, weak password).
-        return Err(StatusCode::BAD_REQUEST);
-    }
-
-    // Step 2: Extract the canonical user ID from the external response.
-    let supabase_user = response
-        .json::<SupabaseAuthResponse>()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+) -> Result<Json<User>, ApiError> {
+    // Step 1: Call external Auth provider (Supabase) for the canonical user ID.
+    let user_id =
+        auth_provider::signup(&state.http, &state.config, &payload.email, &payload.password)
+            .await?;
 
-    // Step 3: Create the mirrored profile in our local database (`public.profiles`).
+    // Step 2: Create the mirrored profile in our local database (`public.profiles`).
     let new_user = User {
-        id: supabase_user.id,
+        id: user_id,
         email: payload.email,
         role: payload.role,
     };
@@ -416,6 +3105,159 @@ pub async fn register_user(
     Ok(Json(created_user))
 }
 
+/// get_metrics
+///
+/// [Public Route] Process counters in the Prometheus text format, for scraping.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses((status = 200, description = "Prometheus metrics", body = String, content_type = "text/plain"))
+)]
+pub async fn get_metrics() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], metrics::render())
+}
+
+/// get_deep_health
+///
+/// [Public Route] Readiness detail for monitoring: pings the database and reports every
+/// supervised background task. `amber` while any task is stale (no successful pass for
+/// `supervisor::STALE_AFTER_INTERVALS` intervals, e.g. a stuck outbox dispatcher); `red`, with
+/// a 503, when the database does not answer. Error messages stay in the logs.
+#[utoipa::path(
+    get,
+    path = "/health/deep",
+    responses(
+        (status = 200, description = "Green or amber", body = DeepHealth),
+        (status = 503, description = "Database unreachable", body = DeepHealth)
+    )
+)]
+pub async fn get_deep_health(State(state): State<AppState>) -> (StatusCode, Json<DeepHealth>) {
+    let database = match state.repo.ping().await {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!(error = %e, "Deep health check: database unreachable");
+            false
+        }
+    };
+    let tasks = state.tasks.report();
+    let status = if !database {
+        HealthStatus::Red
+    } else if tasks.iter().any(|t| t.stale) {
+        HealthStatus::Amber
+    } else {
+        HealthStatus::Green
+    };
+    let code = match status {
+        HealthStatus::Red => StatusCode::SERVICE_UNAVAILABLE,
+        HealthStatus::Green | HealthStatus::Amber => StatusCode::OK,
+    };
+    (code, Json(DeepHealth { status, database, tasks }))
+}
+
+/// get_admin_audit
+///
+/// [Admin Route] The audit log, newest first, filtered by actor, action, target type and a
+/// `from`/`to` time range. JSON responses are paginated; a CSV download (`Accept: text/csv` or
+/// `?format=csv`) streams every matching entry.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
+#[utoipa::path(
+    get,
+    path = "/admin/audit",
+    params(AuditQuery),
+    responses(
+        (status = 200, description = "A page of audit entries", body = Paginated<AuditEntry>),
+        (status = 200, description = "Every matching audit entry (CSV)", content_type = "text/csv", body = String),
+        (status = 400, description = "`actor_id`, `from` or `to` is malformed"),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 422, description = "`from` is not before `to`, or invalid `page`/`per_page`", body = ErrorResponse)
+    )
+)]
+pub async fn get_admin_audit(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<AuditQuery>,
+    format: ResponseFormat,
+) -> Result<Response, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(StatusCode::FORBIDDEN, "forbidden", "Admin role required."));
+    }
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from >= to
+    {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_range",
+            "`from` must be before `to`.",
+        )
+        .with_details(serde_json::json!({ "field": "to" })));
+    }
+    let page = PageRequest::parse(query.page.as_deref(), query.per_page.as_deref(), None)?;
+    let given = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let filter = AuditFilter {
+        actor_id: query.actor_id,
+        action: given(query.action),
+        target_type: given(query.target_type),
+        from: query.from,
+        to: query.to,
+    };
+
+    if format == ResponseFormat::Csv {
+        let filename = format!("audit-{}.csv", state.clock.now().format("%Y-%m-%d"));
+        return Ok(export::stream_csv(&filename, state.repo.stream_audit_log(filter)));
+    }
+    let (items, total_count) = state.repo.get_audit_log(&filter, page).await;
+    let links = UrlBuilder::new(&state.config).page_links(&uri, page.page, page.per_page, total_count);
+    Ok(Json(Paginated {
+        items,
+        total_count,
+        page: page.page,
+        per_page: page.per_page,
+        links,
+        next_cursor: None,
+    })
+    .into_response())
+}
+
+/// Recent samples returned by GET /admin/errors when `samples` is omitted.
+pub const ERROR_SAMPLES_DEFAULT: usize = 20;
+/// Upper bound on `samples` for GET /admin/errors.
+pub const ERROR_SAMPLES_MAX: usize = 100;
+
+/// get_admin_errors
+///
+/// [Admin Route] The error budget of this instance: 5xx and 429 responses within the last
+/// `window` (15m, 1h or 24h; default 1h), counted per route and error code, with the newest
+/// `samples` of them (default 20, at most 100). Read from the bounded in-process log, so it
+/// covers at most the last `error_log::ERROR_LOG_CAPACITY` errors since the process started.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
+#[utoipa::path(
+    get,
+    path = "/admin/errors",
+    params(ErrorSummaryQuery),
+    responses(
+        (status = 200, description = "Error counts and recent samples", body = ErrorSummary),
+        (status = 403, description = "Not Admin", body = ErrorResponse)
+    )
+)]
+pub async fn get_admin_errors(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<ErrorSummaryQuery>,
+) -> Result<Json<ErrorSummary>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    let samples = query.samples.unwrap_or(ERROR_SAMPLES_DEFAULT).min(ERROR_SAMPLES_MAX);
+    Ok(Json(state.errors.summary(query.window.unwrap_or_default(), samples)))
+}
+
 /// get_presigned_url
 ///
 /// [Authenticated Route] Generates a temporary, secure URL for direct client-to-cloud upload.
@@ -423,24 +3265,63 @@ pub async fn register_user(
 /// *Security*: The URL is short-lived (10 minutes max), constrained to the specified `file_type`,
 /// and uses a unique, cryptographically secure object key (UUID). This implements the **Media Pipeline**
 /// feature by offloading heavy media uploads from the application server.
+///
+/// *Abuse Guard*: Every issued URL is recorded. Non-admins are limited to `max_presigns_per_hour`
+/// (429 `presign_rate_limited`) and to `max_pending_uploads` unconfirmed uploads per purpose
+/// (409 `too_many_pending_uploads`); an upload is confirmed once a project references its key.
 #[utoipa::path(
     post,
     path = "/upload/presigned",
     request_body = PresignedUrlRequest,
-    responses((status = 200, description = "URL", body = PresignedUrlResponse))
+    responses(
+        (status = 200, description = "URL", body = PresignedUrlResponse),
+        (status = 409, description = "Too many pending uploads for this purpose", body = ErrorResponse),
+        (status = 429, description = "Hourly presign limit reached", body = ErrorResponse)
+    )
 )]
 pub async fn get_presigned_url(
-    AuthUser { id: _user_id, .. }: AuthUser,
+    AuthUser { id: user_id, role }: AuthUser,
     State(state): State<AppState>,
     Json(payload): Json<PresignedUrlRequest>,
-) -> impl IntoResponse {
+) -> Result<Json<PresignedUrlResponse>, ApiError> {
+    let purpose = payload
+        .purpose
+        .unwrap_or_else(|| UploadPurpose::infer(&payload.file_type));
+
+    if role != "admin" {
+        let counts = state.repo.get_upload_counts(user_id, purpose).await;
+        if counts.last_hour >= state.config.max_presigns_per_hour {
+            return Err(ApiError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "presign_rate_limited",
+                "Too many upload URLs requested. Please try again later.",
+            )
+            .with_details(serde_json::json!({
+                "count": counts.last_hour,
+                "limit": state.config.max_presigns_per_hour,
+            })));
+        }
+        if counts.outstanding >= state.config.max_pending_uploads {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                "too_many_pending_uploads",
+                "Finish or abandon a pending upload before starting another.",
+            )
+            .with_details(serde_json::json!({
+                "purpose": purpose,
+                "outstanding": counts.outstanding,
+                "limit": state.config.max_pending_uploads,
+            })));
+        }
+    }
+
     // Generate a unique, structured object key (e.g., 'uploads/UUID.ext').
     let extension = std::path::Path::new(&payload.filename)
         .extension()
         .and_then(std::ffi::OsStr::to_str)
         .unwrap_or("bin");
     let unique_id = Uuid::new_v4();
-    let object_key = format!("uploads/{}.{}", unique_id, extension);
+    let object_key = format!("{UPLOAD_KEY_PREFIX}{unique_id}.{extension}");
 
     match state
         .storage
@@ -449,32 +3330,103 @@ pub async fn get_presigned_url(
         .await
     {
         Ok(url) => {
-            let response = PresignedUrlResponse {
-                upload_url: url,
+            let expires_at = chrono::Utc::now()
+                + chrono::Duration::from_std(PRESIGN_TTL).unwrap_or_default();
+            // Untracked URLs would bypass the guard above, so a tracking failure fails the request.
+            if !state
+                .repo
+                .record_upload(user_id, &object_key, purpose, expires_at)
+                .await
+            {
+                return Err(ApiError::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "upload_tracking_failed",
+                    "Failed",
+                ));
+            }
+            Ok(Json(PresignedUrlResponse {
+                upload_url: url.into_inner(),
                 resource_key: object_key,
-            };
-            (StatusCode::OK, Json(response)).into_response()
+            }))
         }
         Err(e) => {
             // Log the underlying storage error for debugging but return a generic internal error.
-            eprintln!("Storage Error: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed").into_response()
+            // SDK messages can quote the signed request URL, so query strings are stripped first.
+            tracing::error!("Storage Error: {}", redact::redact_urls(&e));
+            Err(ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "storage_error",
+                "Failed",
+            ))
         }
     }
 }
 
+/// update_comment
+///
+/// [Authenticated Route] Replaces the text of the caller's own comment, keeping its place in
+/// the thread, and marks it as edited.
+///
+/// *Ownership*: Enforced by the repository for everyone. Unlike `delete_comment` there is no
+/// admin tier: moderators remove comments, they do not put words in other people's mouths.
+///
+/// *Validation*: The new text follows the rules of `add_comment` (see `normalize_comment`).
+///
+/// *Limits*: Like posting, an edit is refused while the discussion is locked and counts towards
+/// the comment rate limit (admins exempt). A comment hidden pending review cannot be edited, so
+/// moderators decide on the text that was reported.
+#[utoipa::path(
+    put,
+    path = "/comments/{id}",
+    params(("id" = i64, Path, description = "Comment ID (1 to 2^53 - 1)")),
+    request_body = UpdateCommentRequest,
+    responses(
+        (status = 200, description = "Updated", body = Comment),
+        (status = 400, description = "Invalid Comment ID"),
+        (status = 403, description = "Discussion is locked", body = ErrorResponse),
+        (status = 404, description = "Not Found or Not Yours", body = ErrorResponse),
+        (status = 409, description = "Hidden pending moderator review", body = ErrorResponse),
+        (status = 422, description = "Blank or longer than 2000 characters", body = ErrorResponse),
+        (status = 429, description = "Comment rate limit reached; see `Retry-After`", body = ErrorResponse)
+    )
+)]
+pub async fn update_comment(
+    AuthUser { id: user_id, role }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<CommentId>,
+    Json(payload): Json<UpdateCommentRequest>,
+) -> Result<Json<Comment>, ApiError> {
+    let text = normalize_comment(&payload.text)?;
+    if role != "admin" {
+        check_comment_rate(&state, user_id).await?;
+    }
+    match state.repo.update_comment(id.get(), user_id, UpdateCommentRequest { text }).await {
+        Some(CommentEditOutcome::Edited(comment)) => Ok(Json(*comment)),
+        Some(CommentEditOutcome::Locked) => Err(comments_locked_error()),
+        Some(CommentEditOutcome::HiddenPendingReview) => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "comment_under_review",
+            "This comment is hidden pending moderator review and cannot be edited.",
+        )),
+        None => Err(ApiError::new(StatusCode::NOT_FOUND, "not_found", "Comment not found.")),
+    }
+}
+
 /// delete_comment
 ///
 /// [Authenticated Route] Deletes a comment, implementing two tiers of authorization.
+/// The delete is soft: the thread keeps a "[deleted]" placeholder while later comments
+/// follow it (see `Repository::get_comments`), and an already deleted comment is a 404.
 ///
 /// *RBAC/Ownership*: Checks for the "admin" role first (Force Delete), otherwise
 /// checks for comment ownership (Owner Delete).
 #[utoipa::path(
     delete,
     path = "/comments/{id}",
-    params(("id" = i64, Path, description = "Comment ID")),
+    params(("id" = i64, Path, description = "Comment ID (1 to 2^53 - 1)")),
     responses(
         (status = 204, description = "Deleted"),
+        (status = 400, description = "Invalid Comment ID"),
         (status = 404, description = "Not Found")
     )
 )]
@@ -483,8 +3435,9 @@ pub async fn delete_comment(
         id: user_id, role, ..
     }: AuthUser,
     State(state): State<AppState>,
-    Path(id): Path<i64>,
+    Path(id): Path<CommentId>,
 ) -> StatusCode {
+    let id = id.get();
     if role == "admin" {
         // Admin Force Delete: Ignores ownership checks.
         if state.repo.delete_comment_admin(id).await {
@@ -500,6 +3453,237 @@ pub async fn delete_comment(
     StatusCode::NOT_FOUND
 }
 
+/// delete_project_comment
+///
+/// [Authenticated Route] Deletes a comment addressed through its project. Same two tiers of
+/// authorization as `delete_comment`, plus the comment must belong to the project in the URL;
+/// a mismatch is reported as 404 so ids cannot be probed across projects.
+#[utoipa::path(
+    delete,
+    path = "/projects/{id}/comments/{comment_id}",
+    params(
+        ("id" = Uuid, Path, description = "Project ID"),
+        ("comment_id" = i64, Path, description = "Comment ID (1 to 2^53 - 1)")
+    ),
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 400, description = "Invalid Comment ID"),
+        (status = 404, description = "Not Found")
+    )
+)]
+pub async fn delete_project_comment(
+    AuthUser {
+        id: user_id, role, ..
+    }: AuthUser,
+    State(state): State<AppState>,
+    Path((project_id, comment_id)): Path<(Uuid, CommentId)>,
+) -> StatusCode {
+    // Admins skip the ownership check but never the project scope.
+    let owner = (role != "admin").then_some(user_id);
+    if state
+        .repo
+        .delete_project_comment(project_id, comment_id.get(), owner)
+        .await
+    {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+/// report_comment
+///
+/// [Authenticated Route] Reports a comment for moderation. Once the comment has reports from
+/// `auto_hide_report_threshold` distinct users it is hidden from public listings and admins
+/// are notified. Repeat reports from the same user are accepted but not counted twice.
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/comments/{comment_id}/reports",
+    params(
+        ("id" = Uuid, Path, description = "Project ID"),
+        ("comment_id" = i64, Path, description = "Comment ID (1 to 2^53 - 1)")
+    ),
+    request_body = CreateReportRequest,
+    responses(
+        (status = 204, description = "Reported"),
+        (status = 400, description = "Invalid Comment ID"),
+        (status = 404, description = "Not Found")
+    )
+)]
+pub async fn report_comment(
+    AuthUser { id: user_id, .. }: AuthUser,
+    State(state): State<AppState>,
+    Path((project_id, comment_id)): Path<(Uuid, CommentId)>,
+    Json(payload): Json<CreateReportRequest>,
+) -> StatusCode {
+    submit_report(&state, Some(project_id), comment_id, user_id, payload).await
+}
+
+/// report_comment_by_id
+///
+/// [Authenticated Route] `report_comment` for clients that only hold the comment id, such as
+/// a notification or the moderation queue. Same rules; 404 unless the comment is on a public
+/// project.
+#[utoipa::path(
+    post,
+    path = "/comments/{id}/report",
+    params(("id" = i64, Path, description = "Comment ID (1 to 2^53 - 1)")),
+    request_body = CreateReportRequest,
+    responses(
+        (status = 204, description = "Reported"),
+        (status = 400, description = "Invalid Comment ID"),
+        (status = 404, description = "Not Found")
+    )
+)]
+pub async fn report_comment_by_id(
+    AuthUser { id: user_id, .. }: AuthUser,
+    State(state): State<AppState>,
+    Path(comment_id): Path<CommentId>,
+    Json(payload): Json<CreateReportRequest>,
+) -> StatusCode {
+    submit_report(&state, None, comment_id, user_id, payload).await
+}
+
+/// The body of both report routes; `project_id` is the project scope of the nested one.
+async fn submit_report(
+    state: &AppState,
+    project_id: Option<Uuid>,
+    comment_id: CommentId,
+    user_id: Uuid,
+    payload: CreateReportRequest,
+) -> StatusCode {
+    let reason = payload
+        .reason
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    match state
+        .repo
+        .create_report(
+            project_id,
+            comment_id.get(),
+            user_id,
+            reason,
+            state.config.auto_hide_report_threshold,
+        )
+        .await
+    {
+        Some(outcome) => {
+            if outcome.hidden {
+                tracing::info!(
+                    comment_id = comment_id.get(),
+                    reports = outcome.pending_reports,
+                    "comment hidden pending review"
+                );
+            }
+            // The reporter is not told whether the comment was hidden.
+            StatusCode::NO_CONTENT
+        }
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+/// restore_comment
+///
+/// [Admin Route] Unhides a comment that was auto-hidden by reports and resolves its pending
+/// reports. Restoring a visible comment just resolves any reports against it.
+#[utoipa::path(
+    patch,
+    path = "/admin/comments/{id}/restore",
+    params(("id" = i64, Path, description = "Comment ID (1 to 2^53 - 1)")),
+    responses(
+        (status = 204, description = "Restored"),
+        (status = 400, description = "Invalid Comment ID"),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found")
+    )
+)]
+pub async fn restore_comment(
+    AuthUser { id: admin_id, role, .. }: AuthUser,
+    State(state): State<AppState>,
+    Path(id): Path<CommentId>,
+) -> Result<StatusCode, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(StatusCode::FORBIDDEN, "forbidden", "Admin role required."));
+    }
+    if state.repo.restore_comment(id.get()).await {
+        let entry = NewAuditEntry::new(Some(admin_id), "comment.restore", "comment", id.get());
+        audit::record(&*state.repo, entry).await;
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::new(StatusCode::NOT_FOUND, "not_found", "Comment not found."))
+    }
+}
+
+/// get_reported_comments
+///
+/// [Admin Route] The moderation queue: every comment with pending reports, most reported
+/// first, with the reasons given. Restoring (PATCH /admin/comments/{id}/restore) dismisses the
+/// reports and deleting the comment removes them; either way it leaves the queue.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
+#[utoipa::path(
+    get,
+    path = "/admin/comments/reported",
+    responses(
+        (status = 200, description = "Reported comments, most reported first", body = [ReportedComment]),
+        (status = 403, description = "Not Admin", body = ErrorResponse)
+    )
+)]
+pub async fn get_reported_comments(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ReportedComment>>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    Ok(Json(state.repo.get_reported_comments().await))
+}
+
+/// get_admin_comments
+///
+/// [Admin Route] Every comment in the system, hidden ones included, newest first with the
+/// project title and author email, so moderators need not open each project. Narrowed by
+/// `project_id`, `user_id` and a case-insensitive `search` of the text; paged with
+/// `limit` (default 50, at most 100) and `offset`.
+///
+/// *Authorization*: Explicitly checks that the `role` is "admin".
+#[utoipa::path(
+    get,
+    path = "/admin/comments",
+    params(AdminCommentQuery),
+    responses(
+        (status = 200, description = "Matching comments, newest first", body = [AdminComment]),
+        (status = 400, description = "`project_id` or `user_id` is malformed"),
+        (status = 403, description = "Not Admin", body = ErrorResponse),
+        (status = 422, description = "Invalid `limit` or `offset`", body = ErrorResponse)
+    )
+)]
+pub async fn get_admin_comments(
+    AuthUser { role, .. }: AuthUser,
+    State(state): State<AppState>,
+    Query(query): Query<AdminCommentQuery>,
+) -> Result<Json<Vec<AdminComment>>, ApiError> {
+    if role != "admin" {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Admin role required.",
+        ));
+    }
+    let limit = parse_limit(query.limit.as_deref(), DEFAULT_COMMENT_PAGE_LIMIT, MAX_COMMENT_PAGE_LIMIT)?;
+    let offset = parse_offset(query.offset.as_deref())?;
+    let filter = AdminCommentFilter {
+        project_id: query.project_id,
+        user_id: query.user_id,
+        search: query.search.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+    };
+    Ok(Json(state.repo.get_admin_comments(&filter, limit, offset).await))
+}
+
 /// get_notifications
 ///
 /// [Authenticated Route] Retrieves the recipient user's list of notifications.
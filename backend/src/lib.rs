@@ -1,6 +1,6 @@
 use axum::{
-    extract::{FromRef, Request}, 
-    http::HeaderName,
+    extract::Request,
+    http::{HeaderName, HeaderValue, header},
     Router,
     middleware::{self, Next},
     response::Response, 
@@ -12,19 +12,50 @@ use tower::ServiceBuilder;
 use tower_http::{
     cors::{Any, CorsLayer},
     request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    set_header::SetResponseHeaderLayer,
     trace::{DefaultOnResponse, TraceLayer},
 };
 use tracing::{Level, Span};
+use std::{sync::Arc, time::Duration};
 
 // --- Module Structure ---
 
 // Core application services and components.
 pub mod auth;
+pub mod build_info;
 pub mod handlers;
 pub mod models;
 pub mod repository;
 pub mod storage;
 pub mod config;
+pub mod error;
+pub mod error_log;
+pub mod rate_limit;
+pub mod casing;
+pub mod og;
+pub mod embed;
+pub mod readiness;
+pub mod export;
+pub mod sparse;
+pub mod extract;
+pub mod redact;
+pub mod moderation;
+pub mod links;
+pub mod normalize;
+pub mod auth_provider;
+pub mod metrics;
+pub mod pagination;
+pub mod clock;
+pub mod maintenance;
+pub mod settings;
+pub mod captcha;
+pub mod outbox;
+pub mod audit;
+pub mod smoke;
+pub mod slug;
+pub mod security_headers;
+pub mod supervisor;
+pub mod state;
 
 // Module for routing segregation (Public, Authenticated, Admin).
 pub mod routes;
@@ -37,6 +68,9 @@ use auth::AuthUser; // The resolved authenticated user identity.
 pub use config::AppConfig;
 pub use repository::{RepositoryState, PostgresRepository};
 pub use storage::{MockStorageService, S3StorageClient, StorageState};
+pub use settings::{SettingsService, SettingsState};
+pub use supervisor::{TaskMonitor, TaskMonitorState};
+pub use state::{AppState, AppStateBuilder};
 
 /// ApiDoc
 ///
@@ -48,21 +82,40 @@ pub use storage::{MockStorageService, S3StorageClient, StorageState};
 #[openapi(
     // List all public handler functions here for documentation generation.
     paths(
-        handlers::get_projects, handlers::get_project_details, handlers::get_featured_projects, 
-        handlers::get_admin_projects, handlers::create_project, handlers::vote_project, 
-        handlers::update_project_status, handlers::get_presigned_url, handlers::register_user, 
-        handlers::get_me, handlers::get_admin_stats, handlers::get_my_projects, 
+        handlers::get_projects, handlers::get_project_details, handlers::get_project_by_slug, handlers::record_view, handlers::get_like_count, handlers::get_comment_count, handlers::get_featured_projects, handlers::get_random_project, 
+        handlers::get_admin_projects, handlers::get_pending_reviews, handlers::create_project, handlers::vote_project, handlers::unvote_project, handlers::get_project_likers, 
+        handlers::update_project_status, handlers::bulk_update_project_status, handlers::reassign_project_owner, handlers::get_presigned_url, handlers::register_user, 
+        handlers::get_me, handlers::get_admin_stats, handlers::get_my_projects, handlers::get_my_activity, 
         handlers::add_comment, handlers::get_comments, handlers::delete_project, 
-        handlers::update_project, handlers::delete_comment, handlers::get_notifications,
-        handlers::mark_notification_read
+        handlers::update_project, handlers::update_comment, handlers::delete_comment, handlers::delete_project_comment,
+        handlers::get_notifications,
+        handlers::mark_notification_read, handlers::set_comments_lock, handlers::set_project_subscription,
+        handlers::lock_comments_for_cohort, handlers::get_project_og, handlers::get_project_embed,
+        handlers::get_project_readiness, handlers::get_project_stats, handlers::submit_project, handlers::resubmit_project,
+        handlers::get_supervisor_report, handlers::get_version, handlers::get_public_stats, handlers::get_tags, handlers::get_project_years, handlers::get_departments, handlers::get_technologies, handlers::get_project_archive,
+        handlers::get_changed_projects, handlers::get_public_events, handlers::stream_admin_projects, handlers::export_admin_projects_csv,
+        handlers::get_programmes, handlers::create_programme, handlers::get_metrics, handlers::get_deep_health, handlers::get_admin_errors, handlers::get_admin_audit,
+        handlers::report_comment, handlers::report_comment_by_id, handlers::restore_comment, handlers::get_reported_comments, handlers::get_admin_comments, handlers::run_draft_reminders, handlers::get_invalid_years, handlers::remap_invalid_years, handlers::get_outbox_job,
+        handlers::get_setting, handlers::put_setting,
+        handlers::submit_feedback, handlers::get_project_feedback, handlers::get_all_feedback
     ),
     // List all models (schemas) used in the request/response bodies.
     components(
         schemas(
-            models::Project, models::CreateProjectRequest, models::UpdateProjectRequest,
-            models::Like, models::Comment, models::CreateCommentRequest, models::PresignedUrlRequest, 
+            models::Project, models::EmbedDocument, models::DraftReminderReport, models::StaleDraft, models::AcademicYears, models::InvalidYearProject, models::InvalidYearsReport, models::YearRemapRequest, embed::EmbedFormat, models::CreateProjectRequest, models::CreatedProject, models::UpdateProjectRequest,
+            models::Like, models::Comment, models::CreateCommentRequest, models::UpdateCommentRequest, models::PresignedUrlRequest, 
             models::PresignedUrlResponse, models::AdminDashboardStats, models::UserProfile,
-            models::NotificationResponse,
+            models::NotificationResponse, models::CommentsLockRequest, models::BulkUpdateResponse, models::BulkStatusRequest, models::BulkStatusResponse, models::ReassignOwnerRequest, models::LikeCountResponse, models::CommentCountResponse, models::ProjectLiker, models::ProjectStats, models::DailyCount, models::PendingReview, models::TechnologyCount,
+            models::ProjectDetail, models::ProjectListItem, models::ProjectOwner, models::MyProjectSummary, models::ReviewState, models::ProjectReview, models::SubscriptionState, models::ProjectStatus, models::CommentOrder, models::ProjectSort,
+            models::ReadinessReport, models::ReadinessCheck, models::SupervisorStats, models::UpdateStatusRequest,
+            models::VersionInfo, models::DeepHealth, models::TaskHealth, models::HealthStatus, models::ErrorSummary, models::ErrorGroup, models::ErrorSample, models::ErrorWindow, models::AuditEntry, models::PublicStats, models::TagCount, models::YearCount, models::DepartmentCount, models::UploadPurpose,
+            models::ChangedSinceRequest, models::ChangedSinceResponse, models::PageLinks,
+            models::PublicEvent, models::PublicEventKind, models::PublicEventsResponse,
+            models::Programme, models::CreateProgrammeRequest, models::CreateReportRequest, models::ReportedComment, models::AdminComment,
+            models::Setting, models::UpdateSettingRequest, models::MaintenanceMode,
+            models::CreateFeedbackRequest, models::ProjectFeedback, models::VoteResponse, models::ViewResponse, models::ProjectUpdateResult,
+            models::PostedComment, models::CommentPage, models::OutboxJob, models::ActivityItem,
+            error::ErrorResponse, error::FieldError,
         )
     ),
     tags(
@@ -71,44 +124,6 @@ pub use storage::{MockStorageService, S3StorageClient, StorageState};
 )]
 struct ApiDoc;
 
-/// AppState
-///
-/// Implements the **Unified State Pattern**. This is the single, thread-safe, and immutable
-/// container holding all essential application services and configuration.
-/// The application state is shared across all incoming requests.
-#[derive(Clone)]
-pub struct AppState {
-    /// Repository Layer: Abstracts database access via the PgPool connection.
-    pub repo: RepositoryState,
-    /// Storage Layer: Abstracts S3/MinIO access and presigned URL generation.
-    pub storage: StorageState,
-    /// Configuration: The loaded, immutable environment configuration.
-    pub config: AppConfig,
-}
-
-// --- Axum FromRef Extractor Implementations ---
-
-// These implementations allow handlers to selectively pull components from the shared AppState.
-// This is critical for dependency injection and adhering to the Clean Architecture boundaries.
-
-impl FromRef<AppState> for RepositoryState {
-    fn from_ref(app_state: &AppState) -> RepositoryState {
-        app_state.repo.clone()
-    }
-}
-
-impl FromRef<AppState> for StorageState {
-    fn from_ref(app_state: &AppState) -> StorageState {
-        app_state.storage.clone()
-    }
-}
-
-impl FromRef<AppState> for AppConfig {
-    fn from_ref(app_state: &AppState) -> AppConfig {
-        app_state.config.clone()
-    }
-}
-
 /// auth_middleware
 ///
 /// A middleware function that enforces authentication for the `authenticated_routes`.
@@ -139,6 +154,27 @@ pub fn create_router(state: AppState) -> Router {
     // Header name constant for Request Correlation.
     let x_request_id = HeaderName::from_static("x-request-id");
 
+    // Per-client token bucket shared by every route behind the limiter layer.
+    let rate_limiter = Arc::new(
+        rate_limit::RateLimiter::new(state.config.rate_limit_per_minute, Duration::from_secs(60))
+            .with_trusted_proxy_hops(state.config.trusted_proxy_hops),
+    );
+    // Much smaller hourly budget for the anonymous feedback form, checked after the global one.
+    let feedback_limiter = Arc::new(
+        rate_limit::RateLimiter::new(state.config.feedback_per_hour, Duration::from_secs(60 * 60))
+            .with_trusted_proxy_hops(state.config.trusted_proxy_hops),
+    );
+
+    // Read before the state is moved into the router.
+    let normalize_paths = state.config.normalize_paths;
+    let security_policy = Arc::new(security_headers::SecurityPolicy::new(
+        &state.config.security_headers,
+        &state.config.env,
+    ));
+    let count_db_calls = state.config.env == config::Env::Local;
+    let server = build_info::response_server_header(&state.config);
+    let error_log = state.errors.clone();
+
     // 2. Base Router Assembly
     let base_router = Router::new()
         // Documentation: Serve the auto-generated Swagger UI.
@@ -146,6 +182,7 @@ pub fn create_router(state: AppState) -> Router {
         
         // Public Routes: No middleware applied.
         .merge(public::public_routes())
+        .merge(public::feedback_routes(feedback_limiter))
         
         // Authenticated Routes: Protected by the `auth_middleware`.
         // This implements the first layer of Defense-in-Depth for these routes.
@@ -162,11 +199,35 @@ pub fn create_router(state: AppState) -> Router {
         .nest("/admin", admin::admin_routes())
         
         // Apply the Unified State to all routes.
-        .with_state(state); 
+        .with_state(state)
+
+        // Request Class: Opens the scope in which `AuthUser` marks admin/moderator requests, so
+        // their repository calls may use the reserved database permits (`repository::priority`).
+        .layer(middleware::from_fn(repository::priority::request_class))
+
+        // Rate Limiting: Applied to every route (exempt paths are skipped inside the middleware).
+        // Attaches X-RateLimit-* headers to all responses and rejects exhausted clients with 429.
+        .layer(middleware::from_fn_with_state(rate_limiter, rate_limit::rate_limit))
+
+        // Error Log: Records every 5xx and 429 (route template, status, code, request ID) in
+        // the bounded in-process log behind GET /admin/errors. Outside the limiter to see its 429s.
+        .layer(middleware::from_fn_with_state(error_log, error_log::record_errors))
+
+        // Response Casing: Re-cases JSON bodies to camelCase for clients sending
+        // `X-Api-Case: camel` (or `?case=camel`). Applied outside the limiter so 429 bodies match.
+        .layer(middleware::from_fn(casing::json_casing));
+
+    // DB Call Counting (Local only): Adds `X-DB-Calls` with the number of repository calls the
+    // request made. Only meaningful when the state's repository is a `CountingRepository`.
+    let base_router = if count_db_calls {
+        base_router.layer(middleware::from_fn(repository::counting::db_calls_header))
+    } else {
+        base_router
+    };
 
     // 3. Observability and Correlation Layers (Applied outermost/first)
     // This section implements the Production Observability Stack.
-    base_router
+    let app = base_router
         .layer(
              ServiceBuilder::new()
                  // 3a. Request ID Generation: Generates a unique UUID for every incoming request.
@@ -191,13 +252,37 @@ pub fn create_router(state: AppState) -> Router {
         )
         // 4. CORS Layer (Applied last, allowing all traffic in/out after processing)
         .layer(cors)
+        // 5. Server Header: Identifies the deployed build (`fyp-portal/<version> (<commit>)`),
+        // unless `VERSION_ADMIN_ONLY` hides it.
+        .layer(SetResponseHeaderLayer::overriding(
+            header::SERVER,
+            HeaderValue::from_str(&server)
+                .unwrap_or_else(|_| HeaderValue::from_static("fyp-portal")),
+        ))
+        // 6. Security Headers: nosniff, Referrer-Policy, X-Frame-Options (not on embeds), HSTS in
+        // Production and a Content-Security-Policy on HTML. Outermost, so 429s and 404s get them.
+        .layer(middleware::from_fn_with_state(
+            security_policy,
+            security_headers::security_headers,
+        ));
+
+    // 7. Path Normalization: `Router::layer` middleware runs after route matching, so the
+    // rewrite wraps the finished app as an outer router's fallback to take effect *before*
+    // routing. Opt out with `NORMALIZE_PATHS=false`.
+    if !normalize_paths {
+        return app;
+    }
+    Router::new()
+        .fallback_service(app)
+        .layer(middleware::map_request(normalize::normalize_request))
 }
 
 /// trace_span_logger
 ///
 /// Helper function used by `TraceLayer` to customize the tracing span creation.
 /// It extracts the `x-request-id` header (if present) and includes it in the
-/// structured logging metadata alongside the HTTP method and URI.
+/// structured logging metadata alongside the HTTP method and URI, plus the running
+/// `service.version` so log lines can be matched to a build.
 ///
 /// *Goal*: Ensure every log line for a single request is correlated by a unique ID.
 fn trace_span_logger(request: &axum::http::Request<axum::body::Body>) -> Span {
@@ -213,5 +298,7 @@ fn trace_span_logger(request: &axum::http::Request<axum::body::Body>) -> Span {
         method = ?request.method(),
         uri = ?request.uri(),
         req_id = %request_id, 
+        service.version = build_info::VERSION,
+        service.commit = build_info::COMMIT,
     )
 }
@@ -0,0 +1,79 @@
+use crate::{config::AppConfig, models::PageLinks};
+use axum::http::Uri;
+
+/// UrlBuilder
+///
+/// Turns request-relative paths into absolute API URLs using the configured `public_api_url`,
+/// so links stay correct behind the reverse proxy (which the request's own Host/scheme are not).
+pub struct UrlBuilder<'a> {
+    base: &'a str,
+}
+
+impl<'a> UrlBuilder<'a> {
+    pub fn new(config: &'a AppConfig) -> Self {
+        Self {
+            base: &config.public_api_url,
+        }
+    }
+
+    /// The absolute URL for a path (with optional query), e.g. `/projects?year=2024`.
+    pub fn absolute(&self, path_and_query: &str) -> String {
+        format!("{}{}", self.base, path_and_query)
+    }
+
+    /// with_page
+    ///
+    /// The absolute URL of `uri` with its `page` parameter set to `page`. All other query
+    /// pairs are kept verbatim and in order, so filters survive byte-for-byte. `uri` must be
+    /// the *original* request URI (axum's `OriginalUri`): inside nested routers the plain
+    /// `Uri` has the mount prefix stripped.
+    pub fn with_page(&self, uri: &Uri, page: i64) -> String {
+        self.with_param(uri, "page", &page.to_string())
+    }
+
+    /// with_param
+    ///
+    /// `with_page` for any query parameter: the absolute URL of `uri` with `key` set to
+    /// `value` (appended last), every other pair kept verbatim. `value` must be URL-safe.
+    pub fn with_param(&self, uri: &Uri, key: &str, value: &str) -> String {
+        let mut pairs: Vec<String> = uri
+            .query()
+            .unwrap_or("")
+            .split('&')
+            .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(key))
+            .map(str::to_string)
+            .collect();
+        pairs.push(format!("{}={}", key, value));
+        self.absolute(&format!("{}?{}", uri.path(), pairs.join("&")))
+    }
+
+    /// page_links
+    ///
+    /// `self`/`next`/`prev` links for `page` (1-based) of a listing with `total_count` items
+    /// shown `per_page` at a time. An empty listing has a single (empty) first page.
+    pub fn page_links(&self, uri: &Uri, page: i64, per_page: i64, total_count: i64) -> PageLinks {
+        let last_page = if per_page > 0 {
+            ((total_count + per_page - 1) / per_page).max(1)
+        } else {
+            1
+        };
+        PageLinks {
+            self_link: self.with_page(uri, page),
+            next: (page < last_page).then(|| self.with_page(uri, page + 1)),
+            prev: (page > 1).then(|| self.with_page(uri, (page - 1).min(last_page))),
+        }
+    }
+
+    /// cursor_links
+    ///
+    /// Links for a keyset page: `self` is the request as made and `next` swaps in
+    /// `next_cursor`. There is no `prev`, since cursors only move forward.
+    pub fn cursor_links(&self, uri: &Uri, next_cursor: Option<&str>) -> PageLinks {
+        let path_and_query = uri.path_and_query().map_or(uri.path(), |pq| pq.as_str());
+        PageLinks {
+            self_link: self.absolute(path_and_query),
+            next: next_cursor.map(|cursor| self.with_param(uri, "cursor", cursor)),
+            prev: None,
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use fyp_portal::{
-    AppState,
+    AppStateBuilder,
     config::{AppConfig, Env},
     create_router,
-    repository::{PostgresRepository, RepositoryState},
+    maintenance::{self, AuditRetention, DraftReminders},
+    outbox::OutboxDispatcher,
+    settings::{SettingsRefresh, SettingsService},
+    repository::{PostgresRepository, RepositoryState, counting::CountingRepository, priority::PriorityRepository},
     storage::{S3StorageClient, StorageState},
 };
 use sqlx::postgres::PgPoolOptions;
@@ -48,18 +51,36 @@ async fn main() {
         }
     }
 
-    tracing::info!("Application starting in {:?} mode", config.env);
+    // Startup Banner: Identifies exactly which build is running.
+    tracing::info!(
+        version = fyp_portal::build_info::VERSION,
+        commit = fyp_portal::build_info::COMMIT,
+        built_at = fyp_portal::build_info::BUILT_AT,
+        "Application starting in {:?} mode",
+        config.env
+    );
 
     // 4. Database Initialization (Postgres)
     // Creates a connection pool to the Postgres instance defined in the configuration.
     let pool = PgPoolOptions::new()
-        .max_connections(5)
+        .max_connections(config.db_max_connections)
         .connect(&config.db_url)
         .await
         .expect("FATAL: Failed to connect to Postgres. Check DATABASE_URL.");
 
     // Instantiate the Repository, wrapping it in an Arc for thread-safe sharing.
-    let repo = Arc::new(PostgresRepository::new(pool)) as RepositoryState;
+    // Every call holds one of the pool's permits; DB_ADMIN_RESERVED of them are kept for admin
+    // and moderator requests, so public traffic spikes cannot starve moderation.
+    // LOCAL-ONLY: count queries per request so N+1 patterns show up in X-DB-Calls.
+    let postgres = Arc::new(PriorityRepository::new(
+        Arc::new(PostgresRepository::new(pool)),
+        config.db_max_connections as usize,
+        config.db_admin_reserved as usize,
+    )) as RepositoryState;
+    let repo = match config.env {
+        Env::Local => Arc::new(CountingRepository::new(postgres)) as RepositoryState,
+        Env::Production => postgres,
+    };
 
     // 5. Storage Initialization (S3/MinIO)
     // Instantiates the S3-compatible client using credentials resolved by AppConfig.
@@ -83,14 +104,52 @@ async fn main() {
     let storage = Arc::new(s3_client) as StorageState;
 
     // 6. Unified State Assembly
-    // Bundles all initialized dependencies into the shared AppState.
-    let app_state = AppState {
-        repo,
-        storage,
-        config,
-    };
+    // Bundles all initialized dependencies into the shared AppState; the builder adds the
+    // outbound HTTP client, the task monitor and the wall clock.
+    // Settings are loaded before serving, so the first requests already see stored values.
+    let settings = Arc::new(SettingsService::new());
+    if let Err(e) = settings.refresh(&*repo).await {
+        tracing::warn!(error = %e, "Initial settings load failed; starting with defaults");
+    }
+    let app_state = AppStateBuilder::new(repo, storage)
+        .config(config)
+        .settings(settings)
+        .build();
 
-    // 7. Router and Server Startup
+    // 7. Background Maintenance
+    // Detached loops for the process lifetime. Each is supervised: a failing or panicking pass
+    // is logged and retried with backoff, and its health is reported by GET /health/deep.
+    // Periodic housekeeping (stale-draft reminders).
+    maintenance::spawn_runner(
+        app_state.tasks().clone(),
+        Arc::new(DraftReminders::new(app_state.repo().clone(), app_state.config())),
+        app_state.clock().clone(),
+        app_state.config().maintenance_interval,
+    );
+    // Audit entries past AUDIT_RETENTION_DAYS are purged on the same schedule.
+    maintenance::spawn_runner(
+        app_state.tasks().clone(),
+        Arc::new(AuditRetention::new(app_state.repo().clone(), app_state.config())),
+        app_state.clock().clone(),
+        app_state.config().maintenance_interval,
+    );
+    // Settings changed on other instances arrive through this reload loop.
+    maintenance::spawn_runner(
+        app_state.tasks().clone(),
+        Arc::new(SettingsRefresh::new(app_state.settings().clone(), app_state.repo().clone())),
+        app_state.clock().clone(),
+        app_state.config().settings_refresh_interval,
+    );
+    // Comment notifications too large to send in the request are expanded here, in batches,
+    // and audit entries whose direct insert failed are written.
+    maintenance::spawn_runner(
+        app_state.tasks().clone(),
+        Arc::new(OutboxDispatcher::new(app_state.repo().clone())),
+        app_state.clock().clone(),
+        app_state.config().outbox_dispatch_interval,
+    );
+
+    // 8. Router and Server Startup
     let app = create_router(app_state);
 
     // Binds the TCP listener and initiates the HTTP server.
@@ -100,7 +159,13 @@ async fn main() {
     tracing::info!("Listening on 0.0.0.0:3000");
     tracing::info!("API Documentation (Swagger UI) available at: http://localhost:3000/swagger-ui");
 
-    // The long-running Axum server process.
-    axum::serve(listener, app).await.unwrap();
+    // The long-running Axum server process. Connection info is attached so the rate limiter
+    // can key requests by peer address when no proxy header is present.
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
 
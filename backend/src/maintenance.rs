@@ -0,0 +1,161 @@
+use crate::{
+    audit,
+    clock::Clock,
+    config::AppConfig,
+    models::{DraftReminderReport, NewAuditEntry},
+    repository::{DraftSweep, RepositoryError, RepositoryState},
+    supervisor::{self, TaskMonitorState},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::{sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+
+/// MaintenanceTask
+///
+/// A periodic background job. `run` is handed the pass time from the runner's clock rather
+/// than reading the wall clock itself, so tests can drive it with a `MockClock`. An error
+/// (or a panic) fails the pass, which the runner retries with backoff.
+#[async_trait]
+pub trait MaintenanceTask: Send + Sync {
+    /// Short name used in logs and in GET /health/deep.
+    fn name(&self) -> &'static str;
+    async fn run(&self, now: DateTime<Utc>) -> Result<(), RepositoryError>;
+}
+
+/// spawn_runner
+///
+/// Runs `task` once per `interval` under `supervisor::spawn_supervised`, so a failing or
+/// panicking pass is restarted and shows up in the monitor rather than ending the loop. The
+/// runner only stops when the returned handle is aborted.
+pub fn spawn_runner(
+    monitor: TaskMonitorState,
+    task: Arc<dyn MaintenanceTask>,
+    clock: Arc<dyn Clock>,
+    interval: Duration,
+) -> JoinHandle<()> {
+    supervisor::spawn_supervised(monitor, task.name(), interval, move || {
+        let (task, now) = (task.clone(), clock.now());
+        async move {
+            tracing::debug!(task = task.name(), "Running maintenance task");
+            task.run(now).await
+        }
+    })
+}
+
+/// DraftReminders
+///
+/// The stale-draft pass. Drafts untouched for `draft_reminder_days` get one "draft_reminder"
+/// notification for their owner; drafts untouched for `draft_cleanup_days` are flagged for the
+/// orphaned-upload cleanup. The repository records both per draft, so repeated passes never
+/// repeat either action.
+pub struct DraftReminders {
+    repo: RepositoryState,
+    reminder_after: chrono::Duration,
+    cleanup_after: chrono::Duration,
+}
+
+impl DraftReminders {
+    pub fn new(repo: RepositoryState, config: &AppConfig) -> Self {
+        Self {
+            repo,
+            reminder_after: chrono::Duration::days(config.draft_reminder_days),
+            cleanup_after: chrono::Duration::days(config.draft_cleanup_days),
+        }
+    }
+
+    /// sweep
+    ///
+    /// One pass as of `now`. With `dry_run` the report lists the drafts that would be
+    /// reminded or flagged and nothing is written. Otherwise it lists only the drafts this
+    /// pass actually handled (a concurrent pass may have taken some).
+    pub async fn sweep(&self, now: DateTime<Utc>, dry_run: bool) -> DraftReminderReport {
+        let mut report = DraftReminderReport { dry_run, ..DraftReminderReport::default() };
+        for (sweep, after) in [
+            (DraftSweep::Reminder, self.reminder_after),
+            (DraftSweep::Cleanup, self.cleanup_after),
+        ] {
+            let mut handled = Vec::new();
+            for draft in self.repo.get_stale_drafts(sweep, now - after).await {
+                if dry_run || self.repo.mark_stale_draft(sweep, draft.project_id, now).await {
+                    handled.push(draft);
+                }
+            }
+            match sweep {
+                DraftSweep::Reminder => report.reminded = handled,
+                DraftSweep::Cleanup => report.flagged_for_cleanup = handled,
+            }
+        }
+        report
+    }
+}
+
+#[async_trait]
+impl MaintenanceTask for DraftReminders {
+    fn name(&self) -> &'static str {
+        "draft_reminders"
+    }
+
+    /// Fails when the database is unreachable, which the sweep queries would report as "no
+    /// stale drafts".
+    async fn run(&self, now: DateTime<Utc>) -> Result<(), RepositoryError> {
+        self.repo.ping().await?;
+        let report = self.sweep(now, false).await;
+        if !report.reminded.is_empty() || !report.flagged_for_cleanup.is_empty() {
+            tracing::info!(
+                reminded = report.reminded.len(),
+                flagged_for_cleanup = report.flagged_for_cleanup.len(),
+                "Stale draft sweep"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// AuditRetention
+///
+/// Purges audit log entries older than `audit_retention_days`. A pass that removes anything
+/// logs the count and audits the purge itself, as an `audit.purge` entry by the system (no
+/// actor), which is recent enough to survive the next passes.
+pub struct AuditRetention {
+    repo: RepositoryState,
+    keep_for: chrono::Duration,
+}
+
+impl AuditRetention {
+    pub fn new(repo: RepositoryState, config: &AppConfig) -> Self {
+        Self {
+            repo,
+            keep_for: chrono::Duration::days(config.audit_retention_days),
+        }
+    }
+
+    /// Deletes the entries created before `now` minus the retention period; returns how many.
+    pub async fn purge(&self, now: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let before = now - self.keep_for;
+        let purged = self.repo.purge_audit_log(before).await?;
+        if purged > 0 {
+            tracing::info!(purged, before = %before, "Audit log retention purge");
+            let entry = NewAuditEntry {
+                actor_id: None,
+                action: "audit.purge".to_string(),
+                target_type: "audit_log".to_string(),
+                target_id: None,
+                details: serde_json::json!({ "purged": purged, "before": before }),
+            };
+            audit::record(&*self.repo, entry).await;
+        }
+        Ok(purged)
+    }
+}
+
+#[async_trait]
+impl MaintenanceTask for AuditRetention {
+    fn name(&self) -> &'static str {
+        "audit_retention"
+    }
+
+    async fn run(&self, now: DateTime<Utc>) -> Result<(), RepositoryError> {
+        self.purge(now).await.map(|_| ())
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicI64, AtomicU64, Ordering},
+};
+
+/// Prometheus text exposition format, as served by `GET /metrics`.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// CounterVec
+///
+/// An in-process, monotonically increasing counter with one label whose values are fixed at
+/// compile time. Unknown label values are ignored rather than allocating a new series.
+pub struct CounterVec<const N: usize> {
+    name: &'static str,
+    help: &'static str,
+    label: &'static str,
+    values: [&'static str; N],
+    counts: [AtomicU64; N],
+}
+
+impl<const N: usize> CounterVec<N> {
+    pub const fn new(
+        name: &'static str,
+        help: &'static str,
+        label: &'static str,
+        values: [&'static str; N],
+    ) -> Self {
+        Self {
+            name,
+            help,
+            label,
+            values,
+            counts: [const { AtomicU64::new(0) }; N],
+        }
+    }
+
+    pub fn inc(&self, value: &str) {
+        if let Some(i) = self.values.iter().position(|v| *v == value) {
+            self.counts[i].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn get(&self, value: &str) -> u64 {
+        self.values
+            .iter()
+            .position(|v| *v == value)
+            .map_or(0, |i| self.counts[i].load(Ordering::Relaxed))
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} counter", self.name);
+        for (value, count) in self.values.iter().zip(&self.counts) {
+            let _ = writeln!(
+                out,
+                "{}{{{}=\"{}\"}} {}",
+                self.name,
+                self.label,
+                value,
+                count.load(Ordering::Relaxed)
+            );
+        }
+    }
+}
+
+/// GaugeVec
+///
+/// Like `CounterVec`, but for a value that goes up and down (e.g. a queue length).
+pub struct GaugeVec<const N: usize> {
+    name: &'static str,
+    help: &'static str,
+    label: &'static str,
+    values: [&'static str; N],
+    gauges: [AtomicI64; N],
+}
+
+impl<const N: usize> GaugeVec<N> {
+    pub const fn new(
+        name: &'static str,
+        help: &'static str,
+        label: &'static str,
+        values: [&'static str; N],
+    ) -> Self {
+        Self {
+            name,
+            help,
+            label,
+            values,
+            gauges: [const { AtomicI64::new(0) }; N],
+        }
+    }
+
+    pub fn add(&self, value: &str, delta: i64) {
+        if let Some(i) = self.values.iter().position(|v| *v == value) {
+            self.gauges[i].fetch_add(delta, Ordering::Relaxed);
+        }
+    }
+
+    pub fn get(&self, value: &str) -> i64 {
+        self.values
+            .iter()
+            .position(|v| *v == value)
+            .map_or(0, |i| self.gauges[i].load(Ordering::Relaxed))
+    }
+
+    fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} gauge", self.name);
+        for (value, gauge) in self.values.iter().zip(&self.gauges) {
+            let _ = writeln!(
+                out,
+                "{}{{{}=\"{}\"}} {}",
+                self.name,
+                self.label,
+                value,
+                gauge.load(Ordering::Relaxed)
+            );
+        }
+    }
+}
+
+/// Calls to the external auth provider, by outcome (see `auth_provider::Outcome`).
+pub static AUTH_PROVIDER_REQUESTS: CounterVec<4> = CounterVec::new(
+    "auth_provider_requests_total",
+    "Requests made to the external auth provider, by outcome.",
+    "outcome",
+    ["success", "rejected", "timeout", "error"],
+);
+
+/// Repository calls waiting for a database permit, by request class (see `repository::priority`).
+pub static DB_PERMIT_QUEUE_DEPTH: GaugeVec<3> = GaugeVec::new(
+    "db_permit_queue_depth",
+    "Repository calls currently waiting for a database permit, by request class.",
+    "class",
+    ["public", "privileged", "lookup"],
+);
+
+/// render
+///
+/// Every registered metric in the Prometheus text format.
+pub fn render() -> String {
+    let mut out = String::new();
+    AUTH_PROVIDER_REQUESTS.render(&mut out);
+    DB_PERMIT_QUEUE_DEPTH.render(&mut out);
+    out
+}
+
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use sqlx::FromRow;
 use ts_rs::TS;
 use utoipa::ToSchema;
@@ -46,11 +47,44 @@ pub struct Project {
     pub report: Option<String>,
 
     // Logic Fields
-    // Controls public visibility (enforced at the Repository layer).
+    // Review state; only `approved` projects are publicly visible (enforced at the Repository layer).
+    pub status: ProjectStatus,
+    // Deprecated: `status == approved`, generated by the database. Kept for one release for
+    // clients that still read it.
     pub is_public: bool,
     // Allows separate control over the report document visibility, even if the project is public.
+    // Enforced by `redact_for_public`: other people only see the `report` key when set.
     pub report_is_public: bool,
+    // Freezes new comments and likes while keeping existing comments readable.
+    pub comments_locked: bool,
+    // Free-form label -> URL map (e.g. {"repository": "https://..."}). Keys are user data.
+    #[ts(type = "Record<string, string>")]
+    #[schema(value_type = Object)]
+    pub links: serde_json::Value,
+    // Owner has explicitly declared the project has no demo video (satisfies the readiness check).
+    pub video_waived: bool,
+    // Set when the owner submits the draft for review; `None` means the project is still a draft.
+    #[ts(type = "string | null")]
+    pub submitted_at: Option<DateTime<Utc>>,
+    // Name of the supervising staff member; `None` until assigned.
+    pub supervisor: Option<String>,
+    // Course the project belongs to, one of the configured DEPARTMENTS; `None` until chosen.
+    pub department: Option<String>,
+    // Languages, frameworks and tools used (e.g. ["postgres", "rust"]): lower-case, sorted.
+    #[serde(default)]
+    pub technologies: Vec<String>,
+    // Public detail-page views, counted by POST /projects/{id}/view.
+    pub view_count: i64,
+    // Readable address for GET /projects/slug/{slug}, e.g. "smart-greenhouse-2024". Follows
+    // title changes; earlier slugs keep resolving.
+    pub slug: String,
     pub year: i32,
+    // Computed, not a column: comments on the project, excluding those held for review on
+    // public listings. Owner and admin views count every comment.
+    pub comment_count: i64,
+    // From `project_tags`: lower-case labels, sorted.
+    #[serde(default)]
+    pub tags: Vec<String>,
 
     // Timestamp handling for database integration and JSON serialization.
     #[ts(type = "string")]
@@ -59,6 +93,275 @@ pub struct Project {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Project {
+    /// The project as shown to anyone but its owner and admins: without the `report` key when
+    /// the report is not public, since the key is enough to build the storage URL.
+    pub fn redact_for_public(mut self) -> Self {
+        if !self.report_is_public {
+            self.report = None;
+        }
+        self
+    }
+}
+
+/// ProjectStatus
+///
+/// Where a project is in the review flow, stored as lower-case text in `projects.status`.
+/// New projects start as `draft`, submitting moves them to `pending`, and an admin approves
+/// (publishes) or rejects them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq, Default, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[ts(export)]
+pub enum ProjectStatus {
+    #[default]
+    Draft,
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ProjectStatus {
+    /// The value stored in `projects.status`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectStatus::Draft => "draft",
+            ProjectStatus::Pending => "pending",
+            ProjectStatus::Approved => "approved",
+            ProjectStatus::Rejected => "rejected",
+        }
+    }
+
+    /// Whether projects in this state are publicly visible.
+    pub fn is_public(&self) -> bool {
+        *self == ProjectStatus::Approved
+    }
+
+    /// The status a plain publish/hide decision stands for: `approved` or `rejected`.
+    pub fn from_visibility(is_public: bool) -> Self {
+        if is_public { ProjectStatus::Approved } else { ProjectStatus::Rejected }
+    }
+}
+
+/// ProjectDetail
+///
+/// Response for the single-project view (GET /projects/{id}). Flattens the project, adds its
+/// owner's profile, its programme codes and, for authenticated callers, their comment
+/// subscription state and whether they liked it.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct ProjectDetail {
+    #[serde(flatten)]
+    pub project: Project,
+    // `subscribed` if the caller receives comment notifications, `muted` if they opted out.
+    // Omitted for anonymous callers and for users who are not subscribed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub subscription: Option<SubscriptionState>,
+    // Codes of the programmes the project belongs to, sorted.
+    #[serde(default)]
+    pub programme_codes: Vec<String>,
+    // The owner's profile; null when their `profiles` row is missing.
+    #[serde(default)]
+    pub owner: Option<ProjectOwner>,
+    // Whether the caller has liked the project. Omitted for anonymous callers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub liked_by_me: Option<bool>,
+}
+
+/// ProjectListItem
+///
+/// An entry of GET /projects: the project, flattened, and for authenticated callers whether
+/// they have liked it (the listing's heart icon).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct ProjectListItem {
+    #[serde(flatten)]
+    pub project: Project,
+    // Omitted for anonymous callers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub liked_by_me: Option<bool>,
+}
+
+/// ProjectOwner
+///
+/// The owner of a project as shown on its detail page ("by ..."), from their `profiles` row.
+/// Listings carry only `user_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default, PartialEq)]
+#[ts(export)]
+pub struct ProjectOwner {
+    pub id: Uuid,
+    pub email: String,
+    pub avatar_url: Option<String>,
+}
+
+impl ProjectOwner {
+    pub fn new(id: Uuid, email: String) -> Self {
+        Self { id, email, avatar_url: Some(avatar_url(id)) }
+    }
+}
+
+/// ProjectReview
+///
+/// An admin status decision from `project_reviews`, as shown to the project owner. The
+/// reviewer is recorded but not exposed. A resubmission by the owner is recorded as a
+/// `pending` review without a reason.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, PartialEq)]
+#[ts(export)]
+pub struct ProjectReview {
+    pub project_id: Uuid,
+    // The status the admin set (`pending` for a resubmission).
+    pub status: ProjectStatus,
+    // The explanation given to the owner, if any.
+    pub reason: Option<String>,
+    #[ts(type = "string")]
+    pub reviewed_at: DateTime<Utc>,
+}
+
+/// ResubmitOutcome
+///
+/// Result of an owner's resubmission attempt (internal).
+#[derive(Debug, Clone)]
+pub enum ResubmitOutcome {
+    // Back in the review queue, as `pending`.
+    Resubmitted(Box<Project>),
+    // Only rejected projects can be resubmitted; this is the project's current status.
+    NotRejected(ProjectStatus),
+    // The project has already been resubmitted the maximum number of times.
+    LimitReached,
+}
+
+/// CommentEditOutcome
+///
+/// Result of an author's attempt to edit their comment (internal).
+#[derive(Debug, Clone)]
+pub enum CommentEditOutcome {
+    // The new text is stored and the edit logged.
+    Edited(Box<Comment>),
+    // The project's discussion is locked; nothing changed.
+    Locked,
+    // The comment is hidden pending moderator review and stays as it was reported.
+    HiddenPendingReview,
+}
+
+/// OwnerTransferOutcome
+///
+/// Result of an admin moving a project to another account (internal).
+#[derive(Debug, Clone)]
+pub enum OwnerTransferOutcome {
+    // Now owned by the new account; `previous_owner` is the account it was taken from (the
+    // same one when the project already belonged to the target).
+    Transferred { project: Box<Project>, previous_owner: Uuid },
+    // No profile exists for the target account.
+    UnknownOwner,
+}
+
+/// ReassignOwnerRequest
+///
+/// Input payload for PUT /admin/projects/{id}/owner.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ReassignOwnerRequest {
+    pub new_owner_id: Uuid,
+}
+
+/// BulkStatusOutcome
+///
+/// Result of a bulk status change (internal): the projects whose status changed, and the
+/// requested ids that matched no project. Projects already in the target status are in neither.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BulkStatusOutcome {
+    pub changed: Vec<Uuid>,
+    pub not_found: Vec<Uuid>,
+}
+
+/// ReviewState
+///
+/// The owner dashboard's short answer to "where is my project?": `approved` (public),
+/// `hidden` (rejected) or `pending` (neither yet, whether still a draft or awaiting review;
+/// `status` tells those two apart).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum ReviewState {
+    #[default]
+    Pending,
+    Approved,
+    Hidden,
+}
+
+impl From<ProjectStatus> for ReviewState {
+    fn from(status: ProjectStatus) -> Self {
+        match status {
+            ProjectStatus::Draft | ProjectStatus::Pending => ReviewState::Pending,
+            ProjectStatus::Approved => ReviewState::Approved,
+            ProjectStatus::Rejected => ReviewState::Hidden,
+        }
+    }
+}
+
+/// MyProjectSummary
+///
+/// An entry of GET /me/projects: the project, flattened (with its `comment_count`), plus its
+/// like count, its `review_state` and the latest admin review so the owner can see why a
+/// project was rejected or hidden.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct MyProjectSummary {
+    #[serde(flatten)]
+    pub project: Project,
+    pub like_count: i64,
+    pub review_state: ReviewState,
+    // Omitted until an admin has reviewed the project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub latest_review: Option<ProjectReview>,
+}
+
+impl MyProjectSummary {
+    /// A summary of `project` with `like_count` likes and no review attached yet.
+    pub fn new(project: Project, like_count: i64) -> Self {
+        Self {
+            review_state: project.status.into(),
+            project,
+            like_count,
+            latest_review: None,
+        }
+    }
+}
+
+/// PendingReview
+///
+/// An entry of GET /admin/projects/pending: a project awaiting review, flattened, with the
+/// submitter's email and how long it has been waiting.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct PendingReview {
+    #[serde(flatten)]
+    pub project: Project,
+    // Null when the owner's profile row is missing.
+    pub owner_email: Option<String>,
+    // When the project entered the queue: its latest submission, or its creation for
+    // projects that were never formally submitted.
+    pub waiting_since: DateTime<Utc>,
+    pub waiting_seconds: i64,
+}
+
+impl PendingReview {
+    /// The queue entry for `project`, with its wait measured up to `now`.
+    pub fn new(project: Project, owner_email: Option<String>, now: DateTime<Utc>) -> Self {
+        let waiting_since = project.submitted_at.unwrap_or(project.created_at);
+        Self {
+            waiting_seconds: (now - waiting_since).num_seconds().max(0),
+            waiting_since,
+            project,
+            owner_email,
+        }
+    }
+}
+
 /// Like
 ///
 /// Internal structure representing a single vote record in the `public.project_likes` table.
@@ -72,7 +375,208 @@ pub struct Like {
     pub project_id: Uuid,
 }
 
-/// --- Request Payloads (Input Schemas) ---
+/// LikeOutcome
+///
+/// Result of recording a like (internal), with the project's like count after the attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LikeOutcome {
+    Created(i64),
+    // The user had already liked the project; nothing changed.
+    AlreadyExists(i64),
+    // The user owns the project, and owners cannot like their own work; nothing changed.
+    OwnProject(i64),
+}
+
+impl LikeOutcome {
+    pub fn like_count(&self) -> i64 {
+        match self {
+            Self::Created(count) | Self::AlreadyExists(count) | Self::OwnProject(count) => *count,
+        }
+    }
+}
+
+/// VoteResponse
+///
+/// Body of POST /projects/{id}/vote, for both a new like (200) and a repeated one (409), so
+/// the client can render the heart and counter without another request.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[ts(export)]
+pub struct VoteResponse {
+    pub already_liked: bool,
+    pub like_count: i64,
+}
+
+impl From<LikeOutcome> for VoteResponse {
+    fn from(outcome: LikeOutcome) -> Self {
+        Self {
+            already_liked: matches!(outcome, LikeOutcome::AlreadyExists(_)),
+            like_count: outcome.like_count(),
+        }
+    }
+}
+
+/// ViewResponse
+///
+/// Body of POST /projects/{id}/view: the project's view count including this view.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[ts(export)]
+pub struct ViewResponse {
+    pub view_count: i64,
+}
+
+/// ProjectLiker
+///
+/// One entry of GET /projects/{id}/likes/users: a user who liked the project.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[ts(export)]
+pub struct ProjectLiker {
+    pub id: Uuid,
+    // Null when the user's profile row is missing.
+    pub email: Option<String>,
+    pub liked_at: DateTime<Utc>,
+}
+
+/// LikeCountResponse
+///
+/// Body of GET /projects/{id}/likes: the project's current like total.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[ts(export)]
+pub struct LikeCountResponse {
+    pub count: i64,
+}
+
+/// CommentCountResponse
+///
+/// Body of GET /projects/{id}/comments/count: how many comments the public thread shows.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[ts(export)]
+pub struct CommentCountResponse {
+    pub count: i64,
+}
+
+/// Programme
+///
+/// A degree programme from the admin-managed `programmes` lookup. Projects reference
+/// programmes by `code` through the `project_programmes` join table.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, Default, PartialEq)]
+#[ts(export)]
+pub struct Programme {
+    // Short, upper-case identifier, e.g. "CS" or "MS-JOINT".
+    pub code: String,
+    pub name: String,
+}
+
+/// TagCount
+///
+/// One entry of GET /tags: a tag and the number of public projects carrying it.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, Default, PartialEq, Eq)]
+#[ts(export)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i64,
+}
+
+/// TechnologyCount
+///
+/// One entry of GET /technologies: a technology and the number of public projects using it.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, Default, PartialEq, Eq)]
+#[ts(export)]
+pub struct TechnologyCount {
+    pub technology: String,
+    pub count: i64,
+}
+
+/// DepartmentCount
+///
+/// One entry of GET /departments: a department and the number of public projects in it.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, Default, PartialEq, Eq)]
+#[ts(export)]
+pub struct DepartmentCount {
+    pub department: String,
+    pub count: i64,
+}
+
+/// YearCount
+///
+/// One entry of GET /projects/years: a cohort year and the number of public projects in it.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, Default, PartialEq, Eq)]
+#[ts(export)]
+pub struct YearCount {
+    pub year: i32,
+    pub count: i64,
+}
+
+/// DailyCount
+///
+/// A count for one UTC calendar day, e.g. the likes a project received that day.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, PartialEq, Eq)]
+#[ts(export)]
+pub struct DailyCount {
+    pub day: chrono::NaiveDate,
+    pub count: i64,
+}
+
+/// ProjectStats
+///
+/// Engagement figures for one of the caller's projects (GET /me/projects/{id}/stats), e.g.
+/// for the slides of a final presentation.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[ts(export)]
+pub struct ProjectStats {
+    pub project_id: Uuid,
+    pub like_count: i64,
+    // Visible comments only, as shown on the project page.
+    pub comment_count: i64,
+    pub view_count: i64,
+    // One entry per day of the last `PROJECT_STATS_DAYS` days, oldest first, ending today
+    // (UTC). Days without likes are included with a count of 0.
+    pub likes_per_day: Vec<DailyCount>,
+}
+
+/// ActivityItem
+///
+/// One entry of the caller's activity feed (GET /me/activity), tagged by `kind`: a comment
+/// they wrote, a project they liked, or a visibility change of a project they own.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[ts(export)]
+pub enum ActivityItem {
+    Commented {
+        #[ts(type = "string")]
+        at: DateTime<Utc>,
+        project_id: Uuid,
+        project_title: String,
+        comment_id: i64,
+        comment: String,
+    },
+    Liked {
+        #[ts(type = "string")]
+        at: DateTime<Utc>,
+        project_id: Uuid,
+        project_title: String,
+    },
+    StatusChanged {
+        #[ts(type = "string")]
+        at: DateTime<Utc>,
+        project_id: Uuid,
+        project_title: String,
+        // true when the project was published, false when it was hidden again.
+        is_public: bool,
+    },
+}
+
+impl ActivityItem {
+    /// When it happened; the feed is ordered by this, newest first.
+    pub fn at(&self) -> DateTime<Utc> {
+        match self {
+            ActivityItem::Commented { at, .. }
+            | ActivityItem::Liked { at, .. }
+            | ActivityItem::StatusChanged { at, .. } => *at,
+        }
+    }
+}
+
+// --- Request Payloads (Input Schemas) ---
 
 /// CreateProjectRequest
 ///
@@ -89,6 +593,27 @@ pub struct CreateProjectRequest {
     pub cover_image_key: String,
     pub video_key: Option<String>,
     pub report_key: Option<String>,
+    // Optional label -> URL map (repository, live demo, ...).
+    #[serde(default)]
+    pub links: Option<BTreeMap<String, String>>,
+    #[serde(default)]
+    pub supervisor: Option<String>,
+    // One of the configured DEPARTMENTS, matched case-insensitively.
+    #[serde(default)]
+    pub department: Option<String>,
+    // Programme codes (see `Programme`); joint-degree projects list several.
+    #[serde(default)]
+    pub programme_codes: Option<Vec<String>>,
+    // Free-form labels, lower-cased on input; at most 10 of at most 30 characters.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Technologies used, lower-cased and de-duplicated on input; at most 15 of at most 40
+    // characters.
+    #[serde(default)]
+    pub technologies: Vec<String>,
+    // Creates the project even if one with the same title and year exists (otherwise 409).
+    #[serde(default)]
+    pub force: bool,
 }
 
 /// RegisterUserRequest
@@ -117,6 +642,69 @@ pub struct PresignedUrlRequest {
     /// The MIME type, used to constrain the S3 upload to the allowed type (security).
     #[schema(example = "video/mp4")]
     pub file_type: String,
+    /// What the file will be attached to. Inferred from `file_type` when omitted.
+    #[serde(default)]
+    #[ts(optional)]
+    pub purpose: Option<UploadPurpose>,
+}
+
+/// UploadPurpose
+///
+/// The project slot an upload is destined for. Pending-upload limits are counted per purpose.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum UploadPurpose {
+    CoverImage,
+    Video,
+    Report,
+}
+
+impl UploadPurpose {
+    /// Best guess from the MIME type: images are covers, videos are demos, anything else a report.
+    pub fn infer(file_type: &str) -> Self {
+        if file_type.starts_with("image/") {
+            UploadPurpose::CoverImage
+        } else if file_type.starts_with("video/") {
+            UploadPurpose::Video
+        } else {
+            UploadPurpose::Report
+        }
+    }
+
+    /// The value stored in `uploads.purpose`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UploadPurpose::CoverImage => "cover_image",
+            UploadPurpose::Video => "video",
+            UploadPurpose::Report => "report",
+        }
+    }
+}
+
+/// UploadCounts
+///
+/// Internal snapshot of a user's recent upload activity, used by the presign guard.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UploadCounts {
+    /// Presigned URLs issued to the user in the last hour (all purposes).
+    pub last_hour: i64,
+    /// Unexpired uploads for one purpose that no project references yet.
+    pub outstanding: i64,
+}
+
+/// RecentComments
+///
+/// Internal snapshot of a user's recent commenting, used by the comment rate limit. Each comment
+/// posted and each edit counts once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecentComments {
+    /// Comments posted or edited by the user in the last minute, and the oldest of them.
+    pub last_minute: i64,
+    pub oldest_last_minute: Option<DateTime<Utc>>,
+    /// Comments posted or edited by the user in the last day (all projects), and the oldest of them.
+    pub last_day: i64,
+    pub oldest_last_day: Option<DateTime<Utc>>,
 }
 
 /// PresignedUrlResponse
@@ -140,9 +728,19 @@ pub struct CreateCommentRequest {
     pub text: String,
 }
 
+/// UpdateCommentRequest
+///
+/// Input payload for correcting one's own comment (PUT /comments/{id}).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct UpdateCommentRequest {
+    pub text: String,
+}
+
 /// UpdateProjectRequest
 ///
 /// Partial update payload for modifying an existing project (PUT /projects/{id}).
+/// `user_id` is fixed at creation; a payload naming it is rejected (see `extract::ProjectPatch`).
 ///
 /// *Optimization*: Uses `Option<T>` for all fields and `#[serde(skip_serializing_if = "Option::is_none")]`
 /// to efficiently handle partial updates, ensuring only provided fields are included in the JSON payload.
@@ -163,9 +761,478 @@ pub struct UpdateProjectRequest {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub report_key: Option<String>,
+
+    /// Corrects the author's name (also accepted as `author`); must not be blank.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author_name: Option<String>,
+
+    /// Moves the project to another cohort year, within the `academic_years` setting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<i32>,
+
+    /// Replaces the whole link map when provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<BTreeMap<String, String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_waived: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supervisor: Option<String>,
+
+    /// One of the configured DEPARTMENTS, matched case-insensitively.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+
+    /// Replaces the project's whole programme set when provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub programme_codes: Option<Vec<String>>,
+
+    /// Replaces the project's whole tag set when provided (same limits as on create).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
+    /// Replaces the project's technologies when provided (same limits as on create).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub technologies: Option<Vec<String>>,
+}
+
+/// ProjectUpdateResult
+///
+/// Response of PUT /projects/{id}: the project after the update and the request fields that
+/// actually changed it, spelled as in `UpdateProjectRequest`. An empty list means the update
+/// was a no-op (`updated_at` is left alone).
+/// `truncated` and `abstract_length` are filled in by the handler (see `?truncate=true`).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct ProjectUpdateResult {
+    pub project: Project,
+    pub changed_fields: Vec<String>,
+    // True when `?truncate=true` cut the abstract to fit `MAX_ABSTRACT_CHARS`.
+    #[serde(default)]
+    pub truncated: bool,
+    // Characters in the stored abstract, so clients can tell how much was kept.
+    #[serde(default)]
+    pub abstract_length: usize,
+}
+
+/// CreatedProject
+///
+/// Response of POST /projects: the new project, plus whether `?truncate=true` cut its
+/// abstract and how many characters were stored.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct CreatedProject {
+    #[serde(flatten)]
+    pub project: Project,
+    pub truncated: bool,
+    pub abstract_length: usize,
+}
+
+/// ChangedSinceRequest
+///
+/// Input for POST /projects/changed-since. The client sends the time of its last visit and,
+/// optionally, the projects it is currently showing (at most 200).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ChangedSinceRequest {
+    #[ts(type = "string")]
+    pub since: DateTime<Utc>,
+    /// Restricts the check to these projects; omitted means every public project.
+    #[serde(default)]
+    pub ids: Option<Vec<Uuid>>,
+}
+
+/// ChangedSinceResponse
+///
+/// Public projects edited or commented on after `since`. `since` echoes the cutoff that was
+/// actually applied, which is clamped to at most 90 days back.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ChangedSinceResponse {
+    #[ts(type = "string")]
+    pub since: DateTime<Utc>,
+    pub ids: Vec<Uuid>,
+}
+
+/// PublicEventKind
+///
+/// What happened to a project's public visibility. Deleting a public project also counts as
+/// unpublishing it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text")]
+#[ts(export)]
+pub enum PublicEventKind {
+    #[serde(rename = "project.published")]
+    #[sqlx(rename = "project.published")]
+    Published,
+    #[serde(rename = "project.unpublished")]
+    #[sqlx(rename = "project.unpublished")]
+    Unpublished,
+}
+
+/// PublicEvent
+///
+/// One entry of the publication feed (GET /events/public). `project` is the public project
+/// as it is now, so it is only present on `project.published` events whose project is still
+/// public; hidden or deleted projects are never disclosed through older events.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct PublicEvent {
+    pub id: i64,
+    pub kind: PublicEventKind,
+    pub project_id: Uuid,
+    #[ts(type = "string")]
+    pub at: DateTime<Utc>,
+    pub project: Option<Project>,
+}
+
+/// PublicEventsResponse
+///
+/// A page of the publication feed, oldest first. Pass `next_since` as `since` on the next
+/// call; `has_more` means that call will return more events straight away.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct PublicEventsResponse {
+    pub events: Vec<PublicEvent>,
+    #[ts(type = "string")]
+    pub next_since: DateTime<Utc>,
+    pub has_more: bool,
+}
+
+/// CreateProgrammeRequest
+///
+/// Admin payload for adding a programme (POST /admin/programmes). The code is upper-cased;
+/// it must be 2-16 letters, digits or dashes.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct CreateProgrammeRequest {
+    pub code: String,
+    pub name: String,
+}
+
+/// CreateFeedbackRequest
+///
+/// Anonymous feedback on a public project (POST /projects/{id}/feedback). The message must be
+/// 10-1000 characters once trimmed. `captcha_token` is required only when a CAPTCHA secret is
+/// configured.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct CreateFeedbackRequest {
+    pub message: String,
+    // Lets the owner reply; never shown publicly.
+    #[serde(default)]
+    pub contact_email: Option<String>,
+    #[serde(default)]
+    pub captcha_token: Option<String>,
+}
+
+/// ProjectFeedback
+///
+/// One piece of anonymous feedback, as the project owner or an admin reads it
+/// (GET /me/projects/{id}/feedback, GET /admin/feedback).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, Default, PartialEq)]
+#[ts(export)]
+pub struct ProjectFeedback {
+    pub id: i64,
+    pub project_id: Uuid,
+    pub message: String,
+    pub contact_email: Option<String>,
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
 }
 
-/// --- Dashboard & Profile Schemas (Output) ---
+/// MaintenanceMode
+///
+/// Value of the `maintenance_mode` setting. `read_only` is meant for migrations and exports:
+/// reads keep working while writes are refused.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum MaintenanceMode {
+    #[default]
+    Off,
+    ReadOnly,
+}
+
+/// Setting
+///
+/// One runtime setting (GET/PUT /admin/settings/{key}). Keys that were never set report their
+/// default value with no `updated_at`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq)]
+#[ts(export)]
+pub struct Setting {
+    pub key: String,
+    // JSON whose shape depends on the key (timestamp string, enum string, boolean, ...).
+    #[ts(type = "unknown")]
+    #[schema(value_type = Object)]
+    pub value: serde_json::Value,
+    #[ts(type = "string | null")]
+    pub updated_at: Option<DateTime<Utc>>,
+    // Admin who last changed it.
+    pub updated_by: Option<Uuid>,
+}
+
+/// UpdateSettingRequest
+///
+/// Admin payload for PUT /admin/settings/{key}. `value` is validated against the key's
+/// registered rule before it is stored.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct UpdateSettingRequest {
+    #[ts(type = "unknown")]
+    #[schema(value_type = Object)]
+    pub value: serde_json::Value,
+}
+
+/// UpdateStatusRequest
+///
+/// Admin payload for moving a project through review (PUT /admin/projects/{id}/status).
+/// A bare JSON boolean is still accepted for older clients (see `extract::StatusUpdate`).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default, PartialEq)]
+#[ts(export)]
+pub struct UpdateStatusRequest {
+    pub status: ProjectStatus,
+    /// Explanation shown to the owner (e.g. why a project was rejected). Stored with the
+    /// review and sent to the owner in a `status_change` notification.
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// When true, the owner is notified even if no reason is given.
+    #[serde(default)]
+    pub notify_owner: bool,
+}
+
+/// BulkStatusRequest
+///
+/// Admin payload for publishing or hiding many projects at once (POST /admin/projects/bulk_status).
+/// `is_public: true` approves every listed project and `false` rejects them, like the legacy
+/// boolean body of the single-project endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default, PartialEq)]
+#[ts(export)]
+pub struct BulkStatusRequest {
+    pub ids: Vec<Uuid>,
+    pub is_public: bool,
+}
+
+/// CommentsLockRequest
+///
+/// Input payload for locking or unlocking discussion on a project (PUT /projects/{id}/comments-lock).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct CommentsLockRequest {
+    pub locked: bool,
+}
+
+/// SubscriptionState
+///
+/// A user's comment-notification preference for a project (PUT /projects/{id}/subscription).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum SubscriptionState {
+    Subscribed,
+    Muted,
+}
+
+impl SubscriptionState {
+    /// The value stored in `project_subscriptions.state`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubscriptionState::Subscribed => "subscribed",
+            SubscriptionState::Muted => "muted",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Option<Self> {
+        match value {
+            "subscribed" => Some(SubscriptionState::Subscribed),
+            "muted" => Some(SubscriptionState::Muted),
+            _ => None,
+        }
+    }
+}
+
+// --- Dashboard & Profile Schemas (Output) ---
+
+/// VersionInfo
+///
+/// Build metadata of the running binary (GET /version). Fields that were unavailable at
+/// compile time are reported as "unknown".
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct VersionInfo {
+    pub version: String,
+    pub commit: String,
+    pub built_at: String,
+    // The runtime environment ("local" | "production").
+    pub env: String,
+}
+
+/// HealthStatus
+///
+/// Overall verdict of GET /health/deep: `red` when the database is unreachable, `amber` when a
+/// background task has gone too long without a successful pass, `green` otherwise.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum HealthStatus {
+    Green,
+    Amber,
+    Red,
+}
+
+/// TaskHealth
+///
+/// One supervised background task as reported by GET /health/deep. Error messages are only
+/// logged, never exposed here.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq)]
+#[ts(export)]
+pub struct TaskHealth {
+    pub name: String,
+    // Seconds between passes.
+    pub interval_secs: u64,
+    // `None` until the first pass succeeds.
+    #[ts(type = "string | null")]
+    pub last_success: Option<DateTime<Utc>>,
+    // Failed passes since the last success.
+    pub consecutive_failures: u32,
+    // Times the task was restarted after an error or panic since the process started.
+    pub restarts: u64,
+    // No successful pass for several intervals; turns the overall status amber.
+    pub stale: bool,
+}
+
+/// DeepHealth
+///
+/// Response of GET /health/deep: the database round trip and every background task.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct DeepHealth {
+    pub status: HealthStatus,
+    // Whether a trivial query succeeded.
+    pub database: bool,
+    pub tasks: Vec<TaskHealth>,
+}
+
+/// ErrorWindow
+///
+/// How far back GET /admin/errors looks: the last 15 minutes, hour (default) or day.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq, Default)]
+#[ts(export)]
+pub enum ErrorWindow {
+    #[serde(rename = "15m")]
+    FifteenMinutes,
+    #[default]
+    #[serde(rename = "1h")]
+    Hour,
+    #[serde(rename = "24h")]
+    Day,
+}
+
+impl ErrorWindow {
+    pub fn duration(self) -> chrono::Duration {
+        match self {
+            ErrorWindow::FifteenMinutes => chrono::Duration::minutes(15),
+            ErrorWindow::Hour => chrono::Duration::hours(1),
+            ErrorWindow::Day => chrono::Duration::hours(24),
+        }
+    }
+}
+
+/// ErrorSample
+///
+/// One 5xx or 429 response kept by the in-process error log. Only the route template is
+/// recorded (never the concrete path, query or body), so samples carry no personal data.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq)]
+#[ts(export)]
+pub struct ErrorSample {
+    #[ts(type = "string")]
+    pub at: DateTime<Utc>,
+    // Method and matched route template, e.g. "GET /projects/{id}".
+    pub route: String,
+    pub status: u16,
+    // The `ErrorResponse` code, or the snake_cased status reason for bare status responses.
+    pub code: String,
+    // The `x-request-id` of the failed request, for finding it in the logs.
+    pub request_id: Option<String>,
+}
+
+/// ErrorGroup
+///
+/// Errors of one route and code within the requested window.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq)]
+#[ts(export)]
+pub struct ErrorGroup {
+    pub route: String,
+    pub code: String,
+    pub status: u16,
+    pub count: u64,
+    #[ts(type = "string")]
+    pub last_seen: DateTime<Utc>,
+}
+
+/// ErrorSummary
+///
+/// Response of GET /admin/errors: error counts per route and code since `since`, most
+/// frequent first, and the newest samples of that window.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ErrorSummary {
+    pub window: ErrorWindow,
+    #[ts(type = "string")]
+    pub since: DateTime<Utc>,
+    pub total: u64,
+    pub groups: Vec<ErrorGroup>,
+    // Newest first.
+    pub recent: Vec<ErrorSample>,
+}
+
+/// EmbedDocument
+///
+/// oEmbed-style description of a project's embeddable card (GET /embed/projects/{id}?format=json).
+/// `html` is ready-to-paste iframe markup.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct EmbedDocument {
+    // Always "rich".
+    #[serde(rename = "type")]
+    pub embed_type: String,
+    // oEmbed version, always "1.0".
+    pub version: String,
+    pub title: String,
+    pub author_name: String,
+    pub provider_name: String,
+    pub provider_url: String,
+    // The project's page on the portal.
+    pub url: String,
+    pub thumbnail_url: Option<String>,
+    pub html: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// ReadinessCheck
+///
+/// A single, named rule from the submission checklist. `detail` explains a failure
+/// (or confirms a pass) in words the student can act on.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq)]
+#[ts(export)]
+pub struct ReadinessCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// ReadinessReport
+///
+/// Output schema for the submission checklist (GET /me/projects/{id}/readiness).
+/// `ready` is true only when every check passed.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+}
 
 /// AdminDashboardStats
 ///
@@ -180,6 +1247,81 @@ pub struct AdminDashboardStats {
     pub pending_reviews: i64,
 }
 
+/// PublicStats
+///
+/// Landing-page counters (GET /stats/public). Computed over public projects only, so unlike
+/// `AdminDashboardStats` it carries nothing about pending reviews or users.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default, PartialEq)]
+#[ts(export)]
+pub struct PublicStats {
+    pub public_projects: i64,
+    pub distinct_years: i64,
+    pub distinct_supervisors: i64,
+    /// Likes on public projects only.
+    pub total_likes: i64,
+}
+
+/// BulkUpdateResponse
+///
+/// Output schema for admin bulk actions, reporting how many rows were changed.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct BulkUpdateResponse {
+    pub updated: i64,
+}
+
+/// BulkStatusResponse
+///
+/// Output schema for POST /admin/projects/bulk_status: how many projects changed status, and
+/// the requested ids that matched no project.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default, PartialEq)]
+#[ts(export)]
+pub struct BulkStatusResponse {
+    pub updated: i64,
+    pub not_found: Vec<Uuid>,
+}
+
+/// SupervisorStats
+///
+/// One row of the staff supervisor report (GET /admin/reports/supervisors).
+/// Projects with no supervisor are grouped under "Unassigned".
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default, PartialEq)]
+#[ts(export)]
+pub struct SupervisorStats {
+    pub supervisor: String,
+    pub project_count: i64,
+    /// Number of those projects currently visible to the public.
+    pub public_count: i64,
+    /// Mean like count per project (0 when the supervisor's projects have no likes).
+    pub avg_likes: f64,
+    pub total_comments: i64,
+}
+
+/// ProjectExportRow
+///
+/// One line of the coordinator's spreadsheet export (GET /admin/projects/export.csv), covering
+/// every project whatever its status (internal; only ever rendered as CSV).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectExportRow {
+    pub id: Uuid,
+    pub title: String,
+    pub author: String,
+    pub year: i32,
+    pub status: ProjectStatus,
+    pub like_count: i64,
+    pub created_at: DateTime<Utc>,
+    // None if the owner has no profile row.
+    pub owner_email: Option<String>,
+}
+
+/// avatar_url
+///
+/// The generated avatar for a user: a DiceBear image seeded with their ID, so it is stable
+/// without storing anything.
+pub fn avatar_url(id: Uuid) -> String {
+    format!("https://api.dicebear.com/7.x/avataaars/svg?seed={id}")
+}
+
 /// UserProfile
 ///
 /// Output schema for the authenticated user's profile (GET /me).
@@ -203,7 +1345,9 @@ pub struct UserProfile {
 pub struct Comment {
     // Using BigInt (i64) for comment ID due to the high volume potential.
     pub id: i64,
-    pub user_id: Uuid,
+    // The author; null on a deleted comment's placeholder, which must not say who wrote it.
+    #[ts(type = "string | null")]
+    pub user_id: Option<Uuid>,
     pub project_id: Uuid,
     pub comment: String,
     #[ts(type = "string")]
@@ -211,9 +1355,313 @@ pub struct Comment {
     // This field is loaded via a JOIN in the repository query.
     #[sqlx(default)]
     pub author_email: Option<String>,
+    // Hidden after repeated reports until a moderator restores it. Only its author and admins
+    // ever receive such a comment; clients show it with an "under review" marker.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub hidden_pending_review: bool,
+    // When its author last corrected the text (PUT /comments/{id}); `edited` is set from then on.
+    #[sqlx(default)]
+    #[serde(default)]
+    #[ts(type = "string | null")]
+    pub edited_at: Option<DateTime<Utc>>,
+    #[sqlx(default)]
+    #[serde(default)]
+    pub edited: bool,
+    // Deleted by its author or a moderator but kept as a placeholder because later comments
+    // follow it: `comment` is `DELETED_COMMENT_TEXT`, `user_id`, `author_email` and `edited_at`
+    // are null and the flags false.
+    #[sqlx(default)]
+    #[serde(default)]
+    pub is_deleted: bool,
 }
 
-/// --- Notification System Schemas ---
+/// The text a deleted comment is shown with when it stays in its thread as a placeholder.
+pub const DELETED_COMMENT_TEXT: &str = "[deleted]";
+
+/// CommentPage
+///
+/// Response of GET /projects/{id}/comments. Without `limit` or `before` it holds the whole
+/// thread; a page holds older comments oldest first, and `has_more` says that a request with
+/// `before` set to its first comment's id returns more.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct CommentPage {
+    pub comments: Vec<Comment>,
+    pub has_more: bool,
+}
+
+/// PostedComment
+///
+/// Response of POST /projects/{id}/comments: the new comment and, when its notifications were
+/// too many to write in the request, the outbox job that sends them (see GET /admin/outbox/{id}).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct PostedComment {
+    #[serde(flatten)]
+    pub comment: Comment,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub fanout_job_id: Option<i64>,
+}
+
+/// OutboxJob
+///
+/// A queued background job (GET /admin/outbox/{id}). A `pending_fanout` job writes one
+/// notification per recipient in batches; `processed` counts the ones written so far against
+/// the `total` counted when it was queued (recipients who join or mute meanwhile can make the
+/// final figure differ). An `audit_entry` job holds one audit entry whose direct insert failed.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, PartialEq)]
+#[ts(export)]
+pub struct OutboxJob {
+    pub id: i64,
+    pub kind: String,
+    // "pending" until the dispatcher finishes it, then "done".
+    pub status: String,
+    // Kind-specific input; for `pending_fanout`, the project, actor and comment ids; for
+    // `audit_entry`, the `NewAuditEntry`.
+    #[ts(type = "unknown")]
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    pub total: i64,
+    pub processed: i64,
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "string")]
+    pub updated_at: DateTime<Utc>,
+    #[ts(type = "string | null")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// AuditEntry
+///
+/// One admin action in the audit log (GET /admin/audit): who (`actor_id`, null for the system
+/// itself) did `action` to which record, with action-specific `details`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow, PartialEq)]
+#[ts(export)]
+pub struct AuditEntry {
+    pub id: i64,
+    #[ts(type = "string | null")]
+    pub actor_id: Option<Uuid>,
+    // Dotted verb, e.g. "project.status" or "setting.put".
+    pub action: String,
+    // Kind of record acted on, e.g. "project", "comment", "setting".
+    pub target_type: String,
+    pub target_id: Option<String>,
+    #[ts(type = "unknown")]
+    #[schema(value_type = Object)]
+    pub details: serde_json::Value,
+    #[ts(type = "string")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// NewAuditEntry
+///
+/// An audit entry about to be written. Also the payload of an `audit_entry` outbox job, which
+/// carries it when the direct insert fails.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NewAuditEntry {
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<String>,
+    pub details: serde_json::Value,
+}
+
+impl NewAuditEntry {
+    /// An entry by `actor_id` with empty details.
+    pub fn new(actor_id: Option<Uuid>, action: &str, target_type: &str, target_id: impl ToString) -> Self {
+        Self {
+            actor_id,
+            action: action.to_string(),
+            target_type: target_type.to_string(),
+            target_id: Some(target_id.to_string()),
+            details: serde_json::json!({}),
+        }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+/// CreateReportRequest
+///
+/// Input payload for reporting a comment (POST /projects/{id}/comments/{comment_id}/reports, or
+/// POST /comments/{id}/report).
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
+#[ts(export)]
+pub struct CreateReportRequest {
+    // Optional free-text explanation shown to moderators.
+    pub reason: Option<String>,
+}
+
+/// ReportOutcome
+///
+/// Result of filing a report (internal): the comment's distinct pending reports and whether
+/// this report crossed the auto-hide threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReportOutcome {
+    pub pending_reports: i64,
+    pub hidden: bool,
+}
+
+/// AdminComment
+///
+/// A row of the moderators' comment listing (GET /admin/comments): any comment, hidden ones
+/// included, with the title of the project it was left on.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, FromRow)]
+#[ts(export)]
+pub struct AdminComment {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub comment: Comment,
+    pub project_title: String,
+}
+
+/// ReportedComment
+///
+/// An entry of the moderation queue (GET /admin/comments/reported): a comment with pending
+/// reports, how many distinct users filed them, when the latest came in and the reasons given.
+/// It leaves the queue once a moderator restores (dismisses) or deletes the comment.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ReportedComment {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub project_title: String,
+    pub report_count: i64,
+    #[ts(type = "string")]
+    pub last_reported_at: DateTime<Utc>,
+    // Oldest first; reports without a reason are counted but not listed.
+    pub reasons: Vec<String>,
+}
+
+/// StaleDraft
+///
+/// A draft (never submitted, not public) found by a stale-draft sweep.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[ts(export)]
+pub struct StaleDraft {
+    pub project_id: Uuid,
+    pub owner_id: Uuid,
+    pub title: String,
+    // Last edit; a draft is stale once this is older than the sweep's threshold.
+    #[ts(type = "string")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DraftReminderReport
+///
+/// Outcome of one stale-draft maintenance pass (POST /admin/maintenance/draft-reminders).
+/// With `dry_run` the lists show what would happen and nothing is written.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq, Default)]
+#[ts(export)]
+pub struct DraftReminderReport {
+    pub dry_run: bool,
+    /// Owners notified with a "draft_reminder" (once per draft, ever).
+    pub reminded: Vec<StaleDraft>,
+    /// Very old drafts flagged for the orphaned-upload cleanup.
+    pub flagged_for_cleanup: Vec<StaleDraft>,
+}
+
+/// AcademicYears
+///
+/// The cohort years projects may be filed under, inclusive (the `academic_years` setting).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct AcademicYears {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl AcademicYears {
+    pub fn contains(&self, year: i32) -> bool {
+        (self.min..=self.max).contains(&year)
+    }
+}
+
+/// InvalidYearProject
+///
+/// A project whose year lies outside the configured academic years.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[ts(export)]
+pub struct InvalidYearProject {
+    pub project_id: Uuid,
+    pub owner_id: Uuid,
+    pub title: String,
+    pub year: i32,
+}
+
+/// InvalidYearsReport
+///
+/// Response of GET /admin/maintenance/invalid-years: the allowed range and every project
+/// outside it, by year.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[ts(export)]
+pub struct InvalidYearsReport {
+    pub allowed: AcademicYears,
+    pub projects: Vec<InvalidYearProject>,
+}
+
+/// YearRemapRequest
+///
+/// Admin payload for POST /admin/maintenance/invalid-years/remap: moves every project filed
+/// under `from` to `to`, which must be an allowed academic year.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct YearRemapRequest {
+    pub from: i32,
+    pub to: i32,
+}
+
+/// CommentOrder
+///
+/// Sort order for a project's comment thread (GET /projects/{id}/comments?order=...).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum CommentOrder {
+    Oldest,
+    Newest,
+}
+
+/// ProjectSort
+///
+/// Order of the public project listing (GET /projects?sort=...). Ties fall back to newest
+/// first, then id, so page boundaries are stable between requests.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, ToSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ProjectSort {
+    #[default]
+    Newest,
+    Oldest,
+    MostLiked,
+    // Case-insensitive A-Z.
+    Title,
+    // Highest `view_count` first.
+    MostViewed,
+}
+
+impl ProjectSort {
+    pub const ALL: [ProjectSort; 5] = [Self::Newest, Self::Oldest, Self::MostLiked, Self::Title, Self::MostViewed];
+
+    /// The query-string spelling, e.g. `most_liked`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Newest => "newest",
+            Self::Oldest => "oldest",
+            Self::MostLiked => "most_liked",
+            Self::Title => "title",
+            Self::MostViewed => "most_viewed",
+        }
+    }
+}
+
+// --- Notification System Schemas ---
 
 /// Notification
 ///
@@ -232,10 +1680,44 @@ pub struct Notification {
     #[sqlx(rename = "type")]
     pub notification_type: String,
 
+    // Optional free-text context (e.g. the reason given for a status change).
+    pub message: Option<String>,
+
     pub is_read: bool,
     pub created_at: DateTime<Utc>,
 }
 
+/// NewNotification
+///
+/// A notification about to be written (`Repository::create_notification`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewNotification {
+    // Recipient
+    pub user_id: Uuid,
+    pub actor_id: Uuid,
+    pub project_id: Uuid,
+    pub notification_type: String,
+    pub message: Option<String>,
+}
+
+impl NewNotification {
+    /// A notification for `user_id` about `actor_id`'s action on a project, without a message.
+    pub fn new(user_id: Uuid, actor_id: Uuid, project_id: Uuid, notification_type: &str) -> Self {
+        Self {
+            user_id,
+            actor_id,
+            project_id,
+            notification_type: notification_type.to_string(),
+            message: None,
+        }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
 /// NotificationResponse
 ///
 /// Enriched response structure for the Frontend (UI Ready).
@@ -252,14 +1734,54 @@ pub struct NotificationResponse {
     pub project_id: Uuid,
     pub project_title: String,
 
-    // Type: "like" | "comment"
+    // Type: "like" | "comment" | "status_change" | "comment_hidden" | "draft_reminder" | "feedback" | "resubmission" | "ownership_change" | "mention"
     // We send it as "type" in JSON for API compatibility but read it as `notification_type` in Rust.
     #[serde(rename = "type")]
     #[sqlx(rename = "type")]
     pub notification_type: String,
 
+    // Optional context, e.g. the admin's reason for a status change.
+    pub message: Option<String>,
+
     pub is_read: bool,
 
     #[ts(type = "string")]
     pub created_at: DateTime<Utc>,
 }
+
+// --- Pagination Envelope ---
+
+/// PageLinks
+///
+/// Absolute URLs for navigating a paginated listing. Each keeps the caller's original query
+/// parameters (filters, `fields`, ...) and swaps only `page`. `next` is omitted on the last
+/// page and `prev` on the first.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default, PartialEq)]
+#[ts(export)]
+pub struct PageLinks {
+    #[serde(rename = "self")]
+    #[ts(rename = "self")]
+    pub self_link: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub next: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub prev: Option<String>,
+}
+
+/// Paginated
+///
+/// Envelope for a single page of a listing. `page` is 1-based; `total_count` counts every
+/// matching item, not just this page. `next_cursor` continues after the last item (keyset
+/// pagination) and is `null` once a page comes back short.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub total_count: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub links: PageLinks,
+    pub next_cursor: Option<String>,
+}
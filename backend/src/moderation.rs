@@ -0,0 +1,147 @@
+/// Elements dropped together with their content: what is inside them is code or embedded
+/// documents, not text anyone typed for readers.
+const DROPPED_ELEMENTS: [&str; 7] = ["script", "style", "iframe", "object", "embed", "template", "noscript"];
+
+/// sanitize_comment
+///
+/// Makes comment text inert before it is stored: invisible formatting characters are removed
+/// first (so they cannot split a tag name), then every HTML tag and comment. Script-like
+/// elements (`DROPPED_ELEMENTS`) lose their content too; other tags are removed and their text
+/// kept, so `<b>great</b> work` becomes `great work`. Stripping repeats until nothing changes,
+/// which stops fragments such as `<<b>script>` from reassembling into a tag. A `<` that does
+/// not open a tag (`x < y`) and character references like `&lt;` are left as typed: the result
+/// is plain text that clients must still escape when rendering.
+pub fn sanitize_comment(raw: &str) -> String {
+    let mut text = strip_invisible(raw);
+    loop {
+        let stripped = strip_markup(&text);
+        if stripped == text {
+            return text;
+        }
+        text = stripped;
+    }
+}
+
+/// strip_invisible
+///
+/// Drops control characters other than newline and tab, and the invisible characters used to
+/// disguise text: bidirectional overrides and isolates (the "Trojan Source" characters), the
+/// zero-width space, word joiner and byte order mark. Joiners that shape emoji and scripts
+/// (U+200C, U+200D) and the left-to-right/right-to-left marks are kept.
+fn strip_invisible(raw: &str) -> String {
+    raw.chars()
+        .filter(|&c| {
+            let control = c.is_control() && c != '\n' && c != '\t';
+            let disguise = matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{200B}' | '\u{2060}' | '\u{FEFF}');
+            !(control || disguise)
+        })
+        .collect()
+}
+
+/// strip_markup
+///
+/// One pass of tag removal over `text`. A `<` starts a tag when followed by a letter, `/`,
+/// `!` or `?`; the tag runs to the first `>` outside a quoted attribute value. A tag that is
+/// never closed loses only its `<`, which leaves the rest harmless text.
+fn strip_markup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let tag = &rest[start..];
+        let opens_tag = tag[1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'));
+        if !opens_tag {
+            out.push('<');
+            rest = &tag[1..];
+            continue;
+        }
+        let Some(end) = tag_end(tag) else {
+            rest = &tag[1..];
+            continue;
+        };
+        rest = &tag[end..];
+        let name = tag_name(tag);
+        if !tag.starts_with("</") && DROPPED_ELEMENTS.contains(&name.as_str()) {
+            rest = skip_element_content(rest, &name);
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Byte offset just past the `>` closing the tag at the start of `tag`, if there is one.
+/// `<!-- ... -->` comments end at `-->`.
+fn tag_end(tag: &str) -> Option<usize> {
+    if let Some(body) = tag.strip_prefix("<!--") {
+        return body.find("-->").map(|i| 4 + i + 3);
+    }
+    let mut quote = None;
+    for (i, c) in tag.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i + 1),
+            (None, _) => {}
+        }
+    }
+    None
+}
+
+/// The lower-cased element name of a tag, e.g. `script` for `<SCRIPT src=x>` or `</script >`.
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('<')
+        .trim_start_matches('/')
+        .chars()
+        .take_while(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// What follows the content of a dropped element: the text after its closing tag, or nothing
+/// when the element is never closed.
+fn skip_element_content<'a>(rest: &'a str, name: &str) -> &'a str {
+    let closing = format!("</{name}");
+    let Some(at) = find_ignoring_ascii_case(rest, &closing) else {
+        return "";
+    };
+    let after = &rest[at..];
+    tag_end(after).map_or("", |end| &after[end..])
+}
+
+/// Byte offset of the first match of the ASCII `needle` in `haystack`, ignoring ASCII case,
+/// without copying `haystack`. A match starts with an ASCII byte, so it is a char boundary.
+fn find_ignoring_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// extract_mentions
+///
+/// The lower-cased email prefixes mentioned in `text`, in order of first appearance and without
+/// repeats: `@alice` mentions whoever's email starts `alice@`. A mention is an `@` at the start
+/// or after a character that cannot be part of an email, followed by the characters an email's
+/// local part may use (letters, digits, `.`, `_`, `%`, `+`, `-`). Trailing dots are sentence
+/// punctuation (`thanks @alice.`), and the `@` inside `bob@ul.ie` is not a mention.
+pub fn extract_mentions(text: &str) -> Vec<String> {
+    let local_part = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-');
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous = None;
+    for (i, c) in text.char_indices() {
+        if c == '@' && !previous.is_some_and(|p: char| local_part(p) || p == '@') {
+            let rest = &text[i + 1..];
+            let end = rest.find(|c: char| !local_part(c)).unwrap_or(rest.len());
+            let prefix = rest[..end].trim_end_matches('.').to_ascii_lowercase();
+            if !prefix.is_empty() && !mentions.contains(&prefix) {
+                mentions.push(prefix);
+            }
+        }
+        previous = Some(c);
+    }
+    mentions
+}
@@ -0,0 +1,53 @@
+use axum::{
+    extract::Request,
+    http::{Uri, uri::PathAndQuery},
+};
+
+/// Path prefix whose trailing slash is significant: Swagger UI redirects `/swagger-ui` to
+/// `/swagger-ui/`, so stripping it there would loop.
+const TRAILING_SLASH_EXEMPT: &str = "/swagger-ui";
+
+/// normalize_path
+///
+/// Collapses runs of `/` into one and strips a trailing `/` (except for the root and the
+/// Swagger UI). Returns `None` when the path is already normal. Percent-encoded slashes
+/// (`%2F`) are data, not separators, and are left alone.
+pub fn normalize_path(path: &str) -> Option<String> {
+    let mut normalized = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c == '/' && normalized.ends_with('/') {
+            continue;
+        }
+        normalized.push(c);
+    }
+    if normalized.len() > 1
+        && normalized.ends_with('/')
+        && !normalized.starts_with(TRAILING_SLASH_EXEMPT)
+    {
+        normalized.pop();
+    }
+    (normalized != path).then_some(normalized)
+}
+
+/// normalize_request
+///
+/// `map_request` middleware applied in front of the router (see `create_router`), so that
+/// `/projects/`, `//projects` and `/projects//{id}` reach the same handlers as their canonical
+/// forms. Only the path changes; the query string and any scheme/authority are kept as-is.
+pub async fn normalize_request(mut request: Request) -> Request {
+    let Some(path) = normalize_path(request.uri().path()) else {
+        return request;
+    };
+    let path_and_query = match request.uri().query() {
+        Some(query) => format!("{}?{}", path, query),
+        None => path,
+    };
+    let mut parts = request.uri().clone().into_parts();
+    parts.path_and_query = PathAndQuery::try_from(path_and_query).ok();
+    if parts.path_and_query.is_some()
+        && let Ok(uri) = Uri::from_parts(parts)
+    {
+        *request.uri_mut() = uri;
+    }
+    request
+}
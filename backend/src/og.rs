@@ -0,0 +1,156 @@
+use crate::{
+    config::AppConfig,
+    models::{Project, UploadPurpose},
+    repository::Repository,
+    storage::StorageService,
+};
+
+/// Maximum length (in characters) of the description shown in link previews.
+const EXCERPT_MAX_CHARS: usize = 200;
+
+/// escape_html
+///
+/// Escapes a value for safe interpolation into HTML text *and* double/single-quoted
+/// attribute values. Every user-controlled field in a preview document goes through this.
+pub fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#x27;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// excerpt
+///
+/// Builds the short, plain-text description used in link previews: whitespace is collapsed,
+/// blocked words are masked with asterisks (case-insensitive, whole words only), and the
+/// result is cut at a word boundary with an ellipsis once it exceeds `max_chars`.
+pub fn excerpt(text: &str, max_chars: usize, blocked_words: &[String]) -> String {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| mask_word(word, blocked_words))
+        .collect();
+
+    let mut out = String::new();
+    for word in words {
+        let needed = if out.is_empty() { 0 } else { 1 } + word.chars().count();
+        if out.chars().count() + needed > max_chars {
+            if out.is_empty() {
+                // A single enormous "word" (e.g. a URL) is hard-cut instead of dropped.
+                out = word.chars().take(max_chars).collect();
+            }
+            out.push('…');
+            return out;
+        }
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(&word);
+    }
+    out
+}
+
+/// Masks the alphanumeric core of `word` if it matches a blocked word, keeping punctuation.
+fn mask_word(word: &str, blocked_words: &[String]) -> String {
+    let core = word.trim_matches(|c: char| !c.is_alphanumeric());
+    if core.is_empty() || !blocked_words.iter().any(|b| b.eq_ignore_ascii_case(core)) {
+        return word.to_string();
+    }
+    word.replacen(core, &"*".repeat(core.chars().count()), 1)
+}
+
+/// media_url
+///
+/// Resolves a stored object key to a URL third parties can fetch, through
+/// `StorageService::get_media_url`. Absolute URLs are passed through; empty keys, and keys the
+/// store cannot sign, yield `None` (the preview then simply has no image).
+pub async fn media_url(storage: &dyn StorageService, key: &str) -> Option<String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    if key.starts_with("http://") || key.starts_with("https://") {
+        return Some(key.to_string());
+    }
+    match storage.get_media_url(key.trim_start_matches('/')).await {
+        Ok(url) => Some(url.into_inner()),
+        Err(e) => {
+            tracing::warn!("media_url: no URL for a preview image: {}", e);
+            None
+        }
+    }
+}
+
+/// cover_url
+///
+/// The preview image for a public project: its cover, through `media_url`. A stored key is
+/// only signed when it is one of the owner's recorded cover uploads. Every upload lives under
+/// the same prefix, so without this an owner could point their cover at someone else's
+/// private report and have the public page hand out a signed URL for it.
+pub async fn cover_url(repo: &dyn Repository, storage: &dyn StorageService, project: &Project) -> Option<String> {
+    let key = project.cover_image.trim().trim_start_matches('/');
+    let is_key = !key.is_empty() && !key.starts_with("http://") && !key.starts_with("https://");
+    if is_key && !repo.is_own_upload(project.user_id, key, UploadPurpose::CoverImage).await {
+        tracing::warn!(project_id = %project.id, "cover_url: cover is not one of the owner's uploads");
+        return None;
+    }
+    media_url(storage, key).await
+}
+
+/// render_project
+///
+/// Renders the minimal HTML document served to link-preview crawlers for a public project:
+/// Open Graph and Twitter card tags plus a canonical link. Human visitors who land here are
+/// sent on to the SPA page via a meta refresh. `image` is the cover's `media_url`.
+pub fn render_project(project: &Project, config: &AppConfig, image: Option<&str>) -> String {
+    let canonical = format!(
+        "{}/projects/{}",
+        config.public_site_url.trim_end_matches('/'),
+        project.id
+    );
+    let title = escape_html(&project.title);
+    let description = escape_html(&excerpt(
+        &project.abstract_text,
+        EXCERPT_MAX_CHARS,
+        &config.excerpt_blocked_words,
+    ));
+    let canonical = escape_html(&canonical);
+    let image = image.map(escape_html);
+
+    let mut meta = vec![
+        r#"<meta property="og:type" content="article">"#.to_string(),
+        format!(r#"<meta property="og:title" content="{title}">"#),
+        format!(r#"<meta property="og:description" content="{description}">"#),
+        format!(r#"<meta property="og:url" content="{canonical}">"#),
+        format!(r#"<meta name="twitter:title" content="{title}">"#),
+        format!(r#"<meta name="twitter:description" content="{description}">"#),
+    ];
+    match &image {
+        Some(url) => {
+            meta.push(format!(r#"<meta property="og:image" content="{url}">"#));
+            meta.push(format!(r#"<meta name="twitter:image" content="{url}">"#));
+            meta.push(r#"<meta name="twitter:card" content="summary_large_image">"#.to_string());
+        }
+        None => meta.push(r#"<meta name="twitter:card" content="summary">"#.to_string()),
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<meta name=\"description\" content=\"{description}\">\n{meta}\n<link rel=\"canonical\" href=\"{canonical}\">\n<meta http-equiv=\"refresh\" content=\"0; url={canonical}\">\n</head>\n<body><a href=\"{canonical}\">{title}</a></body>\n</html>\n",
+        meta = meta.join("\n"),
+    )
+}
+
+/// render_not_found
+///
+/// The shell returned for missing or private projects. Deliberately carries no metadata so
+/// nothing about a hidden project leaks into previews.
+pub fn render_not_found() -> String {
+    "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Not Found</title>\n<meta name=\"robots\" content=\"noindex\">\n</head>\n<body></body>\n</html>\n".to_string()
+}
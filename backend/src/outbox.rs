@@ -0,0 +1,68 @@
+use crate::{maintenance::MaintenanceTask, models::OutboxJob, repository::{RepositoryError, RepositoryState}};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Recipients one dispatcher batch notifies, each batch in its own transaction.
+pub const FANOUT_BATCH_SIZE: i64 = 1_000;
+
+/// OutboxDispatcher
+///
+/// Works through queued `pending_fanout` jobs, oldest first, as a task for
+/// `maintenance::spawn_runner`. Each batch commits its notifications together with the job's
+/// cursor, so a dispatcher stopped at any point is resumed by the next one without notifying
+/// anyone twice, and several instances can dispatch side by side.
+pub struct OutboxDispatcher {
+    repo: RepositoryState,
+    batch_size: i64,
+}
+
+impl OutboxDispatcher {
+    pub fn new(repo: RepositoryState) -> Self {
+        Self { repo, batch_size: FANOUT_BATCH_SIZE }
+    }
+
+    /// Overrides `FANOUT_BATCH_SIZE`, so tests can watch a job progress over a few recipients.
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Runs one batch of the oldest pending job; None when nothing is pending.
+    pub async fn run_batch(&self) -> Option<OutboxJob> {
+        self.repo.run_fanout_batch(self.batch_size).await
+    }
+
+    /// drain
+    ///
+    /// Runs batches until no job is pending and returns how many ran. A repository error ends
+    /// the pass early; the job is picked up again from its cursor on the next one.
+    pub async fn drain(&self) -> usize {
+        let mut batches = 0;
+        while let Some(job) = self.run_batch().await {
+            batches += 1;
+            if job.status == "done" {
+                tracing::info!(job = job.id, notified = job.processed, "Fan-out job finished");
+            }
+        }
+        batches
+    }
+}
+
+#[async_trait]
+impl MaintenanceTask for OutboxDispatcher {
+    fn name(&self) -> &'static str {
+        "outbox_dispatcher"
+    }
+
+    /// Fails when the database is unreachable: the batches themselves cannot tell an outage
+    /// from an empty queue. Audit entries queued by `audit::record` are moved in the same pass.
+    async fn run(&self, _now: DateTime<Utc>) -> Result<(), RepositoryError> {
+        self.repo.ping().await?;
+        self.drain().await;
+        let moved = self.repo.flush_audit_outbox().await?;
+        if moved > 0 {
+            tracing::info!(moved, "Queued audit entries written");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{error::ApiError, models::Project};
+
+/// Page size used when the client does not send `per_page`.
+pub const DEFAULT_PER_PAGE: i64 = 20;
+/// Largest accepted `per_page`; bigger pages are rejected rather than silently clamped.
+pub const MAX_PER_PAGE: i64 = 100;
+
+/// Cursor
+///
+/// A keyset position in a listing ordered by `(created_at, id)` descending: the next page
+/// starts strictly after this row. Unlike an offset it does not shift when rows are inserted
+/// or approved while a client is scrolling. Clients treat the encoded form as opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    /// The position just after `project`.
+    pub fn after(project: &Project) -> Self {
+        Self {
+            created_at: project.created_at,
+            id: project.id,
+        }
+    }
+
+    /// Hex of `<created_at in microseconds>:<id>`; microseconds match Postgres precision.
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.created_at.timestamp_micros(), self.id.simple())
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// decode
+    ///
+    /// Reverses `encode`. Anything else is a 400 `invalid_cursor` rather than an empty page,
+    /// so a corrupted or hand-edited cursor is noticed.
+    pub fn decode(raw: &str) -> Result<Self, ApiError> {
+        Self::try_decode(raw.trim()).ok_or_else(|| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_cursor",
+                "`cursor` is not a cursor returned by this listing.",
+            )
+        })
+    }
+
+    fn try_decode(raw: &str) -> Option<Self> {
+        if !raw.len().is_multiple_of(2) {
+            return None;
+        }
+        let bytes = (0..raw.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(raw.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let text = String::from_utf8(bytes).ok()?;
+        let (micros, id) = text.split_once(':')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: Uuid::try_parse(id).ok()?,
+        })
+    }
+}
+
+/// PageRequest
+///
+/// A validated page selection for paginated listings: either a 1-based offset page, or
+/// (keyset mode) the page starting `after` a cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    pub page: i64,
+    pub per_page: i64,
+    pub after: Option<Cursor>,
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self {
+            page: 1,
+            per_page: DEFAULT_PER_PAGE,
+            after: None,
+        }
+    }
+}
+
+impl PageRequest {
+    /// parse
+    ///
+    /// Validates the raw `page`, `per_page` and `cursor` query values. The numbers are taken
+    /// as strings so that non-numeric input gets the same 422 `invalid_pagination` body as
+    /// out-of-range numbers, instead of the extractor's plain-text 400. A cursor replaces the
+    /// page number, so sending both is rejected.
+    pub fn parse(page: Option<&str>, per_page: Option<&str>, cursor: Option<&str>) -> Result<Self, ApiError> {
+        let defaults = Self::default();
+        let cursor = cursor.map(str::trim).filter(|c| !c.is_empty());
+        if cursor.is_some() && page.is_some_and(|p| !p.trim().is_empty()) {
+            return Err(ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "invalid_pagination",
+                "`page` cannot be combined with `cursor`.",
+            )
+            .with_details(serde_json::json!({ "field": "page" })));
+        }
+        Ok(Self {
+            page: parse_bounded("page", page, defaults.page, i64::MAX)?,
+            per_page: parse_bounded("per_page", per_page, defaults.per_page, MAX_PER_PAGE)?,
+            after: cursor.map(Cursor::decode).transpose()?,
+        })
+    }
+
+    /// Rows to skip for this page. Always zero in keyset mode, where the cursor does the skipping.
+    pub fn offset(&self) -> i64 {
+        if self.after.is_some() {
+            return 0;
+        }
+        (self.page - 1).saturating_mul(self.per_page)
+    }
+
+    /// next_cursor
+    ///
+    /// The cursor for the page after `items` (this page's rows, in listing order), or `None`
+    /// when the page was not full and so nothing can follow.
+    pub fn next_cursor(&self, items: &[Project]) -> Option<String> {
+        if (items.len() as i64) < self.per_page {
+            return None;
+        }
+        items.last().map(|last| Cursor::after(last).encode())
+    }
+}
+
+fn parse_bounded(name: &str, raw: Option<&str>, default: i64, max: i64) -> Result<i64, ApiError> {
+    let Some(raw) = raw.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Ok(default);
+    };
+    match raw.parse::<i64>() {
+        Ok(value) if (1..=max).contains(&value) => Ok(value),
+        _ => Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "invalid_pagination",
+            format!("`{}` must be an integer between 1 and {}.", name, max),
+        )
+        .with_details(serde_json::json!({ "field": name, "min": 1, "max": max }))),
+    }
+}
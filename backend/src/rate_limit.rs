@@ -0,0 +1,237 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::error::ApiError;
+
+/// Paths that are never rate limited (monitoring probes, metrics scrapes and API documentation).
+const EXEMPT_PREFIXES: [&str; 4] = ["/health", "/metrics", "/swagger-ui", "/api-docs"];
+
+/// Most clients one limiter keeps a bucket for. A client arriving when the map is full first
+/// triggers a sweep of refilled buckets, then evicts the least recently seen one.
+pub const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// RateLimitStatus
+///
+/// Snapshot of a client's token bucket taken while the limiter lock is held.
+/// This is what both the `X-RateLimit-*` headers and the 429 body are built from,
+/// so no second lookup is needed after the handler runs.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitStatus {
+    /// Whether the current request consumed a token.
+    pub allowed: bool,
+    /// Bucket capacity (requests per window).
+    pub limit: u32,
+    /// Whole tokens left after this request.
+    pub remaining: u32,
+    /// Unix timestamp (seconds) at which the bucket will be full again.
+    pub reset: u64,
+    /// Whole seconds until the next token, at least 1 (used for `Retry-After`). The bucket is
+    /// full again only at `reset`, but a client may try again as soon as one token is back.
+    pub retry_after: u64,
+}
+
+impl RateLimitStatus {
+    /// Writes the standard `X-RateLimit-*` headers onto a response. Headers already set by an
+    /// inner, route-specific limiter are kept: that is the budget the client runs out of first.
+    fn apply(&self, headers: &mut HeaderMap) {
+        if headers.contains_key("x-ratelimit-limit") {
+            return;
+        }
+        headers.insert("x-ratelimit-limit", HeaderValue::from(self.limit));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from(self.remaining));
+        headers.insert("x-ratelimit-reset", HeaderValue::from(self.reset));
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    /// Whether the bucket would be full by `now`, i.e. indistinguishable from a fresh one.
+    fn is_full_at(&self, now: Instant, capacity: f64, refill_per_sec: f64) -> bool {
+        self.tokens + now.duration_since(self.last_refill).as_secs_f64() * refill_per_sec >= capacity
+    }
+}
+
+struct Buckets {
+    by_client: HashMap<String, Bucket>,
+    last_sweep: Instant,
+}
+
+/// RateLimiter
+///
+/// An in-process token bucket limiter keyed by client address. Each client gets `limit`
+/// tokens which refill continuously over `window`. A single mutex guards the bucket map;
+/// every request takes it exactly once. Buckets that have refilled are dropped once per
+/// window, and the map never holds more than `MAX_TRACKED_CLIENTS` of them.
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    trusted_proxy_hops: usize,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit: limit.max(1),
+            window,
+            trusted_proxy_hops: 0,
+            buckets: Mutex::new(Buckets { by_client: HashMap::new(), last_sweep: Instant::now() }),
+        }
+    }
+
+    /// Keys clients by the `X-Forwarded-For` hop appended by the outermost of `hops` trusted
+    /// reverse proxies instead of the socket peer (see `client_key`). Zero, the default, means
+    /// the header is ignored.
+    pub fn with_trusted_proxy_hops(mut self, hops: usize) -> Self {
+        self.trusted_proxy_hops = hops;
+        self
+    }
+
+    /// Number of clients currently holding a bucket.
+    pub fn tracked_clients(&self) -> usize {
+        self.buckets.lock().unwrap_or_else(|e| e.into_inner()).by_client.len()
+    }
+
+    /// Refills the caller's bucket, attempts to take one token, and reports the resulting state.
+    pub fn check(&self, key: &str) -> RateLimitStatus {
+        let now = Instant::now();
+        let capacity = self.limit as f64;
+        let refill_per_sec = capacity / self.window.as_secs_f64().max(f64::EPSILON);
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let Buckets { by_client, last_sweep } = &mut *buckets;
+        let at_capacity = by_client.len() >= MAX_TRACKED_CLIENTS && !by_client.contains_key(key);
+        if at_capacity || now.duration_since(*last_sweep) >= self.window {
+            // A full bucket is the same as no bucket, so dropping it loses nothing.
+            by_client.retain(|_, bucket| !bucket.is_full_at(now, capacity, refill_per_sec));
+            *last_sweep = now;
+        }
+        if at_capacity && by_client.len() >= MAX_TRACKED_CLIENTS {
+            let oldest = by_client.iter().min_by_key(|(_, bucket)| bucket.last_refill).map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                by_client.remove(&oldest);
+            }
+        }
+        let bucket = by_client.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.last_refill = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        let seconds_to_full = ((capacity - bucket.tokens) / refill_per_sec).ceil() as u64;
+        let seconds_to_token = ((1.0 - bucket.tokens) / refill_per_sec).ceil().max(1.0) as u64;
+
+        RateLimitStatus {
+            allowed,
+            limit: self.limit,
+            remaining: bucket.tokens.floor() as u32,
+            reset: unix_now() + seconds_to_full,
+            retry_after: seconds_to_token,
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// client_key
+///
+/// Identifies the caller for bucketing. Behind `trusted_proxy_hops` reverse proxies, this is
+/// the `X-Forwarded-For` hop that many entries from the end: the address the outermost proxy
+/// saw, which the client cannot forge (everything left of it can be). Otherwise, or when the
+/// header is shorter than that, it is the socket peer address, and finally a shared "unknown"
+/// bucket.
+fn client_key(request: &Request, trusted_proxy_hops: usize) -> String {
+    if trusted_proxy_hops > 0 {
+        let hops: Vec<&str> = request
+            .headers()
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .collect();
+        if let Some(forwarded) = hops
+            .len()
+            .checked_sub(trusted_proxy_hops)
+            .map(|i| hops[i])
+            .filter(|v| !v.is_empty())
+        {
+            return forwarded.to_string();
+        }
+    }
+
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// rate_limit
+///
+/// Middleware enforcing the per-client token bucket. Exempt paths bypass the limiter entirely
+/// (no headers). Every other response carries `X-RateLimit-Limit`, `X-RateLimit-Remaining`
+/// and `X-RateLimit-Reset`; exhausted clients receive a 429 with `Retry-After` and the same
+/// counters in the JSON body.
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if EXEMPT_PREFIXES.iter().any(|prefix| path.starts_with(prefix)) {
+        return next.run(request).await;
+    }
+
+    let status = limiter.check(&client_key(&request, limiter.trusted_proxy_hops));
+
+    let mut response = if status.allowed {
+        next.run(request).await
+    } else {
+        let mut rejected = ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "rate_limited",
+            "Too many requests. Please slow down.",
+        )
+        .with_details(serde_json::json!({
+            "limit": status.limit,
+            "remaining": status.remaining,
+            "reset": status.reset,
+        }))
+        .into_response();
+        rejected
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(status.retry_after));
+        rejected
+    };
+
+    status.apply(response.headers_mut());
+    response
+}
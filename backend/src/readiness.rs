@@ -0,0 +1,143 @@
+use crate::models::{Project, ReadinessCheck, ReadinessReport};
+
+/// Minimum abstract length (in whitespace-separated words) accepted for review.
+pub const MIN_ABSTRACT_WORDS: usize = 100;
+
+// Stable check names; the frontend keys its checklist UI off these.
+pub const CHECK_COVER_IMAGE: &str = "cover_image";
+pub const CHECK_ABSTRACT_LENGTH: &str = "abstract_length";
+pub const CHECK_VIDEO: &str = "video";
+pub const CHECK_LINKS: &str = "links";
+pub const CHECK_COHORT_YEAR: &str = "cohort_year";
+
+fn check(name: &str, passed: bool, detail: impl Into<String>) -> ReadinessCheck {
+    ReadinessCheck {
+        name: name.to_string(),
+        passed,
+        detail: detail.into(),
+    }
+}
+
+/// check_cover_image
+///
+/// The cover key must be set *and* confirmed to exist in storage (a presigned URL was issued
+/// but the upload may never have completed). `in_storage` is the result of that lookup.
+pub fn check_cover_image(project: &Project, in_storage: bool) -> ReadinessCheck {
+    if project.cover_image.trim().is_empty() {
+        check(CHECK_COVER_IMAGE, false, "No cover image has been uploaded.")
+    } else if !in_storage {
+        check(
+            CHECK_COVER_IMAGE,
+            false,
+            "The cover image could not be found in storage. Please upload it again.",
+        )
+    } else {
+        check(CHECK_COVER_IMAGE, true, "Cover image uploaded.")
+    }
+}
+
+/// check_abstract_length
+///
+/// Requires at least `MIN_ABSTRACT_WORDS` words in the abstract.
+pub fn check_abstract_length(project: &Project) -> ReadinessCheck {
+    let words = project.abstract_text.split_whitespace().count();
+    check(
+        CHECK_ABSTRACT_LENGTH,
+        words >= MIN_ABSTRACT_WORDS,
+        format!(
+            "Abstract has {} of at least {} words.",
+            words, MIN_ABSTRACT_WORDS
+        ),
+    )
+}
+
+/// check_video
+///
+/// Requires a demo video, unless the owner has explicitly waived it.
+pub fn check_video(project: &Project) -> ReadinessCheck {
+    let has_video = project
+        .video
+        .as_deref()
+        .is_some_and(|key| !key.trim().is_empty());
+
+    match (has_video, project.video_waived) {
+        (true, _) => check(CHECK_VIDEO, true, "Demo video uploaded."),
+        (false, true) => check(CHECK_VIDEO, true, "Demo video explicitly waived."),
+        (false, false) => check(
+            CHECK_VIDEO,
+            false,
+            "Upload a demo video or mark the video as waived.",
+        ),
+    }
+}
+
+/// check_links
+///
+/// Requires at least one non-empty entry in the project's link map.
+pub fn check_links(project: &Project) -> ReadinessCheck {
+    let count = project
+        .links
+        .as_object()
+        .map(|links| {
+            links
+                .values()
+                .filter(|url| url.as_str().is_some_and(|u| !u.trim().is_empty()))
+                .count()
+        })
+        .unwrap_or(0);
+
+    if count > 0 {
+        check(CHECK_LINKS, true, format!("{} link(s) provided.", count))
+    } else {
+        check(
+            CHECK_LINKS,
+            false,
+            "Add at least one link (e.g. source repository or live demo).",
+        )
+    }
+}
+
+/// check_cohort_year
+///
+/// Only projects for the cohort currently being assessed may be submitted.
+pub fn check_cohort_year(project: &Project, current_cohort_year: i32) -> ReadinessCheck {
+    if project.year == current_cohort_year {
+        check(
+            CHECK_COHORT_YEAR,
+            true,
+            format!("Year matches the {} cohort.", current_cohort_year),
+        )
+    } else {
+        check(
+            CHECK_COHORT_YEAR,
+            false,
+            format!(
+                "Project year {} does not match the current cohort ({}).",
+                project.year, current_cohort_year
+            ),
+        )
+    }
+}
+
+/// evaluate
+///
+/// Runs every rule and assembles the report. The cover image storage lookup is performed
+/// by the caller so this stays synchronous and testable without HTTP or S3.
+pub fn evaluate(
+    project: &Project,
+    cover_in_storage: bool,
+    current_cohort_year: i32,
+) -> ReadinessReport {
+    let checks = vec![
+        check_cover_image(project, cover_in_storage),
+        check_abstract_length(project),
+        check_video(project),
+        check_links(project),
+        check_cohort_year(project, current_cohort_year),
+    ];
+
+    ReadinessReport {
+        ready: checks.iter().all(|c| c.passed),
+        checks,
+    }
+}
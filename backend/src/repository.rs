@@ -1,8 +1,133 @@
-use crate::models::{AdminDashboardStats, CreateProjectRequest, Project, User, Like, Comment, UpdateProjectRequest};
+use crate::slug;
+use crate::pagination::PageRequest;
+use crate::models::{DELETED_COMMENT_TEXT, AdminDashboardStats, CreateProjectRequest, Project, User, Like, Comment, UpdateCommentRequest, CommentEditOutcome, UpdateProjectRequest, SupervisorStats, SubscriptionState, PublicStats, Programme, CommentOrder, UploadCounts, UploadPurpose, RecentComments, ProjectStatus, ProjectReview, ReportOutcome, StaleDraft, ProjectSort, TagCount, Setting, ProjectFeedback, LikeOutcome, ProjectUpdateResult, YearCount, PostedComment, OutboxJob, ActivityItem, InvalidYearProject, ResubmitOutcome, BulkStatusOutcome, ProjectExportRow, PublicEvent, PublicEventKind, MyProjectSummary, AuditEntry, NewAuditEntry, DepartmentCount, ProjectOwner, OwnerTransferOutcome, ProjectStats, DailyCount, PendingReview, TechnologyCount, ProjectLiker, NewNotification, Notification, ReportedComment, AdminComment};
 use async_trait::async_trait;
+use futures::{StreamExt, stream::BoxStream};
 use sqlx::{PgPool, query_builder::QueryBuilder};
 use uuid::Uuid;
-use std::sync::Arc;
+use std::{collections::{BTreeMap, BTreeSet, HashMap, HashSet}, fmt, sync::{Arc, Mutex}, time::{Duration, Instant}};
+
+pub mod counting;
+pub mod priority;
+#[cfg(feature = "test-util")]
+pub mod memory;
+
+/// RepositoryError
+///
+/// A persistence failure reported to the caller. Most repository methods log and fall back to
+/// an empty result instead; streams cannot, since a failure mid-way must end the response, and
+/// neither can the settings load, where an empty result would reset every setting.
+#[derive(Debug)]
+pub struct RepositoryError(pub String);
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for RepositoryError {}
+
+impl From<sqlx::Error> for RepositoryError {
+    fn from(e: sqlx::Error) -> Self {
+        Self(e.to_string())
+    }
+}
+
+/// HiddenComments
+///
+/// Which comments held for review (`hidden_pending_review`) a comment listing includes:
+/// none for the public, the viewer's own for signed-in users, all for moderators. It also
+/// decides whose threads can be read at all, as in `get_project_authorized`: public projects
+/// for everyone, also the viewer's own projects, and every project for moderators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HiddenComments {
+    Exclude,
+    AuthoredBy(Uuid),
+    Include,
+}
+
+/// CommentCursor
+///
+/// Where a page of comments starts (`before` or `after` on GET /projects/{id}/comments): past a
+/// comment id the client already shows, or past a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentCursor {
+    Id(i64),
+    Time(chrono::DateTime<chrono::Utc>),
+}
+
+/// ProjectViewer
+///
+/// Who is asking for a project's detail view, which decides whether they may see it: public
+/// projects for everyone, also their own for signed-in users, every project for admins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectViewer {
+    Public,
+    User(Uuid),
+    Admin,
+}
+
+/// DraftSweep
+///
+/// The two stale-draft maintenance passes. Each is recorded per draft, so a draft is reminded
+/// at most once and flagged for cleanup at most once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DraftSweep {
+    /// Notify the owner ("draft_reminder").
+    Reminder,
+    /// Flag the draft for the orphaned-upload cleanup.
+    Cleanup,
+}
+
+/// ProjectListFilter
+///
+/// Filters for the public project listing (GET /projects). The page and its `total_count`
+/// are computed with the same filters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectListFilter {
+    pub year: Option<i32>,
+    pub search: Option<String>,
+    // Programme code, already normalized to upper case.
+    pub programme: Option<String>,
+    // Tag, already normalized to lower case.
+    pub tag: Option<String>,
+    // Supervisor name, already trimmed; matched case-insensitively against the trimmed column.
+    pub supervisor: Option<String>,
+    // Department, already trimmed; matched case-insensitively.
+    pub department: Option<String>,
+    // Technologies, already lower-cased; a project must use every one of them.
+    pub technologies: Vec<String>,
+    // Author name, already trimmed; matched exactly and case-insensitively, unlike `search`.
+    pub author: Option<String>,
+    // Listing order; does not affect `total_count`.
+    pub sort: ProjectSort,
+}
+
+/// AuditFilter
+///
+/// Filters for the audit log (GET /admin/audit). The page and its `total_count` are computed
+/// with the same filters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditFilter {
+    pub actor_id: Option<Uuid>,
+    pub action: Option<String>,
+    pub target_type: Option<String>,
+    // Inclusive lower and exclusive upper bound on `created_at`.
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// AdminCommentFilter
+///
+/// Filters for the moderators' comment listing (GET /admin/comments). `search` is matched
+/// case-insensitively anywhere in the comment text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdminCommentFilter {
+    pub project_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub search: Option<String>,
+}
 
 /// Repository Trait
 ///
@@ -15,76 +140,1135 @@ use std::sync::Arc;
 #[async_trait]
 pub trait Repository: Send + Sync {
     // --- Project Retrieval ---
-    // Public listing with filtering. Must enforce is_public=true.
-    async fn get_projects(&self, year: Option<i32>, search: Option<String>) -> Vec<Project>;
+    // Public listing with filtering. Must enforce is_public=true. Returns one page of rows
+    // (newest first, starting after `page.after` in keyset mode) and the number of rows
+    // matching the filters across all pages.
+    async fn get_projects(&self, filter: ProjectListFilter, page: PageRequest) -> (Vec<Project>, i64);
     // Admin access: retrieves all projects regardless of status.
     async fn get_all_projects(&self) -> Vec<Project>;
-    // Retrieves top projects ranked by like count.
-    async fn get_top_projects(&self, limit: i64) -> Vec<Project>;
+    // Admin review queue: at most `limit` `pending` projects with their owner's email, longest
+    // waiting first (by `submitted_at`, or `created_at` when never submitted).
+    async fn get_pending_reviews(&self, limit: i64) -> Vec<PendingReview>;
+    // Admin export: the same rows as `get_all_projects`, yielded one at a time. The stream
+    // owns its connection, and stops fetching while the consumer is not polling.
+    fn stream_all_projects(&self) -> BoxStream<'static, Result<Project, RepositoryError>>;
+    // Admin spreadsheet export: every project with its like count and owner email, oldest first,
+    // streamed like `stream_all_projects`.
+    fn stream_project_export(&self) -> BoxStream<'static, Result<ProjectExportRow, RepositoryError>>;
+    // Retrieves top projects with at least `min_likes` likes, ranked by like count, optionally
+    // from one year only. Ties go to the newer project (then the higher id), so equal counts
+    // always come back in one order.
+    async fn get_top_projects(&self, limit: i64, min_likes: i64, year: Option<i32>) -> Vec<Project>;
+    // Every public project of `year`, liked or not, in the `get_top_projects` ranking.
+    async fn get_year_archive(&self, year: i32) -> Vec<Project>;
+    // One public project picked at random. The same `seed` picks the same project for as long
+    // as the set of public projects is unchanged; None draws a fresh one on every call.
+    async fn get_random_public_project(&self, seed: Option<i64>) -> Option<Project>;
 
     // Retrieval methods with specific visibility and authorization rules.
     async fn get_project(&self, id: Uuid) -> Option<Project>;
     async fn get_project_authorized(&self, id: Uuid, user_id: Uuid) -> Option<Project>;
     async fn get_public_project(&self, id: Uuid) -> Option<Project>;
+    // The project `viewer` may see, with its owner's profile (None when the profile row is
+    // missing). The comment count includes held comments for admins only.
+    async fn get_project_detail(&self, id: Uuid, viewer: ProjectViewer) -> Option<(Project, Option<ProjectOwner>)>;
+    // The project whose current or any earlier slug is `slug`, whatever its visibility.
+    async fn resolve_project_slug(&self, slug: &str) -> Option<Uuid>;
+    // The oldest project of `year` whose title matches `title` once both are put through
+    // `title_key`, whatever its owner or status (used to refuse duplicate submissions).
+    async fn find_project_by_title_year(&self, title: &str, year: i32) -> Option<Uuid>;
+    // Projects `user_id` owns in `year`, whatever their status (the per-year submission cap).
+    async fn count_user_projects_in_year(&self, user_id: Uuid, year: i32) -> i64;
+    // Public projects updated or commented on after `since`, optionally limited to `ids`.
+    async fn get_changed_project_ids(&self, since: chrono::DateTime<chrono::Utc>, ids: Option<Vec<Uuid>>) -> Vec<Uuid>;
+    // Publish/unpublish events recorded after `since`, oldest first, at most `limit`. Published
+    // events carry their project only while it is still public.
+    async fn get_public_events(&self, since: chrono::DateTime<chrono::Utc>, limit: i64) -> Vec<PublicEvent>;
+    // Counts one view of a public project, returning the new total. None if the project is
+    // missing or private. Does not touch `updated_at`.
+    async fn increment_view(&self, id: Uuid) -> Option<i64>;
+    // The project's like total; None if the project is missing or not public.
+    async fn get_like_count(&self, id: Uuid) -> Option<i64>;
 
     // --- Project Actions ---
     async fn create_project(&self, req: CreateProjectRequest, user_id: Uuid) -> Project;
-    // Idempotent operation: returns true if a row was inserted, false otherwise (conflict).
-    async fn like_project(&self, like: Like) -> bool; 
-    // Admin action: changes the is_public status.
-    async fn set_project_status(&self, id: Uuid, is_public: bool) -> Option<Project>;
+    // Idempotent: whether a like was recorded or already existed, with the resulting like count.
+    // A new like notifies the owner ("like"). Owners cannot like their own project (admins
+    // included): `OwnProject`, nothing written. None if the project is missing or not public,
+    // whoever asks, so likes never accrue before approval.
+    async fn like_project(&self, like: Like) -> Option<LikeOutcome>;
+    // Removes the like and the owner's "like" notification for it. False if there was no like.
+    async fn unlike_project(&self, like: Like) -> bool;
+    // The subset of `project_ids` that `user_id` has liked, for the `liked_by_me` flag.
+    async fn get_liked_project_ids(&self, user_id: Uuid, project_ids: &[Uuid]) -> Vec<Uuid>;
+    // Owner/Admin view: one page of the users who liked the project, most recent like first,
+    // with the total across all pages. Access is checked by the caller.
+    async fn get_project_likers(&self, project_id: Uuid, page: PageRequest) -> (Vec<ProjectLiker>, i64);
+    // Admin action: moves the project to another review status. Only `approved` is public.
+    async fn set_project_status(&self, id: Uuid, status: ProjectStatus) -> Option<Project>;
+    // Admin bulk action: approves (`is_public`) or rejects every listed project in one statement.
+    // Reports which projects changed and which ids matched none; `ids` must not repeat.
+    async fn set_projects_status_bulk(&self, ids: &[Uuid], is_public: bool) -> BulkStatusOutcome;
 
     // --- User/Auth ---
     async fn get_user(&self, id: Uuid) -> Option<User>;
     async fn create_user(&self, user: User) -> User;
+    // The profiles whose email local part (before the `@`) is one of `prefixes`, compared
+    // case-insensitively. `prefixes` are lower-case.
+    async fn get_users_by_email_prefix(&self, prefixes: &[String]) -> Vec<User>;
     async fn get_stats(&self) -> AdminDashboardStats;
+    // Landing-page counters over public projects only. May be served from a short-lived cache.
+    async fn get_public_stats(&self) -> PublicStats;
     
     // --- Owner Actions ---
-    async fn get_my_projects(&self, user_id: Uuid) -> Vec<Project>;
+    // Every project of `user_id` whatever its status, newest first, with like and comment
+    // counts. `latest_review` is left for the caller to attach.
+    async fn get_my_project_summaries(&self, user_id: Uuid) -> Vec<MyProjectSummary>;
+    // The user's comments and likes, and visibility changes of their own projects, from the
+    // last `ACTIVITY_WINDOW_DAYS` days, newest first, with the total across all pages. Comments
+    // and likes on projects that are no longer public (and not the user's) are left out.
+    async fn get_user_activity(&self, user_id: Uuid, page: PageRequest) -> (Vec<ActivityItem>, i64);
+    // Owner-Only: like, comment and view totals of the project with its likes per day over the
+    // last `PROJECT_STATS_DAYS` days. None unless the project exists and `owner_id` owns it.
+    async fn get_project_stats(&self, id: Uuid, owner_id: Uuid) -> Option<ProjectStats>;
     // Owner-Only: Deletes only if the user_id matches the project's user_id.
     async fn delete_project(&self, id: Uuid, user_id: Uuid) -> bool; 
-    // Owner-Only: Updates only if the user_id matches. Uses COALESCE for partial updates.
-    async fn update_project(&self, id: Uuid, user_id: Uuid, req: UpdateProjectRequest) -> Option<Project>;
+    // Owner-Only: Updates only if the user_id matches. Only fields that differ from the stored
+    // values are written (see `reduce_project_update`); a no-op leaves `updated_at` alone.
+    async fn update_project(&self, id: Uuid, user_id: Uuid, req: UpdateProjectRequest) -> Option<ProjectUpdateResult>;
     
     // --- Comments & Moderation ---
-    async fn add_comment(&self, project_id: Uuid, user_id: Uuid, text: String) -> Comment;
-    async fn get_comments(&self, project_id: Uuid) -> Vec<Comment>;
+    // Adds a comment and notifies its recipients (see `comment_recipients`). Up to
+    // `fanout_threshold` recipients are notified in the same transaction; above it, one
+    // `pending_fanout` outbox job is queued instead and its id returned with the comment.
+    // None if the project (or the author's profile) does not exist, or the project's discussion
+    // is locked: the lock is checked by the insert itself, so one that lands while the comment
+    // is being posted still keeps it out. Any other database error is returned, as nothing was
+    // written. Visibility is the caller's to check.
+    async fn add_comment(&self, project_id: Uuid, user_id: Uuid, text: String, fanout_threshold: i64) -> Result<Option<PostedComment>, RepositoryError>;
+    // The thread of a project `hidden` lets the viewer read (see `HiddenComments`); empty
+    // otherwise. A deleted comment stays as a placeholder (`Comment::is_deleted`) while a
+    // later comment that is not deleted follows it, and is left out once none does.
+    async fn get_comments(&self, project_id: Uuid, order: CommentOrder, hidden: HiddenComments) -> Vec<Comment>;
+    // "Load older comments": up to `limit` comments before `before` (from the newest when None),
+    // newest first, with the visibility rules of `get_comments`.
+    async fn get_comments_before(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        before: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment>;
+    // "Load newer comments": up to `limit` comments after `after` (from the first when None),
+    // oldest first, with the visibility rules of `get_comments`.
+    async fn get_comments_after(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        after: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment>;
+    // The user's comments and comment edits (on any project) in the last minute and the last
+    // day, for the comment rate limit. Deleted comments still count.
+    async fn get_recent_comments(&self, user_id: Uuid) -> RecentComments;
+    // Number of publicly listed comments on a public project (the default thread order, the
+    // comment bubble on project cards). None if the project is missing or not public.
+    async fn count_comments(&self, project_id: Uuid) -> Option<i64>;
+    // Files a report against a comment on a public project. Reaching `threshold` distinct pending
+    // reports hides the comment and notifies admins, in one transaction. `project_id` scopes the
+    // lookup to one project, `None` finds the comment by id alone. None if no such comment.
+    async fn create_report(
+        &self,
+        project_id: Option<Uuid>,
+        comment_id: i64,
+        reporter_id: Uuid,
+        reason: Option<String>,
+        threshold: i64,
+    ) -> Option<ReportOutcome>;
+    // Moderator action: unhides a comment and resolves its pending reports. False if no such comment.
+    async fn restore_comment(&self, comment_id: i64) -> bool;
+    // The moderation queue: comments with pending reports on any project, most reported first,
+    // then most recently reported. Deleted comments leave the queue.
+    async fn get_reported_comments(&self) -> Vec<ReportedComment>;
+
+    /// Admin: Every comment matching `filter`, hidden and deleted ones included with their
+    /// original text, newest first, skipping
+    /// `offset` and returning at most `limit`.
+    async fn get_admin_comments(&self, filter: &AdminCommentFilter, limit: i64, offset: i64) -> Vec<AdminComment>;
 
     /// Admin Override: Delete ANY project by ID (No ownership check).
     async fn delete_project_admin(&self, id: Uuid) -> bool;
     
-    /// User: Delete their OWN comment (Ownership check required).
+    /// User: Replace the text of their OWN comment, stamp `edited_at` and log the edit (see
+    /// `get_recent_comments`). None if the comment is missing, deleted or someone else's; there
+    /// is no admin override. A locked discussion or a comment hidden pending review is left
+    /// unchanged and reported as such.
+    async fn update_comment(&self, id: i64, user_id: Uuid, req: UpdateCommentRequest) -> Option<CommentEditOutcome>;
+
+    /// User: Delete their OWN comment (Ownership check required). Comments are soft-deleted
+    /// (`deleted_at` is stamped); deleting one twice is false, like a missing one.
     async fn delete_comment(&self, id: i64, user_id: Uuid) -> bool;
     
-    /// Admin: Delete ANY comment (No ownership check).
+    /// Admin: Delete ANY comment (No ownership check). Soft, like `delete_comment`.
     async fn delete_comment_admin(&self, id: i64) -> bool;
 
+    /// Deletes a comment addressed through its project. The comment must belong to
+    /// `project_id`; `user_id` enforces ownership, `None` skips it (admin override).
+    async fn delete_project_comment(&self, project_id: Uuid, id: i64, user_id: Option<Uuid>) -> bool;
+
     // --- Notifications ---
     // Retrieves enriched notification responses for the recipient (user_id).
     async fn get_notifications(&self, user_id: Uuid) -> Vec<crate::models::NotificationResponse>;
     // Marks a notification as read, enforced by ownership check (`user_id`).
     async fn mark_notification_read(&self, notification_id: Uuid, user_id: Uuid) -> bool;
+    // Writes one notification. Nobody is notified of their own action: None when the actor is
+    // the recipient, and on database error.
+    async fn create_notification(&self, notification: NewNotification) -> Option<Notification>;
+
+    // --- Discussion Lock ---
+    // Locks/unlocks discussion on a project, enforced by ownership check (`user_id`).
+    async fn set_comments_locked(&self, id: Uuid, user_id: Uuid, locked: bool) -> Option<Project>;
+    // Admin override: locks/unlocks discussion on any project.
+    async fn set_comments_locked_admin(&self, id: Uuid, locked: bool) -> Option<Project>;
+    // Admin bulk action: locks every project in a cohort year, returning the number changed.
+    async fn lock_comments_for_year(&self, year: i32) -> i64;
+    // Whether new comments and likes are currently refused for a project.
+    async fn are_comments_locked(&self, project_id: Uuid) -> bool;
+
+    // --- Submission ---
+    // Moves a draft into the review queue: stamps `submitted_at` and sets status `pending`. Authorization and the
+    // readiness checklist are enforced by the handler before this is called. None if the project
+    // does not exist or is no longer a draft (e.g. a concurrent submit, or an admin decision).
+    async fn mark_project_submitted(&self, id: Uuid) -> Option<Project>;
+    // Owner-Only: puts a rejected project back in the review queue (status `pending`, fresh
+    // `submitted_at` and `updated_at`), counts the attempt, records it as a `pending` review
+    // without a reason and notifies every admin ("resubmission"), in one transaction. Refused
+    // for any other status, or once `max_attempts` resubmissions have been made. None if the
+    // project does not exist or is not the owner's.
+    async fn resubmit_project(&self, id: Uuid, owner_id: Uuid, max_attempts: i32) -> Option<ResubmitOutcome>;
+    // Admin action: makes `new_owner_id` the project's owner and sends the previous and the new
+    // owner an `ownership_change` notification from `actor_id`, in one transaction. Nothing
+    // changes or is sent when the target already owns it. None if the project does not exist.
+    async fn reassign_project_owner(&self, project_id: Uuid, new_owner_id: Uuid, actor_id: Uuid) -> Option<OwnerTransferOutcome>;
+
+    // --- Staff Reports ---
+    // Per-supervisor project counts and engagement aggregates, optionally limited to one year.
+    async fn get_supervisor_report(&self, year: Option<i32>) -> Vec<SupervisorStats>;
+
+    // Creates a `status_change` notification for the project owner, sent by `actor_id` (an admin).
+    // Returns false if nothing was inserted (unknown project, or the owner is the actor).
+    async fn notify_status_change(
+        &self,
+        project_id: Uuid,
+        actor_id: Uuid,
+        reason: Option<String>,
+    ) -> bool;
+
+    // --- Project Reviews ---
+    // Records an admin status decision in `project_reviews`. Returns false if the project does
+    // not exist.
+    async fn record_project_review(
+        &self,
+        project_id: Uuid,
+        reviewer_id: Uuid,
+        status: ProjectStatus,
+        reason: Option<String>,
+    ) -> bool;
+    // The newest review of each of the owner's projects that has been reviewed.
+    async fn get_latest_reviews(&self, owner_id: Uuid) -> Vec<ProjectReview>;
+
+    // --- Comment Subscriptions ---
+    // Stores an explicit subscribe/mute preference. Returns false if the project does not exist.
+    async fn set_subscription(&self, project_id: Uuid, user_id: Uuid, state: SubscriptionState) -> bool;
+    // The caller's effective state: an explicit preference if set, otherwise `Subscribed` for the
+    // owner and past commenters, and `None` for everyone else.
+    async fn get_subscription(&self, project_id: Uuid, user_id: Uuid) -> Option<SubscriptionState>;
+
+    // --- Upload Tracking ---
+    // Records a presigned upload URL issued to `user_id`. Returns false if the insert failed.
+    async fn record_upload(
+        &self,
+        user_id: Uuid,
+        object_key: &str,
+        purpose: UploadPurpose,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> bool;
+    // Presigns in the last hour, and unexpired uploads for `purpose` no project references yet.
+    async fn get_upload_counts(&self, user_id: Uuid, purpose: UploadPurpose) -> UploadCounts;
+    // Whether `object_key` was issued to `user_id` as an upload for `purpose`.
+    async fn is_own_upload(&self, user_id: Uuid, object_key: &str, purpose: UploadPurpose) -> bool;
+
+    // --- Programmes ---
+    async fn get_programmes(&self) -> Vec<Programme>;
+    // Admin action: returns None if the code is already taken.
+    async fn create_programme(&self, code: &str, name: &str) -> Option<Programme>;
+    // The subset of `codes` with no matching programme (used to validate project payloads).
+    async fn unknown_programme_codes(&self, codes: &[String]) -> Vec<String>;
+    // Codes of the programmes a project belongs to, sorted.
+    async fn get_project_programmes(&self, project_id: Uuid) -> Vec<String>;
+
+    // --- Tags ---
+    // Distinct tags on public projects with how many carry each, most used first.
+    async fn get_tag_counts(&self) -> Vec<TagCount>;
+    // Years that have public projects with how many each has, newest year first.
+    async fn get_project_years(&self) -> Vec<YearCount>;
+    // Departments of public projects with how many each has, most projects first.
+    async fn get_department_counts(&self) -> Vec<DepartmentCount>;
+    // Technologies of public projects with how many use each, most used first.
+    async fn get_technology_counts(&self) -> Vec<TechnologyCount>;
+
+    // --- Anonymous Feedback ---
+    // Stores feedback on a public project and notifies its owner ("feedback"). Returns None if
+    // the project is missing or not public.
+    async fn add_feedback(&self, project_id: Uuid, message: &str, contact_email: Option<&str>) -> Option<ProjectFeedback>;
+    // Newest first; one project's feedback, or every project's when `project_id` is None.
+    async fn get_feedback(&self, project_id: Option<Uuid>) -> Vec<ProjectFeedback>;
+
+    // --- Settings ---
+    // Every stored setting. Keys never set are absent; their defaults live in `settings`.
+    async fn get_settings(&self) -> Result<Vec<Setting>, RepositoryError>;
+    // Inserts or replaces one setting; None on database error. Values are validated by the caller.
+    async fn put_setting(&self, key: &str, value: serde_json::Value, updated_by: Uuid) -> Option<Setting>;
+
+    // --- Stale Drafts ---
+    // Drafts (not submitted, not public) last edited before `untouched_before` that `sweep` has
+    // not handled yet, oldest first.
+    async fn get_stale_drafts(&self, sweep: DraftSweep, untouched_before: chrono::DateTime<chrono::Utc>) -> Vec<StaleDraft>;
+    // Records `sweep` for a draft at `at` (a reminder also notifies the owner). Returns false if
+    // the sweep already handled it or it is no longer a draft.
+    async fn mark_stale_draft(&self, sweep: DraftSweep, project_id: Uuid, at: chrono::DateTime<chrono::Utc>) -> bool;
+
+    // --- Academic Years ---
+    // Projects whose year lies outside `min..=max`, by year then title.
+    async fn get_projects_outside_years(&self, min: i32, max: i32) -> Vec<InvalidYearProject>;
+    // Admin bulk fix: moves every project of year `from` to `to`, re-picking each one's slug for
+    // the new year (old slugs keep resolving). Returns the number moved; all or nothing.
+    async fn remap_project_year(&self, from: i32, to: i32) -> i64;
+
+    // --- Audit Log ---
+    // Writes one entry, stamped with the current time. Errors are returned rather than logged so
+    // the caller can fall back to `queue_audit`.
+    async fn record_audit(&self, entry: &NewAuditEntry) -> Result<(), RepositoryError>;
+    // Queues the entry as an `audit_entry` outbox job for `flush_audit_outbox`; returns its id.
+    async fn queue_audit(&self, entry: &NewAuditEntry) -> Result<i64, RepositoryError>;
+    // One page of entries matching `filter`, newest first, and how many match across all pages.
+    async fn get_audit_log(&self, filter: &AuditFilter, page: PageRequest) -> (Vec<AuditEntry>, i64);
+    // Every entry matching `filter`, newest first, streamed for the CSV download.
+    fn stream_audit_log(&self, filter: AuditFilter) -> BoxStream<'static, Result<AuditEntry, RepositoryError>>;
+    // Deletes every entry created before `before`; returns how many.
+    async fn purge_audit_log(&self, before: chrono::DateTime<chrono::Utc>) -> Result<u64, RepositoryError>;
+
+    // --- Outbox ---
+    // Expands up to `batch_size` more recipients of the oldest pending fan-out job, advancing its
+    // cursor in the same transaction, and marks it done once a batch comes up short. Returns the
+    // job as left by the batch, or None when nothing is pending.
+    async fn run_fanout_batch(&self, batch_size: i64) -> Option<OutboxJob>;
+    async fn get_outbox_job(&self, id: i64) -> Option<OutboxJob>;
+    // Moves every pending `audit_entry` job into the audit log, dated when it was queued, and
+    // marks it done in the same transaction. Returns the number moved.
+    async fn flush_audit_outbox(&self) -> Result<u64, RepositoryError>;
+
+    // --- Health ---
+    // A trivial round trip, for GET /health/deep and for background passes whose other calls
+    // cannot tell an outage from an empty result.
+    async fn ping(&self) -> Result<(), RepositoryError>;
+}
+
+/// links_json
+///
+/// Converts the request-side link map into the JSONB value stored on `projects.links`
+/// (an empty object when no links were supplied).
+fn links_json(links: Option<BTreeMap<String, String>>) -> serde_json::Value {
+    serde_json::to_value(links.unwrap_or_default()).unwrap_or_else(|_| serde_json::json!({}))
+}
+
+/// title_key
+///
+/// The form in which titles are compared for duplicates: trimmed, inner whitespace collapsed to
+/// single spaces, lower-cased. `find_project_by_title_year` applies the same in SQL.
+fn title_key(title: &str) -> String {
+    title.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// reduce_project_update
+///
+/// Drops the fields of `req` that would not change `current` (whose programme codes are
+/// `programmes`), after trimming `title` and `abstract_text`. Returns what is left and the
+/// names of the changed fields, in `UpdateProjectRequest` order. Programmes and tags compare
+/// as sets. Both repositories go through this, so they agree on what counts as a change.
+fn reduce_project_update(
+    current: &Project,
+    programmes: &[String],
+    req: UpdateProjectRequest,
+) -> (UpdateProjectRequest, Vec<String>) {
+    let mut changed = Vec::new();
+    let mut differs = |name: &str, differs: bool| {
+        if differs {
+            changed.push(name.to_string());
+        }
+        differs
+    };
+    let as_set = |values: &[String]| values.iter().cloned().collect::<BTreeSet<_>>();
+    let reduced = UpdateProjectRequest {
+        title: req.title.map(|t| t.trim().to_string()).filter(|t| differs("title", *t != current.title)),
+        abstract_text: req
+            .abstract_text
+            .map(|a| a.trim().to_string())
+            .filter(|a| differs("abstract_text", *a != current.abstract_text)),
+        cover_image_key: req.cover_image_key.filter(|k| differs("cover_image_key", *k != current.cover_image)),
+        video_key: req.video_key.filter(|k| differs("video_key", current.video.as_ref() != Some(k))),
+        report_key: req.report_key.filter(|k| differs("report_key", current.report.as_ref() != Some(k))),
+        author_name: req
+            .author_name
+            .map(|a| a.trim().to_string())
+            .filter(|a| differs("author_name", *a != current.author)),
+        year: req.year.filter(|y| differs("year", *y != current.year)),
+        links: req.links.filter(|l| differs("links", links_json(Some(l.clone())) != current.links)),
+        video_waived: req.video_waived.filter(|w| differs("video_waived", *w != current.video_waived)),
+        supervisor: req.supervisor.filter(|s| differs("supervisor", current.supervisor.as_ref() != Some(s))),
+        department: req.department.filter(|d| differs("department", current.department.as_ref() != Some(d))),
+        programme_codes: req
+            .programme_codes
+            .filter(|c| differs("programme_codes", as_set(c) != as_set(programmes))),
+        tags: req.tags.filter(|t| differs("tags", as_set(t) != as_set(&current.tags))),
+        technologies: req
+            .technologies
+            .filter(|t| differs("technologies", as_set(t) != as_set(&current.technologies))),
+    };
+    (reduced, changed)
+}
+
+/// file_report
+///
+/// Transaction body of `create_report`. Reporting the same comment twice while a report is
+/// pending is a no-op, so only distinct reporters count towards the threshold.
+async fn file_report(
+    pool: &PgPool,
+    project_id: Option<Uuid>,
+    comment_id: i64,
+    reporter_id: Uuid,
+    reason: Option<String>,
+    threshold: i64,
+) -> Result<Option<ReportOutcome>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let Some(target) = sqlx::query!(
+        r#"SELECT c.hidden_pending_review, c.project_id FROM project_comments c
+           JOIN projects pr ON c.project_id = pr.id
+           WHERE c.id = $1 AND ($2::uuid IS NULL OR c.project_id = $2) AND pr.is_public = true
+             AND c.deleted_at IS NULL
+           FOR UPDATE OF c"#,
+        comment_id,
+        project_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+    let (already_hidden, project_id) = (target.hidden_pending_review, target.project_id);
+
+    sqlx::query!(
+        r#"INSERT INTO comment_reports (comment_id, reporter_id, reason) VALUES ($1, $2, $3)
+           ON CONFLICT (comment_id, reporter_id) WHERE resolved_at IS NULL DO NOTHING"#,
+        comment_id,
+        reporter_id,
+        reason
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let pending_reports = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM comment_reports
+           WHERE comment_id = $1 AND resolved_at IS NULL"#,
+        comment_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let hidden = !already_hidden && pending_reports >= threshold;
+    if hidden {
+        sqlx::query!(
+            "UPDATE project_comments SET hidden_pending_review = true WHERE id = $1",
+            comment_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        // Every admin gets a moderation notification; the reporter who tripped it is the actor.
+        sqlx::query!(
+            r#"INSERT INTO notifications (user_id, actor_id, project_id, type, message)
+               SELECT id, $1, $2, 'comment_hidden', $3 FROM profiles WHERE role = 'admin'"#,
+            reporter_id,
+            project_id,
+            format!("Comment {} was hidden after {} reports.", comment_id, pending_reports)
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(Some(ReportOutcome { pending_reports, hidden }))
+}
+
+/// edit_comment
+///
+/// Transaction body of `update_comment`. The row lock keeps the lock and review checks true
+/// until the new text and its `comment_edits` row are written.
+async fn edit_comment(
+    pool: &PgPool,
+    id: i64,
+    user_id: Uuid,
+    text: String,
+) -> Result<Option<CommentEditOutcome>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let Some(target) = sqlx::query!(
+        r#"SELECT c.hidden_pending_review, pr.comments_locked FROM project_comments c
+           JOIN projects pr ON c.project_id = pr.id
+           WHERE c.id = $1 AND c.user_id = $2 AND c.deleted_at IS NULL
+           FOR UPDATE OF c"#,
+        id,
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+    if target.comments_locked {
+        return Ok(Some(CommentEditOutcome::Locked));
+    }
+    if target.hidden_pending_review {
+        return Ok(Some(CommentEditOutcome::HiddenPendingReview));
+    }
+
+    let comment = sqlx::query_as!(
+        Comment,
+        r#"
+        UPDATE project_comments c
+        SET comment = $2, edited_at = NOW()
+        FROM profiles p
+        WHERE c.id = $1 AND p.id = c.user_id
+        RETURNING
+            c.id, c.user_id AS "user_id?", c.project_id, c.comment, c.created_at, p.email as author_email,
+            c.hidden_pending_review, c.edited_at, TRUE AS "edited!", FALSE AS "is_deleted!"
+        "#,
+        id,
+        text
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    sqlx::query!("INSERT INTO comment_edits (comment_id, user_id) VALUES ($1, $2)", id, user_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(Some(CommentEditOutcome::Edited(Box::new(comment))))
+}
+
+/// resubmit
+///
+/// Transaction body of `resubmit_project`. The row lock makes the status and attempt checks
+/// hold until the update, so two concurrent resubmissions cannot both pass the cap.
+async fn resubmit(
+    pool: &PgPool,
+    id: Uuid,
+    owner_id: Uuid,
+    max_attempts: i32,
+) -> Result<Option<ResubmitOutcome>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let Some(current) = sqlx::query!(
+        r#"SELECT status AS "status: ProjectStatus", resubmission_count FROM projects
+           WHERE id = $1 AND user_id = $2
+           FOR UPDATE"#,
+        id,
+        owner_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+    if current.status != ProjectStatus::Rejected {
+        return Ok(Some(ResubmitOutcome::NotRejected(current.status)));
+    }
+    if current.resubmission_count >= max_attempts {
+        return Ok(Some(ResubmitOutcome::LimitReached));
+    }
+
+    let project = sqlx::query_as!(
+        Project,
+        r#"
+        UPDATE projects
+        SET status = 'pending', submitted_at = NOW(), updated_at = NOW(),
+            resubmission_count = resubmission_count + 1
+        WHERE id = $1
+        RETURNING id, user_id, author, title, abstract as abstract_text, 
+                  cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, 
+                  year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!"
+        "#,
+        id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    // A reasonless `pending` review supersedes the rejection as the owner's latest review.
+    sqlx::query!(
+        "INSERT INTO project_reviews (project_id, reviewer_id, status, reason) VALUES ($1, $2, 'pending', NULL)",
+        id,
+        owner_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        r#"INSERT INTO notifications (user_id, actor_id, project_id, type, message)
+           SELECT id, $2, $1, 'resubmission', $3 FROM profiles WHERE role = 'admin' AND id <> $2"#,
+        id,
+        owner_id,
+        resubmission_message(current.resubmission_count + 1, max_attempts)
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(Some(ResubmitOutcome::Resubmitted(Box::new(project))))
+}
+
+/// reassign_owner
+///
+/// Transaction body of `reassign_project_owner`. The project row is locked before the target
+/// profile is checked, so a concurrent transfer of the same project waits rather than
+/// notifying an owner who has already been replaced.
+async fn reassign_owner(
+    pool: &PgPool,
+    project_id: Uuid,
+    new_owner_id: Uuid,
+    actor_id: Uuid,
+) -> Result<Option<OwnerTransferOutcome>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let Some(previous_owner) = sqlx::query_scalar!("SELECT user_id FROM projects WHERE id = $1 FOR UPDATE", project_id)
+        .fetch_optional(&mut *tx)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let Some(new_owner_email) = sqlx::query_scalar!("SELECT email FROM profiles WHERE id = $1", new_owner_id)
+        .fetch_optional(&mut *tx)
+        .await?
+    else {
+        return Ok(Some(OwnerTransferOutcome::UnknownOwner));
+    };
+
+    let project = sqlx::query_as!(
+        Project,
+        r#"
+        UPDATE projects
+        SET user_id = $2, updated_at = CASE WHEN user_id = $2 THEN updated_at ELSE NOW() END
+        WHERE id = $1
+        RETURNING id, user_id, author, title, abstract as abstract_text,
+                  cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug,
+                  year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!"
+        "#,
+        project_id,
+        new_owner_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if previous_owner != new_owner_id {
+        sqlx::query!(
+            r#"INSERT INTO notifications (user_id, actor_id, project_id, type, message)
+               VALUES ($1, $3, $4, 'ownership_change', $5), ($2, $3, $4, 'ownership_change', $6)"#,
+            previous_owner,
+            new_owner_id,
+            actor_id,
+            project_id,
+            format!("Transferred to {new_owner_email} by an admin."),
+            "Transferred to you by an admin."
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(Some(OwnerTransferOutcome::Transferred { project: Box::new(project), previous_owner }))
+}
+
+/// Transaction body of `like_project`: the like, the count, and the owner's "like"
+/// notification when the like is new, committed together.
+async fn record_like(pool: &PgPool, like: &Like) -> Result<Option<LikeOutcome>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    // The count query cannot see the CTE's own insert, so a new like is added on top.
+    let Some(row) = sqlx::query!(
+        r#"
+        WITH project AS (
+            SELECT id, user_id FROM projects WHERE id = $2 AND is_public = true
+        ), inserted AS (
+            INSERT INTO project_likes (user_id, project_id)
+            SELECT $1, id FROM project WHERE user_id <> $1
+            ON CONFLICT DO NOTHING
+            RETURNING project_id
+        )
+        SELECT
+            project.user_id AS owner_id,
+            (project.user_id = $1) AS "own!",
+            EXISTS (SELECT 1 FROM inserted) AS "created!",
+            (SELECT COUNT(*) FROM project_likes l WHERE l.project_id = $2)
+                + (SELECT COUNT(*) FROM inserted) AS "like_count!"
+        FROM project
+        "#,
+        like.user_id,
+        like.project_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+    if row.created {
+        let notification = NewNotification::new(row.owner_id, like.user_id, like.project_id, "like");
+        insert_notification(&mut tx, &notification).await?;
+    }
+    tx.commit().await?;
+    Ok(Some(if row.own {
+        LikeOutcome::OwnProject(row.like_count)
+    } else if row.created {
+        LikeOutcome::Created(row.like_count)
+    } else {
+        LikeOutcome::AlreadyExists(row.like_count)
+    }))
+}
+
+/// insert_notification
+///
+/// Writes `notification` inside the caller's transaction, unless its actor is its recipient.
+/// Shared by `create_notification` and the like transaction.
+async fn insert_notification(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    notification: &NewNotification,
+) -> sqlx::Result<Option<Notification>> {
+    if notification.user_id == notification.actor_id {
+        return Ok(None);
+    }
+    sqlx::query_as!(
+        Notification,
+        r#"INSERT INTO notifications (user_id, actor_id, project_id, type, message)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING id, user_id, actor_id, project_id, type AS notification_type, message, is_read, created_at"#,
+        notification.user_id,
+        notification.actor_id,
+        notification.project_id,
+        notification.notification_type,
+        notification.message
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map(Some)
+}
+
+/// The message of the admins' "resubmission" notification.
+fn resubmission_message(attempt: i32, max_attempts: i32) -> String {
+    format!("Resubmitted for review after rejection (attempt {attempt} of {max_attempts}).")
+}
+
+/// fanout_batch
+///
+/// Transaction body of `run_fanout_batch`. The job row stays locked for the whole batch and
+/// `SKIP LOCKED` lets a second dispatcher move on to the next job; the notifications and the
+/// new cursor commit together, so a dispatcher that dies mid-batch leaves nothing behind and
+/// the batch is simply redone.
+async fn fanout_batch(pool: &PgPool, batch_size: i64) -> Result<Option<OutboxJob>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let Some(job) = sqlx::query!(
+        r#"SELECT id, (payload->>'project_id')::uuid AS "project_id!", (payload->>'actor_id')::uuid AS "actor_id!", cursor
+           FROM outbox WHERE kind = 'pending_fanout' AND status = 'pending'
+           ORDER BY id LIMIT 1 FOR UPDATE SKIP LOCKED"#
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+    let batch = sqlx::query!(
+        r#"
+        WITH batch AS (
+            SELECT r.recipient_id FROM comment_recipients($1, $2) r
+            WHERE $3::uuid IS NULL OR r.recipient_id > $3
+            ORDER BY r.recipient_id LIMIT $4
+        ), inserted AS (
+            INSERT INTO notifications (user_id, actor_id, project_id, type)
+            SELECT recipient_id, $2, $1, 'comment' FROM batch
+            RETURNING user_id
+        )
+        SELECT COUNT(*) AS "written!", (ARRAY_AGG(user_id ORDER BY user_id DESC))[1] AS last FROM inserted
+        "#,
+        job.project_id,
+        job.actor_id,
+        job.cursor,
+        batch_size
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    // A short batch means the recipients ran out.
+    let job = sqlx::query_as!(
+        OutboxJob,
+        r#"UPDATE outbox SET processed = processed + $2, cursor = COALESCE($3, cursor), updated_at = NOW(),
+               status = CASE WHEN $2 < $4 THEN 'done' ELSE status END,
+               completed_at = CASE WHEN $2 < $4 THEN NOW() END
+           WHERE id = $1
+           RETURNING id, kind, status, payload, total, processed, created_at, updated_at, completed_at"#,
+        job.id,
+        batch.written,
+        batch.last,
+        batch_size
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(Some(job))
+}
+
+/// push_audit_filters
+///
+/// Appends the audit log's optional filters to a query already ending in a `WHERE` clause.
+fn push_audit_filters(builder: &mut QueryBuilder<'_, sqlx::Postgres>, filter: &AuditFilter) {
+    if let Some(actor_id) = filter.actor_id {
+        builder.push(" AND actor_id = ");
+        builder.push_bind(actor_id);
+    }
+    if let Some(action) = &filter.action {
+        builder.push(" AND action = ");
+        builder.push_bind(action.clone());
+    }
+    if let Some(target_type) = &filter.target_type {
+        builder.push(" AND target_type = ");
+        builder.push_bind(target_type.clone());
+    }
+    if let Some(from) = filter.from {
+        builder.push(" AND created_at >= ");
+        builder.push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        builder.push(" AND created_at < ");
+        builder.push_bind(to);
+    }
+}
+
+/// push_admin_comment_filters
+///
+/// Appends the admin comment listing's optional filters to a query already ending in a
+/// `WHERE` clause over `project_comments c`.
+fn push_admin_comment_filters(builder: &mut QueryBuilder<'_, sqlx::Postgres>, filter: &AdminCommentFilter) {
+    if let Some(project_id) = filter.project_id {
+        builder.push(" AND c.project_id = ");
+        builder.push_bind(project_id);
+    }
+    if let Some(user_id) = filter.user_id {
+        builder.push(" AND c.user_id = ");
+        builder.push_bind(user_id);
+    }
+    if let Some(s) = &filter.search {
+        builder.push(" AND c.comment ILIKE ");
+        builder.push_bind(format!("%{}%", s));
+    }
+}
+
+/// push_project_filters
+///
+/// Appends the public listing's optional filters to a query already ending in a `WHERE`
+/// clause. Shared by the page query and its count so the two can never disagree.
+fn push_project_filters(builder: &mut QueryBuilder<'_, sqlx::Postgres>, filter: &ProjectListFilter) {
+    if let Some(y) = filter.year {
+        // Without a search, `status = 'approved' AND year = $n ORDER BY created_at DESC, id DESC`
+        // is an Index Scan of `idx_projects_public_year_listing` that stops at the page size.
+        builder.push(" AND year = ");
+        builder.push_bind(y);
+    }
+
+    if let Some(s) = &filter.search {
+        // Case-insensitive search across title, abstract, and author fields.
+        //
+        // Expected plan (migration 26): a BitmapOr of the three `idx_projects_*_trgm` GIN
+        // scans feeding a Bitmap Heap Scan that rechecks the ILIKEs and filters on `status`,
+        // then a top-N sort for the page. It needs a trigram in the term: the handler's
+        // 2-character minimum still falls back to scanning every approved row, and only a
+        // 3+ character term reaches the trigram indexes.
+        let search_pattern = format!("%{}%", s);
+        builder.push(" AND (title ILIKE ");
+        builder.push_bind(search_pattern.clone());
+        builder.push(" OR abstract ILIKE ");
+        builder.push_bind(search_pattern.clone());
+        builder.push(" OR author ILIKE ");
+        builder.push_bind(search_pattern);
+        builder.push(")");
+    }
+
+    if let Some(code) = &filter.programme {
+        // Joint-degree projects match each of their programmes.
+        builder.push(" AND EXISTS (SELECT 1 FROM project_programmes pp WHERE pp.project_id = projects.id AND pp.programme_code = ");
+        builder.push_bind(code.clone());
+        builder.push(")");
+    }
+
+    if let Some(tag) = &filter.tag {
+        builder.push(" AND EXISTS (SELECT 1 FROM project_tags pt WHERE pt.project_id = projects.id AND pt.tag = ");
+        builder.push_bind(tag.clone());
+        builder.push(")");
+    }
+
+    if !filter.technologies.is_empty() {
+        builder.push(" AND technologies @> ");
+        builder.push_bind(filter.technologies.clone());
+    }
+
+    if let Some(name) = &filter.supervisor {
+        builder.push(" AND LOWER(TRIM(supervisor)) = LOWER(");
+        builder.push_bind(name.clone());
+        builder.push(")");
+    }
+
+    if let Some(department) = &filter.department {
+        builder.push(" AND LOWER(department) = LOWER(");
+        builder.push_bind(department.clone());
+        builder.push(")");
+    }
+
+    if let Some(name) = &filter.author {
+        builder.push(" AND LOWER(author) = LOWER(");
+        builder.push_bind(name.clone());
+        builder.push(")");
+    }
+}
+
+/// replace_project_programmes
+///
+/// Sets a project's programme membership to exactly `codes`. Runs inside the caller's
+/// transaction so the project row and its programmes change together.
+async fn replace_project_programmes(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    project_id: Uuid,
+    codes: &[String],
+) -> sqlx::Result<()> {
+    sqlx::query!("DELETE FROM project_programmes WHERE project_id = $1", project_id)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query!(
+        "INSERT INTO project_programmes (project_id, programme_code) SELECT $1, UNNEST($2::text[]) ON CONFLICT DO NOTHING",
+        project_id,
+        codes
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// replace_project_tags
+///
+/// Sets a project's tags to exactly `tags`, inside the caller's transaction. Returns the
+/// stored set, sorted, for the caller's response (its `RETURNING` ran before the change).
+async fn replace_project_tags(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    project_id: Uuid,
+    tags: &[String],
+) -> sqlx::Result<Vec<String>> {
+    sqlx::query!("DELETE FROM project_tags WHERE project_id = $1", project_id)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query!(
+        "INSERT INTO project_tags (project_id, tag) SELECT $1, UNNEST($2::text[]) ON CONFLICT DO NOTHING",
+        project_id,
+        tags
+    )
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query_scalar!("SELECT tag FROM project_tags WHERE project_id = $1 ORDER BY tag", project_id)
+        .fetch_all(&mut **tx)
+        .await
+}
+
+/// pick_slug
+///
+/// The first slug for `title` and `year` that no other project has ever held (see
+/// `slug::first_free`), inside the caller's transaction. Slugs this project held before count
+/// as free, so renaming back to an earlier title brings its old slug back. A transaction-level
+/// advisory lock on the base keeps two projects from settling on the same suffix at once.
+async fn pick_slug(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    project_id: Uuid,
+    title: &str,
+    year: i32,
+) -> sqlx::Result<String> {
+    let base = slug::base(title, year);
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+        .bind(&base)
+        .execute(&mut **tx)
+        .await?;
+    let taken: HashSet<String> = sqlx::query_scalar!(
+        "SELECT slug FROM project_slugs WHERE project_id <> $2 AND (slug = $1 OR slug LIKE $1 || '-%')",
+        base,
+        project_id
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .collect();
+    Ok(slug::first_free(&base, |candidate| taken.contains(candidate)))
+}
+
+/// record_slug
+///
+/// Adds `slug` to the project's slug history (a no-op if it held it before).
+async fn record_slug(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    project_id: Uuid,
+    slug: &str,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        "INSERT INTO project_slugs (slug, project_id) VALUES ($1, $2) ON CONFLICT (slug) DO NOTHING",
+        slug,
+        project_id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
 }
 
+/// Rows fetched ahead of a slow `stream_all_projects` consumer before the query pauses.
+const PROJECT_STREAM_BUFFER: usize = 32;
+
 /// RepositoryState
 ///
 /// The concrete type used to share the persistence layer access across the application state.
 pub type RepositoryState = Arc<dyn Repository>;
 
+/// How long `get_public_stats` results are reused before the aggregate is recomputed. Status
+/// changes and project deletions drop the cached value early.
+pub const PUBLIC_STATS_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// query_public_stats
+///
+/// The single aggregate behind `get_public_stats`. Takes any executor so it can also run
+/// inside a transaction. **Security**: every count reads `public_projects` only.
+pub async fn query_public_stats<'e, E>(executor: E) -> sqlx::Result<PublicStats>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    sqlx::query_as!(
+        PublicStats,
+        r#"
+        SELECT
+            COUNT(*) AS "public_projects!",
+            COUNT(DISTINCT p.year) AS "distinct_years!",
+            COUNT(DISTINCT NULLIF(TRIM(p.supervisor), '')) AS "distinct_supervisors!",
+            COALESCE(SUM((SELECT COUNT(*) FROM project_likes l WHERE l.project_id = p.id)), 0)::bigint AS "total_likes!"
+        FROM public_projects p
+        "#
+    )
+    .fetch_one(executor)
+    .await
+}
+
 /// PostgresRepository
 ///
 /// The concrete implementation of the `Repository` trait, backed by the PostgreSQL database.
 pub struct PostgresRepository {
     pool: PgPool,
+    // Last public stats result and when it was computed (see `PUBLIC_STATS_TTL`).
+    public_stats_cache: Mutex<Option<(Instant, PublicStats)>>,
 }
 
 impl PostgresRepository {
     /// Creates a new repository instance using the initialized connection pool.
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self { pool, public_stats_cache: Mutex::new(None) }
+    }
+
+    /// Drops the cached public stats after a write that can change which projects are public,
+    /// so an unpublished project stops counting on the very next request.
+    fn invalidate_public_stats(&self) {
+        *self.public_stats_cache.lock().unwrap() = None;
+    }
+}
+
+/// How far back GET /me/activity looks.
+pub const ACTIVITY_WINDOW_DAYS: i32 = 90;
+
+/// Days covered by `ProjectStats::likes_per_day`, today included.
+pub const PROJECT_STATS_DAYS: i32 = 30;
+
+/// The three sources of `get_user_activity` for user `$1` over the last `$2` days, as one
+/// row shape. Shared by the page query and its count.
+const USER_ACTIVITY: &str = r#"
+    SELECT 'commented' AS kind, c.created_at AS at, p.id AS project_id, p.title AS project_title,
+           c.id AS comment_id, c.comment AS comment, NULL::boolean AS is_public
+    FROM project_comments c JOIN projects p ON p.id = c.project_id
+    WHERE c.user_id = $1 AND (p.is_public OR p.user_id = $1) AND c.deleted_at IS NULL
+      AND c.created_at >= NOW() - make_interval(days => $2)
+    UNION ALL
+    SELECT 'liked', l.liked_at, p.id, p.title, NULL, NULL, NULL
+    FROM project_likes l JOIN projects p ON p.id = l.project_id
+    WHERE l.user_id = $1 AND (p.is_public OR p.user_id = $1)
+      AND l.liked_at >= NOW() - make_interval(days => $2)
+    UNION ALL
+    SELECT 'status_changed', h.changed_at, p.id, p.title, NULL, NULL, h.is_public
+    FROM project_status_history h JOIN projects p ON p.id = h.project_id
+    WHERE p.user_id = $1
+      AND h.changed_at >= NOW() - make_interval(days => $2)
+"#;
+
+/// One `USER_ACTIVITY` row; the nullable columns belong to a single `kind` each.
+#[derive(sqlx::FromRow)]
+struct ActivityRow {
+    kind: String,
+    at: chrono::DateTime<chrono::Utc>,
+    project_id: Uuid,
+    project_title: String,
+    comment_id: Option<i64>,
+    comment: Option<String>,
+    is_public: Option<bool>,
+}
+
+impl From<ActivityRow> for ActivityItem {
+    fn from(row: ActivityRow) -> Self {
+        let ActivityRow { kind, at, project_id, project_title, comment_id, comment, is_public } = row;
+        match kind.as_str() {
+            "commented" => ActivityItem::Commented {
+                at,
+                project_id,
+                project_title,
+                comment_id: comment_id.unwrap_or_default(),
+                comment: comment.unwrap_or_default(),
+            },
+            "liked" => ActivityItem::Liked { at, project_id, project_title },
+            _ => ActivityItem::StatusChanged { at, project_id, project_title, is_public: is_public.unwrap_or_default() },
+        }
     }
 }
 
+/// One `get_my_project_summaries` row: the project's columns plus its like count.
+#[derive(sqlx::FromRow)]
+struct OwnedProjectRow {
+    #[sqlx(flatten)]
+    project: Project,
+    like_count: i64,
+}
+
+/// One `get_pending_reviews` row: the project's columns plus the owner's email, NULL when the
+/// LEFT JOIN finds no profile.
+#[derive(sqlx::FromRow)]
+struct PendingReviewRow {
+    #[sqlx(flatten)]
+    project: Project,
+    owner_email: Option<String>,
+}
+
+/// One `get_project_detail` row: the project's columns plus the owner's email, which is NULL
+/// when the LEFT JOIN finds no profile.
+#[derive(sqlx::FromRow)]
+struct ProjectDetailRow {
+    #[sqlx(flatten)]
+    project: Project,
+    owner_email: Option<String>,
+}
+
+/// The source of every public listing built with `QueryBuilder`: the `public_projects` view
+/// (approved projects, migration 28), aliased to the table name so the filters can qualify
+/// columns with it. The `query!` macros read the same view; its columns come back nullable to
+/// them, so the NOT NULL ones carry a `!` override.
+const PUBLIC_PROJECTS: &str = " FROM public_projects projects ";
+
 #[async_trait]
 impl Repository for PostgresRepository {
 
@@ -92,47 +1276,67 @@ impl Repository for PostgresRepository {
     ///
     /// Implements flexible search/filtering using QueryBuilder for safe parameterization,
     /// adhering to the **"No SQL Injection Risk"** mandate.
-    /// **Security**: Strictly enforces `WHERE is_public = true` in the base query.
-    async fn get_projects(&self, year: Option<i32>, search: Option<String>) -> Vec<Project> {
+    /// **Security**: Strictly reads `public_projects` in the base query.
+    async fn get_projects(&self, filter: ProjectListFilter, page: PageRequest) -> (Vec<Project>, i64) {
         let mut builder: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
             r#"
             SELECT 
                 id, user_id, author, title, abstract, 
-                cover_image, video, report, is_public, report_is_public, 
-                year, created_at, updated_at 
-            FROM projects 
-            WHERE is_public = true 
+                cover_image, video, report, status, is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, 
+                year, created_at, updated_at, (SELECT COUNT(*) FROM visible_comments c WHERE c.project_id = projects.id) AS comment_count, 
+                ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS tags 
             "#
         );
-        
-        if let Some(y) = year {
-            builder.push(" AND year = ");
-            builder.push_bind(y);
+        builder.push(PUBLIC_PROJECTS);
+        if filter.sort == ProjectSort::MostLiked {
+            // The per-project like count of `get_top_projects`, pre-aggregated so the bare
+            // column names used by the filters stay unambiguous.
+            builder.push(" LEFT JOIN (SELECT project_id, COUNT(*) AS like_count FROM project_likes GROUP BY project_id) likes ON likes.project_id = projects.id ");
         }
-        
-        if let Some(s) = search {
-            // Case-insensitive search across title, abstract, and author fields.
-            let search_pattern = format!("%{}%", s);
-            builder.push(" AND (title ILIKE ");
-            builder.push_bind(search_pattern.clone());
-            builder.push(" OR abstract ILIKE ");
-            builder.push_bind(search_pattern.clone());
-            builder.push(" OR author ILIKE ");
-            builder.push_bind(search_pattern);
+        // Every filter starts with AND.
+        builder.push(" WHERE true");
+        push_project_filters(&mut builder, &filter);
+        if let Some(after) = page.after {
+            // Keyset mode: strictly after the cursor row in the listing order below.
+            builder.push(" AND (created_at, id) < (");
+            builder.push_bind(after.created_at);
+            builder.push(", ");
+            builder.push_bind(after.id);
             builder.push(")");
         }
-        
-        builder.push(" ORDER BY created_at DESC");
-        
-        let query = builder.build_query_as::<Project>();
-        
-        match query.fetch_all(&self.pool).await {
+        // Fixed SQL per enum variant, never user text. The id tie-breaker keeps page
+        // boundaries stable between requests.
+        builder.push(match filter.sort {
+            ProjectSort::Newest => " ORDER BY created_at DESC, id DESC",
+            ProjectSort::Oldest => " ORDER BY created_at ASC, id ASC",
+            ProjectSort::MostLiked => " ORDER BY COALESCE(likes.like_count, 0) DESC, created_at DESC, id DESC",
+            ProjectSort::Title => r#" ORDER BY LOWER(title) COLLATE "C" ASC, created_at DESC, id DESC"#,
+            ProjectSort::MostViewed => " ORDER BY view_count DESC, created_at DESC, id DESC",
+        });
+        builder.push(" LIMIT ");
+        builder.push_bind(page.per_page);
+        builder.push(" OFFSET ");
+        builder.push_bind(page.offset());
+
+        let projects = match builder.build_query_as::<Project>().fetch_all(&self.pool).await {
             Ok(p) => p,
             Err(e) => { 
                 tracing::error!("get_projects error: {:?}", e); 
                 vec![] 
             }
-        }
+        };
+
+        let mut count: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("SELECT COUNT(*)");
+        count.push(PUBLIC_PROJECTS);
+        count.push(" WHERE true");
+        push_project_filters(&mut count, &filter);
+        let total_count = count
+            .build_query_scalar::<i64>()
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("get_projects count error: {:?}", e); 0 });
+
+        (projects, total_count)
     }
 
     /// get_all_projects
@@ -141,28 +1345,127 @@ impl Repository for PostgresRepository {
     /// **Note**: Does *not* include the `WHERE is_public = true` restriction.
     async fn get_all_projects(&self) -> Vec<Project> {
         match sqlx::query_as!(Project, 
-            r#"SELECT id, user_id, author, title, abstract as abstract_text, cover_image, video, report, is_public, report_is_public, year, created_at, updated_at FROM projects ORDER BY is_public ASC, created_at DESC"#
+            r#"SELECT id, user_id, author, title, abstract as abstract_text, cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!" FROM projects ORDER BY is_public ASC, created_at DESC"#
         ).fetch_all(&self.pool).await {
             Ok(p) => p,
             Err(e) => { tracing::error!("get_all_projects error: {:?}", e); vec![] }
         }
     }
+
+    /// get_pending_reviews
+    ///
+    /// Only `status = 'pending'` is the queue: drafts are not yet submitted and rejected projects
+    /// wait on their owner. Held comments are counted, as everywhere on the admin side.
+    async fn get_pending_reviews(&self, limit: i64) -> Vec<PendingReview> {
+        let rows = sqlx::query_as::<_, PendingReviewRow>(
+            r#"SELECT p.id, p.user_id, p.author, p.title, p.abstract, p.cover_image, p.video, p.report, p.status, p.is_public, p.report_is_public, p.comments_locked, p.links, p.video_waived, p.submitted_at, p.supervisor, p.department, p.technologies, p.view_count, p.slug, p.year, p.created_at, p.updated_at,
+                      (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = p.id AND c.deleted_at IS NULL) AS comment_count,
+                      ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = p.id ORDER BY t.tag) AS tags,
+                      u.email AS owner_email
+               FROM projects p
+               LEFT JOIN profiles u ON u.id = p.user_id
+               WHERE p.status = 'pending'
+               ORDER BY COALESCE(p.submitted_at, p.created_at), p.id
+               LIMIT $1"#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_pending_reviews error: {:?}", e); vec![] });
+        let now = chrono::Utc::now();
+        rows.into_iter().map(|row| PendingReview::new(row.project, row.owner_email, now)).collect()
+    }
+
+    /// stream_all_projects
+    ///
+    /// Runs the `get_all_projects` query on a background task that forwards rows through a
+    /// bounded channel: when the client reads slowly the channel fills and fetching pauses,
+    /// and when the client disconnects the task ends and releases its connection. The first
+    /// database error is forwarded and ends the stream.
+    fn stream_all_projects(&self) -> BoxStream<'static, Result<Project, RepositoryError>> {
+        let pool = self.pool.clone();
+        let (tx, rx) = tokio::sync::mpsc::channel(PROJECT_STREAM_BUFFER);
+        tokio::spawn(async move {
+            let mut rows = sqlx::query_as!(Project,
+                r#"SELECT id, user_id, author, title, abstract as abstract_text, cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!" FROM projects ORDER BY is_public ASC, created_at DESC"#
+            ).fetch(&pool);
+            while let Some(row) = rows.next().await {
+                let failed = row.is_err();
+                if tx.send(row.map_err(RepositoryError::from)).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|row| (row, rx)) }).boxed()
+    }
+
+    /// stream_project_export
+    ///
+    /// The spreadsheet rows, streamed through the same bounded channel as
+    /// `stream_all_projects`. The owner email comes from a LEFT JOIN on `profiles`, so a
+    /// project whose owner has no profile is still exported.
+    fn stream_project_export(&self) -> BoxStream<'static, Result<ProjectExportRow, RepositoryError>> {
+        let pool = self.pool.clone();
+        let (tx, rx) = tokio::sync::mpsc::channel(PROJECT_STREAM_BUFFER);
+        tokio::spawn(async move {
+            let mut rows = sqlx::query_as!(ProjectExportRow,
+                r#"SELECT p.id, p.title, p.author, p.year, p.status AS "status: ProjectStatus",
+                          (SELECT COUNT(*) FROM project_likes l WHERE l.project_id = p.id) AS "like_count!",
+                          p.created_at, u.email AS "owner_email?"
+                   FROM projects p LEFT JOIN profiles u ON u.id = p.user_id
+                   ORDER BY p.created_at ASC, p.id ASC"#
+            ).fetch(&pool);
+            while let Some(row) = rows.next().await {
+                let failed = row.is_err();
+                if tx.send(row.map_err(RepositoryError::from)).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|row| (row, rx)) }).boxed()
+    }
     
     /// get_top_projects
     ///
-    /// Retrieves projects by a ranking based on the number of likes.
-    /// **Security**: Enforces `WHERE p.is_public = true`.
-    async fn get_top_projects(&self, limit: i64) -> Vec<Project> {
+    /// Retrieves projects by a ranking based on the number of likes. The LEFT JOIN keeps
+    /// unliked projects, so `min_likes = 0` can return them; the threshold drops them otherwise.
+    /// **Security**: Reads `public_projects` only.
+    async fn get_top_projects(&self, limit: i64, min_likes: i64, year: Option<i32>) -> Vec<Project> {
         match sqlx::query_as!(
             Project,
-            r#"SELECT p.id, p.user_id, p.author, p.title, p.abstract as abstract_text, p.cover_image, p.video, p.report, p.is_public, p.report_is_public, p.year, p.created_at, p.updated_at FROM projects p LEFT JOIN project_likes l ON p.id = l.project_id WHERE p.is_public = true GROUP BY p.id ORDER BY COUNT(l.user_id) DESC LIMIT $1"#,
-            limit
+            r#"SELECT p.id AS "id!", p.user_id AS "user_id!", p.author AS "author!", p.title AS "title!", p.abstract AS "abstract_text!", p.cover_image AS "cover_image!", p.video, p.report, p.status AS "status!: ProjectStatus", p.is_public AS "is_public!", p.report_is_public AS "report_is_public!", p.comments_locked AS "comments_locked!", p.links AS "links!", p.video_waived AS "video_waived!", p.submitted_at, p.supervisor, p.department, p.technologies AS "technologies!", p.view_count AS "view_count!", p.slug AS "slug!", p.year AS "year!", p.created_at AS "created_at!", p.updated_at AS "updated_at!", (SELECT COUNT(*) FROM visible_comments c WHERE c.project_id = p.id) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = p.id ORDER BY t.tag) AS "tags!" FROM public_projects p LEFT JOIN (SELECT project_id, COUNT(*) AS like_count FROM project_likes GROUP BY project_id) l ON l.project_id = p.id WHERE ($3::int IS NULL OR p.year = $3) AND COALESCE(l.like_count, 0) >= $2 ORDER BY COALESCE(l.like_count, 0) DESC, p.created_at DESC, p.id DESC LIMIT $1"#,
+            limit,
+            min_likes,
+            year
         ).fetch_all(&self.pool).await {
             Ok(p) => p,
             Err(e) => { tracing::error!("get_top_projects error: {:?}", e); vec![] }
         }
     }
 
+    /// get_year_archive
+    ///
+    /// The whole ranking of one year: `get_top_projects` without a limit or a like threshold,
+    /// so the archive and the featured list can never disagree on order.
+    async fn get_year_archive(&self, year: i32) -> Vec<Project> {
+        self.get_top_projects(i64::MAX, 0, Some(year)).await
+    }
+
+    /// get_random_public_project
+    ///
+    /// Ranks the `public_projects` view (so hidden projects can never come up) by a hash of each
+    /// id and the seed. Without a seed every row hashes with its own `random()` value instead.
+    async fn get_random_public_project(&self, seed: Option<i64>) -> Option<Project> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT p.id AS "id!", p.user_id AS "user_id!", p.author AS "author!", p.title AS "title!", p.abstract AS "abstract_text!", p.cover_image AS "cover_image!", p.video, p.report, p.status AS "status!: ProjectStatus", p.is_public AS "is_public!", p.report_is_public AS "report_is_public!", p.comments_locked AS "comments_locked!", p.links AS "links!", p.video_waived AS "video_waived!", p.submitted_at, p.supervisor, p.department, p.technologies AS "technologies!", p.view_count AS "view_count!", p.slug AS "slug!", p.year AS "year!", p.created_at AS "created_at!", p.updated_at AS "updated_at!", (SELECT COUNT(*) FROM visible_comments c WHERE c.project_id = p.id) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = p.id ORDER BY t.tag) AS "tags!" FROM public_projects p ORDER BY md5(p.id::text || COALESCE($1::bigint::text, random()::text)) LIMIT 1"#,
+            seed
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_random_public_project error: {:?}", e); None })
+    }
+
     /// get_project
     ///
     /// Simple retrieval of any project by ID (no visibility check). Primarily for internal use
@@ -170,8 +1473,8 @@ impl Repository for PostgresRepository {
     async fn get_project(&self, id: Uuid) -> Option<Project> {
         sqlx::query_as!(Project, 
             r#"SELECT id, user_id, author, title, abstract as abstract_text, 
-                      cover_image, video, report, is_public, report_is_public, 
-                      year, created_at, updated_at 
+                      cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, 
+                      year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!" 
                 FROM projects 
                 WHERE id = $1"#,
             id)
@@ -187,8 +1490,8 @@ impl Repository for PostgresRepository {
     async fn get_project_authorized(&self, id: Uuid, user_id: Uuid) -> Option<Project> {
         sqlx::query_as!(Project, 
             r#"SELECT id, user_id, author, title, abstract as abstract_text, 
-                      cover_image, video, report, is_public, report_is_public, 
-                      year, created_at, updated_at 
+                      cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, 
+                      year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL AND NOT c.hidden_pending_review) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!" 
                 FROM projects 
                 WHERE id = $1 AND (is_public = true OR user_id = $2)"#,
             id, user_id)
@@ -200,14 +1503,15 @@ impl Repository for PostgresRepository {
 
     /// get_public_project
     ///
-    /// Retrieves a project *only* if it is marked as public. Used by the public detail handler.
+    /// Retrieves a project *only* if it is public (read through `public_projects`). Used by the
+    /// public detail handler.
     async fn get_public_project(&self, id: Uuid) -> Option<Project> {
         sqlx::query_as!(Project, 
-            r#"SELECT id, user_id, author, title, abstract as abstract_text, 
-                      cover_image, video, report, is_public, report_is_public, 
-                      year, created_at, updated_at 
-                FROM projects 
-                WHERE id = $1 AND is_public = true"#, 
+            r#"SELECT id AS "id!", user_id AS "user_id!", author AS "author!", title AS "title!", abstract AS "abstract_text!", 
+                      cover_image AS "cover_image!", video, report, status AS "status!: ProjectStatus", is_public AS "is_public!", report_is_public AS "report_is_public!", comments_locked AS "comments_locked!", links AS "links!", video_waived AS "video_waived!", submitted_at, supervisor, department, technologies AS "technologies!", view_count AS "view_count!", slug AS "slug!", 
+                      year AS "year!", created_at AS "created_at!", updated_at AS "updated_at!", (SELECT COUNT(*) FROM visible_comments c WHERE c.project_id = projects.id) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!" 
+                FROM public_projects projects 
+                WHERE id = $1"#, 
             id)
         .fetch_optional(&self.pool).await.unwrap_or_else(|e| { 
             tracing::error!("get_public_project error: {:?}", e); 
@@ -215,87 +1519,510 @@ impl Repository for PostgresRepository {
         })
     }
 
-    /// create_project
+    /// get_project_detail
     ///
-    /// Inserts a new project. All new projects are set to `is_public = false` by default,
-    /// requiring administrative approval.
-    async fn create_project(&self, req: CreateProjectRequest, user_id: Uuid) -> Project {
-        let new_id = Uuid::new_v4();
-        sqlx::query_as!(
-            Project,
-            r#"INSERT INTO projects (id, user_id, author, title, abstract, cover_image, video, report, year, is_public, report_is_public, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, false, false, NOW(), NOW()) RETURNING id, user_id, author, title, abstract as abstract_text, cover_image, video, report, is_public, report_is_public, year, created_at, updated_at"#,
-            new_id, user_id, req.author_name, req.title, req.abstract_text, req.cover_image_key, req.video_key, req.report_key, req.year
-        ).fetch_one(&self.pool).await.expect("Failed to insert project")
+    /// One query for the detail view: the project LEFT JOINed with its owner's profile. The
+    /// public rule is read from `public_projects`; owners also see their own projects and admins
+    /// every project, as in `get_project_authorized` and `get_project`.
+    async fn get_project_detail(&self, id: Uuid, viewer: ProjectViewer) -> Option<(Project, Option<ProjectOwner>)> {
+        let (admin, user_id) = match viewer {
+            ProjectViewer::Public => (false, None),
+            ProjectViewer::User(user_id) => (false, Some(user_id)),
+            ProjectViewer::Admin => (true, None),
+        };
+        let row = sqlx::query_as::<_, ProjectDetailRow>(
+            r#"SELECT p.id, p.user_id, p.author, p.title, p.abstract, p.cover_image, p.video, p.report, p.status, p.is_public, p.report_is_public, p.comments_locked, p.links, p.video_waived, p.submitted_at, p.supervisor, p.department, p.technologies, p.view_count, p.slug, p.year, p.created_at, p.updated_at,
+                      (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = p.id AND c.deleted_at IS NULL AND ($2 OR NOT c.hidden_pending_review)) AS comment_count,
+                      ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = p.id ORDER BY t.tag) AS tags,
+                      u.email AS owner_email
+               FROM projects p
+               LEFT JOIN profiles u ON u.id = p.user_id
+               WHERE p.id = $1
+                 AND ($2 OR p.user_id = $3 OR EXISTS (SELECT 1 FROM public_projects pp WHERE pp.id = p.id))"#,
+        )
+        .bind(id)
+        .bind(admin)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("get_project_detail error: {:?}", e);
+            None
+        })?;
+        let owner = row.owner_email.map(|email| ProjectOwner::new(row.project.user_id, email));
+        Some((row.project, owner))
     }
 
-    /// like_project
+    /// resolve_project_slug
     ///
-    /// Inserts a project like. Uses `ON CONFLICT DO NOTHING` to ensure **idempotency**.
-    /// The function returns true only if a new row was inserted (`rows_affected > 0`).
-    async fn like_project(&self, like: Like) -> bool {
-        let result = sqlx::query!("INSERT INTO project_likes (user_id, project_id) VALUES ($1, $2) ON CONFLICT DO NOTHING", like.user_id, like.project_id).execute(&self.pool).await;
-        match result { 
-            Ok(res) => res.rows_affected() > 0, 
-            Err(e) => { 
-                // A true conflict (double vote) does not error, only database errors are caught here.
-                tracing::error!("like error: {:?}", e); 
-                false 
-            } 
-        }
+    /// Looks `slug` up in the slug history, so links made before a rename still resolve.
+    async fn resolve_project_slug(&self, slug: &str) -> Option<Uuid> {
+        sqlx::query_scalar!("SELECT project_id FROM project_slugs WHERE slug = $1", slug)
+            .fetch_optional(&self.pool)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("resolve_project_slug error: {:?}", e);
+                None
+            })
     }
 
-    /// set_project_status
+    /// find_project_by_title_year
     ///
-    /// Updates the `is_public` flag. Used by the admin status update handler.
-    async fn set_project_status(&self, id: Uuid, is_public: bool) -> Option<Project> {
-        sqlx::query_as!(Project, r#"UPDATE projects SET is_public = $1 WHERE id = $2 RETURNING id, user_id, author, title, abstract as abstract_text, cover_image, video, report, is_public, report_is_public, year, created_at, updated_at"#, is_public, id)
-        .fetch_optional(&self.pool).await.unwrap_or_else(|e| { tracing::error!("status error: {:?}", e); None })
+    /// Served by `idx_projects_title_year`, whose expression this repeats.
+    async fn find_project_by_title_year(&self, title: &str, year: i32) -> Option<Uuid> {
+        sqlx::query_scalar!(
+            r#"SELECT id FROM projects
+               WHERE year = $1 AND lower(regexp_replace(btrim(title), '\s+', ' ', 'g')) = $2
+               ORDER BY created_at, id
+               LIMIT 1"#,
+            year,
+            title_key(title)
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("find_project_by_title_year error: {:?}", e);
+            None
+        })
     }
 
-    /// get_user
+    /// count_user_projects_in_year
     ///
-    /// Retrieves user profile data (ID, email, role) needed for authentication and authorization.
-    async fn get_user(&self, id: Uuid) -> Option<User> {
-        sqlx::query_as!(User, "SELECT id, email, role FROM profiles WHERE id = $1", id).fetch_optional(&self.pool).await.unwrap_or(None)
+    /// Drafts and rejected projects count too: the cap is on submissions made, not on those
+    /// that got through.
+    async fn count_user_projects_in_year(&self, user_id: Uuid, year: i32) -> i64 {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM projects WHERE user_id = $1 AND year = $2"#,
+            user_id,
+            year
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("count_user_projects_in_year error: {:?}", e);
+            0
+        })
     }
 
-    /// create_user
+    /// get_changed_project_ids
     ///
-    /// Creates the mirroring profile record in `public.profiles` after external auth success.
-    async fn create_user(&self, user: User) -> User {
-        sqlx::query_as!(User, "INSERT INTO profiles (id, email, role) VALUES ($1, $2, $3) RETURNING id, email, role", user.id, user.email, user.role).fetch_one(&self.pool).await.expect("Failed to create user")
+    /// One pass over public projects: a project counts as changed if its own row was updated
+    /// after `since` or it gained a comment after `since`. `ids = None` checks every public project.
+    async fn get_changed_project_ids(&self, since: chrono::DateTime<chrono::Utc>, ids: Option<Vec<Uuid>>) -> Vec<Uuid> {
+        sqlx::query_scalar!(
+            r#"SELECT p.id AS "id!" FROM public_projects p
+               WHERE ($2::uuid[] IS NULL OR p.id = ANY($2))
+                 AND (p.updated_at > $1
+                      OR EXISTS (SELECT 1 FROM project_comments c WHERE c.project_id = p.id AND c.created_at > $1))
+               ORDER BY p.id"#,
+            since,
+            ids.as_deref()
+        )
+        .fetch_all(&self.pool).await.unwrap_or_else(|e| {
+            tracing::error!("get_changed_project_ids error: {:?}", e);
+            vec![]
+        })
     }
 
-    /// get_stats
+    /// get_public_events
     ///
-    /// Compiles all necessary counters for the administrative dashboard in a single call.
-    async fn get_stats(&self) -> AdminDashboardStats {
-        let total_projects = sqlx::query_scalar!("SELECT COUNT(*) FROM projects").fetch_one(&self.pool).await.unwrap_or(Some(0)).unwrap_or(0);
-        let total_users = sqlx::query_scalar!("SELECT COUNT(*) FROM profiles").fetch_one(&self.pool).await.unwrap_or(Some(0)).unwrap_or(0);
-        let total_likes = sqlx::query_scalar!("SELECT COUNT(*) FROM project_likes").fetch_one(&self.pool).await.unwrap_or(Some(0)).unwrap_or(0);
-        let pending_reviews = sqlx::query_scalar!("SELECT COUNT(*) FROM projects WHERE is_public = false").fetch_one(&self.pool).await.unwrap_or(Some(0)).unwrap_or(0);
-        AdminDashboardStats { total_projects, total_users, total_likes, pending_reviews }
-    }
+    /// Reads a page of `public_events` (written by triggers on `projects`), then the projects
+    /// of its publish events that are still public in one more query, with the columns of
+    /// `get_public_project`.
+    async fn get_public_events(&self, since: chrono::DateTime<chrono::Utc>, limit: i64) -> Vec<PublicEvent> {
+        let rows = match sqlx::query!(
+            r#"SELECT id, kind AS "kind: PublicEventKind", project_id, created_at
+               FROM public_events
+               WHERE created_at > $1
+               ORDER BY created_at, id
+               LIMIT $2"#,
+            since,
+            limit
+        )
+        .fetch_all(&self.pool).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::error!("get_public_events error: {:?}", e);
+                return vec![];
+            }
+        };
 
-    // --- OWNER ACTIONS ---
+        let published: Vec<Uuid> = rows
+            .iter()
+            .filter(|row| row.kind == PublicEventKind::Published)
+            .map(|row| row.project_id)
+            .collect();
+        let projects = match sqlx::query_as!(Project,
+            r#"SELECT id AS "id!", user_id AS "user_id!", author AS "author!", title AS "title!", abstract AS "abstract_text!", 
+                      cover_image AS "cover_image!", video, report, status AS "status!: ProjectStatus", is_public AS "is_public!", report_is_public AS "report_is_public!", comments_locked AS "comments_locked!", links AS "links!", video_waived AS "video_waived!", submitted_at, supervisor, department, technologies AS "technologies!", view_count AS "view_count!", slug AS "slug!", 
+                      year AS "year!", created_at AS "created_at!", updated_at AS "updated_at!", (SELECT COUNT(*) FROM visible_comments c WHERE c.project_id = projects.id) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!" 
+                FROM public_projects projects 
+                WHERE id = ANY($1)"#,
+            &published)
+        .fetch_all(&self.pool).await {
+            Ok(projects) => projects.into_iter().map(|p| (p.id, p)).collect::<HashMap<_, _>>(),
+            // A page without its projects would read as "no longer public", so send nothing.
+            Err(e) => {
+                tracing::error!("get_public_events projects error: {:?}", e);
+                return vec![];
+            }
+        };
 
-    /// get_my_projects
+        rows.into_iter()
+            .map(|row| PublicEvent {
+                id: row.id,
+                kind: row.kind,
+                project_id: row.project_id,
+                at: row.created_at,
+                project: match row.kind {
+                    PublicEventKind::Published => projects.get(&row.project_id).cloned(),
+                    PublicEventKind::Unpublished => None,
+                },
+            })
+            .collect()
+    }
+
+    /// increment_view
     ///
-    /// Retrieves all projects owned by the authenticated user, including unapproved/hidden ones.
-    async fn get_my_projects(&self, user_id: Uuid) -> Vec<Project> {
-        match sqlx::query_as!(Project, r#"SELECT id, user_id, author, title, abstract as abstract_text, cover_image, video, report, is_public, report_is_public, year, created_at, updated_at FROM projects WHERE user_id = $1 ORDER BY created_at DESC"#, user_id).fetch_all(&self.pool).await {
-            Ok(p) => p,
-            Err(e) => { tracing::error!("get_my_projects error: {:?}", e); vec![] }
-        }
+    /// A single in-place `UPDATE`, so concurrent views never lose increments and no row is
+    /// read first.
+    async fn increment_view(&self, id: Uuid) -> Option<i64> {
+        sqlx::query_scalar!(
+            "UPDATE projects SET view_count = view_count + 1 WHERE id = $1 AND is_public = true RETURNING view_count",
+            id
+        )
+        .fetch_optional(&self.pool).await.unwrap_or_else(|e| {
+            tracing::error!("increment_view error: {:?}", e);
+            None
+        })
     }
 
-    /// delete_project
+    /// get_like_count
     ///
-    /// Deletes a project only if the provided `user_id` matches the project owner.
+    /// One `COUNT(*)` over the project's likes, through `public_projects` so hidden projects
+    /// answer like missing ones.
+    async fn get_like_count(&self, id: Uuid) -> Option<i64> {
+        sqlx::query_scalar!(
+            r#"SELECT (SELECT COUNT(*) FROM project_likes l WHERE l.project_id = p.id) AS "count!"
+               FROM public_projects p WHERE p.id = $1"#,
+            id
+        )
+        .fetch_optional(&self.pool).await.unwrap_or_else(|e| {
+            tracing::error!("get_like_count error: {:?}", e);
+            None
+        })
+    }
+
+    /// create_project
+    ///
+    /// Inserts a new project. All new projects start as `draft` (not public), requiring
+    /// submission and administrative approval.
+    async fn create_project(&self, req: CreateProjectRequest, user_id: Uuid) -> Project {
+        let new_id = Uuid::new_v4();
+        let mut tx = self.pool.begin().await.expect("Failed to start transaction");
+        let slug = pick_slug(&mut tx, new_id, &req.title, req.year).await.expect("Failed to pick a project slug");
+        let mut project = sqlx::query_as!(
+            Project,
+            r#"INSERT INTO projects (id, user_id, author, title, abstract, cover_image, video, report, year, links, supervisor, department, technologies, slug, status, report_is_public, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, 'draft', false, NOW(), NOW()) RETURNING id, user_id, author, title, abstract as abstract_text, cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!""#,
+            new_id, user_id, req.author_name, req.title, req.abstract_text, req.cover_image_key, req.video_key, req.report_key, req.year,
+            links_json(req.links), req.supervisor, req.department, &req.technologies, slug
+        ).fetch_one(&mut *tx).await.expect("Failed to insert project");
+        record_slug(&mut tx, new_id, &project.slug).await.expect("Failed to record project slug");
+        if let Some(codes) = req.programme_codes {
+            replace_project_programmes(&mut tx, new_id, &codes).await.expect("Failed to insert project programmes");
+        }
+        if !req.tags.is_empty() {
+            project.tags = replace_project_tags(&mut tx, new_id, &req.tags).await.expect("Failed to insert project tags");
+        }
+        tx.commit().await.expect("Failed to commit project");
+        project
+    }
+
+    /// like_project
+    ///
+    /// Inserts a project like with `ON CONFLICT DO NOTHING` (**idempotency**) and counts the
+    /// project's likes, in one transaction with the owner's notification (`record_like`). A
+    /// repeated like writes nothing, so it cannot notify twice. The project must be public and
+    /// the liker must not own it; both are part of the statement, so every caller gets them.
+    async fn like_project(&self, like: Like) -> Option<LikeOutcome> {
+        record_like(&self.pool, &like)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("like error: {:?}", e); None })
+    }
+
+    /// unlike_project
+    ///
+    /// Deletes the like and, in the same statement, the "like" notification `like_project`
+    /// sent the owner for it, so a like/unlike cycle leaves no trace.
+    async fn unlike_project(&self, like: Like) -> bool {
+        sqlx::query_scalar!(
+            r#"
+            WITH removed AS (
+                DELETE FROM project_likes WHERE user_id = $1 AND project_id = $2
+                RETURNING project_id
+            ), notification AS (
+                DELETE FROM notifications n USING removed r
+                WHERE n.project_id = r.project_id AND n.actor_id = $1 AND n.type = 'like'
+            )
+            SELECT EXISTS (SELECT 1 FROM removed) AS "removed!"
+            "#,
+            like.user_id,
+            like.project_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("unlike error: {:?}", e);
+            false
+        })
+    }
+
+    /// get_liked_project_ids
+    ///
+    /// One `EXISTS` probe of `project_likes` (its primary key) per requested project, so a
+    /// listing page costs a single round trip.
+    async fn get_liked_project_ids(&self, user_id: Uuid, project_ids: &[Uuid]) -> Vec<Uuid> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT p.id AS "id!"
+            FROM unnest($2::uuid[]) AS p(id)
+            WHERE EXISTS (SELECT 1 FROM project_likes l WHERE l.user_id = $1 AND l.project_id = p.id)
+            "#,
+            user_id,
+            project_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("get_liked_project_ids error: {:?}", e);
+            vec![]
+        })
+    }
+
+    /// get_project_likers
+    ///
+    /// The likes joined to `profiles` for the email, newest first with the user id as the
+    /// tie-breaker so pages do not overlap, plus a count of all of them.
+    async fn get_project_likers(&self, project_id: Uuid, page: PageRequest) -> (Vec<ProjectLiker>, i64) {
+        let items = sqlx::query_as!(
+            ProjectLiker,
+            r#"SELECT l.user_id AS id, u.email AS "email?", l.liked_at
+               FROM project_likes l LEFT JOIN profiles u ON u.id = l.user_id
+               WHERE l.project_id = $1
+               ORDER BY l.liked_at DESC, l.user_id
+               LIMIT $2 OFFSET $3"#,
+            project_id,
+            page.per_page,
+            page.offset()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_project_likers error: {:?}", e); vec![] });
+        let total_count = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM project_likes WHERE project_id = $1"#, project_id)
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("get_project_likers count error: {:?}", e); 0 });
+        (items, total_count)
+    }
+
+    /// set_project_status
+    ///
+    /// Updates the review `status` (and with it the generated `is_public`). Used by the admin
+    /// status update handler. Invalidates the public stats cache once the write is done,
+    /// whether or not the row existed.
+    async fn set_project_status(&self, id: Uuid, status: ProjectStatus) -> Option<Project> {
+        let project = sqlx::query_as!(Project, r#"UPDATE projects SET status = $1 WHERE id = $2 RETURNING id, user_id, author, title, abstract as abstract_text, cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!""#, status.as_str(), id)
+        .fetch_optional(&self.pool).await.unwrap_or_else(|e| { tracing::error!("status error: {:?}", e); None });
+        self.invalidate_public_stats();
+        project
+    }
+
+    /// set_projects_status_bulk
+    ///
+    /// One `UPDATE ... WHERE id = ANY($2)` that skips rows already in the target status, so
+    /// only real changes are counted (and recorded by the status history trigger). The `found`
+    /// snapshot is taken before the update, so it also covers the unchanged rows.
+    async fn set_projects_status_bulk(&self, ids: &[Uuid], is_public: bool) -> BulkStatusOutcome {
+        let status = ProjectStatus::from_visibility(is_public);
+        let rows = sqlx::query!(
+            r#"
+            WITH found AS (SELECT id FROM projects WHERE id = ANY($2)),
+            changed AS (
+                UPDATE projects SET status = $1 WHERE id = ANY($2) AND status <> $1 RETURNING id
+            )
+            SELECT f.id AS "id!", (c.id IS NOT NULL) AS "changed!"
+            FROM found f LEFT JOIN changed c ON c.id = f.id
+            "#,
+            status.as_str(),
+            ids
+        )
+        .fetch_all(&self.pool)
+        .await;
+        self.invalidate_public_stats();
+
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::error!("set_projects_status_bulk error: {:?}", e);
+                return BulkStatusOutcome::default();
+            }
+        };
+        let found: HashSet<Uuid> = rows.iter().map(|r| r.id).collect();
+        BulkStatusOutcome {
+            changed: rows.iter().filter(|r| r.changed).map(|r| r.id).collect(),
+            not_found: ids.iter().filter(|id| !found.contains(id)).copied().collect(),
+        }
+    }
+
+    /// get_user
+    ///
+    /// Retrieves user profile data (ID, email, role) needed for authentication and authorization.
+    async fn get_user(&self, id: Uuid) -> Option<User> {
+        sqlx::query_as!(User, "SELECT id, email, role FROM profiles WHERE id = $1", id).fetch_optional(&self.pool).await.unwrap_or(None)
+    }
+
+    /// create_user
+    ///
+    /// Creates the mirroring profile record in `public.profiles` after external auth success.
+    async fn create_user(&self, user: User) -> User {
+        sqlx::query_as!(User, "INSERT INTO profiles (id, email, role) VALUES ($1, $2, $3) RETURNING id, email, role", user.id, user.email, user.role).fetch_one(&self.pool).await.expect("Failed to create user")
+    }
+
+    /// get_users_by_email_prefix
+    ///
+    /// Resolves comment mentions: `split_part` cuts each email at its first `@`.
+    async fn get_users_by_email_prefix(&self, prefixes: &[String]) -> Vec<User> {
+        sqlx::query_as!(
+            User,
+            "SELECT id, email, role FROM profiles WHERE lower(split_part(email, '@', 1)) = ANY($1) ORDER BY email",
+            prefixes
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("get_users_by_email_prefix error: {:?}", e);
+            Vec::new()
+        })
+    }
+
+    /// get_stats
+    ///
+    /// Compiles all necessary counters for the administrative dashboard in a single call.
+    async fn get_stats(&self) -> AdminDashboardStats {
+        let total_projects = sqlx::query_scalar!("SELECT COUNT(*) FROM projects").fetch_one(&self.pool).await.unwrap_or(Some(0)).unwrap_or(0);
+        let total_users = sqlx::query_scalar!("SELECT COUNT(*) FROM profiles").fetch_one(&self.pool).await.unwrap_or(Some(0)).unwrap_or(0);
+        let total_likes = sqlx::query_scalar!("SELECT COUNT(*) FROM project_likes").fetch_one(&self.pool).await.unwrap_or(Some(0)).unwrap_or(0);
+        let pending_reviews = sqlx::query_scalar!("SELECT COUNT(*) FROM projects WHERE status = 'pending'").fetch_one(&self.pool).await.unwrap_or(Some(0)).unwrap_or(0);
+        AdminDashboardStats { total_projects, total_users, total_likes, pending_reviews }
+    }
+
+    /// get_public_stats
+    ///
+    /// Serves the landing-page counters from an in-process cache for `PUBLIC_STATS_TTL`.
+    /// The lock is never held across the query; concurrent misses simply both recompute.
+    /// Failures are not cached.
+    async fn get_public_stats(&self) -> PublicStats {
+        if let Some((at, stats)) = self.public_stats_cache.lock().unwrap().as_ref()
+            && at.elapsed() < PUBLIC_STATS_TTL
+        {
+            return stats.clone();
+        }
+
+        match query_public_stats(&self.pool).await {
+            Ok(stats) => {
+                *self.public_stats_cache.lock().unwrap() = Some((Instant::now(), stats.clone()));
+                stats
+            }
+            Err(e) => { tracing::error!("get_public_stats error: {:?}", e); PublicStats::default() }
+        }
+    }
+
+    // --- OWNER ACTIONS ---
+
+    /// get_my_project_summaries
+    ///
+    /// Retrieves all projects owned by the authenticated user, whatever their status, with the
+    /// like and comment counts aggregated in the same query.
+    async fn get_my_project_summaries(&self, user_id: Uuid) -> Vec<MyProjectSummary> {
+        match sqlx::query_as::<_, OwnedProjectRow>(r#"SELECT id, user_id, author, title, abstract, cover_image, video, report, status, is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS comment_count, (SELECT COUNT(*) FROM project_likes l WHERE l.project_id = projects.id) AS like_count, ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS tags FROM projects WHERE user_id = $1 ORDER BY created_at DESC"#).bind(user_id).fetch_all(&self.pool).await {
+            Ok(rows) => rows.into_iter().map(|row| MyProjectSummary::new(row.project, row.like_count)).collect(),
+            Err(e) => { tracing::error!("get_my_project_summaries error: {:?}", e); vec![] }
+        }
+    }
+
+    /// get_user_activity
+    ///
+    /// One UNION ALL over comments, likes and status history (`USER_ACTIVITY`), ordered by
+    /// time with fixed tie-breakers so page boundaries are stable, plus a count of the same rows.
+    async fn get_user_activity(&self, user_id: Uuid, page: PageRequest) -> (Vec<ActivityItem>, i64) {
+        let items = sqlx::query_as::<_, ActivityRow>(&format!(
+            "SELECT * FROM ({USER_ACTIVITY}) a ORDER BY a.at DESC, a.kind, a.project_id, a.comment_id LIMIT $3 OFFSET $4"
+        ))
+        .bind(user_id)
+        .bind(ACTIVITY_WINDOW_DAYS)
+        .bind(page.per_page)
+        .bind(page.offset())
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| rows.into_iter().map(ActivityItem::from).collect())
+        .unwrap_or_else(|e| { tracing::error!("get_user_activity error: {:?}", e); vec![] });
+        let total_count = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM ({USER_ACTIVITY}) a"))
+            .bind(user_id)
+            .bind(ACTIVITY_WINDOW_DAYS)
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("get_user_activity count error: {:?}", e); 0 });
+        (items, total_count)
+    }
+
+    /// get_project_stats
+    ///
+    /// The ownership check is part of the totals query; the daily series is only read once it
+    /// has passed. Days are UTC calendar days, generated in SQL so that quiet days are present.
+    async fn get_project_stats(&self, id: Uuid, owner_id: Uuid) -> Option<ProjectStats> {
+        let totals = sqlx::query!(
+            r#"SELECT p.view_count,
+                      (SELECT COUNT(*) FROM project_likes l WHERE l.project_id = p.id) AS "like_count!",
+                      (SELECT COUNT(*) FROM visible_comments c WHERE c.project_id = p.id) AS "comment_count!"
+               FROM projects p
+               WHERE p.id = $1 AND p.user_id = $2"#,
+            id,
+            owner_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_project_stats error: {:?}", e); None })?;
+        let likes_per_day = sqlx::query_as!(
+            DailyCount,
+            r#"SELECT d.day AS "day!", COUNT(l.project_id) AS "count!"
+               FROM generate_series((NOW() AT TIME ZONE 'UTC')::date - ($2::int - 1), (NOW() AT TIME ZONE 'UTC')::date, INTERVAL '1 day') AS g(ts)
+               CROSS JOIN LATERAL (SELECT g.ts::date AS day) d
+               LEFT JOIN project_likes l ON l.project_id = $1 AND (l.liked_at AT TIME ZONE 'UTC')::date = d.day
+               GROUP BY d.day
+               ORDER BY d.day"#,
+            id,
+            PROJECT_STATS_DAYS
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_project_stats error: {:?}", e); vec![] });
+        Some(ProjectStats {
+            project_id: id,
+            like_count: totals.like_count,
+            comment_count: totals.comment_count,
+            view_count: totals.view_count,
+            likes_per_day,
+        })
+    }
+
+    /// delete_project
+    ///
+    /// Deletes a project only if the provided `user_id` matches the project owner.
     /// This is the **Owner-Only** authorization check.
     async fn delete_project(&self, id: Uuid, user_id: Uuid) -> bool {
         match sqlx::query!("DELETE FROM projects WHERE id = $1 AND user_id = $2", id, user_id).execute(&self.pool).await {
-            Ok(res) => res.rows_affected() > 0,
+            Ok(res) if res.rows_affected() > 0 => { self.invalidate_public_stats(); true }
+            Ok(_) => false,
             Err(e) => { tracing::error!("delete error: {:?}", e); false }
         }
     }
@@ -303,10 +2030,52 @@ impl Repository for PostgresRepository {
     /// update_project
     ///
     /// Updates a project only if the provided `user_id` matches the owner.
-    /// Uses the PostgreSQL `COALESCE` function to efficiently handle `Option<T>` fields,
-    /// only updating a column if the corresponding field in `req` is `Some`.
-    async fn update_project(&self, id: Uuid, user_id: Uuid, req: UpdateProjectRequest) -> Option<Project> {
-        sqlx::query_as!(
+    /// The row is read first (locked) and the request reduced to what actually differs; the
+    /// PostgreSQL `COALESCE` function then only updates a column if its field is still `Some`.
+    /// Nothing is written, and `updated_at` is kept, when no field differs.
+    async fn update_project(&self, id: Uuid, user_id: Uuid, req: UpdateProjectRequest) -> Option<ProjectUpdateResult> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| tracing::error!("update error: {:?}", e)).ok()?;
+        // Locked, so the comparison below still holds when the update is written.
+        let current = sqlx::query_as!(
+            Project,
+            r#"SELECT id, user_id, author, title, abstract as abstract_text,
+                      cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug,
+                      year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!"
+                FROM projects
+                WHERE id = $1 AND user_id = $2
+                FOR UPDATE"#,
+            id, user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("update error: {:?}", e); None })?;
+        let programmes = match req.programme_codes {
+            Some(_) => sqlx::query_scalar!("SELECT programme_code FROM project_programmes WHERE project_id = $1", id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(|e| tracing::error!("update programmes error: {:?}", e))
+                .ok()?,
+            None => vec![],
+        };
+        let (req, changed_fields) = reduce_project_update(&current, &programmes, req);
+        if changed_fields.is_empty() {
+            return Some(ProjectUpdateResult { project: current, changed_fields, ..ProjectUpdateResult::default() });
+        }
+
+        let programme_codes = req.programme_codes;
+        let tags = req.tags;
+        // A new title or year means a new slug; the old one stays in `project_slugs`.
+        let slug = match (&req.title, req.year) {
+            (None, None) => None,
+            (title, year) => Some(
+                pick_slug(&mut tx, id, title.as_ref().unwrap_or(&current.title), year.unwrap_or(current.year))
+                    .await
+                    .map_err(|e| tracing::error!("update slug error: {:?}", e))
+                    .ok()?,
+            ),
+        };
+        let mut project = sqlx::query_as!(
             Project,
             r#"
             UPDATE projects 
@@ -315,18 +2084,53 @@ impl Repository for PostgresRepository {
                 cover_image = COALESCE($5, cover_image),
                 video = COALESCE($6, video),
                 report = COALESCE($7, report),
+                links = COALESCE($8, links),
+                video_waived = COALESCE($9, video_waived),
+                supervisor = COALESCE($10, supervisor),
+                department = COALESCE($11, department),
+                author = COALESCE($12, author),
+                year = COALESCE($13, year),
+                slug = COALESCE($14, slug),
+                technologies = COALESCE($15, technologies),
                 updated_at = NOW()
             WHERE id = $1 AND user_id = $2
             RETURNING id, user_id, author, title, abstract as abstract_text, 
-                      cover_image, video, report, is_public, report_is_public, 
-                      year, created_at, updated_at
+                      cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, 
+                      year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!"
             "#,
             id, user_id,
-            req.title, req.abstract_text, req.cover_image_key, req.video_key, req.report_key
+            req.title, req.abstract_text, req.cover_image_key, req.video_key, req.report_key,
+            req.links.map(|links| links_json(Some(links))), req.video_waived, req.supervisor, req.department,
+            req.author_name, req.year, slug, req.technologies.as_deref()
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
-        .unwrap_or_else(|e| { tracing::error!("update error: {:?}", e); None })
+        .unwrap_or_else(|e| { tracing::error!("update error: {:?}", e); None })?;
+
+        if slug.is_some()
+            && let Err(e) = record_slug(&mut tx, id, &project.slug).await
+        {
+            tracing::error!("update slug error: {:?}", e);
+            return None;
+        }
+        // Programmes are only touched once the ownership check above has passed.
+        if let Some(codes) = &programme_codes
+            && let Err(e) = replace_project_programmes(&mut tx, id, codes).await
+        {
+            tracing::error!("update programmes error: {:?}", e);
+            return None;
+        }
+        if let Some(tags) = &tags {
+            match replace_project_tags(&mut tx, id, tags).await {
+                Ok(stored) => project.tags = stored,
+                Err(e) => {
+                    tracing::error!("update tags error: {:?}", e);
+                    return None;
+                }
+            }
+        }
+        tx.commit().await.map_err(|e| tracing::error!("update error: {:?}", e)).ok()?;
+        Some(ProjectUpdateResult { project, changed_fields, ..ProjectUpdateResult::default() })
     }
     
     // --- COMMENT ACTIONS ---
@@ -334,43 +2138,354 @@ impl Repository for PostgresRepository {
     /// add_comment
     ///
     /// Inserts a new comment and immediately joins with `profiles` to return the enriched
-    /// `Comment` model, including the author's email.
-    async fn add_comment(&self, project_id: Uuid, user_id: Uuid, text: String) -> Comment {
+    /// `Comment` model, including the author's email. The row is selected from `projects` only
+    /// while its discussion is unlocked, so a missing or locked project inserts nothing and is
+    /// answered with None, as is a missing profile (a foreign-key violation). Any other error
+    /// rolls the transaction back and is returned.
+    async fn add_comment(&self, project_id: Uuid, user_id: Uuid, text: String, fanout_threshold: i64) -> Result<Option<PostedComment>, RepositoryError> {
+        let mut tx = self.pool.begin().await?;
         // Uses a CTE (Common Table Expression) to perform the insert and subsequent join in one query.
-        let rec = sqlx::query!(
+        let inserted = sqlx::query!(
             r#"
             WITH inserted AS (
-                INSERT INTO project_comments (project_id, user_id, comment) VALUES ($1, $2, $3) RETURNING id, user_id, project_id, comment, created_at
+                INSERT INTO project_comments (project_id, user_id, comment)
+                -- FOR SHARE holds off a concurrent lock until this commits, and re-checks the
+                -- flag if one committed first.
+                SELECT id, $2, $3 FROM projects WHERE id = $1 AND NOT comments_locked FOR SHARE
+                RETURNING id, user_id, project_id, comment, created_at
             )
             SELECT i.id, i.user_id, i.project_id, i.comment, i.created_at, p.email as author_email
             FROM inserted i JOIN profiles p ON i.user_id = p.id
             "#,
             project_id, user_id, text
         )
-        .fetch_one(&self.pool).await.expect("Failed to add comment");
+        .fetch_optional(&mut *tx).await;
+        let rec = match inserted {
+            Ok(Some(rec)) => rec,
+            Ok(None) => return Ok(None),
+            // `project_comments` references both `projects` and `profiles` (SQLSTATE 23503).
+            Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        // Small fan-outs are written here; larger ones become one outbox row for the dispatcher.
+        let recipients = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM comment_recipients($1, $2)"#,
+            project_id, user_id
+        )
+        .fetch_one(&mut *tx).await?;
+        let fanout_job_id = if recipients > fanout_threshold {
+            let payload = serde_json::json!({ "project_id": project_id, "actor_id": user_id, "comment_id": rec.id });
+            let id = sqlx::query_scalar!(
+                "INSERT INTO outbox (kind, payload, total) VALUES ('pending_fanout', $1, $2) RETURNING id",
+                payload, recipients
+            )
+            .fetch_one(&mut *tx).await?;
+            Some(id)
+        } else {
+            sqlx::query!(
+                r#"
+                INSERT INTO notifications (user_id, actor_id, project_id, type)
+                SELECT r.recipient_id, $2, $1, 'comment' FROM comment_recipients($1, $2) r
+                "#,
+                project_id, user_id
+            )
+            .execute(&mut *tx).await?;
+            None
+        };
+        tx.commit().await?;
 
         // Manually map the anonymous record to the final enriched Comment struct.
-        Comment { id: rec.id, user_id: rec.user_id, project_id: rec.project_id, comment: rec.comment, created_at: rec.created_at, author_email: Some(rec.author_email) }
+        let comment = Comment { id: rec.id, user_id: Some(rec.user_id), project_id: rec.project_id, comment: rec.comment, created_at: rec.created_at, author_email: Some(rec.author_email), hidden_pending_review: false, edited_at: None, edited: false, is_deleted: false };
+        Ok(Some(PostedComment { comment, fanout_job_id }))
     }
 
-    /// get_comments
+    async fn get_comments(&self, project_id: Uuid, order: CommentOrder, hidden: HiddenComments) -> Vec<Comment> {
+        let newest_first = order == CommentOrder::Newest;
+        let include_hidden = hidden == HiddenComments::Include;
+        let viewer = match hidden {
+            HiddenComments::AuthoredBy(user_id) => Some(user_id),
+            _ => None,
+        };
+        sqlx::query_as!(
+            Comment,
+            r#"
+            SELECT
+                t.id AS "id!", t.user_id, t.project_id AS "project_id!", t.comment AS "comment!",
+                t.created_at AS "created_at!", t.author_email,
+                t.hidden_pending_review AS "hidden_pending_review!", t.edited_at,
+                t.edited AS "edited!", t.is_deleted AS "is_deleted!"
+            -- The visibility rule and the placeholder projection live in `comment_thread`.
+            FROM comment_thread($1, $3, $4, $5) t
+            -- The id tie-breaker keeps comments posted in the same instant in a stable order.
+            ORDER BY
+                CASE WHEN $2 THEN t.created_at END DESC,
+                CASE WHEN $2 THEN t.id END DESC,
+                t.created_at ASC,
+                t.id ASC
+            "#,
+            project_id,
+            newest_first,
+            include_hidden,
+            viewer as Option<Uuid>,
+            DELETED_COMMENT_TEXT
+        ).fetch_all(&self.pool).await.unwrap_or_default()
+    }
+
+    /// get_comments_before
     ///
-    /// Retrieves all comments for a project, enforcing the **Visibility Logic** by joining
-    /// with the `projects` table and checking `pr.is_public = true`.
-    async fn get_comments(&self, project_id: Uuid) -> Vec<Comment> {
+    /// Pages backwards by id, which follows posting order; a time cursor only narrows the rows
+    /// the id order walks through.
+    async fn get_comments_before(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        before: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        let include_hidden = hidden == HiddenComments::Include;
+        let viewer = match hidden {
+            HiddenComments::AuthoredBy(user_id) => Some(user_id),
+            _ => None,
+        };
+        let (before_id, before_time) = match before {
+            Some(CommentCursor::Id(id)) => (Some(id), None),
+            Some(CommentCursor::Time(at)) => (None, Some(at)),
+            None => (None, None),
+        };
         sqlx::query_as!(
             Comment,
             r#"
-            SELECT 
-                c.id, c.user_id, c.project_id, c.comment, c.created_at, p.email as author_email
-            FROM project_comments c 
-            JOIN profiles p ON c.user_id = p.id
-            JOIN projects pr ON c.project_id = pr.id -- Enforces project existence/visibility
-            WHERE c.project_id = $1 AND pr.is_public = true -- ADDED VISIBILITY CHECK
-            ORDER BY c.created_at ASC
+            SELECT
+                t.id AS "id!", t.user_id, t.project_id AS "project_id!", t.comment AS "comment!",
+                t.created_at AS "created_at!", t.author_email,
+                t.hidden_pending_review AS "hidden_pending_review!", t.edited_at,
+                t.edited AS "edited!", t.is_deleted AS "is_deleted!"
+            FROM comment_thread($1, $2, $3, $7) t
+            WHERE ($4::bigint IS NULL OR t.id < $4)
+              AND ($5::timestamptz IS NULL OR t.created_at < $5)
+            ORDER BY t.id DESC
+            LIMIT $6
             "#,
+            project_id,
+            include_hidden,
+            viewer as Option<Uuid>,
+            before_id,
+            before_time,
+            limit,
+            DELETED_COMMENT_TEXT
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_comments_before error: {:?}", e); vec![] })
+    }
+
+    /// get_comments_after
+    ///
+    /// `get_comments_before` walking the other way: forwards by id from the cursor.
+    async fn get_comments_after(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        after: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        let include_hidden = hidden == HiddenComments::Include;
+        let viewer = match hidden {
+            HiddenComments::AuthoredBy(user_id) => Some(user_id),
+            _ => None,
+        };
+        let (after_id, after_time) = match after {
+            Some(CommentCursor::Id(id)) => (Some(id), None),
+            Some(CommentCursor::Time(at)) => (None, Some(at)),
+            None => (None, None),
+        };
+        sqlx::query_as!(
+            Comment,
+            r#"
+            SELECT
+                t.id AS "id!", t.user_id, t.project_id AS "project_id!", t.comment AS "comment!",
+                t.created_at AS "created_at!", t.author_email,
+                t.hidden_pending_review AS "hidden_pending_review!", t.edited_at,
+                t.edited AS "edited!", t.is_deleted AS "is_deleted!"
+            FROM comment_thread($1, $2, $3, $7) t
+            WHERE ($4::bigint IS NULL OR t.id > $4)
+              AND ($5::timestamptz IS NULL OR t.created_at > $5)
+            ORDER BY t.id ASC
+            LIMIT $6
+            "#,
+            project_id,
+            include_hidden,
+            viewer as Option<Uuid>,
+            after_id,
+            after_time,
+            limit,
+            DELETED_COMMENT_TEXT
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_comments_after error: {:?}", e); vec![] })
+    }
+
+    /// get_recent_comments
+    ///
+    /// One pass over the user's last day of comments and edits (`idx_project_comments_user`,
+    /// `idx_comment_edits_user`).
+    async fn get_recent_comments(&self, user_id: Uuid) -> RecentComments {
+        sqlx::query_as!(
+            RecentComments,
+            r#"
+            WITH activity AS (
+                SELECT created_at AS at FROM project_comments
+                WHERE user_id = $1 AND created_at > NOW() - INTERVAL '1 day'
+                UNION ALL
+                SELECT edited_at FROM comment_edits
+                WHERE user_id = $1 AND edited_at > NOW() - INTERVAL '1 day'
+            )
+            SELECT
+                COUNT(*) FILTER (WHERE at > NOW() - INTERVAL '1 minute') AS "last_minute!",
+                MIN(at) FILTER (WHERE at > NOW() - INTERVAL '1 minute') AS oldest_last_minute,
+                COUNT(*) AS "last_day!",
+                MIN(at) AS oldest_last_day
+            FROM activity
+            "#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_recent_comments error: {:?}", e); RecentComments::default() })
+    }
+
+    /// count_comments
+    ///
+    /// Applies the same visibility rule as `get_comments` for an anonymous viewer (hidden
+    /// comments are not counted). One `COUNT(*)`, through `public_projects` like
+    /// `get_like_count`, so private projects answer like missing ones.
+    async fn count_comments(&self, project_id: Uuid) -> Option<i64> {
+        sqlx::query_scalar!(
+            r#"SELECT (SELECT COUNT(*) FROM visible_comments c WHERE c.project_id = p.id) AS "count!"
+               FROM public_projects p WHERE p.id = $1"#,
             project_id
-        ).fetch_all(&self.pool).await.unwrap_or_default()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("count_comments error: {:?}", e); None })
+    }
+
+    /// create_report
+    ///
+    /// Records the report and, in the same transaction, counts distinct pending reporters and
+    /// flips `hidden_pending_review` once `threshold` is reached. The comment row is locked so
+    /// concurrent reports cannot both miss (or both trip) the threshold.
+    async fn create_report(
+        &self,
+        project_id: Option<Uuid>,
+        comment_id: i64,
+        reporter_id: Uuid,
+        reason: Option<String>,
+        threshold: i64,
+    ) -> Option<ReportOutcome> {
+        file_report(&self.pool, project_id, comment_id, reporter_id, reason, threshold)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("create_report error: {:?}", e); None })
+    }
+
+    /// restore_comment
+    ///
+    /// Clears the hidden flag and resolves every pending report, so the auto-hide count starts
+    /// again from zero.
+    async fn restore_comment(&self, comment_id: i64) -> bool {
+        let result: Result<bool, sqlx::Error> = async {
+            let mut tx = self.pool.begin().await?;
+            let restored = sqlx::query!(
+                "UPDATE project_comments SET hidden_pending_review = false WHERE id = $1",
+                comment_id
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected() > 0;
+            sqlx::query!(
+                "UPDATE comment_reports SET resolved_at = NOW() WHERE comment_id = $1 AND resolved_at IS NULL",
+                comment_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok(restored)
+        }
+        .await;
+        result.unwrap_or_else(|e| { tracing::error!("restore_comment error: {:?}", e); false })
+    }
+
+    /// get_reported_comments
+    ///
+    /// Groups the pending reports per comment. Comments on projects that have since gone
+    /// private are listed too: moderators see every project.
+    async fn get_reported_comments(&self) -> Vec<ReportedComment> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                c.id, c.user_id, c.project_id, c.comment, c.created_at, p.email AS "author_email?",
+                c.hidden_pending_review, c.edited_at, pr.title AS project_title,
+                COUNT(*) AS "report_count!",
+                MAX(r.created_at) AS "last_reported_at!",
+                ARRAY_REMOVE(ARRAY_AGG(r.reason ORDER BY r.created_at, r.id), NULL) AS "reasons!"
+            FROM comment_reports r
+            JOIN project_comments c ON c.id = r.comment_id
+            JOIN projects pr ON pr.id = c.project_id
+            LEFT JOIN profiles p ON p.id = c.user_id
+            WHERE r.resolved_at IS NULL AND c.deleted_at IS NULL
+            GROUP BY c.id, p.email, pr.title
+            ORDER BY COUNT(*) DESC, MAX(r.created_at) DESC, c.id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_reported_comments error: {:?}", e); vec![] });
+        rows.into_iter()
+            .map(|row| ReportedComment {
+                comment: Comment {
+                    id: row.id,
+                    user_id: Some(row.user_id),
+                    project_id: row.project_id,
+                    comment: row.comment,
+                    created_at: row.created_at,
+                    author_email: row.author_email,
+                    hidden_pending_review: row.hidden_pending_review,
+                    edited: row.edited_at.is_some(),
+                    edited_at: row.edited_at,
+                    is_deleted: false,
+                },
+                project_title: row.project_title,
+                report_count: row.report_count,
+                last_reported_at: row.last_reported_at,
+                reasons: row.reasons,
+            })
+            .collect()
+    }
+
+    async fn get_admin_comments(&self, filter: &AdminCommentFilter, limit: i64, offset: i64) -> Vec<AdminComment> {
+        let mut builder = QueryBuilder::new(
+            r#"
+            SELECT
+                c.id, c.user_id, c.project_id, c.comment, c.created_at, p.email AS author_email,
+                c.hidden_pending_review, c.edited_at, c.edited_at IS NOT NULL AS edited,
+                c.deleted_at IS NOT NULL AS is_deleted, pr.title AS project_title
+            FROM project_comments c
+            JOIN projects pr ON pr.id = c.project_id
+            LEFT JOIN profiles p ON p.id = c.user_id
+            WHERE true"#,
+        );
+        push_admin_comment_filters(&mut builder, filter);
+        builder.push(" ORDER BY c.created_at DESC, c.id DESC LIMIT ");
+        builder.push_bind(limit);
+        builder.push(" OFFSET ");
+        builder.push_bind(offset);
+        builder
+            .build_query_as::<AdminComment>()
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("get_admin_comments error: {:?}", e); vec![] })
     }
 
     /// delete_project_admin
@@ -378,17 +2493,31 @@ impl Repository for PostgresRepository {
     /// **Admin Override**: Deletes a project without checking ownership.
     async fn delete_project_admin(&self, id: Uuid) -> bool {
         match sqlx::query!("DELETE FROM projects WHERE id = $1", id).execute(&self.pool).await {
-            Ok(res) => res.rows_affected() > 0,
+            Ok(res) if res.rows_affected() > 0 => { self.invalidate_public_stats(); true }
+            Ok(_) => false,
             Err(e) => { tracing::error!("admin delete error: {:?}", e); false }
         }
     }
 
+    /// update_comment
+    ///
+    /// Rewrites a comment only if the provided `user_id` matches the comment author.
+    /// **Owner-Only** check, in the row lookup of `edit_comment` like `delete_comment`.
+    async fn update_comment(&self, id: i64, user_id: Uuid, req: UpdateCommentRequest) -> Option<CommentEditOutcome> {
+        edit_comment(&self.pool, id, user_id, req.text)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("update comment error: {:?}", e); None })
+    }
+
     /// delete_comment
     ///
-    /// Deletes a comment only if the provided `user_id` matches the comment author.
+    /// Soft-deletes a comment only if the provided `user_id` matches the comment author.
     /// **Owner-Only** check.
     async fn delete_comment(&self, id: i64, user_id: Uuid) -> bool {
-        match sqlx::query!("DELETE FROM project_comments WHERE id = $1 AND user_id = $2", id, user_id).execute(&self.pool).await {
+        match sqlx::query!(
+            "UPDATE project_comments SET deleted_at = NOW() WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL",
+            id, user_id
+        ).execute(&self.pool).await {
             Ok(res) => res.rows_affected() > 0,
             Err(e) => { tracing::error!("delete comment error: {:?}", e); false }
         }
@@ -396,14 +2525,29 @@ impl Repository for PostgresRepository {
 
     /// delete_comment_admin
     ///
-    /// **Admin Override**: Deletes a comment without checking ownership.
+    /// **Admin Override**: Soft-deletes a comment without checking ownership.
     async fn delete_comment_admin(&self, id: i64) -> bool {
-        match sqlx::query!("DELETE FROM project_comments WHERE id = $1", id).execute(&self.pool).await {
+        match sqlx::query!("UPDATE project_comments SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL", id).execute(&self.pool).await {
             Ok(res) => res.rows_affected() > 0,
             Err(e) => { tracing::error!("admin delete comment error: {:?}", e); false }
         }
     }
 
+    /// delete_project_comment
+    ///
+    /// Scopes the delete to the project in the URL, so a valid id from another project is
+    /// treated as not found instead of being deleted.
+    async fn delete_project_comment(&self, project_id: Uuid, id: i64, user_id: Option<Uuid>) -> bool {
+        match sqlx::query!(
+            r#"UPDATE project_comments SET deleted_at = NOW()
+               WHERE id = $1 AND project_id = $2 AND ($3::uuid IS NULL OR user_id = $3) AND deleted_at IS NULL"#,
+            id, project_id, user_id as Option<Uuid>
+        ).execute(&self.pool).await {
+            Ok(res) => res.rows_affected() > 0,
+            Err(e) => { tracing::error!("delete project comment error: {:?}", e); false }
+        }
+    }
+
     // --- NOTIFICATIONS ---
 
     /// get_notifications
@@ -418,6 +2562,7 @@ impl Repository for PostgresRepository {
             n.project_id, 
             p.title as project_title, 
             n.type, 
+            n.message, 
             n.is_read, 
             n.created_at
         FROM notifications n
@@ -437,6 +2582,97 @@ impl Repository for PostgresRepository {
         })
     }
 
+    /// notify_status_change
+    ///
+    /// Unlike likes and comments (notified whenever they are created), status change
+    /// notifications are explicit: the admin chooses whether to notify. The owner is resolved in the same
+    /// statement, and admins acting on their own projects do not notify themselves.
+    async fn notify_status_change(
+        &self,
+        project_id: Uuid,
+        actor_id: Uuid,
+        reason: Option<String>,
+    ) -> bool {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO notifications (user_id, actor_id, project_id, type, message)
+            SELECT p.user_id, $2, p.id, 'status_change', $3
+            FROM projects p
+            WHERE p.id = $1 AND p.user_id <> $2
+            "#,
+            project_id,
+            actor_id,
+            reason
+        )
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(r) => r.rows_affected() > 0,
+            Err(e) => {
+                tracing::error!("notify_status_change error: {:?}", e);
+                false
+            }
+        }
+    }
+
+    // --- PROJECT REVIEWS ---
+
+    /// record_project_review
+    ///
+    /// Inserting via SELECT from `projects` makes an unknown project a no-op (false) rather than
+    /// a foreign key error.
+    async fn record_project_review(
+        &self,
+        project_id: Uuid,
+        reviewer_id: Uuid,
+        status: ProjectStatus,
+        reason: Option<String>,
+    ) -> bool {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO project_reviews (project_id, reviewer_id, status, reason)
+            SELECT id, $2, $3, $4 FROM projects WHERE id = $1
+            "#,
+            project_id,
+            reviewer_id,
+            status.as_str(),
+            reason
+        )
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(r) => r.rows_affected() > 0,
+            Err(e) => {
+                tracing::error!("record_project_review error: {:?}", e);
+                false
+            }
+        }
+    }
+
+    /// get_latest_reviews
+    ///
+    /// `DISTINCT ON` keeps the newest row per project; the identity column breaks ties between
+    /// reviews recorded in the same instant.
+    async fn get_latest_reviews(&self, owner_id: Uuid) -> Vec<ProjectReview> {
+        sqlx::query_as!(
+            ProjectReview,
+            r#"
+            SELECT DISTINCT ON (r.project_id)
+                   r.project_id, r.status AS "status: ProjectStatus", r.reason, r.created_at AS reviewed_at
+            FROM project_reviews r
+            JOIN projects p ON p.id = r.project_id
+            WHERE p.user_id = $1
+            ORDER BY r.project_id, r.created_at DESC, r.id DESC
+            "#,
+            owner_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_latest_reviews error: {:?}", e); vec![] })
+    }
+
     /// mark_notification_read
     ///
     /// Sets `is_read = true` for a notification, enforced by an **ownership check** (`user_id`).
@@ -455,4 +2691,721 @@ impl Repository for PostgresRepository {
         }
     }
 }
+
+    /// create_notification
+    ///
+    /// A single insert through `insert_notification`, in its own transaction.
+    async fn create_notification(&self, notification: NewNotification) -> Option<Notification> {
+        let result = async {
+            let mut tx = self.pool.begin().await?;
+            let created = insert_notification(&mut tx, &notification).await?;
+            tx.commit().await?;
+            Ok::<_, sqlx::Error>(created)
+        }
+        .await;
+        result.unwrap_or_else(|e| { tracing::error!("create_notification error: {:?}", e); None })
+    }
+
+    // --- DISCUSSION LOCK ---
+
+    /// set_comments_locked
+    ///
+    /// Sets `comments_locked` only if the provided `user_id` matches the owner.
+    async fn set_comments_locked(&self, id: Uuid, user_id: Uuid, locked: bool) -> Option<Project> {
+        sqlx::query_as!(
+            Project,
+            r#"
+            UPDATE projects SET comments_locked = $3
+            WHERE id = $1 AND user_id = $2
+            RETURNING id, user_id, author, title, abstract as abstract_text, 
+                      cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, 
+                      year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!"
+            "#,
+            id, user_id, locked
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("set_comments_locked error: {:?}", e); None })
+    }
+
+    /// set_comments_locked_admin
+    ///
+    /// **Admin Override**: Sets `comments_locked` without checking ownership.
+    async fn set_comments_locked_admin(&self, id: Uuid, locked: bool) -> Option<Project> {
+        sqlx::query_as!(
+            Project,
+            r#"
+            UPDATE projects SET comments_locked = $2
+            WHERE id = $1
+            RETURNING id, user_id, author, title, abstract as abstract_text, 
+                      cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, 
+                      year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!"
+            "#,
+            id, locked
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("set_comments_locked_admin error: {:?}", e); None })
+    }
+
+    /// lock_comments_for_year
+    ///
+    /// Locks an entire cohort in a single statement. Projects that are already locked are
+    /// not counted, so the returned number reflects what actually changed.
+    async fn lock_comments_for_year(&self, year: i32) -> i64 {
+        match sqlx::query!("UPDATE projects SET comments_locked = true WHERE year = $1 AND comments_locked = false", year).execute(&self.pool).await {
+            Ok(r) => r.rows_affected() as i64,
+            Err(e) => {
+                tracing::error!("lock_comments_for_year error: {:?}", e);
+                0
+            }
+        }
+    }
+
+    /// are_comments_locked
+    ///
+    /// Returns `false` for unknown projects so the caller's own not-found handling applies.
+    async fn are_comments_locked(&self, project_id: Uuid) -> bool {
+        sqlx::query_scalar!("SELECT comments_locked FROM projects WHERE id = $1", project_id)
+            .fetch_optional(&self.pool)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("are_comments_locked error: {:?}", e); None })
+            .unwrap_or(false)
+    }
+
+    // --- SUBMISSION ---
+
+    /// mark_project_submitted
+    ///
+    /// Stamps `submitted_at` and moves the project to `pending`, placing it in the admin
+    /// review queue. Only a draft moves: the handler's checks run before this write, and the
+    /// guard keeps an approved or rejected project from being pushed back to `pending` (and
+    /// unpublished by the status trigger) if it changed in between.
+    async fn mark_project_submitted(&self, id: Uuid) -> Option<Project> {
+        sqlx::query_as!(
+            Project,
+            r#"
+            UPDATE projects SET submitted_at = NOW(), status = 'pending', updated_at = NOW()
+            WHERE id = $1 AND status = 'draft'
+            RETURNING id, user_id, author, title, abstract as abstract_text, 
+                      cover_image, video, report, status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, 
+                      year, created_at, updated_at, (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = projects.id AND c.deleted_at IS NULL) AS "comment_count!", ARRAY(SELECT t.tag FROM project_tags t WHERE t.project_id = projects.id ORDER BY t.tag) AS "tags!"
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("mark_project_submitted error: {:?}", e); None })
+    }
+
+    async fn resubmit_project(&self, id: Uuid, owner_id: Uuid, max_attempts: i32) -> Option<ResubmitOutcome> {
+        resubmit(&self.pool, id, owner_id, max_attempts)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("resubmit_project error: {:?}", e); None })
+    }
+
+    async fn reassign_project_owner(&self, project_id: Uuid, new_owner_id: Uuid, actor_id: Uuid) -> Option<OwnerTransferOutcome> {
+        reassign_owner(&self.pool, project_id, new_owner_id, actor_id)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("reassign_project_owner error: {:?}", e); None })
+    }
+
+    // --- STAFF REPORTS ---
+
+    /// get_supervisor_report
+    ///
+    /// Aggregates per supervisor in a single query. Likes and comments are counted per project
+    /// first (correlated subqueries) so joining both tables cannot multiply the totals.
+    /// NULL or blank supervisors fall into an "Unassigned" bucket, which is listed last.
+    async fn get_supervisor_report(&self, year: Option<i32>) -> Vec<SupervisorStats> {
+        sqlx::query_as!(
+            SupervisorStats,
+            r#"
+            WITH per_project AS (
+                SELECT
+                    COALESCE(NULLIF(TRIM(p.supervisor), ''), 'Unassigned') AS supervisor,
+                    p.is_public,
+                    (SELECT COUNT(*) FROM project_likes l WHERE l.project_id = p.id) AS likes,
+                    (SELECT COUNT(*) FROM project_comments c WHERE c.project_id = p.id AND c.deleted_at IS NULL) AS comments
+                FROM projects p
+                WHERE ($1::int IS NULL OR p.year = $1)
+            )
+            SELECT
+                supervisor AS "supervisor!",
+                COUNT(*) AS "project_count!",
+                COUNT(*) FILTER (WHERE is_public) AS "public_count!",
+                COALESCE(AVG(likes), 0)::float8 AS "avg_likes!",
+                COALESCE(SUM(comments), 0)::bigint AS "total_comments!"
+            FROM per_project
+            GROUP BY supervisor
+            ORDER BY (supervisor = 'Unassigned'), supervisor
+            "#,
+            year
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_supervisor_report error: {:?}", e); vec![] })
+    }
+
+    // --- COMMENT SUBSCRIPTIONS ---
+
+    /// set_subscription
+    ///
+    /// Upserts the user's preference. Inserting via SELECT from `projects` makes an unknown
+    /// project a no-op (false) rather than a foreign key error.
+    async fn set_subscription(&self, project_id: Uuid, user_id: Uuid, state: SubscriptionState) -> bool {
+        sqlx::query!(
+            r#"
+            INSERT INTO project_subscriptions (project_id, user_id, state)
+            SELECT p.id, $2, $3 FROM projects p WHERE p.id = $1
+            ON CONFLICT (project_id, user_id) DO UPDATE SET state = EXCLUDED.state, updated_at = NOW()
+            "#,
+            project_id, user_id, state.as_str()
+        )
+        .execute(&self.pool)
+        .await
+        .map(|r| r.rows_affected() > 0)
+        .unwrap_or_else(|e| { tracing::error!("set_subscription error: {:?}", e); false })
+    }
+
+    /// get_subscription
+    ///
+    /// Mirrors the recipient rules of the `comment_recipients` function, so the state shown to
+    /// the user always matches whether they will actually be notified.
+    async fn get_subscription(&self, project_id: Uuid, user_id: Uuid) -> Option<SubscriptionState> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(
+                (SELECT s.state FROM project_subscriptions s WHERE s.project_id = $1 AND s.user_id = $2),
+                CASE WHEN EXISTS (SELECT 1 FROM projects p WHERE p.id = $1 AND p.user_id = $2)
+                       OR EXISTS (SELECT 1 FROM project_comments c WHERE c.project_id = $1 AND c.user_id = $2)
+                     THEN 'subscribed' END
+            ) AS state
+            "#,
+            project_id, user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_subscription error: {:?}", e); None })
+        .and_then(|state| SubscriptionState::from_db(&state))
+    }
+
+    // --- UPLOAD TRACKING ---
+
+    /// record_upload
+    ///
+    /// Stores one issued presigned URL. Keys are unique UUID paths, so a conflict means a bug.
+    async fn record_upload(
+        &self,
+        user_id: Uuid,
+        object_key: &str,
+        purpose: UploadPurpose,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        sqlx::query!(
+            "INSERT INTO uploads (user_id, object_key, purpose, expires_at) VALUES ($1, $2, $3, $4)",
+            user_id, object_key, purpose.as_str(), expires_at
+        )
+        .execute(&self.pool)
+        .await
+        .map(|r| r.rows_affected() > 0)
+        .unwrap_or_else(|e| { tracing::error!("record_upload error: {:?}", e); false })
+    }
+
+    /// get_upload_counts
+    ///
+    /// An upload is "confirmed" once a project references its key (cover, video or report).
+    /// Only the last hour is scanned: an outstanding upload expires long before that.
+    async fn get_upload_counts(&self, user_id: Uuid, purpose: UploadPurpose) -> UploadCounts {
+        sqlx::query_as!(
+            UploadCounts,
+            r#"
+            SELECT
+                COUNT(*) AS "last_hour!",
+                COUNT(*) FILTER (
+                    WHERE u.purpose = $2
+                      AND u.expires_at > NOW()
+                      AND NOT EXISTS (
+                          SELECT 1 FROM projects p
+                          WHERE p.cover_image = u.object_key OR p.video = u.object_key OR p.report = u.object_key
+                      )
+                ) AS "outstanding!"
+            FROM uploads u
+            WHERE u.user_id = $1 AND u.created_at > NOW() - INTERVAL '1 hour'
+            "#,
+            user_id, purpose.as_str()
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_upload_counts error: {:?}", e); UploadCounts::default() })
+    }
+
+    /// is_own_upload
+    ///
+    /// Keys are unique, so this is one index lookup. A lookup failure counts as "not theirs".
+    async fn is_own_upload(&self, user_id: Uuid, object_key: &str, purpose: UploadPurpose) -> bool {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                SELECT 1 FROM uploads WHERE user_id = $1 AND object_key = $2 AND purpose = $3
+            ) AS "owned!""#,
+            user_id, object_key, purpose.as_str()
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("is_own_upload error: {:?}", e); false })
+    }
+
+    // --- PROGRAMMES ---
+
+    /// get_programmes
+    ///
+    /// The full programme lookup, ordered by code.
+    async fn get_programmes(&self) -> Vec<Programme> {
+        sqlx::query_as!(Programme, "SELECT code, name FROM programmes ORDER BY code")
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("get_programmes error: {:?}", e); vec![] })
+    }
+
+    /// create_programme
+    ///
+    /// Inserts a programme. `ON CONFLICT DO NOTHING` turns a duplicate code into `None`.
+    async fn create_programme(&self, code: &str, name: &str) -> Option<Programme> {
+        sqlx::query_as!(
+            Programme,
+            "INSERT INTO programmes (code, name) VALUES ($1, $2) ON CONFLICT (code) DO NOTHING RETURNING code, name",
+            code,
+            name
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("create_programme error: {:?}", e); None })
+    }
+
+    /// unknown_programme_codes
+    ///
+    /// Returns the given codes that are not in the lookup, in input order.
+    async fn unknown_programme_codes(&self, codes: &[String]) -> Vec<String> {
+        sqlx::query_scalar!(
+            r#"SELECT c.code AS "code!" FROM UNNEST($1::text[]) WITH ORDINALITY AS c(code, n)
+               WHERE NOT EXISTS (SELECT 1 FROM programmes p WHERE p.code = c.code)
+               ORDER BY c.n"#,
+            codes
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("unknown_programme_codes error: {:?}", e);
+            // Fail closed: if the lookup is unreachable nothing is considered valid.
+            codes.to_vec()
+        })
+    }
+
+    /// get_project_programmes
+    ///
+    /// Programme codes of one project, sorted.
+    async fn get_project_programmes(&self, project_id: Uuid) -> Vec<String> {
+        sqlx::query_scalar!(
+            "SELECT programme_code FROM project_programmes WHERE project_id = $1 ORDER BY programme_code",
+            project_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_project_programmes error: {:?}", e); vec![] })
+    }
+
+    /// get_tag_counts
+    ///
+    /// Usage counts over public projects only, so draft tags never surface in the filter
+    /// dropdown. Ties are listed alphabetically.
+    async fn get_tag_counts(&self) -> Vec<TagCount> {
+        sqlx::query_as!(
+            TagCount,
+            r#"SELECT t.tag, COUNT(*) AS "count!"
+               FROM project_tags t JOIN public_projects p ON p.id = t.project_id
+               GROUP BY t.tag
+               ORDER BY COUNT(*) DESC, t.tag"#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_tag_counts error: {:?}", e); vec![] })
+    }
+
+    /// get_technology_counts
+    ///
+    /// Public project counts per technology for the listing's technology filter.
+    async fn get_technology_counts(&self) -> Vec<TechnologyCount> {
+        sqlx::query_as!(
+            TechnologyCount,
+            r#"SELECT t.technology AS "technology!", COUNT(*) AS "count!"
+               FROM public_projects p CROSS JOIN LATERAL unnest(p.technologies) AS t(technology)
+               GROUP BY t.technology
+               ORDER BY COUNT(*) DESC, t.technology"#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_technology_counts error: {:?}", e); vec![] })
+    }
+
+    /// get_project_years
+    ///
+    /// Public project counts per year for the listing's year filter. Years with only drafts or
+    /// hidden projects are left out.
+    async fn get_project_years(&self) -> Vec<YearCount> {
+        sqlx::query_as!(
+            YearCount,
+            r#"SELECT year AS "year!", COUNT(*) AS "count!"
+               FROM public_projects
+               GROUP BY year
+               ORDER BY year DESC"#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_project_years error: {:?}", e); vec![] })
+    }
+
+    /// get_department_counts
+    ///
+    /// Public project counts per department for the listing's department filter. Projects
+    /// without a department are left out.
+    async fn get_department_counts(&self) -> Vec<DepartmentCount> {
+        sqlx::query_as!(
+            DepartmentCount,
+            r#"SELECT department AS "department!", COUNT(*) AS "count!"
+               FROM public_projects
+               WHERE department IS NOT NULL
+               GROUP BY department
+               ORDER BY COUNT(*) DESC, department"#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_department_counts error: {:?}", e); vec![] })
+    }
+
+    /// add_feedback
+    ///
+    /// The visibility check, the insert and the owner's notification are one statement, so
+    /// feedback can never land on a project that was hidden in between. The notification names
+    /// the owner as its actor: the sender is anonymous.
+    async fn add_feedback(&self, project_id: Uuid, message: &str, contact_email: Option<&str>) -> Option<ProjectFeedback> {
+        sqlx::query_as!(
+            ProjectFeedback,
+            r#"
+            WITH added AS (
+                INSERT INTO project_feedback (project_id, message, contact_email)
+                SELECT id, $2, $3 FROM projects WHERE id = $1 AND is_public = true
+                RETURNING id, project_id, message, contact_email, created_at
+            ), notified AS (
+                INSERT INTO notifications (user_id, actor_id, project_id, type)
+                SELECT p.user_id, p.user_id, p.id, 'feedback'
+                FROM added a JOIN projects p ON p.id = a.project_id
+            )
+            SELECT id AS "id!", project_id AS "project_id!", message AS "message!", contact_email,
+                   created_at AS "created_at!"
+            FROM added
+            "#,
+            project_id,
+            message,
+            contact_email
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("add_feedback error: {:?}", e); None })
+    }
+
+    async fn get_feedback(&self, project_id: Option<Uuid>) -> Vec<ProjectFeedback> {
+        sqlx::query_as!(
+            ProjectFeedback,
+            r#"SELECT id, project_id, message, contact_email, created_at FROM project_feedback
+               WHERE $1::uuid IS NULL OR project_id = $1
+               ORDER BY created_at DESC, id DESC"#,
+            project_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_feedback error: {:?}", e); vec![] })
+    }
+
+    /// get_settings
+    ///
+    /// The whole `settings` table; small enough to load on every refresh.
+    async fn get_settings(&self) -> Result<Vec<Setting>, RepositoryError> {
+        sqlx::query_as!(
+            Setting,
+            r#"SELECT key, value, updated_at AS "updated_at?", updated_by FROM settings ORDER BY key"#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)
+    }
+
+    /// put_setting
+    ///
+    /// Upserts on the key, stamping the time and the admin who made the change.
+    async fn put_setting(&self, key: &str, value: serde_json::Value, updated_by: Uuid) -> Option<Setting> {
+        sqlx::query_as!(
+            Setting,
+            r#"INSERT INTO settings (key, value, updated_at, updated_by) VALUES ($1, $2, NOW(), $3)
+               ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at, updated_by = EXCLUDED.updated_by
+               RETURNING key, value, updated_at AS "updated_at?", updated_by"#,
+            key,
+            value,
+            updated_by
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| tracing::error!("put_setting error: {:?}", e))
+        .ok()
+    }
+
+    // --- STALE DRAFTS ---
+
+    /// get_stale_drafts
+    ///
+    /// Uses the partial `idx_projects_stale_drafts` index; each sweep has its own
+    /// "already handled" column.
+    async fn get_stale_drafts(&self, sweep: DraftSweep, untouched_before: chrono::DateTime<chrono::Utc>) -> Vec<StaleDraft> {
+        let result = match sweep {
+            DraftSweep::Reminder => sqlx::query_as!(
+                StaleDraft,
+                r#"SELECT id AS project_id, user_id AS owner_id, title, updated_at FROM projects
+                   WHERE submitted_at IS NULL AND is_public = false AND updated_at < $1
+                     AND draft_reminded_at IS NULL
+                   ORDER BY updated_at, id"#,
+                untouched_before
+            )
+            .fetch_all(&self.pool)
+            .await,
+            DraftSweep::Cleanup => sqlx::query_as!(
+                StaleDraft,
+                r#"SELECT id AS project_id, user_id AS owner_id, title, updated_at FROM projects
+                   WHERE submitted_at IS NULL AND is_public = false AND updated_at < $1
+                     AND cleanup_flagged_at IS NULL
+                   ORDER BY updated_at, id"#,
+                untouched_before
+            )
+            .fetch_all(&self.pool)
+            .await,
+        };
+        result.unwrap_or_else(|e| { tracing::error!("get_stale_drafts error: {:?}", e); vec![] })
+    }
+
+    /// mark_stale_draft
+    ///
+    /// The `IS NULL` guard makes marking idempotent even if two passes overlap: only the one
+    /// that sets the timestamp inserts the reminder (same statement).
+    async fn mark_stale_draft(&self, sweep: DraftSweep, project_id: Uuid, at: chrono::DateTime<chrono::Utc>) -> bool {
+        let result = match sweep {
+            DraftSweep::Reminder => sqlx::query!(
+                r#"
+                WITH marked AS (
+                    UPDATE projects SET draft_reminded_at = $2
+                    WHERE id = $1 AND submitted_at IS NULL AND is_public = false AND draft_reminded_at IS NULL
+                    RETURNING id, user_id
+                )
+                INSERT INTO notifications (user_id, actor_id, project_id, type)
+                SELECT user_id, user_id, id, 'draft_reminder' FROM marked
+                "#,
+                project_id,
+                at
+            )
+            .execute(&self.pool)
+            .await,
+            DraftSweep::Cleanup => sqlx::query!(
+                r#"UPDATE projects SET cleanup_flagged_at = $2
+                   WHERE id = $1 AND submitted_at IS NULL AND is_public = false AND cleanup_flagged_at IS NULL"#,
+                project_id,
+                at
+            )
+            .execute(&self.pool)
+            .await,
+        };
+        match result {
+            Ok(r) => r.rows_affected() > 0,
+            Err(e) => {
+                tracing::error!("mark_stale_draft error: {:?}", e);
+                false
+            }
+        }
+    }
+
+    // --- ACADEMIC YEARS ---
+
+    async fn get_projects_outside_years(&self, min: i32, max: i32) -> Vec<InvalidYearProject> {
+        sqlx::query_as!(
+            InvalidYearProject,
+            r#"SELECT id AS project_id, user_id AS owner_id, title, year FROM projects
+               WHERE year < $1 OR year > $2
+               ORDER BY year, title, id"#,
+            min,
+            max
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_projects_outside_years error: {:?}", e); vec![] })
+    }
+
+    /// remap_project_year
+    ///
+    /// One transaction: the rows are locked, then each gets a slug picked for its new year, so
+    /// a failure part way leaves every project where it was.
+    async fn remap_project_year(&self, from: i32, to: i32) -> i64 {
+        let result: sqlx::Result<i64> = async {
+            let mut tx = self.pool.begin().await?;
+            let rows = sqlx::query!("SELECT id, title FROM projects WHERE year = $1 ORDER BY id FOR UPDATE", from)
+                .fetch_all(&mut *tx)
+                .await?;
+            for row in &rows {
+                let slug = pick_slug(&mut tx, row.id, &row.title, to).await?;
+                sqlx::query!(
+                    "UPDATE projects SET year = $2, slug = $3, updated_at = NOW() WHERE id = $1",
+                    row.id,
+                    to,
+                    slug
+                )
+                .execute(&mut *tx)
+                .await?;
+                record_slug(&mut tx, row.id, &slug).await?;
+            }
+            tx.commit().await?;
+            Ok(rows.len() as i64)
+        }
+        .await;
+        result.unwrap_or_else(|e| { tracing::error!("remap_project_year error: {:?}", e); 0 })
+    }
+    // --- AUDIT LOG ---
+
+    async fn record_audit(&self, entry: &NewAuditEntry) -> Result<(), RepositoryError> {
+        sqlx::query!(
+            "INSERT INTO audit_log (actor_id, action, target_type, target_id, details) VALUES ($1, $2, $3, $4, $5)",
+            entry.actor_id,
+            entry.action,
+            entry.target_type,
+            entry.target_id,
+            entry.details
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn queue_audit(&self, entry: &NewAuditEntry) -> Result<i64, RepositoryError> {
+        let payload = serde_json::to_value(entry).map_err(|e| RepositoryError(e.to_string()))?;
+        let id = sqlx::query_scalar!("INSERT INTO outbox (kind, payload, total) VALUES ('audit_entry', $1, 1) RETURNING id", payload)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(id)
+    }
+
+    /// get_audit_log
+    ///
+    /// With an actor the page is a range scan of `idx_audit_log_actor_created`; without one,
+    /// of `idx_audit_log_created`.
+    async fn get_audit_log(&self, filter: &AuditFilter, page: PageRequest) -> (Vec<AuditEntry>, i64) {
+        let mut builder = QueryBuilder::new(
+            "SELECT id, actor_id, action, target_type, target_id, details, created_at FROM audit_log WHERE true",
+        );
+        push_audit_filters(&mut builder, filter);
+        builder.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+        builder.push_bind(page.per_page);
+        builder.push(" OFFSET ");
+        builder.push_bind(page.offset());
+        let items = builder
+            .build_query_as::<AuditEntry>()
+            .fetch_all(&self.pool)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("get_audit_log error: {:?}", e); vec![] });
+
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM audit_log WHERE true");
+        push_audit_filters(&mut count, filter);
+        let total = count
+            .build_query_scalar::<i64>()
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("get_audit_log count error: {:?}", e); 0 });
+        (items, total)
+    }
+
+    /// stream_audit_log
+    ///
+    /// The same query as `get_audit_log` without the page, streamed through the bounded channel
+    /// used by the project exports.
+    fn stream_audit_log(&self, filter: AuditFilter) -> BoxStream<'static, Result<AuditEntry, RepositoryError>> {
+        let pool = self.pool.clone();
+        let (tx, rx) = tokio::sync::mpsc::channel(PROJECT_STREAM_BUFFER);
+        tokio::spawn(async move {
+            let mut builder = QueryBuilder::new(
+                "SELECT id, actor_id, action, target_type, target_id, details, created_at FROM audit_log WHERE true",
+            );
+            push_audit_filters(&mut builder, &filter);
+            builder.push(" ORDER BY created_at DESC, id DESC");
+            let mut rows = builder.build_query_as::<AuditEntry>().fetch(&pool);
+            while let Some(row) = rows.next().await {
+                let failed = row.is_err();
+                if tx.send(row.map_err(RepositoryError::from)).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|row| (row, rx)) }).boxed()
+    }
+
+    async fn purge_audit_log(&self, before: chrono::DateTime<chrono::Utc>) -> Result<u64, RepositoryError> {
+        let result = sqlx::query!("DELETE FROM audit_log WHERE created_at < $1", before)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    // --- OUTBOX ---
+
+    async fn run_fanout_batch(&self, batch_size: i64) -> Option<OutboxJob> {
+        fanout_batch(&self.pool, batch_size)
+            .await
+            .unwrap_or_else(|e| { tracing::error!("run_fanout_batch error: {:?}", e); None })
+    }
+
+    async fn get_outbox_job(&self, id: i64) -> Option<OutboxJob> {
+        sqlx::query_as!(
+            OutboxJob,
+            "SELECT id, kind, status, payload, total, processed, created_at, updated_at, completed_at FROM outbox WHERE id = $1",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .unwrap_or_else(|e| { tracing::error!("get_outbox_job error: {:?}", e); None })
+    }
+
+    /// flush_audit_outbox
+    ///
+    /// One statement, so a job is never both moved and left pending. `SKIP LOCKED` lets two
+    /// dispatchers flush side by side without moving an entry twice.
+    async fn flush_audit_outbox(&self) -> Result<u64, RepositoryError> {
+        let result = sqlx::query!(
+            r#"
+            WITH jobs AS (
+                SELECT id FROM outbox WHERE kind = 'audit_entry' AND status = 'pending'
+                ORDER BY id FOR UPDATE SKIP LOCKED
+            ), done AS (
+                UPDATE outbox o SET status = 'done', processed = 1, updated_at = NOW(), completed_at = NOW()
+                FROM jobs WHERE o.id = jobs.id
+                RETURNING o.id, o.payload, o.created_at
+            )
+            INSERT INTO audit_log (actor_id, action, target_type, target_id, details, created_at)
+            SELECT (payload->>'actor_id')::uuid, payload->>'action', payload->>'target_type',
+                   payload->>'target_id', COALESCE(payload->'details', '{}'::jsonb), created_at
+            FROM done ORDER BY id
+            "#
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    // --- HEALTH ---
+
+    async fn ping(&self) -> Result<(), RepositoryError> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,657 @@
+use super::{AdminCommentFilter, AuditFilter, CommentCursor, DraftSweep, HiddenComments, ProjectListFilter, ProjectViewer, Repository, RepositoryError};
+use crate::models::{
+    ActivityItem, AdminDashboardStats, AuditEntry, NewAuditEntry, NewNotification, Notification, BulkStatusOutcome, Comment, CommentEditOutcome, CommentOrder, CreateProjectRequest, DepartmentCount, InvalidYearProject, ProjectOwner, OwnerTransferOutcome, ProjectStats, PendingReview, TechnologyCount, Like, LikeOutcome, MyProjectSummary, OutboxJob, PostedComment, Programme, Project,
+    ProjectExportRow, ProjectFeedback, ProjectLiker, ProjectReview, ProjectStatus, ProjectUpdateResult, PublicEvent, PublicStats, ReportOutcome, ReportedComment, AdminComment, ResubmitOutcome, StaleDraft, SubscriptionState, SupervisorStats, Setting, TagCount, UpdateCommentRequest, UpdateProjectRequest,
+    UploadCounts, UploadPurpose, RecentComments, User, YearCount,
+};
+use crate::pagination::PageRequest;
+use async_trait::async_trait;
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+use futures::stream::BoxStream;
+use std::{cell::RefCell, collections::BTreeMap, fmt, sync::Arc};
+use uuid::Uuid;
+
+/// Response header carrying how many repository calls served the request (Local env only).
+pub const DB_CALLS_HEADER: &str = "x-db-calls";
+
+tokio::task_local! {
+    // Calls made by the current request; only set inside `track`.
+    static DB_CALLS: RefCell<DbCalls>;
+}
+
+/// DbCalls
+///
+/// Repository calls, counted per trait method name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DbCalls(BTreeMap<&'static str, u32>);
+
+impl DbCalls {
+    pub fn total(&self) -> u32 {
+        self.0.values().sum()
+    }
+
+    /// Calls to one method, e.g. `get("get_user")`.
+    pub fn get(&self, method: &str) -> u32 {
+        self.0.get(method).copied().unwrap_or(0)
+    }
+
+    fn record(&mut self, method: &'static str) {
+        *self.0.entry(method).or_default() += 1;
+    }
+}
+
+/// `get_projects=1 get_user=2`, by method name.
+impl fmt::Display for DbCalls {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs: Vec<String> = self.0.iter().map(|(method, n)| format!("{}={}", method, n)).collect();
+        f.write_str(&pairs.join(" "))
+    }
+}
+
+/// track
+///
+/// Runs `future` with a fresh per-task counter and returns its output together with the
+/// repository calls it made through a `CountingRepository`. Work moved to other tasks
+/// (e.g. `tokio::spawn`) is not counted.
+pub async fn track<F: Future>(future: F) -> (F::Output, DbCalls) {
+    DB_CALLS
+        .scope(RefCell::new(DbCalls::default()), async {
+            let output = future.await;
+            (output, DB_CALLS.with(|calls| calls.borrow().clone()))
+        })
+        .await
+}
+
+/// db_calls_header
+///
+/// Local-only middleware: counts the repository calls behind each request, returns the total
+/// in `X-DB-Calls`, and logs the per-method breakdown at debug level.
+pub async fn db_calls_header(request: Request, next: Next) -> Response {
+    let (mut response, calls) = track(next.run(request)).await;
+    tracing::debug!(db_calls = calls.total(), breakdown = %calls, "Repository calls for request");
+    response
+        .headers_mut()
+        .insert(DB_CALLS_HEADER, HeaderValue::from(calls.total()));
+    response
+}
+
+/// CountingRepository
+///
+/// A `Repository` decorator that counts every call by method name into the current request's
+/// `track` scope, then delegates to `inner`. `main` wraps the Postgres repository in it in the
+/// Local env. With the `test-util` feature it also keeps running totals across all tasks, so
+/// tests can assert how many calls a handler made.
+pub struct CountingRepository<R: ?Sized = dyn Repository> {
+    inner: Arc<R>,
+    #[cfg(feature = "test-util")]
+    totals: std::sync::Mutex<DbCalls>,
+}
+
+impl<R: Repository + ?Sized> CountingRepository<R> {
+    pub fn new(inner: Arc<R>) -> Self {
+        Self {
+            inner,
+            #[cfg(feature = "test-util")]
+            totals: Default::default(),
+        }
+    }
+
+    /// Every call made through this repository so far.
+    #[cfg(feature = "test-util")]
+    pub fn totals(&self) -> DbCalls {
+        self.totals.lock().unwrap().clone()
+    }
+
+    fn record(&self, method: &'static str) {
+        // Outside a `track` scope (startup, background tasks) there is nothing to count into.
+        let _ = DB_CALLS.try_with(|calls| calls.borrow_mut().record(method));
+        #[cfg(feature = "test-util")]
+        self.totals.lock().unwrap().record(method);
+    }
+}
+
+#[async_trait]
+impl<R: Repository + ?Sized> Repository for CountingRepository<R> {
+    async fn get_projects(
+        &self,
+        filter: ProjectListFilter,
+        page: PageRequest,
+    ) -> (Vec<Project>, i64) {
+        self.record("get_projects");
+        self.inner.get_projects(filter, page).await
+    }
+
+    async fn get_all_projects(&self) -> Vec<Project> {
+        self.record("get_all_projects");
+        self.inner.get_all_projects().await
+    }
+
+    async fn get_pending_reviews(&self, limit: i64) -> Vec<PendingReview> {
+        self.record("get_pending_reviews");
+        self.inner.get_pending_reviews(limit).await
+    }
+
+    fn stream_all_projects(&self) -> BoxStream<'static, Result<Project, RepositoryError>> {
+        self.record("stream_all_projects");
+        self.inner.stream_all_projects()
+    }
+
+    fn stream_project_export(&self) -> BoxStream<'static, Result<ProjectExportRow, RepositoryError>> {
+        self.record("stream_project_export");
+        self.inner.stream_project_export()
+    }
+
+    async fn get_top_projects(&self, limit: i64, min_likes: i64, year: Option<i32>) -> Vec<Project> {
+        self.record("get_top_projects");
+        self.inner.get_top_projects(limit, min_likes, year).await
+    }
+
+    async fn get_year_archive(&self, year: i32) -> Vec<Project> {
+        self.record("get_year_archive");
+        self.inner.get_year_archive(year).await
+    }
+
+    async fn get_random_public_project(&self, seed: Option<i64>) -> Option<Project> {
+        self.record("get_random_public_project");
+        self.inner.get_random_public_project(seed).await
+    }
+
+    async fn get_project(&self, id: Uuid) -> Option<Project> {
+        self.record("get_project");
+        self.inner.get_project(id).await
+    }
+
+    async fn get_project_authorized(&self, id: Uuid, user_id: Uuid) -> Option<Project> {
+        self.record("get_project_authorized");
+        self.inner.get_project_authorized(id, user_id).await
+    }
+
+    async fn get_public_project(&self, id: Uuid) -> Option<Project> {
+        self.record("get_public_project");
+        self.inner.get_public_project(id).await
+    }
+
+    async fn get_project_detail(&self, id: Uuid, viewer: ProjectViewer) -> Option<(Project, Option<ProjectOwner>)> {
+        self.record("get_project_detail");
+        self.inner.get_project_detail(id, viewer).await
+    }
+
+    async fn resolve_project_slug(&self, slug: &str) -> Option<Uuid> {
+        self.record("resolve_project_slug");
+        self.inner.resolve_project_slug(slug).await
+    }
+
+    async fn find_project_by_title_year(&self, title: &str, year: i32) -> Option<Uuid> {
+        self.record("find_project_by_title_year");
+        self.inner.find_project_by_title_year(title, year).await
+    }
+
+    async fn count_user_projects_in_year(&self, user_id: Uuid, year: i32) -> i64 {
+        self.record("count_user_projects_in_year");
+        self.inner.count_user_projects_in_year(user_id, year).await
+    }
+
+    async fn get_changed_project_ids(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        ids: Option<Vec<Uuid>>,
+    ) -> Vec<Uuid> {
+        self.record("get_changed_project_ids");
+        self.inner.get_changed_project_ids(since, ids).await
+    }
+
+    async fn get_public_events(&self, since: chrono::DateTime<chrono::Utc>, limit: i64) -> Vec<PublicEvent> {
+        self.record("get_public_events");
+        self.inner.get_public_events(since, limit).await
+    }
+
+    async fn increment_view(&self, id: Uuid) -> Option<i64> {
+        self.record("increment_view");
+        self.inner.increment_view(id).await
+    }
+
+    async fn get_like_count(&self, id: Uuid) -> Option<i64> {
+        self.record("get_like_count");
+        self.inner.get_like_count(id).await
+    }
+
+    async fn create_project(&self, req: CreateProjectRequest, user_id: Uuid) -> Project {
+        self.record("create_project");
+        self.inner.create_project(req, user_id).await
+    }
+
+    async fn like_project(&self, like: Like) -> Option<LikeOutcome> {
+        self.record("like_project");
+        self.inner.like_project(like).await
+    }
+
+    async fn unlike_project(&self, like: Like) -> bool {
+        self.record("unlike_project");
+        self.inner.unlike_project(like).await
+    }
+
+    async fn get_liked_project_ids(&self, user_id: Uuid, project_ids: &[Uuid]) -> Vec<Uuid> {
+        self.record("get_liked_project_ids");
+        self.inner.get_liked_project_ids(user_id, project_ids).await
+    }
+
+    async fn get_project_likers(&self, project_id: Uuid, page: PageRequest) -> (Vec<ProjectLiker>, i64) {
+        self.record("get_project_likers");
+        self.inner.get_project_likers(project_id, page).await
+    }
+
+    async fn set_project_status(&self, id: Uuid, status: ProjectStatus) -> Option<Project> {
+        self.record("set_project_status");
+        self.inner.set_project_status(id, status).await
+    }
+
+    async fn set_projects_status_bulk(&self, ids: &[Uuid], is_public: bool) -> BulkStatusOutcome {
+        self.record("set_projects_status_bulk");
+        self.inner.set_projects_status_bulk(ids, is_public).await
+    }
+
+    async fn get_user(&self, id: Uuid) -> Option<User> {
+        self.record("get_user");
+        self.inner.get_user(id).await
+    }
+
+    async fn create_user(&self, user: User) -> User {
+        self.record("create_user");
+        self.inner.create_user(user).await
+    }
+
+    async fn get_users_by_email_prefix(&self, prefixes: &[String]) -> Vec<User> {
+        self.record("get_users_by_email_prefix");
+        self.inner.get_users_by_email_prefix(prefixes).await
+    }
+
+    async fn get_stats(&self) -> AdminDashboardStats {
+        self.record("get_stats");
+        self.inner.get_stats().await
+    }
+
+    async fn get_public_stats(&self) -> PublicStats {
+        self.record("get_public_stats");
+        self.inner.get_public_stats().await
+    }
+
+    async fn get_my_project_summaries(&self, user_id: Uuid) -> Vec<MyProjectSummary> {
+        self.record("get_my_project_summaries");
+        self.inner.get_my_project_summaries(user_id).await
+    }
+
+    async fn delete_project(&self, id: Uuid, user_id: Uuid) -> bool {
+        self.record("delete_project");
+        self.inner.delete_project(id, user_id).await
+    }
+
+    async fn update_project(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: UpdateProjectRequest,
+    ) -> Option<ProjectUpdateResult> {
+        self.record("update_project");
+        self.inner.update_project(id, user_id, req).await
+    }
+
+    async fn add_comment(&self, project_id: Uuid, user_id: Uuid, text: String, fanout_threshold: i64) -> Result<Option<PostedComment>, RepositoryError> {
+        self.record("add_comment");
+        self.inner.add_comment(project_id, user_id, text, fanout_threshold).await
+    }
+
+    async fn get_comments(
+        &self,
+        project_id: Uuid,
+        order: CommentOrder,
+        hidden: HiddenComments,
+    ) -> Vec<Comment> {
+        self.record("get_comments");
+        self.inner.get_comments(project_id, order, hidden).await
+    }
+
+    async fn get_comments_before(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        before: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        self.record("get_comments_before");
+        self.inner.get_comments_before(project_id, hidden, before, limit).await
+    }
+
+    async fn get_comments_after(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        after: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        self.record("get_comments_after");
+        self.inner.get_comments_after(project_id, hidden, after, limit).await
+    }
+
+    async fn get_recent_comments(&self, user_id: Uuid) -> RecentComments {
+        self.record("get_recent_comments");
+        self.inner.get_recent_comments(user_id).await
+    }
+
+    async fn count_comments(&self, project_id: Uuid) -> Option<i64> {
+        self.record("count_comments");
+        self.inner.count_comments(project_id).await
+    }
+
+    async fn create_report(
+        &self,
+        project_id: Option<Uuid>,
+        comment_id: i64,
+        reporter_id: Uuid,
+        reason: Option<String>,
+        threshold: i64,
+    ) -> Option<ReportOutcome> {
+        self.record("create_report");
+        self.inner.create_report(project_id, comment_id, reporter_id, reason, threshold).await
+    }
+
+    async fn restore_comment(&self, comment_id: i64) -> bool {
+        self.record("restore_comment");
+        self.inner.restore_comment(comment_id).await
+    }
+
+    async fn get_reported_comments(&self) -> Vec<ReportedComment> {
+        self.record("get_reported_comments");
+        self.inner.get_reported_comments().await
+    }
+
+    async fn get_admin_comments(&self, filter: &AdminCommentFilter, limit: i64, offset: i64) -> Vec<AdminComment> {
+        self.record("get_admin_comments");
+        self.inner.get_admin_comments(filter, limit, offset).await
+    }
+
+    async fn delete_project_admin(&self, id: Uuid) -> bool {
+        self.record("delete_project_admin");
+        self.inner.delete_project_admin(id).await
+    }
+
+    async fn update_comment(&self, id: i64, user_id: Uuid, req: UpdateCommentRequest) -> Option<CommentEditOutcome> {
+        self.record("update_comment");
+        self.inner.update_comment(id, user_id, req).await
+    }
+
+    async fn delete_comment(&self, id: i64, user_id: Uuid) -> bool {
+        self.record("delete_comment");
+        self.inner.delete_comment(id, user_id).await
+    }
+
+    async fn delete_comment_admin(&self, id: i64) -> bool {
+        self.record("delete_comment_admin");
+        self.inner.delete_comment_admin(id).await
+    }
+
+    async fn delete_project_comment(&self, project_id: Uuid, id: i64, user_id: Option<Uuid>) -> bool {
+        self.record("delete_project_comment");
+        self.inner.delete_project_comment(project_id, id, user_id).await
+    }
+
+    async fn get_notifications(&self, user_id: Uuid) -> Vec<crate::models::NotificationResponse> {
+        self.record("get_notifications");
+        self.inner.get_notifications(user_id).await
+    }
+
+    async fn mark_notification_read(&self, notification_id: Uuid, user_id: Uuid) -> bool {
+        self.record("mark_notification_read");
+        self.inner.mark_notification_read(notification_id, user_id).await
+    }
+
+    async fn create_notification(&self, notification: NewNotification) -> Option<Notification> {
+        self.record("create_notification");
+        self.inner.create_notification(notification).await
+    }
+
+    async fn set_comments_locked(&self, id: Uuid, user_id: Uuid, locked: bool) -> Option<Project> {
+        self.record("set_comments_locked");
+        self.inner.set_comments_locked(id, user_id, locked).await
+    }
+
+    async fn set_comments_locked_admin(&self, id: Uuid, locked: bool) -> Option<Project> {
+        self.record("set_comments_locked_admin");
+        self.inner.set_comments_locked_admin(id, locked).await
+    }
+
+    async fn lock_comments_for_year(&self, year: i32) -> i64 {
+        self.record("lock_comments_for_year");
+        self.inner.lock_comments_for_year(year).await
+    }
+
+    async fn are_comments_locked(&self, project_id: Uuid) -> bool {
+        self.record("are_comments_locked");
+        self.inner.are_comments_locked(project_id).await
+    }
+
+    async fn mark_project_submitted(&self, id: Uuid) -> Option<Project> {
+        self.record("mark_project_submitted");
+        self.inner.mark_project_submitted(id).await
+    }
+
+    async fn resubmit_project(&self, id: Uuid, owner_id: Uuid, max_attempts: i32) -> Option<ResubmitOutcome> {
+        self.record("resubmit_project");
+        self.inner.resubmit_project(id, owner_id, max_attempts).await
+    }
+
+    async fn reassign_project_owner(&self, project_id: Uuid, new_owner_id: Uuid, actor_id: Uuid) -> Option<OwnerTransferOutcome> {
+        self.record("reassign_project_owner");
+        self.inner.reassign_project_owner(project_id, new_owner_id, actor_id).await
+    }
+
+    async fn get_supervisor_report(&self, year: Option<i32>) -> Vec<SupervisorStats> {
+        self.record("get_supervisor_report");
+        self.inner.get_supervisor_report(year).await
+    }
+
+    async fn notify_status_change(
+        &self,
+        project_id: Uuid,
+        actor_id: Uuid,
+        reason: Option<String>,
+    ) -> bool {
+        self.record("notify_status_change");
+        self.inner.notify_status_change(project_id, actor_id, reason).await
+    }
+
+    async fn record_project_review(
+        &self,
+        project_id: Uuid,
+        reviewer_id: Uuid,
+        status: ProjectStatus,
+        reason: Option<String>,
+    ) -> bool {
+        self.record("record_project_review");
+        self.inner.record_project_review(project_id, reviewer_id, status, reason).await
+    }
+
+    async fn get_latest_reviews(&self, owner_id: Uuid) -> Vec<ProjectReview> {
+        self.record("get_latest_reviews");
+        self.inner.get_latest_reviews(owner_id).await
+    }
+
+    async fn set_subscription(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+        state: SubscriptionState,
+    ) -> bool {
+        self.record("set_subscription");
+        self.inner.set_subscription(project_id, user_id, state).await
+    }
+
+    async fn get_subscription(&self, project_id: Uuid, user_id: Uuid) -> Option<SubscriptionState> {
+        self.record("get_subscription");
+        self.inner.get_subscription(project_id, user_id).await
+    }
+
+    async fn record_upload(
+        &self,
+        user_id: Uuid,
+        object_key: &str,
+        purpose: UploadPurpose,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        self.record("record_upload");
+        self.inner.record_upload(user_id, object_key, purpose, expires_at).await
+    }
+
+    async fn get_upload_counts(&self, user_id: Uuid, purpose: UploadPurpose) -> UploadCounts {
+        self.record("get_upload_counts");
+        self.inner.get_upload_counts(user_id, purpose).await
+    }
+
+    async fn is_own_upload(&self, user_id: Uuid, object_key: &str, purpose: UploadPurpose) -> bool {
+        self.record("is_own_upload");
+        self.inner.is_own_upload(user_id, object_key, purpose).await
+    }
+
+    async fn get_programmes(&self) -> Vec<Programme> {
+        self.record("get_programmes");
+        self.inner.get_programmes().await
+    }
+
+    async fn create_programme(&self, code: &str, name: &str) -> Option<Programme> {
+        self.record("create_programme");
+        self.inner.create_programme(code, name).await
+    }
+
+    async fn unknown_programme_codes(&self, codes: &[String]) -> Vec<String> {
+        self.record("unknown_programme_codes");
+        self.inner.unknown_programme_codes(codes).await
+    }
+
+    async fn get_project_programmes(&self, project_id: Uuid) -> Vec<String> {
+        self.record("get_project_programmes");
+        self.inner.get_project_programmes(project_id).await
+    }
+
+    async fn get_tag_counts(&self) -> Vec<TagCount> {
+        self.record("get_tag_counts");
+        self.inner.get_tag_counts().await
+    }
+
+    async fn get_user_activity(&self, user_id: Uuid, page: PageRequest) -> (Vec<ActivityItem>, i64) {
+        self.record("get_user_activity");
+        self.inner.get_user_activity(user_id, page).await
+    }
+
+    async fn get_project_stats(&self, id: Uuid, owner_id: Uuid) -> Option<ProjectStats> {
+        self.record("get_project_stats");
+        self.inner.get_project_stats(id, owner_id).await
+    }
+
+    async fn get_project_years(&self) -> Vec<YearCount> {
+        self.record("get_project_years");
+        self.inner.get_project_years().await
+    }
+
+    async fn get_department_counts(&self) -> Vec<DepartmentCount> {
+        self.record("get_department_counts");
+        self.inner.get_department_counts().await
+    }
+
+    async fn get_technology_counts(&self) -> Vec<TechnologyCount> {
+        self.record("get_technology_counts");
+        self.inner.get_technology_counts().await
+    }
+
+    async fn add_feedback(&self, project_id: Uuid, message: &str, contact_email: Option<&str>) -> Option<ProjectFeedback> {
+        self.record("add_feedback");
+        self.inner.add_feedback(project_id, message, contact_email).await
+    }
+
+    async fn get_feedback(&self, project_id: Option<Uuid>) -> Vec<ProjectFeedback> {
+        self.record("get_feedback");
+        self.inner.get_feedback(project_id).await
+    }
+
+    async fn get_settings(&self) -> Result<Vec<Setting>, RepositoryError> {
+        self.record("get_settings");
+        self.inner.get_settings().await
+    }
+
+    async fn put_setting(&self, key: &str, value: serde_json::Value, updated_by: Uuid) -> Option<Setting> {
+        self.record("put_setting");
+        self.inner.put_setting(key, value, updated_by).await
+    }
+
+    async fn get_stale_drafts(
+        &self,
+        sweep: DraftSweep,
+        untouched_before: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<StaleDraft> {
+        self.record("get_stale_drafts");
+        self.inner.get_stale_drafts(sweep, untouched_before).await
+    }
+
+    async fn mark_stale_draft(
+        &self,
+        sweep: DraftSweep,
+        project_id: Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        self.record("mark_stale_draft");
+        self.inner.mark_stale_draft(sweep, project_id, at).await
+    }
+
+    async fn get_projects_outside_years(&self, min: i32, max: i32) -> Vec<InvalidYearProject> {
+        self.record("get_projects_outside_years");
+        self.inner.get_projects_outside_years(min, max).await
+    }
+
+    async fn remap_project_year(&self, from: i32, to: i32) -> i64 {
+        self.record("remap_project_year");
+        self.inner.remap_project_year(from, to).await
+    }
+
+    async fn record_audit(&self, entry: &NewAuditEntry) -> Result<(), RepositoryError> {
+        self.record("record_audit");
+        self.inner.record_audit(entry).await
+    }
+
+    async fn queue_audit(&self, entry: &NewAuditEntry) -> Result<i64, RepositoryError> {
+        self.record("queue_audit");
+        self.inner.queue_audit(entry).await
+    }
+
+    async fn get_audit_log(&self, filter: &AuditFilter, page: PageRequest) -> (Vec<AuditEntry>, i64) {
+        self.record("get_audit_log");
+        self.inner.get_audit_log(filter, page).await
+    }
+
+    fn stream_audit_log(&self, filter: AuditFilter) -> BoxStream<'static, Result<AuditEntry, RepositoryError>> {
+        self.record("stream_audit_log");
+        self.inner.stream_audit_log(filter)
+    }
+
+    async fn purge_audit_log(&self, before: chrono::DateTime<chrono::Utc>) -> Result<u64, RepositoryError> {
+        self.record("purge_audit_log");
+        self.inner.purge_audit_log(before).await
+    }
+
+    async fn run_fanout_batch(&self, batch_size: i64) -> Option<OutboxJob> {
+        self.record("run_fanout_batch");
+        self.inner.run_fanout_batch(batch_size).await
+    }
+
+    async fn get_outbox_job(&self, id: i64) -> Option<OutboxJob> {
+        self.record("get_outbox_job");
+        self.inner.get_outbox_job(id).await
+    }
+
+    async fn flush_audit_outbox(&self) -> Result<u64, RepositoryError> {
+        self.record("flush_audit_outbox");
+        self.inner.flush_audit_outbox().await
+    }
+
+    async fn ping(&self) -> Result<(), RepositoryError> {
+        self.record("ping");
+        self.inner.ping().await
+    }
+}
@@ -0,0 +1,1745 @@
+use super::{ACTIVITY_WINDOW_DAYS, PROJECT_STATS_DAYS, AdminCommentFilter, AuditFilter, CommentCursor, DraftSweep, HiddenComments, ProjectListFilter, ProjectViewer, Repository, RepositoryError, links_json, reduce_project_update};
+use crate::models::{
+    ActivityItem, AdminDashboardStats, AuditEntry, NewAuditEntry, NewNotification, BulkStatusOutcome, Comment, CommentEditOutcome, CommentOrder, CreateProjectRequest, DepartmentCount, InvalidYearProject, ProjectOwner, OwnerTransferOutcome, ProjectStats, DailyCount, PendingReview, TechnologyCount, Like, LikeOutcome, MyProjectSummary, Notification,
+    NotificationResponse, OutboxJob, PostedComment, Programme, Project, ProjectExportRow, ProjectFeedback, ProjectLiker, ProjectReview, ProjectSort, ProjectStatus, ProjectUpdateResult, PublicEvent, PublicEventKind, PublicStats, ReportOutcome, ReportedComment, AdminComment, ResubmitOutcome, StaleDraft, SubscriptionState,
+    Setting, SupervisorStats, TagCount, DELETED_COMMENT_TEXT, UpdateCommentRequest, UpdateProjectRequest, UploadCounts, UploadPurpose, RecentComments, User, YearCount,
+};
+use crate::slug;
+use crate::pagination::PageRequest;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+use uuid::Uuid;
+
+/// A row of `comment_reports`.
+struct Report {
+    comment_id: i64,
+    reporter_id: Uuid,
+    reason: Option<String>,
+    created_at: DateTime<Utc>,
+    resolved: bool,
+}
+
+/// A row of `uploads`.
+struct Upload {
+    user_id: Uuid,
+    object_key: String,
+    purpose: UploadPurpose,
+    expires_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+}
+
+/// A row of `outbox`, with the `pending_fanout` payload unpacked.
+struct Fanout {
+    job: OutboxJob,
+    project_id: Uuid,
+    actor_id: Uuid,
+    cursor: Option<Uuid>,
+}
+
+/// The tables, one map each. Everything sits behind a single lock so that multi-table
+/// operations (a comment and its notifications, a report and the auto-hide) are atomic,
+/// as they are inside a Postgres transaction or trigger.
+#[derive(Default)]
+struct Tables {
+    profiles: HashMap<Uuid, User>,
+    projects: HashMap<Uuid, Project>,
+    project_programmes: HashMap<Uuid, BTreeSet<String>>,
+    project_tags: HashMap<Uuid, BTreeSet<String>>,
+    // (user_id, project_id) -> liked_at
+    likes: HashMap<(Uuid, Uuid), DateTime<Utc>>,
+    // Keyed by id, which increases like the identity column, so iteration is insertion order.
+    comments: BTreeMap<i64, Comment>,
+    last_comment_id: i64,
+    // `comment_edits`: (user_id, edited_at), oldest first.
+    comment_edits: Vec<(Uuid, DateTime<Utc>)>,
+    reports: Vec<Report>,
+    notifications: Vec<Notification>,
+    // (project_id, user_id)
+    subscriptions: HashMap<(Uuid, Uuid), SubscriptionState>,
+    uploads: Vec<Upload>,
+    programmes: BTreeMap<String, Programme>,
+    // The `draft_reminded_at` / `cleanup_flagged_at` columns: (project_id, sweep) -> when.
+    draft_sweeps: HashMap<(Uuid, DraftSweep), DateTime<Utc>>,
+    settings: BTreeMap<String, Setting>,
+    // In insertion (= id) order, like `comments`.
+    feedback: Vec<ProjectFeedback>,
+    last_feedback_id: i64,
+    outbox: BTreeMap<i64, Fanout>,
+    // `outbox` rows of kind `audit_entry`; ids are shared with the fan-out jobs.
+    audit_outbox: BTreeMap<i64, OutboxJob>,
+    // `audit_log`, in id order.
+    audit_log: Vec<AuditEntry>,
+    last_audit_id: i64,
+    // `project_slugs`: every slug a project has held -> that project.
+    slugs: HashMap<String, Uuid>,
+    // `project_status_history`: (project_id, is_public, changed_at), oldest first.
+    status_history: Vec<(Uuid, bool, DateTime<Utc>)>,
+    // `project_reviews`: (reviewer_id, review), oldest first.
+    reviews: Vec<(Uuid, ProjectReview)>,
+    // The `resubmission_count` column; absent means 0.
+    resubmissions: HashMap<Uuid, i32>,
+    // `public_events`: (kind, project_id, created_at), in id order. Outlives deleted projects.
+    public_events: Vec<(PublicEventKind, Uuid, DateTime<Utc>)>,
+}
+
+impl Tables {
+    /// The `outbox` identity column, across both kinds of job.
+    fn next_outbox_id(&self) -> i64 {
+        1 + self.outbox.keys().chain(self.audit_outbox.keys()).max().copied().unwrap_or(0)
+    }
+
+    fn insert_audit(&mut self, entry: NewAuditEntry, created_at: DateTime<Utc>) {
+        self.last_audit_id += 1;
+        self.audit_log.push(AuditEntry {
+            id: self.last_audit_id,
+            actor_id: entry.actor_id,
+            action: entry.action,
+            target_type: entry.target_type,
+            target_id: entry.target_id,
+            details: entry.details,
+            created_at,
+        });
+    }
+
+    fn notify(&mut self, user_id: Uuid, actor_id: Uuid, project_id: Uuid, kind: &str, message: Option<String>) {
+        self.notifications.push(Notification {
+            id: Uuid::new_v4(),
+            user_id,
+            actor_id,
+            project_id,
+            notification_type: kind.to_string(),
+            message,
+            is_read: false,
+            created_at: Utc::now(),
+        });
+    }
+
+    /// The `author_email` join of the comment queries; `None` when the author has no profile.
+    fn with_author(&self, comment: &Comment) -> Option<Comment> {
+        let author = self.profiles.get(&comment.user_id?)?;
+        Some(Comment {
+            author_email: Some(author.email.clone()),
+            ..comment.clone()
+        })
+    }
+
+    fn pending_reports(&self, comment_id: i64) -> impl Iterator<Item = &Report> {
+        self.reports.iter().filter(move |r| r.comment_id == comment_id && !r.resolved)
+    }
+
+    /// The `comment_count` and `tags` subqueries; public views skip comments held for review.
+    fn with_computed(&self, project: &Project, public_view: bool) -> Project {
+        let comment_count = self
+            .comments
+            .values()
+            .filter(|c| c.project_id == project.id && !c.is_deleted && !(public_view && c.hidden_pending_review))
+            .count() as i64;
+        let tags = self.project_tags.get(&project.id).map(|tags| tags.iter().cloned().collect()).unwrap_or_default();
+        Project { comment_count, tags, ..project.clone() }
+    }
+
+    /// The `pick_slug` / `record_slug` pair: the first slug no other project has held, added to
+    /// the project's history.
+    fn assign_slug(&mut self, project_id: Uuid, title: &str, year: i32) -> String {
+        let base = slug::base(title, year);
+        let slug = slug::first_free(&base, |candidate| {
+            self.slugs.get(candidate).is_some_and(|owner| *owner != project_id)
+        });
+        self.slugs.insert(slug.clone(), project_id);
+        slug
+    }
+
+    fn like_count(&self, project_id: Uuid) -> i64 {
+        self.likes.keys().filter(|(_, p)| *p == project_id).count() as i64
+    }
+
+    fn is_public(&self, project_id: Uuid) -> bool {
+        self.projects.get(&project_id).is_some_and(|p| p.is_public)
+    }
+
+    /// `project_likes` and `project_comments` reference `projects` without `ON DELETE CASCADE`,
+    /// so Postgres refuses to delete a project that has either.
+    fn is_referenced(&self, project_id: Uuid) -> bool {
+        self.likes.keys().any(|(_, p)| *p == project_id)
+            || self.comments.values().any(|c| c.project_id == project_id)
+    }
+
+    /// The `record_public_event` triggers: one event per visibility flip.
+    fn record_publication(&mut self, project_id: Uuid, is_public: bool) {
+        let kind = if is_public { PublicEventKind::Published } else { PublicEventKind::Unpublished };
+        self.public_events.push((kind, project_id, Utc::now()));
+    }
+
+    /// Removes a project and the rows that cascade with it.
+    fn remove_project(&mut self, id: Uuid) -> bool {
+        if self.is_referenced(id) {
+            return false;
+        }
+        let Some(project) = self.projects.remove(&id) else {
+            return false;
+        };
+        // `on_public_project_delete`.
+        if project.is_public {
+            self.record_publication(id, false);
+        }
+        self.project_programmes.remove(&id);
+        self.project_tags.remove(&id);
+        self.slugs.retain(|_, project_id| *project_id != id);
+        self.draft_sweeps.retain(|(project_id, _), _| *project_id != id);
+        self.notifications.retain(|n| n.project_id != id);
+        self.subscriptions.retain(|(project_id, _), _| *project_id != id);
+        self.feedback.retain(|f| f.project_id != id);
+        self.status_history.retain(|(project_id, ..)| *project_id != id);
+        self.reviews.retain(|(_, review)| review.project_id != id);
+        true
+    }
+
+    /// Not submitted and not public: what the stale-draft sweeps act on.
+    fn is_draft(&self, project_id: Uuid) -> bool {
+        self.projects.get(&project_id).is_some_and(|p| p.submitted_at.is_none() && !p.is_public)
+    }
+
+    /// Stamps a comment deleted (`deleted_at`); false if it is missing or already deleted.
+    fn soft_delete_comment(&mut self, id: i64) -> bool {
+        match self.comments.get_mut(&id) {
+            Some(comment) if !comment.is_deleted => {
+                comment.is_deleted = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The `comment_recipients` function: the owner, past commenters and explicit subscribers,
+    /// minus anyone who muted the project and minus the commenter. Sorted, like the `uuid`
+    /// ordering the dispatcher's cursor relies on.
+    fn comment_recipients(&self, project_id: Uuid, actor_id: Uuid) -> BTreeSet<Uuid> {
+        let mut recipients = BTreeSet::new();
+        recipients.extend(self.projects.get(&project_id).map(|p| p.user_id));
+        recipients.extend(self.comments.values().filter(|c| c.project_id == project_id).filter_map(|c| c.user_id));
+        recipients.extend(
+            self.subscriptions
+                .iter()
+                .filter(|((p, _), state)| *p == project_id && **state == SubscriptionState::Subscribed)
+                .map(|((_, user_id), _)| *user_id),
+        );
+        recipients.retain(|user_id| {
+            *user_id != actor_id
+                && self.subscriptions.get(&(project_id, *user_id)) != Some(&SubscriptionState::Muted)
+        });
+        recipients
+    }
+}
+
+fn matches_filter(project: &Project, filter: &ProjectListFilter, tables: &Tables) -> bool {
+    let member = |set: &HashMap<Uuid, BTreeSet<String>>, value: &String| set.get(&project.id).is_some_and(|values| values.contains(value));
+    let contains = |haystack: &str, needle: &str| haystack.to_lowercase().contains(&needle.to_lowercase());
+    project.is_public
+        && filter.year.is_none_or(|year| project.year == year)
+        && filter.search.as_deref().is_none_or(|s| {
+            contains(&project.title, s) || contains(&project.abstract_text, s) || contains(&project.author, s)
+        })
+        && filter.programme.as_ref().is_none_or(|code| member(&tables.project_programmes, code))
+        && filter.tag.as_ref().is_none_or(|tag| member(&tables.project_tags, tag))
+        && filter.supervisor.as_deref().is_none_or(|name| {
+            project.supervisor.as_deref().is_some_and(|s| s.trim().to_lowercase() == name.to_lowercase())
+        })
+        && filter.department.as_deref().is_none_or(|department| {
+            project.department.as_deref().is_some_and(|d| d.to_lowercase() == department.to_lowercase())
+        })
+        && filter.author.as_deref().is_none_or(|name| project.author.to_lowercase() == name.to_lowercase())
+        && filter.technologies.iter().all(|t| project.technologies.contains(t))
+}
+
+/// InMemoryRepository
+///
+/// A database-free `Repository` for tests, behind the `test-util` feature. It keeps the
+/// semantics of `PostgresRepository`, including the ones Postgres gets from constraints and
+/// triggers: visibility filters, ownership checks, idempotent likes, the `profiles` joins on
+/// comments and notifications, and the comment fan-out. The
+/// `repository_conformance_tests` suite runs the same assertions against both.
+///
+/// Users are seeded through `create_user`, as the auth flow does.
+#[derive(Default)]
+pub struct InMemoryRepository {
+    tables: RwLock<Tables>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Tables> {
+        self.tables.read().unwrap()
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Tables> {
+        self.tables.write().unwrap()
+    }
+
+    /// Every project, drafts first and then newest first (`get_all_projects` order).
+    fn all_projects(&self) -> Vec<Project> {
+        let tables = self.read();
+        let mut projects: Vec<Project> = tables.projects.values().map(|p| tables.with_computed(p, false)).collect();
+        projects.sort_by(|a, b| a.is_public.cmp(&b.is_public).then(b.created_at.cmp(&a.created_at)));
+        projects
+    }
+
+    /// Audit entries matching `filter`, newest first (`get_audit_log` order).
+    fn audit_entries(&self, filter: &AuditFilter) -> Vec<AuditEntry> {
+        let tables = self.read();
+        let mut matching: Vec<AuditEntry> = tables
+            .audit_log
+            .iter()
+            .filter(|e| {
+                filter.actor_id.is_none_or(|actor| e.actor_id == Some(actor))
+                    && filter.action.as_ref().is_none_or(|action| &e.action == action)
+                    && filter.target_type.as_ref().is_none_or(|target| &e.target_type == target)
+                    && filter.from.is_none_or(|from| e.created_at >= from)
+                    && filter.to.is_none_or(|to| e.created_at < to)
+            })
+            .cloned()
+            .collect();
+        matching.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        matching
+    }
+
+    /// Applies `change` to a project row, optionally only when `owner` matches.
+    fn update_row(&self, id: Uuid, owner: Option<Uuid>, change: impl FnOnce(&mut Project)) -> Option<Project> {
+        let mut tables = self.write();
+        let project = tables.projects.get_mut(&id)?;
+        if owner.is_some_and(|owner| project.user_id != owner) {
+            return None;
+        }
+        change(project);
+        let project = project.clone();
+        Some(tables.with_computed(&project, false))
+    }
+}
+
+#[async_trait]
+impl Repository for InMemoryRepository {
+    async fn get_projects(&self, filter: ProjectListFilter, page: PageRequest) -> (Vec<Project>, i64) {
+        let tables = self.read();
+        let mut matching: Vec<&Project> = tables
+            .projects
+            .values()
+            .filter(|p| matches_filter(p, &filter, &tables))
+            .collect();
+        matching.sort_by_key(|p| std::cmp::Reverse((p.created_at, p.id)));
+        // Stable sorts on top of newest-first give the same tie-breaks as the SQL.
+        match filter.sort {
+            ProjectSort::Newest => {}
+            ProjectSort::Oldest => matching.reverse(),
+            ProjectSort::MostLiked => matching.sort_by_key(|p| std::cmp::Reverse(tables.like_count(p.id))),
+            ProjectSort::Title => matching.sort_by_key(|p| p.title.to_lowercase()),
+            ProjectSort::MostViewed => matching.sort_by_key(|p| std::cmp::Reverse(p.view_count)),
+        }
+        let total_count = matching.len() as i64;
+        let items = matching
+            .into_iter()
+            .filter(|p| page.after.is_none_or(|after| (p.created_at, p.id) < (after.created_at, after.id)))
+            .skip(page.offset() as usize)
+            .take(page.per_page as usize)
+            .map(|p| tables.with_computed(p, true))
+            .collect();
+        (items, total_count)
+    }
+
+    async fn get_all_projects(&self) -> Vec<Project> {
+        self.all_projects()
+    }
+
+    async fn get_pending_reviews(&self, limit: i64) -> Vec<PendingReview> {
+        let tables = self.read();
+        let mut pending: Vec<&Project> = tables.projects.values().filter(|p| p.status == ProjectStatus::Pending).collect();
+        pending.sort_by_key(|p| (p.submitted_at.unwrap_or(p.created_at), p.id));
+        let now = Utc::now();
+        pending
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|p| {
+                let email = tables.profiles.get(&p.user_id).map(|u| u.email.clone());
+                PendingReview::new(tables.with_computed(p, false), email, now)
+            })
+            .collect()
+    }
+
+    fn stream_all_projects(&self) -> BoxStream<'static, Result<Project, RepositoryError>> {
+        futures::stream::iter(self.all_projects().into_iter().map(Ok)).boxed()
+    }
+
+    fn stream_project_export(&self) -> BoxStream<'static, Result<ProjectExportRow, RepositoryError>> {
+        let tables = self.read();
+        let mut rows: Vec<ProjectExportRow> = tables
+            .projects
+            .values()
+            .map(|p| ProjectExportRow {
+                id: p.id,
+                title: p.title.clone(),
+                author: p.author.clone(),
+                year: p.year,
+                status: p.status,
+                like_count: tables.like_count(p.id),
+                created_at: p.created_at,
+                owner_email: tables.profiles.get(&p.user_id).map(|u| u.email.clone()),
+            })
+            .collect();
+        rows.sort_by_key(|row| (row.created_at, row.id));
+        futures::stream::iter(rows.into_iter().map(Ok)).boxed()
+    }
+
+    async fn get_top_projects(&self, limit: i64, min_likes: i64, year: Option<i32>) -> Vec<Project> {
+        let tables = self.read();
+        let mut projects: Vec<&Project> = tables
+            .projects
+            .values()
+            .filter(|p| p.is_public && year.is_none_or(|y| p.year == y) && tables.like_count(p.id) >= min_likes)
+            .collect();
+        projects.sort_by_key(|p| std::cmp::Reverse((tables.like_count(p.id), p.created_at, p.id)));
+        projects
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|p| tables.with_computed(p, true))
+            .collect()
+    }
+
+    async fn get_year_archive(&self, year: i32) -> Vec<Project> {
+        self.get_top_projects(i64::MAX, 0, Some(year)).await
+    }
+
+    async fn get_random_public_project(&self, seed: Option<i64>) -> Option<Project> {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let seed = seed.unwrap_or_else(|| Uuid::new_v4().as_u64_pair().0 as i64);
+        let rank = |id: &Uuid| {
+            let mut hasher = DefaultHasher::new();
+            (id, seed).hash(&mut hasher);
+            hasher.finish()
+        };
+        let tables = self.read();
+        tables
+            .projects
+            .values()
+            .filter(|p| p.is_public)
+            .min_by_key(|p| rank(&p.id))
+            .map(|p| tables.with_computed(p, true))
+    }
+
+    async fn get_project(&self, id: Uuid) -> Option<Project> {
+        let tables = self.read();
+        tables.projects.get(&id).map(|p| tables.with_computed(p, false))
+    }
+
+    async fn get_project_authorized(&self, id: Uuid, user_id: Uuid) -> Option<Project> {
+        let tables = self.read();
+        tables
+            .projects
+            .get(&id)
+            .filter(|p| p.is_public || p.user_id == user_id)
+            .map(|p| tables.with_computed(p, true))
+    }
+
+    async fn get_public_project(&self, id: Uuid) -> Option<Project> {
+        let tables = self.read();
+        tables
+            .projects
+            .get(&id)
+            .filter(|p| p.is_public)
+            .map(|p| tables.with_computed(p, true))
+    }
+
+    async fn get_project_detail(&self, id: Uuid, viewer: ProjectViewer) -> Option<(Project, Option<ProjectOwner>)> {
+        let tables = self.read();
+        let project = tables.projects.get(&id).filter(|p| match viewer {
+            ProjectViewer::Public => p.is_public,
+            ProjectViewer::User(user_id) => p.is_public || p.user_id == user_id,
+            ProjectViewer::Admin => true,
+        })?;
+        let owner = tables.profiles.get(&project.user_id).map(|u| ProjectOwner::new(u.id, u.email.clone()));
+        Some((tables.with_computed(project, viewer != ProjectViewer::Admin), owner))
+    }
+
+    async fn resolve_project_slug(&self, slug: &str) -> Option<Uuid> {
+        self.read().slugs.get(slug).copied()
+    }
+
+    async fn find_project_by_title_year(&self, title: &str, year: i32) -> Option<Uuid> {
+        let key = super::title_key(title);
+        self.read()
+            .projects
+            .values()
+            .filter(|p| p.year == year && super::title_key(&p.title) == key)
+            .min_by_key(|p| (p.created_at, p.id))
+            .map(|p| p.id)
+    }
+
+    async fn count_user_projects_in_year(&self, user_id: Uuid, year: i32) -> i64 {
+        self.read().projects.values().filter(|p| p.user_id == user_id && p.year == year).count() as i64
+    }
+
+    async fn get_changed_project_ids(&self, since: DateTime<Utc>, ids: Option<Vec<Uuid>>) -> Vec<Uuid> {
+        let tables = self.read();
+        let mut changed: Vec<Uuid> = tables
+            .projects
+            .values()
+            .filter(|p| p.is_public && ids.as_ref().is_none_or(|ids| ids.contains(&p.id)))
+            .filter(|p| {
+                p.updated_at > since
+                    || tables.comments.values().any(|c| c.project_id == p.id && c.created_at > since)
+            })
+            .map(|p| p.id)
+            .collect();
+        changed.sort();
+        changed
+    }
+
+    async fn get_public_events(&self, since: DateTime<Utc>, limit: i64) -> Vec<PublicEvent> {
+        let tables = self.read();
+        tables
+            .public_events
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, _, at))| *at > since)
+            .take(limit as usize)
+            .map(|(index, &(kind, project_id, at))| PublicEvent {
+                id: index as i64 + 1,
+                kind,
+                project_id,
+                at,
+                project: match kind {
+                    PublicEventKind::Published => tables
+                        .projects
+                        .get(&project_id)
+                        .filter(|p| p.is_public)
+                        .map(|p| tables.with_computed(p, true)),
+                    PublicEventKind::Unpublished => None,
+                },
+            })
+            .collect()
+    }
+
+    async fn increment_view(&self, id: Uuid) -> Option<i64> {
+        let mut tables = self.write();
+        let project = tables.projects.get_mut(&id).filter(|p| p.is_public)?;
+        project.view_count += 1;
+        Some(project.view_count)
+    }
+
+    async fn get_like_count(&self, id: Uuid) -> Option<i64> {
+        let tables = self.read();
+        if !tables.is_public(id) {
+            return None;
+        }
+        Some(tables.likes.keys().filter(|(_, project_id)| *project_id == id).count() as i64)
+    }
+
+    async fn create_project(&self, req: CreateProjectRequest, user_id: Uuid) -> Project {
+        let now = Utc::now();
+        let mut tables = self.write();
+        let id = Uuid::new_v4();
+        let slug = tables.assign_slug(id, &req.title, req.year);
+        let project = Project {
+            id,
+            user_id,
+            author: req.author_name,
+            title: req.title,
+            abstract_text: req.abstract_text,
+            cover_image: req.cover_image_key,
+            video: req.video_key,
+            report: req.report_key,
+            status: ProjectStatus::Draft,
+            is_public: false,
+            report_is_public: false,
+            comments_locked: false,
+            links: links_json(req.links),
+            video_waived: false,
+            submitted_at: None,
+            supervisor: req.supervisor,
+            department: req.department,
+            technologies: req.technologies,
+            view_count: 0,
+            slug,
+            year: req.year,
+            comment_count: 0,
+            tags: req.tags.iter().cloned().collect::<BTreeSet<_>>().into_iter().collect(),
+            created_at: now,
+            updated_at: now,
+        };
+        if let Some(codes) = req.programme_codes {
+            tables.project_programmes.insert(project.id, codes.into_iter().collect());
+        }
+        if !project.tags.is_empty() {
+            tables.project_tags.insert(project.id, project.tags.iter().cloned().collect());
+        }
+        tables.projects.insert(project.id, project.clone());
+        project
+    }
+
+    async fn like_project(&self, like: Like) -> Option<LikeOutcome> {
+        let mut tables = self.write();
+        let owner = tables.projects.get(&like.project_id).filter(|p| p.is_public)?.user_id;
+        let key = (like.user_id, like.project_id);
+        let count = |tables: &Tables| tables.likes.keys().filter(|(_, project_id)| *project_id == like.project_id).count() as i64;
+        if owner == like.user_id {
+            return Some(LikeOutcome::OwnProject(count(&tables)));
+        }
+        let created = !tables.likes.contains_key(&key);
+        tables.likes.entry(key).or_insert_with(Utc::now);
+        if created {
+            tables.notify(owner, like.user_id, like.project_id, "like", None);
+        }
+        let count = count(&tables);
+        Some(if created { LikeOutcome::Created(count) } else { LikeOutcome::AlreadyExists(count) })
+    }
+
+    async fn unlike_project(&self, like: Like) -> bool {
+        let mut tables = self.write();
+        if tables.likes.remove(&(like.user_id, like.project_id)).is_none() {
+            return false;
+        }
+        tables.notifications.retain(|n| {
+            !(n.project_id == like.project_id && n.actor_id == like.user_id && n.notification_type == "like")
+        });
+        true
+    }
+
+    async fn get_liked_project_ids(&self, user_id: Uuid, project_ids: &[Uuid]) -> Vec<Uuid> {
+        let tables = self.read();
+        project_ids.iter().copied().filter(|id| tables.likes.contains_key(&(user_id, *id))).collect()
+    }
+
+    async fn get_project_likers(&self, project_id: Uuid, page: PageRequest) -> (Vec<ProjectLiker>, i64) {
+        let tables = self.read();
+        let mut likers: Vec<ProjectLiker> = tables
+            .likes
+            .iter()
+            .filter(|((_, liked), _)| *liked == project_id)
+            .map(|(&(user_id, _), &liked_at)| ProjectLiker {
+                id: user_id,
+                email: tables.profiles.get(&user_id).map(|u| u.email.clone()),
+                liked_at,
+            })
+            .collect();
+        likers.sort_by(|a, b| b.liked_at.cmp(&a.liked_at).then(a.id.cmp(&b.id)));
+        let total_count = likers.len() as i64;
+        let items = likers.into_iter().skip(page.offset() as usize).take(page.per_page as usize).collect();
+        (items, total_count)
+    }
+
+    async fn set_project_status(&self, id: Uuid, status: ProjectStatus) -> Option<Project> {
+        let mut changed = false;
+        let project = self.update_row(id, None, |p| {
+            changed = p.is_public != status.is_public();
+            p.status = status;
+            // The generated `is_public` column.
+            p.is_public = status.is_public();
+        })?;
+        // The `on_project_status_change` and `on_project_publication_change` triggers.
+        if changed {
+            let mut tables = self.write();
+            tables.status_history.push((id, status.is_public(), Utc::now()));
+            tables.record_publication(id, status.is_public());
+        }
+        Some(project)
+    }
+
+    async fn set_projects_status_bulk(&self, ids: &[Uuid], is_public: bool) -> BulkStatusOutcome {
+        let status = ProjectStatus::from_visibility(is_public);
+        let mut tables = self.write();
+        let mut outcome = BulkStatusOutcome::default();
+        for &id in ids {
+            let Some(project) = tables.projects.get_mut(&id) else {
+                outcome.not_found.push(id);
+                continue;
+            };
+            if project.status == status {
+                continue;
+            }
+            let visibility_changed = project.is_public != status.is_public();
+            project.status = status;
+            project.is_public = status.is_public();
+            if visibility_changed {
+                tables.status_history.push((id, status.is_public(), Utc::now()));
+                tables.record_publication(id, status.is_public());
+            }
+            outcome.changed.push(id);
+        }
+        outcome
+    }
+
+    async fn get_user(&self, id: Uuid) -> Option<User> {
+        self.read().profiles.get(&id).cloned()
+    }
+
+    async fn create_user(&self, user: User) -> User {
+        let mut tables = self.write();
+        assert!(!tables.profiles.contains_key(&user.id), "Failed to create user");
+        tables.profiles.insert(user.id, user.clone());
+        user
+    }
+
+    async fn get_users_by_email_prefix(&self, prefixes: &[String]) -> Vec<User> {
+        let mut users: Vec<User> = self
+            .read()
+            .profiles
+            .values()
+            .filter(|u| {
+                let local = u.email.split('@').next().unwrap_or_default().to_lowercase();
+                prefixes.contains(&local)
+            })
+            .cloned()
+            .collect();
+        users.sort_by(|a, b| a.email.cmp(&b.email));
+        users
+    }
+
+    async fn get_stats(&self) -> AdminDashboardStats {
+        let tables = self.read();
+        AdminDashboardStats {
+            total_projects: tables.projects.len() as i64,
+            total_users: tables.profiles.len() as i64,
+            total_likes: tables.likes.len() as i64,
+            pending_reviews: tables.projects.values().filter(|p| p.status == ProjectStatus::Pending).count() as i64,
+        }
+    }
+
+    async fn get_public_stats(&self) -> PublicStats {
+        let tables = self.read();
+        let public: Vec<&Project> = tables.projects.values().filter(|p| p.is_public).collect();
+        PublicStats {
+            public_projects: public.len() as i64,
+            distinct_years: public.iter().map(|p| p.year).collect::<BTreeSet<_>>().len() as i64,
+            distinct_supervisors: public
+                .iter()
+                .filter_map(|p| p.supervisor.as_deref().map(str::trim).filter(|s| !s.is_empty()))
+                .collect::<BTreeSet<_>>()
+                .len() as i64,
+            total_likes: public.iter().map(|p| tables.like_count(p.id)).sum(),
+        }
+    }
+
+    async fn get_my_project_summaries(&self, user_id: Uuid) -> Vec<MyProjectSummary> {
+        let tables = self.read();
+        let mut summaries: Vec<MyProjectSummary> = tables
+            .projects
+            .values()
+            .filter(|p| p.user_id == user_id)
+            .map(|p| MyProjectSummary::new(tables.with_computed(p, false), tables.like_count(p.id)))
+            .collect();
+        summaries.sort_by_key(|s| std::cmp::Reverse(s.project.created_at));
+        summaries
+    }
+
+    async fn get_user_activity(&self, user_id: Uuid, page: PageRequest) -> (Vec<ActivityItem>, i64) {
+        let tables = self.read();
+        let since = Utc::now() - chrono::Duration::days(ACTIVITY_WINDOW_DAYS.into());
+        let visible = |project_id: Uuid| {
+            tables.projects.get(&project_id).filter(|p| p.is_public || p.user_id == user_id)
+        };
+        let comments = tables.comments.values().filter(|c| c.user_id == Some(user_id) && !c.is_deleted).filter_map(|c| {
+            visible(c.project_id).map(|p| ActivityItem::Commented {
+                at: c.created_at,
+                project_id: p.id,
+                project_title: p.title.clone(),
+                comment_id: c.id,
+                comment: c.comment.clone(),
+            })
+        });
+        let likes = tables.likes.iter().filter(|((u, _), _)| *u == user_id).filter_map(|((_, project_id), at)| {
+            visible(*project_id).map(|p| ActivityItem::Liked { at: *at, project_id: p.id, project_title: p.title.clone() })
+        });
+        let status_changes = tables.status_history.iter().filter_map(|(project_id, is_public, at)| {
+            let p = tables.projects.get(project_id).filter(|p| p.user_id == user_id)?;
+            Some(ActivityItem::StatusChanged { at: *at, project_id: p.id, project_title: p.title.clone(), is_public: *is_public })
+        });
+        let mut items: Vec<ActivityItem> = comments.chain(likes).chain(status_changes).filter(|item| item.at() >= since).collect();
+        // `ORDER BY at DESC, kind, project_id, comment_id`.
+        items.sort_by_key(|item| {
+            let (kind, project_id, comment_id) = match item {
+                ActivityItem::Commented { project_id, comment_id, .. } => (0, *project_id, *comment_id),
+                ActivityItem::Liked { project_id, .. } => (1, *project_id, 0),
+                ActivityItem::StatusChanged { project_id, .. } => (2, *project_id, 0),
+            };
+            (std::cmp::Reverse(item.at()), kind, project_id, comment_id)
+        });
+        let total_count = items.len() as i64;
+        let items = items.into_iter().skip(page.offset() as usize).take(page.per_page as usize).collect();
+        (items, total_count)
+    }
+
+    async fn get_project_stats(&self, id: Uuid, owner_id: Uuid) -> Option<ProjectStats> {
+        let tables = self.read();
+        let project = tables.projects.get(&id).filter(|p| p.user_id == owner_id)?;
+        let today = Utc::now().date_naive();
+        let likes_per_day = (0..PROJECT_STATS_DAYS)
+            .rev()
+            .map(|ago| {
+                let day = today - chrono::Duration::days(ago.into());
+                let count = tables
+                    .likes
+                    .iter()
+                    .filter(|((_, p), at)| *p == id && at.date_naive() == day)
+                    .count() as i64;
+                DailyCount { day, count }
+            })
+            .collect();
+        Some(ProjectStats {
+            project_id: id,
+            like_count: tables.like_count(id),
+            comment_count: tables.with_computed(project, true).comment_count,
+            view_count: project.view_count,
+            likes_per_day,
+        })
+    }
+
+    async fn delete_project(&self, id: Uuid, user_id: Uuid) -> bool {
+        let mut tables = self.write();
+        tables.projects.get(&id).is_some_and(|p| p.user_id == user_id) && tables.remove_project(id)
+    }
+
+    async fn update_project(&self, id: Uuid, user_id: Uuid, req: UpdateProjectRequest) -> Option<ProjectUpdateResult> {
+        let mut tables = self.write();
+        let current = tables.with_computed(tables.projects.get(&id).filter(|p| p.user_id == user_id)?, false);
+        let programmes: Vec<String> = tables.project_programmes.get(&id).into_iter().flatten().cloned().collect();
+        let (req, changed_fields) = reduce_project_update(&current, &programmes, req);
+        if changed_fields.is_empty() {
+            return Some(ProjectUpdateResult { project: current, changed_fields, ..ProjectUpdateResult::default() });
+        }
+        if req.title.is_some() || req.year.is_some() {
+            let title = req.title.as_ref().unwrap_or(&current.title);
+            let slug = tables.assign_slug(id, title, req.year.unwrap_or(current.year));
+            tables.projects.get_mut(&id)?.slug = slug;
+        }
+        let project = tables.projects.get_mut(&id)?;
+        if let Some(title) = req.title {
+            project.title = title;
+        }
+        if let Some(abstract_text) = req.abstract_text {
+            project.abstract_text = abstract_text;
+        }
+        if let Some(cover_image) = req.cover_image_key {
+            project.cover_image = cover_image;
+        }
+        if let Some(video) = req.video_key {
+            project.video = Some(video);
+        }
+        if let Some(report) = req.report_key {
+            project.report = Some(report);
+        }
+        if let Some(author) = req.author_name {
+            project.author = author;
+        }
+        if let Some(year) = req.year {
+            project.year = year;
+        }
+        if let Some(links) = req.links {
+            project.links = links_json(Some(links));
+        }
+        if let Some(video_waived) = req.video_waived {
+            project.video_waived = video_waived;
+        }
+        if let Some(supervisor) = req.supervisor {
+            project.supervisor = Some(supervisor);
+        }
+        if let Some(department) = req.department {
+            project.department = Some(department);
+        }
+        if let Some(technologies) = req.technologies {
+            project.technologies = technologies;
+        }
+        project.updated_at = Utc::now();
+        let project = project.clone();
+        if let Some(codes) = req.programme_codes {
+            tables.project_programmes.insert(id, codes.into_iter().collect());
+        }
+        if let Some(tags) = req.tags {
+            tables.project_tags.insert(id, tags.into_iter().collect());
+        }
+        Some(ProjectUpdateResult { project: tables.with_computed(&project, false), changed_fields, ..ProjectUpdateResult::default() })
+    }
+
+    async fn add_comment(&self, project_id: Uuid, user_id: Uuid, text: String, fanout_threshold: i64) -> Result<Option<PostedComment>, RepositoryError> {
+        let mut tables = self.write();
+        // The foreign keys of `project_comments`, and the discussion lock.
+        let open = tables.projects.get(&project_id).is_some_and(|p| !p.comments_locked);
+        if !open || !tables.profiles.contains_key(&user_id) {
+            return Ok(None);
+        }
+        tables.last_comment_id += 1;
+        let comment = Comment {
+            id: tables.last_comment_id,
+            user_id: Some(user_id),
+            project_id,
+            comment: text,
+            created_at: Utc::now(),
+            author_email: None,
+            hidden_pending_review: false,
+            edited_at: None,
+            edited: false,
+            is_deleted: false,
+        };
+        let Some(enriched) = tables.with_author(&comment) else { return Ok(None) };
+        let comment_id = comment.id;
+        tables.comments.insert(comment_id, comment);
+        let recipients = tables.comment_recipients(project_id, user_id);
+        let fanout_job_id = if recipients.len() as i64 > fanout_threshold {
+            let id = tables.next_outbox_id();
+            let now = Utc::now();
+            let job = OutboxJob {
+                id,
+                kind: "pending_fanout".to_string(),
+                status: "pending".to_string(),
+                payload: serde_json::json!({ "project_id": project_id, "actor_id": user_id, "comment_id": comment_id }),
+                total: recipients.len() as i64,
+                processed: 0,
+                created_at: now,
+                updated_at: now,
+                completed_at: None,
+            };
+            tables.outbox.insert(id, Fanout { job, project_id, actor_id: user_id, cursor: None });
+            Some(id)
+        } else {
+            for recipient in recipients {
+                tables.notify(recipient, user_id, project_id, "comment", None);
+            }
+            None
+        };
+        Ok(Some(PostedComment { comment: enriched, fanout_job_id }))
+    }
+
+    async fn get_comments(&self, project_id: Uuid, order: CommentOrder, hidden: HiddenComments) -> Vec<Comment> {
+        let tables = self.read();
+        let readable = tables.projects.get(&project_id).is_some_and(|p| match hidden {
+            _ if p.is_public => true,
+            HiddenComments::Include => true,
+            HiddenComments::AuthoredBy(viewer) => p.user_id == viewer,
+            HiddenComments::Exclude => false,
+        });
+        if !readable {
+            return vec![];
+        }
+        let visible = |c: &Comment| match hidden {
+            _ if !c.hidden_pending_review => true,
+            HiddenComments::Include => true,
+            HiddenComments::AuthoredBy(viewer) => c.user_id == Some(viewer),
+            HiddenComments::Exclude => false,
+        };
+        let mut comments: Vec<Comment> = tables
+            .comments
+            .values()
+            .filter(|c| c.project_id == project_id)
+            .filter(|c| visible(c))
+            .filter(|c| {
+                !c.is_deleted
+                    || tables.comments.range(c.id + 1..).any(|(_, later)| {
+                        later.project_id == project_id && !later.is_deleted && visible(later)
+                    })
+            })
+            .filter_map(|c| tables.with_author(c))
+            .map(|c| {
+                if c.is_deleted {
+                    Comment {
+                        user_id: None,
+                        comment: DELETED_COMMENT_TEXT.to_string(),
+                        author_email: None,
+                        edited_at: None,
+                        edited: false,
+                        hidden_pending_review: false,
+                        ..c
+                    }
+                } else {
+                    c
+                }
+            })
+            .collect();
+        comments.sort_by_key(|c| (c.created_at, c.id));
+        if order == CommentOrder::Newest {
+            comments.reverse();
+        }
+        comments
+    }
+
+    async fn get_comments_before(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        before: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        let mut comments: Vec<Comment> = self
+            .get_comments(project_id, CommentOrder::Oldest, hidden)
+            .await
+            .into_iter()
+            .filter(|c| match before {
+                Some(CommentCursor::Id(id)) => c.id < id,
+                Some(CommentCursor::Time(at)) => c.created_at < at,
+                None => true,
+            })
+            .collect();
+        comments.sort_by_key(|c| std::cmp::Reverse(c.id));
+        comments.truncate(limit.max(0) as usize);
+        comments
+    }
+
+    async fn get_comments_after(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        after: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        let mut comments: Vec<Comment> = self
+            .get_comments(project_id, CommentOrder::Oldest, hidden)
+            .await
+            .into_iter()
+            .filter(|c| match after {
+                Some(CommentCursor::Id(id)) => c.id > id,
+                Some(CommentCursor::Time(at)) => c.created_at > at,
+                None => true,
+            })
+            .collect();
+        comments.sort_by_key(|c| c.id);
+        comments.truncate(limit.max(0) as usize);
+        comments
+    }
+
+    async fn get_recent_comments(&self, user_id: Uuid) -> RecentComments {
+        let tables = self.read();
+        let now = Utc::now();
+        let since = |window: chrono::Duration| {
+            let posted = tables.comments.values().filter(|c| c.user_id == Some(user_id)).map(|c| c.created_at);
+            let edited = tables.comment_edits.iter().filter(|(u, _)| *u == user_id).map(|&(_, at)| at);
+            let times: Vec<DateTime<Utc>> = posted.chain(edited).filter(|&at| at > now - window).collect();
+            (times.len() as i64, times.into_iter().min())
+        };
+        let (last_minute, oldest_last_minute) = since(chrono::Duration::minutes(1));
+        let (last_day, oldest_last_day) = since(chrono::Duration::days(1));
+        RecentComments { last_minute, oldest_last_minute, last_day, oldest_last_day }
+    }
+
+    async fn count_comments(&self, project_id: Uuid) -> Option<i64> {
+        let tables = self.read();
+        if !tables.is_public(project_id) {
+            return None;
+        }
+        let count = tables
+            .comments
+            .values()
+            .filter(|c| c.project_id == project_id && !c.hidden_pending_review && !c.is_deleted)
+            .count();
+        Some(count as i64)
+    }
+
+    async fn create_report(
+        &self,
+        project_id: Option<Uuid>,
+        comment_id: i64,
+        reporter_id: Uuid,
+        reason: Option<String>,
+        threshold: i64,
+    ) -> Option<ReportOutcome> {
+        let mut tables = self.write();
+        let comment = tables
+            .comments
+            .get(&comment_id)
+            .filter(|c| project_id.is_none_or(|id| c.project_id == id) && tables.is_public(c.project_id) && !c.is_deleted)?;
+        let (already_hidden, project_id) = (comment.hidden_pending_review, comment.project_id);
+
+        // Only distinct reporters count: a second pending report by the same user is a no-op.
+        if !tables.pending_reports(comment_id).any(|r| r.reporter_id == reporter_id) {
+            tables.reports.push(Report { comment_id, reporter_id, reason, created_at: Utc::now(), resolved: false });
+        }
+        let pending_reports = tables.pending_reports(comment_id).count() as i64;
+
+        let hidden = !already_hidden && pending_reports >= threshold;
+        if hidden {
+            if let Some(comment) = tables.comments.get_mut(&comment_id) {
+                comment.hidden_pending_review = true;
+            }
+            let admins: Vec<Uuid> = tables.profiles.values().filter(|u| u.role == "admin").map(|u| u.id).collect();
+            let message = format!("Comment {} was hidden after {} reports.", comment_id, pending_reports);
+            for admin in admins {
+                tables.notify(admin, reporter_id, project_id, "comment_hidden", Some(message.clone()));
+            }
+        }
+        Some(ReportOutcome { pending_reports, hidden })
+    }
+
+    async fn restore_comment(&self, comment_id: i64) -> bool {
+        let mut tables = self.write();
+        let Some(comment) = tables.comments.get_mut(&comment_id) else {
+            return false;
+        };
+        comment.hidden_pending_review = false;
+        for report in tables.reports.iter_mut().filter(|r| r.comment_id == comment_id) {
+            report.resolved = true;
+        }
+        true
+    }
+
+    async fn get_reported_comments(&self) -> Vec<ReportedComment> {
+        let tables = self.read();
+        let mut queue: Vec<ReportedComment> = tables
+            .comments
+            .values()
+            .filter(|c| !c.is_deleted)
+            .filter_map(|c| {
+                let pending: Vec<&Report> = tables.pending_reports(c.id).collect();
+                let last_reported_at = pending.iter().map(|r| r.created_at).max()?;
+                Some(ReportedComment {
+                    comment: tables.with_author(c).unwrap_or_else(|| c.clone()),
+                    project_title: tables.projects.get(&c.project_id)?.title.clone(),
+                    report_count: pending.len() as i64,
+                    last_reported_at,
+                    reasons: pending.iter().filter_map(|r| r.reason.clone()).collect(),
+                })
+            })
+            .collect();
+        queue.sort_by_key(|r| (std::cmp::Reverse((r.report_count, r.last_reported_at)), r.comment.id));
+        queue
+    }
+
+    async fn get_admin_comments(&self, filter: &AdminCommentFilter, limit: i64, offset: i64) -> Vec<AdminComment> {
+        let tables = self.read();
+        let search = filter.search.as_ref().map(|s| s.to_lowercase());
+        let mut matching: Vec<&Comment> = tables
+            .comments
+            .values()
+            .filter(|c| {
+                filter.project_id.is_none_or(|id| c.project_id == id)
+                    && filter.user_id.is_none_or(|id| c.user_id == Some(id))
+                    && search.as_ref().is_none_or(|s| c.comment.to_lowercase().contains(s))
+            })
+            .collect();
+        matching.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        matching
+            .into_iter()
+            .filter_map(|c| {
+                Some(AdminComment {
+                    comment: tables.with_author(c).unwrap_or_else(|| c.clone()),
+                    project_title: tables.projects.get(&c.project_id)?.title.clone(),
+                })
+            })
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    async fn delete_project_admin(&self, id: Uuid) -> bool {
+        self.write().remove_project(id)
+    }
+
+    async fn update_comment(&self, id: i64, user_id: Uuid, req: UpdateCommentRequest) -> Option<CommentEditOutcome> {
+        let mut tables = self.write();
+        let comment = tables.comments.get(&id).filter(|c| c.user_id == Some(user_id) && !c.is_deleted)?;
+        if tables.projects.get(&comment.project_id).is_some_and(|p| p.comments_locked) {
+            return Some(CommentEditOutcome::Locked);
+        }
+        if comment.hidden_pending_review {
+            return Some(CommentEditOutcome::HiddenPendingReview);
+        }
+        let now = Utc::now();
+        let comment = tables.comments.get_mut(&id)?;
+        comment.comment = req.text;
+        comment.edited_at = Some(now);
+        comment.edited = true;
+        let comment = comment.clone();
+        tables.comment_edits.push((user_id, now));
+        tables.with_author(&comment).map(|c| CommentEditOutcome::Edited(Box::new(c)))
+    }
+
+    async fn delete_comment(&self, id: i64, user_id: Uuid) -> bool {
+        let mut tables = self.write();
+        tables.comments.get(&id).is_some_and(|c| c.user_id == Some(user_id)) && tables.soft_delete_comment(id)
+    }
+
+    async fn delete_comment_admin(&self, id: i64) -> bool {
+        self.write().soft_delete_comment(id)
+    }
+
+    async fn delete_project_comment(&self, project_id: Uuid, id: i64, user_id: Option<Uuid>) -> bool {
+        let mut tables = self.write();
+        let allowed = tables
+            .comments
+            .get(&id)
+            .is_some_and(|c| c.project_id == project_id && user_id.is_none_or(|u| c.user_id == Some(u)));
+        allowed && tables.soft_delete_comment(id)
+    }
+
+    async fn get_notifications(&self, user_id: Uuid) -> Vec<NotificationResponse> {
+        let tables = self.read();
+        let mut notifications: Vec<NotificationResponse> = tables
+            .notifications
+            .iter()
+            .rev()
+            .filter(|n| n.user_id == user_id)
+            .filter_map(|n| {
+                let actor = tables.profiles.get(&n.actor_id)?;
+                let project = tables.projects.get(&n.project_id)?;
+                Some(NotificationResponse {
+                    id: n.id,
+                    actor_email: actor.email.clone(),
+                    project_id: n.project_id,
+                    project_title: project.title.clone(),
+                    notification_type: n.notification_type.clone(),
+                    message: n.message.clone(),
+                    is_read: n.is_read,
+                    created_at: n.created_at,
+                })
+            })
+            .collect();
+        notifications.sort_by_key(|n| std::cmp::Reverse(n.created_at));
+        notifications
+    }
+
+    async fn create_notification(&self, notification: NewNotification) -> Option<Notification> {
+        if notification.user_id == notification.actor_id {
+            return None;
+        }
+        let mut tables = self.write();
+        tables.notify(
+            notification.user_id,
+            notification.actor_id,
+            notification.project_id,
+            &notification.notification_type,
+            notification.message,
+        );
+        tables.notifications.last().cloned()
+    }
+
+    async fn mark_notification_read(&self, notification_id: Uuid, user_id: Uuid) -> bool {
+        let mut tables = self.write();
+        match tables.notifications.iter_mut().find(|n| n.id == notification_id && n.user_id == user_id) {
+            Some(notification) => {
+                notification.is_read = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn set_comments_locked(&self, id: Uuid, user_id: Uuid, locked: bool) -> Option<Project> {
+        self.update_row(id, Some(user_id), |p| p.comments_locked = locked)
+    }
+
+    async fn set_comments_locked_admin(&self, id: Uuid, locked: bool) -> Option<Project> {
+        self.update_row(id, None, |p| p.comments_locked = locked)
+    }
+
+    async fn lock_comments_for_year(&self, year: i32) -> i64 {
+        let mut tables = self.write();
+        let mut changed = 0;
+        for project in tables.projects.values_mut().filter(|p| p.year == year && !p.comments_locked) {
+            project.comments_locked = true;
+            changed += 1;
+        }
+        changed
+    }
+
+    async fn are_comments_locked(&self, project_id: Uuid) -> bool {
+        self.read().projects.get(&project_id).is_some_and(|p| p.comments_locked)
+    }
+
+    async fn mark_project_submitted(&self, id: Uuid) -> Option<Project> {
+        if self.read().projects.get(&id)?.status != ProjectStatus::Draft {
+            return None;
+        }
+        self.update_row(id, None, |p| {
+            let now = Utc::now();
+            p.submitted_at = Some(now);
+            p.status = ProjectStatus::Pending;
+            p.updated_at = now;
+        })
+    }
+
+    async fn resubmit_project(&self, id: Uuid, owner_id: Uuid, max_attempts: i32) -> Option<ResubmitOutcome> {
+        let mut tables = self.write();
+        let status = tables.projects.get(&id).filter(|p| p.user_id == owner_id)?.status;
+        if status != ProjectStatus::Rejected {
+            return Some(ResubmitOutcome::NotRejected(status));
+        }
+        let attempts = tables.resubmissions.get(&id).copied().unwrap_or(0);
+        if attempts >= max_attempts {
+            return Some(ResubmitOutcome::LimitReached);
+        }
+
+        let now = Utc::now();
+        let project = tables.projects.get_mut(&id)?;
+        project.status = ProjectStatus::Pending;
+        project.submitted_at = Some(now);
+        project.updated_at = now;
+        let project = project.clone();
+        tables.resubmissions.insert(id, attempts + 1);
+        let review = ProjectReview { project_id: id, status: ProjectStatus::Pending, reason: None, reviewed_at: now };
+        tables.reviews.push((owner_id, review));
+        let admins: Vec<Uuid> = tables
+            .profiles
+            .values()
+            .filter(|u| u.role == "admin" && u.id != owner_id)
+            .map(|u| u.id)
+            .collect();
+        let message = super::resubmission_message(attempts + 1, max_attempts);
+        for admin in admins {
+            tables.notify(admin, owner_id, id, "resubmission", Some(message.clone()));
+        }
+        Some(ResubmitOutcome::Resubmitted(Box::new(tables.with_computed(&project, false))))
+    }
+
+    async fn reassign_project_owner(&self, project_id: Uuid, new_owner_id: Uuid, actor_id: Uuid) -> Option<OwnerTransferOutcome> {
+        let mut tables = self.write();
+        let previous_owner = tables.projects.get(&project_id)?.user_id;
+        let Some(new_owner_email) = tables.profiles.get(&new_owner_id).map(|u| u.email.clone()) else {
+            return Some(OwnerTransferOutcome::UnknownOwner);
+        };
+        if previous_owner != new_owner_id {
+            let project = tables.projects.get_mut(&project_id)?;
+            project.user_id = new_owner_id;
+            project.updated_at = Utc::now();
+            let message = format!("Transferred to {new_owner_email} by an admin.");
+            tables.notify(previous_owner, actor_id, project_id, "ownership_change", Some(message));
+            tables.notify(new_owner_id, actor_id, project_id, "ownership_change", Some("Transferred to you by an admin.".to_string()));
+        }
+        let project = tables.with_computed(tables.projects.get(&project_id)?, false);
+        Some(OwnerTransferOutcome::Transferred { project: Box::new(project), previous_owner })
+    }
+
+    async fn get_supervisor_report(&self, year: Option<i32>) -> Vec<SupervisorStats> {
+        const UNASSIGNED: &str = "Unassigned";
+        let tables = self.read();
+        let mut groups: BTreeMap<String, Vec<&Project>> = BTreeMap::new();
+        for project in tables.projects.values().filter(|p| year.is_none_or(|y| p.year == y)) {
+            let supervisor = project
+                .supervisor
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .unwrap_or(UNASSIGNED);
+            groups.entry(supervisor.to_string()).or_default().push(project);
+        }
+        let mut report: Vec<SupervisorStats> = groups
+            .into_iter()
+            .map(|(supervisor, projects)| {
+                let likes: i64 = projects.iter().map(|p| tables.like_count(p.id)).sum();
+                SupervisorStats {
+                    project_count: projects.len() as i64,
+                    public_count: projects.iter().filter(|p| p.is_public).count() as i64,
+                    avg_likes: likes as f64 / projects.len() as f64,
+                    total_comments: projects
+                        .iter()
+                        .map(|p| tables.comments.values().filter(|c| c.project_id == p.id && !c.is_deleted).count() as i64)
+                        .sum(),
+                    supervisor,
+                }
+            })
+            .collect();
+        report.sort_by_key(|row| row.supervisor == UNASSIGNED);
+        report
+    }
+
+    async fn notify_status_change(&self, project_id: Uuid, actor_id: Uuid, reason: Option<String>) -> bool {
+        let mut tables = self.write();
+        match tables.projects.get(&project_id).map(|p| p.user_id) {
+            Some(owner) if owner != actor_id => {
+                tables.notify(owner, actor_id, project_id, "status_change", reason);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    async fn record_project_review(
+        &self,
+        project_id: Uuid,
+        reviewer_id: Uuid,
+        status: ProjectStatus,
+        reason: Option<String>,
+    ) -> bool {
+        let mut tables = self.write();
+        if !tables.projects.contains_key(&project_id) {
+            return false;
+        }
+        let review = ProjectReview { project_id, status, reason, reviewed_at: Utc::now() };
+        tables.reviews.push((reviewer_id, review));
+        true
+    }
+
+    async fn get_latest_reviews(&self, owner_id: Uuid) -> Vec<ProjectReview> {
+        let tables = self.read();
+        let mut latest: BTreeMap<Uuid, &ProjectReview> = BTreeMap::new();
+        for (_, review) in &tables.reviews {
+            if tables.projects.get(&review.project_id).is_some_and(|p| p.user_id == owner_id) {
+                latest.insert(review.project_id, review);
+            }
+        }
+        latest.into_values().cloned().collect()
+    }
+
+    async fn set_subscription(&self, project_id: Uuid, user_id: Uuid, state: SubscriptionState) -> bool {
+        let mut tables = self.write();
+        if !tables.projects.contains_key(&project_id) {
+            return false;
+        }
+        tables.subscriptions.insert((project_id, user_id), state);
+        true
+    }
+
+    async fn get_subscription(&self, project_id: Uuid, user_id: Uuid) -> Option<SubscriptionState> {
+        let tables = self.read();
+        if let Some(state) = tables.subscriptions.get(&(project_id, user_id)) {
+            return Some(*state);
+        }
+        let owner = tables.projects.get(&project_id).is_some_and(|p| p.user_id == user_id);
+        let commented = tables.comments.values().any(|c| c.project_id == project_id && c.user_id == Some(user_id));
+        (owner || commented).then_some(SubscriptionState::Subscribed)
+    }
+
+    async fn record_upload(
+        &self,
+        user_id: Uuid,
+        object_key: &str,
+        purpose: UploadPurpose,
+        expires_at: DateTime<Utc>,
+    ) -> bool {
+        let mut tables = self.write();
+        if tables.uploads.iter().any(|u| u.object_key == object_key) {
+            return false;
+        }
+        tables.uploads.push(Upload {
+            user_id,
+            object_key: object_key.to_string(),
+            purpose,
+            expires_at,
+            created_at: Utc::now(),
+        });
+        true
+    }
+
+    async fn get_upload_counts(&self, user_id: Uuid, purpose: UploadPurpose) -> UploadCounts {
+        let tables = self.read();
+        let now = Utc::now();
+        let recent: Vec<&Upload> = tables
+            .uploads
+            .iter()
+            .filter(|u| u.user_id == user_id && u.created_at > now - chrono::Duration::hours(1))
+            .collect();
+        let referenced = |key: &str| {
+            tables.projects.values().any(|p| {
+                p.cover_image == key || p.video.as_deref() == Some(key) || p.report.as_deref() == Some(key)
+            })
+        };
+        UploadCounts {
+            last_hour: recent.len() as i64,
+            outstanding: recent
+                .iter()
+                .filter(|u| u.purpose == purpose && u.expires_at > now && !referenced(&u.object_key))
+                .count() as i64,
+        }
+    }
+
+    async fn is_own_upload(&self, user_id: Uuid, object_key: &str, purpose: UploadPurpose) -> bool {
+        self.read()
+            .uploads
+            .iter()
+            .any(|u| u.user_id == user_id && u.object_key == object_key && u.purpose == purpose)
+    }
+
+    async fn get_programmes(&self) -> Vec<Programme> {
+        self.read().programmes.values().cloned().collect()
+    }
+
+    async fn create_programme(&self, code: &str, name: &str) -> Option<Programme> {
+        // The `programmes.code` CHECK constraint: `^[A-Z0-9-]{2,16}$`.
+        let valid = (2..=16).contains(&code.len())
+            && code.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-');
+        let mut tables = self.write();
+        if !valid || tables.programmes.contains_key(code) {
+            return None;
+        }
+        let programme = Programme { code: code.to_string(), name: name.to_string() };
+        tables.programmes.insert(programme.code.clone(), programme.clone());
+        Some(programme)
+    }
+
+    async fn unknown_programme_codes(&self, codes: &[String]) -> Vec<String> {
+        let tables = self.read();
+        codes.iter().filter(|c| !tables.programmes.contains_key(*c)).cloned().collect()
+    }
+
+    async fn get_project_programmes(&self, project_id: Uuid) -> Vec<String> {
+        self.read()
+            .project_programmes
+            .get(&project_id)
+            .map(|codes| codes.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    async fn get_tag_counts(&self) -> Vec<TagCount> {
+        let tables = self.read();
+        let mut counts: BTreeMap<&String, i64> = BTreeMap::new();
+        for (project_id, tags) in &tables.project_tags {
+            if tables.is_public(*project_id) {
+                for tag in tags {
+                    *counts.entry(tag).or_default() += 1;
+                }
+            }
+        }
+        let mut counts: Vec<TagCount> = counts
+            .into_iter()
+            .map(|(tag, count)| TagCount { tag: tag.clone(), count })
+            .collect();
+        // Stable on the alphabetical order above, so ties stay alphabetical.
+        counts.sort_by_key(|c| std::cmp::Reverse(c.count));
+        counts
+    }
+
+    async fn get_project_years(&self) -> Vec<YearCount> {
+        let tables = self.read();
+        let mut counts: BTreeMap<i32, i64> = BTreeMap::new();
+        for project in tables.projects.values().filter(|p| p.is_public) {
+            *counts.entry(project.year).or_default() += 1;
+        }
+        counts.into_iter().rev().map(|(year, count)| YearCount { year, count }).collect()
+    }
+
+    async fn get_department_counts(&self) -> Vec<DepartmentCount> {
+        let tables = self.read();
+        let mut counts: BTreeMap<&str, i64> = BTreeMap::new();
+        for department in tables.projects.values().filter(|p| p.is_public).filter_map(|p| p.department.as_deref()) {
+            *counts.entry(department).or_default() += 1;
+        }
+        let mut counts: Vec<DepartmentCount> = counts
+            .into_iter()
+            .map(|(department, count)| DepartmentCount { department: department.to_string(), count })
+            .collect();
+        // Stable sort: ties stay in name order, as in the SQL.
+        counts.sort_by_key(|c| std::cmp::Reverse(c.count));
+        counts
+    }
+
+    async fn get_technology_counts(&self) -> Vec<TechnologyCount> {
+        let tables = self.read();
+        let mut counts: BTreeMap<&str, i64> = BTreeMap::new();
+        for technology in tables.projects.values().filter(|p| p.is_public).flat_map(|p| &p.technologies) {
+            *counts.entry(technology).or_default() += 1;
+        }
+        let mut counts: Vec<TechnologyCount> = counts
+            .into_iter()
+            .map(|(technology, count)| TechnologyCount { technology: technology.to_string(), count })
+            .collect();
+        counts.sort_by_key(|c| std::cmp::Reverse(c.count));
+        counts
+    }
+
+    async fn add_feedback(&self, project_id: Uuid, message: &str, contact_email: Option<&str>) -> Option<ProjectFeedback> {
+        let mut tables = self.write();
+        if !tables.is_public(project_id) {
+            return None;
+        }
+        let owner = tables.projects[&project_id].user_id;
+        tables.last_feedback_id += 1;
+        let feedback = ProjectFeedback {
+            id: tables.last_feedback_id,
+            project_id,
+            message: message.to_string(),
+            contact_email: contact_email.map(str::to_string),
+            created_at: Utc::now(),
+        };
+        tables.feedback.push(feedback.clone());
+        tables.notify(owner, owner, project_id, "feedback", None);
+        Some(feedback)
+    }
+
+    async fn get_feedback(&self, project_id: Option<Uuid>) -> Vec<ProjectFeedback> {
+        self.read()
+            .feedback
+            .iter()
+            .rev()
+            .filter(|f| project_id.is_none_or(|id| f.project_id == id))
+            .cloned()
+            .collect()
+    }
+
+    async fn get_settings(&self) -> Result<Vec<Setting>, RepositoryError> {
+        Ok(self.read().settings.values().cloned().collect())
+    }
+
+    async fn put_setting(&self, key: &str, value: serde_json::Value, updated_by: Uuid) -> Option<Setting> {
+        let setting = Setting {
+            key: key.to_string(),
+            value,
+            updated_at: Some(Utc::now()),
+            updated_by: Some(updated_by),
+        };
+        self.write().settings.insert(setting.key.clone(), setting.clone());
+        Some(setting)
+    }
+
+    async fn get_stale_drafts(&self, sweep: DraftSweep, untouched_before: DateTime<Utc>) -> Vec<StaleDraft> {
+        let tables = self.read();
+        let mut drafts: Vec<StaleDraft> = tables
+            .projects
+            .values()
+            .filter(|p| tables.is_draft(p.id) && p.updated_at < untouched_before)
+            .filter(|p| !tables.draft_sweeps.contains_key(&(p.id, sweep)))
+            .map(|p| StaleDraft {
+                project_id: p.id,
+                owner_id: p.user_id,
+                title: p.title.clone(),
+                updated_at: p.updated_at,
+            })
+            .collect();
+        drafts.sort_by_key(|d| (d.updated_at, d.project_id));
+        drafts
+    }
+
+    async fn mark_stale_draft(&self, sweep: DraftSweep, project_id: Uuid, at: DateTime<Utc>) -> bool {
+        let mut tables = self.write();
+        if !tables.is_draft(project_id) || tables.draft_sweeps.contains_key(&(project_id, sweep)) {
+            return false;
+        }
+        tables.draft_sweeps.insert((project_id, sweep), at);
+        if sweep == DraftSweep::Reminder {
+            let owner = tables.projects[&project_id].user_id;
+            tables.notify(owner, owner, project_id, "draft_reminder", None);
+        }
+        true
+    }
+
+    async fn get_projects_outside_years(&self, min: i32, max: i32) -> Vec<InvalidYearProject> {
+        let mut projects: Vec<InvalidYearProject> = self
+            .read()
+            .projects
+            .values()
+            .filter(|p| !(min..=max).contains(&p.year))
+            .map(|p| InvalidYearProject {
+                project_id: p.id,
+                owner_id: p.user_id,
+                title: p.title.clone(),
+                year: p.year,
+            })
+            .collect();
+        projects.sort_by(|a, b| (a.year, &a.title, a.project_id).cmp(&(b.year, &b.title, b.project_id)));
+        projects
+    }
+
+    async fn remap_project_year(&self, from: i32, to: i32) -> i64 {
+        let mut tables = self.write();
+        let mut moved: Vec<(Uuid, String)> =
+            tables.projects.values().filter(|p| p.year == from).map(|p| (p.id, p.title.clone())).collect();
+        moved.sort();
+        for (id, title) in &moved {
+            let slug = tables.assign_slug(*id, title, to);
+            if let Some(project) = tables.projects.get_mut(id) {
+                project.year = to;
+                project.slug = slug;
+                project.updated_at = Utc::now();
+            }
+        }
+        moved.len() as i64
+    }
+
+    async fn record_audit(&self, entry: &NewAuditEntry) -> Result<(), RepositoryError> {
+        self.write().insert_audit(entry.clone(), Utc::now());
+        Ok(())
+    }
+
+    async fn queue_audit(&self, entry: &NewAuditEntry) -> Result<i64, RepositoryError> {
+        let payload = serde_json::to_value(entry).map_err(|e| RepositoryError(e.to_string()))?;
+        let mut tables = self.write();
+        let id = tables.next_outbox_id();
+        let now = Utc::now();
+        let job = OutboxJob {
+            id,
+            kind: "audit_entry".to_string(),
+            status: "pending".to_string(),
+            payload,
+            total: 1,
+            processed: 0,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        };
+        tables.audit_outbox.insert(id, job);
+        Ok(id)
+    }
+
+    async fn get_audit_log(&self, filter: &AuditFilter, page: PageRequest) -> (Vec<AuditEntry>, i64) {
+        let matching = self.audit_entries(filter);
+        let total = matching.len() as i64;
+        let items = matching
+            .into_iter()
+            .skip(page.offset() as usize)
+            .take(page.per_page as usize)
+            .collect();
+        (items, total)
+    }
+
+    fn stream_audit_log(&self, filter: AuditFilter) -> BoxStream<'static, Result<AuditEntry, RepositoryError>> {
+        futures::stream::iter(self.audit_entries(&filter).into_iter().map(Ok)).boxed()
+    }
+
+    async fn purge_audit_log(&self, before: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        let mut tables = self.write();
+        let kept = tables.audit_log.len();
+        tables.audit_log.retain(|e| e.created_at >= before);
+        Ok((kept - tables.audit_log.len()) as u64)
+    }
+
+    async fn run_fanout_batch(&self, batch_size: i64) -> Option<OutboxJob> {
+        let mut tables = self.write();
+        let (&id, fanout) = tables.outbox.iter().find(|(_, f)| f.job.status == "pending")?;
+        let batch: Vec<Uuid> = tables
+            .comment_recipients(fanout.project_id, fanout.actor_id)
+            .into_iter()
+            .filter(|user_id| fanout.cursor.is_none_or(|cursor| *user_id > cursor))
+            .take(batch_size.max(0) as usize)
+            .collect();
+        let (project_id, actor_id) = (fanout.project_id, fanout.actor_id);
+        for recipient in &batch {
+            tables.notify(*recipient, actor_id, project_id, "comment", None);
+        }
+        let fanout = tables.outbox.get_mut(&id)?;
+        let now = Utc::now();
+        fanout.job.processed += batch.len() as i64;
+        fanout.job.updated_at = now;
+        fanout.cursor = batch.last().copied().or(fanout.cursor);
+        if (batch.len() as i64) < batch_size {
+            fanout.job.status = "done".to_string();
+            fanout.job.completed_at = Some(now);
+        }
+        Some(fanout.job.clone())
+    }
+
+    async fn get_outbox_job(&self, id: i64) -> Option<OutboxJob> {
+        let tables = self.read();
+        tables.outbox.get(&id).map(|f| f.job.clone()).or_else(|| tables.audit_outbox.get(&id).cloned())
+    }
+
+    async fn flush_audit_outbox(&self) -> Result<u64, RepositoryError> {
+        let mut tables = self.write();
+        let pending: Vec<i64> = tables.audit_outbox.iter().filter(|(_, job)| job.status == "pending").map(|(id, _)| *id).collect();
+        let now = Utc::now();
+        for id in &pending {
+            let job = tables.audit_outbox.get_mut(id).expect("pending job");
+            let entry: NewAuditEntry = serde_json::from_value(job.payload.clone()).map_err(|e| RepositoryError(e.to_string()))?;
+            job.status = "done".to_string();
+            job.processed = 1;
+            job.updated_at = now;
+            job.completed_at = Some(now);
+            let created_at = job.created_at;
+            tables.insert_audit(entry, created_at);
+        }
+        Ok(pending.len() as u64)
+    }
+
+    async fn ping(&self) -> Result<(), RepositoryError> {
+        Ok(())
+    }
+}
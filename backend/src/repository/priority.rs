@@ -0,0 +1,780 @@
+use super::{AdminCommentFilter, AuditFilter, CommentCursor, DraftSweep, HiddenComments, ProjectListFilter, ProjectViewer, Repository, RepositoryError};
+use crate::metrics::DB_PERMIT_QUEUE_DEPTH;
+use crate::models::{
+    ActivityItem, AdminDashboardStats, AuditEntry, NewAuditEntry, NewNotification, Notification, BulkStatusOutcome, Comment, CommentEditOutcome, CommentOrder, CreateProjectRequest, DepartmentCount, InvalidYearProject, ProjectOwner, OwnerTransferOutcome, ProjectStats, PendingReview, TechnologyCount, Like, LikeOutcome, MyProjectSummary, OutboxJob, PostedComment, Programme, Project,
+    ProjectExportRow, ProjectFeedback, ProjectLiker, ProjectReview, ProjectStatus, ProjectUpdateResult, PublicEvent, PublicStats, ReportOutcome, ReportedComment, AdminComment, ResubmitOutcome, StaleDraft, SubscriptionState, SupervisorStats, Setting, TagCount, UpdateCommentRequest, UpdateProjectRequest,
+    UploadCounts, UploadPurpose, RecentComments, User, YearCount,
+};
+use crate::pagination::PageRequest;
+use async_trait::async_trait;
+use axum::{extract::Request, middleware::Next, response::Response};
+use futures::{StreamExt, stream::BoxStream};
+use std::{
+    cell::Cell,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use uuid::Uuid;
+
+/// Roles whose requests may use the reserved database permits.
+pub const PRIVILEGED_ROLES: [&str; 2] = ["admin", "moderator"];
+
+/// `Lookup` calls that may hold a reserved permit at the same time.
+pub const LOOKUP_RESERVED_BUDGET: usize = 1;
+
+tokio::task_local! {
+    // The class of the current request; only set inside `with_class`.
+    static REQUEST_CLASS: Cell<RequestClass>;
+}
+
+/// RequestClass
+///
+/// Which database permits a repository call may take. Everything starts `Public`; the
+/// `AuthUser` extractor upgrades a request once it has resolved an admin or moderator role.
+/// The extractor's own profile lookup runs as `Lookup`: the role is not known yet, so it may
+/// only borrow a reserved permit within `LOOKUP_RESERVED_BUDGET`, and a flood of valid student
+/// tokens cannot take them all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestClass {
+    Public,
+    Privileged,
+    Lookup,
+}
+
+impl RequestClass {
+    pub fn for_role(role: &str) -> Self {
+        if PRIVILEGED_ROLES.contains(&role) {
+            RequestClass::Privileged
+        } else {
+            RequestClass::Public
+        }
+    }
+
+    /// The `class` label of the `db_permit_queue_depth` metric.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RequestClass::Public => "public",
+            RequestClass::Privileged => "privileged",
+            RequestClass::Lookup => "lookup",
+        }
+    }
+
+    /// The class of the running request; `Public` outside one (startup, background tasks).
+    pub fn current() -> Self {
+        REQUEST_CLASS.try_with(Cell::get).unwrap_or(RequestClass::Public)
+    }
+}
+
+/// Runs `future` as a request of `class`. Nested calls only affect the inner future.
+pub async fn with_class<F: Future>(class: RequestClass, future: F) -> F::Output {
+    REQUEST_CLASS.scope(Cell::new(class), future).await
+}
+
+/// note_role
+///
+/// Upgrades the running request to `Privileged` if `role` is one of `PRIVILEGED_ROLES`.
+/// Called by the `AuthUser` extractor; never downgrades, and does nothing outside a request.
+pub fn note_role(role: &str) {
+    if RequestClass::for_role(role) == RequestClass::Privileged {
+        let _ = REQUEST_CLASS.try_with(|class| class.set(RequestClass::Privileged));
+    }
+}
+
+/// request_class
+///
+/// Middleware opening the per-request class scope (starting as `Public`) that `note_role`
+/// upgrades and `PriorityRepository` reads. Work moved to other tasks runs as `Public`.
+pub async fn request_class(request: Request, next: Next) -> Response {
+    with_class(RequestClass::Public, next.run(request)).await
+}
+
+/// PriorityGate
+///
+/// Two-class admission in front of the connection pool: `total` permits, of which `reserved`
+/// are only handed to privileged calls. Public calls queue for the shared permits; privileged
+/// calls take a shared permit when one is free, a reserved one otherwise, and wait for
+/// whichever frees first. Lookup calls do the same, but only `LOOKUP_RESERVED_BUDGET` of them
+/// hold a reserved permit at once. Waiters of each class are counted in `db_permit_queue_depth`.
+pub struct PriorityGate {
+    shared: Arc<Semaphore>,
+    reserved: Arc<Semaphore>,
+    lookups: Arc<Semaphore>,
+    waiting: [AtomicUsize; 3],
+}
+
+/// AcquireGuard
+///
+/// A database permit held for the duration of one repository call (or one export stream);
+/// released on drop, together with the lookup budget it used, if any.
+pub struct AcquireGuard {
+    _permit: OwnedSemaphorePermit,
+    _budget: Option<OwnedSemaphorePermit>,
+}
+
+// Counts one waiter of a class for as long as it is alive, so cancelled waits are undone too.
+struct QueueSlot<'a> {
+    counter: &'a AtomicUsize,
+    class: RequestClass,
+}
+
+impl<'a> QueueSlot<'a> {
+    fn enter(counter: &'a AtomicUsize, class: RequestClass) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        DB_PERMIT_QUEUE_DEPTH.add(class.as_str(), 1);
+        Self { counter, class }
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::Relaxed);
+        DB_PERMIT_QUEUE_DEPTH.add(self.class.as_str(), -1);
+    }
+}
+
+impl PriorityGate {
+    /// `reserved` is capped so at least one permit stays shared.
+    pub fn new(total: usize, reserved: usize) -> Self {
+        let reserved = reserved.min(total.saturating_sub(1));
+        Self {
+            shared: Arc::new(Semaphore::new(total - reserved)),
+            reserved: Arc::new(Semaphore::new(reserved)),
+            lookups: Arc::new(Semaphore::new(LOOKUP_RESERVED_BUDGET)),
+            waiting: Default::default(),
+        }
+    }
+
+    /// Calls of `class` currently waiting for a permit.
+    pub fn queue_depth(&self, class: RequestClass) -> usize {
+        self.waiting[class as usize].load(Ordering::Relaxed)
+    }
+
+    pub async fn acquire(&self, class: RequestClass) -> AcquireGuard {
+        let shared = || self.shared.clone().try_acquire_owned().ok();
+        let ready = match class {
+            RequestClass::Public => shared().map(|permit| (permit, None)),
+            RequestClass::Privileged => shared()
+                .or_else(|| self.reserved.clone().try_acquire_owned().ok())
+                .map(|permit| (permit, None)),
+            RequestClass::Lookup => shared().map(|permit| (permit, None)).or_else(|| {
+                let budget = self.lookups.clone().try_acquire_owned().ok()?;
+                let permit = self.reserved.clone().try_acquire_owned().ok()?;
+                Some((permit, Some(budget)))
+            }),
+        };
+        if let Some((permit, budget)) = ready {
+            return AcquireGuard { _permit: permit, _budget: budget };
+        }
+
+        let _slot = QueueSlot::enter(&self.waiting[class as usize], class);
+        let reserved_within_budget = async {
+            let budget = self.lookups.clone().acquire_owned().await?;
+            let permit = self.reserved.clone().acquire_owned().await?;
+            Ok((permit, Some(budget)))
+        };
+        let (permit, budget) = match class {
+            RequestClass::Public => self.shared.clone().acquire_owned().await.map(|permit| (permit, None)),
+            RequestClass::Privileged => tokio::select! {
+                permit = self.shared.clone().acquire_owned() => permit.map(|permit| (permit, None)),
+                permit = self.reserved.clone().acquire_owned() => permit.map(|permit| (permit, None)),
+            },
+            RequestClass::Lookup => tokio::select! {
+                permit = self.shared.clone().acquire_owned() => permit.map(|permit| (permit, None)),
+                permit = reserved_within_budget => permit,
+            },
+        }
+        .expect("priority gate semaphores are never closed");
+        AcquireGuard { _permit: permit, _budget: budget }
+    }
+}
+
+/// PriorityRepository
+///
+/// A `Repository` decorator that makes every call hold a `PriorityGate` permit of the current
+/// `RequestClass`, so public reads queue first when the pool is saturated and admin moderation
+/// still gets a connection. `main` sizes it like the Postgres pool. The export streams hold
+/// their connection until they end, so each holds a shared permit as long (see `gated`).
+pub struct PriorityRepository<R: ?Sized = dyn Repository> {
+    inner: Arc<R>,
+    gate: Arc<PriorityGate>,
+}
+
+impl<R: Repository + ?Sized> PriorityRepository<R> {
+    pub fn new(inner: Arc<R>, total: usize, reserved: usize) -> Self {
+        Self {
+            inner,
+            gate: Arc::new(PriorityGate::new(total, reserved)),
+        }
+    }
+
+    pub fn gate(&self) -> &Arc<PriorityGate> {
+        &self.gate
+    }
+
+    async fn acquire(&self) -> AcquireGuard {
+        self.gate.acquire(RequestClass::current()).await
+    }
+}
+
+/// gated
+///
+/// Makes `stream` wait for a shared permit before its first item and hold it until dropped.
+/// Streams outlive the request scope and may run for minutes, so they never take a reserved
+/// permit, whoever started them: a long export must not shut moderation out.
+fn gated<T: Send + 'static>(gate: Arc<PriorityGate>, stream: BoxStream<'static, T>) -> BoxStream<'static, T> {
+    futures::stream::once(async move {
+        let permit = gate.acquire(RequestClass::Public).await;
+        stream.map(move |item| {
+            let _held = &permit;
+            item
+        })
+    })
+    .flatten()
+    .boxed()
+}
+
+#[async_trait]
+impl<R: Repository + ?Sized> Repository for PriorityRepository<R> {
+    async fn get_projects(
+        &self,
+        filter: ProjectListFilter,
+        page: PageRequest,
+    ) -> (Vec<Project>, i64) {
+        let _permit = self.acquire().await;
+        self.inner.get_projects(filter, page).await
+    }
+
+    async fn get_all_projects(&self) -> Vec<Project> {
+        let _permit = self.acquire().await;
+        self.inner.get_all_projects().await
+    }
+
+    async fn get_pending_reviews(&self, limit: i64) -> Vec<PendingReview> {
+        let _permit = self.acquire().await;
+        self.inner.get_pending_reviews(limit).await
+    }
+
+    fn stream_all_projects(&self) -> BoxStream<'static, Result<Project, RepositoryError>> {
+        gated(self.gate.clone(), self.inner.stream_all_projects())
+    }
+
+    fn stream_project_export(&self) -> BoxStream<'static, Result<ProjectExportRow, RepositoryError>> {
+        gated(self.gate.clone(), self.inner.stream_project_export())
+    }
+
+    async fn get_top_projects(&self, limit: i64, min_likes: i64, year: Option<i32>) -> Vec<Project> {
+        let _permit = self.acquire().await;
+        self.inner.get_top_projects(limit, min_likes, year).await
+    }
+
+    async fn get_year_archive(&self, year: i32) -> Vec<Project> {
+        let _permit = self.acquire().await;
+        self.inner.get_year_archive(year).await
+    }
+
+    async fn get_random_public_project(&self, seed: Option<i64>) -> Option<Project> {
+        let _permit = self.acquire().await;
+        self.inner.get_random_public_project(seed).await
+    }
+
+    async fn get_project(&self, id: Uuid) -> Option<Project> {
+        let _permit = self.acquire().await;
+        self.inner.get_project(id).await
+    }
+
+    async fn get_project_authorized(&self, id: Uuid, user_id: Uuid) -> Option<Project> {
+        let _permit = self.acquire().await;
+        self.inner.get_project_authorized(id, user_id).await
+    }
+
+    async fn get_public_project(&self, id: Uuid) -> Option<Project> {
+        let _permit = self.acquire().await;
+        self.inner.get_public_project(id).await
+    }
+
+    async fn get_project_detail(&self, id: Uuid, viewer: ProjectViewer) -> Option<(Project, Option<ProjectOwner>)> {
+        let _permit = self.acquire().await;
+        self.inner.get_project_detail(id, viewer).await
+    }
+
+    async fn resolve_project_slug(&self, slug: &str) -> Option<Uuid> {
+        let _permit = self.acquire().await;
+        self.inner.resolve_project_slug(slug).await
+    }
+
+    async fn find_project_by_title_year(&self, title: &str, year: i32) -> Option<Uuid> {
+        let _permit = self.acquire().await;
+        self.inner.find_project_by_title_year(title, year).await
+    }
+
+    async fn count_user_projects_in_year(&self, user_id: Uuid, year: i32) -> i64 {
+        let _permit = self.acquire().await;
+        self.inner.count_user_projects_in_year(user_id, year).await
+    }
+
+    async fn get_changed_project_ids(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        ids: Option<Vec<Uuid>>,
+    ) -> Vec<Uuid> {
+        let _permit = self.acquire().await;
+        self.inner.get_changed_project_ids(since, ids).await
+    }
+
+    async fn get_public_events(&self, since: chrono::DateTime<chrono::Utc>, limit: i64) -> Vec<PublicEvent> {
+        let _permit = self.acquire().await;
+        self.inner.get_public_events(since, limit).await
+    }
+
+    async fn increment_view(&self, id: Uuid) -> Option<i64> {
+        let _permit = self.acquire().await;
+        self.inner.increment_view(id).await
+    }
+
+    async fn get_like_count(&self, id: Uuid) -> Option<i64> {
+        let _permit = self.acquire().await;
+        self.inner.get_like_count(id).await
+    }
+
+    async fn create_project(&self, req: CreateProjectRequest, user_id: Uuid) -> Project {
+        let _permit = self.acquire().await;
+        self.inner.create_project(req, user_id).await
+    }
+
+    async fn like_project(&self, like: Like) -> Option<LikeOutcome> {
+        let _permit = self.acquire().await;
+        self.inner.like_project(like).await
+    }
+
+    async fn unlike_project(&self, like: Like) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.unlike_project(like).await
+    }
+
+    async fn get_liked_project_ids(&self, user_id: Uuid, project_ids: &[Uuid]) -> Vec<Uuid> {
+        let _permit = self.acquire().await;
+        self.inner.get_liked_project_ids(user_id, project_ids).await
+    }
+
+    async fn get_project_likers(&self, project_id: Uuid, page: PageRequest) -> (Vec<ProjectLiker>, i64) {
+        let _permit = self.acquire().await;
+        self.inner.get_project_likers(project_id, page).await
+    }
+
+    async fn set_project_status(&self, id: Uuid, status: ProjectStatus) -> Option<Project> {
+        let _permit = self.acquire().await;
+        self.inner.set_project_status(id, status).await
+    }
+
+    async fn set_projects_status_bulk(&self, ids: &[Uuid], is_public: bool) -> BulkStatusOutcome {
+        let _permit = self.acquire().await;
+        self.inner.set_projects_status_bulk(ids, is_public).await
+    }
+
+    async fn get_user(&self, id: Uuid) -> Option<User> {
+        let _permit = self.acquire().await;
+        self.inner.get_user(id).await
+    }
+
+    async fn create_user(&self, user: User) -> User {
+        let _permit = self.acquire().await;
+        self.inner.create_user(user).await
+    }
+
+    async fn get_users_by_email_prefix(&self, prefixes: &[String]) -> Vec<User> {
+        let _permit = self.acquire().await;
+        self.inner.get_users_by_email_prefix(prefixes).await
+    }
+
+    async fn get_stats(&self) -> AdminDashboardStats {
+        let _permit = self.acquire().await;
+        self.inner.get_stats().await
+    }
+
+    async fn get_public_stats(&self) -> PublicStats {
+        let _permit = self.acquire().await;
+        self.inner.get_public_stats().await
+    }
+
+    async fn get_my_project_summaries(&self, user_id: Uuid) -> Vec<MyProjectSummary> {
+        let _permit = self.acquire().await;
+        self.inner.get_my_project_summaries(user_id).await
+    }
+
+    async fn delete_project(&self, id: Uuid, user_id: Uuid) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.delete_project(id, user_id).await
+    }
+
+    async fn update_project(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        req: UpdateProjectRequest,
+    ) -> Option<ProjectUpdateResult> {
+        let _permit = self.acquire().await;
+        self.inner.update_project(id, user_id, req).await
+    }
+
+    async fn add_comment(&self, project_id: Uuid, user_id: Uuid, text: String, fanout_threshold: i64) -> Result<Option<PostedComment>, RepositoryError> {
+        let _permit = self.acquire().await;
+        self.inner.add_comment(project_id, user_id, text, fanout_threshold).await
+    }
+
+    async fn get_comments(
+        &self,
+        project_id: Uuid,
+        order: CommentOrder,
+        hidden: HiddenComments,
+    ) -> Vec<Comment> {
+        let _permit = self.acquire().await;
+        self.inner.get_comments(project_id, order, hidden).await
+    }
+
+    async fn get_comments_before(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        before: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        let _permit = self.acquire().await;
+        self.inner.get_comments_before(project_id, hidden, before, limit).await
+    }
+
+    async fn get_comments_after(
+        &self,
+        project_id: Uuid,
+        hidden: HiddenComments,
+        after: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        let _permit = self.acquire().await;
+        self.inner.get_comments_after(project_id, hidden, after, limit).await
+    }
+
+    async fn get_recent_comments(&self, user_id: Uuid) -> RecentComments {
+        let _permit = self.acquire().await;
+        self.inner.get_recent_comments(user_id).await
+    }
+
+    async fn count_comments(&self, project_id: Uuid) -> Option<i64> {
+        let _permit = self.acquire().await;
+        self.inner.count_comments(project_id).await
+    }
+
+    async fn create_report(
+        &self,
+        project_id: Option<Uuid>,
+        comment_id: i64,
+        reporter_id: Uuid,
+        reason: Option<String>,
+        threshold: i64,
+    ) -> Option<ReportOutcome> {
+        let _permit = self.acquire().await;
+        self.inner.create_report(project_id, comment_id, reporter_id, reason, threshold).await
+    }
+
+    async fn restore_comment(&self, comment_id: i64) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.restore_comment(comment_id).await
+    }
+
+    async fn get_reported_comments(&self) -> Vec<ReportedComment> {
+        let _permit = self.acquire().await;
+        self.inner.get_reported_comments().await
+    }
+
+    async fn get_admin_comments(&self, filter: &AdminCommentFilter, limit: i64, offset: i64) -> Vec<AdminComment> {
+        let _permit = self.acquire().await;
+        self.inner.get_admin_comments(filter, limit, offset).await
+    }
+
+    async fn delete_project_admin(&self, id: Uuid) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.delete_project_admin(id).await
+    }
+
+    async fn update_comment(&self, id: i64, user_id: Uuid, req: UpdateCommentRequest) -> Option<CommentEditOutcome> {
+        let _permit = self.acquire().await;
+        self.inner.update_comment(id, user_id, req).await
+    }
+
+    async fn delete_comment(&self, id: i64, user_id: Uuid) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.delete_comment(id, user_id).await
+    }
+
+    async fn delete_comment_admin(&self, id: i64) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.delete_comment_admin(id).await
+    }
+
+    async fn delete_project_comment(&self, project_id: Uuid, id: i64, user_id: Option<Uuid>) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.delete_project_comment(project_id, id, user_id).await
+    }
+
+    async fn get_notifications(&self, user_id: Uuid) -> Vec<crate::models::NotificationResponse> {
+        let _permit = self.acquire().await;
+        self.inner.get_notifications(user_id).await
+    }
+
+    async fn mark_notification_read(&self, notification_id: Uuid, user_id: Uuid) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.mark_notification_read(notification_id, user_id).await
+    }
+
+    async fn create_notification(&self, notification: NewNotification) -> Option<Notification> {
+        let _permit = self.acquire().await;
+        self.inner.create_notification(notification).await
+    }
+
+    async fn set_comments_locked(&self, id: Uuid, user_id: Uuid, locked: bool) -> Option<Project> {
+        let _permit = self.acquire().await;
+        self.inner.set_comments_locked(id, user_id, locked).await
+    }
+
+    async fn set_comments_locked_admin(&self, id: Uuid, locked: bool) -> Option<Project> {
+        let _permit = self.acquire().await;
+        self.inner.set_comments_locked_admin(id, locked).await
+    }
+
+    async fn lock_comments_for_year(&self, year: i32) -> i64 {
+        let _permit = self.acquire().await;
+        self.inner.lock_comments_for_year(year).await
+    }
+
+    async fn are_comments_locked(&self, project_id: Uuid) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.are_comments_locked(project_id).await
+    }
+
+    async fn mark_project_submitted(&self, id: Uuid) -> Option<Project> {
+        let _permit = self.acquire().await;
+        self.inner.mark_project_submitted(id).await
+    }
+
+    async fn resubmit_project(&self, id: Uuid, owner_id: Uuid, max_attempts: i32) -> Option<ResubmitOutcome> {
+        let _permit = self.acquire().await;
+        self.inner.resubmit_project(id, owner_id, max_attempts).await
+    }
+
+    async fn reassign_project_owner(&self, project_id: Uuid, new_owner_id: Uuid, actor_id: Uuid) -> Option<OwnerTransferOutcome> {
+        let _permit = self.acquire().await;
+        self.inner.reassign_project_owner(project_id, new_owner_id, actor_id).await
+    }
+
+    async fn get_supervisor_report(&self, year: Option<i32>) -> Vec<SupervisorStats> {
+        let _permit = self.acquire().await;
+        self.inner.get_supervisor_report(year).await
+    }
+
+    async fn notify_status_change(
+        &self,
+        project_id: Uuid,
+        actor_id: Uuid,
+        reason: Option<String>,
+    ) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.notify_status_change(project_id, actor_id, reason).await
+    }
+
+    async fn record_project_review(
+        &self,
+        project_id: Uuid,
+        reviewer_id: Uuid,
+        status: ProjectStatus,
+        reason: Option<String>,
+    ) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.record_project_review(project_id, reviewer_id, status, reason).await
+    }
+
+    async fn get_latest_reviews(&self, owner_id: Uuid) -> Vec<ProjectReview> {
+        let _permit = self.acquire().await;
+        self.inner.get_latest_reviews(owner_id).await
+    }
+
+    async fn set_subscription(
+        &self,
+        project_id: Uuid,
+        user_id: Uuid,
+        state: SubscriptionState,
+    ) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.set_subscription(project_id, user_id, state).await
+    }
+
+    async fn get_subscription(&self, project_id: Uuid, user_id: Uuid) -> Option<SubscriptionState> {
+        let _permit = self.acquire().await;
+        self.inner.get_subscription(project_id, user_id).await
+    }
+
+    async fn record_upload(
+        &self,
+        user_id: Uuid,
+        object_key: &str,
+        purpose: UploadPurpose,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.record_upload(user_id, object_key, purpose, expires_at).await
+    }
+
+    async fn get_upload_counts(&self, user_id: Uuid, purpose: UploadPurpose) -> UploadCounts {
+        let _permit = self.acquire().await;
+        self.inner.get_upload_counts(user_id, purpose).await
+    }
+
+    async fn is_own_upload(&self, user_id: Uuid, object_key: &str, purpose: UploadPurpose) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.is_own_upload(user_id, object_key, purpose).await
+    }
+
+    async fn get_programmes(&self) -> Vec<Programme> {
+        let _permit = self.acquire().await;
+        self.inner.get_programmes().await
+    }
+
+    async fn create_programme(&self, code: &str, name: &str) -> Option<Programme> {
+        let _permit = self.acquire().await;
+        self.inner.create_programme(code, name).await
+    }
+
+    async fn unknown_programme_codes(&self, codes: &[String]) -> Vec<String> {
+        let _permit = self.acquire().await;
+        self.inner.unknown_programme_codes(codes).await
+    }
+
+    async fn get_project_programmes(&self, project_id: Uuid) -> Vec<String> {
+        let _permit = self.acquire().await;
+        self.inner.get_project_programmes(project_id).await
+    }
+
+    async fn get_tag_counts(&self) -> Vec<TagCount> {
+        let _permit = self.acquire().await;
+        self.inner.get_tag_counts().await
+    }
+
+    async fn get_user_activity(&self, user_id: Uuid, page: PageRequest) -> (Vec<ActivityItem>, i64) {
+        let _permit = self.acquire().await;
+        self.inner.get_user_activity(user_id, page).await
+    }
+
+    async fn get_project_stats(&self, id: Uuid, owner_id: Uuid) -> Option<ProjectStats> {
+        let _permit = self.acquire().await;
+        self.inner.get_project_stats(id, owner_id).await
+    }
+
+    async fn get_project_years(&self) -> Vec<YearCount> {
+        let _permit = self.acquire().await;
+        self.inner.get_project_years().await
+    }
+
+    async fn get_department_counts(&self) -> Vec<DepartmentCount> {
+        let _permit = self.acquire().await;
+        self.inner.get_department_counts().await
+    }
+
+    async fn get_technology_counts(&self) -> Vec<TechnologyCount> {
+        let _permit = self.acquire().await;
+        self.inner.get_technology_counts().await
+    }
+
+    async fn add_feedback(&self, project_id: Uuid, message: &str, contact_email: Option<&str>) -> Option<ProjectFeedback> {
+        let _permit = self.acquire().await;
+        self.inner.add_feedback(project_id, message, contact_email).await
+    }
+
+    async fn get_feedback(&self, project_id: Option<Uuid>) -> Vec<ProjectFeedback> {
+        let _permit = self.acquire().await;
+        self.inner.get_feedback(project_id).await
+    }
+
+    async fn get_settings(&self) -> Result<Vec<Setting>, RepositoryError> {
+        let _permit = self.acquire().await;
+        self.inner.get_settings().await
+    }
+
+    async fn put_setting(&self, key: &str, value: serde_json::Value, updated_by: Uuid) -> Option<Setting> {
+        let _permit = self.acquire().await;
+        self.inner.put_setting(key, value, updated_by).await
+    }
+
+    async fn get_stale_drafts(
+        &self,
+        sweep: DraftSweep,
+        untouched_before: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<StaleDraft> {
+        let _permit = self.acquire().await;
+        self.inner.get_stale_drafts(sweep, untouched_before).await
+    }
+
+    async fn mark_stale_draft(
+        &self,
+        sweep: DraftSweep,
+        project_id: Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        let _permit = self.acquire().await;
+        self.inner.mark_stale_draft(sweep, project_id, at).await
+    }
+
+    async fn get_projects_outside_years(&self, min: i32, max: i32) -> Vec<InvalidYearProject> {
+        let _permit = self.acquire().await;
+        self.inner.get_projects_outside_years(min, max).await
+    }
+
+    async fn remap_project_year(&self, from: i32, to: i32) -> i64 {
+        let _permit = self.acquire().await;
+        self.inner.remap_project_year(from, to).await
+    }
+
+    async fn record_audit(&self, entry: &NewAuditEntry) -> Result<(), RepositoryError> {
+        let _permit = self.acquire().await;
+        self.inner.record_audit(entry).await
+    }
+
+    async fn queue_audit(&self, entry: &NewAuditEntry) -> Result<i64, RepositoryError> {
+        let _permit = self.acquire().await;
+        self.inner.queue_audit(entry).await
+    }
+
+    async fn get_audit_log(&self, filter: &AuditFilter, page: PageRequest) -> (Vec<AuditEntry>, i64) {
+        let _permit = self.acquire().await;
+        self.inner.get_audit_log(filter, page).await
+    }
+
+    fn stream_audit_log(&self, filter: AuditFilter) -> BoxStream<'static, Result<AuditEntry, RepositoryError>> {
+        gated(self.gate.clone(), self.inner.stream_audit_log(filter))
+    }
+
+    async fn purge_audit_log(&self, before: chrono::DateTime<chrono::Utc>) -> Result<u64, RepositoryError> {
+        let _permit = self.acquire().await;
+        self.inner.purge_audit_log(before).await
+    }
+
+    async fn run_fanout_batch(&self, batch_size: i64) -> Option<OutboxJob> {
+        let _permit = self.acquire().await;
+        self.inner.run_fanout_batch(batch_size).await
+    }
+
+    async fn get_outbox_job(&self, id: i64) -> Option<OutboxJob> {
+        let _permit = self.acquire().await;
+        self.inner.get_outbox_job(id).await
+    }
+
+    async fn flush_audit_outbox(&self) -> Result<u64, RepositoryError> {
+        let _permit = self.acquire().await;
+        self.inner.flush_audit_outbox().await
+    }
+
+    async fn ping(&self) -> Result<(), RepositoryError> {
+        let _permit = self.acquire().await;
+        self.inner.ping().await
+    }
+}
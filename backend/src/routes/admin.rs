@@ -1,7 +1,7 @@
 use crate::{AppState, handlers};
 use axum::{
     Router,
-    routing::{get, put},
+    routing::{get, patch, post, put},
 };
 
 /// Admin Router Module
@@ -21,11 +21,23 @@ pub fn admin_routes() -> Router<AppState> {
         // Essential for system health monitoring and oversight.
         .route("/stats", get(handlers::get_admin_stats))
         // GET /admin/projects
-        // Lists ALL projects in the system, including those marked as `is_public=false`
+        // Lists ALL projects in the system, including those not yet approved
         // (hidden/pending review). Used for administrative review and queue management.
         .route("/projects", get(handlers::get_admin_projects))
+        // GET /admin/projects/pending?limit=...
+        // The review queue: pending projects only, longest waiting first, with the submitter's
+        // email and how long each has been waiting.
+        .route("/projects/pending", get(handlers::get_pending_reviews))
+        // GET /admin/projects/stream
+        // The same listing as newline-delimited JSON, streamed row by row for large exports.
+        // A database error mid-way truncates the body after the last complete line.
+        .route("/projects/stream", get(handlers::stream_admin_projects))
+        // GET /admin/projects/export.csv
+        // Every project with its like count and owner email as a CSV download for the
+        // coordinator's spreadsheet, also streamed row by row.
+        .route("/projects/export.csv", get(handlers::export_admin_projects_csv))
         // PUT /projects/{id}/status
-        // Allows an administrator to change a project's visibility (`is_public` field).
+        // Allows an administrator to move a project through review (`status` field).
         // This is the core moderation endpoint used to Publish or Hide projects.
         //
         // Note: The visibility status route is often exposed at a project endpoint
@@ -34,6 +46,94 @@ pub fn admin_routes() -> Router<AppState> {
             "/projects/{id}/status",
             put(handlers::update_project_status),
         )
+        // POST /projects/bulk_status
+        // Bulk action: approves or rejects up to 200 projects at once. Returns how many
+        // changed and which ids matched no project.
+        .route(
+            "/projects/bulk_status",
+            post(handlers::bulk_update_project_status),
+        )
+        // PUT /projects/{id}/owner
+        // Moves a project to another account (`new_owner_id`); both owners are notified.
+        // 404 when the project or the target user does not exist.
+        .route(
+            "/projects/{id}/owner",
+            put(handlers::reassign_project_owner),
+        )
+        // POST /projects/lock-comments?year=YYYY
+        // Bulk action: locks discussion on every project of a cohort in one statement
+        // (typically run once grading is finished). Returns the number of projects changed.
+        .route(
+            "/projects/lock-comments",
+            post(handlers::lock_comments_for_cohort),
+        )
+        // GET/POST /admin/programmes
+        // The programme lookup used to tag (joint-degree) projects. POST adds a programme;
+        // codes are unique and upper-case.
+        .route(
+            "/programmes",
+            get(handlers::get_programmes).post(handlers::create_programme),
+        )
+        // GET/PUT /admin/settings/{key}
+        // Runtime settings (submission deadline, maintenance mode, `academic_years`, `flag.*`
+        // feature flags).
+        // PUT validates the value against the key's registered rule before storing it.
+        .route(
+            "/settings/{key}",
+            get(handlers::get_setting).put(handlers::put_setting),
+        )
+        // GET /admin/feedback
+        // Anonymous project feedback across all projects, newest first.
+        .route("/feedback", get(handlers::get_all_feedback))
+        // GET /admin/comments?project_id=&user_id=&search=&limit=&offset=
+        // Every comment, hidden ones included, newest first with its project title and author
+        // email. Narrowed by project, author or text; paged with limit/offset.
+        .route("/comments", get(handlers::get_admin_comments))
+        // PATCH /admin/comments/{id}/restore
+        // Moderator action: unhides a comment hidden by reports and resolves those reports.
+        .route("/comments/{id}/restore", patch(handlers::restore_comment))
+        // GET /admin/comments/reported
+        // The moderation queue: comments with pending reports and their counts and reasons,
+        // most reported first. A comment leaves it once restored (dismissed) or deleted.
+        .route("/comments/reported", get(handlers::get_reported_comments))
+        // POST /admin/maintenance/draft-reminders?dry_run=true
+        // Runs the stale-draft pass (one reminder per draft, cleanup flag for very old drafts)
+        // immediately; `dry_run` only reports what it would do.
+        .route(
+            "/maintenance/draft-reminders",
+            post(handlers::run_draft_reminders),
+        )
+        // GET /admin/maintenance/invalid-years
+        // Projects whose year is outside the `academic_years` setting, with the allowed range.
+        .route(
+            "/maintenance/invalid-years",
+            get(handlers::get_invalid_years),
+        )
+        // POST /admin/maintenance/invalid-years/remap
+        // Bulk fix: moves every project of one year to an allowed year; returns the count moved.
+        .route(
+            "/maintenance/invalid-years/remap",
+            post(handlers::remap_invalid_years),
+        )
+        // GET /admin/errors?window=1h&samples=20
+        // Error budget of this instance: recent 5xx and 429 responses counted per route and
+        // code over 15m/1h/24h, with the newest samples (request IDs for finding them in logs).
+        .route("/errors", get(handlers::get_admin_errors))
+        // GET /admin/audit?actor_id=&action=&target_type=&from=&to=
+        // Who did what to which record, newest first and paginated. Supports CSV download of
+        // every matching entry via `Accept: text/csv` or `?format=csv`.
+        .route("/audit", get(handlers::get_admin_audit))
+        // GET /admin/outbox/{id}
+        // Progress of a queued background job, e.g. the notification fan-out of a comment on a
+        // busy project (`processed` of `total`, `status` "done" once finished).
+        .route("/outbox/{id}", get(handlers::get_outbox_job))
+        // GET /reports/supervisors?year=YYYY
+        // Staff report: projects, public count, average likes and comments per supervisor.
+        // Supports CSV download via `Accept: text/csv` or `?format=csv`.
+        .route(
+            "/reports/supervisors",
+            get(handlers::get_supervisor_report),
+        )
 
     // Missing Routes (See API Contract):
     // The router should also include routes for force-deleting projects and comments,
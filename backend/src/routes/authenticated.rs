@@ -29,6 +29,29 @@ pub fn authenticated_routes() -> Router<AppState> {
         // Lists all projects owned by the authenticated user, including those that are
         // not yet public (`is_public=false`).
         .route("/me/projects", get(handlers::get_my_projects))
+        // GET /me/activity?page=...&per_page=...
+        // The caller's comments, likes and project status changes from the last 90 days,
+        // newest first, for the profile page.
+        .route("/me/activity", get(handlers::get_my_activity))
+        // GET /me/projects/{id}/readiness
+        // Server-side submission checklist for one of the user's own projects.
+        .route(
+            "/me/projects/{id}/readiness",
+            get(handlers::get_project_readiness),
+        )
+        // GET /me/projects/{id}/stats
+        // Like, comment and view totals of one of the user's own projects, with likes per day
+        // over the last 30 days.
+        .route(
+            "/me/projects/{id}/stats",
+            get(handlers::get_project_stats),
+        )
+        // GET /me/projects/{id}/feedback
+        // Anonymous feedback left on one of the user's own projects (admins may read any).
+        .route(
+            "/me/projects/{id}/feedback",
+            get(handlers::get_project_feedback),
+        )
         // --- Project Submission & Voting ---
         // POST /projects
         // Submits a new project to the system. Requires `user_id` validation.
@@ -42,15 +65,60 @@ pub fn authenticated_routes() -> Router<AppState> {
         )
         // POST /projects/{id}/vote
         // Registers a 'like' for a specific project. The handler implements **idempotency** // using the composite primary key on the `project_likes` table to prevent double voting.
-        .route("/projects/{id}/vote", post(handlers::vote_project))
+        // DELETE /projects/{id}/vote
+        // Removes the caller's like (204), or 404 when there was none.
+        .route("/projects/{id}/vote", post(handlers::vote_project).delete(handlers::unvote_project))
+        // GET /projects/{id}/likes/users?page=...&per_page=...
+        // Who liked the project (id and email), newest like first. Owner or admin only (403).
+        .route("/projects/{id}/likes/users", get(handlers::get_project_likers))
+        // POST /projects/{id}/submit
+        // Submits a draft for admin review. Refused with 422 unless the readiness checklist
+        // passes; admins may override with `?force=true`.
+        .route("/projects/{id}/submit", post(handlers::submit_project))
+        // POST /projects/{id}/resubmit
+        // Owner-only: sends a rejected project back for review (409 for any other status, or
+        // after `max_resubmissions` attempts). Admins are notified.
+        .route("/projects/{id}/resubmit", post(handlers::resubmit_project))
         // --- Commenting System ---
         // POST /projects/{id}/comments
         // Posts a new comment on a specified project.
-        // This action triggers the PostgreSQL notification trigger (`handle_new_comment`).
+        // Notifies participants in the request, or queues a fan-out job (202) for busy projects.
+        // Non-admins are limited per minute and per day (429 with `Retry-After`).
         .route("/projects/{id}/comments", post(handlers::add_comment))
+        // PUT /projects/{id}/comments-lock
+        // Freezes (or reopens) discussion on a project. Owners may lock their own projects;
+        // admins may lock any project. Existing comments stay readable either way.
+        .route("/projects/{id}/comments-lock", put(handlers::set_comments_lock))
+        // PUT /projects/{id}/subscription
+        // Sets the caller's comment-notification preference ("subscribed" | "muted").
+        // The `comment_recipients` function consults these preferences when fanning out.
+        .route(
+            "/projects/{id}/subscription",
+            put(handlers::set_project_subscription),
+        )
+        // PUT /comments/{id}
+        // Allows a user to correct their own comment in place; it is then marked as edited.
+        // Admins get no override here: they may delete other people's comments, not reword them.
         // DELETE /comments/{id}
         // Allows a user to delete their own comment. Ownership validation is required.
-        .route("/comments/{id}", delete(handlers::delete_comment))
+        .route("/comments/{id}", put(handlers::update_comment).delete(handlers::delete_comment))
+        // DELETE /projects/{id}/comments/{comment_id}
+        // Project-scoped variant of the above: the comment must also belong to the project in
+        // the URL, otherwise 404. Comment ids are validated by the `CommentId` extractor.
+        .route(
+            "/projects/{id}/comments/{comment_id}",
+            delete(handlers::delete_project_comment),
+        )
+        // POST /projects/{id}/comments/{comment_id}/reports
+        // Reports a comment for moderation. Enough distinct reporters hide it from public
+        // listings until an admin restores it.
+        .route(
+            "/projects/{id}/comments/{comment_id}/reports",
+            post(handlers::report_comment),
+        )
+        // POST /comments/{id}/report
+        // The same report addressed by comment id alone, for clients without the project id.
+        .route("/comments/{id}/report", post(handlers::report_comment_by_id))
         // --- Notification System ---
         // GET /notifications
         // Retrieves all pending and past notifications for the authenticated user (the recipient).
@@ -1,11 +1,11 @@
-/// Router Module Index
-///
-/// Organizes the application's routing logic into security-segregated modules,
-/// enforcing a Defense-in-Depth strategy. This structure ensures that
-/// access control is applied explicitly at the module level (via Axum layers),
-/// preventing accidental exposure of protected endpoints.
-///
-/// The three modules map directly to the defined access roles.
+//! Router Module Index
+//!
+//! Organizes the application's routing logic into security-segregated modules,
+//! enforcing a Defense-in-Depth strategy. This structure ensures that
+//! access control is applied explicitly at the module level (via Axum layers),
+//! preventing accidental exposure of protected endpoints.
+//!
+//! The three modules map directly to the defined access roles.
 
 /// Routes accessible to all users (anonymous, read-only).
 /// Handlers must enforce visibility checks (`is_public=true`) at the Repository level.
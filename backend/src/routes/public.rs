@@ -1,8 +1,10 @@
-use crate::{AppState, handlers};
+use crate::{AppState, handlers, rate_limit::{self, RateLimiter}};
 use axum::{
     Router,
+    middleware,
     routing::{get, post},
 };
+use std::sync::Arc;
 
 /// Public Router Module
 ///
@@ -20,23 +22,110 @@ pub fn public_routes() -> Router<AppState> {
         // A simple, unauthenticated endpoint used for monitoring and load balancer checks.
         // Returns "ok" immediately to verify the service is running and responsive.
         .route("/health", get(|| async { "ok" }))
+        // GET /health/deep
+        // Readiness detail: a database round trip and the health of every background task.
+        // `amber` when a task has gone several intervals without a successful pass, `red` (503)
+        // when the database is unreachable. Exempt from rate limiting, like /health.
+        .route("/health/deep", get(handlers::get_deep_health))
+        // GET /version
+        // Build metadata (crate version, git commit, build time, environment) for triage.
+        // Public unless VERSION_ADMIN_ONLY is set, in which case the handler requires an admin.
+        .route("/version", get(handlers::get_version))
+        // GET /metrics
+        // Prometheus scrape endpoint (e.g. `auth_provider_requests_total{outcome}`).
+        // Exempt from rate limiting, like /health.
+        .route("/metrics", get(handlers::get_metrics))
         // POST /register
         // Endpoint for new user creation and initial profile setup. This is part of the
         // identity flow managed by Supabase/Auth in production.
         .route("/register", post(handlers::register_user))
-        // GET /projects?year=...&search=...
-        // Lists all public projects, supporting filtering by year and full-text search.
+        // GET /projects?year=...&search=...&supervisor=...&sort=...
+        // Lists all public projects, supporting filtering by year, full-text search, programme,
+        // tag and supervisor, ordered by `sort` (e.g. `most_viewed`).
         // Critical enforcement of `is_public=true` occurs in the handler's Repository query.
         .route("/projects", get(handlers::get_projects))
-        // GET /projects/featured
-        // Retrieves the top 3 projects ranked by the current like count.
+        // GET /projects/featured?limit=...&year=...
+        // Retrieves the top projects (3 by default, at most 20) ranked by the current like
+        // count, from one year: `year`, or else the latest year that has public projects.
         .route("/projects/featured", get(handlers::get_featured_projects))
+        // GET /projects/random?daily=true
+        // One random public project for the landing-page spotlight; `daily` keeps the same pick
+        // for everyone until midnight UTC. 404 when no project is public.
+        .route("/projects/random", get(handlers::get_random_project))
+        // GET /projects/years
+        // Years that have public projects with their counts, newest first, for the year filter.
+        .route("/projects/years", get(handlers::get_project_years))
+        // GET /projects/archive/{year}
+        // "Class of {year}" archive page: all public projects of that cohort, most liked first
+        // (the featured ranking without a limit). Empty for a year with no public projects.
+        .route("/projects/archive/{year}", get(handlers::get_project_archive))
+        // POST /projects/changed-since
+        // "Updated since your last visit" check: returns the ids of public projects edited or
+        // commented on after `since` (clamped to 90 days back), optionally limited to `ids`.
+        .route("/projects/changed-since", post(handlers::get_changed_projects))
+        // GET /events/public?since=...
+        // Publish/unpublish feed for mirroring sites such as the university portal: up to 500
+        // events after `since` plus the `next_since` watermark. Cacheable for 60 seconds.
+        .route("/events/public", get(handlers::get_public_events))
         // GET /projects/{id}
         // Retrieves the detailed view of a single project.
         // Requires a repository-level check to ensure `is_public=true` before data release.
+        // A valid token is optional: it lets owners and admins see non-public projects and adds
+        // the caller's comment subscription state.
         .route("/projects/{id}", get(handlers::get_project_details))
-        // GET /projects/{id}/comments
-        // Lists all associated comments for a specific project.
-        // This endpoint implicitly verifies that the parent project is public before retrieving comments.
+        // GET /projects/slug/{slug}
+        // The same detail view addressed by a readable slug such as `smart-greenhouse-2024`.
+        // Earlier slugs of a renamed project keep resolving; visibility rules are unchanged.
+        .route("/projects/slug/{slug}", get(handlers::get_project_by_slug))
+        // POST /projects/{id}/view
+        // Counts a view of a public project and returns the new total. Anonymous callers are
+        // counted too; private or missing projects 404.
+        .route("/projects/{id}/view", post(handlers::record_view))
+        // GET /projects/{id}/likes
+        // The current like total of a public project as `{ "count": n }`; private or missing
+        // projects 404.
+        .route("/projects/{id}/likes", get(handlers::get_like_count))
+        // GET /projects/{id}/comments?order=oldest|newest
+        // Lists all associated comments for a specific project. Long threads default to newest-first.
+        // This endpoint implicitly verifies that the parent project is public before retrieving comments;
+        // a valid token lets the owner and admins read the thread of a private project too.
         .route("/projects/{id}/comments", get(handlers::get_comments))
+        // GET /projects/{id}/comments/count
+        // The number of comments a public project's thread shows, as `{ "count": n }`, for the
+        // comment bubble on project cards; private or missing projects 404.
+        .route("/projects/{id}/comments/count", get(handlers::get_comment_count))
+        // GET /tags
+        // Distinct tags on public projects with usage counts, for the listing's tag filter.
+        .route("/tags", get(handlers::get_tags))
+        // GET /departments
+        // Departments of public projects with their counts, for the listing's department filter.
+        .route("/departments", get(handlers::get_departments))
+        // GET /technologies
+        // Technologies of public projects with their counts, for the listing's `tech` filter.
+        .route("/technologies", get(handlers::get_technologies))
+        // GET /stats/public
+        // Landing-page counters (public projects, years, supervisors, likes). Aggregated over
+        // public projects only and cached in-process for five minutes.
+        .route("/stats/public", get(handlers::get_public_stats))
+        // GET /projects/{id}/og
+        // HTML link-preview document (Open Graph / Twitter card tags) for chat apps and crawlers.
+        // Private or missing projects get a metadata-free 404 shell.
+        .route("/projects/{id}/og", get(handlers::get_project_og))
+        // GET /embed/projects/{id}?format=html|json
+        // Frameable project card for third-party pages (inline CSS, no JS), or its oEmbed-style
+        // JSON. Served with a CSP that permits any frame ancestor; private projects 404.
+        .route("/embed/projects/{id}", get(handlers::get_project_embed))
+}
+
+/// feedback_routes
+///
+/// The anonymous feedback form, split out so it gets its own, much stricter per-address
+/// limiter (FEEDBACK_PER_HOUR) on top of the global one. Like the routes above it needs no
+/// authentication; the CAPTCHA check, when configured, happens in the handler.
+pub fn feedback_routes(limiter: Arc<RateLimiter>) -> Router<AppState> {
+    Router::new()
+        // POST /projects/{id}/feedback
+        // Stores feedback on a public project for its owner; private or missing projects 404.
+        .route("/projects/{id}/feedback", post(handlers::submit_feedback))
+        .route_layer(middleware::from_fn_with_state(limiter, rate_limit::rate_limit))
 }
@@ -0,0 +1,173 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use std::{env, sync::Arc};
+
+use crate::{config::Env, embed};
+
+/// Routes meant to be framed by other sites; they never get `X-Frame-Options`.
+const FRAMEABLE_PREFIXES: [&str; 1] = ["/embed/"];
+/// The Swagger UI pages, which need their own script and style allowances.
+const SWAGGER_PREFIX: &str = "/swagger-ui";
+
+/// Default `Referrer-Policy`: full URLs on same-origin requests, only the origin cross-site.
+pub const DEFAULT_REFERRER_POLICY: &str = "strict-origin-when-cross-origin";
+/// Default `Strict-Transport-Security` (one year, subdomains included).
+pub const DEFAULT_HSTS: &str = "max-age=31536000; includeSubDomains";
+/// Default policy for the server-rendered HTML (link previews and their 404 shell): no
+/// scripts or styles, only remote images, and no framing.
+pub const DEFAULT_HTML_CSP: &str = "default-src 'none'; img-src http: https:; base-uri 'none'; form-action 'none'; frame-ancestors 'none'";
+/// Default policy for Swagger UI. Its bundle and `swagger-initializer.js` are served from
+/// `/swagger-ui` itself, so scripts only need `'self'`; the UI injects inline styles, draws
+/// icons from `data:` images and fetches `/api-docs/openapi.json` plus "Try it out" calls.
+pub const DEFAULT_SWAGGER_CSP: &str = "default-src 'none'; script-src 'self'; style-src 'self' 'unsafe-inline'; img-src 'self' data:; font-src 'self' data:; connect-src 'self'; base-uri 'self'; form-action 'self'; frame-ancestors 'none'";
+
+/// SecurityHeaders
+///
+/// The `security_headers` section of `AppConfig`: the values the `security_headers`
+/// middleware puts on responses. Every value can be overridden from the environment, and an
+/// empty value leaves that header out. Headers a handler sets itself are never overwritten.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityHeaders {
+    // `X-Content-Type-Options` on every response (SECURITY_CONTENT_TYPE_OPTIONS).
+    pub content_type_options: String,
+    // `Referrer-Policy` on every response (SECURITY_REFERRER_POLICY).
+    pub referrer_policy: String,
+    // `X-Frame-Options` on every response except the embed routes (SECURITY_FRAME_OPTIONS).
+    pub frame_options: String,
+    // `Strict-Transport-Security`, sent in Production only (SECURITY_HSTS).
+    pub hsts: String,
+    // `Content-Security-Policy` for HTML responses outside Swagger UI (SECURITY_HTML_CSP).
+    pub html_csp: String,
+    // `Content-Security-Policy` for the Swagger UI pages (SECURITY_SWAGGER_CSP).
+    pub swagger_csp: String,
+    // `Content-Security-Policy` the embed route sends with its cards (SECURITY_EMBED_CSP).
+    pub embed_csp: String,
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self {
+            content_type_options: "nosniff".to_string(),
+            referrer_policy: DEFAULT_REFERRER_POLICY.to_string(),
+            frame_options: "DENY".to_string(),
+            hsts: DEFAULT_HSTS.to_string(),
+            html_csp: DEFAULT_HTML_CSP.to_string(),
+            swagger_csp: DEFAULT_SWAGGER_CSP.to_string(),
+            embed_csp: embed::EMBED_CSP.to_string(),
+        }
+    }
+}
+
+impl SecurityHeaders {
+    /// from_env
+    ///
+    /// The defaults, with each `SECURITY_*` variable that is set replacing its value.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str, default: String| env::var(name).map(|v| v.trim().to_string()).unwrap_or(default);
+        Self {
+            content_type_options: var("SECURITY_CONTENT_TYPE_OPTIONS", defaults.content_type_options),
+            referrer_policy: var("SECURITY_REFERRER_POLICY", defaults.referrer_policy),
+            frame_options: var("SECURITY_FRAME_OPTIONS", defaults.frame_options),
+            hsts: var("SECURITY_HSTS", defaults.hsts),
+            html_csp: var("SECURITY_HTML_CSP", defaults.html_csp),
+            swagger_csp: var("SECURITY_SWAGGER_CSP", defaults.swagger_csp),
+            embed_csp: var("SECURITY_EMBED_CSP", defaults.embed_csp),
+        }
+    }
+}
+
+/// An optional header value; empty means "do not send".
+///
+/// # Panics
+/// Panics on a value that is not a valid header value, so a mistyped override stops the
+/// server at startup rather than silently dropping the header.
+fn header_value(name: &str, value: &str) -> Option<HeaderValue> {
+    if value.is_empty() {
+        return None;
+    }
+    Some(HeaderValue::from_str(value).unwrap_or_else(|_| panic!("FATAL: invalid {name} header value: {value:?}")))
+}
+
+/// SecurityPolicy
+///
+/// `SecurityHeaders` parsed once when the router is built, with HSTS already dropped outside
+/// Production.
+#[derive(Debug, Clone)]
+pub struct SecurityPolicy {
+    content_type_options: Option<HeaderValue>,
+    referrer_policy: Option<HeaderValue>,
+    frame_options: Option<HeaderValue>,
+    hsts: Option<HeaderValue>,
+    html_csp: Option<HeaderValue>,
+    swagger_csp: Option<HeaderValue>,
+    embed_csp: Option<HeaderValue>,
+}
+
+impl SecurityPolicy {
+    pub fn new(headers: &SecurityHeaders, env: &Env) -> Self {
+        Self {
+            content_type_options: header_value("X-Content-Type-Options", &headers.content_type_options),
+            referrer_policy: header_value("Referrer-Policy", &headers.referrer_policy),
+            frame_options: header_value("X-Frame-Options", &headers.frame_options),
+            hsts: match env {
+                Env::Production => header_value("Strict-Transport-Security", &headers.hsts),
+                Env::Local => None,
+            },
+            html_csp: header_value("Content-Security-Policy", &headers.html_csp),
+            swagger_csp: header_value("Content-Security-Policy", &headers.swagger_csp),
+            embed_csp: header_value("Content-Security-Policy", &headers.embed_csp),
+        }
+    }
+}
+
+fn set_default(headers: &mut HeaderMap, name: HeaderName, value: &Option<HeaderValue>) {
+    if let Some(value) = value
+        && !headers.contains_key(&name)
+    {
+        headers.insert(name, value.clone());
+    }
+}
+
+fn is_html(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/html"))
+}
+
+/// security_headers (Middleware)
+///
+/// Adds the configured security headers to every response. The embed routes skip
+/// `X-Frame-Options` and get the embed policy, which allows any frame ancestor, on every
+/// response (the handler sets it too). Elsewhere a `Content-Security-Policy` is only added to
+/// HTML: the Swagger UI policy under `/swagger-ui`, the HTML policy otherwise.
+pub async fn security_headers(
+    State(policy): State<Arc<SecurityPolicy>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    let frameable = FRAMEABLE_PREFIXES.iter().any(|prefix| path.starts_with(prefix));
+    let swagger = path.starts_with(SWAGGER_PREFIX);
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    set_default(headers, header::X_CONTENT_TYPE_OPTIONS, &policy.content_type_options);
+    set_default(headers, header::REFERRER_POLICY, &policy.referrer_policy);
+    set_default(headers, header::STRICT_TRANSPORT_SECURITY, &policy.hsts);
+    if frameable {
+        set_default(headers, header::CONTENT_SECURITY_POLICY, &policy.embed_csp);
+        return response;
+    }
+    set_default(headers, header::X_FRAME_OPTIONS, &policy.frame_options);
+    if is_html(headers) {
+        let csp = if swagger { &policy.swagger_csp } else { &policy.html_csp };
+        set_default(headers, header::CONTENT_SECURITY_POLICY, csp);
+    }
+    response
+}
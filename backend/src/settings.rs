@@ -0,0 +1,306 @@
+use crate::{
+    error::ApiError,
+    maintenance::MaintenanceTask,
+    models::{AcademicYears, MaintenanceMode, Setting},
+    repository::{Repository, RepositoryError, RepositoryState},
+};
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use chrono::{DateTime, Datelike, Utc};
+use serde_json::Value;
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::sync::watch;
+use uuid::Uuid;
+
+/// Key of the project submission deadline: an RFC 3339 timestamp, or `null` for none.
+pub const SUBMISSION_DEADLINE: &str = "submission_deadline";
+/// Key of the site-wide `MaintenanceMode`.
+pub const MAINTENANCE_MODE: &str = "maintenance_mode";
+/// Key of the `AcademicYears` projects may be filed under.
+pub const ACADEMIC_YEARS: &str = "academic_years";
+/// Prefix of boolean feature flags, e.g. `flag.new_editor`.
+pub const FLAG_PREFIX: &str = "flag.";
+
+/// SettingDefinition
+///
+/// What a key accepts and what it reads as before anyone sets it. A `key` ending in `.` is a
+/// family (every `<prefix><name>` with a lower-case snake_case name), which is how feature
+/// flags are added without registering each one.
+struct SettingDefinition {
+    key: &'static str,
+    default: fn() -> Value,
+    validate: fn(&Value) -> Result<(), String>,
+}
+
+/// The known settings. PUT /admin/settings/{key} refuses any key not matched here.
+const REGISTRY: &[SettingDefinition] = &[
+    SettingDefinition {
+        key: SUBMISSION_DEADLINE,
+        default: || Value::Null,
+        validate: validate_timestamp,
+    },
+    SettingDefinition {
+        key: MAINTENANCE_MODE,
+        default: || serde_json::json!(MaintenanceMode::Off),
+        validate: validate_maintenance_mode,
+    },
+    SettingDefinition {
+        key: ACADEMIC_YEARS,
+        default: || serde_json::json!(default_academic_years()),
+        validate: validate_academic_years,
+    },
+    SettingDefinition {
+        key: FLAG_PREFIX,
+        default: || Value::Bool(false),
+        validate: validate_bool,
+    },
+];
+
+fn definition(key: &str) -> Option<&'static SettingDefinition> {
+    REGISTRY.iter().find(|def| match key.strip_prefix(def.key) {
+        Some("") => !def.key.ends_with('.'),
+        Some(name) => {
+            def.key.ends_with('.')
+                && name.len() <= 64
+                && name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+        }
+        None => false,
+    })
+}
+
+fn validate_timestamp(value: &Value) -> Result<(), String> {
+    match value {
+        Value::Null => Ok(()),
+        Value::String(s) if DateTime::parse_from_rfc3339(s).is_ok() => Ok(()),
+        _ => Err("expected an RFC 3339 timestamp or null".to_string()),
+    }
+}
+
+fn validate_maintenance_mode(value: &Value) -> Result<(), String> {
+    serde_json::from_value::<MaintenanceMode>(value.clone())
+        .map(|_| ())
+        .map_err(|_| "expected \"off\" or \"read_only\"".to_string())
+}
+
+/// Until an admin sets the range: every year from 2000 up to next year's cohort.
+fn default_academic_years() -> AcademicYears {
+    AcademicYears {
+        min: 2000,
+        max: Utc::now().year() + 1,
+    }
+}
+
+fn validate_academic_years(value: &Value) -> Result<(), String> {
+    match serde_json::from_value::<AcademicYears>(value.clone()) {
+        Ok(years) if years.min > years.max => Err("min must not be greater than max".to_string()),
+        Ok(years) if years.min < 1900 || years.max > 2100 => {
+            Err("years must lie between 1900 and 2100".to_string())
+        }
+        Ok(_) => Ok(()),
+        Err(_) => Err("expected {\"min\": <year>, \"max\": <year>}".to_string()),
+    }
+}
+
+fn validate_bool(value: &Value) -> Result<(), String> {
+    if value.is_boolean() {
+        Ok(())
+    } else {
+        Err("expected true or false".to_string())
+    }
+}
+
+fn unknown_setting(key: &str) -> ApiError {
+    ApiError::new(StatusCode::NOT_FOUND, "unknown_setting", "No such setting.")
+        .with_details(serde_json::json!({ "key": key }))
+}
+
+/// Settings
+///
+/// An immutable snapshot of every stored setting, with typed getters that fall back to the
+/// registered default for keys that were never set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Settings {
+    stored: BTreeMap<String, Setting>,
+}
+
+impl Settings {
+    fn value(&self, key: &str) -> Option<&Value> {
+        self.stored.get(key).map(|s| &s.value)
+    }
+
+    /// The setting as an admin sees it; `None` for unregistered keys.
+    pub fn get(&self, key: &str) -> Option<Setting> {
+        let def = definition(key)?;
+        Some(self.stored.get(key).cloned().unwrap_or_else(|| Setting {
+            key: key.to_string(),
+            value: (def.default)(),
+            updated_at: None,
+            updated_by: None,
+        }))
+    }
+
+    /// When project submissions close, if a deadline is set.
+    pub fn deadline(&self) -> Option<DateTime<Utc>> {
+        let raw = self.value(SUBMISSION_DEADLINE)?.as_str()?;
+        DateTime::parse_from_rfc3339(raw).ok().map(|at| at.with_timezone(&Utc))
+    }
+
+    pub fn maintenance(&self) -> MaintenanceMode {
+        self.value(MAINTENANCE_MODE)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// The years projects may be filed under.
+    pub fn academic_years(&self) -> AcademicYears {
+        self.value(ACADEMIC_YEARS)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_else(default_academic_years)
+    }
+
+    /// Whether feature flag `name` (stored as `flag.<name>`) is on. Unset flags are off.
+    pub fn flag(&self, name: &str) -> bool {
+        self.value(&format!("{FLAG_PREFIX}{name}"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+}
+
+/// SettingsService
+///
+/// The process-wide view of the `settings` table. Reads come from an in-memory snapshot, so
+/// they never touch the database; `refresh` reloads it and `set` writes through. The snapshot
+/// lives in a watch channel: middlewares and background tasks `subscribe` and are woken on
+/// each change instead of polling.
+pub struct SettingsService {
+    current: watch::Sender<Settings>,
+}
+
+/// SettingsState
+///
+/// The shared handle to the settings service held in `AppState`.
+pub type SettingsState = Arc<SettingsService>;
+
+impl SettingsService {
+    /// Starts with every setting at its default, until the first `refresh`.
+    pub fn new() -> Self {
+        let (current, _) = watch::channel(Settings::default());
+        Self { current }
+    }
+
+    pub fn snapshot(&self) -> Settings {
+        self.current.borrow().clone()
+    }
+
+    /// A receiver that sees the current snapshot and is notified whenever it changes.
+    pub fn subscribe(&self) -> watch::Receiver<Settings> {
+        self.current.subscribe()
+    }
+
+    pub fn deadline(&self) -> Option<DateTime<Utc>> {
+        self.current.borrow().deadline()
+    }
+
+    pub fn maintenance(&self) -> MaintenanceMode {
+        self.current.borrow().maintenance()
+    }
+
+    pub fn academic_years(&self) -> AcademicYears {
+        self.current.borrow().academic_years()
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.current.borrow().flag(name)
+    }
+
+    /// get
+    ///
+    /// One setting from the snapshot; 404 `unknown_setting` for unregistered keys.
+    pub fn get(&self, key: &str) -> Result<Setting, ApiError> {
+        self.current.borrow().get(key).ok_or_else(|| unknown_setting(key))
+    }
+
+    /// set
+    ///
+    /// Validates `value` against the key's rule (404 `unknown_setting`, 422 `invalid_setting`),
+    /// stores it, and publishes the new snapshot right away. Other instances pick the change up
+    /// on their next refresh.
+    pub async fn set(
+        &self,
+        repo: &dyn Repository,
+        key: &str,
+        value: Value,
+        updated_by: Uuid,
+    ) -> Result<Setting, ApiError> {
+        let def = definition(key).ok_or_else(|| unknown_setting(key))?;
+        if let Err(reason) = (def.validate)(&value) {
+            return Err(ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "invalid_setting",
+                "The value is not valid for this setting.",
+            )
+            .with_details(serde_json::json!({ "key": key, "reason": reason })));
+        }
+        let Some(setting) = repo.put_setting(key, value, updated_by).await else {
+            return Err(ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "setting_not_saved",
+                "Failed",
+            ));
+        };
+        // Every write carries a new `updated_at`, so subscribers are always notified.
+        self.current.send_modify(|settings| {
+            settings.stored.insert(key.to_string(), setting.clone());
+        });
+        Ok(setting)
+    }
+
+    /// refresh
+    ///
+    /// Reloads the snapshot from the repository, notifying subscribers only if something
+    /// changed. On a load error the previous snapshot is kept and the error returned: falling
+    /// back to defaults would, for instance, silently lift maintenance mode.
+    pub async fn refresh(&self, repo: &dyn Repository) -> Result<(), RepositoryError> {
+        let rows = repo.get_settings().await?;
+        let loaded = Settings {
+            stored: rows.into_iter().map(|s| (s.key.clone(), s)).collect(),
+        };
+        self.current.send_if_modified(|settings| {
+            let changed = *settings != loaded;
+            *settings = loaded;
+            changed
+        });
+        Ok(())
+    }
+}
+
+impl Default for SettingsService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// SettingsRefresh
+///
+/// The refresh loop, as a task for `maintenance::spawn_runner` on its own, shorter interval.
+pub struct SettingsRefresh {
+    settings: SettingsState,
+    repo: RepositoryState,
+}
+
+impl SettingsRefresh {
+    pub fn new(settings: SettingsState, repo: RepositoryState) -> Self {
+        Self { settings, repo }
+    }
+}
+
+#[async_trait]
+impl MaintenanceTask for SettingsRefresh {
+    fn name(&self) -> &'static str {
+        "settings_refresh"
+    }
+
+    async fn run(&self, _now: DateTime<Utc>) -> Result<(), RepositoryError> {
+        self.settings.refresh(&*self.repo).await
+    }
+}
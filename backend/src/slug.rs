@@ -0,0 +1,37 @@
+/// Longest title part of a slug. Slugs are ASCII, so this is also its length in bytes.
+const MAX_TITLE_LEN: usize = 60;
+
+/// base
+///
+/// The slug a project would get if nothing else held it: the title lower-cased, every run of
+/// characters other than ASCII letters and digits turned into one `-`, cut to
+/// `MAX_TITLE_LEN`, then the cohort year, e.g. "Smart Greenhouse!" in 2024 is
+/// `smart-greenhouse-2024`. A title with nothing usable becomes `project-<year>`. The backfill
+/// in the `project_slugs` migration applies the same rules in SQL.
+pub fn base(title: &str, year: i32) -> String {
+    let mut words = String::with_capacity(title.len());
+    for c in title.chars() {
+        if c.is_ascii_alphanumeric() {
+            words.push(c.to_ascii_lowercase());
+        } else if !words.ends_with('-') {
+            words.push('-');
+        }
+    }
+    let words = words.trim_start_matches('-');
+    let words = words[..words.len().min(MAX_TITLE_LEN)].trim_end_matches('-');
+    let words = if words.is_empty() { "project" } else { words };
+    format!("{words}-{year}")
+}
+
+/// first_free
+///
+/// `base` itself, or the first of `base-2`, `base-3`, ... for which `taken` is false.
+pub fn first_free(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|candidate| !taken(candidate))
+        .expect("an unbounded range always has a free suffix")
+}
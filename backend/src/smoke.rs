@@ -0,0 +1,292 @@
+use axum::http::StatusCode;
+use chrono::Datelike;
+use futures::future::BoxFuture;
+use jsonwebtoken::{EncodingKey, Header, encode};
+use serde::de::DeserializeOwned;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::Claims,
+    build_info,
+    models::{Paginated, PresignedUrlResponse, Project, User},
+};
+
+/// The only environment label under which `--allow-writes` takes effect.
+pub const WRITABLE_ENV: &str = "staging";
+/// Budget for each smoke request, so a hung deploy fails the run instead of stalling it.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Lifetime of the token signed for the throwaway user.
+const TOKEN_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// SmokeOptions
+///
+/// What to check and where. `env` is the operator's name for the target, not something the
+/// server reports: writes only happen with `allow_writes` and an `env` of "staging".
+#[derive(Debug, Clone)]
+pub struct SmokeOptions {
+    pub base_url: String,
+    pub env: String,
+    pub allow_writes: bool,
+    // Signs a short-lived token for the throwaway user. Without it requests fall back to the
+    // `x-user-id` header, which only a local deployment accepts.
+    pub jwt_secret: Option<String>,
+}
+
+impl SmokeOptions {
+    pub fn writes_enabled(&self) -> bool {
+        self.allow_writes && self.env == WRITABLE_ENV
+    }
+}
+
+/// SmokeError
+///
+/// Why a step failed.
+#[derive(Debug)]
+pub enum SmokeError {
+    /// The request could not be sent or its body not read.
+    Http(reqwest::Error),
+    /// The server answered with a status other than the one the step expects.
+    Status { expected: StatusCode, actual: StatusCode, body: String },
+    /// The step depends on an earlier one that has not run.
+    Precondition(&'static str),
+}
+
+impl fmt::Display for SmokeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SmokeError::Http(e) => write!(f, "request failed: {e}"),
+            SmokeError::Status { expected, actual, body } => {
+                write!(f, "expected {expected}, got {actual}: {body}")
+            }
+            SmokeError::Precondition(what) => write!(f, "{what}"),
+        }
+    }
+}
+
+impl From<reqwest::Error> for SmokeError {
+    fn from(e: reqwest::Error) -> Self {
+        SmokeError::Http(e)
+    }
+}
+
+/// Session
+///
+/// The state one smoke run carries from step to step: the throwaway user and draft the
+/// write steps create, so `cleanup` knows what to remove.
+pub struct Session {
+    client: reqwest::Client,
+    base_url: String,
+    jwt_secret: Option<String>,
+    pub user: Option<User>,
+    pub draft: Option<Uuid>,
+}
+
+impl Session {
+    pub fn new(options: &SmokeOptions) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(build_info::server_header())
+            .build()
+            .expect("FATAL: Failed to build the smoke HTTP client.");
+        Self {
+            client,
+            base_url: options.base_url.trim_end_matches('/').to_string(),
+            jwt_secret: options.jwt_secret.clone(),
+            user: None,
+            draft: None,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Adds the throwaway user's credentials: a signed token when a secret is configured,
+    /// the local `x-user-id` bypass otherwise.
+    fn authed(&self, request: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder, SmokeError> {
+        let user = self.user.as_ref().ok_or(SmokeError::Precondition("no user registered"))?;
+        let Some(secret) = &self.jwt_secret else {
+            return Ok(request.header("x-user-id", user.id.to_string()));
+        };
+        let now = chrono::Utc::now().timestamp() as usize;
+        let claims = Claims { sub: user.id, iat: now, exp: now + TOKEN_TTL.as_secs() as usize };
+        let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes()))
+            .map_err(|_| SmokeError::Precondition("could not sign a token"))?;
+        Ok(request.bearer_auth(token))
+    }
+}
+
+/// Sends `request` and requires `expected`, returning the response for the body to be read.
+async fn expect(request: reqwest::RequestBuilder, expected: StatusCode) -> Result<reqwest::Response, SmokeError> {
+    let response = request.send().await?;
+    let actual = response.status();
+    if actual != expected {
+        let body = response.text().await.unwrap_or_default();
+        return Err(SmokeError::Status { expected, actual, body });
+    }
+    Ok(response)
+}
+
+async fn expect_json<T: DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    expected: StatusCode,
+) -> Result<T, SmokeError> {
+    Ok(expect(request, expected).await?.json().await?)
+}
+
+/// GET /health answers 200.
+pub async fn health(session: &mut Session) -> Result<String, SmokeError> {
+    expect(session.client.get(session.url("/health")), StatusCode::OK).await?;
+    Ok("ok".to_string())
+}
+
+/// GET /projects answers with a page of public projects.
+pub async fn public_projects(session: &mut Session) -> Result<String, SmokeError> {
+    let page: Paginated<Project> =
+        expect_json(session.client.get(session.url("/projects")), StatusCode::OK).await?;
+    Ok(format!("{} of {} projects", page.items.len(), page.total_count))
+}
+
+/// POST /register with a unique address. There is no endpoint to delete a user, so the
+/// account stays behind; its `smoke+` address makes it easy to find.
+pub async fn register_user(session: &mut Session) -> Result<String, SmokeError> {
+    let email = format!("smoke+{}@example.com", Uuid::new_v4().simple());
+    let body = serde_json::json!({
+        "email": email,
+        "password": Uuid::new_v4().to_string(),
+        "role": "student",
+    });
+    let request = session.client.post(session.url("/register")).json(&body);
+    let user: User = expect_json(request, StatusCode::OK).await?;
+    let detail = format!("user {}", user.id);
+    session.user = Some(user);
+    Ok(detail)
+}
+
+/// POST /projects creates a draft owned by the throwaway user.
+pub async fn create_draft(session: &mut Session) -> Result<String, SmokeError> {
+    let body = serde_json::json!({
+        "title": "Smoke test draft",
+        "abstract_text": "Created by the deployment smoke test; deleted at the end of the run.",
+        "author_name": "Smoke Test",
+        "year": chrono::Utc::now().year(),
+        "cover_image_key": "uploads/smoke-cover.png",
+    });
+    let request = session.authed(session.client.post(session.url("/projects")).json(&body))?;
+    let project: Project = expect_json(request, StatusCode::OK).await?;
+    session.draft = Some(project.id);
+    Ok(format!("draft {}", project.id))
+}
+
+/// POST /upload/presigned returns an upload URL. Nothing is uploaded.
+pub async fn request_presign(session: &mut Session) -> Result<String, SmokeError> {
+    let body = serde_json::json!({ "filename": "smoke.png", "file_type": "image/png" });
+    let request = session.authed(session.client.post(session.url("/upload/presigned")).json(&body))?;
+    let presigned: PresignedUrlResponse = expect_json(request, StatusCode::OK).await?;
+    Ok(presigned.resource_key)
+}
+
+/// DELETE /projects/{id} removes the draft.
+pub async fn cleanup(session: &mut Session) -> Result<String, SmokeError> {
+    let id = session.draft.ok_or(SmokeError::Precondition("no draft to delete"))?;
+    let request = session.authed(session.client.delete(session.url(&format!("/projects/{id}"))))?;
+    expect(request, StatusCode::NO_CONTENT).await?;
+    session.draft = None;
+    Ok(format!("deleted {id}"))
+}
+
+/// StepReport
+///
+/// One step as it ran: how long it took and what it found (or why it failed).
+#[derive(Debug)]
+pub struct StepReport {
+    pub name: &'static str,
+    pub latency: Duration,
+    pub result: Result<String, SmokeError>,
+}
+
+/// SmokeReport
+///
+/// The steps that ran, in order. A run stops at the first failure, so only the last step
+/// can have failed, apart from a `cleanup` attempted after it.
+#[derive(Debug, Default)]
+pub struct SmokeReport {
+    pub steps: Vec<StepReport>,
+}
+
+impl SmokeReport {
+    pub fn passed(&self) -> bool {
+        self.steps.iter().all(|s| s.result.is_ok())
+    }
+
+    pub fn first_failure(&self) -> Option<&StepReport> {
+        self.steps.iter().find(|s| s.result.is_err())
+    }
+}
+
+impl fmt::Display for SmokeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            let (status, detail) = match &step.result {
+                Ok(detail) => ("ok", detail.clone()),
+                Err(e) => ("FAIL", e.to_string()),
+            };
+            writeln!(f, "{status:<4} {:<16} {:>6} ms  {detail}", step.name, step.latency.as_millis())?;
+        }
+        match self.first_failure() {
+            Some(step) => write!(f, "smoke test failed at {}", step.name),
+            None => write!(f, "smoke test passed ({} steps)", self.steps.len()),
+        }
+    }
+}
+
+/// A step as stored in the step tables below.
+type StepFn = for<'a> fn(&'a mut Session) -> BoxFuture<'a, Result<String, SmokeError>>;
+
+/// Safe against any deployment.
+const READ_STEPS: [(&str, StepFn); 2] = [
+    ("health", |s| Box::pin(health(s))),
+    ("public_projects", |s| Box::pin(public_projects(s))),
+];
+
+/// Only run when `writes_enabled`; `cleanup` undoes `create_draft`.
+const WRITE_STEPS: [(&str, StepFn); 4] = [
+    ("register_user", |s| Box::pin(register_user(s))),
+    ("create_draft", |s| Box::pin(create_draft(s))),
+    ("request_presign", |s| Box::pin(request_presign(s))),
+    ("cleanup", |s| Box::pin(cleanup(s))),
+];
+
+async fn run_step(session: &mut Session, name: &'static str, step: StepFn) -> StepReport {
+    let started = Instant::now();
+    let result = step(session).await;
+    StepReport { name, latency: started.elapsed(), result }
+}
+
+/// run
+///
+/// Runs the read-only steps, then the write steps when `writes_enabled`, stopping at the
+/// first failure. A draft created before a failure is still deleted, so a broken deploy does
+/// not accumulate smoke drafts.
+pub async fn run(options: &SmokeOptions) -> SmokeReport {
+    let mut session = Session::new(options);
+    let mut report = SmokeReport::default();
+    let writes: &[(&str, StepFn)] = if options.writes_enabled() { &WRITE_STEPS } else { &[] };
+    for &(name, step) in READ_STEPS.iter().chain(writes) {
+        let step = run_step(&mut session, name, step).await;
+        let failed = step.result.is_err();
+        report.steps.push(step);
+        if failed {
+            break;
+        }
+    }
+    if session.draft.is_some() {
+        let (name, step) = WRITE_STEPS[3];
+        report.steps.push(run_step(&mut session, name, step).await);
+    }
+    report
+}
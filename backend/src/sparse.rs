@@ -0,0 +1,130 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{error::ApiError, models::{Paginated, Project}};
+
+/// Fields that are always returned, whatever the client selects.
+pub const MANDATORY_FIELDS: [&str; 1] = ["id"];
+
+/// Project fields that anonymous callers may not select (or receive) via `fields=`.
+pub const SENSITIVE_PROJECT_FIELDS: [&str; 1] = ["user_id"];
+
+/// project_fields
+///
+/// The selectable Project field names, derived from the serialized model so the whitelist
+/// can never drift from the struct. Sensitive fields are only offered to privileged callers.
+pub fn project_fields(include_sensitive: bool) -> Vec<String> {
+    match serde_json::to_value(Project::default()) {
+        Ok(Value::Object(map)) => map
+            .into_iter()
+            .map(|(key, _)| key)
+            .filter(|key| include_sensitive || !SENSITIVE_PROJECT_FIELDS.contains(&key.as_str()))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// parse_fields
+///
+/// Parses a comma-separated `fields=` value against a whitelist. Returns `None` when the
+/// parameter is absent or blank (full rows), or a 422 `unknown_fields` error listing both
+/// the offending names and every valid option.
+pub fn parse_fields(raw: Option<&str>, selectable: &[String]) -> Result<Option<Vec<String>>, ApiError> {
+    let Some(raw) = raw.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Ok(None);
+    };
+
+    let requested: Vec<String> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let unknown: Vec<&String> = requested
+        .iter()
+        .filter(|f| !selectable.contains(f))
+        .collect();
+
+    if !unknown.is_empty() {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "unknown_fields",
+            "One or more requested fields are not available.",
+        )
+        .with_details(serde_json::json!({
+            "unknown": unknown,
+            "valid": selectable,
+        })));
+    }
+
+    Ok(Some(requested))
+}
+
+/// SparseJson
+///
+/// A JSON responder that, given a field selection, serializes the payload and keeps only the
+/// selected keys (plus `MANDATORY_FIELDS`) on each object. Works for a single object or an
+/// array of objects. With no selection it behaves exactly like `Json`.
+#[derive(Debug)]
+pub struct SparseJson<T>(pub T, pub Option<Vec<String>>);
+
+fn retain(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(map) => map.retain(|key, _| {
+            MANDATORY_FIELDS.contains(&key.as_str()) || fields.iter().any(|f| f == key)
+        }),
+        Value::Array(items) => items.iter_mut().for_each(|item| retain(item, fields)),
+        _ => {}
+    }
+}
+
+/// Serializes `payload` and applies `select` to the JSON before responding.
+fn respond_selected<T: Serialize>(payload: &T, select: impl FnOnce(&mut Value)) -> Response {
+    match serde_json::to_value(payload) {
+        Ok(mut value) => {
+            select(&mut value);
+            Json(value).into_response()
+        }
+        Err(e) => {
+            tracing::error!("SparseJson serialization error: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for SparseJson<T> {
+    fn into_response(self) -> Response {
+        let SparseJson(payload, fields) = self;
+        let Some(fields) = fields else {
+            return Json(payload).into_response();
+        };
+        respond_selected(&payload, |value| retain(value, &fields))
+    }
+}
+
+/// SparsePage
+///
+/// `SparseJson` for a `Paginated` envelope: the selection applies to each of the `items`,
+/// while the envelope itself (`total_count`, `links`, ...) is always returned whole.
+#[derive(Debug)]
+pub struct SparsePage<T>(pub Paginated<T>, pub Option<Vec<String>>);
+
+impl<T: Serialize> IntoResponse for SparsePage<T> {
+    fn into_response(self) -> Response {
+        let SparsePage(page, fields) = self;
+        let Some(fields) = fields else {
+            return Json(page).into_response();
+        };
+        respond_selected(&page, |value| {
+            if let Some(items) = value.get_mut("items") {
+                retain(items, &fields);
+            }
+        })
+    }
+}
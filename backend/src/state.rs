@@ -0,0 +1,247 @@
+use axum::extract::FromRef;
+use std::sync::Arc;
+
+use crate::{
+    auth_provider,
+    clock::{Clock, SystemClock},
+    config::AppConfig,
+    error_log::{ErrorLog, ErrorLogState},
+    repository::RepositoryState,
+    settings::{SettingsService, SettingsState},
+    storage::StorageState,
+    supervisor::{TaskMonitor, TaskMonitorState},
+};
+
+/// AppState
+///
+/// Implements the **Unified State Pattern**. This is the single, thread-safe, and immutable
+/// container holding all essential application services and configuration.
+/// The application state is shared across all incoming requests.
+///
+/// Built with `AppStateBuilder`; the fields are private to the crate, so adding one only
+/// touches the builder's defaults. Outside the crate it cannot be assembled by hand:
+///
+/// ```compile_fail
+/// use fyp_portal::{AppConfig, AppState, RepositoryState, SettingsState, StorageState, TaskMonitorState, clock::Clock};
+/// use std::sync::Arc;
+///
+/// fn assemble(
+///     repo: RepositoryState,
+///     storage: StorageState,
+///     config: AppConfig,
+///     http: reqwest::Client,
+///     settings: SettingsState,
+///     tasks: TaskMonitorState,
+///     clock: Arc<dyn Clock>,
+/// ) -> AppState {
+///     AppState { repo, storage, config, http, settings, tasks, clock }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct AppState {
+    /// Repository Layer: Abstracts database access via the PgPool connection.
+    pub(crate) repo: RepositoryState,
+    /// Storage Layer: Abstracts S3/MinIO access and presigned URL generation.
+    pub(crate) storage: StorageState,
+    /// Configuration: The loaded, immutable environment configuration.
+    pub(crate) config: AppConfig,
+    /// Outbound HTTP: Shared, pooled client for the auth provider (see `auth_provider::http_client`).
+    pub(crate) http: reqwest::Client,
+    /// Runtime Settings: Snapshot of the admin-editable `settings` table, with change notifications.
+    pub(crate) settings: SettingsState,
+    /// Background Tasks: Health of the supervised loops, reported by GET /health/deep.
+    pub(crate) tasks: TaskMonitorState,
+    /// Time: The source of "now" for handlers and the background loops.
+    pub(crate) clock: Arc<dyn Clock>,
+    /// Error Log: Recent 5xx and 429 responses of this instance, reported by GET /admin/errors.
+    pub(crate) errors: ErrorLogState,
+}
+
+impl AppState {
+    pub fn repo(&self) -> &RepositoryState {
+        &self.repo
+    }
+
+    pub fn storage(&self) -> &StorageState {
+        &self.storage
+    }
+
+    pub fn config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    pub fn settings(&self) -> &SettingsState {
+        &self.settings
+    }
+
+    pub fn tasks(&self) -> &TaskMonitorState {
+        &self.tasks
+    }
+
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    pub fn errors(&self) -> &ErrorLogState {
+        &self.errors
+    }
+}
+
+/// AppStateBuilder
+///
+/// Assembles an `AppState`. Only the repository and storage have no sensible default;
+/// everything else falls back to what production uses: `AppConfig::default()`, the wall
+/// clock, empty settings, a task monitor and an error log on that clock and
+/// `auth_provider::http_client` for the final config. Tests start from `test_default()` or `production_like()` instead.
+pub struct AppStateBuilder {
+    repo: RepositoryState,
+    storage: StorageState,
+    config: AppConfig,
+    http: Option<reqwest::Client>,
+    settings: Option<SettingsState>,
+    tasks: Option<TaskMonitorState>,
+    errors: Option<ErrorLogState>,
+    clock: Arc<dyn Clock>,
+}
+
+impl AppStateBuilder {
+    pub fn new(repo: RepositoryState, storage: StorageState) -> Self {
+        Self {
+            repo,
+            storage,
+            config: AppConfig::default(),
+            http: None,
+            settings: None,
+            tasks: None,
+            errors: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// test_default
+    ///
+    /// In-memory repository, mock storage, the Local default config and a `MockClock`.
+    #[cfg(feature = "test-util")]
+    pub fn test_default() -> Self {
+        Self::new(
+            Arc::new(crate::repository::memory::InMemoryRepository::new()),
+            Arc::new(crate::storage::MockStorageService::new()),
+        )
+        .clock(Arc::new(crate::clock::MockClock::new()))
+    }
+
+    /// production_like
+    ///
+    /// `test_default()` in the Production environment, with placeholder S3 credentials
+    /// standing in for the secrets `AppConfig::load()` requires there.
+    #[cfg(feature = "test-util")]
+    pub fn production_like() -> Self {
+        Self::test_default().configure(|config| {
+            config.env = crate::config::Env::Production;
+            config.s3_endpoint = "https://s3.example.invalid".to_string();
+            config.s3_key = "test-key".to_string();
+            config.s3_secret = "test-secret".to_string();
+        })
+    }
+
+    pub fn repo(mut self, repo: RepositoryState) -> Self {
+        self.repo = repo;
+        self
+    }
+
+    pub fn storage(mut self, storage: StorageState) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    pub fn config(mut self, config: AppConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Changes individual fields of the config chosen so far.
+    pub fn configure(mut self, change: impl FnOnce(&mut AppConfig)) -> Self {
+        change(&mut self.config);
+        self
+    }
+
+    pub fn http(mut self, http: reqwest::Client) -> Self {
+        self.http = Some(http);
+        self
+    }
+
+    pub fn settings(mut self, settings: SettingsState) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    pub fn tasks(mut self, tasks: TaskMonitorState) -> Self {
+        self.tasks = Some(tasks);
+        self
+    }
+
+    pub fn errors(mut self, errors: ErrorLogState) -> Self {
+        self.errors = Some(errors);
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn build(self) -> AppState {
+        let http = self.http.unwrap_or_else(|| auth_provider::http_client(&self.config));
+        let tasks = self.tasks.unwrap_or_else(|| Arc::new(TaskMonitor::new(self.clock.clone())));
+        let errors = self.errors.unwrap_or_else(|| Arc::new(ErrorLog::new(self.clock.clone())));
+        AppState {
+            repo: self.repo,
+            storage: self.storage,
+            config: self.config,
+            http,
+            settings: self.settings.unwrap_or_else(|| Arc::new(SettingsService::new())),
+            tasks,
+            clock: self.clock,
+            errors,
+        }
+    }
+}
+
+// --- Axum FromRef Extractor Implementations ---
+
+// These implementations allow handlers to selectively pull components from the shared AppState.
+// This is critical for dependency injection and adhering to the Clean Architecture boundaries.
+
+impl FromRef<AppState> for RepositoryState {
+    fn from_ref(app_state: &AppState) -> RepositoryState {
+        app_state.repo.clone()
+    }
+}
+
+impl FromRef<AppState> for StorageState {
+    fn from_ref(app_state: &AppState) -> StorageState {
+        app_state.storage.clone()
+    }
+}
+
+impl FromRef<AppState> for SettingsState {
+    fn from_ref(app_state: &AppState) -> SettingsState {
+        app_state.settings.clone()
+    }
+}
+
+impl FromRef<AppState> for TaskMonitorState {
+    fn from_ref(app_state: &AppState) -> TaskMonitorState {
+        app_state.tasks.clone()
+    }
+}
+
+impl FromRef<AppState> for AppConfig {
+    fn from_ref(app_state: &AppState) -> AppConfig {
+        app_state.config.clone()
+    }
+}
@@ -1,9 +1,54 @@
+use crate::redact;
 use async_trait::async_trait;
 use aws_sdk_s3 as s3;
 use s3::presigning::PresigningConfig;
-use std::sync::Arc;
+use std::fmt;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+/// Lifetime of every presigned upload URL (10 minutes, as per security review). Uploads still
+/// unconfirmed after this are treated as abandoned.
+pub const PRESIGN_TTL: Duration = Duration::from_secs(600);
+
+/// Lifetime of a signed media (GET) URL: seven days, the longest SigV4 allows. Link-preview
+/// crawlers fetch the image long after the page itself was rendered.
+pub const MEDIA_URL_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// PresignedUrl
+///
+/// A signed upload URL as issued by the storage backend. The query string carries the access
+/// key id and signature, so `Debug` and `Display` only show scheme, host and path; the full URL
+/// is reachable through `expose()` / `into_inner()` for handing to the client.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PresignedUrl(String);
+
+impl PresignedUrl {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self(url.into())
+    }
+
+    /// The complete, signed URL. Only for the response body, never for logs.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for PresignedUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&redact::strip_query(&self.0))
+    }
+}
+
+impl fmt::Debug for PresignedUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PresignedUrl({})", self)
+    }
+}
+
 // 1. StorageService Contract
 /// StorageService
 ///
@@ -25,11 +70,23 @@ pub trait StorageService: Send + Sync {
     /// # Arguments
     /// * `key`: The final object key (path + filename) in the S3 bucket.
     /// * `content_type`: The expected MIME type (e.g., "video/mp4").
+    ///
+    /// Error messages must not contain signed URLs; implementations pass them through
+    /// `redact::redact_urls`.
     async fn get_presigned_upload_url(
         &self,
         key: &str,
         content_type: &str,
-    ) -> Result<String, String>;
+    ) -> Result<PresignedUrl, String>;
+
+    /// Checks whether an object has actually been uploaded under `key` (HEAD request).
+    /// Used to confirm that keys stored on a project point at real files.
+    async fn object_exists(&self, key: &str) -> Result<bool, String>;
+
+    /// Returns a URL anyone can download the object under `key` from for `MEDIA_URL_TTL`,
+    /// without credentials: a signed GET, so it also works for the private upload bucket.
+    /// Used for images shown to third parties (link previews, embeds).
+    async fn get_media_url(&self, key: &str) -> Result<PresignedUrl, String>;
 }
 
 // 2. The Real Implementation (S3/MinIO/Supabase)
@@ -102,9 +159,9 @@ impl StorageService for S3StorageClient {
         &self,
         key: &str,
         content_type: &str,
-    ) -> Result<String, String> {
+    ) -> Result<PresignedUrl, String> {
         // Expiration constrained to 10 minutes (600 seconds) as per security review.
-        let expires_in = Duration::from_secs(600);
+        let expires_in = PRESIGN_TTL;
 
         let presigned_req = self
             .client
@@ -115,9 +172,45 @@ impl StorageService for S3StorageClient {
             .content_type(content_type)
             .presigned(PresigningConfig::expires_in(expires_in).unwrap())
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| redact::redact_urls(&e.to_string()))?;
+
+        Ok(PresignedUrl::new(presigned_req.uri()))
+    }
+
+    /// object_exists
+    ///
+    /// Issues a HeadObject call. A "not found" service error means the upload never completed;
+    /// any other failure is reported as an error rather than guessed at.
+    async fn object_exists(&self, key: &str) -> Result<bool, String> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_service_error().is_some_and(|se| se.is_not_found()) => Ok(false),
+            Err(e) => Err(redact::redact_urls(&e.to_string())),
+        }
+    }
 
-        Ok(presigned_req.uri().to_string())
+    /// get_media_url
+    ///
+    /// Presigns a GetObject. Signing is local, so this makes no request to the store.
+    async fn get_media_url(&self, key: &str) -> Result<PresignedUrl, String> {
+        let presigning = PresigningConfig::expires_in(MEDIA_URL_TTL).map_err(|e| e.to_string())?;
+        let presigned_req = self
+            .client
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .presigned(presigning)
+            .await
+            .map_err(|e| redact::redact_urls(&e.to_string()))?;
+
+        Ok(PresignedUrl::new(presigned_req.uri()))
     }
 }
 
@@ -142,15 +235,34 @@ fn sanitize_key(key: &str) -> String {
 pub struct MockStorageService {
     /// When true, all operations return a simulated failure.
     pub should_fail: bool,
+    /// Every key a presigned URL was issued for, in order (shared between clones).
+    pub issued_keys: Arc<Mutex<Vec<String>>>,
+}
+
+impl Default for MockStorageService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MockStorageService {
     pub fn new() -> Self {
-        Self { should_fail: false }
+        Self {
+            should_fail: false,
+            issued_keys: Arc::default(),
+        }
     }
 
     pub fn new_failing() -> Self {
-        Self { should_fail: true }
+        Self {
+            should_fail: true,
+            ..Self::new()
+        }
+    }
+
+    /// Number of presigned URLs handed out so far.
+    pub fn issued_count(&self) -> usize {
+        self.issued_keys.lock().unwrap().len()
     }
 }
 
@@ -164,18 +276,37 @@ impl StorageService for MockStorageService {
         &self,
         key: &str,
         _content_type: &str,
-    ) -> Result<String, String> {
+    ) -> Result<PresignedUrl, String> {
         if self.should_fail {
             return Err("Mock Storage Error: Simulation requested".to_string());
         }
 
         let sanitized_key = sanitize_key(key);
+        self.issued_keys.lock().unwrap().push(sanitized_key.clone());
 
         // Returns a deterministic, local-style URL for mock assertions.
-        Ok(format!(
+        Ok(PresignedUrl::new(format!(
             "http://localhost:9000/mock-bucket/{}?signature=fake",
             sanitized_key
-        ))
+        )))
+    }
+
+    /// Every non-empty key is treated as uploaded; `should_fail` simulates an unreachable store.
+    async fn object_exists(&self, key: &str) -> Result<bool, String> {
+        if self.should_fail {
+            return Err("Mock Storage Error: Simulation requested".to_string());
+        }
+        Ok(!sanitize_key(key).is_empty())
+    }
+
+    async fn get_media_url(&self, key: &str) -> Result<PresignedUrl, String> {
+        if self.should_fail {
+            return Err("Mock Storage Error: Simulation requested".to_string());
+        }
+        Ok(PresignedUrl::new(format!(
+            "http://localhost:9000/mock-bucket/{}?signature=fake",
+            sanitize_key(key)
+        )))
     }
 }
 
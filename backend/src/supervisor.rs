@@ -0,0 +1,174 @@
+use crate::{
+    clock::{Clock, SystemClock},
+    models::TaskHealth,
+    repository::RepositoryError,
+};
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    future::Future,
+    panic::AssertUnwindSafe,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+/// Wait before the first retry of a failed pass; doubled for every further consecutive failure.
+pub const MIN_BACKOFF: Duration = Duration::from_secs(1);
+/// Longest wait between retries, however long a task keeps failing.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+/// A task with no successful pass for this many intervals is reported as stale.
+pub const STALE_AFTER_INTERVALS: u32 = 3;
+
+/// The wait before retry number `failures` (1 for the first).
+pub fn backoff(failures: u32) -> Duration {
+    MIN_BACKOFF
+        .checked_mul(1 << failures.saturating_sub(1).min(16))
+        .map_or(MAX_BACKOFF, |d| d.min(MAX_BACKOFF))
+}
+
+struct TaskRecord {
+    interval: Duration,
+    started_at: DateTime<Utc>,
+    last_success: Option<DateTime<Utc>>,
+    consecutive_failures: u32,
+    restarts: u64,
+}
+
+/// TaskMonitor
+///
+/// Health of the supervised background tasks, by name, for GET /health/deep. Written by
+/// `spawn_supervised` after every pass; staleness is judged against the monitor's clock.
+pub struct TaskMonitor {
+    clock: Arc<dyn Clock>,
+    tasks: RwLock<BTreeMap<&'static str, TaskRecord>>,
+}
+
+/// TaskMonitorState
+///
+/// The shared handle to the task monitor held in `AppState`.
+pub type TaskMonitorState = Arc<TaskMonitor>;
+
+impl TaskMonitor {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self { clock, tasks: RwLock::default() }
+    }
+
+    fn register(&self, name: &'static str, interval: Duration) {
+        let record = TaskRecord {
+            interval,
+            started_at: self.clock.now(),
+            last_success: None,
+            consecutive_failures: 0,
+            restarts: 0,
+        };
+        self.tasks.write().unwrap().insert(name, record);
+    }
+
+    fn record_success(&self, name: &'static str) {
+        if let Some(record) = self.tasks.write().unwrap().get_mut(name) {
+            record.last_success = Some(self.clock.now());
+            record.consecutive_failures = 0;
+        }
+    }
+
+    /// Counts a failed pass and the restart that follows it; returns the consecutive failures.
+    fn record_failure(&self, name: &'static str) -> u32 {
+        let mut tasks = self.tasks.write().unwrap();
+        let Some(record) = tasks.get_mut(name) else {
+            return 1;
+        };
+        record.consecutive_failures += 1;
+        record.restarts += 1;
+        record.consecutive_failures
+    }
+
+    /// report
+    ///
+    /// Every registered task, by name. A task is stale once `STALE_AFTER_INTERVALS` intervals
+    /// have passed since its last success, or since it was started if it never succeeded.
+    pub fn report(&self) -> Vec<TaskHealth> {
+        let now = self.clock.now();
+        self.tasks
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, record)| {
+                let window = chrono::Duration::from_std(record.interval * STALE_AFTER_INTERVALS)
+                    .unwrap_or(chrono::Duration::MAX);
+                let since = record.last_success.unwrap_or(record.started_at);
+                TaskHealth {
+                    name: name.to_string(),
+                    interval_secs: record.interval.as_secs(),
+                    last_success: record.last_success,
+                    consecutive_failures: record.consecutive_failures,
+                    restarts: record.restarts,
+                    stale: now - since > window,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for TaskMonitor {
+    fn default() -> Self {
+        Self::new(Arc::new(SystemClock))
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "non-string panic payload".to_string())
+}
+
+/// spawn_supervised
+///
+/// Runs a fresh future from `pass` once per `interval`, starting immediately, and keeps doing
+/// so whatever the passes do. A pass that returns an error or panics is logged with the task
+/// name and restarted after `backoff` (1s, 2s, 4s, ... up to `MAX_BACKOFF`) instead of
+/// waiting for the next tick; the regular schedule resumes after the next success. A slow
+/// pass delays the next one rather than triggering catch-up passes. Only aborting the
+/// returned handle stops the task.
+pub fn spawn_supervised<F, Fut>(
+    monitor: TaskMonitorState,
+    name: &'static str,
+    interval: Duration,
+    pass: F,
+) -> JoinHandle<()>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), RepositoryError>> + Send + 'static,
+{
+    monitor.register(name, interval);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            loop {
+                let error = match AssertUnwindSafe(async { pass().await }).catch_unwind().await {
+                    Ok(Ok(())) => break,
+                    Ok(Err(e)) => e.to_string(),
+                    Err(panic) => format!("panicked: {}", panic_message(&*panic)),
+                };
+                let failures = monitor.record_failure(name);
+                let retry_in = backoff(failures);
+                tracing::error!(
+                    task = name,
+                    error = %error,
+                    failures,
+                    retry_in_secs = retry_in.as_secs(),
+                    "Background task failed; restarting"
+                );
+                tokio::time::sleep(retry_in).await;
+            }
+            monitor.record_success(name);
+            ticker.reset();
+        }
+    })
+}
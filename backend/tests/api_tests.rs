@@ -1,7 +1,7 @@
 use fyp_portal::{
-    AppConfig, AppState, MockStorageService, create_router,
-    models::Project,
-    repository::{PostgresRepository, RepositoryState},
+    AppConfig, AppStateBuilder, MockStorageService, create_router,
+    models::{ProjectStatus, ActivityItem, CommentPage, NotificationResponse, OutboxJob, Paginated, Project, PublicStats, User, ViewResponse, VoteResponse},
+    repository::{PostgresRepository, Repository, RepositoryState, memory::InMemoryRepository},
     storage::StorageState,
 };
 use sqlx::postgres::PgPoolOptions;
@@ -27,14 +27,20 @@ async fn spawn_app() -> TestApp {
         .expect("Failed to connect to Postgres in tests");
 
     let repo = Arc::new(PostgresRepository::new(pool.clone())) as RepositoryState;
+    let address = serve(repo, AppConfig::load()).await;
+
+    TestApp { address, pool }
+}
+
+/// Serves the full router over `repo` on an ephemeral port and returns its base URL.
+async fn serve(repo: RepositoryState, config: AppConfig) -> String {
     let storage = Arc::new(MockStorageService::new()) as StorageState;
-    let config = AppConfig::load();
 
-    let state = AppState {
-        repo,
-        storage,
-        config,
-    };
+    let state = AppStateBuilder::test_default()
+        .repo(repo)
+        .storage(storage)
+        .config(config)
+        .build();
     let router = create_router(state);
 
     let listener = TcpListener::bind("127.0.0.1:0")
@@ -47,7 +53,7 @@ async fn spawn_app() -> TestApp {
         axum::serve(listener, router).await.unwrap();
     });
 
-    TestApp { address, pool }
+    address
 }
 
 #[tokio::test]
@@ -55,7 +61,7 @@ async fn test_health_check() {
     let app = spawn_app().await;
     let client = reqwest::Client::new();
     let response = client
-        .get(&format!("{}/health", app.address))
+        .get(format!("{}/health", app.address))
         .send()
         .await
         .expect("req fail");
@@ -67,44 +73,454 @@ async fn test_project_lifecycle() {
     let app = spawn_app().await;
     let client = reqwest::Client::new();
     let user_id = Uuid::new_v4();
+    let voter_id = Uuid::new_v4();
 
-    // Seed User
-    sqlx::query!(
-        "INSERT INTO auth.users (id, email) VALUES ($1, $2) ON CONFLICT DO NOTHING",
-        user_id,
-        "t@t.com"
-    )
-    .execute(&app.pool)
-    .await
-    .unwrap();
-    sqlx::query!(
-        "INSERT INTO profiles (id, email, role) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
-        user_id,
-        "t@t.com",
-        "student"
-    )
-    .execute(&app.pool)
-    .await
-    .unwrap();
+    // Seed the owner and a classmate who votes
+    for (id, email) in [(user_id, "t@t.com".to_string()), (voter_id, format!("v{}@t.com", voter_id.simple()))] {
+        sqlx::query!(
+            "INSERT INTO auth.users (id, email) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+            id,
+            email
+        )
+        .execute(&app.pool)
+        .await
+        .unwrap();
+        sqlx::query!(
+            "INSERT INTO profiles (id, email, role) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+            id,
+            email,
+            "student"
+        )
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    }
 
-    // Create
-    let response = client.post(&format!("{}/projects", app.address))
+    // Create (the database outlives the test, so the title is unique to this run)
+    let response = client.post(format!("{}/projects", app.address))
         .header("x-user-id", user_id.to_string())
         .json(&serde_json::json!({
-            "title": "Bot", "abstract_text": "AI", "author_name": "Robo", "year": 2025, "cover_image_key": "img.jpg"
+            "title": format!("Bot {}", user_id.simple()), "abstract_text": "AI", "author_name": "Robo", "year": 2025, "cover_image_key": "uploads/img.jpg"
+        }))
+        .send().await.expect("post fail");
+    assert_eq!(response.status(), 200);
+    let p: Project = response.json().await.unwrap();
+
+    // Vote: a draft cannot be liked yet. Once approved the owner still may not; for anyone else
+    // the first one counts, and a repeat is a 409 with the same like state.
+    let vote = |voter: Uuid| {
+        client
+            .post(format!("{}/projects/{}/vote", app.address, p.id))
+            .header("x-user-id", voter.to_string())
+            .send()
+    };
+    assert_eq!(vote(voter_id).await.unwrap().status(), 404);
+    sqlx::query!("UPDATE projects SET status = 'approved' WHERE id = $1", p.id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(vote(user_id).await.unwrap().status(), 403);
+    let resp = vote(voter_id).await.unwrap();
+    assert_eq!(resp.status(), 200);
+    let body: VoteResponse = resp.json().await.unwrap();
+    assert_eq!(body, VoteResponse { already_liked: false, like_count: 1 });
+
+    let resp = vote(voter_id).await.unwrap();
+    assert_eq!(resp.status(), 409);
+    let body: VoteResponse = resp.json().await.unwrap();
+    assert_eq!(body, VoteResponse { already_liked: true, like_count: 1 });
+}
+
+/// The lifecycle above, end to end through the router on `InMemoryRepository`: create, approve,
+/// vote, comment, and the owner is notified. No database required.
+#[tokio::test]
+async fn test_project_lifecycle_in_memory() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let student = Uuid::new_v4();
+    let admin = Uuid::new_v4();
+    let reader = Uuid::new_v4();
+    for (id, email, role) in [
+        (student, "s@t.com", "student"),
+        (admin, "a@t.com", "admin"),
+        (reader, "r@t.com", "student"),
+    ] {
+        repo.create_user(User { id, email: email.to_string(), role: role.to_string() })
+            .await;
+    }
+    let address = serve(repo.clone(), AppConfig::default()).await;
+    let client = reqwest::Client::new();
+
+    // Create
+    let response = client.post(format!("{}/projects", address))
+        .header("x-user-id", student.to_string())
+        .json(&serde_json::json!({
+            "title": "Bot", "abstract_text": "AI", "author_name": "Robo", "year": 2025, "cover_image_key": "uploads/img.jpg"
         }))
         .send().await.expect("post fail");
     assert_eq!(response.status(), 200);
     let p: Project = response.json().await.unwrap();
 
-    // Vote
+    // Vote: a classmate cannot like it before approval
+    let vote = |voter: Uuid| {
+        client
+            .post(format!("{}/projects/{}/vote", address, p.id))
+            .header("x-user-id", voter.to_string())
+            .send()
+    };
+    assert_eq!(vote(reader).await.unwrap().status(), 404);
+
+    // Not listed until approved.
+    let list: Paginated<Project> = client
+        .get(format!("{}/projects", address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(list.total_count, 0);
+    let resp = client.post(format!("{}/projects/{}/view", address, p.id)).send().await.unwrap();
+    assert_eq!(resp.status(), 404);
+
+    // Approve
     let resp = client
-        .post(&format!("{}/projects/{}/vote", app.address, p.id))
-        .header("x-user-id", user_id.to_string())
+        .put(format!("{}/admin/projects/{}/status", address, p.id))
+        .header("x-user-id", admin.to_string())
+        .json(&serde_json::json!({ "status": "approved" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let approved: Project = resp.json().await.unwrap();
+    assert_eq!(approved.status, ProjectStatus::Approved);
+    assert!(approved.is_public, "is_public is still derived from the status");
+    let list: Paginated<Project> = client
+        .get(format!("{}/projects", address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(list.items.iter().map(|p| p.id).collect::<Vec<_>>(), vec![p.id]);
+
+    // Vote: refused for the owner, counted for a classmate
+    for (voter, expected) in [(student, 403), (reader, 200)] {
+        assert_eq!(vote(voter).await.unwrap().status(), expected);
+    }
+
+    // View, anonymously
+    for expected in [1, 2] {
+        let resp = client.post(format!("{}/projects/{}/view", address, p.id)).send().await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let body: ViewResponse = resp.json().await.unwrap();
+        assert_eq!(body.view_count, expected);
+    }
+    let viewed: Project = client
+        .get(format!("{}/projects/{}", address, p.id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(viewed.view_count, 2);
+
+    // Comment
+    let resp = client
+        .post(format!("{}/projects/{}/comments", address, p.id))
+        .header("x-user-id", reader.to_string())
+        .json(&serde_json::json!({ "text": "Nice work" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+    let CommentPage { comments, .. } = client
+        .get(format!("{}/projects/{}/comments", address, p.id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].author_email.as_deref(), Some("r@t.com"));
+
+    // Notify
+    let notifications: Vec<NotificationResponse> = client
+        .get(format!("{}/notifications", address))
+        .header("x-user-id", student.to_string())
         .send()
         .await
+        .unwrap()
+        .json()
+        .await
         .unwrap();
+    let mut kinds: Vec<&str> = notifications.iter().map(|n| n.notification_type.as_str()).collect();
+    kinds.sort();
+    assert_eq!(kinds, vec!["comment", "like"]);
+    assert!(notifications.iter().all(|n| n.actor_email == "r@t.com" && n.project_title == "Bot"));
+}
+
+/// Hiding a project must take it out of every public aggregate on the very next request, not
+/// just the plain listing.
+#[tokio::test]
+async fn test_hidden_project_leaves_public_aggregates_in_memory() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let (owner, admin) = (Uuid::new_v4(), Uuid::new_v4());
+    let fans = [Uuid::new_v4(), Uuid::new_v4()];
+    repo.create_user(User { id: owner, email: "o@t.com".to_string(), role: "student".to_string() }).await;
+    repo.create_user(User { id: admin, email: "a@t.com".to_string(), role: "admin".to_string() }).await;
+    for fan in fans {
+        repo.create_user(User { id: fan, email: format!("{fan}@t.com"), role: "student".to_string() }).await;
+    }
+    let address = serve(repo.clone(), AppConfig::default()).await;
+    let client = reqwest::Client::new();
+    let set_status = async |id: Uuid, is_public: bool| {
+        let resp = client
+            .put(format!("{}/admin/projects/{}/status", address, id))
+            .header("x-user-id", admin.to_string())
+            .json(&is_public)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+    };
+    let get = async |path: &str| client.get(format!("{}{}", address, path)).send().await.unwrap();
+    let ids = async |path: &str| -> Vec<Uuid> {
+        let body: serde_json::Value = get(path).await.json().await.unwrap();
+        let items = body.get("items").unwrap_or(&body).as_array().unwrap().clone();
+        items.iter().map(|p| p["id"].as_str().unwrap().parse().unwrap()).collect()
+    };
+
+    let mut created = Vec::new();
+    for title in ["Star", "Other"] {
+        let resp = client.post(format!("{}/projects", address))
+            .header("x-user-id", owner.to_string())
+            .json(&serde_json::json!({
+                "title": title, "abstract_text": "A", "author_name": "O", "year": 2025, "cover_image_key": "uploads/img.jpg"
+            }))
+            .send().await.unwrap();
+        let p: Project = resp.json().await.unwrap();
+        set_status(p.id, true).await;
+        created.push(p.id);
+    }
+    let [star, other] = created[..] else { unreachable!() };
+
+    // Liked, viewed and discussed into the top spot.
+    for fan in fans {
+        let resp = client
+            .post(format!("{}/projects/{}/vote", address, star))
+            .header("x-user-id", fan.to_string())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        client.post(format!("{}/projects/{}/view", address, star)).send().await.unwrap();
+    }
+    let resp = client
+        .post(format!("{}/projects/{}/comments", address, star))
+        .header("x-user-id", fans[0].to_string())
+        .json(&serde_json::json!({ "text": "Great" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+
+    assert_eq!(ids("/projects/featured").await, vec![star, other]);
+    assert_eq!(ids("/projects?sort=most_liked").await, vec![star, other]);
+    assert_eq!(ids("/projects?sort=most_viewed").await, vec![star, other]);
+    let stats: PublicStats = get("/stats/public").await.json().await.unwrap();
+    assert_eq!((stats.public_projects, stats.total_likes), (2, 2));
+    let CommentPage { comments, .. } = get(&format!("/projects/{star}/comments")).await.json().await.unwrap();
+    assert_eq!(comments.len(), 1);
+
+    set_status(star, false).await;
+
+    assert_eq!(ids("/projects/featured").await, vec![other]);
+    assert_eq!(ids("/projects?sort=most_liked").await, vec![other]);
+    assert_eq!(ids("/projects?sort=most_viewed").await, vec![other]);
+    let stats: PublicStats = get("/stats/public").await.json().await.unwrap();
+    assert_eq!((stats.public_projects, stats.total_likes), (1, 0));
+    let CommentPage { comments, .. } = get(&format!("/projects/{star}/comments")).await.json().await.unwrap();
+    assert!(comments.is_empty());
+    assert_eq!(get(&format!("/projects/{star}")).await.status(), 404);
+    let resp = client.post(format!("{}/projects/{}/view", address, star)).send().await.unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+/// A comment with more recipients than `fanout_sync_threshold` is accepted with a job id
+/// instead of notifying anyone in the request; admins follow the job at /admin/outbox/{id}.
+#[tokio::test]
+async fn test_large_comment_fanout_is_queued_in_memory() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let (owner, admin, commenter) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    for (id, role) in [(owner, "student"), (admin, "admin"), (commenter, "student")] {
+        repo.create_user(User { id, email: format!("{id}@t.com"), role: role.to_string() }).await;
+    }
+    let config = AppConfig { fanout_sync_threshold: 1, ..AppConfig::default() };
+    let address = serve(repo.clone(), config).await;
+    let client = reqwest::Client::new();
+    let p = repo
+        .create_project(
+            fyp_portal::models::CreateProjectRequest {
+                title: "Busy".to_string(),
+                abstract_text: "A".to_string(),
+                author_name: "O".to_string(),
+                year: 2025,
+                cover_image_key: "img.jpg".to_string(),
+                ..Default::default()
+            },
+            owner,
+        )
+        .await;
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    let comment = async |user_id: Uuid| {
+        client
+            .post(format!("{}/projects/{}/comments", address, p.id))
+            .header("x-user-id", user_id.to_string())
+            .json(&serde_json::json!({ "text": "Hi" }))
+            .send()
+            .await
+            .unwrap()
+    };
+    let job = async |user_id: Uuid, id: i64| {
+        client
+            .get(format!("{}/admin/outbox/{}", address, id))
+            .header("x-user-id", user_id.to_string())
+            .send()
+            .await
+            .unwrap()
+    };
+
+    // One recipient (the owner) is within the threshold.
+    let resp = comment(commenter).await;
+    assert_eq!(resp.status(), 201);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert!(body.get("fanout_job_id").is_none());
+    assert_eq!(body["comment"], "Hi");
+
+    // Two (the owner and the first commenter) are not.
+    let resp = comment(admin).await;
+    assert_eq!(resp.status(), 202);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    let job_id = body["fanout_job_id"].as_i64().unwrap();
+    assert_eq!(repo.get_notifications(owner).await.len(), 1);
+
+    let resp = job(admin, job_id).await;
+    assert_eq!(resp.status(), 200);
+    let queued: OutboxJob = resp.json().await.unwrap();
+    assert_eq!((queued.status.as_str(), queued.total, queued.processed), ("pending", 2, 0));
+    assert_eq!(job(commenter, job_id).await.status(), 403);
+    assert_eq!(job(admin, job_id + 1).await.status(), 404);
+}
+
+/// GET /me/activity serialises each entry with its `kind` tag and pages like the listings.
+#[tokio::test]
+async fn test_my_activity_in_memory() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let (owner, fan) = (Uuid::new_v4(), Uuid::new_v4());
+    for id in [owner, fan] {
+        repo.create_user(User { id, email: format!("{id}@t.com"), role: "student".to_string() }).await;
+    }
+    let address = serve(repo.clone(), AppConfig::default()).await;
+    let client = reqwest::Client::new();
+    let p = repo
+        .create_project(
+            fyp_portal::models::CreateProjectRequest {
+                title: "Robot Arm".to_string(),
+                abstract_text: "A".to_string(),
+                author_name: "O".to_string(),
+                year: 2025,
+                cover_image_key: "img.jpg".to_string(),
+                ..Default::default()
+            },
+            owner,
+        )
+        .await;
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    repo.like_project(fyp_portal::models::Like { user_id: fan, project_id: p.id }).await.unwrap();
+    repo.add_comment(p.id, fan, "Great demo".to_string(), 500).await.unwrap().unwrap();
+    let activity = async |user: Uuid, query: &str| {
+        client
+            .get(format!("{}/me/activity{}", address, query))
+            .header("x-user-id", user.to_string())
+            .send()
+            .await
+            .unwrap()
+    };
+
+    let resp = activity(fan, "").await;
     assert_eq!(resp.status(), 200);
+    let page: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(page["total_count"], 2);
+    assert_eq!(page["items"][0]["kind"], "commented");
+    assert_eq!(page["items"][0]["comment"], "Great demo");
+    assert_eq!(page["items"][0]["project_title"], "Robot Arm");
+    assert_eq!(page["items"][1]["kind"], "liked");
+    assert_eq!(page["items"][1]["project_id"], p.id.to_string());
+
+    let page: Paginated<ActivityItem> = activity(owner, "?per_page=1").await.json().await.unwrap();
+    assert_eq!(page.total_count, 1);
+    assert!(matches!(&page.items[..], [ActivityItem::StatusChanged { is_public: true, .. }]));
+
+    assert_eq!(activity(fan, "?per_page=0").await.status(), 422);
+    let resp = client.get(format!("{}/me/activity", address)).send().await.unwrap();
+    assert_eq!(resp.status(), 401);
+}
+
+/// GET /projects/slug/{slug} answers exactly like GET /projects/{id}, including for slugs the
+/// project had before a rename.
+#[tokio::test]
+async fn test_project_slug_route_in_memory() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let owner = Uuid::new_v4();
+    repo.create_user(User { id: owner, email: "o@t.com".to_string(), role: "student".to_string() }).await;
+    let address = serve(repo.clone(), AppConfig::default()).await;
+    let client = reqwest::Client::new();
+    let p = repo
+        .create_project(
+            fyp_portal::models::CreateProjectRequest {
+                title: "Smart Greenhouse!".to_string(),
+                abstract_text: "A".to_string(),
+                author_name: "O".to_string(),
+                year: 2025,
+                cover_image_key: "img.jpg".to_string(),
+                ..Default::default()
+            },
+            owner,
+        )
+        .await;
+    assert_eq!(p.slug, "smart-greenhouse-2025");
+    let get = async |path: String, user: Option<Uuid>| {
+        let mut request = client.get(format!("{}{}", address, path));
+        if let Some(user) = user {
+            request = request.header("x-user-id", user.to_string());
+        }
+        request.send().await.unwrap()
+    };
+
+    // Drafts follow the detail route's visibility rules.
+    assert_eq!(get(format!("/projects/slug/{}", p.slug), None).await.status(), 404);
+    assert_eq!(get(format!("/projects/slug/{}", p.slug), Some(owner)).await.status(), 200);
+
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    let by_id: serde_json::Value = get(format!("/projects/{}", p.id), None).await.json().await.unwrap();
+    let resp = get(format!("/projects/slug/{}", p.slug), None).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.json::<serde_json::Value>().await.unwrap(), by_id);
+
+    let rename = fyp_portal::models::UpdateProjectRequest {
+        title: Some("Greenhouse Monitor".to_string()),
+        ..Default::default()
+    };
+    repo.update_project(p.id, owner, rename).await.unwrap();
+    for slug in ["greenhouse-monitor-2025", "smart-greenhouse-2025"] {
+        let body: serde_json::Value = get(format!("/projects/slug/{slug}"), None).await.json().await.unwrap();
+        assert_eq!(body["id"], p.id.to_string());
+        assert_eq!(body["slug"], "greenhouse-monitor-2025");
+    }
+    assert_eq!(get("/projects/slug/unknown-2025".to_string(), None).await.status(), 404);
 }
 
 #[tokio::test]
@@ -132,29 +548,29 @@ async fn test_get_public_projects() {
     .unwrap();
 
     // 1. Create Private Project
-    let resp = client.post(&format!("{}/projects", app.address))
+    let resp = client.post(format!("{}/projects", app.address))
         .header("x-user-id", user_id.to_string())
         .json(&serde_json::json!({
-            "title": "Secret", "abstract_text": "Shh", "author_name": "Spy", "year": 2025, "cover_image_key": "img.jpg"
+            "title": format!("Secret {}", user_id.simple()), "abstract_text": "Shh", "author_name": "Spy", "year": 2025, "cover_image_key": "uploads/img.jpg"
         }))
         .send().await.unwrap();
     let p: Project = resp.json().await.unwrap();
 
     // 2. Verify NOT in public list
     let list_resp = client
-        .get(&format!("{}/projects", app.address))
+        .get(format!("{}/projects", app.address))
         .send()
         .await
         .unwrap();
-    let list: Vec<Project> = list_resp.json().await.unwrap();
+    let list: Paginated<Project> = list_resp.json().await.unwrap();
     assert!(
-        list.iter().all(|proj| proj.id != p.id),
+        list.items.iter().all(|proj| proj.id != p.id),
         "Private project should not be listed"
     );
 
     // 3. Approve Project (Set Public)
     let status_resp = client
-        .put(&format!("{}/admin/projects/{}/status", app.address, p.id))
+        .put(format!("{}/admin/projects/{}/status", app.address, p.id))
         .header("x-user-id", user_id.to_string())
         .json(&true)
         .send()
@@ -164,7 +580,7 @@ async fn test_get_public_projects() {
 
     // 4. Verify IS in public list
     let status_resp = client
-        .put(&format!("{}/admin/projects/{}/status", app.address, p.id))
+        .put(format!("{}/admin/projects/{}/status", app.address, p.id))
         .header("x-user-id", user_id.to_string())
         .json(&true)
         .send()
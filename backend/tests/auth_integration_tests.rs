@@ -4,7 +4,7 @@ use axum::{
     http::{Method, Request, StatusCode, Uri, header, request::Parts}, // Added Method, Uri, Request
 };
 use fyp_portal::{
-    AppState,
+    AppState, AppStateBuilder,
     auth::{AuthUser, Claims},
     config::Env,
     models::{Project, User},
@@ -29,17 +29,26 @@ impl Repository for MockAuthRepo {
     // Implement all other unused trait methods with placeholders (ensuring they compile)
     async fn get_projects(
         &self,
-        _year: Option<i32>,
-        _search: Option<String>,
-    ) -> Vec<fyp_portal::models::Project> {
-        vec![]
+        _filter: fyp_portal::repository::ProjectListFilter,
+        _page: fyp_portal::pagination::PageRequest,
+    ) -> (Vec<fyp_portal::models::Project>, i64) {
+        (vec![], 0)
     }
     async fn get_all_projects(&self) -> Vec<fyp_portal::models::Project> {
         vec![]
     }
-    async fn get_top_projects(&self, _limit: i64) -> Vec<fyp_portal::models::Project> {
+    async fn get_pending_reviews(&self, _limit: i64) -> Vec<fyp_portal::models::PendingReview> {
+        vec![]
+    }
+    async fn get_top_projects(&self, _limit: i64, _min_likes: i64, _year: Option<i32>) -> Vec<fyp_portal::models::Project> {
+        vec![]
+    }
+    async fn get_year_archive(&self, _year: i32) -> Vec<fyp_portal::models::Project> {
         vec![]
     }
+    async fn get_random_public_project(&self, _seed: Option<i64>) -> Option<fyp_portal::models::Project> {
+        None
+    }
     async fn get_project(&self, _id: Uuid) -> Option<fyp_portal::models::Project> {
         None
     }
@@ -50,23 +59,38 @@ impl Repository for MockAuthRepo {
     ) -> fyp_portal::models::Project {
         fyp_portal::models::Project::default()
     }
-    async fn like_project(&self, _like: fyp_portal::models::Like) -> bool {
+    async fn like_project(&self, _like: fyp_portal::models::Like) -> Option<fyp_portal::models::LikeOutcome> {
+        None
+    }
+    async fn unlike_project(&self, _like: fyp_portal::models::Like) -> bool {
         false
     }
+    async fn get_liked_project_ids(&self, _u: Uuid, _ids: &[Uuid]) -> Vec<Uuid> {
+        vec![]
+    }
+    async fn get_project_likers(&self, _p: Uuid, _page: fyp_portal::pagination::PageRequest) -> (Vec<fyp_portal::models::ProjectLiker>, i64) {
+        (vec![], 0)
+    }
     async fn set_project_status(
         &self,
         _id: Uuid,
-        _is_public: bool,
+        _status: fyp_portal::models::ProjectStatus,
     ) -> Option<fyp_portal::models::Project> {
         None
     }
+    async fn set_projects_status_bulk(&self, _ids: &[Uuid], _is_public: bool) -> fyp_portal::models::BulkStatusOutcome {
+        fyp_portal::models::BulkStatusOutcome::default()
+    }
     async fn create_user(&self, _user: User) -> User {
         User::default()
     }
+    async fn get_users_by_email_prefix(&self, _prefixes: &[String]) -> Vec<User> {
+        Vec::new()
+    }
     async fn get_stats(&self) -> fyp_portal::models::AdminDashboardStats {
         fyp_portal::models::AdminDashboardStats::default()
     }
-    async fn get_my_projects(&self, _user_id: Uuid) -> Vec<fyp_portal::models::Project> {
+    async fn get_my_project_summaries(&self, _user_id: Uuid) -> Vec<fyp_portal::models::MyProjectSummary> {
         vec![]
     }
     async fn delete_project(&self, _id: Uuid, _user_id: Uuid) -> bool {
@@ -77,7 +101,7 @@ impl Repository for MockAuthRepo {
         _id: Uuid,
         _user_id: Uuid,
         _req: fyp_portal::models::UpdateProjectRequest,
-    ) -> Option<fyp_portal::models::Project> {
+    ) -> Option<fyp_portal::models::ProjectUpdateResult> {
         None
     }
     async fn add_comment(
@@ -85,15 +109,53 @@ impl Repository for MockAuthRepo {
         _project_id: Uuid,
         _user_id: Uuid,
         _text: String,
-    ) -> fyp_portal::models::Comment {
-        fyp_portal::models::Comment::default()
+        _fanout_threshold: i64,
+    ) -> Result<Option<fyp_portal::models::PostedComment>, fyp_portal::repository::RepositoryError> {
+        Ok(Some(fyp_portal::models::PostedComment::default()))
     }
-    async fn get_comments(&self, _project_id: Uuid) -> Vec<fyp_portal::models::Comment> {
+    async fn get_comments(
+        &self,
+        _project_id: Uuid,
+        _o: fyp_portal::models::CommentOrder,
+        _h: fyp_portal::repository::HiddenComments,
+    ) -> Vec<fyp_portal::models::Comment> {
         vec![]
     }
+    async fn get_comments_before(
+        &self,
+        _project_id: Uuid,
+        _h: fyp_portal::repository::HiddenComments,
+        _before: Option<fyp_portal::repository::CommentCursor>,
+        _limit: i64,
+    ) -> Vec<fyp_portal::models::Comment> {
+        vec![]
+    }
+    async fn get_comments_after(
+        &self,
+        _project_id: Uuid,
+        _h: fyp_portal::repository::HiddenComments,
+        _after: Option<fyp_portal::repository::CommentCursor>,
+        _limit: i64,
+    ) -> Vec<fyp_portal::models::Comment> {
+        vec![]
+    }
+    async fn get_recent_comments(&self, _user_id: Uuid) -> fyp_portal::models::RecentComments {
+        fyp_portal::models::RecentComments::default()
+    }
+    async fn count_comments(&self, _project_id: Uuid) -> Option<i64> {
+        None
+    }
     async fn delete_project_admin(&self, _id: Uuid) -> bool {
         false
     }
+    async fn update_comment(
+        &self,
+        _id: i64,
+        _user_id: Uuid,
+        _req: fyp_portal::models::UpdateCommentRequest,
+    ) -> Option<fyp_portal::models::CommentEditOutcome> {
+        None
+    }
     async fn delete_comment(&self, _id: i64, _user_id: Uuid) -> bool {
         false
     }
@@ -109,6 +171,9 @@ impl Repository for MockAuthRepo {
     async fn mark_notification_read(&self, _notification_id: Uuid, _user_id: Uuid) -> bool {
         false
     }
+    async fn create_notification(&self, _n: fyp_portal::models::NewNotification) -> Option<fyp_portal::models::Notification> {
+        None
+    }
 
     async fn get_project_authorized(&self, id: Uuid, user_id: Uuid) -> Option<Project> {
         // Mock implementation - you can customize based on your test needs
@@ -121,6 +186,256 @@ impl Repository for MockAuthRepo {
         // Mock implementation - only return if public
         self.get_project(id).await.filter(|p| p.is_public)
     }
+    async fn get_project_detail(&self, id: Uuid, viewer: fyp_portal::repository::ProjectViewer) -> Option<(Project, Option<fyp_portal::models::ProjectOwner>)> {
+        let project = match viewer {
+            fyp_portal::repository::ProjectViewer::Public => self.get_public_project(id).await,
+            fyp_portal::repository::ProjectViewer::User(user_id) => self.get_project_authorized(id, user_id).await,
+            fyp_portal::repository::ProjectViewer::Admin => self.get_project(id).await,
+        }?;
+        Some((project, None))
+    }
+
+    async fn set_comments_locked(&self, _id: Uuid, _user_id: Uuid, _l: bool) -> Option<Project> {
+        None
+    }
+    async fn set_comments_locked_admin(&self, _id: Uuid, _l: bool) -> Option<Project> {
+        None
+    }
+    async fn lock_comments_for_year(&self, _year: i32) -> i64 {
+        0
+    }
+    async fn are_comments_locked(&self, _project_id: Uuid) -> bool {
+        false
+    }
+    async fn mark_project_submitted(&self, _id: Uuid) -> Option<Project> {
+        None
+    }
+    async fn resubmit_project(&self, _id: Uuid, _owner_id: Uuid, _max_attempts: i32) -> Option<fyp_portal::models::ResubmitOutcome> {
+        None
+    }
+    async fn reassign_project_owner(&self, _project_id: Uuid, _new_owner_id: Uuid, _actor_id: Uuid) -> Option<fyp_portal::models::OwnerTransferOutcome> {
+        None
+    }
+    async fn get_supervisor_report(&self, _year: Option<i32>) -> Vec<fyp_portal::models::SupervisorStats> {
+        vec![]
+    }
+    async fn notify_status_change(&self, _p: Uuid, _a: Uuid, _r: Option<String>) -> bool {
+        false
+    }
+    async fn set_subscription(&self, _p: Uuid, _u: Uuid, _s: fyp_portal::models::SubscriptionState) -> bool {
+        false
+    }
+    async fn get_subscription(&self, _p: Uuid, _u: Uuid) -> Option<fyp_portal::models::SubscriptionState> {
+        None
+    }
+    async fn delete_project_comment(&self, _p: Uuid, _id: i64, _u: Option<Uuid>) -> bool {
+        false
+    }
+    async fn get_public_stats(&self) -> fyp_portal::models::PublicStats {
+        fyp_portal::models::PublicStats::default()
+    }
+    async fn record_upload(
+        &self,
+        _u: Uuid,
+        _k: &str,
+        _p: fyp_portal::models::UploadPurpose,
+        _e: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        true
+    }
+    async fn get_upload_counts(&self, _u: Uuid, _p: fyp_portal::models::UploadPurpose) -> fyp_portal::models::UploadCounts {
+        fyp_portal::models::UploadCounts::default()
+    }
+    async fn is_own_upload(&self, _u: Uuid, _k: &str, _p: fyp_portal::models::UploadPurpose) -> bool {
+        false
+    }
+    async fn get_changed_project_ids(
+        &self,
+        _since: chrono::DateTime<chrono::Utc>,
+        _ids: Option<Vec<Uuid>>,
+    ) -> Vec<Uuid> {
+        vec![]
+    }
+    async fn get_public_events(
+        &self,
+        _since: chrono::DateTime<chrono::Utc>,
+        _limit: i64,
+    ) -> Vec<fyp_portal::models::PublicEvent> {
+        vec![]
+    }
+    async fn increment_view(&self, _id: Uuid) -> Option<i64> {
+        None
+    }
+    async fn get_like_count(&self, _id: Uuid) -> Option<i64> {
+        None
+    }
+    fn stream_all_projects(
+        &self,
+    ) -> futures::stream::BoxStream<
+        'static,
+        Result<fyp_portal::models::Project, fyp_portal::repository::RepositoryError>,
+    > {
+        Box::pin(futures::stream::empty())
+    }
+    fn stream_project_export(
+        &self,
+    ) -> futures::stream::BoxStream<
+        'static,
+        Result<fyp_portal::models::ProjectExportRow, fyp_portal::repository::RepositoryError>,
+    > {
+        Box::pin(futures::stream::empty())
+    }
+    async fn get_programmes(&self) -> Vec<fyp_portal::models::Programme> {
+        vec![]
+    }
+    async fn create_programme(
+        &self,
+        _code: &str,
+        _name: &str,
+    ) -> Option<fyp_portal::models::Programme> {
+        None
+    }
+    async fn unknown_programme_codes(&self, _codes: &[String]) -> Vec<String> {
+        vec![]
+    }
+    async fn get_project_programmes(&self, _project_id: Uuid) -> Vec<String> {
+        vec![]
+    }
+    async fn get_tag_counts(&self) -> Vec<fyp_portal::models::TagCount> {
+        vec![]
+    }
+    async fn get_project_years(&self) -> Vec<fyp_portal::models::YearCount> {
+        vec![]
+    }
+    async fn get_department_counts(&self) -> Vec<fyp_portal::models::DepartmentCount> {
+        vec![]
+    }
+    async fn get_technology_counts(&self) -> Vec<fyp_portal::models::TechnologyCount> {
+        vec![]
+    }
+    async fn add_feedback(&self, _project_id: Uuid, _message: &str, _contact_email: Option<&str>) -> Option<fyp_portal::models::ProjectFeedback> {
+        None
+    }
+    async fn get_feedback(&self, _project_id: Option<Uuid>) -> Vec<fyp_portal::models::ProjectFeedback> {
+        vec![]
+    }
+    async fn get_settings(
+        &self,
+    ) -> Result<Vec<fyp_portal::models::Setting>, fyp_portal::repository::RepositoryError> {
+        Ok(vec![])
+    }
+    async fn put_setting(
+        &self,
+        _key: &str,
+        _value: serde_json::Value,
+        _by: Uuid,
+    ) -> Option<fyp_portal::models::Setting> {
+        None
+    }
+    async fn get_stale_drafts(
+        &self,
+        _s: fyp_portal::repository::DraftSweep,
+        _before: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<fyp_portal::models::StaleDraft> {
+        vec![]
+    }
+    async fn mark_stale_draft(&self, _s: fyp_portal::repository::DraftSweep, _p: Uuid, _at: chrono::DateTime<chrono::Utc>) -> bool {
+        false
+    }
+    async fn get_projects_outside_years(&self, _min: i32, _max: i32) -> Vec<fyp_portal::models::InvalidYearProject> {
+        vec![]
+    }
+    async fn remap_project_year(&self, _from: i32, _to: i32) -> i64 {
+        0
+    }
+    async fn run_fanout_batch(&self, _batch_size: i64) -> Option<fyp_portal::models::OutboxJob> {
+        None
+    }
+    async fn get_outbox_job(&self, _id: i64) -> Option<fyp_portal::models::OutboxJob> {
+        None
+    }
+    async fn record_audit(&self, _entry: &fyp_portal::models::NewAuditEntry) -> Result<(), fyp_portal::repository::RepositoryError> {
+        Ok(())
+    }
+    async fn queue_audit(&self, _entry: &fyp_portal::models::NewAuditEntry) -> Result<i64, fyp_portal::repository::RepositoryError> {
+        Ok(0)
+    }
+    async fn get_audit_log(&self, _filter: &fyp_portal::repository::AuditFilter, _page: fyp_portal::pagination::PageRequest) -> (Vec<fyp_portal::models::AuditEntry>, i64) {
+        (vec![], 0)
+    }
+    fn stream_audit_log(
+        &self,
+        _filter: fyp_portal::repository::AuditFilter,
+    ) -> futures::stream::BoxStream<
+        'static,
+        Result<fyp_portal::models::AuditEntry, fyp_portal::repository::RepositoryError>,
+    > {
+        Box::pin(futures::stream::empty())
+    }
+    async fn purge_audit_log(&self, _before: chrono::DateTime<chrono::Utc>) -> Result<u64, fyp_portal::repository::RepositoryError> {
+        Ok(0)
+    }
+    async fn flush_audit_outbox(&self) -> Result<u64, fyp_portal::repository::RepositoryError> {
+        Ok(0)
+    }
+    async fn resolve_project_slug(&self, _slug: &str) -> Option<Uuid> {
+        None
+    }
+    async fn find_project_by_title_year(&self, _title: &str, _year: i32) -> Option<Uuid> {
+        None
+    }
+    async fn count_user_projects_in_year(&self, _user_id: Uuid, _year: i32) -> i64 {
+        0
+    }
+    async fn get_user_activity(
+        &self,
+        _u: Uuid,
+        _p: fyp_portal::pagination::PageRequest,
+    ) -> (Vec<fyp_portal::models::ActivityItem>, i64) {
+        (vec![], 0)
+    }
+    async fn get_project_stats(&self, _id: Uuid, _owner_id: Uuid) -> Option<fyp_portal::models::ProjectStats> {
+        None
+    }
+    async fn record_project_review(
+        &self,
+        _p: Uuid,
+        _r: Uuid,
+        _s: fyp_portal::models::ProjectStatus,
+        _reason: Option<String>,
+    ) -> bool {
+        false
+    }
+    async fn get_latest_reviews(&self, _o: Uuid) -> Vec<fyp_portal::models::ProjectReview> {
+        vec![]
+    }
+    async fn ping(&self) -> Result<(), fyp_portal::repository::RepositoryError> {
+        Ok(())
+    }
+    async fn create_report(
+        &self,
+        _p: Option<Uuid>,
+        _c: i64,
+        _u: Uuid,
+        _r: Option<String>,
+        _t: i64,
+    ) -> Option<fyp_portal::models::ReportOutcome> {
+        None
+    }
+    async fn restore_comment(&self, _id: i64) -> bool {
+        false
+    }
+    async fn get_reported_comments(&self) -> Vec<fyp_portal::models::ReportedComment> {
+        vec![]
+    }
+    async fn get_admin_comments(
+        &self,
+        _: &fyp_portal::repository::AdminCommentFilter,
+        _: i64,
+        _: i64,
+    ) -> Vec<fyp_portal::models::AdminComment> {
+        vec![]
+    }
 }
 
 // --- Helper Functions ---
@@ -144,28 +459,17 @@ fn create_token(user_id: Uuid, exp_offset: u64) -> String {
     encode(&Header::default(), &claims, &key).unwrap()
 }
 
-// FIX 1: Added secret_key argument to ensure the AppConfig uses the test secret
+/// Production tests start from the production-like preset, so every Production-only field
+/// already has a stub; only the repository and the JWT secret vary per test.
 fn create_app_state(env: Env, repo: MockAuthRepo, jwt_secret: String) -> AppState {
-    // 1. Start with a safe default config
-    let mut config = fyp_portal::config::AppConfig::default();
-
-    // 2. Override the environment and secret to match the test constant
-    config.env = env.clone();
-    config.jwt_secret = jwt_secret;
-
-    // 3. For Env::Production tests, ensure all other production-required fields
-    //    are set to non-panicking stubs, even if AppConfig::default() didn't panic.
-    if env == Env::Production {
-        config.s3_endpoint = "http://mock-prod-supabase".to_string();
-        config.s3_key = "prod_key_stub".to_string();
-        config.s3_secret = "prod_secret_stub".to_string();
-    }
-
-    AppState {
-        repo: Arc::new(repo),
-        storage: Arc::new(fyp_portal::storage::MockStorageService::new()),
-        config,
-    }
+    let builder = match env {
+        Env::Production => AppStateBuilder::production_like(),
+        Env::Local => AppStateBuilder::test_default(),
+    };
+    builder
+        .repo(Arc::new(repo))
+        .configure(|config| config.jwt_secret = jwt_secret)
+        .build()
 }
 
 /// Helper to get the mutable Parts struct from a generated Request
@@ -0,0 +1,174 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{HeaderName, HeaderValue, Request, StatusCode, header, request::Parts},
+};
+use fyp_portal::{
+    AppState, AppStateBuilder,
+    auth::{AuthUser, Claims},
+    config::Env,
+    repository::{counting::CountingRepository, memory::InMemoryRepository},
+};
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use std::{sync::Arc, time::SystemTime};
+use uuid::Uuid;
+
+// --- Helpers ---
+
+// Every case in this file must be rejected before the extractor touches the database, so
+// the state's repository counts its calls and any call at all fails the test.
+
+const TEST_JWT_SECRET: &str = "negative-test-secret-value-0987654321";
+const TEST_USER_ID: Uuid = Uuid::from_u128(1);
+
+/// `{"alg":"none","typ":"JWT"}` header with valid, unexpired claims and an empty signature.
+const ALG_NONE_TOKEN: &str = "eyJhbGciOiJub25lIiwidHlwIjoiSldUIn0.eyJzdWIiOiIwMDAwMDAwMC0wMDAwLTAwMDAtMDAwMC0wMDAwMDAwMDAwMDEiLCJleHAiOjQxMDI0NDQ4MDAsImlhdCI6MTcwMDAwMDAwMH0.";
+
+fn app_state(env: Env, repo: Arc<CountingRepository<InMemoryRepository>>) -> AppState {
+    let builder = match env {
+        Env::Production => AppStateBuilder::production_like(),
+        Env::Local => AppStateBuilder::test_default(),
+    };
+    builder
+        .repo(repo)
+        .configure(|config| config.jwt_secret = TEST_JWT_SECRET.to_string())
+        .build()
+}
+
+/// Signs valid, unexpired claims with the *correct* secret but the given algorithm.
+fn token_with_algorithm(algorithm: Algorithm) -> String {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let claims = Claims {
+        sub: TEST_USER_ID,
+        iat: now as usize,
+        exp: (now + 3600) as usize,
+    };
+    let key = EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes());
+    encode(&Header::new(algorithm), &claims, &key).unwrap()
+}
+
+fn parts_with(headers: Vec<(HeaderName, HeaderValue)>) -> Parts {
+    let mut builder = Request::builder().uri("/");
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+    let (parts, _) = builder.body(axum::body::Body::empty()).unwrap().into_parts();
+    parts
+}
+
+fn bearer(token: &str) -> (HeaderName, HeaderValue) {
+    (
+        header::AUTHORIZATION,
+        HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    )
+}
+
+/// Runs the extractor in both environments and asserts a clean 401 every time.
+async fn assert_rejected(headers: Vec<(HeaderName, HeaderValue)>) {
+    for env in [Env::Production, Env::Local] {
+        let repo = Arc::new(CountingRepository::new(Arc::new(InMemoryRepository::new())));
+        let state = app_state(env.clone(), repo.clone());
+        let mut parts = parts_with(headers.clone());
+        let result = AuthUser::from_request_parts(&mut parts, &state).await;
+        assert_eq!(
+            result.unwrap_err(),
+            StatusCode::UNAUTHORIZED,
+            "expected 401 in {:?}",
+            env
+        );
+        assert_eq!(repo.totals(), Default::default(), "reached the repository in {:?}", env);
+    }
+}
+
+// --- Tests ---
+
+#[tokio::test]
+async fn test_wrong_authorization_scheme_is_rejected() {
+    let token = token_with_algorithm(Algorithm::HS256);
+    for value in [
+        "Basic dXNlcjpwYXNzd29yZA==".to_string(),
+        format!("bearer {}", token),
+        format!("Token {}", token),
+        token.clone(),
+        "Bearer".to_string(),
+        "Bearer ".to_string(),
+    ] {
+        assert_rejected(vec![(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&value).unwrap(),
+        )])
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn test_token_with_extra_segments_is_rejected() {
+    let token = token_with_algorithm(Algorithm::HS256);
+    assert_rejected(vec![bearer(&format!("{}.extra", token))]).await;
+    assert_rejected(vec![bearer(&format!("{}..", token))]).await;
+    assert_rejected(vec![bearer("only.two")]).await;
+    assert_rejected(vec![bearer("garbage")]).await;
+}
+
+#[tokio::test]
+async fn test_alg_none_token_is_rejected() {
+    assert_rejected(vec![bearer(ALG_NONE_TOKEN)]).await;
+}
+
+#[tokio::test]
+async fn test_wrong_hmac_algorithm_is_rejected() {
+    // Correct secret, correct claims, but HS384/HS512 instead of the pinned HS256.
+    assert_rejected(vec![bearer(&token_with_algorithm(Algorithm::HS384))]).await;
+    assert_rejected(vec![bearer(&token_with_algorithm(Algorithm::HS512))]).await;
+}
+
+#[tokio::test]
+async fn test_oversized_header_is_rejected() {
+    let huge = "a".repeat(64 * 1024);
+    assert_rejected(vec![bearer(&huge)]).await;
+    assert_rejected(vec![bearer(&format!("{huge}.{huge}.{huge}"))]).await;
+    assert_rejected(vec![(
+        HeaderName::from_static("x-user-id"),
+        HeaderValue::from_str(&huge).unwrap(),
+    )])
+    .await;
+}
+
+#[tokio::test]
+async fn test_non_utf8_header_bytes_are_rejected() {
+    let bytes = HeaderValue::from_bytes(b"Bearer \xff\xfe\xfd").unwrap();
+    assert_rejected(vec![(header::AUTHORIZATION, bytes.clone())]).await;
+    assert_rejected(vec![(HeaderName::from_static("x-user-id"), bytes)]).await;
+}
+
+#[tokio::test]
+async fn test_sql_like_local_bypass_header_never_reaches_repository() {
+    for value in [
+        "' OR '1'='1",
+        "1; DROP TABLE profiles; --",
+        "00000000-0000-0000-0000-000000000001' --",
+        "\" OR \"\"=\"",
+        "",
+    ] {
+        assert_rejected(vec![(
+            HeaderName::from_static("x-user-id"),
+            HeaderValue::from_str(value).unwrap(),
+        )])
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn test_bad_bypass_header_with_garbage_bearer_is_rejected() {
+    // The malformed bypass falls through to the JWT flow, which must still fail fast.
+    assert_rejected(vec![
+        (
+            HeaderName::from_static("x-user-id"),
+            HeaderValue::from_static("not-a-uuid"),
+        ),
+        bearer("not.a.jwt"),
+    ])
+    .await;
+}
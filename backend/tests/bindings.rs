@@ -7,3 +7,15 @@ fn generate_bindings() {
     // We just need a dummy test to trigger the build profile.
     assert_eq!(1, 1);
 }
+
+#[test]
+fn activity_item_exports_as_a_discriminated_union() {
+    use fyp_portal::models::ActivityItem;
+    use ts_rs::TS;
+
+    let decl = ActivityItem::decl();
+    for kind in ["commented", "liked", "status_changed"] {
+        assert!(decl.contains(&format!("\"kind\": \"{kind}\"")), "{decl}");
+    }
+    assert!(decl.contains("at: string"), "{decl}");
+}
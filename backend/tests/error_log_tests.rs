@@ -0,0 +1,119 @@
+//! The in-process error log behind GET /admin/errors: grouping, windows and its bound.
+
+use axum::http::StatusCode;
+use fyp_portal::{
+    clock::{Clock, MockClock},
+    error_log::{ERROR_LOG_CAPACITY, ErrorLog},
+    models::ErrorWindow,
+};
+use std::{sync::Arc, thread};
+
+fn record(log: &ErrorLog, route: &str, status: StatusCode, code: &str, request_id: &str) {
+    log.record(route.to_string(), status, code.to_string(), Some(request_id.to_string()));
+}
+
+#[test]
+fn test_only_server_errors_and_rate_limits_are_tracked() {
+    assert!(ErrorLog::tracks(StatusCode::INTERNAL_SERVER_ERROR));
+    assert!(ErrorLog::tracks(StatusCode::SERVICE_UNAVAILABLE));
+    assert!(ErrorLog::tracks(StatusCode::TOO_MANY_REQUESTS));
+    assert!(!ErrorLog::tracks(StatusCode::NOT_FOUND));
+    assert!(!ErrorLog::tracks(StatusCode::FORBIDDEN));
+    assert!(!ErrorLog::tracks(StatusCode::OK));
+}
+
+#[test]
+fn test_summary_groups_by_route_and_code_most_frequent_first() {
+    let log = ErrorLog::new(Arc::new(MockClock::new()));
+    record(&log, "GET /health/deep", StatusCode::SERVICE_UNAVAILABLE, "service_unavailable", "a");
+    record(&log, "POST /upload/presigned", StatusCode::INTERNAL_SERVER_ERROR, "storage_error", "b");
+    record(&log, "POST /upload/presigned", StatusCode::INTERNAL_SERVER_ERROR, "storage_error", "c");
+    record(&log, "POST /upload/presigned", StatusCode::TOO_MANY_REQUESTS, "rate_limited", "d");
+
+    let summary = log.summary(ErrorWindow::Hour, 2);
+    assert_eq!(summary.total, 4);
+    let groups: Vec<_> = summary
+        .groups
+        .iter()
+        .map(|g| (g.route.as_str(), g.code.as_str(), g.status, g.count))
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            ("POST /upload/presigned", "storage_error", 500, 2),
+            ("GET /health/deep", "service_unavailable", 503, 1),
+            ("POST /upload/presigned", "rate_limited", 429, 1),
+        ]
+    );
+    // Newest first, cut to the requested number.
+    let recent: Vec<_> = summary.recent.iter().map(|s| s.request_id.as_deref().unwrap()).collect();
+    assert_eq!(recent, ["d", "c"]);
+}
+
+#[test]
+fn test_summary_only_counts_errors_within_the_window() {
+    let clock = Arc::new(MockClock::new());
+    let log = ErrorLog::new(clock.clone());
+    record(&log, "GET /health/deep", StatusCode::SERVICE_UNAVAILABLE, "service_unavailable", "old");
+    clock.advance(chrono::Duration::minutes(30));
+    record(&log, "GET /health/deep", StatusCode::SERVICE_UNAVAILABLE, "service_unavailable", "new");
+
+    let totals = |log: &ErrorLog| {
+        [ErrorWindow::FifteenMinutes, ErrorWindow::Hour, ErrorWindow::Day]
+            .map(|window| log.summary(window, 10).total)
+    };
+    assert_eq!(totals(&log), [1, 2, 2]);
+    let quarter = log.summary(ErrorWindow::FifteenMinutes, 10);
+    assert_eq!(quarter.since, clock.now() - chrono::Duration::minutes(15));
+    assert_eq!(quarter.recent[0].request_id.as_deref(), Some("new"));
+    assert_eq!(quarter.groups[0].last_seen, quarter.recent[0].at);
+
+    clock.advance(chrono::Duration::hours(2));
+    assert_eq!(totals(&log), [0, 0, 2]);
+    clock.advance(chrono::Duration::hours(24));
+    let day = log.summary(ErrorWindow::Day, 10);
+    assert_eq!((day.total, day.groups.len(), day.recent.len()), (0, 0, 0));
+    // Expired samples stay until evicted; they are only left out of summaries.
+    assert_eq!(log.len(), 2);
+}
+
+#[test]
+fn test_log_stays_bounded_under_concurrent_load() {
+    let log = Arc::new(ErrorLog::new(Arc::new(MockClock::new())));
+    let writers: Vec<_> = (0..8)
+        .map(|writer| {
+            let log = log.clone();
+            thread::spawn(move || {
+                for i in 0..500 {
+                    let id = format!("{writer}-{i}");
+                    record(&log, "GET /projects", StatusCode::INTERNAL_SERVER_ERROR, "internal_server_error", &id);
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    assert_eq!(log.len(), ERROR_LOG_CAPACITY);
+    let summary = log.summary(ErrorWindow::Hour, 5);
+    assert_eq!(summary.total, ERROR_LOG_CAPACITY as u64);
+    assert_eq!(summary.groups.len(), 1);
+    assert_eq!(summary.groups[0].count, ERROR_LOG_CAPACITY as u64);
+    assert_eq!(summary.recent.len(), 5);
+}
+
+#[test]
+fn test_oldest_samples_are_evicted_first() {
+    let log = ErrorLog::with_capacity(Arc::new(MockClock::new()), 3);
+    for id in ["1", "2", "3", "4", "5"] {
+        record(&log, "GET /projects", StatusCode::INTERNAL_SERVER_ERROR, "internal_server_error", id);
+    }
+    let recent: Vec<_> = log
+        .summary(ErrorWindow::Hour, 10)
+        .recent
+        .into_iter()
+        .map(|s| s.request_id.unwrap())
+        .collect();
+    assert_eq!(recent, ["5", "4", "3"]);
+}
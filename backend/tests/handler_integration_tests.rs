@@ -1,28 +1,44 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
 use axum::{
     Json,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{FromRequest, OriginalUri, Path, Query, State},
+    http::{StatusCode, Uri, header},
     response::IntoResponse,
 };
 use fyp_portal::{
-    AppState,
+    AppState, AppStateBuilder,
     auth::AuthUser,
     config::AppConfig,
-    handlers,
-    models::{
-        AdminDashboardStats, Comment, CreateProjectRequest, NotificationResponse,
-        PresignedUrlRequest, Project, UpdateProjectRequest, User,
+    error::FieldError,
+    export::ResponseFormat,
+    extract::{CommentId, MAX_COMMENT_ID, OwnerOrAdmin, ProjectId, ProjectPatch, StatusUpdate},
+    handlers, metrics,
+    models::{ProjectStatus, 
+        AdminDashboardStats, AuditEntry, NewAuditEntry, BulkStatusOutcome, BulkStatusRequest, ChangedSinceRequest, ChangedSinceResponse, Comment, CommentEditOutcome, CommentOrder, CommentsLockRequest, CreateCommentRequest,
+        CreateFeedbackRequest, CreateProjectRequest, CreatedProject, InvalidYearProject, Like, LikeCountResponse, LikeOutcome, MyProjectSummary, ReviewState, NotificationResponse, OutboxJob, PostedComment, PresignedUrlRequest, Programme, Project, ProjectFeedback, ProjectUpdateResult, PublicStats, RegisterUserRequest, ReportOutcome, ResubmitOutcome, CreateReportRequest,
+        StaleDraft, SubscriptionState, Setting, SupervisorStats, TagCount, TechnologyCount, UpdateCommentRequest, UpdateProjectRequest, UpdateStatusRequest,
+        UploadCounts, UploadPurpose, User, VoteResponse, YearCount, DepartmentCount, ProjectOwner, OwnerTransferOutcome, ReassignOwnerRequest,
     },
-    repository::Repository,
+    pagination::PageRequest,
+    repository::{AdminCommentFilter, AuditFilter, CommentCursor, DraftSweep, HiddenComments, ProjectListFilter, ProjectViewer, Repository, RepositoryError, memory::InMemoryRepository},
+    sparse::SparseJson,
     storage::MockStorageService,
 };
-use std::sync::Arc;
+use futures::{StreamExt, stream::BoxStream};
+use std::sync::{Arc, Mutex};
 use tokio::test;
 use uuid::Uuid;
 
 // --- MOCK REPOSITORY IMPLEMENTATION ---
 
+/// A recorded `get_changed_project_ids` call: the cutoff and the optional id scope.
+type ChangedSinceQuery = (DateTime<Utc>, Option<Vec<Uuid>>);
+/// A recorded `get_projects` call: the filters and the requested page.
+type ProjectQuery = (ProjectListFilter, PageRequest);
+/// A recorded `create_report` call: project, comment, reporter, reason and threshold.
+type ReportCall = (Option<Uuid>, i64, Uuid, Option<String>, i64);
+
 // This struct is the central control point for testing handler logic.
 // Handlers rely on traits, so we mock the trait implementation.
 pub struct MockRepoControl {
@@ -31,13 +47,74 @@ pub struct MockRepoControl {
     pub delete_project_called: bool,
     pub delete_project_admin_called: bool,
     pub like_project_result: bool,
+    pub like_outcome: Option<LikeOutcome>,
+    pub unlike_result: bool,
+    pub liked_project_ids: Vec<Uuid>,
+    pub like_count: i64,
+    // What `count_comments` returns; None stands for a private or missing project.
+    pub comment_count: Option<i64>,
+    // What `get_recent_comments` returns; every `add_comment` adds one to both windows.
+    pub recent_comments: Mutex<fyp_portal::models::RecentComments>,
+    pub likers: Vec<fyp_portal::models::ProjectLiker>,
+    // What `update_comment` returns; every call is recorded as (id, user_id, text).
+    pub updated_comment: Option<Comment>,
+    pub comment_updates: Mutex<Vec<(i64, Uuid, String)>>,
     pub get_project_result: Option<Project>,
+    // What `count_user_projects_in_year` reports, for the per-year submission cap.
+    pub user_year_project_count: i64,
     pub get_user_role: String,
+    pub comments_locked: bool,
 
     // Pre-canned outputs for handler requests
     pub projects_to_return: Vec<Project>,
     pub stats_to_return: AdminDashboardStats,
+    pub public_stats: PublicStats,
     pub notifications_to_return: Vec<NotificationResponse>,
+    pub supervisor_report: Vec<SupervisorStats>,
+    pub subscription: Option<SubscriptionState>,
+    // `stream_all_projects` fails after yielding this many of `projects_to_return`.
+    pub stream_error_after: Option<usize>,
+    pub programmes: Vec<Programme>,
+    pub tag_counts: Vec<TagCount>,
+    pub report_outcome: Option<ReportOutcome>,
+    pub restore_comment_result: bool,
+    pub stale_drafts: Vec<(DraftSweep, StaleDraft)>,
+    pub ping_fails: bool,
+    // `get_projects` takes this long, as if the database were under load.
+    pub slow_reads: Option<std::time::Duration>,
+    // `set_projects_status_bulk` reports these as not found and every other id as changed.
+    pub missing_ids: Vec<Uuid>,
+    // `record_audit` fails, as if the audit insert hit a database error.
+    pub audit_insert_fails: bool,
+    // `add_comment` fails, as if the comment transaction hit a database error.
+    pub add_comment_fails: bool,
+    // Accounts `reassign_project_owner` accepts as the new owner; any other id is unknown.
+    pub known_user_ids: Vec<Uuid>,
+    // What `get_project_stats` returns when the caller owns `get_project_result`.
+    pub project_stats: Option<fyp_portal::models::ProjectStats>,
+
+    // Recorded side effects
+    pub project_queries: Mutex<Vec<ProjectQuery>>,
+    pub status_notifications: Mutex<Vec<(Uuid, Uuid, Option<String>)>>,
+    // (reviewer_id, review); also what `get_latest_reviews` returns.
+    pub reviews: Mutex<Vec<(Uuid, fyp_portal::models::ProjectReview)>>,
+    pub subscription_updates: Mutex<Vec<(Uuid, Uuid, SubscriptionState)>>,
+    pub comment_orders: Mutex<Vec<CommentOrder>>,
+    pub comment_visibility: Mutex<Vec<HiddenComments>>,
+    // (before or after, limit) of each `get_comments_before` / `get_comments_after` call.
+    pub comment_pages: Mutex<Vec<(Option<CommentCursor>, i64)>>,
+    // (filter, limit, offset) of each `get_admin_comments` call.
+    pub admin_comment_queries: Mutex<Vec<(AdminCommentFilter, i64, i64)>>,
+    pub reports: Mutex<Vec<ReportCall>>,
+    pub recorded_uploads: Mutex<Vec<(Uuid, String, UploadPurpose)>>,
+    pub changed_since_queries: Mutex<Vec<ChangedSinceQuery>>,
+    pub draft_marks: Mutex<Vec<(DraftSweep, Uuid)>>,
+    pub bulk_status_calls: Mutex<Vec<(Vec<Uuid>, bool)>>,
+    pub audit_entries: Mutex<Vec<NewAuditEntry>>,
+    pub queued_audit: Mutex<Vec<NewAuditEntry>>,
+    // (project_id, new_owner_id, actor_id)
+    pub owner_transfers: Mutex<Vec<(Uuid, Uuid, Uuid)>>,
+    pub pending_queue_limits: Mutex<Vec<i64>>,
 }
 
 impl Default for MockRepoControl {
@@ -47,11 +124,55 @@ impl Default for MockRepoControl {
             delete_project_called: false,
             delete_project_admin_called: false,
             like_project_result: true, // Default to success for simpler tests
+            like_outcome: Some(LikeOutcome::Created(1)),
+            unlike_result: true,
+            liked_project_ids: vec![],
+            like_count: 0,
+            comment_count: None,
+            recent_comments: Mutex::new(fyp_portal::models::RecentComments::default()),
+            likers: vec![],
+            updated_comment: None,
+            comment_updates: Mutex::new(vec![]),
             get_project_result: Some(Project::default()),
+            user_year_project_count: 0,
             get_user_role: "student".to_string(),
+            comments_locked: false,
             projects_to_return: vec![],
             stats_to_return: AdminDashboardStats::default(),
+            public_stats: PublicStats::default(),
             notifications_to_return: vec![],
+            supervisor_report: vec![],
+            subscription: None,
+            stream_error_after: None,
+            programmes: vec![],
+            tag_counts: vec![],
+            report_outcome: None,
+            restore_comment_result: false,
+            stale_drafts: vec![],
+            ping_fails: false,
+            slow_reads: None,
+            missing_ids: vec![],
+            audit_insert_fails: false,
+            add_comment_fails: false,
+            known_user_ids: vec![],
+            project_stats: None,
+            project_queries: Mutex::new(vec![]),
+            status_notifications: Mutex::new(vec![]),
+            reviews: Mutex::new(vec![]),
+            subscription_updates: Mutex::new(vec![]),
+            comment_orders: Mutex::new(vec![]),
+            comment_visibility: Mutex::new(vec![]),
+            comment_pages: Mutex::new(vec![]),
+            admin_comment_queries: Mutex::new(vec![]),
+            reports: Mutex::new(vec![]),
+            recorded_uploads: Mutex::new(vec![]),
+            changed_since_queries: Mutex::new(vec![]),
+            draft_marks: Mutex::new(vec![]),
+            bulk_status_calls: Mutex::new(vec![]),
+            audit_entries: Mutex::new(vec![]),
+            queued_audit: Mutex::new(vec![]),
+            owner_transfers: Mutex::new(vec![]),
+            pending_queue_limits: Mutex::new(vec![]),
         }
     }
 }
@@ -59,23 +180,42 @@ impl Default for MockRepoControl {
 #[async_trait]
 impl Repository for MockRepoControl {
     // --- Handlers use these methods: ---
-    async fn get_projects(&self, _year: Option<i32>, _search: Option<String>) -> Vec<Project> {
-        self.projects_to_return.clone()
+    async fn get_projects(&self, filter: ProjectListFilter, page: PageRequest) -> (Vec<Project>, i64) {
+        self.project_queries.lock().unwrap().push((filter, page));
+        if let Some(delay) = self.slow_reads {
+            tokio::time::sleep(delay).await;
+        }
+        (self.projects_to_return.clone(), self.projects_to_return.len() as i64)
     }
     async fn get_all_projects(&self) -> Vec<Project> {
         self.projects_to_return.clone()
     }
-    async fn get_top_projects(&self, _limit: i64) -> Vec<Project> {
+    async fn get_pending_reviews(&self, limit: i64) -> Vec<fyp_portal::models::PendingReview> {
+        self.pending_queue_limits.lock().unwrap().push(limit);
+        let now = Utc::now();
+        self.projects_to_return
+            .iter()
+            .take(limit as usize)
+            .map(|p| fyp_portal::models::PendingReview::new(p.clone(), Some(format!("{}@test.com", p.user_id)), now))
+            .collect()
+    }
+    async fn get_top_projects(&self, _limit: i64, _min_likes: i64, _year: Option<i32>) -> Vec<Project> {
+        self.projects_to_return.clone()
+    }
+    async fn get_year_archive(&self, _year: i32) -> Vec<Project> {
         self.projects_to_return.clone()
     }
+    async fn get_random_public_project(&self, _seed: Option<i64>) -> Option<Project> {
+        self.projects_to_return.iter().find(|p| p.is_public).cloned()
+    }
     async fn get_project(&self, _id: Uuid) -> Option<Project> {
         self.get_project_result.clone()
     }
     async fn get_stats(&self) -> AdminDashboardStats {
         self.stats_to_return.clone()
     }
-    async fn get_my_projects(&self, _user_id: Uuid) -> Vec<Project> {
-        self.projects_to_return.clone()
+    async fn get_my_project_summaries(&self, _user_id: Uuid) -> Vec<MyProjectSummary> {
+        self.projects_to_return.iter().cloned().map(|p| MyProjectSummary::new(p, 0)).collect()
     }
     async fn get_notifications(&self, _user_id: Uuid) -> Vec<NotificationResponse> {
         self.notifications_to_return.clone()
@@ -86,8 +226,17 @@ impl Repository for MockRepoControl {
         // In a real mock, you would record the input here
         Project::default() // Return a default struct to satisfy compiler
     }
-    async fn like_project(&self, _like: fyp_portal::models::Like) -> bool {
-        self.like_project_result
+    async fn like_project(&self, _like: fyp_portal::models::Like) -> Option<LikeOutcome> {
+        self.like_outcome
+    }
+    async fn unlike_project(&self, _like: fyp_portal::models::Like) -> bool {
+        self.unlike_result
+    }
+    async fn get_liked_project_ids(&self, _user_id: Uuid, project_ids: &[Uuid]) -> Vec<Uuid> {
+        project_ids.iter().copied().filter(|id| self.liked_project_ids.contains(id)).collect()
+    }
+    async fn get_project_likers(&self, _project_id: Uuid, _page: PageRequest) -> (Vec<fyp_portal::models::ProjectLiker>, i64) {
+        (self.likers.clone(), self.likers.len() as i64)
     }
     async fn delete_project(&self, _id: Uuid, _user_id: Uuid) -> bool {
         self.delete_project_called
@@ -100,25 +249,88 @@ impl Repository for MockRepoControl {
         _id: Uuid,
         _user_id: Uuid,
         _req: UpdateProjectRequest,
-    ) -> Option<Project> {
-        self.get_project_result.clone()
+    ) -> Option<ProjectUpdateResult> {
+        let project = self.get_project_result.clone()?;
+        Some(ProjectUpdateResult { project, ..ProjectUpdateResult::default() })
     }
-    async fn add_comment(&self, _project_id: Uuid, _user_id: Uuid, _text: String) -> Comment {
-        Comment::default()
+    async fn add_comment(&self, _project_id: Uuid, _user_id: Uuid, _text: String, _fanout_threshold: i64) -> Result<Option<PostedComment>, RepositoryError> {
+        if self.add_comment_fails {
+            return Err(RepositoryError("connection reset".to_string()));
+        }
+        if self.comments_locked {
+            return Ok(None);
+        }
+        let mut recent = self.recent_comments.lock().unwrap();
+        recent.last_minute += 1;
+        recent.oldest_last_minute.get_or_insert_with(Utc::now);
+        recent.last_day += 1;
+        recent.oldest_last_day.get_or_insert_with(Utc::now);
+        Ok(Some(PostedComment::default()))
     }
-    async fn get_comments(&self, _project_id: Uuid) -> Vec<Comment> {
+    async fn get_comments(
+        &self,
+        _project_id: Uuid,
+        order: CommentOrder,
+        hidden: HiddenComments,
+    ) -> Vec<Comment> {
+        self.comment_orders.lock().unwrap().push(order);
+        self.comment_visibility.lock().unwrap().push(hidden);
         self.projects_to_return
             .clone()
             .into_iter()
             .map(|_| Comment::default())
             .collect()
     }
-    async fn set_project_status(&self, _id: Uuid, _is_public: bool) -> Option<Project> {
+    async fn get_comments_before(
+        &self,
+        _project_id: Uuid,
+        hidden: HiddenComments,
+        before: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        self.comment_visibility.lock().unwrap().push(hidden);
+        self.comment_pages.lock().unwrap().push((before, limit));
+        self.projects_to_return
+            .iter()
+            .take(limit as usize)
+            .map(|_| Comment::default())
+            .collect()
+    }
+    async fn get_comments_after(
+        &self,
+        _project_id: Uuid,
+        hidden: HiddenComments,
+        after: Option<CommentCursor>,
+        limit: i64,
+    ) -> Vec<Comment> {
+        self.comment_visibility.lock().unwrap().push(hidden);
+        self.comment_pages.lock().unwrap().push((after, limit));
+        self.projects_to_return
+            .iter()
+            .take(limit as usize)
+            .map(|_| Comment::default())
+            .collect()
+    }
+    async fn get_recent_comments(&self, _user_id: Uuid) -> fyp_portal::models::RecentComments {
+        *self.recent_comments.lock().unwrap()
+    }
+    async fn count_comments(&self, _project_id: Uuid) -> Option<i64> {
+        self.comment_count
+    }
+    async fn set_project_status(&self, _id: Uuid, _status: fyp_portal::models::ProjectStatus) -> Option<Project> {
         self.get_project_result.clone()
     }
+    async fn set_projects_status_bulk(&self, ids: &[Uuid], is_public: bool) -> BulkStatusOutcome {
+        self.bulk_status_calls.lock().unwrap().push((ids.to_vec(), is_public));
+        let (not_found, changed) = ids.iter().partition(|id| self.missing_ids.contains(id));
+        BulkStatusOutcome { changed, not_found }
+    }
     async fn mark_notification_read(&self, _notification_id: Uuid, _user_id: Uuid) -> bool {
         self.like_project_result
     }
+    async fn create_notification(&self, _n: fyp_portal::models::NewNotification) -> Option<fyp_portal::models::Notification> {
+        None
+    }
 
     // Minimal mocks for compilation
     async fn get_user(&self, _id: Uuid) -> Option<User> {
@@ -131,6 +343,13 @@ impl Repository for MockRepoControl {
     async fn create_user(&self, _user: User) -> User {
         User::default()
     }
+    async fn get_users_by_email_prefix(&self, _prefixes: &[String]) -> Vec<User> {
+        Vec::new()
+    }
+    async fn update_comment(&self, id: i64, user_id: Uuid, req: UpdateCommentRequest) -> Option<CommentEditOutcome> {
+        self.comment_updates.lock().unwrap().push((id, user_id, req.text));
+        self.updated_comment.clone().map(|comment| CommentEditOutcome::Edited(Box::new(comment)))
+    }
     async fn delete_comment(&self, _id: i64, _user_id: Uuid) -> bool {
         self.delete_project_called
     }
@@ -147,6 +366,331 @@ impl Repository for MockRepoControl {
     async fn get_public_project(&self, id: Uuid) -> Option<Project> {
         self.get_project(id).await.filter(|p| p.is_public)
     }
+    async fn get_project_detail(&self, id: Uuid, viewer: ProjectViewer) -> Option<(Project, Option<ProjectOwner>)> {
+        let project = match viewer {
+            ProjectViewer::Public => self.get_public_project(id).await,
+            ProjectViewer::User(user_id) => self.get_project_authorized(id, user_id).await,
+            ProjectViewer::Admin => self.get_project(id).await,
+        }?;
+        Some((project, None))
+    }
+
+    // Discussion lock: owner path requires ownership, admin path does not.
+    async fn set_comments_locked(&self, id: Uuid, user_id: Uuid, locked: bool) -> Option<Project> {
+        self.get_project(id)
+            .await
+            .filter(|p| p.user_id == user_id)
+            .map(|p| Project {
+                comments_locked: locked,
+                ..p
+            })
+    }
+    async fn set_comments_locked_admin(&self, id: Uuid, locked: bool) -> Option<Project> {
+        self.get_project(id).await.map(|p| Project {
+            comments_locked: locked,
+            ..p
+        })
+    }
+    async fn lock_comments_for_year(&self, _year: i32) -> i64 {
+        self.projects_to_return.len() as i64
+    }
+    async fn are_comments_locked(&self, _project_id: Uuid) -> bool {
+        self.comments_locked
+    }
+    async fn mark_project_submitted(&self, id: Uuid) -> Option<Project> {
+        self.get_project(id).await.filter(|p| p.status == ProjectStatus::Draft).map(|p| Project {
+            submitted_at: Some(chrono::Utc::now()),
+            ..p
+        })
+    }
+    async fn resubmit_project(&self, _id: Uuid, _owner_id: Uuid, _max_attempts: i32) -> Option<ResubmitOutcome> {
+        None
+    }
+    async fn reassign_project_owner(&self, project_id: Uuid, new_owner_id: Uuid, actor_id: Uuid) -> Option<OwnerTransferOutcome> {
+        let project = self.get_project(project_id).await?;
+        if !self.known_user_ids.contains(&new_owner_id) {
+            return Some(OwnerTransferOutcome::UnknownOwner);
+        }
+        self.owner_transfers.lock().unwrap().push((project_id, new_owner_id, actor_id));
+        Some(OwnerTransferOutcome::Transferred {
+            previous_owner: project.user_id,
+            project: Box::new(Project { user_id: new_owner_id, ..project }),
+        })
+    }
+    async fn get_supervisor_report(&self, _year: Option<i32>) -> Vec<SupervisorStats> {
+        self.supervisor_report.clone()
+    }
+    async fn notify_status_change(
+        &self,
+        project_id: Uuid,
+        actor_id: Uuid,
+        reason: Option<String>,
+    ) -> bool {
+        self.status_notifications
+            .lock()
+            .unwrap()
+            .push((project_id, actor_id, reason));
+        true
+    }
+    async fn set_subscription(&self, project_id: Uuid, user_id: Uuid, state: SubscriptionState) -> bool {
+        self.subscription_updates
+            .lock()
+            .unwrap()
+            .push((project_id, user_id, state));
+        self.get_project_result.is_some()
+    }
+    async fn get_subscription(&self, _project_id: Uuid, _user_id: Uuid) -> Option<SubscriptionState> {
+        self.subscription
+    }
+    async fn delete_project_comment(
+        &self,
+        project_id: Uuid,
+        _id: i64,
+        user_id: Option<Uuid>,
+    ) -> bool {
+        // The stored comment lives on `get_project_result`'s project; other ids are mismatches.
+        let in_project = self
+            .get_project_result
+            .as_ref()
+            .is_some_and(|p| p.id == project_id);
+        in_project
+            && match user_id {
+                Some(_) => self.delete_project_called,
+                None => self.delete_project_admin_called,
+            }
+    }
+    async fn get_public_stats(&self) -> PublicStats {
+        self.public_stats.clone()
+    }
+    async fn record_upload(
+        &self,
+        user_id: Uuid,
+        object_key: &str,
+        purpose: UploadPurpose,
+        _expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        self.recorded_uploads
+            .lock()
+            .unwrap()
+            .push((user_id, object_key.to_string(), purpose));
+        true
+    }
+    async fn get_upload_counts(&self, user_id: Uuid, purpose: UploadPurpose) -> UploadCounts {
+        // Counts what was recorded; nothing is ever confirmed or expired in the mock.
+        let uploads = self.recorded_uploads.lock().unwrap();
+        let mine = uploads.iter().filter(|(u, _, _)| *u == user_id);
+        UploadCounts {
+            last_hour: mine.clone().count() as i64,
+            outstanding: mine.filter(|(_, _, p)| *p == purpose).count() as i64,
+        }
+    }
+    async fn is_own_upload(&self, user_id: Uuid, object_key: &str, purpose: UploadPurpose) -> bool {
+        self.recorded_uploads
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(u, k, p)| *u == user_id && k == object_key && *p == purpose)
+    }
+    async fn get_changed_project_ids(
+        &self,
+        since: DateTime<Utc>,
+        ids: Option<Vec<Uuid>>,
+    ) -> Vec<Uuid> {
+        self.changed_since_queries
+            .lock()
+            .unwrap()
+            .push((since, ids));
+        self.projects_to_return.iter().map(|p| p.id).collect()
+    }
+    async fn get_public_events(&self, _since: DateTime<Utc>, _limit: i64) -> Vec<fyp_portal::models::PublicEvent> {
+        vec![]
+    }
+    async fn increment_view(&self, _id: Uuid) -> Option<i64> {
+        let project = self.get_project_result.as_ref().filter(|p| p.is_public)?;
+        Some(project.view_count + 1)
+    }
+    async fn get_like_count(&self, _id: Uuid) -> Option<i64> {
+        self.get_project_result.as_ref().filter(|p| p.is_public)?;
+        Some(self.like_count)
+    }
+    fn stream_all_projects(&self) -> BoxStream<'static, Result<Project, RepositoryError>> {
+        let mut rows: Vec<_> = self.projects_to_return.iter().cloned().map(Ok).collect();
+        if let Some(n) = self.stream_error_after {
+            rows.truncate(n);
+            rows.push(Err(RepositoryError("connection reset".to_string())));
+            // Anything after the error must never reach the client.
+            rows.push(Ok(Project::default()));
+        }
+        futures::stream::iter(rows).boxed()
+    }
+    fn stream_project_export(&self) -> BoxStream<'static, Result<fyp_portal::models::ProjectExportRow, RepositoryError>> {
+        futures::stream::empty().boxed()
+    }
+    async fn get_programmes(&self) -> Vec<Programme> {
+        self.programmes.clone()
+    }
+    async fn create_programme(&self, code: &str, name: &str) -> Option<Programme> {
+        if self.programmes.iter().any(|p| p.code == code) {
+            return None;
+        }
+        Some(Programme {
+            code: code.to_string(),
+            name: name.to_string(),
+        })
+    }
+    async fn unknown_programme_codes(&self, codes: &[String]) -> Vec<String> {
+        codes
+            .iter()
+            .filter(|code| !self.programmes.iter().any(|p| &p.code == *code))
+            .cloned()
+            .collect()
+    }
+    async fn get_project_programmes(&self, _project_id: Uuid) -> Vec<String> {
+        self.programmes.iter().map(|p| p.code.clone()).collect()
+    }
+    async fn get_tag_counts(&self) -> Vec<TagCount> {
+        self.tag_counts.clone()
+    }
+    async fn get_project_years(&self) -> Vec<YearCount> {
+        vec![]
+    }
+    async fn get_department_counts(&self) -> Vec<DepartmentCount> {
+        vec![]
+    }
+    async fn get_technology_counts(&self) -> Vec<fyp_portal::models::TechnologyCount> {
+        vec![]
+    }
+    async fn add_feedback(&self, _project_id: Uuid, _message: &str, _contact_email: Option<&str>) -> Option<ProjectFeedback> {
+        None
+    }
+    async fn get_feedback(&self, _project_id: Option<Uuid>) -> Vec<ProjectFeedback> {
+        vec![]
+    }
+    async fn get_settings(&self) -> Result<Vec<Setting>, RepositoryError> {
+        Ok(vec![])
+    }
+    async fn put_setting(&self, key: &str, value: serde_json::Value, updated_by: Uuid) -> Option<Setting> {
+        Some(Setting { key: key.to_string(), value, updated_at: Some(Utc::now()), updated_by: Some(updated_by) })
+    }
+    async fn get_stale_drafts(&self, sweep: DraftSweep, _untouched_before: DateTime<Utc>) -> Vec<StaleDraft> {
+        self.stale_drafts.iter().filter(|(s, _)| *s == sweep).map(|(_, d)| d.clone()).collect()
+    }
+    async fn mark_stale_draft(&self, sweep: DraftSweep, project_id: Uuid, _at: DateTime<Utc>) -> bool {
+        self.draft_marks.lock().unwrap().push((sweep, project_id));
+        true
+    }
+    async fn get_projects_outside_years(&self, _min: i32, _max: i32) -> Vec<InvalidYearProject> {
+        vec![]
+    }
+    async fn remap_project_year(&self, _from: i32, _to: i32) -> i64 {
+        0
+    }
+    async fn run_fanout_batch(&self, _batch_size: i64) -> Option<OutboxJob> {
+        None
+    }
+    async fn get_outbox_job(&self, _id: i64) -> Option<OutboxJob> {
+        None
+    }
+    async fn record_audit(&self, entry: &NewAuditEntry) -> Result<(), RepositoryError> {
+        if self.audit_insert_fails {
+            return Err(RepositoryError("audit_log is unavailable".to_string()));
+        }
+        self.audit_entries.lock().unwrap().push(entry.clone());
+        Ok(())
+    }
+    async fn queue_audit(&self, entry: &NewAuditEntry) -> Result<i64, RepositoryError> {
+        let mut queued = self.queued_audit.lock().unwrap();
+        queued.push(entry.clone());
+        Ok(queued.len() as i64)
+    }
+    async fn get_audit_log(&self, _filter: &AuditFilter, _page: PageRequest) -> (Vec<AuditEntry>, i64) {
+        (vec![], 0)
+    }
+    fn stream_audit_log(&self, _filter: AuditFilter) -> BoxStream<'static, Result<AuditEntry, RepositoryError>> {
+        futures::stream::empty().boxed()
+    }
+    async fn purge_audit_log(&self, _before: DateTime<Utc>) -> Result<u64, RepositoryError> {
+        Ok(0)
+    }
+    async fn flush_audit_outbox(&self) -> Result<u64, RepositoryError> {
+        Ok(0)
+    }
+    async fn resolve_project_slug(&self, _slug: &str) -> Option<Uuid> {
+        None
+    }
+    async fn find_project_by_title_year(&self, _title: &str, _year: i32) -> Option<Uuid> {
+        None
+    }
+    async fn count_user_projects_in_year(&self, _user_id: Uuid, _year: i32) -> i64 {
+        self.user_year_project_count
+    }
+    async fn get_user_activity(
+        &self,
+        _u: Uuid,
+        _p: fyp_portal::pagination::PageRequest,
+    ) -> (Vec<fyp_portal::models::ActivityItem>, i64) {
+        (vec![], 0)
+    }
+    async fn get_project_stats(&self, id: Uuid, owner_id: Uuid) -> Option<fyp_portal::models::ProjectStats> {
+        self.get_project_result
+            .clone()
+            .filter(|p| p.id == id && p.user_id == owner_id)
+            .and(self.project_stats.clone())
+    }
+    async fn record_project_review(
+        &self,
+        project_id: Uuid,
+        reviewer_id: Uuid,
+        status: fyp_portal::models::ProjectStatus,
+        reason: Option<String>,
+    ) -> bool {
+        let review = fyp_portal::models::ProjectReview {
+            project_id,
+            status,
+            reason,
+            reviewed_at: chrono::Utc::now(),
+        };
+        self.reviews.lock().unwrap().push((reviewer_id, review));
+        true
+    }
+    async fn get_latest_reviews(&self, _o: Uuid) -> Vec<fyp_portal::models::ProjectReview> {
+        self.reviews.lock().unwrap().iter().map(|(_, review)| review.clone()).collect()
+    }
+    async fn ping(&self) -> Result<(), fyp_portal::repository::RepositoryError> {
+        if self.ping_fails {
+            return Err(fyp_portal::repository::RepositoryError("connection refused".to_string()));
+        }
+        Ok(())
+    }
+    async fn create_report(
+        &self,
+        project_id: Option<Uuid>,
+        comment_id: i64,
+        reporter_id: Uuid,
+        reason: Option<String>,
+        threshold: i64,
+    ) -> Option<ReportOutcome> {
+        self.reports
+            .lock()
+            .unwrap()
+            .push((project_id, comment_id, reporter_id, reason, threshold));
+        self.report_outcome
+    }
+    async fn restore_comment(&self, _id: i64) -> bool {
+        self.restore_comment_result
+    }
+    async fn get_reported_comments(&self) -> Vec<fyp_portal::models::ReportedComment> {
+        vec![]
+    }
+    async fn get_admin_comments(
+        &self,
+        filter: &AdminCommentFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Vec<fyp_portal::models::AdminComment> {
+        self.admin_comment_queries.lock().unwrap().push((filter.clone(), limit, offset));
+        vec![]
+    }
 }
 
 // --- TEST UTILITIES ---
@@ -159,11 +703,18 @@ fn create_test_state(
     repo_control: MockRepoControl,
     storage_control: MockStorageService,
 ) -> AppState {
-    AppState {
-        repo: Arc::new(repo_control),
-        storage: Arc::new(storage_control),
-        config: AppConfig::default(),
-    }
+    AppStateBuilder::test_default()
+        .repo(Arc::new(repo_control))
+        .storage(Arc::new(storage_control))
+        .build()
+}
+
+fn comment_id(id: i64) -> CommentId {
+    CommentId::try_from(id).unwrap()
+}
+
+fn no_fields() -> handlers::FieldsFilter {
+    handlers::FieldsFilter { fields: None }
 }
 
 // Creates AuthUser for handler calls
@@ -180,11 +731,27 @@ fn student_user() -> AuthUser {
     }
 }
 
+// What the `OwnerOrAdmin` extractor hands a handler for `user` acting on a project owned by
+// the test student.
+fn owner_or_admin(user: AuthUser) -> OwnerOrAdmin<ProjectId> {
+    OwnerOrAdmin {
+        user,
+        resource: Project {
+            id: TEST_ID,
+            user_id: TEST_ID,
+            ..Project::default()
+        },
+    }
+}
+
 // --- HANDLER TESTS ---
 
 #[test]
 async fn test_get_project_details_success() {
-    let mock_project = Project::default();
+    let mock_project = Project {
+        is_public: true,
+        ..Project::default()
+    };
     let state = create_test_state(
         MockRepoControl {
             get_project_result: Some(mock_project.clone()),
@@ -193,7 +760,7 @@ async fn test_get_project_details_success() {
         MockStorageService::new(),
     );
 
-    let result = handlers::get_project_details(State(state), Path(TEST_ID)).await;
+    let result = handlers::get_project_details(None, State(state), Path(TEST_ID)).await;
 
     assert!(result.is_ok());
 
@@ -216,21 +783,219 @@ async fn test_get_project_details_not_found() {
         MockStorageService::new(),
     );
 
-    let result = handlers::get_project_details(State(state), Path(TEST_ID)).await;
+    let result = handlers::get_project_details(None, State(state), Path(TEST_ID)).await;
 
     assert!(result.is_err());
     assert_eq!(result.unwrap_err(), StatusCode::NOT_FOUND);
 }
 
+#[test]
+async fn test_get_project_details_hides_private_projects_from_strangers() {
+    let hidden = Project {
+        id: TEST_ID,
+        user_id: TEST_ID,
+        is_public: false,
+        ..Project::default()
+    };
+    let state = || {
+        create_test_state(
+            MockRepoControl {
+                get_project_result: Some(hidden.clone()),
+                ..MockRepoControl::default()
+            },
+            MockStorageService::new(),
+        )
+    };
+    let stranger = AuthUser {
+        id: Uuid::new_v4(),
+        role: "student".to_string(),
+    };
+
+    for user in [None, Some(stranger)] {
+        let result = handlers::get_project_details(user, State(state()), Path(TEST_ID)).await;
+        assert_eq!(result.unwrap_err(), StatusCode::NOT_FOUND);
+    }
+    // The owner (the test student) and admins still reach it.
+    for user in [student_user(), admin_user()] {
+        let Json(detail) = handlers::get_project_details(Some(user), State(state()), Path(TEST_ID))
+            .await
+            .unwrap();
+        assert_eq!(detail.project.id, TEST_ID);
+    }
+}
+
+#[test]
+async fn test_like_count_is_served_for_public_projects_only() {
+    let state = |is_public| {
+        create_test_state(
+            MockRepoControl {
+                get_project_result: Some(Project { id: TEST_ID, is_public, ..Project::default() }),
+                like_count: 3,
+                ..MockRepoControl::default()
+            },
+            MockStorageService::new(),
+        )
+    };
+
+    let Json(body) = handlers::get_like_count(State(state(true)), Path(TEST_ID)).await.unwrap();
+    assert_eq!(body, LikeCountResponse { count: 3 });
+    assert_eq!(serde_json::to_value(&body).unwrap(), serde_json::json!({ "count": 3 }));
+
+    let err = handlers::get_like_count(State(state(false)), Path(TEST_ID)).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::NOT_FOUND);
+    assert_eq!(err.body.code, "not_found");
+}
+
+#[test]
+async fn test_comment_count_is_served_for_public_projects_only() {
+    let state = |is_public: bool| {
+        create_test_state(
+            MockRepoControl {
+                comment_count: is_public.then_some(12),
+                ..MockRepoControl::default()
+            },
+            MockStorageService::new(),
+        )
+    };
+
+    let Json(body) = handlers::get_comment_count(State(state(true)), Path(TEST_ID)).await.unwrap();
+    assert_eq!(serde_json::to_value(&body).unwrap(), serde_json::json!({ "count": 12 }));
+
+    let err = handlers::get_comment_count(State(state(false)), Path(TEST_ID)).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::NOT_FOUND);
+    assert_eq!(err.body.code, "not_found");
+}
+
+#[test]
+async fn test_comment_count_follows_project_and_comment_visibility() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .configure(|config| config.auto_hide_report_threshold = 1)
+        .build();
+    for id in [TEST_ID, TEST_ADMIN_ID] {
+        repo.create_user(User { id, email: format!("{id}@ul.ie"), role: "student".to_string() }).await;
+    }
+    let project = repo.create_project(valid_project_request(), TEST_ID).await;
+    let count = |id: Uuid| {
+        let state = state.clone();
+        async move {
+            match handlers::get_comment_count(State(state), Path(id)).await {
+                Ok(Json(body)) => Ok(body.count),
+                Err(err) => Err(err.status),
+            }
+        }
+    };
+
+    // Drafts answer like missing projects, even once they have comments.
+    repo.add_comment(project.id, TEST_ID, "Early".to_string(), 500).await.unwrap().unwrap();
+    assert_eq!(count(project.id).await, Err(StatusCode::NOT_FOUND));
+    assert_eq!(count(Uuid::new_v4()).await, Err(StatusCode::NOT_FOUND));
+
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    let held = repo.add_comment(project.id, TEST_ID, "Spam".to_string(), 500).await.unwrap().unwrap().comment;
+    assert_eq!(count(project.id).await, Ok(2));
+    // A comment held for review drops out of the public count.
+    repo.create_report(None, held.id, TEST_ADMIN_ID, None, 1).await.unwrap();
+    assert_eq!(count(project.id).await, Ok(1));
+
+    repo.set_project_status(project.id, ProjectStatus::Draft).await.unwrap();
+    assert_eq!(count(project.id).await, Err(StatusCode::NOT_FOUND));
+}
+
+#[test]
+async fn test_private_report_key_is_only_shown_to_owner_and_admins() {
+    let state = |report_is_public| {
+        let project = Project {
+            id: TEST_ID,
+            user_id: TEST_ID,
+            is_public: true,
+            report: Some("reports/thesis.pdf".to_string()),
+            report_is_public,
+            ..Project::default()
+        };
+        create_test_state(
+            MockRepoControl {
+                get_project_result: Some(project.clone()),
+                projects_to_return: vec![project],
+                ..MockRepoControl::default()
+            },
+            MockStorageService::new(),
+        )
+    };
+    let stranger = AuthUser { id: Uuid::new_v4(), role: "student".to_string() };
+    let report = async |user, report_is_public| {
+        let Json(detail) = handlers::get_project_details(user, State(state(report_is_public)), Path(TEST_ID)).await.unwrap();
+        detail.project.report
+    };
+
+    assert_eq!(report(None, false).await, None);
+    assert_eq!(report(Some(stranger), false).await, None);
+    // The owner (the test student) reaches it through the authorized path, as do admins.
+    for user in [student_user(), admin_user()] {
+        assert_eq!(report(Some(user), false).await.as_deref(), Some("reports/thesis.pdf"));
+    }
+    assert_eq!(report(None, true).await.as_deref(), Some("reports/thesis.pdf"));
+
+    // Listings are public whoever asks, so a private report's key is absent from the JSON.
+    let response = handlers::get_projects(None, State(state(false)), projects_uri(), Query(project_filter(None)))
+        .await
+        .unwrap()
+        .into_response();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let page: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(page["items"][0]["report"], serde_json::Value::Null);
+    assert!(!String::from_utf8_lossy(&bytes).contains("reports/thesis.pdf"));
+}
+
+#[test]
+async fn test_public_detail_route_does_not_leak_unpublished_projects() {
+    use tower::util::ServiceExt;
+
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let user = |role: &str| User { id: Uuid::new_v4(), email: format!("{role}@ul.ie"), role: role.to_string() };
+    let owner = repo.create_user(user("student")).await.id;
+    let stranger = repo.create_user(user("student")).await.id;
+    let request = CreateProjectRequest { title: "Hidden Greenhouse".to_string(), ..CreateProjectRequest::default() };
+    let project = repo.create_project(request, owner).await;
+    repo.set_project_status(project.id, ProjectStatus::Pending).await.unwrap();
+
+    let app = fyp_portal::create_router(state);
+    let status = |uri: String, caller: Option<Uuid>| {
+        let mut request = axum::http::Request::get(uri);
+        if let Some(caller) = caller {
+            request = request.header("x-user-id", caller.to_string());
+        }
+        let app = app.clone();
+        async move { app.oneshot(request.body(axum::body::Body::empty()).unwrap()).await.unwrap().status() }
+    };
+    let by_id = format!("/projects/{}", project.id);
+    let by_slug = format!("/projects/slug/{}", project.slug);
+
+    for uri in [by_id.clone(), by_slug.clone()] {
+        assert_eq!(status(uri.clone(), None).await, StatusCode::NOT_FOUND, "{uri}");
+        assert_eq!(status(uri.clone(), Some(stranger)).await, StatusCode::NOT_FOUND, "{uri}");
+        assert_eq!(status(uri, Some(owner)).await, StatusCode::OK);
+    }
+
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    assert_eq!(status(by_id, None).await, StatusCode::OK);
+    assert_eq!(status(by_slug, None).await, StatusCode::OK);
+}
+
 #[test]
 async fn test_get_admin_projects_forbidden() {
     let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
 
     // Call with a non-admin user
-    let result = handlers::get_admin_projects(student_user(), State(state)).await;
+    let result =
+        handlers::get_admin_projects(student_user(), State(state), Query(no_fields())).await;
 
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), StatusCode::FORBIDDEN);
+    assert_eq!(result.unwrap_err().status, StatusCode::FORBIDDEN);
 }
 
 #[test]
@@ -246,43 +1011,114 @@ async fn test_get_admin_projects_success() {
     );
 
     // Call with admin user
-    let result = handlers::get_admin_projects(admin_user(), State(state)).await;
+    let result = handlers::get_admin_projects(admin_user(), State(state), Query(no_fields())).await;
 
     assert!(result.is_ok());
-    let Json(projects) = result.unwrap();
+    let SparseJson(projects, fields) = result.unwrap();
     assert_eq!(projects.len(), 1);
+    assert!(fields.is_none());
 }
 
-#[test]
-async fn test_vote_project_success() {
+async fn vote_with(outcome: Option<LikeOutcome>) -> Result<(StatusCode, Json<VoteResponse>), fyp_portal::error::ApiError> {
     let state = create_test_state(
         MockRepoControl {
-            like_project_result: true,
+            like_outcome: outcome,
             ..MockRepoControl::default()
         },
         MockStorageService::new(),
     );
+    handlers::vote_project(student_user(), State(state), Path(TEST_ID)).await
+}
+
+#[test]
+async fn test_vote_project_success() {
+    let (status, Json(body)) = vote_with(Some(LikeOutcome::Created(4))).await.unwrap();
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, VoteResponse { already_liked: false, like_count: 4 });
+}
 
-    let result = handlers::vote_project(student_user(), State(state), Path(TEST_ID)).await;
+#[test]
+async fn test_vote_project_conflict_reports_current_like_state() {
+    let (status, Json(body)) = vote_with(Some(LikeOutcome::AlreadyExists(7))).await.unwrap();
 
-    assert!(result.is_ok());
-    assert_eq!(result.unwrap(), StatusCode::OK);
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body, VoteResponse { already_liked: true, like_count: 7 });
+}
+
+#[test]
+async fn test_vote_project_missing_project_is_not_found() {
+    let err = vote_with(None).await.unwrap_err();
+
+    assert_eq!(err.status, StatusCode::NOT_FOUND);
+    assert_eq!(err.body.code, "not_found");
+}
+
+#[test]
+async fn test_vote_project_on_own_project_is_forbidden() {
+    let err = vote_with(Some(LikeOutcome::OwnProject(3))).await.unwrap_err();
+
+    // Distinct from the 409 of a repeated like: nothing was, or will be, recorded.
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+    assert_eq!(err.body.code, "own_project");
+}
+
+#[test]
+async fn test_owners_cannot_like_their_own_project_even_as_admin() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let own = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(own.id, ProjectStatus::Approved).await.unwrap();
+
+    let err = handlers::vote_project(admin_user(), State(state.clone()), Path(own.id)).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+    assert_eq!(err.body.code, "own_project");
+    // Anyone else still can.
+    let (status, Json(body)) = handlers::vote_project(student_user(), State(state), Path(own.id)).await.unwrap();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, VoteResponse { already_liked: false, like_count: 1 });
 }
 
 #[test]
-async fn test_vote_project_conflict() {
+async fn test_vote_project_on_hidden_project_is_not_found_and_records_nothing() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let draft = repo.create_project(valid_project_request(), TEST_ID).await;
+
+    // A draft answers like a missing project, also to an admin who can otherwise see it.
+    let err = handlers::vote_project(admin_user(), State(state.clone()), Path(draft.id)).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::NOT_FOUND);
+    assert_eq!(err.body.code, "not_found");
+
+    // Nothing was counted while it was hidden.
+    repo.set_project_status(draft.id, ProjectStatus::Approved).await.unwrap();
+    assert_eq!(repo.get_like_count(draft.id).await, Some(0));
+    assert!(repo.get_notifications(TEST_ID).await.is_empty());
+}
+
+async fn unvote_with(unlike_result: bool) -> StatusCode {
     let state = create_test_state(
         MockRepoControl {
-            like_project_result: false,
+            unlike_result,
             ..MockRepoControl::default()
         },
         MockStorageService::new(),
     );
+    handlers::unvote_project(student_user(), State(state), Path(TEST_ID)).await
+}
 
-    let result = handlers::vote_project(student_user(), State(state), Path(TEST_ID)).await;
+#[test]
+async fn test_unvote_project_success() {
+    assert_eq!(unvote_with(true).await, StatusCode::NO_CONTENT);
+}
 
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), StatusCode::CONFLICT);
+#[test]
+async fn test_unvote_project_without_like_is_not_found() {
+    assert_eq!(unvote_with(false).await, StatusCode::NOT_FOUND);
 }
 
 #[test]
@@ -295,7 +1131,7 @@ async fn test_delete_project_not_found_or_not_owner() {
         MockStorageService::new(),
     );
 
-    let status = handlers::delete_project(student_user(), State(state), Path(TEST_ID)).await;
+    let status = handlers::delete_project(State(state), owner_or_admin(student_user())).await;
 
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
@@ -310,42 +1146,173 @@ async fn test_delete_project_success() {
         MockStorageService::new(),
     );
 
-    let status = handlers::delete_project(student_user(), State(state), Path(TEST_ID)).await;
+    let status = handlers::delete_project(State(state), owner_or_admin(student_user())).await;
 
     assert_eq!(status, StatusCode::NO_CONTENT);
 }
 
 #[test]
-async fn test_get_presigned_url_success() {
-    // We remove the conflicting hardcoded upload_url_to_return line.
-
-    let resource_key_prefix = "uploads/";
+async fn test_delete_project_as_admin_uses_admin_delete() {
+    // The owner-scoped delete would fail for an admin; the admin path must be taken.
+    let state = create_test_state(
+        MockRepoControl {
+            delete_project_called: false,
+            delete_project_admin_called: true,
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
 
-    // 1. Setup Mock Storage (MockStorageService handles the URL construction)
-    let mock_storage = fyp_portal::storage::MockStorageService {
-        should_fail: false,
-        // NOTE: If your MockStorageService doesn't store state,
-        // we must check the format, not the exact string.
-    };
+    let status = handlers::delete_project(State(state), owner_or_admin(admin_user())).await;
 
-    // ... (rest of setup) ...
-    let state = create_test_state(MockRepoControl::default(), mock_storage);
-    let auth_user = student_user();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
 
-    // 2. Define the Request Payload
-    let payload = PresignedUrlRequest {
-        filename: "my_report.pdf".to_string(),
-        file_type: "application/pdf".to_string(),
-    };
+/// Runs `GET /projects/{id}` through the `OwnerOrAdmin<ProjectId>` extractor as a user with
+/// `role`, against a single project owned by `owner` (or none).
+async fn owner_or_admin_status(role: &str, project: Option<Project>) -> StatusCode {
+    use tower::util::ServiceExt;
 
-    // --- EXECUTION ---
-    let response = handlers::get_presigned_url(auth_user, State(state), Json(payload)).await;
-    let response = response.into_response();
-    let (parts, body) = response.into_parts();
-    let status = parts.status;
-    let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
-    let body_json: fyp_portal::models::PresignedUrlResponse =
-        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON response from handler");
+    let state = create_test_state(
+        MockRepoControl {
+            get_user_role: role.to_string(),
+            get_project_result: project,
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+    let app = axum::Router::new()
+        .route(
+            "/projects/{id}",
+            axum::routing::get(|owned: OwnerOrAdmin<ProjectId>| async move {
+                assert_eq!(owned.resource.id, TEST_ID);
+                StatusCode::OK
+            }),
+        )
+        .with_state(state);
+    let request = axum::http::Request::builder()
+        .uri(format!("/projects/{}", TEST_ID))
+        .header("x-user-id", TEST_ADMIN_ID.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    app.oneshot(request).await.unwrap().status()
+}
+
+fn project_owned_by(owner: Uuid, is_public: bool) -> Option<Project> {
+    Some(Project {
+        id: TEST_ID,
+        user_id: owner,
+        is_public,
+        ..Project::default()
+    })
+}
+
+#[test]
+async fn test_owner_or_admin_extractor_matrix() {
+    // The caller is always TEST_ADMIN_ID; only their role and the project's owner vary.
+    let caller = TEST_ADMIN_ID;
+    let stranger = Uuid::from_u128(789);
+
+    // Owner: allowed, even while the project is private.
+    assert_eq!(owner_or_admin_status("student", project_owned_by(caller, false)).await, StatusCode::OK);
+    // Admin: allowed on anyone's project, public or not.
+    assert_eq!(owner_or_admin_status("admin", project_owned_by(stranger, false)).await, StatusCode::OK);
+    assert_eq!(owner_or_admin_status("admin", project_owned_by(stranger, true)).await, StatusCode::OK);
+    // Stranger: forbidden on a visible project, not found on a private one.
+    assert_eq!(owner_or_admin_status("student", project_owned_by(stranger, true)).await, StatusCode::FORBIDDEN);
+    assert_eq!(owner_or_admin_status("student", project_owned_by(stranger, false)).await, StatusCode::NOT_FOUND);
+    // Missing project: not found for everyone.
+    assert_eq!(owner_or_admin_status("student", None).await, StatusCode::NOT_FOUND);
+    assert_eq!(owner_or_admin_status("admin", None).await, StatusCode::NOT_FOUND);
+}
+
+async fn likers_response(role: &str, owner: Uuid, query: &str) -> (StatusCode, serde_json::Value) {
+    use tower::util::ServiceExt;
+
+    let liker = fyp_portal::models::ProjectLiker {
+        id: TEST_ID,
+        email: Some("fan@example.com".to_string()),
+        liked_at: DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().with_timezone(&Utc),
+    };
+    let state = create_test_state(
+        MockRepoControl {
+            get_user_role: role.to_string(),
+            get_project_result: project_owned_by(owner, true),
+            likers: vec![liker],
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+    let app = axum::Router::new()
+        .route("/projects/{id}/likes/users", axum::routing::get(handlers::get_project_likers))
+        .with_state(state);
+    let request = axum::http::Request::builder()
+        .uri(format!("/projects/{}/likes/users{}", TEST_ID, query))
+        .header("x-user-id", TEST_ADMIN_ID.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    (status, response_json(response).await)
+}
+
+#[test]
+async fn test_project_likers_are_listed_for_owner_and_admins_only() {
+    // The caller is always TEST_ADMIN_ID; only their role and the project's owner vary.
+    let stranger = Uuid::from_u128(789);
+
+    let (status, body) = likers_response("student", TEST_ADMIN_ID, "?per_page=5").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["per_page"], 5);
+    assert_eq!(
+        body["items"],
+        serde_json::json!([{ "id": TEST_ID, "email": "fan@example.com", "liked_at": "2024-05-01T12:00:00Z" }])
+    );
+    assert_eq!(likers_response("admin", stranger, "").await.0, StatusCode::OK);
+
+    let (status, body) = likers_response("student", stranger, "").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "forbidden");
+
+    let (status, body) = likers_response("student", TEST_ADMIN_ID, "?per_page=1000").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "invalid_pagination");
+}
+
+#[test]
+async fn test_get_presigned_url_success() {
+    // We remove the conflicting hardcoded upload_url_to_return line.
+
+    let resource_key_prefix = "uploads/";
+
+    // 1. Setup Mock Storage (MockStorageService handles the URL construction)
+    let mock_storage = fyp_portal::storage::MockStorageService {
+        should_fail: false,
+        // NOTE: If your MockStorageService doesn't store state,
+        // we must check the format, not the exact string.
+        ..fyp_portal::storage::MockStorageService::new()
+    };
+
+    // ... (rest of setup) ...
+    let state = create_test_state(MockRepoControl::default(), mock_storage);
+    let auth_user = student_user();
+
+    // 2. Define the Request Payload
+    let payload = PresignedUrlRequest {
+        filename: "my_report.pdf".to_string(),
+        file_type: "application/pdf".to_string(),
+        purpose: None,
+    };
+
+    // --- EXECUTION ---
+    let response = handlers::get_presigned_url(auth_user, State(state), Json(payload)).await;
+    let response = response.into_response();
+    let (parts, body) = response.into_parts();
+    let status = parts.status;
+    let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+    let body_json: fyp_portal::models::PresignedUrlResponse =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON response from handler");
 
     // --- ASSERTIONS ---
     assert_eq!(status, StatusCode::OK);
@@ -397,8 +1364,4336 @@ async fn test_delete_comment_admin_override() {
     );
 
     // Call with an admin user
-    let status = handlers::delete_comment(admin_user(), State(state), Path(123i64)).await;
+    let status = handlers::delete_comment(admin_user(), State(state), Path(comment_id(123))).await;
 
     // Assert the handler took the admin path
     assert_eq!(status, StatusCode::NO_CONTENT);
 }
+
+#[test]
+async fn test_update_comment_passes_the_caller_as_owner() {
+    let control = Arc::new(MockRepoControl {
+        updated_comment: Some(Comment { id: 123, edited: true, ..Comment::default() }),
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let edit = UpdateCommentRequest { text: "Fixed".to_string() };
+
+    let Json(comment) = handlers::update_comment(student_user(), State(state), Path(comment_id(123)), Json(edit))
+        .await
+        .unwrap();
+    assert!(comment.edited);
+    assert_eq!(*control.comment_updates.lock().unwrap(), vec![(123, TEST_ID, "Fixed".to_string())]);
+}
+
+#[test]
+async fn test_update_comment_is_owner_only_even_for_admins() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    repo.create_user(User { id: TEST_ID, email: "author@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let project = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    let posted = repo.add_comment(project.id, TEST_ID, "Teh results".to_string(), 500).await.unwrap().unwrap().comment;
+    let edit = |user: AuthUser, id: i64| {
+        let request = UpdateCommentRequest { text: "The results".to_string() };
+        handlers::update_comment(user, State(state.clone()), Path(comment_id(id)), Json(request))
+    };
+
+    // Admins may delete the comment, not reword it; the same 404 as a missing comment.
+    for (user, id) in [(admin_user(), posted.id), (student_user(), posted.id + 1)] {
+        let err = edit(user, id).await.unwrap_err();
+        assert_eq!((err.status, err.body.code.as_str()), (StatusCode::NOT_FOUND, "not_found"));
+    }
+    let listed = repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Exclude).await;
+    assert_eq!((listed[0].comment.as_str(), listed[0].edited), ("Teh results", false));
+
+    let Json(edited) = edit(student_user(), posted.id).await.unwrap();
+    assert_eq!((edited.id, edited.comment.as_str(), edited.edited), (posted.id, "The results", true));
+    assert!(edited.edited_at.is_some());
+    assert_eq!(edited.created_at, posted.created_at);
+}
+
+// --- COMMENT TEXT VALIDATION ---
+
+/// Posts `text` as TEST_ID through a router over `repo`, as a client would.
+async fn post_comment(repo: Arc<InMemoryRepository>, project_id: Uuid, text: &str) -> (StatusCode, serde_json::Value) {
+    use tower::util::ServiceExt;
+
+    let state = AppStateBuilder::test_default()
+        .repo(repo)
+        .build();
+    let app = axum::Router::new()
+        .route("/projects/{id}/comments", axum::routing::post(handlers::add_comment))
+        .with_state(state);
+    let request = axum::http::Request::post(format!("/projects/{project_id}/comments"))
+        .header("x-user-id", TEST_ID.to_string())
+        .header("content-type", "application/json")
+        .body(axum::body::Body::from(serde_json::json!({ "text": text }).to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    (status, response_json(response).await)
+}
+
+#[test]
+async fn test_commenting_on_a_missing_or_hidden_project_is_not_found() {
+    let repo = Arc::new(InMemoryRepository::new());
+    repo.create_user(User { id: TEST_ID, email: "reader@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let draft = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    let own_draft = repo.create_project(valid_project_request(), TEST_ID).await;
+
+    for project_id in [Uuid::new_v4(), draft.id] {
+        let (status, body) = post_comment(repo.clone(), project_id, "Hello?").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "not_found");
+    }
+    let (status, _) = post_comment(repo.clone(), own_draft.id, "Note to self").await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(repo.get_comments(draft.id, CommentOrder::Oldest, HiddenComments::Include).await.is_empty());
+
+    // Admins may comment anywhere, but a project that does not exist is still a 404, not a 500.
+    let repo = Arc::new(InMemoryRepository::new());
+    repo.create_user(User { id: TEST_ID, email: "mod@ul.ie".to_string(), role: "admin".to_string() })
+        .await;
+    let draft = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    let (status, body) = post_comment(repo.clone(), Uuid::new_v4(), "Hello?").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "not_found");
+    let (status, _) = post_comment(repo.clone(), draft.id, "Please add a video.").await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[test]
+async fn test_comment_text_is_validated_through_the_router() {
+    let repo = Arc::new(InMemoryRepository::new());
+    repo.create_user(User { id: TEST_ID, email: "reader@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let project = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+
+    let too_long = "a".repeat(handlers::MAX_COMMENT_CHARS + 1);
+    // Far over the raw cap: refused before the sanitizer walks it.
+    let tag_flood = "<script></script>".repeat(120_000);
+    for (text, code) in [
+        ("", "required"),
+        (" \n\t \r\n ", "required"),
+        (too_long.as_str(), "too_long"),
+        (tag_flood.as_str(), "too_long"),
+    ] {
+        let (status, body) = post_comment(repo.clone(), project.id, text).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "validation_failed");
+        assert_eq!(body["details"]["errors"][0]["field"], "text");
+        assert_eq!(body["details"]["errors"][0]["code"], code, "{:?}", &text[..text.len().min(40)]);
+    }
+    assert!(repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Include).await.is_empty());
+
+    // Exactly at the limit is fine; so is padding that trimming removes.
+    let longest = format!("  {}\n", "b".repeat(handlers::MAX_COMMENT_CHARS));
+    assert_eq!(post_comment(repo.clone(), project.id, &longest).await.0, StatusCode::CREATED);
+    // Markup still counts towards the raw cap, not only the text that survives it.
+    let marked_up = format!("ok{}", "<b></b>".repeat(handlers::MAX_RAW_COMMENT_BYTES / 7));
+    assert_eq!(post_comment(repo.clone(), project.id, &marked_up).await.0, StatusCode::CREATED);
+    let over = format!("{marked_up}<b></b>");
+    assert_eq!(post_comment(repo.clone(), project.id, &over).await.0, StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Stored trimmed, with blank-line runs cut to one.
+    let (status, body) = post_comment(repo.clone(), project.id, "\n  Great work!\r\n\r\n\r\n\n\nWhich dataset?\n\n ").await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["comment"], "Great work!\n\nWhich dataset?");
+}
+
+#[test]
+async fn test_owner_and_admins_read_comments_on_a_project_pending_review() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let stranger = Uuid::from_u128(789);
+    for (id, role) in [(TEST_ID, "student"), (TEST_ADMIN_ID, "admin"), (stranger, "student")] {
+        repo.create_user(User { id, email: format!("{id}@ul.ie"), role: role.to_string() }).await;
+    }
+    let project = repo.create_project(valid_project_request(), TEST_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Pending).await.unwrap();
+    let remark = repo.add_comment(project.id, TEST_ADMIN_ID, "Please add a demo video.".to_string(), 500).await.unwrap().unwrap().comment;
+    let read = |user: Option<AuthUser>, limit: Option<&str>| {
+        let filter = handlers::CommentFilter { order: None, limit: limit.map(str::to_string), before: None, after: None };
+        let state = state.clone();
+        async move {
+            let Json(page) = handlers::get_comments(user, State(state), Path(project.id), Query(filter)).await.unwrap();
+            page.comments.into_iter().map(|c| c.id).collect::<Vec<_>>()
+        }
+    };
+
+    for limit in [None, Some("10")] {
+        assert_eq!(read(Some(student_user()), limit).await, vec![remark.id]);
+        assert_eq!(read(Some(admin_user()), limit).await, vec![remark.id]);
+        // Anyone else sees an empty thread, as before.
+        assert!(read(Some(AuthUser { id: stranger, ..student_user() }), limit).await.is_empty());
+        assert!(read(None, limit).await.is_empty());
+    }
+}
+
+#[test]
+async fn test_comment_bursts_are_rate_limited_per_user() {
+    let control = Arc::new(MockRepoControl {
+        get_project_result: Some(Project { is_public: true, ..Project::default() }),
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .configure(|config| config.max_comments_per_minute = 5)
+        .build();
+    let post = |user: AuthUser| {
+        let payload = CreateCommentRequest { text: "First!".to_string() };
+        handlers::add_comment(user, State(state.clone()), Path(TEST_ID), Json(payload))
+    };
+
+    for _ in 0..5 {
+        assert!(post(student_user()).await.is_ok());
+    }
+    let err = post(student_user()).await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::TOO_MANY_REQUESTS, "comment_rate_limited"));
+    assert_eq!(
+        err.body.details,
+        Some(serde_json::json!({ "window": "minute", "count": 5, "limit": 5 }))
+    );
+    // The oldest comment of the burst leaves the window within the minute (rounded up, and the
+    // mock stamps comments with the wall clock, a moment after the test clock started).
+    let retry_after = err.retry_after.unwrap();
+    assert!((1..=61).contains(&retry_after), "{retry_after}");
+    let response = err.into_response();
+    assert_eq!(response.headers()[header::RETRY_AFTER], retry_after.to_string());
+    assert_eq!(control.recent_comments.lock().unwrap().last_minute, 5);
+
+    // Admins are not limited.
+    assert!(post(admin_user()).await.is_ok());
+}
+
+#[test]
+async fn test_daily_comment_limit_waits_for_the_oldest_comment_of_the_day() {
+    let clock = Arc::new(fyp_portal::clock::MockClock::new());
+    let now = fyp_portal::clock::Clock::now(&*clock);
+    let state = |last_minute: i64, last_day: i64| {
+        let control = MockRepoControl {
+            recent_comments: Mutex::new(fyp_portal::models::RecentComments {
+                last_minute,
+                oldest_last_minute: (last_minute > 0).then(|| now - Duration::seconds(20)),
+                last_day,
+                oldest_last_day: Some(now - Duration::hours(23)),
+            }),
+            get_project_result: Some(Project { is_public: true, ..Project::default() }),
+            ..MockRepoControl::default()
+        };
+        AppStateBuilder::test_default()
+            .repo(Arc::new(control))
+            .clock(clock.clone())
+            .configure(|config| config.max_comments_per_day = 50)
+            .build()
+    };
+    let post = |state: AppState| {
+        let payload = CreateCommentRequest { text: "Again".to_string() };
+        handlers::add_comment(student_user(), State(state), Path(TEST_ID), Json(payload))
+    };
+
+    assert!(post(state(0, 49)).await.is_ok());
+    let err = post(state(0, 50)).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(err.body.details.as_ref().unwrap()["window"], "day");
+    assert_eq!(err.retry_after, Some(3600));
+    // With both windows exhausted the longer wait is the one reported.
+    let err = post(state(5, 50)).await.unwrap_err();
+    assert_eq!(err.retry_after, Some(3600));
+    let err = post(state(5, 10)).await.unwrap_err();
+    assert_eq!(err.retry_after, Some(40));
+}
+
+#[test]
+async fn test_failed_comment_insert_is_a_500() {
+    let control = MockRepoControl {
+        add_comment_fails: true,
+        get_project_result: Some(Project { is_public: true, ..Project::default() }),
+        ..MockRepoControl::default()
+    };
+    let state = create_test_state(control, MockStorageService::new());
+    let payload = CreateCommentRequest { text: "Hello".to_string() };
+    let err = handlers::add_comment(student_user(), State(state), Path(TEST_ID), Json(payload))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(err.body.code, "comment_failed");
+}
+
+#[test]
+async fn test_mentions_notify_each_mentioned_user_once() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let mut users = std::collections::HashMap::new();
+    for (name, email) in [("alice", "alice@ul.ie"), ("bob", "Bob@studentmail.ul.ie"), ("sam1", "sam@ul.ie"), ("sam2", "sam@studentmail.ul.ie")] {
+        let user = repo.create_user(User { id: Uuid::new_v4(), email: email.to_string(), role: "student".to_string() }).await;
+        users.insert(name, user.id);
+    }
+    repo.create_user(User { id: TEST_ID, email: "reader@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let project = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+
+    let text = "@alice check the results section. @BOB agrees, right @bob? cc @Alice @reader @ghost @sam, or mail alice@ul.ie";
+    let (status, _) = post_comment(repo.clone(), project.id, text).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let mentions = |user_id| {
+        let repo = repo.clone();
+        async move {
+            let notifications = repo.get_notifications(user_id).await;
+            notifications.into_iter().filter(|n| n.notification_type == "mention").collect::<Vec<_>>()
+        }
+    };
+    for name in ["alice", "bob"] {
+        let received = mentions(users[name]).await;
+        assert_eq!(received.len(), 1, "{name}");
+        assert_eq!(received[0].actor_email, "reader@ul.ie");
+        assert_eq!(received[0].project_id, project.id);
+    }
+    // The author mentioning themself, an unknown prefix and an ambiguous one notify nobody.
+    assert!(mentions(TEST_ID).await.is_empty());
+    assert!(mentions(users["sam1"]).await.is_empty());
+    assert!(mentions(users["sam2"]).await.is_empty());
+
+    // A second comment is a second mention.
+    post_comment(repo.clone(), project.id, "@alice one more thing").await;
+    assert_eq!(mentions(users["alice"]).await.len(), 2);
+    assert_eq!(mentions(users["bob"]).await.len(), 1);
+}
+
+#[test]
+async fn test_mentions_past_the_cap_notify_nobody() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let mut mentioned = vec![];
+    for n in 0..handlers::MAX_MENTIONS_PER_COMMENT + 2 {
+        let email = format!("user{n:02}@ul.ie");
+        mentioned.push(repo.create_user(User { id: Uuid::new_v4(), email, role: "student".to_string() }).await.id);
+    }
+    repo.create_user(User { id: TEST_ID, email: "reader@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let project = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+
+    // Repeats do not use up the cap; the first ten distinct mentions are notified.
+    let text = (0..mentioned.len()).map(|n| format!("@user{n:02} @user00")).collect::<Vec<_>>().join(" ");
+    let (status, _) = post_comment(repo.clone(), project.id, &text).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let notified: Vec<bool> = futures::future::join_all(mentioned.iter().map(|id| repo.get_notifications(*id)))
+        .await
+        .iter()
+        .map(|notifications| notifications.iter().any(|n| n.notification_type == "mention"))
+        .collect();
+    let mut expected = vec![true; handlers::MAX_MENTIONS_PER_COMMENT];
+    expected.extend([false, false]);
+    assert_eq!(notified, expected);
+}
+
+#[test]
+async fn test_comment_markup_is_stripped_before_storing() {
+    let repo = Arc::new(InMemoryRepository::new());
+    repo.create_user(User { id: TEST_ID, email: "reader@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let project = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+
+    // Nothing but markup is as blank as no text at all.
+    let (status, body) = post_comment(repo.clone(), project.id, " <script>alert(1)</script>\r\n").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["details"]["errors"][0]["code"], "required");
+
+    let (status, body) = post_comment(repo.clone(), project.id, "<b>Great</b> <img src=x onerror=alert(1)>demo\u{202E}!").await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["comment"], "Great demo!");
+    let stored = repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Exclude).await;
+    assert_eq!(stored.iter().map(|c| c.comment.as_str()).collect::<Vec<_>>(), vec!["Great demo!"]);
+
+    // Edits go through the same sanitizer.
+    let author = AuthUser { id: TEST_ID, ..student_user() };
+    let request = UpdateCommentRequest { text: "<i>Great</i> demo<script>x()</script>".to_string() };
+    let state = AppStateBuilder::test_default().repo(repo.clone()).build();
+    let Json(edited) = handlers::update_comment(author, State(state), Path(comment_id(stored[0].id)), Json(request))
+        .await
+        .unwrap();
+    assert_eq!(edited.comment, "Great demo");
+}
+
+#[test]
+async fn test_comment_edits_are_validated_like_new_comments() {
+    let control = Arc::new(MockRepoControl {
+        updated_comment: Some(Comment::default()),
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let edit = |text: &str| {
+        let request = UpdateCommentRequest { text: text.to_string() };
+        handlers::update_comment(student_user(), State(state.clone()), Path(comment_id(1)), Json(request))
+    };
+
+    let err = edit("   ").await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::UNPROCESSABLE_ENTITY, "validation_failed"));
+    assert!(edit(" Fixed \n\n\n\n typo ").await.is_ok());
+    assert_eq!(*control.comment_updates.lock().unwrap(), vec![(1, TEST_ID, "Fixed \n\n typo".to_string())]);
+}
+
+#[test]
+async fn test_comment_edits_respect_the_lock_moderation_and_rate_limit() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .configure(|config| config.max_comments_per_minute = 3)
+        .build();
+    repo.create_user(User { id: TEST_ID, email: "author@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let project = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    let posted = repo.add_comment(project.id, TEST_ID, "Frist".to_string(), 500).await.unwrap().unwrap().comment;
+    let edit = |text: &str| {
+        let request = UpdateCommentRequest { text: text.to_string() };
+        handlers::update_comment(student_user(), State(state.clone()), Path(comment_id(posted.id)), Json(request))
+    };
+
+    repo.set_comments_locked(project.id, TEST_ADMIN_ID, true).await.unwrap();
+    let err = edit("First").await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::FORBIDDEN, "comments_locked"));
+    repo.set_comments_locked(project.id, TEST_ADMIN_ID, false).await.unwrap();
+
+    // The comment and two edits use up the minute's budget of three.
+    assert!(edit("First").await.is_ok());
+    assert!(edit("First!").await.is_ok());
+    let err = edit("First!!").await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::TOO_MANY_REQUESTS, "comment_rate_limited"));
+
+    repo.create_report(None, posted.id, TEST_ADMIN_ID, None, 1).await.unwrap();
+    let admin_edit = UpdateCommentRequest { text: "Cleaned up".to_string() };
+    let author = AuthUser { id: TEST_ID, role: "admin".to_string() };
+    let err = handlers::update_comment(author, State(state.clone()), Path(comment_id(posted.id)), Json(admin_edit))
+        .await
+        .unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::CONFLICT, "comment_under_review"));
+    let listed = repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Include).await;
+    assert_eq!(listed[0].comment, "First!");
+}
+
+// --- DISCUSSION LOCK TESTS ---
+
+#[test]
+async fn test_owner_can_lock_comments() {
+    let state = create_test_state(
+        MockRepoControl {
+            get_project_result: Some(Project {
+                user_id: TEST_ID,
+                ..Project::default()
+            }),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let result = handlers::set_comments_lock(
+        student_user(),
+        State(state),
+        Path(Uuid::new_v4()),
+        Json(CommentsLockRequest { locked: true }),
+    )
+    .await;
+
+    let Json(project) = result.unwrap();
+    assert!(project.comments_locked);
+}
+
+#[test]
+async fn test_non_owner_cannot_lock_comments_but_admin_can() {
+    let other_owner = Project {
+        user_id: Uuid::from_u128(999),
+        ..Project::default()
+    };
+    let control = || MockRepoControl {
+        get_project_result: Some(other_owner.clone()),
+        ..MockRepoControl::default()
+    };
+
+    let state = create_test_state(control(), MockStorageService::new());
+    let result = handlers::set_comments_lock(
+        student_user(),
+        State(state),
+        Path(Uuid::new_v4()),
+        Json(CommentsLockRequest { locked: true }),
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), StatusCode::NOT_FOUND);
+
+    let state = create_test_state(control(), MockStorageService::new());
+    let result = handlers::set_comments_lock(
+        admin_user(),
+        State(state),
+        Path(Uuid::new_v4()),
+        Json(CommentsLockRequest { locked: true }),
+    )
+    .await;
+    assert!(result.unwrap().0.comments_locked);
+}
+
+#[test]
+async fn test_admin_bulk_lock_reports_count() {
+    let state = create_test_state(
+        MockRepoControl {
+            projects_to_return: vec![Project::default(); 3],
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let result = handlers::lock_comments_for_cohort(
+        admin_user(),
+        State(state.clone()),
+        Query(handlers::CohortFilter { year: 2024 }),
+    )
+    .await;
+    assert_eq!(result.unwrap().0.updated, 3);
+
+    let forbidden = handlers::lock_comments_for_cohort(
+        student_user(),
+        State(state),
+        Query(handlers::CohortFilter { year: 2024 }),
+    )
+    .await;
+    assert_eq!(forbidden.unwrap_err(), StatusCode::FORBIDDEN);
+}
+
+#[test]
+async fn test_draft_reminders_dry_run_does_not_mark() {
+    let draft = StaleDraft {
+        project_id: TEST_ID,
+        owner_id: TEST_ID,
+        title: "Forgotten".to_string(),
+        updated_at: Utc::now() - Duration::days(30),
+    };
+    let control = Arc::new(MockRepoControl {
+        stale_drafts: vec![(DraftSweep::Reminder, draft.clone())],
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let run = |user, dry_run| {
+        handlers::run_draft_reminders(user, State(state.clone()), Query(handlers::DryRunFilter { dry_run }))
+    };
+
+    let Json(preview) = run(admin_user(), Some(true)).await.unwrap();
+    assert!(preview.dry_run);
+    assert_eq!(preview.reminded, vec![draft.clone()]);
+    assert!(preview.flagged_for_cleanup.is_empty());
+    assert!(control.draft_marks.lock().unwrap().is_empty());
+
+    let Json(report) = run(admin_user(), None).await.unwrap();
+    assert!(!report.dry_run);
+    assert_eq!(report.reminded, vec![draft]);
+    assert_eq!(*control.draft_marks.lock().unwrap(), vec![(DraftSweep::Reminder, TEST_ID)]);
+
+    assert_eq!(run(student_user(), Some(true)).await.unwrap_err(), StatusCode::FORBIDDEN);
+}
+
+#[test]
+async fn test_locked_project_rejects_comments_and_votes_but_stays_readable() {
+    let control = || MockRepoControl {
+        comments_locked: true,
+        get_project_result: Some(Project { is_public: true, ..Project::default() }),
+        projects_to_return: vec![Project::default(); 2],
+        ..MockRepoControl::default()
+    };
+
+    let state = create_test_state(control(), MockStorageService::new());
+    let err = handlers::add_comment(
+        student_user(),
+        State(state),
+        Path(TEST_ID),
+        Json(CreateCommentRequest {
+            text: "too late".to_string(),
+        }),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+    assert_eq!(err.body.code, "comments_locked");
+
+    let state = create_test_state(control(), MockStorageService::new());
+    let err = handlers::vote_project(student_user(), State(state), Path(TEST_ID))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+    assert_eq!(err.body.code, "comments_locked");
+
+    // Reading is unaffected by the lock.
+    let state = create_test_state(control(), MockStorageService::new());
+    let filter = handlers::CommentFilter { order: None, limit: None, before: None, after: None };
+    let Json(page) = handlers::get_comments(None, State(state), Path(TEST_ID), Query(filter))
+            .await
+            .unwrap();
+    assert_eq!(page.comments.len(), 2);
+}
+
+// --- LINK PREVIEW (OPEN GRAPH) TESTS ---
+
+/// The upload log for a project whose cover its owner uploaded, as the presign flow records it.
+fn cover_uploaded(project: &Project) -> Mutex<Vec<(Uuid, String, UploadPurpose)>> {
+    Mutex::new(vec![(project.user_id, project.cover_image.clone(), UploadPurpose::CoverImage)])
+}
+
+async fn og_body(state: AppState) -> (StatusCode, String) {
+    let response = handlers::get_project_og(State(state), Path(TEST_ID)).await;
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+#[test]
+async fn test_project_og_contains_preview_tags() {
+    let project = Project {
+        id: TEST_ID,
+        title: "Rust Showcase".to_string(),
+        abstract_text: "A   portal\nfor final year projects.".to_string(),
+        cover_image: "uploads/cover.png".to_string(),
+        is_public: true,
+        ..Project::default()
+    };
+    let state = create_test_state(
+        MockRepoControl {
+            recorded_uploads: cover_uploaded(&project),
+            get_project_result: Some(project),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let (status, html) = og_body(state).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains(r#"<meta property="og:title" content="Rust Showcase">"#));
+    assert!(html.contains(
+        r#"<meta property="og:description" content="A portal for final year projects.">"#
+    ));
+    assert!(html.contains(
+        r#"<meta property="og:image" content="http://localhost:9000/mock-bucket/uploads/cover.png?signature=fake">"#
+    ));
+    assert!(html.contains(&format!(
+        r#"<link rel="canonical" href="http://localhost:3000/projects/{}">"#,
+        TEST_ID
+    )));
+    assert!(html.contains(r#"<meta name="twitter:card" content="summary_large_image">"#));
+}
+
+#[test]
+async fn test_project_og_escapes_interpolated_fields() {
+    let project = Project {
+        title: r#"</script><script>alert("x")</script>"#.to_string(),
+        abstract_text: "Tom & Jerry's <b>demo</b>".to_string(),
+        cover_image: r#"uploads/a"><script>.png"#.to_string(),
+        is_public: true,
+        ..Project::default()
+    };
+    let state = create_test_state(
+        MockRepoControl {
+            recorded_uploads: cover_uploaded(&project),
+            get_project_result: Some(project),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let (_, html) = og_body(state).await;
+
+    assert!(!html.contains("</script>"));
+    assert!(!html.contains("<script>"));
+    assert!(html.contains("&lt;/script&gt;&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt;"));
+    assert!(html.contains("Tom &amp; Jerry&#x27;s &lt;b&gt;demo&lt;/b&gt;"));
+    // The image URL is built from a stored key, so it is escaped like the text fields.
+    assert!(html.contains(
+        r#"<meta property="og:image" content="http://localhost:9000/mock-bucket/uploads/a&quot;&gt;&lt;script&gt;.png?signature=fake">"#
+    ));
+}
+
+#[test]
+async fn test_project_og_without_a_signable_cover_has_no_image() {
+    let project = Project {
+        title: "Rust Showcase".to_string(),
+        cover_image: "uploads/cover.png".to_string(),
+        is_public: true,
+        ..Project::default()
+    };
+    let state = create_test_state(
+        MockRepoControl {
+            recorded_uploads: cover_uploaded(&project),
+            get_project_result: Some(project),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new_failing(),
+    );
+
+    let (status, html) = og_body(state).await;
+
+    // The preview is still served, as a plain summary card.
+    assert_eq!(status, StatusCode::OK);
+    assert!(!html.contains("og:image"));
+    assert!(html.contains(r#"<meta name="twitter:card" content="summary">"#));
+}
+
+#[test]
+async fn test_project_og_does_not_sign_someone_elses_upload() {
+    // The key is a real upload, but another user's report: the owner cannot borrow it as a cover.
+    let project = Project {
+        title: "Rust Showcase".to_string(),
+        user_id: Uuid::new_v4(),
+        cover_image: "uploads/someone-elses-report.pdf".to_string(),
+        is_public: true,
+        ..Project::default()
+    };
+    let state = create_test_state(
+        MockRepoControl {
+            recorded_uploads: Mutex::new(vec![(Uuid::new_v4(), project.cover_image.clone(), UploadPurpose::Report)]),
+            get_project_result: Some(project),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let (status, html) = og_body(state).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(!html.contains("og:image"));
+    assert!(!html.contains("signature="));
+}
+
+#[test]
+async fn test_project_og_private_project_has_no_metadata() {
+    let state = create_test_state(
+        MockRepoControl {
+            get_project_result: Some(Project {
+                title: "Secret Title".to_string(),
+                is_public: false,
+                ..Project::default()
+            }),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let (status, html) = og_body(state).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!html.contains("og:"));
+    assert!(!html.contains("twitter:"));
+    assert!(!html.contains("Secret Title"));
+}
+
+#[test]
+async fn test_excerpt_masks_blocked_words_and_truncates() {
+    let blocked = vec!["darn".to_string()];
+    assert_eq!(
+        fyp_portal::og::excerpt("Well, DARN! that is good", 100, &blocked),
+        "Well, ****! that is good"
+    );
+    assert_eq!(
+        fyp_portal::og::excerpt("one two three four", 9, &[]),
+        "one two…"
+    );
+}
+
+// --- EMBEDDABLE CARD TESTS ---
+
+async fn embed_response(project: Option<Project>, format: Option<fyp_portal::embed::EmbedFormat>) -> (StatusCode, axum::http::HeaderMap, String) {
+    let state = create_test_state(
+        MockRepoControl {
+            recorded_uploads: project.as_ref().map(cover_uploaded).unwrap_or_default(),
+            get_project_result: project,
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+    let response = handlers::get_project_embed(State(state), Path(TEST_ID), Query(handlers::EmbedFilter { format })).await;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+fn embeddable_project() -> Project {
+    Project {
+        id: TEST_ID,
+        title: "Rust Showcase".to_string(),
+        author: "Ada Lovelace".to_string(),
+        abstract_text: "A portal for final year projects.".to_string(),
+        cover_image: "uploads/cover.png".to_string(),
+        is_public: true,
+        ..Project::default()
+    }
+}
+
+#[test]
+async fn test_project_embed_html_card() {
+    let (status, headers, html) = embed_response(Some(embeddable_project()), None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/html"));
+    let csp = headers[header::CONTENT_SECURITY_POLICY].to_str().unwrap();
+    assert!(csp.contains("default-src 'none'"));
+    assert!(csp.contains("frame-ancestors *"));
+    assert!(headers.get(header::X_FRAME_OPTIONS).is_none());
+
+    assert!(html.contains(&format!(
+        r#"<a href="http://localhost:3000/projects/{}" target="_blank" rel="noopener""#,
+        TEST_ID
+    )));
+    assert!(html.contains(">Rust Showcase</a>"));
+    assert!(html.contains("Ada Lovelace"));
+    assert!(html.contains("A portal for final year projects."));
+    assert!(html.contains(r#"<img src="http://localhost:9000/mock-bucket/uploads/cover.png?signature=fake""#));
+    assert!(!html.contains("<script"));
+}
+
+#[test]
+async fn test_project_embed_json_document() {
+    let (status, headers, body) =
+        embed_response(Some(embeddable_project()), Some(fyp_portal::embed::EmbedFormat::Json)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers.get(header::CONTENT_SECURITY_POLICY).is_some());
+    let doc: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(doc["type"], "rich");
+    assert_eq!(doc["version"], "1.0");
+    assert_eq!(doc["title"], "Rust Showcase");
+    assert_eq!(doc["author_name"], "Ada Lovelace");
+    assert_eq!(doc["url"], format!("http://localhost:3000/projects/{}", TEST_ID));
+    assert_eq!(doc["thumbnail_url"], "http://localhost:9000/mock-bucket/uploads/cover.png?signature=fake");
+    assert_eq!(doc["width"], 400);
+    assert!(doc["html"].as_str().unwrap().starts_with(&format!(
+        r#"<iframe src="http://localhost:3000/embed/projects/{}""#,
+        TEST_ID
+    )));
+}
+
+#[test]
+async fn test_project_embed_private_project_is_not_found() {
+    let private = Project {
+        title: "Secret Title".to_string(),
+        is_public: false,
+        ..embeddable_project()
+    };
+
+    let (status, _, html) = embed_response(Some(private.clone()), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!html.contains("Secret Title"));
+
+    let (status, _, body) = embed_response(Some(private), Some(fyp_portal::embed::EmbedFormat::Json)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!body.contains("Secret Title"));
+    let error: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(error["code"], "not_found");
+}
+
+#[test]
+async fn test_project_embed_escapes_hostile_title() {
+    let hostile = Project {
+        title: r#""><script>alert('x')</script>"#.to_string(),
+        author: "<b>Mallory</b>".to_string(),
+        ..embeddable_project()
+    };
+
+    let (_, _, html) = embed_response(Some(hostile.clone()), None).await;
+    assert!(!html.contains("<script>"));
+    assert!(!html.contains("<b>Mallory"));
+    assert!(html.contains("&quot;&gt;&lt;script&gt;alert(&#x27;x&#x27;)&lt;/script&gt;"));
+
+    // The iframe markup is meant to be pasted into other pages, so it is escaped too.
+    let (_, _, body) = embed_response(Some(hostile), Some(fyp_portal::embed::EmbedFormat::Json)).await;
+    let doc: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let iframe = doc["html"].as_str().unwrap();
+    assert!(!iframe.contains("<script>"));
+    assert!(iframe.contains(r#"title="&quot;&gt;&lt;script&gt;"#));
+    assert_eq!(doc["title"], r#""><script>alert('x')</script>"#);
+}
+
+#[test]
+async fn test_project_embed_does_not_sign_someone_elses_upload() {
+    let project = embeddable_project();
+    let state = create_test_state(
+        MockRepoControl {
+            recorded_uploads: Mutex::new(vec![(Uuid::new_v4(), project.cover_image.clone(), UploadPurpose::CoverImage)]),
+            get_project_result: Some(project),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let format = Some(fyp_portal::embed::EmbedFormat::Json);
+    let response = handlers::get_project_embed(State(state), Path(TEST_ID), Query(handlers::EmbedFilter { format })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let doc: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(doc["title"], "Rust Showcase");
+    assert!(doc.get("thumbnail_url").is_none_or(serde_json::Value::is_null));
+}
+
+// --- SUBMISSION READINESS TESTS ---
+
+fn draft_owned_by_student() -> Project {
+    Project {
+        user_id: TEST_ID,
+        abstract_text: "too short".to_string(),
+        ..Project::default()
+    }
+}
+
+#[test]
+async fn test_project_stats_are_owner_only() {
+    let project_id = Uuid::new_v4();
+    let stats = fyp_portal::models::ProjectStats {
+        project_id,
+        like_count: 4,
+        comment_count: 2,
+        view_count: 31,
+        likes_per_day: vec![],
+    };
+    let state = create_test_state(
+        MockRepoControl {
+            get_project_result: Some(Project { id: project_id, user_id: TEST_ID, ..Project::default() }),
+            project_stats: Some(stats.clone()),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let Json(body) = handlers::get_project_stats(student_user(), State(state.clone()), Path(project_id))
+        .await
+        .unwrap();
+    assert_eq!(body, stats);
+
+    // Someone else's project is a 404, even for an admin.
+    for user in [admin_user(), AuthUser { id: Uuid::new_v4(), role: "student".to_string() }] {
+        let err = handlers::get_project_stats(user, State(state.clone()), Path(project_id)).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
+
+#[test]
+async fn test_project_stats_count_todays_likes_in_the_last_bucket() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let project = repo.create_project(CreateProjectRequest::default(), TEST_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    for _ in 0..3 {
+        repo.like_project(Like { user_id: Uuid::new_v4(), project_id: project.id }).await;
+    }
+    repo.increment_view(project.id).await;
+
+    let Json(stats) = handlers::get_project_stats(student_user(), State(state), Path(project.id))
+        .await
+        .unwrap();
+    assert_eq!((stats.like_count, stats.comment_count, stats.view_count), (3, 0, 1));
+    assert_eq!(stats.likes_per_day.len(), fyp_portal::repository::PROJECT_STATS_DAYS as usize);
+    let today = stats.likes_per_day.last().unwrap();
+    assert_eq!(today.day, Utc::now().date_naive());
+    assert_eq!(today.count, 3);
+    assert!(stats.likes_per_day[..29].iter().all(|d| d.count == 0));
+    assert!(stats.likes_per_day.windows(2).all(|w| w[1].day - w[0].day == Duration::days(1)));
+}
+
+#[test]
+async fn test_readiness_report_lists_failed_checks() {
+    let state = create_test_state(
+        MockRepoControl {
+            get_project_result: Some(draft_owned_by_student()),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let Json(report) = handlers::get_project_readiness(student_user(), State(state), Path(TEST_ID))
+        .await
+        .unwrap();
+
+    assert!(!report.ready);
+    assert!(report.checks.iter().any(|c| c.name == "abstract_length" && !c.passed));
+    assert!(report.checks.iter().any(|c| c.name == "cover_image" && !c.passed));
+}
+
+#[test]
+async fn test_submit_refuses_unready_project_with_422() {
+    let state = create_test_state(
+        MockRepoControl {
+            get_project_result: Some(draft_owned_by_student()),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let err = handlers::submit_project(
+        student_user(),
+        State(state),
+        Path(TEST_ID),
+        Query(handlers::SubmitOptions { force: false }),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "not_ready");
+    let failed = err.body.details.unwrap()["checks"].as_array().unwrap().clone();
+    assert!(!failed.is_empty());
+    assert!(failed.iter().all(|c| c["passed"] == false));
+}
+
+#[test]
+async fn test_submit_force_override_is_admin_only() {
+    let control = || MockRepoControl {
+        get_project_result: Some(draft_owned_by_student()),
+        ..MockRepoControl::default()
+    };
+
+    // Students may not force.
+    let state = create_test_state(control(), MockStorageService::new());
+    let err = handlers::submit_project(
+        student_user(),
+        State(state),
+        Path(TEST_ID),
+        Query(handlers::SubmitOptions { force: true }),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+    // Admins may, even for a project they do not own.
+    let state = create_test_state(control(), MockStorageService::new());
+    let Json(project) = handlers::submit_project(
+        admin_user(),
+        State(state),
+        Path(TEST_ID),
+        Query(handlers::SubmitOptions { force: true }),
+    )
+    .await
+    .unwrap();
+    assert!(project.submitted_at.is_some());
+}
+
+#[test]
+async fn test_submit_ready_project_succeeds() {
+    let project = Project {
+        user_id: TEST_ID,
+        cover_image: "uploads/cover.png".to_string(),
+        abstract_text: vec!["word"; 100].join(" "),
+        video_waived: true,
+        links: serde_json::json!({ "demo": "https://example.com" }),
+        year: AppConfig::default().current_cohort_year,
+        ..Project::default()
+    };
+    let state = create_test_state(
+        MockRepoControl {
+            get_project_result: Some(project),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let Json(submitted) = handlers::submit_project(
+        student_user(),
+        State(state),
+        Path(TEST_ID),
+        Query(handlers::SubmitOptions { force: false }),
+    )
+    .await
+    .unwrap();
+    assert!(submitted.submitted_at.is_some());
+}
+
+#[test]
+async fn test_submit_that_loses_a_race_is_a_conflict() {
+    // Approved by an admin after the handler read it as an unsubmitted draft: the guarded write
+    // refuses it rather than pushing it back to pending.
+    let project = Project {
+        user_id: TEST_ID,
+        status: ProjectStatus::Approved,
+        cover_image: "uploads/cover.png".to_string(),
+        abstract_text: vec!["word"; 100].join(" "),
+        video_waived: true,
+        links: serde_json::json!({ "demo": "https://example.com" }),
+        year: AppConfig::default().current_cohort_year,
+        ..Project::default()
+    };
+    let state = create_test_state(
+        MockRepoControl {
+            get_project_result: Some(project),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let err = handlers::submit_project(
+        student_user(),
+        State(state),
+        Path(TEST_ID),
+        Query(handlers::SubmitOptions { force: false }),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::CONFLICT, "already_submitted"));
+}
+
+#[test]
+async fn test_resubmit_maps_outcomes_to_responses() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .configure(|config| config.max_resubmissions = 1)
+        .build();
+    let project = repo.create_project(CreateProjectRequest::default(), TEST_ID).await;
+    let resubmit = |user| handlers::resubmit_project(user, State(state.clone()), Path(project.id));
+
+    let err = resubmit(student_user()).await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::CONFLICT, "not_rejected"));
+    assert_eq!(err.body.details.unwrap()["status"], "draft");
+
+    repo.set_project_status(project.id, ProjectStatus::Rejected).await.unwrap();
+    // Admins are not owners here either.
+    assert_eq!(resubmit(admin_user()).await.unwrap_err().status, StatusCode::NOT_FOUND);
+    let Json(resubmitted) = resubmit(student_user()).await.unwrap();
+    assert_eq!(resubmitted.status, ProjectStatus::Pending);
+
+    repo.set_project_status(project.id, ProjectStatus::Rejected).await.unwrap();
+    let err = resubmit(student_user()).await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::CONFLICT, "resubmission_limit"));
+    assert_eq!(err.body.details.unwrap()["max_resubmissions"], 1);
+}
+
+// --- SUPERVISOR REPORT TESTS ---
+
+fn sample_supervisor_report() -> Vec<SupervisorStats> {
+    vec![
+        SupervisorStats {
+            supervisor: "Dr. Smith, PhD".to_string(),
+            project_count: 2,
+            public_count: 1,
+            avg_likes: 1.5,
+            total_comments: 4,
+        },
+        SupervisorStats {
+            supervisor: "Unassigned".to_string(),
+            project_count: 1,
+            public_count: 0,
+            avg_likes: 0.0,
+            total_comments: 0,
+        },
+    ]
+}
+
+#[test]
+async fn test_supervisor_report_is_admin_only() {
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+
+    let result = handlers::get_supervisor_report(
+        student_user(),
+        State(state),
+        Query(handlers::ReportFilter { year: None }),
+        ResponseFormat::Json,
+    )
+    .await;
+
+    assert_eq!(result.unwrap_err(), StatusCode::FORBIDDEN);
+}
+
+#[test]
+async fn test_supervisor_report_negotiates_json_and_csv() {
+    let control = || MockRepoControl {
+        supervisor_report: sample_supervisor_report(),
+        ..MockRepoControl::default()
+    };
+
+    let state = create_test_state(control(), MockStorageService::new());
+    let response = handlers::get_supervisor_report(
+        admin_user(),
+        State(state),
+        Query(handlers::ReportFilter { year: None }),
+        ResponseFormat::Json,
+    )
+    .await
+    .unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let rows: Vec<SupervisorStats> = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(rows, sample_supervisor_report());
+
+    let state = create_test_state(control(), MockStorageService::new());
+    let response = handlers::get_supervisor_report(
+        admin_user(),
+        State(state),
+        Query(handlers::ReportFilter { year: Some(2024) }),
+        ResponseFormat::Csv,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/csv; charset=utf-8"
+    );
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"supervisors-2024.csv\""
+    );
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let csv = String::from_utf8(bytes.to_vec()).unwrap();
+    assert_eq!(
+        csv,
+        "supervisor,project_count,public_count,avg_likes,total_comments\r\n\
+         \"Dr. Smith, PhD\",2,1,1.50,4\r\n\
+         Unassigned,1,0,0.00,0\r\n"
+    );
+}
+
+#[test]
+async fn test_response_format_negotiation() {
+    use axum::extract::FromRequestParts;
+
+    async fn negotiate(uri: &str, accept: Option<&str>) -> ResponseFormat {
+        let mut builder = axum::http::Request::builder().uri(uri);
+        if let Some(accept) = accept {
+            builder = builder.header("accept", accept);
+        }
+        let (mut parts, _) = builder.body(()).unwrap().into_parts();
+        ResponseFormat::from_request_parts(&mut parts, &())
+            .await
+            .unwrap()
+    }
+
+    assert_eq!(negotiate("/r", None).await, ResponseFormat::Json);
+    assert_eq!(negotiate("/r", Some("application/json")).await, ResponseFormat::Json);
+    assert_eq!(negotiate("/r?format=csv", None).await, ResponseFormat::Csv);
+    assert_eq!(negotiate("/r?year=2024&format=CSV", None).await, ResponseFormat::Csv);
+    assert_eq!(
+        negotiate("/r", Some("application/json, text/csv;q=0.9")).await,
+        ResponseFormat::Csv
+    );
+}
+
+// --- SPARSE FIELDSET TESTS ---
+
+async fn response_json(response: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+fn project_filter(fields: Option<&str>) -> handlers::ProjectFilter {
+    handlers::ProjectFilter {
+        year: None,
+        tag: None,
+        supervisor: None,
+        author: None,
+        department: None,
+        search: None,
+        programme: None,
+        fields: fields.map(str::to_string),
+        page: None,
+        per_page: None,
+        cursor: None,
+        sort: None,
+    }
+}
+
+fn projects_uri() -> OriginalUri {
+    OriginalUri(Uri::from_static("/projects"))
+}
+
+#[test]
+async fn test_sparse_fields_keep_only_selected_keys_plus_id() {
+    let state = create_test_state(
+        MockRepoControl {
+            projects_to_return: vec![Project {
+                title: "Poster".to_string(),
+                year: 2024,
+                ..Project::default()
+            }],
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let response = handlers::get_projects(None, State(state), projects_uri(), Query(project_filter(Some("title, year"))))
+        .await
+        .unwrap()
+        .into_response();
+    let body = response_json(response).await;
+
+    let project = body["items"][0].as_object().unwrap();
+    let mut keys: Vec<_> = project.keys().cloned().collect();
+    keys.sort();
+    assert_eq!(keys, vec!["id", "title", "year"]);
+    assert_eq!(project["title"], "Poster");
+}
+
+#[test]
+async fn test_sparse_fields_absent_returns_full_rows() {
+    let state = create_test_state(
+        MockRepoControl {
+            projects_to_return: vec![Project::default()],
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let response = handlers::get_projects(None, State(state), projects_uri(), Query(project_filter(None)))
+        .await
+        .unwrap()
+        .into_response();
+    let body = response_json(response).await;
+    assert!(body["items"][0].get("abstract_text").is_some());
+    assert!(body["items"][0].get("user_id").is_some());
+}
+
+#[test]
+async fn test_project_listing_returns_page_envelope_with_links() {
+    let control = Arc::new(MockRepoControl {
+        projects_to_return: vec![Project::default(); 3],
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let filter = handlers::ProjectFilter {
+        year: Some(2024),
+        page: Some("2".to_string()),
+        per_page: Some("1".to_string()),
+        ..project_filter(None)
+    };
+    let uri = OriginalUri(Uri::from_static("/projects?year=2024&page=2&per_page=1"));
+
+    let response = handlers::get_projects(None, State(state.clone()), uri, Query(filter))
+        .await
+        .unwrap()
+        .into_response();
+    let body = response_json(response).await;
+
+    assert_eq!(body["total_count"], 3);
+    assert_eq!(body["page"], 2);
+    assert_eq!(body["per_page"], 1);
+    let base = &state.config().public_api_url;
+    assert_eq!(body["links"]["next"], format!("{base}/projects?year=2024&per_page=1&page=3"));
+    assert_eq!(body["links"]["prev"], format!("{base}/projects?year=2024&per_page=1&page=1"));
+    let queries = control.project_queries.lock().unwrap();
+    assert_eq!(queries[0].0.year, Some(2024));
+    assert_eq!(queries[0].1, PageRequest { page: 2, per_page: 1, after: None });
+}
+
+#[test]
+async fn test_project_listing_rejects_invalid_pagination() {
+    for (page, per_page, field) in [
+        (Some("0"), None, "page"),
+        (Some("two"), None, "page"),
+        (None, Some("0"), "per_page"),
+        (None, Some("101"), "per_page"),
+        (None, Some("-5"), "per_page"),
+    ] {
+        let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+        let filter = handlers::ProjectFilter {
+            page: page.map(str::to_string),
+            per_page: per_page.map(str::to_string),
+            ..project_filter(None)
+        };
+        let err = handlers::get_projects(None, State(state), projects_uri(), Query(filter))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY, "{page:?} {per_page:?}");
+        assert_eq!(err.body.code, "invalid_pagination");
+        assert_eq!(err.body.details.unwrap()["field"], field);
+    }
+}
+
+#[test]
+async fn test_project_listing_search_has_a_minimum_length() {
+    let control = Arc::new(MockRepoControl::default());
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let list = |search: &str| {
+        let filter = handlers::ProjectFilter { search: Some(search.to_string()), ..project_filter(None) };
+        handlers::get_projects(None, State(state.clone()), projects_uri(), Query(filter))
+    };
+
+    for short in ["a", "  é "] {
+        let err = list(short).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY, "{short:?}");
+        assert_eq!(err.body.code, "invalid_search");
+        assert_eq!(err.body.details.unwrap()["min"], handlers::MIN_SEARCH_CHARS);
+    }
+    assert!(control.project_queries.lock().unwrap().is_empty());
+
+    // Terms reach the repository trimmed; a blank one is no search at all.
+    for (raw, expected) in [(" ab ", Some("ab")), ("   ", None)] {
+        assert!(list(raw).await.is_ok());
+        let queries = control.project_queries.lock().unwrap();
+        assert_eq!(queries.last().unwrap().0.search.as_deref(), expected);
+    }
+}
+
+#[test]
+async fn test_project_listing_sort_is_validated_and_passed_through() {
+    use fyp_portal::models::ProjectSort;
+
+    let control = Arc::new(MockRepoControl {
+        projects_to_return: vec![Project::default()],
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    for (raw, expected) in [
+        (None, ProjectSort::Newest),
+        (Some("oldest"), ProjectSort::Oldest),
+        (Some("most_liked"), ProjectSort::MostLiked),
+        (Some(" title "), ProjectSort::Title),
+        (Some("most_viewed"), ProjectSort::MostViewed),
+    ] {
+        let filter = handlers::ProjectFilter {
+            sort: raw.map(str::to_string),
+            per_page: Some("1".to_string()),
+            ..project_filter(None)
+        };
+        let response = handlers::get_projects(None, State(state.clone()), projects_uri(), Query(filter))
+            .await
+            .unwrap()
+            .into_response();
+        let body = response_json(response).await;
+        assert_eq!(control.project_queries.lock().unwrap().last().unwrap().0.sort, expected);
+        // Cursors only make sense in newest order, so other orders never hand one out.
+        assert_eq!(body["next_cursor"].is_string(), expected == ProjectSort::Newest, "{raw:?}");
+    }
+
+    let filter = handlers::ProjectFilter {
+        sort: Some("popular".to_string()),
+        ..project_filter(None)
+    };
+    let err = handlers::get_projects(None, State(state.clone()), projects_uri(), Query(filter))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "invalid_sort");
+    let details = err.body.details.unwrap();
+    assert_eq!(details["field"], "sort");
+    assert_eq!(details["valid"], serde_json::json!(["newest", "oldest", "most_liked", "title", "most_viewed"]));
+
+    let filter = handlers::ProjectFilter {
+        sort: Some("title".to_string()),
+        cursor: Some(fyp_portal::pagination::Cursor::after(&Project::default()).encode()),
+        ..project_filter(None)
+    };
+    let err = handlers::get_projects(None, State(state), projects_uri(), Query(filter))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "invalid_pagination");
+    assert_eq!(err.body.details.unwrap()["field"], "cursor");
+    assert_eq!(control.project_queries.lock().unwrap().len(), 5);
+}
+
+#[test]
+async fn test_project_listing_cursor_mode_returns_next_cursor_and_links() {
+    let newest = Project {
+        id: Uuid::new_v4(),
+        created_at: Utc::now(),
+        ..Project::default()
+    };
+    let control = Arc::new(MockRepoControl {
+        projects_to_return: vec![newest.clone()],
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+
+    // A full offset page hands out a cursor for its last row.
+    let filter = handlers::ProjectFilter {
+        per_page: Some("1".to_string()),
+        ..project_filter(None)
+    };
+    let response = handlers::get_projects(None, State(state.clone()), projects_uri(), Query(filter))
+        .await
+        .unwrap()
+        .into_response();
+    let body = response_json(response).await;
+    let cursor = body["next_cursor"].as_str().unwrap().to_string();
+
+    // Following it passes the decoded position to the repository.
+    let filter = handlers::ProjectFilter {
+        year: Some(2024),
+        per_page: Some("1".to_string()),
+        cursor: Some(cursor.clone()),
+        ..project_filter(None)
+    };
+    let uri = format!("/projects?year=2024&per_page=1&cursor={cursor}");
+    let response = handlers::get_projects(None, State(state.clone()), OriginalUri(uri.parse().unwrap()), Query(filter))
+        .await
+        .unwrap()
+        .into_response();
+    let body = response_json(response).await;
+
+    let after = control.project_queries.lock().unwrap()[1].1.after.unwrap();
+    assert_eq!(after.id, newest.id);
+    assert_eq!(after.created_at.timestamp_micros(), newest.created_at.timestamp_micros());
+    let base = &state.config().public_api_url;
+    assert_eq!(body["links"]["self"], format!("{base}{uri}"));
+    assert_eq!(
+        body["links"]["next"],
+        format!("{base}/projects?year=2024&per_page=1&cursor={}", body["next_cursor"].as_str().unwrap())
+    );
+    assert!(body["links"].get("prev").is_none());
+}
+
+#[test]
+async fn test_project_listing_rejects_invalid_cursor() {
+    for cursor in ["not-a-cursor", "abc", "3132333a6e6f7065", ""] {
+        let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+        let filter = handlers::ProjectFilter {
+            cursor: Some(cursor.to_string()),
+            ..project_filter(None)
+        };
+        let result = handlers::get_projects(None, State(state), projects_uri(), Query(filter)).await;
+        if cursor.is_empty() {
+            // An empty value is the same as no cursor.
+            assert!(result.is_ok());
+            continue;
+        }
+        let err = result.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST, "{cursor}");
+        assert_eq!(err.body.code, "invalid_cursor");
+    }
+
+    // A cursor replaces the page number; both at once is ambiguous.
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+    let cursor = fyp_portal::pagination::Cursor::after(&Project::default()).encode();
+    let filter = handlers::ProjectFilter {
+        page: Some("2".to_string()),
+        cursor: Some(cursor),
+        ..project_filter(None)
+    };
+    let err = handlers::get_projects(None, State(state), projects_uri(), Query(filter))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "invalid_pagination");
+}
+
+#[test]
+#[tracing_test::traced_test]
+async fn test_featured_projects_fall_back_to_unliked_until_enough_are_liked() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let publish = || async {
+        let request = CreateProjectRequest {
+            title: "Featured".to_string(),
+            ..CreateProjectRequest::default()
+        };
+        let id = repo.create_project(request, Uuid::new_v4()).await.id;
+        repo.set_project_status(id, ProjectStatus::Approved).await.unwrap();
+        id
+    };
+    let like = |project_id| repo.like_project(Like { user_id: Uuid::new_v4(), project_id });
+    let featured = || async {
+        let filter = handlers::FeaturedFilter { limit: None, year: None };
+        handlers::get_featured_projects(State(state.clone()), Query(filter)).await.unwrap().0
+    };
+    let ids = |projects: Vec<Project>| projects.into_iter().map(|p| p.id).collect::<Vec<_>>();
+
+    let liked = [publish().await, publish().await];
+    for id in liked {
+        like(id).await;
+    }
+    let unliked = [publish().await, publish().await];
+
+    // Two liked projects: the newest unliked one fills the third slot.
+    assert_eq!(ids(featured().await), vec![liked[1], liked[0], unliked[1]]);
+    assert!(logs_contain("falling back to min_likes=0"));
+
+    // A third liked project: only liked projects are featured.
+    like(unliked[0]).await;
+    assert_eq!(ids(featured().await), vec![unliked[0], liked[1], liked[0]]);
+}
+
+#[test]
+async fn test_featured_projects_limit_is_validated_and_capped() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let owner = Uuid::new_v4();
+    for year in [2023, 2024].into_iter().cycle().take(50) {
+        let request = CreateProjectRequest { year, ..CreateProjectRequest::default() };
+        let id = repo.create_project(request, owner).await.id;
+        repo.set_project_status(id, ProjectStatus::Approved).await.unwrap();
+    }
+    let featured = |limit: Option<&str>, year: Option<i32>| {
+        let filter = handlers::FeaturedFilter { limit: limit.map(str::to_string), year };
+        handlers::get_featured_projects(State(state.clone()), Query(filter))
+    };
+
+    assert_eq!(featured(None, None).await.unwrap().0.len(), handlers::DEFAULT_FEATURED_LIMIT as usize);
+    assert_eq!(featured(Some("10"), None).await.unwrap().0.len(), 10);
+    assert_eq!(featured(Some("500"), None).await.unwrap().0.len(), handlers::MAX_FEATURED_LIMIT as usize);
+
+    let top_2023 = featured(Some("500"), Some(2023)).await.unwrap().0;
+    assert_eq!(top_2023.len(), handlers::MAX_FEATURED_LIMIT as usize);
+    assert!(top_2023.iter().all(|p| p.year == 2023));
+    assert!(featured(None, Some(1999)).await.unwrap().0.is_empty());
+
+    for bad in ["0", "-3", "three"] {
+        let err = featured(Some(bad), None).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY, "{bad}");
+        assert_eq!(err.body.code, "invalid_limit");
+        assert_eq!(err.body.details.unwrap()["field"], "limit");
+    }
+}
+
+#[test]
+async fn test_featured_projects_default_to_the_latest_cohort() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let publish = |year| {
+        let repo = repo.clone();
+        async move {
+            let request = CreateProjectRequest { year, ..CreateProjectRequest::default() };
+            let id = repo.create_project(request, Uuid::new_v4()).await.id;
+            repo.set_project_status(id, ProjectStatus::Approved).await.unwrap();
+            id
+        }
+    };
+    let like = |project_id, fans: usize| {
+        let repo = repo.clone();
+        async move {
+            for _ in 0..fans {
+                repo.like_project(Like { user_id: Uuid::new_v4(), project_id }).await;
+            }
+        }
+    };
+    let featured = |year: Option<i32>| {
+        let filter = handlers::FeaturedFilter { limit: Some("5".to_string()), year };
+        let state = state.clone();
+        async move {
+            let projects = handlers::get_featured_projects(State(state), Query(filter)).await.unwrap().0;
+            projects.into_iter().map(|p| p.id).collect::<Vec<_>>()
+        }
+    };
+
+    // Last year's hit has far more likes than anything from this year.
+    let viral = publish(2023).await;
+    like(viral, 10).await;
+    let current = [publish(2024).await, publish(2024).await];
+    like(current[0], 1).await;
+    like(current[1], 2).await;
+    // A newer draft does not make its year the latest cohort.
+    repo.create_project(CreateProjectRequest { year: 2025, ..CreateProjectRequest::default() }, Uuid::new_v4())
+        .await;
+
+    assert_eq!(featured(None).await, vec![current[1], current[0]]);
+    assert_eq!(featured(Some(2023)).await, vec![viral]);
+    assert!(featured(Some(2025)).await.is_empty());
+}
+
+#[test]
+async fn test_random_project_is_public_and_daily_pick_is_stable() {
+    use tower::util::ServiceExt;
+
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let app = fyp_portal::create_router(state);
+    let get = |uri: &'static str| {
+        let request = axum::http::Request::get(uri).body(axum::body::Body::empty()).unwrap();
+        app.clone().oneshot(request)
+    };
+    let owner = Uuid::new_v4();
+
+    // Nothing public yet: a private project is never picked.
+    let hidden = repo.create_project(CreateProjectRequest::default(), owner).await.id;
+    let response = get("/projects/random").await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response_json(response).await["code"], "not_found");
+
+    let mut public = Vec::new();
+    for _ in 0..5 {
+        let id = repo.create_project(CreateProjectRequest::default(), owner).await.id;
+        repo.set_project_status(id, ProjectStatus::Approved).await.unwrap();
+        public.push(id.to_string());
+    }
+    for _ in 0..20 {
+        let response = get("/projects/random").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "no-store");
+        let id = response_json(response).await["id"].as_str().unwrap().to_string();
+        assert!(public.contains(&id), "{id} is not public (hidden: {hidden})");
+    }
+
+    let daily = get("/projects/random?daily=true").await.unwrap();
+    assert_eq!(daily.headers()[header::CACHE_CONTROL], "public, max-age=300");
+    let pick = response_json(daily).await["id"].clone();
+    for _ in 0..5 {
+        assert_eq!(response_json(get("/projects/random?daily=true").await.unwrap()).await["id"], pick);
+    }
+}
+
+#[test]
+async fn test_spotlight_seed_changes_at_midnight_utc() {
+    let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+    assert_eq!(handlers::spotlight_seed(at("1970-01-01T23:59:59Z")), 0);
+    assert_eq!(handlers::spotlight_seed(at("2024-05-01T00:00:00Z")), handlers::spotlight_seed(at("2024-05-01T23:59:59Z")));
+    assert_eq!(handlers::spotlight_seed(at("2024-05-02T00:00:00+01:00")), handlers::spotlight_seed(at("2024-05-01T12:00:00Z")));
+    assert_eq!(handlers::spotlight_seed(at("2024-05-02T00:00:00Z")), handlers::spotlight_seed(at("2024-05-01T00:00:00Z")) + 1);
+}
+
+#[test]
+async fn test_year_archive_lists_one_cohort_most_liked_first() {
+    use tower::util::ServiceExt;
+
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let owner = Uuid::new_v4();
+    let seed = |year: i32, likes: usize, status: ProjectStatus| {
+        let repo = repo.clone();
+        async move {
+            let request = CreateProjectRequest { year, ..CreateProjectRequest::default() };
+            let id = repo.create_project(request, owner).await.id;
+            repo.set_project_status(id, status).await.unwrap();
+            for _ in 0..likes {
+                repo.like_project(Like { user_id: Uuid::new_v4(), project_id: id }).await;
+            }
+            id
+        }
+    };
+    let quiet_2023 = seed(2023, 0, ProjectStatus::Approved).await;
+    let popular_2023 = seed(2023, 2, ProjectStatus::Approved).await;
+    seed(2023, 5, ProjectStatus::Rejected).await;
+    let only_2024 = seed(2024, 1, ProjectStatus::Approved).await;
+
+    let app = fyp_portal::create_router(state);
+    let get = |year: &str| {
+        let request = axum::http::Request::get(format!("/projects/archive/{year}"))
+            .body(axum::body::Body::empty())
+            .unwrap();
+        app.clone().oneshot(request)
+    };
+    let ids = |body: serde_json::Value| {
+        body.as_array().unwrap().iter().map(|p| p["id"].as_str().unwrap().to_string()).collect::<Vec<_>>()
+    };
+
+    let response = get("2023").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(ids(response_json(response).await), [popular_2023.to_string(), quiet_2023.to_string()]);
+    let response = get("2024").await.unwrap();
+    assert_eq!(ids(response_json(response).await), [only_2024.to_string()]);
+
+    let response = get("2019").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response_json(response).await, serde_json::json!([]));
+    assert_eq!(get("class-of-2023").await.unwrap().status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+async fn test_sparse_fields_unknown_name_is_422_with_valid_options() {
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+
+    let err = handlers::get_projects(None, State(state), projects_uri(), Query(project_filter(Some("title,nope"))))
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "unknown_fields");
+    let details = err.body.details.unwrap();
+    assert_eq!(details["unknown"], serde_json::json!(["nope"]));
+    let valid = details["valid"].as_array().unwrap();
+    assert!(valid.contains(&serde_json::json!("title")));
+}
+
+#[test]
+async fn test_sparse_fields_user_id_only_selectable_by_admin() {
+    let control = || MockRepoControl {
+        projects_to_return: vec![Project::default()],
+        ..MockRepoControl::default()
+    };
+
+    // Anonymous listing: user_id is not an eligible field.
+    let state = create_test_state(control(), MockStorageService::new());
+    let err = handlers::get_projects(None, State(state), projects_uri(), Query(project_filter(Some("user_id"))))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    let valid = err.body.details.unwrap()["valid"].clone();
+    assert!(!valid.as_array().unwrap().contains(&serde_json::json!("user_id")));
+
+    // Admin listing: user_id may be selected.
+    let state = create_test_state(control(), MockStorageService::new());
+    let response = handlers::get_admin_projects(
+        admin_user(),
+        State(state),
+        Query(handlers::FieldsFilter {
+            fields: Some("user_id".to_string()),
+        }),
+    )
+    .await
+    .unwrap()
+    .into_response();
+    let body = response_json(response).await;
+    let mut keys: Vec<_> = body[0].as_object().unwrap().keys().cloned().collect();
+    keys.sort();
+    assert_eq!(keys, vec!["id", "user_id"]);
+}
+
+// --- Status Update Payload ---
+
+async fn extract_status(body: &str) -> Result<StatusUpdate, fyp_portal::error::ApiError> {
+    let request = axum::http::Request::builder()
+        .method("PUT")
+        .header("content-type", "application/json")
+        .body(axum::body::Body::from(body.to_string()))
+        .unwrap();
+    StatusUpdate::from_request(request, &()).await
+}
+
+#[test]
+#[tracing_test::traced_test]
+async fn test_status_update_accepts_legacy_boolean_with_warning() {
+    let StatusUpdate(payload) = extract_status("true").await.unwrap();
+    assert_eq!(
+        payload,
+        UpdateStatusRequest {
+            status: ProjectStatus::Approved,
+            reason: None,
+            notify_owner: false,
+        }
+    );
+    assert!(logs_contain("deprecated"));
+
+    let StatusUpdate(payload) = extract_status("false").await.unwrap();
+    assert_eq!(payload.status, ProjectStatus::Rejected);
+}
+
+#[test]
+#[tracing_test::traced_test]
+async fn test_status_update_accepts_structured_payload() {
+    let StatusUpdate(payload) = extract_status(
+        r#"{"status": "rejected", "reason": "Contains personal data", "notify_owner": true}"#,
+    )
+    .await
+    .unwrap();
+    assert_eq!(payload.status, ProjectStatus::Rejected);
+    assert_eq!(payload.reason.as_deref(), Some("Contains personal data"));
+    assert!(payload.notify_owner);
+    assert!(!logs_contain("deprecated"));
+
+    let err = extract_status(r#"{"reason": "missing status"}"#)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "invalid_body");
+
+    let err = extract_status(r#"{"status": "published"}"#).await.unwrap_err();
+    assert_eq!(err.body.code, "invalid_body");
+}
+
+// --- Project Patch Payload ---
+
+async fn extract_patch(body: &str) -> Result<ProjectPatch, fyp_portal::error::ApiError> {
+    let request = axum::http::Request::builder()
+        .method("PUT")
+        .header("content-type", "application/json")
+        .body(axum::body::Body::from(body.to_string()))
+        .unwrap();
+    ProjectPatch::from_request(request, &()).await
+}
+
+#[test]
+async fn test_project_patch_rejects_immutable_fields() {
+    let err = extract_patch(r#"{"title": "New", "year": 2019, "user_id": "00000000-0000-0000-0000-000000000000"}"#)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "immutable_field");
+    assert_eq!(err.body.details.unwrap()["fields"], serde_json::json!(["user_id"]));
+
+    let ProjectPatch(payload) = extract_patch(r#"{"title": "New"}"#).await.unwrap();
+    assert_eq!(payload.title.as_deref(), Some("New"));
+    // Author and year can be corrected; `author` is the response spelling of `author_name`.
+    let ProjectPatch(payload) = extract_patch(r#"{"year": 2024, "author": "Ada"}"#).await.unwrap();
+    assert_eq!((payload.year, payload.author_name.as_deref()), (Some(2024), Some("Ada")));
+    let err = extract_patch(r#"{"title": 7}"#).await.unwrap_err();
+    assert_eq!(err.body.code, "invalid_body");
+}
+
+#[test]
+async fn test_status_update_notifies_owner_with_reason() {
+    let project_id = Uuid::new_v4();
+    let control = Arc::new(MockRepoControl {
+        get_project_result: Some(Project {
+            id: project_id,
+            ..Project::default()
+        }),
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+
+    let Json(hidden) = handlers::update_project_status(
+        admin_user(),
+        State(state.clone()),
+        Path(project_id),
+        StatusUpdate(UpdateStatusRequest {
+            status: ProjectStatus::Rejected,
+            reason: Some("Missing consent form".to_string()),
+            notify_owner: true,
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(hidden.id, project_id);
+
+    // Without notify_owner or a reason no notification is recorded.
+    let _ = handlers::update_project_status(
+        admin_user(),
+        State(state),
+        Path(project_id),
+        StatusUpdate(UpdateStatusRequest {
+            status: ProjectStatus::Approved,
+            ..UpdateStatusRequest::default()
+        }),
+    )
+    .await
+    .unwrap();
+
+    let recorded = control.status_notifications.lock().unwrap();
+    assert_eq!(
+        *recorded,
+        vec![(
+            project_id,
+            TEST_ADMIN_ID,
+            Some("Missing consent form".to_string())
+        )]
+    );
+}
+
+#[test]
+async fn test_bulk_status_reports_changes_and_unknown_ids() {
+    let (first, second, unknown) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let control = Arc::new(MockRepoControl {
+        missing_ids: vec![unknown],
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let bulk = |user, ids: Vec<Uuid>| {
+        handlers::bulk_update_project_status(user, State(state.clone()), Json(BulkStatusRequest { ids, is_public: true }))
+    };
+
+    let err = bulk(student_user(), vec![first]).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+    // Repeated ids are sent once; each changed project gets a review without a reason.
+    let Json(response) = bulk(admin_user(), vec![first, unknown, second, first]).await.unwrap();
+    assert_eq!(response.updated, 2);
+    assert_eq!(response.not_found, vec![unknown]);
+    assert_eq!(*control.bulk_status_calls.lock().unwrap(), vec![(vec![first, unknown, second], true)]);
+    let reviews = control.reviews.lock().unwrap().clone();
+    let reviewed: Vec<_> = reviews.iter().map(|(admin, r)| (*admin, r.project_id, r.status, r.reason.clone())).collect();
+    assert_eq!(
+        reviewed,
+        vec![
+            (TEST_ADMIN_ID, first, ProjectStatus::Approved, None),
+            (TEST_ADMIN_ID, second, ProjectStatus::Approved, None),
+        ]
+    );
+    assert!(control.status_notifications.lock().unwrap().is_empty());
+
+    // The batch is capped after de-duplication.
+    let too_many: Vec<Uuid> = (0..=handlers::BULK_STATUS_MAX_IDS).map(|_| Uuid::new_v4()).collect();
+    let err = bulk(admin_user(), too_many).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "too_many_ids");
+    assert_eq!(control.bulk_status_calls.lock().unwrap().len(), 1);
+    let at_limit = vec![first; handlers::BULK_STATUS_MAX_IDS + 1];
+    assert!(bulk(admin_user(), at_limit).await.is_ok());
+}
+
+#[test]
+async fn test_pending_review_queue_is_admin_only_and_limited() {
+    let submitted = Utc::now() - Duration::hours(3);
+    let waiting = Project {
+        id: Uuid::new_v4(),
+        status: ProjectStatus::Pending,
+        submitted_at: Some(submitted),
+        ..Project::default()
+    };
+    let control = Arc::new(MockRepoControl {
+        projects_to_return: vec![waiting.clone()],
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let queue = |user, limit: Option<&str>| {
+        let filter = handlers::PendingQueueFilter { limit: limit.map(str::to_string) };
+        handlers::get_pending_reviews(user, State(state.clone()), Query(filter))
+    };
+
+    let err = queue(student_user(), None).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+    assert!(control.pending_queue_limits.lock().unwrap().is_empty());
+
+    let Json(entries) = queue(admin_user(), None).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].project.id, waiting.id);
+    assert_eq!(entries[0].owner_email, Some(format!("{}@test.com", waiting.user_id)));
+    assert_eq!(entries[0].waiting_since, submitted);
+    assert!((3 * 3600..3 * 3600 + 60).contains(&entries[0].waiting_seconds));
+
+    // The entry is the project, flattened, plus the queue fields.
+    let body = serde_json::to_value(&entries[0]).unwrap();
+    assert_eq!(body["id"], waiting.id.to_string());
+    assert_eq!(body["status"], "pending");
+    assert!(body["waiting_seconds"].is_i64());
+
+    let _ = queue(admin_user(), Some("500")).await.unwrap();
+    assert_eq!(
+        *control.pending_queue_limits.lock().unwrap(),
+        vec![handlers::DEFAULT_PENDING_LIMIT, handlers::MAX_PENDING_LIMIT]
+    );
+    let err = queue(admin_user(), Some("0")).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.details.unwrap()["max"], handlers::MAX_PENDING_LIMIT);
+}
+
+#[test]
+async fn test_pending_review_entry_falls_back_to_creation_time() {
+    let created = Utc::now() - Duration::days(2);
+    let entry = fyp_portal::models::PendingReview::new(
+        Project { status: ProjectStatus::Pending, created_at: created, ..Project::default() },
+        None,
+        created + Duration::minutes(90),
+    );
+    assert_eq!(entry.waiting_since, created);
+    assert_eq!(entry.waiting_seconds, 90 * 60);
+}
+
+#[test]
+async fn test_reassign_project_owner_is_admin_only_and_audited() {
+    let (project_id, new_owner) = (Uuid::new_v4(), Uuid::new_v4());
+    let control = Arc::new(MockRepoControl {
+        get_project_result: Some(Project { id: project_id, user_id: TEST_ID, ..Project::default() }),
+        known_user_ids: vec![new_owner],
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let reassign = |user, new_owner_id| {
+        handlers::reassign_project_owner(user, State(state.clone()), Path(project_id), Json(ReassignOwnerRequest { new_owner_id }))
+    };
+
+    let err = reassign(student_user(), new_owner).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+    assert!(control.owner_transfers.lock().unwrap().is_empty());
+
+    // An unknown target account is a 404 that names the field.
+    let err = reassign(admin_user(), Uuid::new_v4()).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::NOT_FOUND);
+    assert_eq!(err.body.code, "not_found");
+    assert_eq!(err.body.details.as_ref().unwrap()["field"], "new_owner_id");
+
+    let Json(project) = reassign(admin_user(), new_owner).await.unwrap();
+    assert_eq!(project.user_id, new_owner);
+    assert_eq!(*control.owner_transfers.lock().unwrap(), vec![(project_id, new_owner, TEST_ADMIN_ID)]);
+    let audit = control.audit_entries.lock().unwrap().clone();
+    assert_eq!(audit.len(), 1);
+    assert_eq!(audit[0].action, "project.owner");
+    assert_eq!(audit[0].target_id, Some(project_id.to_string()));
+    assert_eq!(audit[0].details["from"], TEST_ID.to_string());
+    assert_eq!(audit[0].details["to"], new_owner.to_string());
+}
+
+#[test]
+async fn test_reassign_missing_project_owner_is_not_found() {
+    let new_owner = Uuid::new_v4();
+    let control = Arc::new(MockRepoControl {
+        get_project_result: None,
+        known_user_ids: vec![new_owner],
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+
+    let err = handlers::reassign_project_owner(
+        admin_user(),
+        State(state),
+        Path(Uuid::new_v4()),
+        Json(ReassignOwnerRequest { new_owner_id: new_owner }),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.status, StatusCode::NOT_FOUND);
+    assert!(err.body.details.is_none());
+    assert!(control.owner_transfers.lock().unwrap().is_empty());
+    assert!(control.audit_entries.lock().unwrap().is_empty());
+}
+
+#[test]
+async fn test_rejection_reason_is_reviewed_notified_and_shown_to_owner() {
+    let project_id = Uuid::new_v4();
+    let other_id = Uuid::new_v4();
+    let control = Arc::new(MockRepoControl {
+        get_project_result: Some(Project {
+            id: project_id,
+            ..Project::default()
+        }),
+        projects_to_return: vec![
+            Project { id: project_id, ..Project::default() },
+            Project { id: other_id, ..Project::default() },
+        ],
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let set_status = |status, reason: &str| {
+        handlers::update_project_status(
+            admin_user(),
+            State(state.clone()),
+            Path(project_id),
+            StatusUpdate(UpdateStatusRequest {
+                status,
+                reason: Some(reason.to_string()),
+                notify_owner: false,
+            }),
+        )
+    };
+
+    // A blank reason counts as none: reviewed, but the owner is not notified.
+    let _ = set_status(ProjectStatus::Pending, "   ").await.unwrap();
+    assert!(control.status_notifications.lock().unwrap().is_empty());
+    // A reason notifies the owner even without notify_owner.
+    let _ = set_status(ProjectStatus::Rejected, "  No ethics approval ").await.unwrap();
+    assert_eq!(
+        *control.status_notifications.lock().unwrap(),
+        vec![(project_id, TEST_ADMIN_ID, Some("No ethics approval".to_string()))]
+    );
+    let reviews: Vec<_> = control
+        .reviews
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(reviewer, r)| (*reviewer, r.status, r.reason.clone()))
+        .collect();
+    assert_eq!(
+        reviews,
+        vec![
+            (TEST_ADMIN_ID, ProjectStatus::Pending, None),
+            (TEST_ADMIN_ID, ProjectStatus::Rejected, Some("No ethics approval".to_string())),
+        ]
+    );
+
+    // The owner's listing carries the latest review; unreviewed projects omit the field.
+    let response = handlers::get_my_projects(student_user(), State(state)).await.into_response();
+    let body = response_json(response).await;
+    assert_eq!(body[0]["id"], project_id.to_string());
+    assert_eq!(body[0]["latest_review"]["status"], "rejected");
+    assert_eq!(body[0]["latest_review"]["reason"], "No ethics approval");
+    assert!(body[1].get("latest_review").is_none());
+}
+
+#[test]
+async fn test_my_projects_summarise_review_state_and_counts() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let fan = repo.create_user(User { id: Uuid::new_v4(), email: "fan@ul.ie".to_string(), role: "student".to_string() }).await.id;
+    let fresh = repo.create_project(CreateProjectRequest::default(), TEST_ID).await;
+
+    let body = response_json(handlers::get_my_projects(student_user(), State(state.clone())).await.into_response()).await;
+    assert_eq!(body[0]["id"], fresh.id.to_string());
+    assert_eq!(body[0]["status"], "draft");
+    assert_eq!(body[0]["review_state"], "pending");
+    assert_eq!((body[0]["like_count"].as_i64(), body[0]["comment_count"].as_i64()), (Some(0), Some(0)));
+
+    let approved = repo.create_project(CreateProjectRequest::default(), TEST_ID).await;
+    repo.set_project_status(approved.id, ProjectStatus::Approved).await.unwrap();
+    repo.like_project(Like { user_id: fan, project_id: approved.id }).await.unwrap();
+    repo.add_comment(approved.id, fan, "Nice work".to_string(), 100).await.unwrap().unwrap();
+    repo.set_project_status(fresh.id, ProjectStatus::Rejected).await.unwrap();
+
+    // Newest first.
+    let Json(summaries) = handlers::get_my_projects(student_user(), State(state)).await;
+    let states: Vec<_> = summaries
+        .iter()
+        .map(|s| (s.project.id, s.review_state, s.like_count, s.project.comment_count))
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            (approved.id, ReviewState::Approved, 1, 1),
+            (fresh.id, ReviewState::Hidden, 0, 0),
+        ]
+    );
+}
+
+// --- Deep Health ---
+
+#[test]
+async fn test_deep_health_is_red_when_the_database_is_down() {
+    let state = create_test_state(
+        MockRepoControl { ping_fails: true, ..MockRepoControl::default() },
+        MockStorageService::new(),
+    );
+    let response = handlers::get_deep_health(State(state)).await.into_response();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = response_json(response).await;
+    assert_eq!(body["status"], "red");
+    assert_eq!(body["database"], false);
+    assert_eq!(body["tasks"], serde_json::json!([]));
+    // The connection error is logged, not returned.
+    assert!(!body.to_string().contains("connection refused"));
+}
+
+// --- Error Log ---
+
+#[test]
+async fn test_failed_requests_are_summarised_by_route_and_code() {
+    use tower::util::ServiceExt;
+
+    let state = AppStateBuilder::test_default()
+        .repo(Arc::new(MockRepoControl {
+            ping_fails: true,
+            get_user_role: "admin".to_string(),
+            ..MockRepoControl::default()
+        }))
+        .storage(Arc::new(MockStorageService::new_failing()))
+        .configure(|config| config.rate_limit_per_minute = 2)
+        .build();
+    let app = fyp_portal::create_router(state);
+    let send = |method: &str, uri: &str, client: &str| {
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-user-id", TEST_ADMIN_ID.to_string())
+            .extension(axum::extract::ConnectInfo(std::net::SocketAddr::new(client.parse().unwrap(), 40_000)))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(r#"{"filename":"demo.mp4","file_type":"video/mp4"}"#))
+            .unwrap();
+        app.clone().oneshot(request)
+    };
+
+    // The failing repository turns deep health red; the failing storage fails both presigns
+    // the limiter allows, and the third is rate limited. A 403 is not an error to track.
+    for _ in 0..2 {
+        let response = send("GET", "/health/deep", "10.0.0.1").await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+    let mut request_ids = vec![];
+    for expected in [500, 500, 429] {
+        let response = send("POST", "/upload/presigned", "10.0.0.2").await.unwrap();
+        assert_eq!(response.status().as_u16(), expected);
+        request_ids.push(response.headers()["x-request-id"].to_str().unwrap().to_string());
+    }
+
+    let response = send("GET", "/admin/errors?window=15m&samples=2", "10.0.0.3").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    assert_eq!(body["window"], "15m");
+    assert_eq!(body["total"], 5);
+    let groups: Vec<_> = body["groups"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|g| (g["route"].as_str().unwrap(), g["code"].as_str().unwrap(), g["status"].as_u64().unwrap(), g["count"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            ("GET /health/deep", "service_unavailable", 503, 2),
+            ("POST /upload/presigned", "storage_error", 500, 2),
+            ("POST /upload/presigned", "rate_limited", 429, 1),
+        ]
+    );
+    let recent: Vec<_> = body["recent"].as_array().unwrap().iter().map(|s| s["request_id"].as_str().unwrap()).collect();
+    assert_eq!(recent, [request_ids[2].as_str(), request_ids[1].as_str()]);
+}
+
+#[test]
+async fn test_error_summary_requires_admin() {
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+    let result = handlers::get_admin_errors(
+        student_user(),
+        State(state),
+        Query(handlers::ErrorSummaryQuery { window: None, samples: None }),
+    )
+    .await;
+    assert_eq!(result.unwrap_err().status, StatusCode::FORBIDDEN);
+}
+
+// --- Audit Log ---
+
+#[test]
+async fn test_status_change_is_audited_even_when_the_audit_insert_fails() {
+    let project_id = Uuid::new_v4();
+    for audit_insert_fails in [false, true] {
+        let control = Arc::new(MockRepoControl {
+            get_project_result: Some(Project { id: project_id, ..Project::default() }),
+            audit_insert_fails,
+            ..MockRepoControl::default()
+        });
+        let state = AppStateBuilder::test_default().repo(control.clone()).build();
+        let result = handlers::update_project_status(
+            admin_user(),
+            State(state),
+            Path(project_id),
+            StatusUpdate(UpdateStatusRequest {
+                status: ProjectStatus::Rejected,
+                reason: Some("Missing consent form".to_string()),
+                ..UpdateStatusRequest::default()
+            }),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        // A failed insert leaves the entry queued in the outbox instead.
+        let written = control.audit_entries.lock().unwrap().clone();
+        let queued = control.queued_audit.lock().unwrap().clone();
+        let (entry, missing) = if audit_insert_fails { (&queued, &written) } else { (&written, &queued) };
+        assert!(missing.is_empty());
+        assert_eq!(entry.len(), 1);
+        assert_eq!(entry[0].actor_id, Some(TEST_ADMIN_ID));
+        assert_eq!((entry[0].action.as_str(), entry[0].target_type.as_str()), ("project.status", "project"));
+        assert_eq!(entry[0].target_id, Some(project_id.to_string()));
+        assert_eq!(entry[0].details["reason"], "Missing consent form");
+    }
+}
+
+#[test]
+async fn test_audit_log_filters_by_actor_and_time_range_and_downloads_as_csv() {
+    use tower::util::ServiceExt;
+
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default().repo(repo.clone()).build();
+    let mut admins = vec![];
+    for name in ["first", "second"] {
+        let id = Uuid::new_v4();
+        repo.create_user(User { id, email: format!("{name}@admin.com"), role: "admin".to_string() }).await;
+        admins.push(id);
+    }
+    let project = repo.create_project(CreateProjectRequest::default(), Uuid::new_v4()).await;
+    let app = fyp_portal::create_router(state);
+    let send = |method: &str, uri: String, admin: Uuid, body: &str| {
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-user-id", admin.to_string())
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap();
+        app.clone().oneshot(request)
+    };
+
+    // Three admin actions, a few milliseconds apart so each has its own timestamp.
+    for (admin, status) in [(admins[0], "approved"), (admins[1], "rejected"), (admins[0], "approved")] {
+        let body = format!(r#"{{"status":"{status}"}}"#);
+        let response = send("PUT", format!("/admin/projects/{}/status", project.id), admin, &body).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    let (log, _) = repo.get_audit_log(&AuditFilter::default(), PageRequest::default()).await;
+    let at = |i: usize| log[i].created_at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true);
+    let ids = |body: &serde_json::Value| body["items"].as_array().unwrap().iter().map(|e| e["id"].as_i64().unwrap()).collect::<Vec<_>>();
+
+    let response = send("GET", format!("/admin/audit?actor_id={}&per_page=1", admins[0]), admins[1], "").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    assert_eq!((body["total_count"].as_i64(), ids(&body)), (Some(2), vec![log[0].id]));
+    assert_eq!(body["items"][0]["action"], "project.status");
+    assert_eq!(body["items"][0]["target_id"], project.id.to_string());
+
+    // `from` is inclusive and `to` exclusive: the middle entry alone.
+    let uri = format!("/admin/audit?action=project.status&from={}&to={}", at(1), at(0));
+    let body = response_json(send("GET", uri, admins[0], "").await.unwrap()).await;
+    assert_eq!(ids(&body), vec![log[1].id]);
+    let uri = format!("/admin/audit?from={}&to={}", at(0), at(1));
+    let response = send("GET", uri, admins[0], "").await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    // CSV has every matching entry, not just one page.
+    let uri = format!("/admin/audit?actor_id={}&per_page=1&format=csv", admins[0]);
+    let response = send("GET", uri, admins[0], "").await.unwrap();
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv; charset=utf-8");
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let csv = String::from_utf8(bytes.to_vec()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "id,created_at,actor_id,action,target_type,target_id,details");
+    assert_eq!(lines.len(), 3);
+    let row = |i: usize| {
+        let entry = &log[i];
+        format!(
+            r#"{},{},{},project.status,project,{},"{{""reason"":null,""status"":""approved""}}""#,
+            entry.id,
+            entry.created_at.to_rfc3339(),
+            admins[0],
+            project.id
+        )
+    };
+    assert_eq!(lines[1..], [row(0), row(2)]);
+}
+
+#[test]
+async fn test_audit_log_requires_admin() {
+    use tower::util::ServiceExt;
+
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+    let request = axum::http::Request::get("/admin/audit")
+        .header("x-user-id", Uuid::new_v4().to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = fyp_portal::create_router(state).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+// --- Request Prioritization ---
+
+#[tokio::test(start_paused = true)]
+async fn test_admin_request_gets_a_reserved_permit_while_public_reads_flood_the_pool() {
+    use fyp_portal::repository::priority::{PriorityRepository, RequestClass};
+    use tower::util::ServiceExt;
+
+    // Two permits, like a 2-connection pool; with one reserved, the admin gets through.
+    // Without the reservation the same flood holds both permits and the admin times out.
+    for (reserved, admin_gets_through) in [(1, true), (0, false)] {
+        let repo = Arc::new(PriorityRepository::new(
+            Arc::new(MockRepoControl {
+                get_user_role: "admin".to_string(),
+                slow_reads: Some(std::time::Duration::from_secs(30)),
+                ..MockRepoControl::default()
+            }),
+            2,
+            reserved,
+        ));
+        let state = AppStateBuilder::test_default()
+            .repo(repo.clone())
+            .configure(|config| config.rate_limit_per_minute = 1000)
+            .build();
+        let app = fyp_portal::create_router(state);
+        let send = |uri: &str, user: Option<Uuid>| {
+            let mut request = axum::http::Request::get(uri);
+            if let Some(user) = user {
+                request = request.header("x-user-id", user.to_string());
+            }
+            app.clone().oneshot(request.body(axum::body::Body::empty()).unwrap())
+        };
+
+        let flood: Vec<_> = (0..10).map(|_| tokio::spawn(send("/projects", None))).collect();
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        let queued = 10 - (2 - reserved);
+        assert_eq!(repo.gate().queue_depth(RequestClass::Public), queued);
+
+        let admin = tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            send("/admin/stats", Some(TEST_ADMIN_ID)),
+        )
+        .await;
+        assert_eq!(admin.is_ok(), admin_gets_through, "reserved = {reserved}");
+        if let Ok(response) = admin {
+            assert_eq!(response.unwrap().status(), StatusCode::OK);
+            assert_eq!(repo.gate().queue_depth(RequestClass::Public), queued);
+        }
+        for request in flood {
+            request.abort();
+        }
+    }
+    assert!(metrics::render().contains("# TYPE db_permit_queue_depth gauge"));
+}
+
+// --- Comment Subscriptions ---
+
+#[test]
+async fn test_project_details_include_subscription_only_when_signed_in() {
+    let control = || MockRepoControl {
+        get_project_result: Some(Project {
+            is_public: true,
+            ..Project::default()
+        }),
+        subscription: Some(SubscriptionState::Muted),
+        ..MockRepoControl::default()
+    };
+
+    let state = create_test_state(control(), MockStorageService::new());
+    let response = handlers::get_project_details(None, State(state), Path(TEST_ID))
+        .await
+        .unwrap()
+        .into_response();
+    let body = response_json(response).await;
+    assert!(body.get("subscription").is_none());
+    assert!(body.get("title").is_some());
+
+    let state = create_test_state(control(), MockStorageService::new());
+    let response = handlers::get_project_details(Some(student_user()), State(state), Path(TEST_ID))
+        .await
+        .unwrap()
+        .into_response();
+    let body = response_json(response).await;
+    assert_eq!(body["subscription"], "muted");
+}
+
+#[test]
+async fn test_set_subscription_records_preference() {
+    let control = Arc::new(MockRepoControl {
+        get_project_result: Some(Project {
+            is_public: true,
+            ..Project::default()
+        }),
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+
+    let Json(saved) = handlers::set_project_subscription(
+        student_user(),
+        State(state),
+        Path(TEST_ID),
+        Json(SubscriptionState::Muted),
+    )
+    .await
+    .unwrap();
+    assert_eq!(saved, SubscriptionState::Muted);
+    assert_eq!(
+        *control.subscription_updates.lock().unwrap(),
+        vec![(TEST_ID, TEST_ID, SubscriptionState::Muted)]
+    );
+}
+
+#[test]
+async fn test_set_subscription_on_hidden_project_is_not_found() {
+    let state = create_test_state(
+        MockRepoControl {
+            get_project_result: Some(Project {
+                is_public: false,
+                user_id: Uuid::new_v4(),
+                ..Project::default()
+            }),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let result = handlers::set_project_subscription(
+        student_user(),
+        State(state),
+        Path(TEST_ID),
+        Json(SubscriptionState::Subscribed),
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), StatusCode::NOT_FOUND);
+}
+
+// --- Comment Id Validation ---
+
+#[test]
+async fn test_comment_id_path_rejects_out_of_range_values() {
+    use tower::util::ServiceExt;
+
+    let app = axum::Router::new().route(
+        "/projects/{id}/comments/{comment_id}",
+        axum::routing::get(|Path((_, id)): Path<(Uuid, CommentId)>| async move {
+            id.get().to_string()
+        }),
+    );
+    let status = |comment: String| {
+        let app = app.clone();
+        async move {
+            let uri = format!("/projects/{}/comments/{}", Uuid::nil(), comment);
+            let request = axum::http::Request::get(uri)
+                .body(axum::body::Body::empty())
+                .unwrap();
+            app.oneshot(request).await.unwrap().status()
+        }
+    };
+
+    assert_eq!(status("42".into()).await, StatusCode::OK);
+    assert_eq!(status(MAX_COMMENT_ID.to_string()).await, StatusCode::OK);
+    for bad in [
+        "0".to_string(),
+        "-1".to_string(),
+        (MAX_COMMENT_ID + 1).to_string(),
+        i64::MAX.to_string(),
+        "99999999999999999999".to_string(),
+        "abc".to_string(),
+    ] {
+        assert_eq!(status(bad.clone()).await, StatusCode::BAD_REQUEST, "{bad}");
+    }
+}
+
+#[test]
+async fn test_delete_project_comment_scopes_to_project() {
+    let project_id = Uuid::new_v4();
+    let control = || MockRepoControl {
+        get_project_result: Some(Project {
+            id: project_id,
+            ..Project::default()
+        }),
+        delete_project_called: true,
+        ..MockRepoControl::default()
+    };
+
+    // Normal operation: the owner deletes a comment on the right project.
+    let state = create_test_state(control(), MockStorageService::new());
+    let status = handlers::delete_project_comment(
+        student_user(),
+        State(state),
+        Path((project_id, comment_id(7))),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Mismatch: the same comment addressed through another project is not found.
+    let state = create_test_state(control(), MockStorageService::new());
+    let status = handlers::delete_project_comment(
+        student_user(),
+        State(state),
+        Path((Uuid::new_v4(), comment_id(7))),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// --- Public Stats ---
+
+#[test]
+async fn test_public_stats_exposes_only_public_counters() {
+    let stats = PublicStats {
+        public_projects: 142,
+        distinct_years: 5,
+        distinct_supervisors: 38,
+        total_likes: 900,
+    };
+    let state = create_test_state(
+        MockRepoControl {
+            public_stats: stats.clone(),
+            stats_to_return: AdminDashboardStats {
+                total_users: 10,
+                pending_reviews: 7,
+                ..AdminDashboardStats::default()
+            },
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let response = handlers::get_public_stats(State(state)).await.into_response();
+    assert_eq!(
+        response.headers()[axum::http::header::CACHE_CONTROL],
+        "public, max-age=300"
+    );
+    let body = response_json(response).await;
+    let mut keys: Vec<_> = body.as_object().unwrap().keys().cloned().collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        vec!["distinct_supervisors", "distinct_years", "public_projects", "total_likes"]
+    );
+    assert_eq!(serde_json::from_value::<PublicStats>(body).unwrap(), stats);
+}
+
+// --- Comment Ordering ---
+
+async fn requested_comment_order(thread_len: usize, order: Option<CommentOrder>) -> CommentOrder {
+    let control = Arc::new(MockRepoControl {
+        projects_to_return: vec![Project::default(); thread_len],
+        comment_count: Some(thread_len as i64),
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .configure(|config| config.comments_newest_first_after = 3)
+        .build();
+    let _ = handlers::get_comments(
+        None,
+        State(state),
+        Path(TEST_ID),
+        Query(handlers::CommentFilter { order, limit: None, before: None, after: None }),
+    )
+    .await;
+    let orders = control.comment_orders.lock().unwrap();
+    orders[0]
+}
+
+#[test]
+async fn test_comment_order_defaults_by_thread_length() {
+    // Short threads read oldest-first; past the threshold the default flips.
+    assert_eq!(requested_comment_order(3, None).await, CommentOrder::Oldest);
+    assert_eq!(requested_comment_order(4, None).await, CommentOrder::Newest);
+}
+
+#[test]
+async fn test_explicit_comment_order_wins() {
+    assert_eq!(
+        requested_comment_order(10, Some(CommentOrder::Oldest)).await,
+        CommentOrder::Oldest
+    );
+    assert_eq!(
+        requested_comment_order(0, Some(CommentOrder::Newest)).await,
+        CommentOrder::Newest
+    );
+}
+
+#[test]
+async fn test_comment_pages_follow_the_requested_order() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    repo.create_user(User { id: TEST_ID, email: "reader@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let project = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    let mut posted = Vec::new();
+    for n in 0..5 {
+        posted.push(repo.add_comment(project.id, TEST_ID, format!("#{n}"), 500).await.unwrap().unwrap().comment.id);
+    }
+    let page = |order: Option<CommentOrder>, limit: &str, cursor: Option<i64>| {
+        let cursor = cursor.map(|id| id.to_string());
+        let forwards = order == Some(CommentOrder::Oldest);
+        let filter = handlers::CommentFilter {
+            order,
+            limit: Some(limit.to_string()),
+            before: if forwards { None } else { cursor.clone() },
+            after: if forwards { cursor } else { None },
+        };
+        let state = state.clone();
+        async move {
+            let Json(page) = handlers::get_comments(None, State(state), Path(project.id), Query(filter))
+                .await
+                .unwrap();
+            (page.comments.into_iter().map(|c| c.id).collect::<Vec<_>>(), page.has_more)
+        }
+    };
+    let reversed = |ids: &[i64]| ids.iter().rev().copied().collect::<Vec<_>>();
+
+    // Without `order`: the newest two, oldest first; then older pages until the start.
+    assert_eq!(page(None, "2", None).await, (posted[3..].to_vec(), true));
+    assert_eq!(page(None, "2", Some(posted[3])).await, (posted[1..3].to_vec(), true));
+    assert_eq!(page(None, "2", Some(posted[1])).await, (posted[..1].to_vec(), false));
+    assert_eq!(page(None, "5", None).await, (posted.clone(), false));
+
+    // Newest first: the same pages, each read newest first.
+    let newest = Some(CommentOrder::Newest);
+    assert_eq!(page(newest, "2", None).await, (reversed(&posted[3..]), true));
+    assert_eq!(page(newest, "2", Some(posted[3])).await, (reversed(&posted[1..3]), true));
+    assert_eq!(page(newest, "2", Some(posted[1])).await, (posted[..1].to_vec(), false));
+
+    // Oldest first: pages forward from the first comment.
+    let oldest = Some(CommentOrder::Oldest);
+    assert_eq!(page(oldest, "2", None).await, (posted[..2].to_vec(), true));
+    assert_eq!(page(oldest, "2", Some(posted[1])).await, (posted[2..4].to_vec(), true));
+    assert_eq!(page(oldest, "2", Some(posted[3])).await, (posted[4..].to_vec(), false));
+}
+
+#[test]
+async fn test_comment_page_limit_is_capped_and_cursor_validated() {
+    let control = Arc::new(MockRepoControl::default());
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let comments = |limit: Option<&str>, before: Option<&str>| {
+        let filter = handlers::CommentFilter {
+            order: None,
+            limit: limit.map(str::to_string),
+            before: before.map(str::to_string),
+            after: None,
+        };
+        handlers::get_comments(None, State(state.clone()), Path(TEST_ID), Query(filter))
+    };
+
+    // One row past the page is asked for, to tell whether older comments remain.
+    for (limit, before) in [(Some("500"), None), (None, Some("42")), (None, Some("2024-05-01T12:00:00+01:00"))] {
+        assert!(comments(limit, before).await.is_ok());
+    }
+    let at = "2024-05-01T11:00:00Z".parse().unwrap();
+    assert_eq!(
+        *control.comment_pages.lock().unwrap(),
+        vec![
+            (None, handlers::MAX_COMMENT_PAGE_LIMIT + 1),
+            (Some(CommentCursor::Id(42)), handlers::DEFAULT_COMMENT_PAGE_LIMIT + 1),
+            (Some(CommentCursor::Time(at)), handlers::DEFAULT_COMMENT_PAGE_LIMIT + 1),
+        ]
+    );
+
+    let err = comments(Some("0"), None).await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::UNPROCESSABLE_ENTITY, "invalid_limit"));
+    let err = comments(None, Some("yesterday")).await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::BAD_REQUEST, "invalid_cursor"));
+
+    // A cursor only makes sense in the direction the order pages in.
+    for (order, before, after, field) in [
+        (Some(CommentOrder::Oldest), Some("42"), None, "before"),
+        (Some(CommentOrder::Newest), None, Some("42"), "after"),
+        (None, None, Some("42"), "after"),
+    ] {
+        let filter = handlers::CommentFilter {
+            order,
+            limit: None,
+            before: before.map(str::to_string),
+            after: after.map(str::to_string),
+        };
+        let err = handlers::get_comments(None, State(state.clone()), Path(TEST_ID), Query(filter)).await.unwrap_err();
+        assert_eq!((err.status, err.body.code.as_str()), (StatusCode::UNPROCESSABLE_ENTITY, "invalid_pagination"));
+        assert_eq!(err.body.details.unwrap()["field"], field);
+    }
+    assert_eq!(control.comment_pages.lock().unwrap().len(), 3);
+}
+
+#[test]
+async fn test_comment_order_query_is_validated() {
+    use axum::extract::FromRequestParts;
+
+    let parse = |uri: &str| {
+        let (mut parts, _) = axum::http::Request::get(uri).body(()).unwrap().into_parts();
+        async move { Query::<handlers::CommentFilter>::from_request_parts(&mut parts, &()).await }
+    };
+    assert_eq!(
+        parse("/c?order=newest").await.unwrap().0.order,
+        Some(CommentOrder::Newest)
+    );
+    assert!(parse("/c").await.unwrap().0.order.is_none());
+    let Err(err) = parse("/c?order=most_reactions").await else {
+        panic!("unknown order accepted");
+    };
+    assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+}
+
+// --- Presign Abuse Guard ---
+
+fn presign_request(file_type: &str) -> PresignedUrlRequest {
+    PresignedUrlRequest {
+        filename: "upload.bin".to_string(),
+        file_type: file_type.to_string(),
+        purpose: None,
+    }
+}
+
+#[test]
+async fn test_presign_pending_uploads_limit_is_per_purpose() {
+    let storage = MockStorageService::new();
+    let state = create_test_state(MockRepoControl::default(), storage.clone());
+
+    for _ in 0..5 {
+        let Json(_) = handlers::get_presigned_url(
+            student_user(),
+            State(state.clone()),
+            Json(presign_request("video/mp4")),
+        )
+        .await
+        .unwrap();
+    }
+
+    // A sixth pending video is refused with the counts in the body...
+    let err = handlers::get_presigned_url(
+        student_user(),
+        State(state.clone()),
+        Json(presign_request("video/mp4")),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.status, StatusCode::CONFLICT);
+    assert_eq!(err.body.code, "too_many_pending_uploads");
+    assert_eq!(
+        err.body.details.unwrap(),
+        serde_json::json!({ "purpose": "video", "outstanding": 5, "limit": 5 })
+    );
+    assert_eq!(storage.issued_count(), 5);
+
+    // ...but another purpose is unaffected.
+    let Json(_) = handlers::get_presigned_url(
+        student_user(),
+        State(state),
+        Json(PresignedUrlRequest {
+            purpose: Some(UploadPurpose::Report),
+            ..presign_request("video/mp4")
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(storage.issued_count(), 6);
+}
+
+#[test]
+async fn test_presign_hourly_limit_returns_429() {
+    let storage = MockStorageService::new();
+    let state = AppStateBuilder::test_default()
+        .repo(Arc::new(MockRepoControl::default()))
+        .storage(Arc::new(storage.clone()))
+        .configure(|config| config.max_presigns_per_hour = 7)
+        .build();
+
+    for file_type in ["image/png", "image/png", "image/png", "video/mp4", "video/mp4"]
+        .into_iter()
+        .chain(["application/pdf"; 2])
+    {
+        let Json(_) = handlers::get_presigned_url(
+            student_user(),
+            State(state.clone()),
+            Json(presign_request(file_type)),
+        )
+        .await
+        .unwrap();
+    }
+
+    let err = handlers::get_presigned_url(
+        student_user(),
+        State(state),
+        Json(presign_request("application/pdf")),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(err.body.code, "presign_rate_limited");
+    assert_eq!(
+        err.body.details.unwrap(),
+        serde_json::json!({ "count": 7, "limit": 7 })
+    );
+    assert_eq!(storage.issued_count(), 7);
+}
+
+#[test]
+async fn test_presign_limits_do_not_apply_to_admins() {
+    let storage = MockStorageService::new();
+    let state = AppStateBuilder::test_default()
+        .repo(Arc::new(MockRepoControl::default()))
+        .storage(Arc::new(storage.clone()))
+        .configure(|config| {
+            config.max_presigns_per_hour = 2;
+            config.max_pending_uploads = 1;
+        })
+        .build();
+
+    for _ in 0..4 {
+        let Json(_) = handlers::get_presigned_url(
+            admin_user(),
+            State(state.clone()),
+            Json(presign_request("video/mp4")),
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(storage.issued_count(), 4);
+}
+
+// --- Changed Since ---
+
+async fn changed_since(
+    req: ChangedSinceRequest,
+) -> (
+    Result<ChangedSinceResponse, fyp_portal::error::ApiError>,
+    Vec<ChangedSinceQuery>,
+) {
+    let control = Arc::new(MockRepoControl::default());
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let result = handlers::get_changed_projects(State(state), Json(req))
+        .await
+        .map(|Json(r)| r);
+    let queries = control.changed_since_queries.lock().unwrap().clone();
+    (result, queries)
+}
+
+#[test]
+async fn test_changed_since_clamps_old_cutoff() {
+    let before = Utc::now();
+    let (result, queries) = changed_since(ChangedSinceRequest {
+        since: before - Duration::days(400),
+        ids: None,
+    })
+    .await;
+
+    // The repository sees the clamped cutoff, and the response reports it.
+    let (since, ids) = &queries[0];
+    assert!(*since >= before - handlers::CHANGED_SINCE_MAX_AGE);
+    assert!(*since <= Utc::now() - handlers::CHANGED_SINCE_MAX_AGE);
+    assert_eq!(ids, &None);
+    assert_eq!(result.unwrap().since, *since);
+}
+
+#[test]
+async fn test_changed_since_keeps_recent_cutoff_and_ids() {
+    let since = Utc::now() - Duration::days(3);
+    let ids = vec![TEST_ID, Uuid::new_v4()];
+    let (result, queries) = changed_since(ChangedSinceRequest {
+        since,
+        ids: Some(ids.clone()),
+    })
+    .await;
+
+    assert_eq!(queries, vec![(since, Some(ids))]);
+    assert_eq!(result.unwrap().since, since);
+}
+
+#[test]
+async fn test_changed_since_rejects_too_many_ids() {
+    let ids: Vec<Uuid> = (0..=handlers::CHANGED_SINCE_MAX_IDS)
+        .map(|_| Uuid::new_v4())
+        .collect();
+    let (result, queries) = changed_since(ChangedSinceRequest {
+        since: Utc::now(),
+        ids: Some(ids),
+    })
+    .await;
+
+    let Err(err) = result else {
+        panic!("expected 201 ids to be rejected")
+    };
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "too_many_ids");
+    assert!(queries.is_empty());
+}
+
+// --- Public Events ---
+
+async fn public_events_page(state: &AppState, since: Option<DateTime<Utc>>) -> fyp_portal::models::PublicEventsResponse {
+    let response = handlers::get_public_events(State(state.clone()), Query(handlers::PublicEventsQuery { since }))
+        .await
+        .into_response();
+    assert_eq!(response.headers()[axum::http::header::CACHE_CONTROL], "public, max-age=60");
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[test]
+async fn test_public_events_page_through_publish_and_unpublish() {
+    use fyp_portal::models::PublicEventKind;
+
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let owner = Uuid::new_v4();
+    let mut ids = Vec::new();
+    for _ in 0..handlers::PUBLIC_EVENTS_PAGE_SIZE + 2 {
+        ids.push(repo.create_project(CreateProjectRequest::default(), owner).await.id);
+    }
+    // Publishing every project, then hiding the first, is one more event than two pages hold.
+    assert_eq!(repo.set_projects_status_bulk(&ids, true).await.changed.len(), ids.len());
+    repo.set_project_status(ids[0], ProjectStatus::Rejected).await.unwrap();
+
+    let first = public_events_page(&state, None).await;
+    assert!(first.has_more);
+    assert!(first.events.len() <= handlers::PUBLIC_EVENTS_PAGE_SIZE);
+    assert_eq!(first.next_since, first.events.last().unwrap().at);
+    let second = public_events_page(&state, Some(first.next_since)).await;
+    assert!(!second.has_more);
+
+    // Nothing skipped or repeated across the watermark, oldest first.
+    let events: Vec<_> = first.events.iter().chain(&second.events).collect();
+    assert_eq!(events.len(), ids.len() + 1);
+    assert!(events.windows(2).all(|pair| (pair[0].at, pair[0].id) < (pair[1].at, pair[1].id)));
+    let published: Vec<Uuid> = events[..ids.len()].iter().map(|event| event.project_id).collect();
+    assert_eq!(published, ids);
+    assert!(events[..ids.len()].iter().all(|event| event.kind == PublicEventKind::Published));
+
+    // The hidden project's publish event no longer carries it; the others do.
+    assert!(events[0].project.is_none());
+    assert_eq!(events[1].project.as_ref().map(|p| p.id), Some(ids[1]));
+    let hidden = events.last().unwrap();
+    assert_eq!((hidden.kind, hidden.project_id), (PublicEventKind::Unpublished, ids[0]));
+    assert!(hidden.project.is_none());
+
+    // Caught up: the watermark stays put until something happens.
+    let idle = public_events_page(&state, Some(second.next_since)).await;
+    assert!(idle.events.is_empty() && !idle.has_more);
+    assert_eq!(idle.next_since, second.next_since);
+    repo.set_project_status(ids[0], ProjectStatus::Approved).await.unwrap();
+    let next = public_events_page(&state, Some(idle.next_since)).await;
+    let kinds: Vec<_> = next.events.iter().map(|event| (event.kind, event.project_id)).collect();
+    assert_eq!(kinds, vec![(PublicEventKind::Published, ids[0])]);
+    assert_eq!(next.events[0].project.as_ref().map(|p| p.id), Some(ids[0]));
+}
+
+// --- Admin Project Stream ---
+
+fn titled_projects(titles: &[&str]) -> Vec<Project> {
+    titles
+        .iter()
+        .map(|title| Project {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            ..Project::default()
+        })
+        .collect()
+}
+
+#[test]
+async fn test_admin_project_stream_yields_one_line_per_project() {
+    let projects = titled_projects(&["Alpha", "Beta", "Gamma"]);
+    let state = create_test_state(
+        MockRepoControl {
+            projects_to_return: projects.clone(),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let response = handlers::stream_admin_projects(admin_user(), State(state))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()[axum::http::header::CONTENT_TYPE],
+        "application/x-ndjson"
+    );
+
+    // Consume chunk by chunk: every project arrives as its own complete line.
+    let mut chunks = response.into_body().into_data_stream();
+    for expected in &projects {
+        let chunk = chunks.next().await.unwrap().unwrap();
+        assert_eq!(chunk.last(), Some(&b'\n'));
+        let project: Project = serde_json::from_slice(&chunk).unwrap();
+        assert_eq!((project.id, &project.title), (expected.id, &expected.title));
+    }
+    assert!(chunks.next().await.is_none());
+}
+
+#[test]
+#[tracing_test::traced_test]
+async fn test_admin_project_stream_truncates_on_repository_error() {
+    let projects = titled_projects(&["Alpha", "Beta", "Gamma"]);
+    let state = create_test_state(
+        MockRepoControl {
+            projects_to_return: projects.clone(),
+            stream_error_after: Some(1),
+            ..MockRepoControl::default()
+        },
+        MockStorageService::new(),
+    );
+
+    let response = handlers::stream_admin_projects(admin_user(), State(state))
+        .await
+        .unwrap();
+    // The body ends cleanly after the last good row; nothing after the error is sent.
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let lines: Vec<Project> = bytes
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].id, projects[0].id);
+    assert!(logs_contain("aborting after database error"));
+    assert!(logs_contain("connection reset"));
+}
+
+#[test]
+async fn test_admin_project_stream_requires_admin() {
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+    let Err(err) = handlers::stream_admin_projects(student_user(), State(state)).await else {
+        panic!("students must not stream the admin listing")
+    };
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+}
+
+#[test]
+async fn test_admin_csv_export_escapes_titles_and_joins_owner_email() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let owner = User { id: Uuid::new_v4(), email: "owner@ul.ie".to_string(), role: "student".to_string() };
+    repo.create_user(owner.clone()).await;
+    let payload = CreateProjectRequest {
+        title: "Robots, \"Rovers\" and Drones".to_string(),
+        author_name: "Ada".to_string(),
+        year: 2024,
+        ..CreateProjectRequest::default()
+    };
+    let project = repo.create_project(payload, owner.id).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    repo.like_project(Like { user_id: TEST_ID, project_id: project.id }).await;
+
+    let Err(err) = handlers::export_admin_projects_csv(student_user(), State(state.clone())).await else {
+        panic!("students must not export the project spreadsheet")
+    };
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+    let response = handlers::export_admin_projects_csv(admin_user(), State(state.clone())).await.unwrap();
+    assert_eq!(response.headers()[axum::http::header::CONTENT_TYPE], "text/csv; charset=utf-8");
+    let date = state.clock().now().format("%Y-%m-%d");
+    assert_eq!(
+        response.headers()[axum::http::header::CONTENT_DISPOSITION],
+        format!("attachment; filename=\"projects-{date}.csv\"").as_str()
+    );
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8(bytes.to_vec()).unwrap();
+    let lines: Vec<&str> = body.split_terminator("\r\n").collect();
+    assert_eq!(lines[0], "id,title,author,year,status,like_count,created_at,owner_email");
+    assert_eq!(
+        lines[1],
+        format!(
+            "{},\"Robots, \"\"Rovers\"\" and Drones\",Ada,2024,approved,1,{},owner@ul.ie",
+            project.id,
+            project.created_at.to_rfc3339()
+        )
+    );
+    assert_eq!(lines.len(), 2);
+}
+
+// --- Programmes ---
+
+fn programme(code: &str, name: &str) -> Programme {
+    Programme {
+        code: code.to_string(),
+        name: name.to_string(),
+    }
+}
+
+fn programme_repo() -> MockRepoControl {
+    MockRepoControl {
+        programmes: vec![
+            programme("CS", "Computer Science"),
+            programme("MS", "Management Science"),
+        ],
+        ..MockRepoControl::default()
+    }
+}
+
+/// A create payload that passes `project_field_errors`, for tests about the other checks.
+fn valid_project_request() -> CreateProjectRequest {
+    CreateProjectRequest {
+        title: "Smart Greenhouse".to_string(),
+        author_name: "Ada Lovelace".to_string(),
+        cover_image_key: "uploads/cover.png".to_string(),
+        year: 2024,
+        ..CreateProjectRequest::default()
+    }
+}
+
+#[test]
+async fn test_create_project_lists_every_invalid_field() {
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+    let payload = CreateProjectRequest {
+        title: "   ".to_string(),
+        author_name: String::new(),
+        cover_image_key: "img.jpg".to_string(),
+        ..valid_project_request()
+    };
+    let err = handlers::create_project(student_user(), State(state.clone()), Query(handlers::SaveOptions::default()), Json(payload))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "validation_failed");
+    let errors: Vec<FieldError> = serde_json::from_value(err.body.details.unwrap()["errors"].clone()).unwrap();
+    let failed: Vec<_> = errors.iter().map(|e| (e.field.as_str(), e.code.as_str())).collect();
+    assert_eq!(failed, [("title", "required"), ("author_name", "required"), ("cover_image_key", "invalid_key")]);
+
+    // Length is counted in characters once trimmed: 200 fit, 201 do not.
+    let titled = |title: String| CreateProjectRequest { title, ..valid_project_request() };
+    let err = handlers::create_project(student_user(), State(state.clone()), Query(handlers::SaveOptions::default()), Json(titled("é".repeat(201))))
+        .await
+        .unwrap_err();
+    assert_eq!(err.body.details.unwrap()["errors"][0]["code"], "too_long");
+    let ok = handlers::create_project(student_user(), State(state), Query(handlers::SaveOptions::default()), Json(titled(format!(" {} ", "é".repeat(200)))));
+    assert!(ok.await.is_ok());
+}
+
+#[test]
+async fn test_update_project_checks_only_the_fields_it_sets() {
+    let update = |payload: UpdateProjectRequest| {
+        let state = create_test_state(MockRepoControl { get_project_result: Some(Project::default()), ..MockRepoControl::default() }, MockStorageService::new());
+        handlers::update_project(State(state), owner_or_admin(student_user()), Query(handlers::SaveOptions::default()), ProjectPatch(payload))
+    };
+    let err = update(UpdateProjectRequest { title: Some(String::new()), cover_image_key: Some("uploads/".to_string()), ..UpdateProjectRequest::default() })
+        .await
+        .unwrap_err();
+    assert_eq!(err.body.code, "validation_failed");
+    let errors = &err.body.details.unwrap()["errors"];
+    assert_eq!((errors[0]["field"].as_str(), errors[1]["field"].as_str()), (Some("title"), Some("cover_image_key")));
+    assert!(update(UpdateProjectRequest { video_waived: Some(true), ..UpdateProjectRequest::default() }).await.is_ok());
+}
+
+#[test]
+async fn test_create_project_caps_projects_per_year() {
+    let create = |user, existing| {
+        let state = AppStateBuilder::test_default()
+            .repo(Arc::new(MockRepoControl { user_year_project_count: existing, ..MockRepoControl::default() }))
+            .configure(|config| config.max_projects_per_user_per_year = 3)
+            .build();
+        handlers::create_project(user, State(state), Query(handlers::SaveOptions::default()), Json(valid_project_request()))
+    };
+
+    // Two existing projects leave room for the third.
+    assert!(create(student_user(), 2).await.is_ok());
+    let err = create(student_user(), 3).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::CONFLICT);
+    assert_eq!(err.body.code, "project_limit");
+    assert_eq!(err.body.details.unwrap(), serde_json::json!({ "year": 2024, "limit": 3, "count": 3 }));
+    // Admins are exempt.
+    assert!(create(admin_user(), 3).await.is_ok());
+}
+
+#[test]
+async fn test_create_project_rejects_unknown_programme_codes() {
+    let state = create_test_state(programme_repo(), MockStorageService::new());
+    let payload = CreateProjectRequest {
+        programme_codes: Some(vec!["cs".to_string(), "PHYS".to_string(), "XX".to_string()]),
+        year: 2024,
+        ..valid_project_request()
+    };
+
+    let Err(err) = handlers::create_project(student_user(), State(state), Query(handlers::SaveOptions::default()), Json(payload)).await else {
+        panic!("unknown programme codes must be rejected")
+    };
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "unknown_programme");
+    assert_eq!(err.body.details.unwrap()["unknown"], serde_json::json!(["PHYS", "XX"]));
+}
+
+#[test]
+async fn test_create_project_refuses_duplicate_titles_unless_forced() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let create = |user, title: &str, year, force| {
+        let payload = CreateProjectRequest { title: title.to_string(), year, force, ..valid_project_request() };
+        handlers::create_project(user, State(state.clone()), Query(handlers::SaveOptions::default()), Json(payload))
+    };
+    let Json(CreatedProject { project: original, .. }) = create(student_user(), "Smart  Campus Map", 2024, false).await.unwrap();
+
+    // A group partner submitting the same project, however it is capitalised or spaced.
+    let err = create(admin_user(), "  smart campus   MAP ", 2024, false).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::CONFLICT);
+    assert_eq!(err.body.code, "duplicate_project");
+    assert_eq!(err.body.details.unwrap()["project_id"], original.id.to_string());
+
+    assert!(create(student_user(), "Smart Campus Map", 2023, false).await.is_ok());
+    let Json(CreatedProject { project: forced, .. }) = create(student_user(), "Smart Campus Map", 2024, true).await.unwrap();
+    assert_ne!(forced.id, original.id);
+}
+
+#[test]
+async fn test_project_detail_embeds_owner_profile() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let owner = repo.create_user(User { id: Uuid::new_v4(), email: "ada@ul.ie".to_string(), role: "student".to_string() }).await;
+    let project = repo.create_project(CreateProjectRequest { title: "Owned".to_string(), ..CreateProjectRequest::default() }, owner.id).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    let orphaned = repo.create_project(CreateProjectRequest { title: "Orphaned".to_string(), ..CreateProjectRequest::default() }, Uuid::new_v4()).await;
+    repo.set_project_status(orphaned.id, ProjectStatus::Approved).await.unwrap();
+    let state = AppStateBuilder::test_default().repo(repo).build();
+
+    let Json(detail) = handlers::get_project_details(None, State(state.clone()), Path(project.id)).await.unwrap();
+    let json = serde_json::to_value(&detail).unwrap();
+    assert_eq!(json["owner"]["id"], owner.id.to_string());
+    assert_eq!(json["owner"]["email"], "ada@ul.ie");
+    assert_eq!(json["owner"]["avatar_url"], fyp_portal::models::avatar_url(owner.id));
+
+    // A missing profile row leaves the project readable, with a null owner.
+    let Json(detail) = handlers::get_project_details(None, State(state), Path(orphaned.id)).await.unwrap();
+    assert_eq!(serde_json::to_value(&detail).unwrap()["owner"], serde_json::Value::Null);
+}
+
+#[test]
+async fn test_create_project_checks_department_allowlist() {
+    let state = AppStateBuilder::test_default()
+        .repo(Arc::new(InMemoryRepository::new()))
+        .configure(|config| config.departments = vec!["CS".to_string(), "Biomed".to_string()])
+        .build();
+    let create = |title: &str, department: Option<&str>| {
+        let payload = CreateProjectRequest {
+            title: title.to_string(),
+            year: 2024,
+            department: department.map(str::to_string),
+            ..valid_project_request()
+        };
+        handlers::create_project(student_user(), State(state.clone()), Query(handlers::SaveOptions::default()), Json(payload))
+    };
+
+    // Stored in the configured spelling, so `biomed` and `Biomed` are one department.
+    let Json(created) = create("Spelled", Some(" BIOMED ")).await.unwrap();
+    assert_eq!(created.project.department.as_deref(), Some("Biomed"));
+    let Json(created) = create("Blank", Some("  ")).await.unwrap();
+    assert_eq!(created.project.department, None);
+
+    let err = create("Invented", Some("Underwater Basket Weaving")).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "unknown_department");
+    assert_eq!(err.body.details.unwrap()["allowed"], serde_json::json!(["CS", "Biomed"]));
+}
+
+#[test]
+async fn test_update_project_rejects_unknown_department() {
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+    let payload = UpdateProjectRequest { department: Some("Astrology".to_string()), ..UpdateProjectRequest::default() };
+    let err = handlers::update_project(State(state), owner_or_admin(student_user()), Query(handlers::SaveOptions::default()), ProjectPatch(payload))
+        .await
+        .unwrap_err();
+    assert_eq!(err.body.code, "unknown_department");
+    assert_eq!(err.body.details.unwrap()["department"], "Astrology");
+}
+
+#[test]
+async fn test_update_project_validates_year_and_author() {
+    let update = |payload: UpdateProjectRequest| {
+        let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+        handlers::update_project(State(state), owner_or_admin(student_user()), Query(handlers::SaveOptions::default()), ProjectPatch(payload))
+    };
+    let err = update(UpdateProjectRequest { year: Some(1999), ..UpdateProjectRequest::default() }).await.unwrap_err();
+    assert_eq!(err.body.code, "invalid_year");
+    let err = update(UpdateProjectRequest { author_name: Some("  ".to_string()), ..UpdateProjectRequest::default() }).await.unwrap_err();
+    assert_eq!(err.body.code, "validation_failed");
+    assert_eq!(err.body.details.unwrap()["errors"][0]["field"], "author_name");
+}
+
+#[test]
+async fn test_programme_codes_are_normalized_before_validation() {
+    let state = create_test_state(programme_repo(), MockStorageService::new());
+    // Case, whitespace and duplicates do not make a known code unknown.
+    let payload = UpdateProjectRequest {
+        programme_codes: Some(vec![" cs".to_string(), "CS".to_string(), "ms ".to_string()]),
+        ..UpdateProjectRequest::default()
+    };
+    let result = handlers::update_project(State(state), owner_or_admin(student_user()), Query(handlers::SaveOptions::default()), ProjectPatch(payload)).await;
+    assert!(result.is_ok());
+}
+
+#[test]
+async fn test_project_listing_passes_programme_filter() {
+    let control = Arc::new(MockRepoControl::default());
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let filter = handlers::ProjectFilter {
+        programme: Some(" ms".to_string()),
+        ..project_filter(None)
+    };
+    let _ = handlers::get_projects(None, State(state), projects_uri(), Query(filter)).await;
+    let queries = control.project_queries.lock().unwrap();
+    let expected = ProjectListFilter {
+        programme: Some("MS".to_string()),
+        ..ProjectListFilter::default()
+    };
+    assert_eq!(queries[0], (expected, PageRequest::default()));
+}
+
+#[test]
+async fn test_project_listing_passes_department_filter() {
+    let control = Arc::new(MockRepoControl::default());
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let filter = handlers::ProjectFilter {
+        department: Some(" ee ".to_string()),
+        ..project_filter(None)
+    };
+    let _ = handlers::get_projects(None, State(state), projects_uri(), Query(filter)).await;
+    let queries = control.project_queries.lock().unwrap();
+    let expected = ProjectListFilter {
+        department: Some("ee".to_string()),
+        ..ProjectListFilter::default()
+    };
+    assert_eq!(queries[0], (expected, PageRequest::default()));
+}
+
+#[test]
+async fn test_project_listing_passes_trimmed_supervisor_filter() {
+    let control = Arc::new(MockRepoControl::default());
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    for raw in ["  Dr Jane Smith ", "   "] {
+        let filter = handlers::ProjectFilter {
+            supervisor: Some(raw.to_string()),
+            ..project_filter(None)
+        };
+        let _ = handlers::get_projects(None, State(state.clone()), projects_uri(), Query(filter)).await;
+    }
+    let queries = control.project_queries.lock().unwrap();
+    assert_eq!(queries[0].0.supervisor.as_deref(), Some("Dr Jane Smith"));
+    // A blank value is no filter at all.
+    assert_eq!(queries[1].0, ProjectListFilter::default());
+}
+
+#[test]
+async fn test_project_tags_are_normalized_and_filterable() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let payload = CreateProjectRequest {
+        tags: vec![" Machine-Learning".to_string(), "embedded".to_string(), "machine-learning ".to_string()],
+        year: 2024,
+        ..valid_project_request()
+    };
+    let Json(CreatedProject { project, .. }) = handlers::create_project(student_user(), State(state.clone()), Query(handlers::SaveOptions::default()), Json(payload))
+        .await
+        .unwrap();
+    assert_eq!(project.tags, vec!["embedded", "machine-learning"]);
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+
+    let filter = handlers::ProjectFilter {
+        tag: Some("EMBEDDED ".to_string()),
+        ..project_filter(None)
+    };
+    let response = handlers::get_projects(None, State(state.clone()), projects_uri(), Query(filter))
+        .await
+        .unwrap()
+        .into_response();
+    let body = response_json(response).await;
+    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["items"][0]["tags"], serde_json::json!(["embedded", "machine-learning"]));
+
+    let Json(tags) = handlers::get_tags(State(state)).await;
+    assert_eq!(
+        tags,
+        vec![
+            TagCount { tag: "embedded".to_string(), count: 1 },
+            TagCount { tag: "machine-learning".to_string(), count: 1 },
+        ]
+    );
+}
+
+#[test]
+async fn test_project_tags_enforce_limits() {
+    let long = "x".repeat(31);
+    let eleven: Vec<String> = (0..11).map(|i| format!("tag-{i}")).collect();
+    for (tags, detail) in [(vec![long.clone()], "max_chars"), (vec!["  ".to_string()], "max_chars"), (eleven, "limit")] {
+        let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+        let payload = CreateProjectRequest {
+            tags: tags.clone(),
+            year: 2024,
+            ..valid_project_request()
+        };
+        let Err(err) = handlers::create_project(student_user(), State(state), Query(handlers::SaveOptions::default()), Json(payload)).await else {
+            panic!("{tags:?} must be rejected")
+        };
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.body.code, "invalid_tags");
+        assert!(err.body.details.unwrap().get(detail).is_some(), "{tags:?}");
+    }
+
+    // Ten distinct tags (a case-only duplicate does not count) and thirty characters are allowed.
+    let mut ten: Vec<String> = (0..10).map(|i| format!("tag-{i}")).collect();
+    ten.push("TAG-0".to_string());
+    for tags in [ten, vec!["y".repeat(30)]] {
+        let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+        let payload = UpdateProjectRequest {
+            tags: Some(tags),
+            ..UpdateProjectRequest::default()
+        };
+        let result = handlers::update_project(State(state), owner_or_admin(student_user()), Query(handlers::SaveOptions::default()), ProjectPatch(payload)).await;
+        assert!(result.is_ok());
+    }
+}
+
+#[test]
+async fn test_project_technologies_filter_requires_every_value() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .configure(|config| config.max_projects_per_user_per_year = 10)
+        .build();
+    let mut ids = Vec::new();
+    for (title, technologies) in [("Both", vec![" Rust", "postgres", "RUST "]), ("Only Rust", vec!["rust"])] {
+        let payload = CreateProjectRequest {
+            title: title.to_string(),
+            technologies: technologies.into_iter().map(String::from).collect(),
+            year: 2024,
+            force: true,
+            ..valid_project_request()
+        };
+        let Json(CreatedProject { project, .. }) = handlers::create_project(student_user(), State(state.clone()), Query(handlers::SaveOptions::default()), Json(payload))
+            .await
+            .unwrap();
+        repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+        ids.push(project.id);
+    }
+
+    // Repeated `tech` values are ANDed: only the project using both matches.
+    let list = |query: &'static str| {
+        let uri = OriginalUri(Uri::from_static(query));
+        handlers::get_projects(None, State(state.clone()), uri, Query(project_filter(None)))
+    };
+    let body = response_json(list("/projects?tech=RUST&tech=postgres").await.unwrap().into_response()).await;
+    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["items"][0]["id"], serde_json::json!(ids[0]));
+    assert_eq!(body["items"][0]["technologies"], serde_json::json!(["postgres", "rust"]));
+    let body = response_json(list("/projects?tech=rust").await.unwrap().into_response()).await;
+    assert_eq!(body["total_count"], 2);
+    let body = response_json(list("/projects?tech=rust&tech=go").await.unwrap().into_response()).await;
+    assert_eq!(body["total_count"], 0);
+
+    let Json(technologies) = handlers::get_technologies(State(state)).await;
+    assert_eq!(
+        technologies,
+        vec![
+            TechnologyCount { technology: "rust".to_string(), count: 2 },
+            TechnologyCount { technology: "postgres".to_string(), count: 1 },
+        ]
+    );
+}
+
+#[test]
+async fn test_liked_by_me_is_set_for_signed_in_callers_only() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let project = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    repo.like_project(fyp_portal::models::Like { user_id: TEST_ID, project_id: project.id }).await;
+
+    let list = |user: Option<AuthUser>| handlers::get_projects(user, State(state.clone()), projects_uri(), Query(project_filter(None)));
+    let anonymous = response_json(list(None).await.unwrap().into_response()).await;
+    assert!(anonymous["items"][0].get("liked_by_me").is_none());
+    let liker = response_json(list(Some(student_user())).await.unwrap().into_response()).await;
+    assert_eq!(liker["items"][0]["liked_by_me"], true);
+    let other = response_json(list(Some(admin_user())).await.unwrap().into_response()).await;
+    assert_eq!(other["items"][0]["liked_by_me"], false);
+
+    let detail = |user: Option<AuthUser>| handlers::get_project_details(user, State(state.clone()), Path(project.id));
+    assert_eq!(detail(None).await.unwrap().0.liked_by_me, None);
+    assert_eq!(detail(Some(student_user())).await.unwrap().0.liked_by_me, Some(true));
+    assert_eq!(detail(Some(admin_user())).await.unwrap().0.liked_by_me, Some(false));
+
+    // The flag can be selected like any other field.
+    let filter = handlers::ProjectFilter {
+        fields: Some("id,liked_by_me".to_string()),
+        ..project_filter(None)
+    };
+    let response = handlers::get_projects(Some(student_user()), State(state), projects_uri(), Query(filter)).await.unwrap();
+    let sparse = response_json(response.into_response()).await;
+    assert_eq!(sparse["items"][0], serde_json::json!({ "id": project.id, "liked_by_me": true }));
+}
+
+#[test]
+async fn test_project_listing_passes_tech_filter() {
+    let control = Arc::new(MockRepoControl::default());
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let uri = OriginalUri(Uri::from_static("/projects?tech=Rust&tech=%20&tech=postgres&tech=rust"));
+    let _ = handlers::get_projects(None, State(state), uri, Query(project_filter(None))).await;
+    let queries = control.project_queries.lock().unwrap();
+    let expected = ProjectListFilter {
+        technologies: vec!["postgres".to_string(), "rust".to_string()],
+        ..ProjectListFilter::default()
+    };
+    assert_eq!(queries[0], (expected, PageRequest::default()));
+}
+
+#[test]
+async fn test_project_technologies_enforce_limits() {
+    let long = "x".repeat(41);
+    let sixteen: Vec<String> = (0..16).map(|i| format!("tech-{i}")).collect();
+    for (technologies, detail) in [(vec![long], "max_chars"), (vec![" ".to_string()], "max_chars"), (sixteen, "limit")] {
+        let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+        let payload = UpdateProjectRequest {
+            technologies: Some(technologies.clone()),
+            ..UpdateProjectRequest::default()
+        };
+        let Err(err) = handlers::update_project(State(state), owner_or_admin(student_user()), Query(handlers::SaveOptions::default()), ProjectPatch(payload)).await else {
+            panic!("{technologies:?} must be rejected")
+        };
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.body.code, "invalid_technologies");
+        assert!(err.body.details.unwrap().get(detail).is_some(), "{technologies:?}");
+    }
+
+    // Fifteen distinct names (a case-only duplicate does not count) of up to forty characters.
+    let mut fifteen: Vec<String> = (0..15).map(|i| format!("tech-{i}")).collect();
+    fifteen.push("TECH-0".to_string());
+    fifteen[1] = "y".repeat(40);
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+    let payload = CreateProjectRequest {
+        technologies: fifteen,
+        year: 2024,
+        ..valid_project_request()
+    };
+    assert!(handlers::create_project(student_user(), State(state), Query(handlers::SaveOptions::default()), Json(payload)).await.is_ok());
+}
+
+#[test]
+async fn test_over_long_abstract_is_explained_or_truncated_on_request() {
+    let repo = Arc::new(InMemoryRepository::new());
+    // Every case below is a new project of the same student and year.
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .configure(|config| config.max_projects_per_user_per_year = 10)
+        .build();
+    let max = handlers::MAX_ABSTRACT_CHARS;
+    let create = |abstract_text: String, truncate| {
+        let payload = CreateProjectRequest { abstract_text, year: 2024, force: true, ..valid_project_request() };
+        handlers::create_project(student_user(), State(state.clone()), Query(handlers::SaveOptions { truncate }), Json(payload))
+    };
+
+    // 1,001 five-character words: 6,005 characters with the separating spaces.
+    let thesis = vec!["word"; 1001].join(" ") + " tail";
+    let err = create(thesis.clone(), false).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "abstract_too_long");
+    assert_eq!(
+        err.body.details.unwrap(),
+        serde_json::json!({ "field": "abstract_text", "max": max, "length": 5009, "excess_chars": 9, "truncate_at": 4999 })
+    );
+
+    // Opting in keeps the submission, cut after the last whole word that fits.
+    let Json(created) = create(thesis.clone(), true).await.unwrap();
+    assert!(created.truncated);
+    assert_eq!(created.abstract_length, 4999);
+    assert_eq!(created.project.abstract_text, thesis[..4999]);
+    assert!(created.project.abstract_text.ends_with("word"));
+    let stored = repo.get_project(created.project.id).await.unwrap();
+    assert_eq!(stored.abstract_text, created.project.abstract_text);
+
+    // Exactly at the limit (surrounding whitespace not counted) is stored untouched.
+    let Json(fits) = create(format!("  {}\n", "a".repeat(max)), true).await.unwrap();
+    assert!(!fits.truncated);
+    assert_eq!(fits.project.abstract_text.chars().count(), max + 3);
+
+    // Lengths are characters, not bytes: multi-byte words are never split inside a character,
+    // and a single word longer than the limit is cut mid-word.
+    let accented = vec!["café"; 1200].join(" ");
+    let err = create(accented.clone(), false).await.unwrap_err();
+    assert_eq!(err.body.details.unwrap()["length"], 5999);
+    let Json(cut) = create(accented.clone(), true).await.unwrap();
+    assert_eq!(cut.abstract_length, 4999);
+    assert!(accented.starts_with(&cut.project.abstract_text));
+    assert!(cut.project.abstract_text.ends_with("café"));
+    let Json(one_word) = create("ü".repeat(max + 1), true).await.unwrap();
+    assert_eq!(one_word.project.abstract_text, "ü".repeat(max));
+
+    // Updates follow the same rules and report them in the update result.
+    let project = repo.create_project(CreateProjectRequest::default(), TEST_ID).await;
+    let update = |truncate| {
+        let payload = UpdateProjectRequest { abstract_text: Some(thesis.clone()), ..UpdateProjectRequest::default() };
+        let owner = OwnerOrAdmin { user: student_user(), resource: project.clone() };
+        handlers::update_project(State(state.clone()), owner, Query(handlers::SaveOptions { truncate }), ProjectPatch(payload))
+    };
+    assert_eq!(update(false).await.unwrap_err().body.code, "abstract_too_long");
+    assert!(repo.get_project(project.id).await.unwrap().abstract_text.is_empty());
+    let Json(result) = update(true).await.unwrap();
+    assert!(result.truncated);
+    assert_eq!(result.abstract_length, 4999);
+    assert_eq!(result.changed_fields, vec!["abstract_text"]);
+}
+
+#[test]
+async fn test_settings_endpoints_require_admin_and_validate() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let put = |user, key: &str, value| {
+        handlers::put_setting(
+            user,
+            State(state.clone()),
+            Path(key.to_string()),
+            Json(fyp_portal::models::UpdateSettingRequest { value }),
+        )
+    };
+
+    let err = put(student_user(), "maintenance_mode", serde_json::json!("read_only")).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+    let err = put(admin_user(), "submission_deadline", serde_json::json!("soon")).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err.body.code, "invalid_setting");
+
+    let Json(stored) = put(admin_user(), "maintenance_mode", serde_json::json!("read_only")).await.unwrap();
+    assert_eq!(stored.value, serde_json::json!("read_only"));
+    assert_eq!(state.settings().maintenance(), fyp_portal::models::MaintenanceMode::ReadOnly);
+    let Json(read) = handlers::get_setting(admin_user(), State(state.clone()), Path("maintenance_mode".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(read, stored);
+    let err = handlers::get_setting(admin_user(), State(state), Path("nope".to_string())).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::NOT_FOUND);
+}
+
+#[test]
+async fn test_project_year_must_be_an_academic_year() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let put = |value| {
+        handlers::put_setting(
+            admin_user(),
+            State(state.clone()),
+            Path("academic_years".to_string()),
+            Json(fyp_portal::models::UpdateSettingRequest { value }),
+        )
+    };
+    let create = |year| {
+        let payload = CreateProjectRequest { year, ..valid_project_request() };
+        handlers::create_project(student_user(), State(state.clone()), Query(handlers::SaveOptions::default()), Json(payload))
+    };
+
+    let err = put(serde_json::json!({ "min": 2025, "max": 2020 })).await.unwrap_err();
+    assert_eq!(err.body.code, "invalid_setting");
+    let _ = put(serde_json::json!({ "min": 2020, "max": 2024 })).await.unwrap();
+
+    for year in [2020, 2024] {
+        assert!(create(year).await.is_ok(), "{year} is inside the range");
+    }
+    for year in [2019, 2025] {
+        let err = create(year).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.body.code, "invalid_year");
+        assert_eq!(err.body.details.unwrap(), serde_json::json!({ "year": year, "min": 2020, "max": 2024 }));
+    }
+
+    // A new range applies to the next request; nothing is restarted or refreshed.
+    let _ = put(serde_json::json!({ "min": 2021, "max": 2025 })).await.unwrap();
+    assert!(create(2025).await.is_ok());
+    assert_eq!(create(2020).await.unwrap_err().body.code, "invalid_year");
+}
+
+#[test]
+async fn test_invalid_years_are_reported_and_remapped() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let range = fyp_portal::models::UpdateSettingRequest { value: serde_json::json!({ "min": 2020, "max": 2025 }) };
+    let _ = handlers::put_setting(admin_user(), State(state.clone()), Path("academic_years".to_string()), Json(range))
+        .await
+        .unwrap();
+    let mut typos = vec![];
+    for (title, year) in [("Typo A", 1999), ("Typo B", 1999), ("Future", 2031), ("Fine", 2024)] {
+        let request = CreateProjectRequest { title: title.to_string(), year, ..CreateProjectRequest::default() };
+        typos.push(repo.create_project(request, TEST_ID).await);
+    }
+
+    assert_eq!(
+        handlers::get_invalid_years(student_user(), State(state.clone())).await.unwrap_err(),
+        StatusCode::FORBIDDEN
+    );
+    let Json(report) = handlers::get_invalid_years(admin_user(), State(state.clone())).await.unwrap();
+    assert_eq!((report.allowed.min, report.allowed.max), (2020, 2025));
+    let listed: Vec<_> = report.projects.iter().map(|p| (p.title.as_str(), p.year)).collect();
+    assert_eq!(listed, [("Typo A", 1999), ("Typo B", 1999), ("Future", 2031)]);
+
+    let remap = |from, to| {
+        let payload = fyp_portal::models::YearRemapRequest { from, to };
+        handlers::remap_invalid_years(admin_user(), State(state.clone()), Json(payload))
+    };
+    for (from, to) in [(1999, 2031), (2024, 2024)] {
+        assert_eq!(remap(from, to).await.unwrap_err().status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+    let Json(moved) = remap(1999, 2020).await.unwrap();
+    assert_eq!(moved.updated, 2);
+    let Json(moved) = remap(1999, 2020).await.unwrap();
+    assert_eq!(moved.updated, 0);
+
+    let fixed = repo.get_project(typos[0].id).await.unwrap();
+    assert_eq!((fixed.year, fixed.slug.as_str()), (2020, "typo-a-2020"));
+    // The old slug still leads to the project.
+    assert_eq!(repo.resolve_project_slug("typo-a-1999").await, Some(typos[0].id));
+    let Json(report) = handlers::get_invalid_years(admin_user(), State(state)).await.unwrap();
+    assert_eq!(report.projects.len(), 1);
+    assert_eq!(report.projects[0].project_id, typos[2].id);
+}
+
+#[test]
+async fn test_create_programme_validates_and_detects_duplicates() {
+    let create = |user: AuthUser, code: &str, name: &str| {
+        let state = create_test_state(programme_repo(), MockStorageService::new());
+        let payload = fyp_portal::models::CreateProgrammeRequest {
+            code: code.to_string(),
+            name: name.to_string(),
+        };
+        handlers::create_programme(user, State(state), Json(payload))
+    };
+
+    let (status, Json(created)) = create(admin_user(), " ee-joint ", "Electronic Eng. (Joint)")
+        .await
+        .unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created, programme("EE-JOINT", "Electronic Eng. (Joint)"));
+
+    let status_of = |result: Result<_, fyp_portal::error::ApiError>| result.map(|_| ()).unwrap_err().status;
+    assert_eq!(status_of(create(admin_user(), "cs", "Again").await), StatusCode::CONFLICT);
+    assert_eq!(status_of(create(admin_user(), "C", "Too short").await), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(status_of(create(admin_user(), "C S", "Space").await), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(status_of(create(admin_user(), "PH", "  ").await), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(status_of(create(student_user(), "PH", "Physics").await), StatusCode::FORBIDDEN);
+}
+
+// --- Auth Provider (Supabase) ---
+
+/// State whose register proxy points at `server`, with a short provider timeout.
+fn auth_provider_state(server: &wiremock::MockServer) -> AppState {
+    let config = AppConfig {
+        supabase_url: Some(server.uri()),
+        supabase_key: Some("test-anon-key".to_string()),
+        auth_provider_timeout: std::time::Duration::from_millis(200),
+        ..AppConfig::default()
+    };
+    AppStateBuilder::test_default()
+        .repo(Arc::new(MockRepoControl::default()))
+        .http(fyp_portal::auth_provider::http_client(&config))
+        .config(config)
+        .build()
+}
+
+fn registration() -> RegisterUserRequest {
+    RegisterUserRequest {
+        email: "new@student.ul.ie".to_string(),
+        password: "correct horse".to_string(),
+        role: "student".to_string(),
+    }
+}
+
+#[test]
+async fn test_register_calls_auth_provider_with_key_and_user_agent() {
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/auth/v1/signup"))
+        .and(matchers::header("apikey", "test-anon-key"))
+        .and(matchers::header("user-agent", fyp_portal::build_info::server_header().as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": Uuid::new_v4() })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let before = metrics::AUTH_PROVIDER_REQUESTS.get("success");
+    let result = handlers::register_user(State(auth_provider_state(&server)), Json(registration())).await;
+
+    assert!(result.is_ok());
+    assert!(metrics::AUTH_PROVIDER_REQUESTS.get("success") > before);
+}
+
+#[test]
+async fn test_register_auth_provider_rejection_is_bad_request() {
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    let server = MockServer::start().await;
+    Mock::given(matchers::path("/auth/v1/signup"))
+        .respond_with(ResponseTemplate::new(422))
+        .mount(&server)
+        .await;
+
+    let Err(err) = handlers::register_user(State(auth_provider_state(&server)), Json(registration())).await else {
+        panic!("a rejected signup must not create a profile")
+    };
+    assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    assert_eq!(err.body.code, "registration_rejected");
+}
+
+#[test]
+async fn test_register_auth_provider_timeout_is_gateway_timeout() {
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    let server = MockServer::start().await;
+    Mock::given(matchers::path("/auth/v1/signup"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "id": Uuid::new_v4() }))
+                .set_delay(std::time::Duration::from_secs(2)),
+        )
+        .mount(&server)
+        .await;
+
+    let before = metrics::AUTH_PROVIDER_REQUESTS.get("timeout");
+    let started = std::time::Instant::now();
+    let Err(err) = handlers::register_user(State(auth_provider_state(&server)), Json(registration())).await else {
+        panic!("a stalled auth provider must time out")
+    };
+
+    assert_eq!(err.status, StatusCode::GATEWAY_TIMEOUT);
+    assert_eq!(err.body.code, "auth_provider_timeout");
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    assert!(metrics::AUTH_PROVIDER_REQUESTS.get("timeout") > before);
+    assert!(metrics::render().contains("auth_provider_requests_total{outcome=\"timeout\"}"));
+}
+
+#[test]
+async fn test_register_without_auth_provider_config_fails_cleanly() {
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+
+    let Err(err) = handlers::register_user(State(state), Json(registration())).await else {
+        panic!("registration needs SUPABASE_URL and SUPABASE_KEY")
+    };
+    assert_eq!(err.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(err.body.code, "auth_provider_unconfigured");
+}
+
+// --- Comment Reports ---
+
+#[test]
+async fn test_comment_listing_visibility_depends_on_viewer() {
+    let visibility = |user: Option<AuthUser>| async move {
+        let control = Arc::new(MockRepoControl::default());
+        let state = AppStateBuilder::test_default()
+            .repo(control.clone())
+            .build();
+        let _ = handlers::get_comments(
+            user,
+            State(state),
+            Path(TEST_ID),
+            Query(handlers::CommentFilter { order: None, limit: None, before: None, after: None }),
+        )
+        .await;
+        control.comment_visibility.lock().unwrap()[0]
+    };
+
+    assert_eq!(visibility(None).await, HiddenComments::Exclude);
+    assert_eq!(visibility(Some(student_user())).await, HiddenComments::AuthoredBy(TEST_ID));
+    assert_eq!(visibility(Some(admin_user())).await, HiddenComments::Include);
+}
+
+#[test]
+async fn test_report_comment_passes_configured_threshold() {
+    let control = Arc::new(MockRepoControl {
+        report_outcome: Some(ReportOutcome { pending_reports: 2, hidden: true }),
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .configure(|config| config.auto_hide_report_threshold = 2)
+        .build();
+
+    let status = handlers::report_comment(
+        student_user(),
+        State(state),
+        Path((TEST_ID, comment_id(7))),
+        Json(CreateReportRequest { reason: Some("  spam  ".to_string()) }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let reports = control.reports.lock().unwrap();
+    assert_eq!(*reports, vec![(Some(TEST_ID), 7, TEST_ID, Some("spam".to_string()), 2)]);
+}
+
+#[test]
+async fn test_report_by_comment_id_is_unscoped() {
+    let control = Arc::new(MockRepoControl {
+        report_outcome: Some(ReportOutcome { pending_reports: 1, hidden: false }),
+        ..MockRepoControl::default()
+    });
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+
+    let status = handlers::report_comment_by_id(
+        student_user(),
+        State(state),
+        Path(comment_id(7)),
+        Json(CreateReportRequest { reason: Some("   ".to_string()) }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let threshold = fyp_portal::config::AppConfig::default().auto_hide_report_threshold;
+    assert_eq!(*control.reports.lock().unwrap(), vec![(None, 7, TEST_ID, None, threshold)]);
+}
+
+#[test]
+async fn test_admin_comment_listing_is_admin_only_and_passes_filters() {
+    let control = Arc::new(MockRepoControl::default());
+    let state = AppStateBuilder::test_default()
+        .repo(control.clone())
+        .build();
+    let list = |user: AuthUser, search: Option<&str>, limit: Option<&str>, offset: Option<&str>| {
+        let query = handlers::AdminCommentQuery {
+            project_id: Some(TEST_ID),
+            user_id: None,
+            search: search.map(str::to_string),
+            limit: limit.map(str::to_string),
+            offset: offset.map(str::to_string),
+        };
+        handlers::get_admin_comments(user, State(state.clone()), Query(query))
+    };
+
+    let Err(err) = list(student_user(), None, None, None).await else {
+        panic!("students must not list every comment")
+    };
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::FORBIDDEN, "forbidden"));
+    assert!(control.admin_comment_queries.lock().unwrap().is_empty());
+
+    assert!(list(admin_user(), Some("  spam "), None, None).await.is_ok());
+    assert!(list(admin_user(), Some("   "), Some("500"), Some("40")).await.is_ok());
+    let filter = |search: Option<&str>| AdminCommentFilter {
+        project_id: Some(TEST_ID),
+        user_id: None,
+        search: search.map(str::to_string),
+    };
+    assert_eq!(
+        *control.admin_comment_queries.lock().unwrap(),
+        vec![
+            (filter(Some("spam")), handlers::DEFAULT_COMMENT_PAGE_LIMIT, 0),
+            (filter(None), handlers::MAX_COMMENT_PAGE_LIMIT, 40),
+        ]
+    );
+
+    let err = list(admin_user(), None, Some("0"), None).await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::UNPROCESSABLE_ENTITY, "invalid_limit"));
+    let err = list(admin_user(), None, None, Some("-1")).await.unwrap_err();
+    assert_eq!((err.status, err.body.code.as_str()), (StatusCode::UNPROCESSABLE_ENTITY, "invalid_offset"));
+    assert_eq!(control.admin_comment_queries.lock().unwrap().len(), 2);
+}
+
+#[test]
+async fn test_reported_comments_queue_is_admin_only_and_empties_on_resolution() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .configure(|config| config.auto_hide_report_threshold = 10)
+        .build();
+    let reporters = [Uuid::from_u128(31), Uuid::from_u128(32)];
+    for (id, role) in [(TEST_ID, "student"), (reporters[0], "student"), (reporters[1], "student")] {
+        repo.create_user(User { id, email: format!("{id}@ul.ie"), role: role.to_string() }).await;
+    }
+    let project = repo.create_project(valid_project_request(), TEST_ADMIN_ID).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    let mild = repo.add_comment(project.id, TEST_ID, "Meh".to_string(), 500).await.unwrap().unwrap().comment;
+    let rude = repo.add_comment(project.id, TEST_ID, "Rude".to_string(), 500).await.unwrap().unwrap().comment;
+    let report = |reporter: Uuid, comment: i64, reason: Option<&str>| {
+        let user = AuthUser { id: reporter, ..student_user() };
+        let request = CreateReportRequest { reason: reason.map(str::to_string) };
+        handlers::report_comment_by_id(user, State(state.clone()), Path(comment_id(comment)), Json(request))
+    };
+    let queue = || async {
+        let Json(queue) = handlers::get_reported_comments(admin_user(), State(state.clone())).await.unwrap();
+        queue.into_iter().map(|r| (r.comment.id, r.report_count, r.reasons)).collect::<Vec<_>>()
+    };
+
+    assert_eq!(report(reporters[0], mild.id, None).await, StatusCode::NO_CONTENT);
+    assert_eq!(report(reporters[0], rude.id, Some("insulting")).await, StatusCode::NO_CONTENT);
+    assert_eq!(report(reporters[1], rude.id, Some("abusive")).await, StatusCode::NO_CONTENT);
+    // A repeat report is accepted but not counted again.
+    assert_eq!(report(reporters[1], rude.id, Some("abusive!!")).await, StatusCode::NO_CONTENT);
+    assert_eq!(report(reporters[0], i64::from(u32::MAX), None).await, StatusCode::NOT_FOUND);
+
+    let Err(err) = handlers::get_reported_comments(student_user(), State(state.clone())).await else {
+        panic!("students must not see the moderation queue")
+    };
+    assert_eq!(err.status, StatusCode::FORBIDDEN);
+    let reasons = |r: &[&str]| r.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        queue().await,
+        vec![(rude.id, 2, reasons(&["insulting", "abusive"])), (mild.id, 1, vec![])]
+    );
+
+    // Dismissing clears one entry, deleting the other.
+    assert_eq!(
+        handlers::restore_comment(admin_user(), State(state.clone()), Path(comment_id(mild.id))).await.unwrap(),
+        StatusCode::NO_CONTENT
+    );
+    assert_eq!(queue().await, vec![(rude.id, 2, reasons(&["insulting", "abusive"]))]);
+    assert_eq!(
+        handlers::delete_comment(admin_user(), State(state.clone()), Path(comment_id(rude.id))).await,
+        StatusCode::NO_CONTENT
+    );
+    assert!(queue().await.is_empty());
+}
+
+#[test]
+async fn test_report_missing_comment_is_not_found() {
+    let state = create_test_state(MockRepoControl::default(), MockStorageService::new());
+
+    let status = handlers::report_comment(
+        student_user(),
+        State(state),
+        Path((TEST_ID, comment_id(7))),
+        Json(CreateReportRequest::default()),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[test]
+async fn test_restore_comment_requires_admin() {
+    let restore = |user: AuthUser, found: bool| async move {
+        let state = create_test_state(
+            MockRepoControl {
+                restore_comment_result: found,
+                ..MockRepoControl::default()
+            },
+            MockStorageService::new(),
+        );
+        handlers::restore_comment(user, State(state), Path(comment_id(7))).await
+    };
+
+    assert_eq!(restore(admin_user(), true).await.unwrap(), StatusCode::NO_CONTENT);
+    assert_eq!(restore(admin_user(), false).await.unwrap_err().status, StatusCode::NOT_FOUND);
+    assert_eq!(restore(student_user(), true).await.unwrap_err().status, StatusCode::FORBIDDEN);
+}
+
+fn feedback(message: &str, contact_email: Option<&str>) -> CreateFeedbackRequest {
+    CreateFeedbackRequest {
+        message: message.to_string(),
+        contact_email: contact_email.map(str::to_string),
+        captcha_token: None,
+    }
+}
+
+#[test]
+async fn test_feedback_validation_bounds() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let project = repo.create_project(CreateProjectRequest::default(), Uuid::new_v4()).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    let submit = |payload| handlers::submit_feedback(State(state.clone()), Path(project.id), Json(payload));
+
+    // Surrounding whitespace does not count towards the bounds.
+    for message in ["x".repeat(9), format!("  {}  ", "x".repeat(9)), "x".repeat(1001)] {
+        let err = submit(feedback(&message, None)).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY, "{} chars", message.len());
+        assert_eq!(err.body.code, "invalid_feedback");
+        assert_eq!(err.body.details.unwrap()["field"], "message");
+    }
+    for email in ["not-an-email", "a@b", "a b@ul.ie", "@ul.ie"] {
+        let err = submit(feedback("Ten chars!", Some(email))).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY, "{email}");
+        assert_eq!(err.body.details.unwrap()["field"], "contact_email");
+    }
+    assert!(repo.get_feedback(None).await.is_empty());
+
+    let (status, Json(stored)) = submit(feedback("  Ten chars!  ", Some(" "))).await.unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(stored.message, "Ten chars!");
+    assert_eq!(stored.contact_email, None);
+    let (_, Json(stored)) = submit(feedback(&"é".repeat(1000), Some("visitor@example.com"))).await.unwrap();
+    assert_eq!(stored.contact_email.as_deref(), Some("visitor@example.com"));
+}
+
+#[test]
+async fn test_feedback_notifies_owner_and_is_readable_only_by_owner_or_admin() {
+    use tower::util::ServiceExt;
+
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .build();
+    let user = |role: &str| User { id: Uuid::new_v4(), email: format!("{role}@ul.ie"), role: role.to_string() };
+    let (owner, stranger, admin) = (user("student"), user("student"), user("admin"));
+    for u in [&owner, &stranger, &admin] {
+        repo.create_user(u.clone()).await;
+    }
+    let public = repo.create_project(CreateProjectRequest::default(), owner.id).await;
+    repo.set_project_status(public.id, ProjectStatus::Approved).await.unwrap();
+    let private = repo.create_project(CreateProjectRequest::default(), owner.id).await;
+
+    // Private projects look missing to anonymous visitors.
+    let submit = |id| handlers::submit_feedback(State(state.clone()), Path(id), Json(feedback("Great poster session!", None)));
+    let err = submit(private.id).await.unwrap_err();
+    assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+    let (_, Json(stored)) = submit(public.id).await.unwrap();
+    let notifications = repo.get_notifications(owner.id).await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].notification_type, "feedback");
+    assert_eq!(notifications[0].project_id, public.id);
+
+    let app = axum::Router::new()
+        .route("/me/projects/{id}/feedback", axum::routing::get(handlers::get_project_feedback))
+        .route("/admin/feedback", axum::routing::get(handlers::get_all_feedback))
+        .with_state(state);
+    let read = |uri: String, caller: Uuid| {
+        let request = axum::http::Request::builder()
+            .uri(uri)
+            .header("x-user-id", caller.to_string())
+            .body(axum::body::Body::empty())
+            .unwrap();
+        app.clone().oneshot(request)
+    };
+    let own = format!("/me/projects/{}/feedback", public.id);
+    for caller in [owner.id, admin.id] {
+        let response = read(own.clone(), caller).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let listed: Vec<ProjectFeedback> = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(listed, vec![stored.clone()]);
+    }
+    assert_eq!(read(own, stranger.id).await.unwrap().status(), StatusCode::FORBIDDEN);
+    assert_eq!(read("/admin/feedback".to_string(), owner.id).await.unwrap().status(), StatusCode::FORBIDDEN);
+    assert_eq!(read("/admin/feedback".to_string(), admin.id).await.unwrap().status(), StatusCode::OK);
+}
+
+#[test]
+async fn test_feedback_requires_captcha_when_configured() {
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_string_contains("response=good-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })))
+        .mount(&server)
+        .await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_string_contains("response=bad-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": false })))
+        .mount(&server)
+        .await;
+
+    let repo = Arc::new(InMemoryRepository::new());
+    let state = AppStateBuilder::test_default()
+        .repo(repo.clone())
+        .configure(|config| {
+            config.captcha_secret = Some("captcha-secret".to_string());
+            config.captcha_verify_url = server.uri();
+        })
+        .build();
+    let project = repo.create_project(CreateProjectRequest::default(), Uuid::new_v4()).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    let submit = |token: Option<&str>| {
+        let payload = CreateFeedbackRequest {
+            captcha_token: token.map(str::to_string),
+            ..feedback("Great poster session!", None)
+        };
+        handlers::submit_feedback(State(state.clone()), Path(project.id), Json(payload))
+    };
+
+    for token in [None, Some("bad-token")] {
+        let err = submit(token).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST, "{token:?}");
+        assert_eq!(err.body.code, "captcha_failed");
+    }
+    assert!(repo.get_feedback(None).await.is_empty());
+    let (status, _) = submit(Some("good-token")).await.unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+}
@@ -0,0 +1,85 @@
+use axum::http::Uri;
+use fyp_portal::{AppConfig, links::UrlBuilder, models::PageLinks};
+
+fn config() -> AppConfig {
+    AppConfig {
+        public_api_url: "https://api.fyp.example".to_string(),
+        ..AppConfig::default()
+    }
+}
+
+fn page_links(uri: &str, page: i64, per_page: i64, total_count: i64) -> PageLinks {
+    let config = config();
+    UrlBuilder::new(&config).page_links(&uri.parse::<Uri>().unwrap(), page, per_page, total_count)
+}
+
+#[test]
+fn test_page_links_preserve_filters() {
+    let links = page_links("/projects?year=2024&search=rust&page=2", 2, 10, 35);
+    assert_eq!(
+        links,
+        PageLinks {
+            self_link: "https://api.fyp.example/projects?year=2024&search=rust&page=2".to_string(),
+            next: Some("https://api.fyp.example/projects?year=2024&search=rust&page=3".to_string()),
+            prev: Some("https://api.fyp.example/projects?year=2024&search=rust&page=1".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_page_links_swap_only_page_and_keep_encoding() {
+    // `page` in the middle is replaced; encoded values and look-alike keys are untouched.
+    let links = page_links("/admin/projects?page=1&search=c%2B%2B&pages=x&fields=id,title", 1, 20, 41);
+    assert_eq!(
+        links.self_link,
+        "https://api.fyp.example/admin/projects?search=c%2B%2B&pages=x&fields=id,title&page=1"
+    );
+    assert_eq!(
+        links.next.as_deref(),
+        Some("https://api.fyp.example/admin/projects?search=c%2B%2B&pages=x&fields=id,title&page=2")
+    );
+}
+
+#[test]
+fn test_page_links_omit_prev_on_first_page() {
+    let links = page_links("/notifications", 1, 20, 45);
+    assert_eq!(links.self_link, "https://api.fyp.example/notifications?page=1");
+    assert_eq!(links.next.as_deref(), Some("https://api.fyp.example/notifications?page=2"));
+    assert_eq!(links.prev, None);
+}
+
+#[test]
+fn test_page_links_omit_next_on_last_page() {
+    let links = page_links("/projects/abc/comments?order=newest&page=3", 3, 20, 45);
+    assert_eq!(links.next, None);
+    assert_eq!(
+        links.prev.as_deref(),
+        Some("https://api.fyp.example/projects/abc/comments?order=newest&page=2")
+    );
+
+    // Exactly full last page, and an empty listing (a single first page).
+    assert_eq!(page_links("/projects?page=2", 2, 20, 40).next, None);
+    let empty = page_links("/projects", 1, 20, 0);
+    assert_eq!((empty.next, empty.prev), (None, None));
+}
+
+#[test]
+fn test_page_links_serialize_self_key() {
+    let json = serde_json::to_value(page_links("/projects", 1, 20, 5)).unwrap();
+    assert_eq!(json, serde_json::json!({ "self": "https://api.fyp.example/projects?page=1" }));
+}
+
+#[test]
+fn test_cursor_links_swap_cursor_and_never_go_back() {
+    let config = config();
+    let uri = "/projects?cursor=aa&year=2024&per_page=5".parse::<Uri>().unwrap();
+    let links = UrlBuilder::new(&config).cursor_links(&uri, Some("bb"));
+    assert_eq!(links.self_link, "https://api.fyp.example/projects?cursor=aa&year=2024&per_page=5");
+    assert_eq!(
+        links.next.as_deref(),
+        Some("https://api.fyp.example/projects?year=2024&per_page=5&cursor=bb")
+    );
+    assert_eq!(links.prev, None);
+
+    assert_eq!(UrlBuilder::new(&config).cursor_links(&uri, None).next, None);
+}
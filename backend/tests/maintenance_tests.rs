@@ -0,0 +1,158 @@
+//! The stale-draft and audit retention maintenance passes, driven by a `MockClock` against the in-memory repository.
+
+use chrono::Duration;
+use fyp_portal::{
+    clock::{Clock, MockClock},
+    config::AppConfig,
+    maintenance::{self, AuditRetention, DraftReminders},
+    models::{CreateProjectRequest, NewAuditEntry, Project, User},
+    pagination::PageRequest,
+    repository::{AuditFilter, Repository, RepositoryState, memory::InMemoryRepository},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn owner(repo: &InMemoryRepository) -> User {
+    let id = Uuid::new_v4();
+    repo.create_user(User {
+        id,
+        email: format!("{}@test.com", id.simple()),
+        role: "student".to_string(),
+    })
+    .await
+}
+
+async fn draft(repo: &InMemoryRepository, owner: &User, title: &str) -> Project {
+    let request = CreateProjectRequest {
+        title: title.to_string(),
+        year: 2024,
+        ..CreateProjectRequest::default()
+    };
+    repo.create_project(request, owner.id).await
+}
+
+async fn reminder_count(repo: &InMemoryRepository, owner: &User) -> usize {
+    repo.get_notifications(owner.id)
+        .await
+        .iter()
+        .filter(|n| n.notification_type == "draft_reminder")
+        .count()
+}
+
+fn setup() -> (Arc<InMemoryRepository>, DraftReminders) {
+    let repo = Arc::new(InMemoryRepository::new());
+    let reminders = DraftReminders::new(repo.clone() as RepositoryState, &AppConfig::default());
+    (repo, reminders)
+}
+
+#[tokio::test]
+async fn test_draft_is_reminded_once_after_threshold() {
+    let (repo, reminders) = setup();
+    let clock = MockClock::new();
+    let student = owner(&repo).await;
+    let abandoned = draft(&repo, &student, "Abandoned").await;
+    let submitted = draft(&repo, &student, "Submitted").await;
+    repo.mark_project_submitted(submitted.id).await.unwrap();
+
+    clock.advance(Duration::days(6));
+    assert!(reminders.sweep(clock.now(), false).await.reminded.is_empty());
+
+    clock.advance(Duration::days(2));
+    let report = reminders.sweep(clock.now(), false).await;
+    let reminded: Vec<Uuid> = report.reminded.iter().map(|d| d.project_id).collect();
+    assert_eq!(reminded, vec![abandoned.id]);
+    assert_eq!(report.reminded[0].owner_id, student.id);
+    assert!(report.flagged_for_cleanup.is_empty());
+    assert_eq!(reminder_count(&repo, &student).await, 1);
+
+    // Later passes never remind the same draft again.
+    for _ in 0..3 {
+        clock.advance(Duration::days(7));
+        assert!(reminders.sweep(clock.now(), false).await.reminded.is_empty());
+    }
+    assert_eq!(reminder_count(&repo, &student).await, 1);
+}
+
+#[tokio::test]
+async fn test_very_old_draft_is_flagged_for_cleanup_once() {
+    let (repo, reminders) = setup();
+    let clock = MockClock::new();
+    let student = owner(&repo).await;
+    let ancient = draft(&repo, &student, "Ancient").await;
+
+    clock.advance(Duration::days(89));
+    let report = reminders.sweep(clock.now(), false).await;
+    assert_eq!(report.reminded.len(), 1);
+    assert!(report.flagged_for_cleanup.is_empty());
+
+    clock.advance(Duration::days(2));
+    let report = reminders.sweep(clock.now(), false).await;
+    assert!(report.reminded.is_empty());
+    assert_eq!(report.flagged_for_cleanup[0].project_id, ancient.id);
+
+    assert!(reminders.sweep(clock.now(), false).await.flagged_for_cleanup.is_empty());
+    assert_eq!(reminder_count(&repo, &student).await, 1);
+}
+
+#[tokio::test]
+async fn test_dry_run_reports_without_writing() {
+    let (repo, reminders) = setup();
+    let clock = MockClock::new();
+    let student = owner(&repo).await;
+    let stale = draft(&repo, &student, "Stale").await;
+    clock.advance(Duration::days(100));
+
+    for _ in 0..2 {
+        let preview = reminders.sweep(clock.now(), true).await;
+        assert!(preview.dry_run);
+        assert_eq!(preview.reminded[0].project_id, stale.id);
+        assert_eq!(preview.flagged_for_cleanup[0].project_id, stale.id);
+    }
+    assert_eq!(reminder_count(&repo, &student).await, 0);
+
+    let report = reminders.sweep(clock.now(), false).await;
+    assert!(!report.dry_run);
+    assert_eq!((report.reminded.len(), report.flagged_for_cleanup.len()), (1, 1));
+    assert_eq!(reminder_count(&repo, &student).await, 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_runner_passes_do_not_repeat_reminders() {
+    let (repo, reminders) = setup();
+    let clock = Arc::new(MockClock::new());
+    let student = owner(&repo).await;
+    draft(&repo, &student, "Forgotten").await;
+    clock.advance(Duration::days(8));
+
+    let interval = std::time::Duration::from_secs(60 * 60);
+    let runner = maintenance::spawn_runner(Arc::default(), Arc::new(reminders), clock.clone(), interval);
+    // Paused time auto-advances, so this covers the immediate pass and three more.
+    tokio::time::sleep(interval * 3 + std::time::Duration::from_secs(1)).await;
+    runner.abort();
+
+    assert_eq!(reminder_count(&repo, &student).await, 1);
+}
+
+#[tokio::test]
+async fn test_audit_retention_purges_only_expired_entries_and_audits_the_purge() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let retention = AuditRetention::new(repo.clone() as RepositoryState, &AppConfig::default());
+    let clock = MockClock::new();
+    let admin = Uuid::new_v4();
+    for action in ["project.status", "setting.put"] {
+        repo.record_audit(&NewAuditEntry::new(Some(admin), action, "project", Uuid::new_v4())).await.unwrap();
+    }
+    let log = async || repo.get_audit_log(&AuditFilter::default(), PageRequest::default()).await.0;
+
+    // Nothing is a full 365 days old yet: no purge, and no entry about one.
+    clock.advance(Duration::days(365));
+    assert_eq!(retention.purge(clock.now()).await.unwrap(), 0);
+    assert_eq!(log().await.len(), 2);
+
+    clock.advance(Duration::days(1));
+    assert_eq!(retention.purge(clock.now()).await.unwrap(), 2);
+    let remaining = log().await;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!((remaining[0].action.as_str(), remaining[0].actor_id), ("audit.purge", None));
+    assert_eq!(remaining[0].details["purged"], 2);
+}
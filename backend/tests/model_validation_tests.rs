@@ -6,6 +6,7 @@ use std::collections::HashMap;
 // --- Test Utilities (Mocking SQLX behavior) ---
 
 // Mock trait to simulate fetching a row for testing FromRow derivation
+#[allow(dead_code)]
 trait MockRow: Row {
     fn mock_get<T>(&self, index: &str) -> T
     where
@@ -39,7 +40,7 @@ async fn test_project_abstract_text_mapping() {
         ("updated_at", Utc::now().to_string()),
     ]
     .iter()
-    .map(|(k, v)| (k.clone(), v.clone()))
+    .map(|(k, v)| (*k, v.clone()))
     .collect();
 
     // Since we cannot mock sqlx::Row, we rely on the integration test (repository_integration.rs)
@@ -62,6 +63,7 @@ fn test_notification_response_json_serialization() {
         project_id: Uuid::new_v4(),
         project_title: "Project X".to_string(),
         notification_type: "like".to_string(), // Rust field name
+        message: None,
         is_read: false,
         created_at: Utc::now(),
     };
@@ -87,6 +89,15 @@ fn test_update_project_request_optionality() {
         cover_image_key: None,
         video_key: None,
         report_key: None,
+        author_name: None,
+        year: None,
+        links: None,
+        video_waived: None,
+        supervisor: None,
+        department: None,
+        technologies: None,
+        tags: None,
+        programme_codes: None,
     };
 
     // The key validation is that it can be created and serialized without error.
@@ -0,0 +1,107 @@
+use fyp_portal::moderation::{extract_mentions, sanitize_comment};
+
+#[test]
+fn test_plain_text_is_unchanged() {
+    for text in [
+        "Great project!",
+        "Line one\n\nLine two\twith a tab",
+        "x < y and y > z, so 3<4",
+        "Use a <-> b or <= 5",
+        "Escaped &lt;script&gt; stays as typed",
+        "Émojis 👩‍💻 and ‌ZWNJ stay",
+        "",
+    ] {
+        assert_eq!(sanitize_comment(text), text, "{text:?}");
+    }
+}
+
+#[test]
+fn test_script_and_style_elements_are_dropped_with_their_content() {
+    assert_eq!(sanitize_comment("<script>alert(1)</script>"), "");
+    assert_eq!(sanitize_comment("Nice<script>fetch('//evil')</script> work"), "Nice work");
+    assert_eq!(sanitize_comment("<SCRIPT type=\"module\">x()</ScRiPt >after"), "after");
+    assert_eq!(sanitize_comment("a<style>body{display:none}</style>b"), "ab");
+    assert_eq!(sanitize_comment("<iframe src=\"//evil\">fallback</iframe>ok"), "ok");
+    // Never closed: everything after the opening tag is dropped.
+    assert_eq!(sanitize_comment("before<script>alert(1)"), "before");
+    // Markup inside a dropped element goes with it.
+    assert_eq!(sanitize_comment("<script>let s = '<b>';</script>done"), "done");
+}
+
+#[test]
+fn test_event_handler_attributes_go_with_their_tags() {
+    assert_eq!(sanitize_comment("<img src=x onerror=alert(1)>"), "");
+    assert_eq!(sanitize_comment("Look <img src=\"a.png\" onerror=\"alert('>')\"> here"), "Look  here");
+    assert_eq!(sanitize_comment("<a href=\"javascript:alert(1)\" onclick='x()'>link</a>"), "link");
+    assert_eq!(sanitize_comment("<svg onload=alert(1)><circle/></svg>"), "");
+    assert_eq!(sanitize_comment("<div\nonmouseover=\"x()\">text</div>"), "text");
+}
+
+#[test]
+fn test_nested_markup_keeps_only_text() {
+    assert_eq!(sanitize_comment("<p><b>Bold <i>and italic</i></b> text</p>"), "Bold and italic text");
+    assert_eq!(sanitize_comment("<ul><li>one</li><li>two</li></ul>"), "onetwo");
+    assert_eq!(sanitize_comment("<div><script><script>x</script></script>y</div>"), "y");
+    assert_eq!(sanitize_comment("<!-- hidden --><b>shown</b>"), "shown");
+    assert_eq!(sanitize_comment("<!DOCTYPE html><?xml version=\"1.0\"?>text"), "text");
+}
+
+#[test]
+fn test_fragments_cannot_reassemble_into_a_tag() {
+    assert_eq!(sanitize_comment("<<b>script>alert(1)<</b>/script>"), "");
+    // What is left of a broken-up tag is inert text.
+    assert_eq!(sanitize_comment("<scr<b></b>ipt>alert(1)</script>"), "ipt>alert(1)");
+    assert_eq!(sanitize_comment("<<i>img src=x onerror=alert(1)>"), "");
+    // An invisible character cannot hide a tag name from the element list.
+    assert_eq!(sanitize_comment("<scr\u{200B}ipt>alert(1)</script>"), "");
+    for text in ["<<b>script>", "<<<<b>>b>>", "<scr<script>ipt>"] {
+        let clean = sanitize_comment(text);
+        assert!(!clean.contains("<s") && !clean.contains("<b"), "{text:?} became {clean:?}");
+    }
+}
+
+#[test]
+fn test_large_repeated_tag_payloads_are_linear() {
+    // Each dropped element used to copy the rest of the text to find its closing tag.
+    let flood = "<script>x</script><SCRIPT>y</ScRiPt>".repeat(50_000) + "end";
+    let started = std::time::Instant::now();
+    assert_eq!(sanitize_comment(&flood), "end");
+    assert!(started.elapsed() < std::time::Duration::from_secs(5), "{:?}", started.elapsed());
+}
+
+#[test]
+fn test_unclosed_tags_lose_only_their_bracket() {
+    assert_eq!(sanitize_comment("I think a <b is fine"), "I think a b is fine");
+    assert_eq!(sanitize_comment("<img src=x onerror=alert(1)"), "img src=x onerror=alert(1)");
+    assert_eq!(sanitize_comment("<a title='unterminated>quote"), "a title='unterminated>quote");
+}
+
+#[test]
+fn test_control_and_disguising_characters_are_removed() {
+    assert_eq!(sanitize_comment("Windows\r\nline\r\nends"), "Windows\nline\nends");
+    assert_eq!(sanitize_comment("bell\u{7}null\u{0}esc\u{1b}[31m"), "bellnullesc[31m");
+    assert_eq!(sanitize_comment("c1\u{85}\u{9f}chars"), "c1chars");
+    // Trojan Source: bidi overrides and isolates.
+    assert_eq!(sanitize_comment("admin\u{202E}gnp.exe"), "admingnp.exe");
+    assert_eq!(sanitize_comment("\u{2066}a\u{2069}\u{2067}b\u{2068}"), "ab");
+    assert_eq!(sanitize_comment("zero\u{200B}width\u{2060}join\u{FEFF}"), "zerowidthjoin");
+    // Marks that right-to-left text needs are kept.
+    assert_eq!(sanitize_comment("שלום\u{200F} world"), "שלום\u{200F} world");
+}
+
+#[test]
+fn test_mentions_are_lower_cased_and_deduplicated() {
+    assert_eq!(extract_mentions("@alice check the results section"), vec!["alice"]);
+    assert_eq!(
+        extract_mentions("@Bob and @alice, then @ALICE again (@bob.smith-2!) thanks @carol."),
+        vec!["bob", "alice", "bob.smith-2", "carol"]
+    );
+    assert_eq!(extract_mentions("line one\n@dave\t@erin+fyp"), vec!["dave", "erin+fyp"]);
+}
+
+#[test]
+fn test_emails_and_bare_at_signs_are_not_mentions() {
+    assert!(extract_mentions("mail bob@ul.ie or x@@y about it").is_empty());
+    assert!(extract_mentions("meet @ 3pm, @. or @@").is_empty());
+    assert!(extract_mentions("no mentions here").is_empty());
+}
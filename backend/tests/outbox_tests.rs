@@ -0,0 +1,105 @@
+//! The outbox dispatcher: batch-by-batch progress of a queued comment fan-out and resumption
+//! by a fresh dispatcher, and audit entries queued after a failed insert, against the in-memory
+//! repository.
+
+use fyp_portal::{
+    maintenance::MaintenanceTask,
+    models::{ProjectStatus, CreateProjectRequest, NewAuditEntry, SubscriptionState, User},
+    outbox::OutboxDispatcher,
+    pagination::PageRequest,
+    repository::{AuditFilter, Repository, RepositoryState, memory::InMemoryRepository},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A public project with an owner and `followers` subscribers, and a comment from an outsider
+/// queued for fan-out. Returns the repository, the job id and everyone who should hear of it.
+async fn queued_fanout(followers: usize) -> (RepositoryState, i64, Vec<Uuid>) {
+    let repo = Arc::new(InMemoryRepository::new());
+    let mut users = Vec::new();
+    for _ in 0..followers + 2 {
+        let id = Uuid::new_v4();
+        repo.create_user(User { id, email: format!("{id}@t.com"), role: "student".to_string() }).await;
+        users.push(id);
+    }
+    let commenter = users.pop().unwrap();
+    let project = repo
+        .create_project(
+            CreateProjectRequest {
+                title: "Busy".to_string(),
+                abstract_text: "A".to_string(),
+                author_name: "O".to_string(),
+                year: 2025,
+                cover_image_key: "img.jpg".to_string(),
+                ..CreateProjectRequest::default()
+            },
+            users[0],
+        )
+        .await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    for follower in &users[1..] {
+        assert!(repo.set_subscription(project.id, *follower, SubscriptionState::Subscribed).await);
+    }
+
+    let posted = repo.add_comment(project.id, commenter, "Hello all".to_string(), 1).await.unwrap().unwrap();
+    (repo, posted.fanout_job_id.unwrap(), users)
+}
+
+#[tokio::test]
+async fn test_batches_report_progress_until_done() {
+    let (repo, job_id, recipients) = queued_fanout(4).await;
+    let dispatcher = OutboxDispatcher::new(repo.clone()).with_batch_size(2);
+
+    let progress = [(2, "pending"), (4, "pending"), (5, "done")];
+    for (processed, status) in progress {
+        let job = dispatcher.run_batch().await.unwrap();
+        assert_eq!(job.id, job_id);
+        assert_eq!((job.processed, job.status.as_str(), job.total), (processed, status, 5));
+        assert_eq!(job.completed_at.is_some(), status == "done");
+    }
+    assert!(dispatcher.run_batch().await.is_none());
+    assert_eq!(repo.get_outbox_job(job_id).await.unwrap().processed, 5);
+    for user_id in recipients {
+        assert_eq!(repo.get_notifications(user_id).await.len(), 1);
+    }
+}
+
+#[tokio::test]
+async fn test_fresh_dispatcher_resumes_without_duplicates() {
+    let (repo, job_id, recipients) = queued_fanout(6).await;
+
+    // The first dispatcher goes away after one batch, as if the process had died.
+    let crashed = OutboxDispatcher::new(repo.clone()).with_batch_size(3);
+    assert_eq!(crashed.run_batch().await.unwrap().processed, 3);
+    drop(crashed);
+
+    let resumed = OutboxDispatcher::new(repo.clone()).with_batch_size(3);
+    assert_eq!(resumed.drain().await, 2);
+    let job = repo.get_outbox_job(job_id).await.unwrap();
+    assert_eq!((job.status.as_str(), job.processed, job.total), ("done", 7, 7));
+    for user_id in recipients {
+        assert_eq!(repo.get_notifications(user_id).await.len(), 1, "{user_id}");
+    }
+    assert_eq!(resumed.drain().await, 0);
+}
+
+#[tokio::test]
+async fn test_dispatcher_pass_writes_queued_audit_entries() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let entry = NewAuditEntry::new(Some(Uuid::new_v4()), "comment.restore", "comment", 7);
+    let job_id = repo.queue_audit(&entry).await.unwrap();
+    let log = async || repo.get_audit_log(&AuditFilter::default(), PageRequest::default()).await.0;
+    assert!(log().await.is_empty());
+
+    let dispatcher = OutboxDispatcher::new(repo.clone());
+    dispatcher.run(chrono::Utc::now()).await.unwrap();
+    let written = log().await;
+    assert_eq!(written.len(), 1);
+    assert_eq!((written[0].action.as_str(), written[0].target_id.as_deref()), ("comment.restore", Some("7")));
+    let job = repo.get_outbox_job(job_id).await.unwrap();
+    assert_eq!((job.status.as_str(), job.processed), ("done", 1));
+    assert_eq!(written[0].created_at, job.created_at);
+
+    dispatcher.run(chrono::Utc::now()).await.unwrap();
+    assert_eq!(log().await.len(), 1);
+}
@@ -0,0 +1,113 @@
+//! Two-class database admission: reserved permits for admin and moderator requests.
+
+use fyp_portal::{
+    models::CreateProjectRequest,
+    repository::{
+        Repository,
+        memory::InMemoryRepository,
+        priority::{self, PriorityGate, PriorityRepository, RequestClass},
+    },
+};
+use futures::StreamExt;
+use std::{sync::Arc, time::Duration};
+use uuid::Uuid;
+
+#[test]
+fn test_only_admins_and_moderators_are_privileged() {
+    assert_eq!(RequestClass::for_role("admin"), RequestClass::Privileged);
+    assert_eq!(RequestClass::for_role("moderator"), RequestClass::Privileged);
+    assert_eq!(RequestClass::for_role("student"), RequestClass::Public);
+    assert_eq!(RequestClass::for_role(""), RequestClass::Public);
+}
+
+#[tokio::test]
+async fn test_roles_upgrade_only_the_running_request() {
+    assert_eq!(RequestClass::current(), RequestClass::Public);
+    priority::note_role("admin");
+    assert_eq!(RequestClass::current(), RequestClass::Public);
+
+    let class = priority::with_class(RequestClass::Public, async {
+        priority::note_role("student");
+        assert_eq!(RequestClass::current(), RequestClass::Public);
+        priority::note_role("admin");
+        // A later non-privileged role never downgrades the request.
+        priority::note_role("student");
+        RequestClass::current()
+    })
+    .await;
+    assert_eq!(class, RequestClass::Privileged);
+    assert_eq!(RequestClass::current(), RequestClass::Public);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_public_calls_never_take_reserved_permits() {
+    let gate = PriorityGate::new(3, 1);
+    let first = gate.acquire(RequestClass::Public).await;
+    let _second = gate.acquire(RequestClass::Public).await;
+
+    // Both shared permits are held; the reserved one stays free for privileged calls.
+    let waiting = tokio::time::timeout(Duration::from_secs(1), gate.acquire(RequestClass::Public));
+    tokio::pin!(waiting);
+    tokio::select! {
+        _ = &mut waiting => panic!("a public call took the reserved permit"),
+        _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+    }
+    assert_eq!(gate.queue_depth(RequestClass::Public), 1);
+    let admin = gate.acquire(RequestClass::Privileged).await;
+
+    // A released shared permit goes to the queued public call; the privileged one holds on.
+    drop(first);
+    assert!(waiting.await.is_ok());
+    assert_eq!(gate.queue_depth(RequestClass::Public), 0);
+    drop(admin);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_lookups_borrow_reserved_permits_only_within_their_budget() {
+    assert_eq!(priority::LOOKUP_RESERVED_BUDGET, 1);
+    let gate = PriorityGate::new(3, 2);
+    let _public = gate.acquire(RequestClass::Public).await;
+
+    // With the shared permit taken, one lookup may borrow a reserved permit ...
+    let first = gate.acquire(RequestClass::Lookup).await;
+    // ... but a second waits, and the last reserved permit stays with privileged calls.
+    let second = tokio::time::timeout(Duration::from_secs(1), gate.acquire(RequestClass::Lookup));
+    tokio::pin!(second);
+    tokio::select! {
+        _ = &mut second => panic!("a second lookup exceeded the budget"),
+        _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+    }
+    assert_eq!(gate.queue_depth(RequestClass::Lookup), 1);
+    let admin = gate.acquire(RequestClass::Privileged).await;
+    drop(admin);
+
+    drop(first);
+    assert!(second.await.is_ok());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_reservation_always_leaves_a_shared_permit() {
+    let gate = PriorityGate::new(1, 5);
+    let only = tokio::time::timeout(Duration::from_secs(1), gate.acquire(RequestClass::Public)).await;
+    assert!(only.is_ok());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_export_streams_hold_a_shared_permit_until_dropped() {
+    let memory = Arc::new(InMemoryRepository::new());
+    memory.create_project(CreateProjectRequest { title: "Exported".to_string(), ..Default::default() }, Uuid::new_v4()).await;
+    let repo = PriorityRepository::new(memory, 2, 1);
+    let gate = repo.gate().clone();
+
+    // Even when an admin starts it, the stream takes the only shared permit, not the reserved one.
+    let mut stream = priority::with_class(RequestClass::Privileged, async { repo.stream_all_projects() }).await;
+    assert!(stream.next().await.unwrap().is_ok());
+    let public = tokio::time::timeout(Duration::from_secs(1), gate.acquire(RequestClass::Public)).await;
+    assert!(public.is_err(), "the stream's permit was handed out");
+    let admin = tokio::time::timeout(Duration::from_secs(1), gate.acquire(RequestClass::Privileged)).await;
+    assert!(admin.is_ok());
+
+    drop(stream);
+    let public = tokio::time::timeout(Duration::from_secs(1), gate.acquire(RequestClass::Public)).await;
+    assert!(public.is_ok());
+}
@@ -0,0 +1,116 @@
+use fyp_portal::{
+    models::Project,
+    readiness::{self, MIN_ABSTRACT_WORDS},
+};
+
+// --- Helpers ---
+
+const COHORT: i32 = 2025;
+
+fn words(n: usize) -> String {
+    vec!["word"; n].join(" ")
+}
+
+/// A project that passes every check; individual tests break one rule at a time.
+fn ready_project() -> Project {
+    Project {
+        cover_image: "uploads/cover.png".to_string(),
+        abstract_text: words(MIN_ABSTRACT_WORDS),
+        video: Some("uploads/demo.mp4".to_string()),
+        links: serde_json::json!({ "repository": "https://github.com/example/fyp" }),
+        year: COHORT,
+        ..Project::default()
+    }
+}
+
+// --- Tests ---
+
+#[test]
+fn test_ready_project_passes_every_check() {
+    let report = readiness::evaluate(&ready_project(), true, COHORT);
+    assert!(report.ready);
+    assert_eq!(report.checks.len(), 5);
+    assert!(report.checks.iter().all(|c| c.passed));
+}
+
+#[test]
+fn test_cover_image_must_be_set_and_in_storage() {
+    let mut project = ready_project();
+    assert!(readiness::check_cover_image(&project, true).passed);
+    assert!(!readiness::check_cover_image(&project, false).passed);
+
+    project.cover_image = "   ".to_string();
+    assert!(!readiness::check_cover_image(&project, true).passed);
+}
+
+#[test]
+fn test_abstract_word_count_boundary() {
+    let mut project = ready_project();
+
+    project.abstract_text = words(MIN_ABSTRACT_WORDS - 1);
+    let check = readiness::check_abstract_length(&project);
+    assert!(!check.passed);
+    assert!(check.detail.contains("99"));
+
+    project.abstract_text = words(MIN_ABSTRACT_WORDS);
+    assert!(readiness::check_abstract_length(&project).passed);
+
+    // Extra whitespace and newlines do not inflate the count.
+    project.abstract_text = format!("  {}  \n\n ", words(MIN_ABSTRACT_WORDS - 1));
+    assert!(!readiness::check_abstract_length(&project).passed);
+}
+
+#[test]
+fn test_video_present_or_waived() {
+    let mut project = ready_project();
+    assert!(readiness::check_video(&project).passed);
+
+    project.video = None;
+    assert!(!readiness::check_video(&project).passed);
+
+    project.video = Some(String::new());
+    assert!(!readiness::check_video(&project).passed);
+
+    project.video_waived = true;
+    assert!(readiness::check_video(&project).passed);
+}
+
+#[test]
+fn test_at_least_one_non_empty_link() {
+    let mut project = ready_project();
+    assert!(readiness::check_links(&project).passed);
+
+    project.links = serde_json::json!({});
+    assert!(!readiness::check_links(&project).passed);
+
+    project.links = serde_json::json!({ "repository": "  " });
+    assert!(!readiness::check_links(&project).passed);
+
+    project.links = serde_json::Value::Null;
+    assert!(!readiness::check_links(&project).passed);
+}
+
+#[test]
+fn test_year_must_match_current_cohort() {
+    let mut project = ready_project();
+    assert!(readiness::check_cohort_year(&project, COHORT).passed);
+
+    project.year = COHORT - 1;
+    assert!(!readiness::check_cohort_year(&project, COHORT).passed);
+
+    project.year = COHORT + 1;
+    assert!(!readiness::check_cohort_year(&project, COHORT).passed);
+}
+
+#[test]
+fn test_single_failure_makes_report_not_ready() {
+    let project = Project {
+        video: None,
+        ..ready_project()
+    };
+    let report = readiness::evaluate(&project, true, COHORT);
+    assert!(!report.ready);
+    let failed: Vec<_> = report.checks.iter().filter(|c| !c.passed).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].name, readiness::CHECK_VIDEO);
+}
@@ -0,0 +1,1968 @@
+//! Runs the same assertions against `PostgresRepository` and `InMemoryRepository`, so the
+//! in-memory implementation used by fast handler tests cannot drift from the real one.
+//!
+//! The Postgres database is shared with other test binaries, so every check works on rows it
+//! created itself (fresh users, projects and a unique search tag) rather than global counts.
+
+use chrono::Utc;
+use fyp_portal::{
+    models::{NewAuditEntry, NewNotification, ProjectStatus, PublicEventKind, ReviewState, ActivityItem, ResubmitOutcome, CommentEditOutcome, CommentOrder, CreateProjectRequest, Like, LikeOutcome, ProjectOwner, ProjectSort, SubscriptionState, TagCount, UpdateCommentRequest, UpdateProjectRequest, UploadPurpose, User, YearCount},
+    pagination::{Cursor, PageRequest},
+    repository::{AdminCommentFilter, AuditFilter, CommentCursor, DraftSweep, HiddenComments, PostgresRepository, ProjectListFilter, ProjectViewer, Repository, memory::InMemoryRepository},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// One repository under test, plus how to seed a user for it.
+struct Backend {
+    repo: Box<dyn Repository>,
+    // Postgres profiles reference `auth.users`, which the repository does not write.
+    pool: Option<PgPool>,
+}
+
+impl Backend {
+    async fn postgres() -> Self {
+        dotenv::dotenv().ok();
+        let db_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set to run integration tests");
+        let pool = PgPool::connect(&db_url).await.expect("Failed to connect to database for integration tests.");
+        sqlx::migrate!("./migrations").run(&pool).await.expect("Failed to run database migrations.");
+        Backend {
+            repo: Box::new(PostgresRepository::new(pool.clone())),
+            pool: Some(pool),
+        }
+    }
+
+    fn in_memory() -> Self {
+        Backend {
+            repo: Box::new(InMemoryRepository::new()),
+            pool: None,
+        }
+    }
+
+    async fn user(&self, role: &str) -> User {
+        let id = Uuid::new_v4();
+        let email = format!("{}-{}@test.com", role, id.simple());
+        if let Some(pool) = &self.pool {
+            sqlx::query!("INSERT INTO auth.users (id, email) VALUES ($1, $2)", id, email)
+                .execute(pool)
+                .await
+                .expect("Failed to seed auth user");
+        }
+        self.repo.create_user(User { id, email, role: role.to_string() }).await
+    }
+}
+
+/// A project request whose title carries `tag`, so listings can be scoped to this test.
+fn project(tag: &str, title: &str) -> CreateProjectRequest {
+    CreateProjectRequest {
+        title: format!("{title} {tag}"),
+        abstract_text: "Abstract".to_string(),
+        author_name: "Author".to_string(),
+        year: 2024,
+        cover_image_key: format!("covers/{}", Uuid::new_v4()),
+        ..CreateProjectRequest::default()
+    }
+}
+
+fn tagged(tag: &str) -> ProjectListFilter {
+    ProjectListFilter {
+        search: Some(tag.to_string()),
+        ..ProjectListFilter::default()
+    }
+}
+
+fn new_tag() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// Declares one `#[tokio::test]` per backend for each check, as `<check>::postgres` and
+/// `<check>::in_memory`.
+macro_rules! conformance {
+    ($($check:ident),* $(,)?) => {$(
+        mod $check {
+            #[tokio::test]
+            async fn postgres() {
+                super::$check(&super::Backend::postgres().await).await;
+            }
+
+            #[tokio::test]
+            async fn in_memory() {
+                super::$check(&super::Backend::in_memory()).await;
+            }
+        }
+    )*};
+}
+
+conformance!(
+    visibility_and_pagination,
+    project_statuses,
+    bulk_statuses,
+    project_export_rows,
+    my_project_summaries,
+    public_events,
+    project_reviews,
+    project_resubmission,
+    cursor_pages_survive_inserts,
+    ownership_checks,
+    update_reports_changed_fields,
+    view_counts,
+    project_slugs,
+    duplicate_titles,
+    likes_are_idempotent_and_notify,
+    top_projects_threshold_and_ties,
+    random_public_project,
+    project_likers,
+    listing_sort_orders,
+    comments_join_authors_and_notify_participants,
+    comment_pages,
+    comment_edits,
+    reported_comment_queue,
+    admin_comment_listing,
+    comment_fanout_threshold,
+    reports_hide_and_restore,
+    project_comment_counts,
+    notifications_are_enriched_and_owned,
+    users_by_email_prefix,
+    project_scoped_comment_delete,
+    soft_deleted_comments,
+    discussion_lock,
+    upload_counts,
+    programme_membership,
+    project_tags,
+    project_years,
+    invalid_years,
+    user_activity,
+    settings_upsert,
+    anonymous_feedback,
+    supervisor_filter,
+    author_filter,
+    department_filter,
+    technology_filter,
+    project_detail_owner,
+    stale_draft_sweeps,
+    public_read_paths_share_one_visibility_rule,
+    audit_log,
+);
+
+async fn visibility_and_pagination(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let stranger = backend.user("student").await;
+    let tag = new_tag();
+
+    let draft = repo.create_project(project(&tag, "Draft"), owner.id).await;
+    assert!(!draft.is_public);
+    let (items, total) = repo.get_projects(tagged(&tag), PageRequest::default()).await;
+    assert!(items.is_empty());
+    assert_eq!(total, 0);
+    assert!(repo.get_public_project(draft.id).await.is_none());
+    assert!(repo.get_project_authorized(draft.id, stranger.id).await.is_none());
+    assert_eq!(repo.get_project_authorized(draft.id, owner.id).await.map(|p| p.id), Some(draft.id));
+    assert_eq!(repo.get_my_project_summaries(owner.id).await.len(), 1);
+
+    let mut published = Vec::new();
+    for i in 0..3 {
+        let p = repo.create_project(project(&tag, &format!("Public {i}")), owner.id).await;
+        repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+        published.push(p.id);
+    }
+
+    let page = |page| PageRequest { page, per_page: 2, after: None };
+    let (first, total) = repo.get_projects(tagged(&tag), page(1)).await;
+    assert_eq!(total, 3);
+    assert_eq!(first.len(), 2);
+    let (second, total) = repo.get_projects(tagged(&tag), page(2)).await;
+    assert_eq!(total, 3);
+    assert_eq!(second.len(), 1);
+
+    // Newest first, and the draft is never listed.
+    let listed: Vec<_> = first.iter().chain(&second).map(|p| p.id).collect();
+    published.reverse();
+    assert_eq!(listed, published);
+    assert!(repo.get_public_project(published[0]).await.is_some());
+}
+
+async fn project_statuses(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    let listed = async || repo.get_projects(tagged(&tag), PageRequest::default()).await.0.len();
+
+    let p = repo.create_project(project(&tag, "Status"), owner.id).await;
+    assert_eq!(p.status, ProjectStatus::Draft);
+    let submitted = repo.mark_project_submitted(p.id).await.unwrap();
+    assert_eq!(submitted.status, ProjectStatus::Pending);
+    assert!(!submitted.is_public);
+    assert_eq!(listed().await, 0);
+
+    // Only `approved` is public, and `is_public` follows the status both ways.
+    for (status, public) in [
+        (ProjectStatus::Approved, true),
+        (ProjectStatus::Rejected, false),
+        (ProjectStatus::Approved, true),
+        (ProjectStatus::Pending, false),
+    ] {
+        let updated = repo.set_project_status(p.id, status).await.unwrap();
+        assert_eq!((updated.status, updated.is_public), (status, public));
+        assert_eq!(repo.get_public_project(p.id).await.is_some(), public);
+        assert_eq!(listed().await, usize::from(public));
+    }
+
+    // The owner sees every status.
+    let draft = repo.create_project(project(&tag, "Draft"), owner.id).await;
+    repo.set_project_status(draft.id, ProjectStatus::Rejected).await.unwrap();
+    let mut mine: Vec<_> = repo.get_my_project_summaries(owner.id).await.into_iter().map(|s| (s.project.id, s.project.status)).collect();
+    mine.sort_by_key(|(id, _)| *id);
+    let mut expected = vec![(p.id, ProjectStatus::Pending), (draft.id, ProjectStatus::Rejected)];
+    expected.sort_by_key(|(id, _)| *id);
+    assert_eq!(mine, expected);
+    assert!(repo.set_project_status(Uuid::new_v4(), ProjectStatus::Approved).await.is_none());
+
+    // The dashboard counts the review queue, not every non-public project. Global counts are
+    // only exact on a backend this test owns.
+    if backend.pool.is_none() {
+        repo.create_project(project(&tag, "Unsubmitted"), owner.id).await;
+        let stats = repo.get_stats().await;
+        assert_eq!(stats.pending_reviews, 1);
+        assert_eq!(stats.pending_reviews, repo.get_pending_reviews(i64::MAX).await.len() as i64);
+    }
+}
+
+async fn bulk_statuses(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    let draft = repo.create_project(project(&tag, "Bulk Draft"), owner.id).await;
+    let approved = repo.create_project(project(&tag, "Bulk Approved"), owner.id).await;
+    repo.set_project_status(approved.id, ProjectStatus::Approved).await.unwrap();
+    let unknown = Uuid::new_v4();
+
+    // Rows already in the target status are found but not changed.
+    let outcome = repo.set_projects_status_bulk(&[draft.id, approved.id, unknown], true).await;
+    assert_eq!((outcome.changed, outcome.not_found), (vec![draft.id], vec![unknown]));
+    let listed = repo.get_projects(tagged(&tag), PageRequest::default()).await.0.len();
+    assert_eq!(listed, 2);
+
+    let outcome = repo.set_projects_status_bulk(&[draft.id, approved.id], false).await;
+    assert_eq!(outcome.changed.len(), 2);
+    assert!(outcome.not_found.is_empty());
+    assert_eq!(repo.get_project(draft.id).await.unwrap().status, ProjectStatus::Rejected);
+    assert!(repo.get_public_project(approved.id).await.is_none());
+}
+
+async fn project_export_rows(backend: &Backend) {
+    use futures::TryStreamExt;
+
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let fans = [backend.user("student").await, backend.user("student").await];
+    let tag = new_tag();
+    let first = repo.create_project(project(&tag, "Exported First"), owner.id).await;
+    let second = repo.create_project(project(&tag, "Exported Second"), owner.id).await;
+    repo.set_project_status(second.id, ProjectStatus::Approved).await.unwrap();
+    for user_id in fans.map(|fan| fan.id) {
+        repo.like_project(Like { user_id, project_id: second.id }).await;
+    }
+
+    // Drafts are exported too, oldest first, with the owner's email from their profile.
+    let rows: Vec<_> = repo.stream_project_export().try_collect().await.unwrap();
+    let ours: Vec<_> = rows
+        .iter()
+        .filter(|row| [first.id, second.id].contains(&row.id))
+        .map(|row| (row.id, row.status, row.like_count, row.owner_email.clone()))
+        .collect();
+    assert_eq!(
+        ours,
+        vec![
+            (first.id, ProjectStatus::Draft, 0, Some(owner.email.clone())),
+            (second.id, ProjectStatus::Approved, 2, Some(owner.email.clone())),
+        ]
+    );
+}
+
+async fn my_project_summaries(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let fan = backend.user("student").await;
+    let tag = new_tag();
+    let older = repo.create_project(project(&tag, "Summarised Draft"), owner.id).await;
+    let newer = repo.create_project(project(&tag, "Summarised Public"), owner.id).await;
+    repo.set_project_status(newer.id, ProjectStatus::Approved).await.unwrap();
+    let other_fan = backend.user("student").await;
+    for user_id in [other_fan.id, fan.id] {
+        repo.like_project(Like { user_id, project_id: newer.id }).await;
+    }
+    repo.add_comment(newer.id, fan.id, "Great demo".to_string(), 500).await.unwrap().unwrap();
+
+    let summaries: Vec<_> = repo
+        .get_my_project_summaries(owner.id)
+        .await
+        .into_iter()
+        .map(|s| (s.project.id, s.review_state, s.like_count, s.project.comment_count, s.latest_review.is_some()))
+        .collect();
+    assert_eq!(
+        summaries,
+        vec![
+            (newer.id, ReviewState::Approved, 2, 1, false),
+            (older.id, ReviewState::Pending, 0, 0, false),
+        ]
+    );
+    assert!(repo.get_my_project_summaries(fan.id).await.is_empty());
+}
+
+async fn public_events(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let since = Utc::now() - chrono::Duration::minutes(1);
+    let ours = async |id: Uuid| {
+        let events = repo.get_public_events(since, 100_000).await;
+        events
+            .into_iter()
+            .filter(|event| event.project_id == id)
+            .map(|event| (event.kind, event.project.map(|p| p.id)))
+            .collect::<Vec<_>>()
+    };
+
+    // Moves between private states are not events; each visibility flip is one.
+    let p = repo.create_project(project(&new_tag(), "Mirrored"), owner.id).await;
+    repo.mark_project_submitted(p.id).await.unwrap();
+    for status in [ProjectStatus::Approved, ProjectStatus::Rejected, ProjectStatus::Pending, ProjectStatus::Approved] {
+        repo.set_project_status(p.id, status).await.unwrap();
+    }
+    repo.set_projects_status_bulk(&[p.id], false).await;
+    repo.set_projects_status_bulk(&[p.id], true).await;
+    let (published, unpublished) = (PublicEventKind::Published, PublicEventKind::Unpublished);
+    assert_eq!(
+        ours(p.id).await,
+        vec![
+            (published, Some(p.id)),
+            (unpublished, None),
+            (published, Some(p.id)),
+            (unpublished, None),
+            (published, Some(p.id)),
+        ]
+    );
+
+    // Deleting a public project unpublishes it, and earlier events stop carrying it.
+    assert!(repo.delete_project_admin(p.id).await);
+    let events = ours(p.id).await;
+    assert_eq!(events.len(), 6);
+    assert!(events.iter().all(|(_, project)| project.is_none()));
+    assert_eq!(events[5].0, unpublished);
+
+    // Deleting a private project is not an event.
+    let private = repo.create_project(project(&new_tag(), "Never Public"), owner.id).await;
+    assert!(repo.delete_project_admin(private.id).await);
+    assert!(ours(private.id).await.is_empty());
+}
+
+async fn project_reviews(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let admin = backend.user("admin").await;
+    let tag = new_tag();
+
+    let first = repo.create_project(project(&tag, "Reviewed"), owner.id).await;
+    let second = repo.create_project(project(&tag, "Also Reviewed"), owner.id).await;
+    let unreviewed = repo.create_project(project(&tag, "Unreviewed"), owner.id).await;
+    assert!(repo.get_latest_reviews(owner.id).await.is_empty());
+
+    assert!(repo.record_project_review(first.id, admin.id, ProjectStatus::Rejected, Some("No consent form".to_string())).await);
+    assert!(repo.record_project_review(first.id, admin.id, ProjectStatus::Rejected, Some("Still no consent form".to_string())).await);
+    assert!(repo.record_project_review(second.id, admin.id, ProjectStatus::Approved, None).await);
+    assert!(!repo.record_project_review(Uuid::new_v4(), admin.id, ProjectStatus::Approved, None).await);
+
+    // The newest review per project, only for the owner's own projects.
+    let mut latest: Vec<_> = repo
+        .get_latest_reviews(owner.id)
+        .await
+        .into_iter()
+        .map(|r| (r.project_id, r.status, r.reason))
+        .collect();
+    latest.sort_by_key(|(id, ..)| *id);
+    let mut expected = vec![
+        (first.id, ProjectStatus::Rejected, Some("Still no consent form".to_string())),
+        (second.id, ProjectStatus::Approved, None),
+    ];
+    expected.sort_by_key(|(id, ..)| *id);
+    assert_eq!(latest, expected);
+    assert!(latest.iter().all(|(id, ..)| *id != unreviewed.id));
+    assert!(repo.get_latest_reviews(admin.id).await.is_empty());
+}
+
+async fn project_resubmission(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let other = backend.user("student").await;
+    let admin = backend.user("admin").await;
+    let tag = new_tag();
+    let p = repo.create_project(project(&tag, "Resubmitted"), owner.id).await;
+    let status_of = |outcome: Option<ResubmitOutcome>| match outcome {
+        Some(ResubmitOutcome::NotRejected(status)) => Some(status),
+        other => panic!("expected NotRejected, got {other:?}"),
+    };
+
+    // Drafts, pending and approved projects cannot be resubmitted.
+    assert_eq!(status_of(repo.resubmit_project(p.id, owner.id, 2).await), Some(ProjectStatus::Draft));
+    repo.mark_project_submitted(p.id).await.unwrap();
+    assert_eq!(status_of(repo.resubmit_project(p.id, owner.id, 2).await), Some(ProjectStatus::Pending));
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    assert_eq!(status_of(repo.resubmit_project(p.id, owner.id, 2).await), Some(ProjectStatus::Approved));
+
+    // Only the owner may resubmit a rejected project; anyone else sees nothing.
+    repo.set_project_status(p.id, ProjectStatus::Rejected).await.unwrap();
+    repo.record_project_review(p.id, admin.id, ProjectStatus::Rejected, Some("No video".to_string())).await;
+    assert!(repo.resubmit_project(p.id, other.id, 2).await.is_none());
+    assert!(repo.resubmit_project(Uuid::new_v4(), owner.id, 2).await.is_none());
+    assert_eq!(repo.get_project(p.id).await.unwrap().status, ProjectStatus::Rejected);
+
+    let before = repo.get_project(p.id).await.unwrap().updated_at;
+    let Some(ResubmitOutcome::Resubmitted(resubmitted)) = repo.resubmit_project(p.id, owner.id, 2).await else {
+        panic!("a rejected project can be resubmitted by its owner")
+    };
+    assert_eq!(resubmitted.status, ProjectStatus::Pending);
+    assert!(!resubmitted.is_public);
+    assert!(resubmitted.updated_at > before);
+    assert!(resubmitted.submitted_at.is_some());
+    // The rejection reason is no longer the latest review.
+    let latest = repo.get_latest_reviews(owner.id).await;
+    assert_eq!(latest.len(), 1);
+    assert_eq!((latest[0].status, latest[0].reason.clone()), (ProjectStatus::Pending, None));
+    let admin_notes = repo.get_notifications(admin.id).await;
+    assert_eq!(admin_notes.len(), 1);
+    assert_eq!((admin_notes[0].notification_type.as_str(), admin_notes[0].project_id), ("resubmission", p.id));
+    assert!(repo.get_notifications(owner.id).await.is_empty());
+
+    // The second attempt uses up the cap; a third rejection cannot be resubmitted.
+    repo.set_project_status(p.id, ProjectStatus::Rejected).await.unwrap();
+    assert!(matches!(repo.resubmit_project(p.id, owner.id, 2).await, Some(ResubmitOutcome::Resubmitted(_))));
+    repo.set_project_status(p.id, ProjectStatus::Rejected).await.unwrap();
+    assert!(matches!(repo.resubmit_project(p.id, owner.id, 2).await, Some(ResubmitOutcome::LimitReached)));
+    assert_eq!(repo.get_project(p.id).await.unwrap().status, ProjectStatus::Rejected);
+    assert_eq!(repo.get_notifications(admin.id).await.len(), 2);
+}
+
+async fn cursor_pages_survive_inserts(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await.id;
+    let tag = &new_tag();
+    let publish = |title: String| async move {
+        let request = CreateProjectRequest {
+            year: 2025,
+            ..project(tag, &title)
+        };
+        let p = repo.create_project(request, owner).await;
+        repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap().id
+    };
+
+    let mut expected = Vec::new();
+    for i in 0..6 {
+        expected.push(publish(format!("Existing {i}")).await);
+    }
+    expected.reverse();
+    // Same search, other year: the year filter must keep it out of every page.
+    let other_year = repo.create_project(project(tag, "Other year"), owner).await;
+    repo.set_project_status(other_year.id, ProjectStatus::Approved).await.unwrap();
+
+    let filter = ProjectListFilter {
+        year: Some(2025),
+        ..tagged(tag)
+    };
+    let mut page = PageRequest { per_page: 2, ..PageRequest::default() };
+    let mut seen = Vec::new();
+    for _ in 0..3 {
+        let (items, total) = repo.get_projects(filter.clone(), page).await;
+        seen.extend(items.iter().map(|p| p.id));
+        // A project approved mid-scroll lands before the cursor, not in the next page.
+        let fresh = publish("Fresh".to_string()).await;
+        assert!(total >= 6);
+        page.after = Some(Cursor::after(items.last().unwrap()));
+        assert_ne!(seen.last(), Some(&fresh));
+    }
+    assert_eq!(seen, expected, "three pages, no duplicates or gaps");
+
+    let (rest, _) = repo.get_projects(filter, page).await;
+    assert!(rest.is_empty());
+}
+
+async fn ownership_checks(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let stranger = backend.user("student").await;
+    let created = repo.create_project(project(&new_tag(), "Owned"), owner.id).await;
+
+    let rename = UpdateProjectRequest {
+        title: Some("Renamed".to_string()),
+        ..UpdateProjectRequest::default()
+    };
+    assert!(repo.update_project(created.id, stranger.id, rename.clone()).await.is_none());
+    assert!(!repo.delete_project(created.id, stranger.id).await);
+
+    let updated = repo.update_project(created.id, owner.id, rename).await.unwrap().project;
+    assert_eq!(updated.title, "Renamed");
+    // Absent fields are left as they were.
+    assert_eq!(updated.abstract_text, created.abstract_text);
+    assert_eq!(updated.cover_image, created.cover_image);
+
+    assert!(repo.delete_project(created.id, owner.id).await);
+    assert!(repo.get_project(created.id).await.is_none());
+    assert!(!repo.delete_project_admin(created.id).await);
+}
+
+async fn update_reports_changed_fields(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let created = repo.create_project(project(&new_tag(), "Patched"), owner.id).await;
+
+    // Re-sending stored values, even padded, changes nothing and leaves `updated_at` alone.
+    let same = UpdateProjectRequest {
+        title: Some(format!("  {}  ", created.title)),
+        abstract_text: Some(created.abstract_text.clone()),
+        tags: Some(vec![]),
+        ..UpdateProjectRequest::default()
+    };
+    let unchanged = repo.update_project(created.id, owner.id, same).await.unwrap();
+    assert!(unchanged.changed_fields.is_empty());
+    assert_eq!(unchanged.project.updated_at, created.updated_at);
+
+    let patch = UpdateProjectRequest {
+        title: Some("Patched again".to_string()),
+        abstract_text: Some(created.abstract_text.clone()),
+        supervisor: Some("Dr Jane Smith".to_string()),
+        tags: Some(vec!["rust".to_string()]),
+        ..UpdateProjectRequest::default()
+    };
+    let changed = repo.update_project(created.id, owner.id, patch).await.unwrap();
+    assert_eq!(changed.changed_fields, vec!["title", "supervisor", "tags"]);
+    assert_eq!(changed.project.title, "Patched again");
+    assert_eq!(changed.project.abstract_text, created.abstract_text);
+    assert_eq!(changed.project.tags, vec!["rust"]);
+}
+
+async fn view_counts(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let created = repo.create_project(project(&new_tag(), "Viewed"), owner.id).await;
+    assert_eq!(created.view_count, 0);
+
+    // Drafts and unknown ids are not counted.
+    assert!(repo.increment_view(created.id).await.is_none());
+    assert!(repo.increment_view(Uuid::new_v4()).await.is_none());
+
+    let published = repo.set_project_status(created.id, ProjectStatus::Approved).await.unwrap();
+    assert_eq!(repo.increment_view(created.id).await, Some(1));
+    assert_eq!(repo.increment_view(created.id).await, Some(2));
+    let viewed = repo.get_public_project(created.id).await.unwrap();
+    assert_eq!(viewed.view_count, 2);
+    // A view is not an edit, so "changed since" checks ignore it.
+    assert_eq!(viewed.updated_at, published.updated_at);
+}
+
+async fn project_slugs(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    let original = format!("slug-test-{tag}-2024");
+    let rename = |title: &str| UpdateProjectRequest {
+        title: Some(format!("{title} {tag}")),
+        ..UpdateProjectRequest::default()
+    };
+
+    let first = repo.create_project(project(&tag, "Slug: Test!"), owner.id).await;
+    assert_eq!(first.slug, original);
+    let second = repo.create_project(project(&tag, "Slug Test"), owner.id).await;
+    assert_eq!(second.slug, format!("{original}-2"));
+
+    // Renaming moves the project to a new slug; the old one still finds it and is not reused.
+    let renamed = repo.update_project(first.id, owner.id, rename("Renamed")).await.unwrap().project;
+    assert_eq!(renamed.slug, format!("renamed-{tag}-2024"));
+    assert_eq!(repo.get_project(first.id).await.unwrap().slug, renamed.slug);
+    assert_eq!(repo.resolve_project_slug(&renamed.slug).await, Some(first.id));
+    assert_eq!(repo.resolve_project_slug(&original).await, Some(first.id));
+    let third = repo.create_project(project(&tag, "Slug Test"), owner.id).await;
+    assert_eq!(third.slug, format!("{original}-3"));
+
+    // Renaming back reclaims the project's own earlier slug; edits that keep the title keep it.
+    let restored = repo.update_project(first.id, owner.id, rename("Slug: Test!")).await.unwrap().project;
+    assert_eq!(restored.slug, original);
+    let edited = UpdateProjectRequest {
+        abstract_text: Some("Edited".to_string()),
+        ..UpdateProjectRequest::default()
+    };
+    assert_eq!(repo.update_project(first.id, owner.id, edited).await.unwrap().project.slug, original);
+
+    assert_eq!(repo.resolve_project_slug(&second.slug).await, Some(second.id));
+    assert_eq!(repo.resolve_project_slug(&format!("unknown-{tag}-2024")).await, None);
+}
+
+async fn duplicate_titles(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let partner = backend.user("student").await;
+    let tag = new_tag();
+    let find = |title: String, year| async move { repo.find_project_by_title_year(&title, year).await };
+    assert_eq!(find(format!("Twice {tag}"), 2024).await, None);
+
+    let first = repo.create_project(project(&tag, "Twice"), owner.id).await;
+    let second = repo.create_project(project(&tag, "Twice"), partner.id).await;
+    // The oldest match, whoever owns it; case and whitespace do not matter, the year does.
+    assert_eq!(find(format!("Twice {tag}"), 2024).await, Some(first.id));
+    assert_eq!(find(format!("  tWICE \t {}  ", tag.to_uppercase()), 2024).await, Some(first.id));
+    assert_eq!(find(format!("Twice {tag}"), 2023).await, None);
+    assert_eq!(find(format!("Twice {tag} again"), 2024).await, None);
+
+    assert!(repo.delete_project(first.id, owner.id).await);
+    assert_eq!(find(format!("Twice {tag}"), 2024).await, Some(second.id));
+}
+
+async fn likes_are_idempotent_and_notify(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let fan = backend.user("student").await;
+    let other_fan = backend.user("student").await;
+    let admin = backend.user("admin").await;
+    let p = repo.create_project(project(&new_tag(), "Liked"), owner.id).await;
+
+    // Until it is approved the project answers like a missing one, its owner included.
+    let like = |user_id| Like { user_id, project_id: p.id };
+    assert_eq!(repo.like_project(like(fan.id)).await, None);
+    assert_eq!(repo.like_project(like(owner.id)).await, None);
+    assert!(repo.get_notifications(owner.id).await.is_empty());
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+
+    assert_eq!(repo.like_project(like(fan.id)).await, Some(LikeOutcome::Created(1)));
+    assert_eq!(repo.like_project(like(fan.id)).await, Some(LikeOutcome::AlreadyExists(1)));
+    assert_eq!(repo.like_project(like(other_fan.id)).await, Some(LikeOutcome::Created(2)));
+    assert_eq!(repo.like_project(Like { user_id: fan.id, project_id: Uuid::new_v4() }).await, None);
+
+    // Owners cannot like their own project, admins included; nothing is written.
+    assert_eq!(repo.like_project(like(owner.id)).await, Some(LikeOutcome::OwnProject(2)));
+    let own = repo.create_project(project(&new_tag(), "Admin's own"), admin.id).await;
+    repo.set_project_status(own.id, ProjectStatus::Approved).await.unwrap();
+    let own_like = Like { user_id: admin.id, project_id: own.id };
+    assert_eq!(repo.like_project(own_like).await, Some(LikeOutcome::OwnProject(0)));
+    assert!(repo.get_liked_project_ids(owner.id, &[p.id]).await.is_empty());
+
+    // One notification per fan's like.
+    let notifications = repo.get_notifications(owner.id).await;
+    assert_eq!(notifications.len(), 2);
+    assert!(notifications.iter().all(|n| n.notification_type == "like"));
+    assert!(notifications.iter().any(|n| n.actor_email == fan.email));
+
+    // `liked_by_me` lookups answer for the requested projects only.
+    let unliked = repo.create_project(project(&new_tag(), "Unliked"), owner.id).await;
+    assert_eq!(repo.get_liked_project_ids(fan.id, &[unliked.id, p.id]).await, vec![p.id]);
+    assert!(repo.get_liked_project_ids(fan.id, &[]).await.is_empty());
+
+    // Taking the like back removes its notification too; the other fan's like stays.
+    assert!(repo.unlike_project(like(fan.id)).await);
+    assert!(!repo.unlike_project(like(fan.id)).await);
+    let notifications = repo.get_notifications(owner.id).await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].actor_email, other_fan.email);
+    assert!(repo.get_liked_project_ids(fan.id, &[p.id]).await.is_empty());
+
+    // The public like total: hidden projects answer like missing ones, and taking a project
+    // down keeps its likes for when it is back.
+    assert_eq!(repo.get_like_count(p.id).await, Some(1));
+    repo.set_project_status(p.id, ProjectStatus::Draft).await.unwrap();
+    assert_eq!(repo.get_like_count(p.id).await, None);
+    assert_eq!(repo.like_project(like(fan.id)).await, None);
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    assert_eq!(repo.get_like_count(p.id).await, Some(1));
+    assert_eq!(repo.get_like_count(Uuid::new_v4()).await, None);
+
+    // Referenced by likes, the project cannot be deleted.
+    assert!(!repo.delete_project_admin(p.id).await);
+}
+
+async fn project_likers(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let p = repo.create_project(project(&new_tag(), "Popular"), owner.id).await;
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    let mut fans = Vec::new();
+    for _ in 0..3 {
+        let fan = backend.user("student").await;
+        repo.like_project(Like { user_id: fan.id, project_id: p.id }).await.unwrap();
+        fans.push(fan);
+    }
+
+    let page = |page| PageRequest { page, per_page: 2, after: None };
+    let (first, total) = repo.get_project_likers(p.id, page(1)).await;
+    let (second, _) = repo.get_project_likers(p.id, page(2)).await;
+    assert_eq!(total, 3);
+    assert_eq!((first.len(), second.len()), (2, 1));
+    // Most recent like first; the pages neither overlap nor skip anyone.
+    let all: Vec<_> = first.iter().chain(&second).collect();
+    assert!(all.windows(2).all(|w| w[0].liked_at >= w[1].liked_at));
+    let mut ids: Vec<Uuid> = all.iter().map(|l| l.id).collect();
+    ids.sort();
+    let mut expected: Vec<Uuid> = fans.iter().map(|f| f.id).collect();
+    expected.sort();
+    assert_eq!(ids, expected);
+    let fan = all.iter().find(|l| l.id == fans[0].id).unwrap();
+    assert_eq!(fan.email.as_deref(), Some(fans[0].email.as_str()));
+
+    assert_eq!(repo.get_project_likers(Uuid::new_v4(), page(1)).await, (vec![], 0));
+}
+
+async fn random_public_project(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    let hidden = repo.create_project(project(&tag, "Hidden"), owner.id).await;
+    for title in ["A", "B"] {
+        let p = repo.create_project(project(&tag, title), owner.id).await;
+        repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    }
+
+    // Other tests' projects share the database, so only visibility is asserted.
+    for seed in [None, None, None, Some(1), Some(2), Some(-7)] {
+        let picked = repo.get_random_public_project(seed).await.expect("public projects exist");
+        assert!(picked.is_public);
+        assert_ne!(picked.id, hidden.id);
+    }
+}
+
+async fn top_projects_threshold_and_ties(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let mut fans = Vec::new();
+    for _ in 0..4 {
+        fans.push(backend.user("student").await.id);
+    }
+    let tag = new_tag();
+    // A year unique to this run, so the year filter sees only these projects.
+    let year = 5000 + (Uuid::new_v4().as_u128() % 100_000) as i32 * 3;
+
+    // Created oldest to newest; A-C tie on three likes. E is from the following year.
+    let mut ids = Vec::new();
+    for (title, likes) in [("A", 3), ("B", 3), ("C", 3), ("D", 4), ("E", 2)] {
+        let cohort = if title == "E" { year + 1 } else { year };
+        let p = repo.create_project(CreateProjectRequest { year: cohort, ..project(&tag, title) }, owner.id).await;
+        repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+        for fan in &fans[..likes] {
+            repo.like_project(Like { user_id: *fan, project_id: p.id }).await;
+        }
+        ids.push(p.id);
+    }
+    let [a, b, c, d, e] = ids[..] else { unreachable!() };
+
+    // Other tests' projects share the database; only the relative order of ours is asserted.
+    let ranked = |projects: Vec<fyp_portal::models::Project>| {
+        projects.into_iter().map(|p| p.id).filter(|id| ids.contains(id)).collect::<Vec<_>>()
+    };
+    assert_eq!(ranked(repo.get_top_projects(1000, 3, None).await), vec![d, c, b, a]);
+    assert_eq!(ranked(repo.get_top_projects(1000, 2, None).await), vec![d, c, b, a, e]);
+    assert!(ranked(repo.get_top_projects(1000, 5, None).await).is_empty());
+
+    // Filtered to a year only these projects use, so the results can be compared whole.
+    let ids_of = |projects: Vec<fyp_portal::models::Project>| projects.into_iter().map(|p| p.id).collect::<Vec<_>>();
+    assert_eq!(ids_of(repo.get_top_projects(2, 0, Some(year)).await), vec![d, c]);
+    assert_eq!(ids_of(repo.get_top_projects(10, 0, Some(year + 1)).await), vec![e]);
+    assert!(repo.get_top_projects(10, 3, Some(year + 1)).await.is_empty());
+
+    // The archive is the same ranking, whole, with unliked projects and nothing else.
+    assert_eq!(ids_of(repo.get_year_archive(year).await), vec![d, c, b, a]);
+    assert_eq!(ids_of(repo.get_year_archive(year + 1).await), vec![e]);
+    assert!(repo.get_year_archive(year + 2).await.is_empty());
+}
+
+async fn listing_sort_orders(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let fans = [backend.user("student").await.id, backend.user("student").await.id];
+    let tag = new_tag();
+
+    // Created oldest to newest; "date" and "banana" tie on zero likes.
+    let mut ids = Vec::new();
+    for (title, likes, views) in [("banana", 0, 1), ("Apple", 2, 0), ("cherry", 1, 3), ("date", 0, 0)] {
+        let p = repo.create_project(project(&tag, title), owner.id).await;
+        repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+        for fan in &fans[..likes] {
+            repo.like_project(Like { user_id: *fan, project_id: p.id }).await;
+        }
+        for _ in 0..views {
+            repo.increment_view(p.id).await.unwrap();
+        }
+        ids.push(p.id);
+    }
+    let [banana, apple, cherry, date] = ids[..] else { unreachable!() };
+
+    let sorted = async |sort| {
+        let filter = ProjectListFilter { sort, ..tagged(&tag) };
+        let (items, total) = repo.get_projects(filter, PageRequest::default()).await;
+        assert_eq!(total, 4);
+        items.into_iter().map(|p| p.id).collect::<Vec<_>>()
+    };
+    assert_eq!(sorted(ProjectSort::Newest).await, vec![date, cherry, apple, banana]);
+    assert_eq!(sorted(ProjectSort::Oldest).await, vec![banana, apple, cherry, date]);
+    assert_eq!(sorted(ProjectSort::MostLiked).await, vec![apple, cherry, date, banana]);
+    assert_eq!(sorted(ProjectSort::Title).await, vec![apple, banana, cherry, date]);
+    assert_eq!(sorted(ProjectSort::MostViewed).await, vec![cherry, banana, date, apple]);
+
+    // Offset pages follow the chosen order.
+    let filter = ProjectListFilter { sort: ProjectSort::MostLiked, ..tagged(&tag) };
+    let (second, _) = repo.get_projects(filter, PageRequest { page: 2, per_page: 3, after: None }).await;
+    assert_eq!(second.into_iter().map(|p| p.id).collect::<Vec<_>>(), vec![banana]);
+}
+
+async fn comments_join_authors_and_notify_participants(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let first = backend.user("student").await;
+    let second = backend.user("student").await;
+    let p = repo.create_project(project(&new_tag(), "Discussed"), owner.id).await;
+
+    // Comments on a draft are listed to its owner and moderators only, even for their author.
+    let early = repo.add_comment(p.id, first.id, "Early".to_string(), 500).await.unwrap().unwrap().comment;
+    assert_eq!(early.author_email.as_deref(), Some(first.email.as_str()));
+    let thread = |hidden| async move {
+        repo.get_comments(p.id, CommentOrder::Oldest, hidden).await.into_iter().map(|c| c.id).collect::<Vec<_>>()
+    };
+    assert!(thread(HiddenComments::Exclude).await.is_empty());
+    assert!(thread(HiddenComments::AuthoredBy(first.id)).await.is_empty());
+    assert_eq!(thread(HiddenComments::AuthoredBy(owner.id)).await, vec![early.id]);
+    assert_eq!(thread(HiddenComments::Include).await, vec![early.id]);
+    let page = repo.get_comments_before(p.id, HiddenComments::AuthoredBy(owner.id), None, 10).await;
+    assert_eq!(page.iter().map(|c| c.id).collect::<Vec<_>>(), vec![early.id]);
+    assert!(repo.get_comments_before(p.id, HiddenComments::AuthoredBy(first.id), None, 10).await.is_empty());
+    assert_eq!(repo.count_comments(p.id).await, None);
+
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    assert_eq!(repo.get_subscription(p.id, first.id).await, Some(SubscriptionState::Subscribed));
+    assert_eq!(repo.get_subscription(p.id, second.id).await, None);
+    assert!(repo.set_subscription(p.id, first.id, SubscriptionState::Muted).await);
+    assert!(!repo.set_subscription(Uuid::new_v4(), first.id, SubscriptionState::Muted).await);
+
+    let late = repo.add_comment(p.id, second.id, "Late".to_string(), 500).await.unwrap().unwrap().comment;
+    let oldest: Vec<_> = repo
+        .get_comments(p.id, CommentOrder::Oldest, HiddenComments::Exclude)
+        .await
+        .into_iter()
+        .map(|c| (c.id, c.author_email))
+        .collect();
+    assert_eq!(oldest, vec![(early.id, Some(first.email.clone())), (late.id, Some(second.email.clone()))]);
+    let newest = repo.get_comments(p.id, CommentOrder::Newest, HiddenComments::Exclude).await;
+    assert_eq!(newest[0].id, late.id);
+    assert_eq!(repo.count_comments(p.id).await, Some(2));
+
+    // The owner hears about both comments; the muted participant about neither.
+    let kinds = |n: Vec<fyp_portal::models::NotificationResponse>| {
+        n.into_iter().map(|n| n.notification_type).collect::<Vec<_>>()
+    };
+    assert_eq!(kinds(repo.get_notifications(owner.id).await), vec!["comment", "comment"]);
+    assert!(repo.get_notifications(first.id).await.is_empty());
+    assert!(repo.get_notifications(second.id).await.is_empty());
+
+    // A missing project is a None, not a panic, and leaves nothing behind.
+    assert!(repo.add_comment(Uuid::new_v4(), first.id, "Lost".to_string(), 500).await.unwrap().is_none());
+    assert_eq!(repo.get_user_activity(first.id, PageRequest { page: 1, per_page: 10, after: None }).await.1, 1);
+}
+
+async fn comment_fanout_threshold(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let commenter = backend.user("student").await;
+    let muted = backend.user("student").await;
+    let mut recipients = vec![owner.clone()];
+    let p = repo.create_project(project(&new_tag(), "Busy"), owner.id).await;
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    for _ in 0..4 {
+        let follower = backend.user("student").await;
+        assert!(repo.set_subscription(p.id, follower.id, SubscriptionState::Subscribed).await);
+        recipients.push(follower);
+    }
+    assert!(repo.set_subscription(p.id, muted.id, SubscriptionState::Muted).await);
+    let comment_count = async |user_id| repo.get_notifications(user_id).await.len();
+
+    // At the threshold the notifications are written with the comment.
+    let posted = repo.add_comment(p.id, commenter.id, "Small".to_string(), 5).await.unwrap().unwrap();
+    assert_eq!(posted.fanout_job_id, None);
+    for user in &recipients {
+        assert_eq!(comment_count(user.id).await, 1);
+    }
+
+    // Above it, one job is queued and nobody is notified yet.
+    let posted = repo.add_comment(p.id, commenter.id, "Large".to_string(), 4).await.unwrap().unwrap();
+    let job_id = posted.fanout_job_id.expect("fan-out should be queued");
+    let job = repo.get_outbox_job(job_id).await.unwrap();
+    assert_eq!((job.kind.as_str(), job.status.as_str()), ("pending_fanout", "pending"));
+    assert_eq!((job.total, job.processed), (5, 0));
+    assert_eq!(job.payload["comment_id"], posted.comment.id);
+    for user in &recipients {
+        assert_eq!(comment_count(user.id).await, 1);
+    }
+    assert!(repo.get_outbox_job(i64::MAX).await.is_none());
+
+    // Other checks may have queued jobs in the shared database, so drain until ours is done.
+    for _ in 0..1_000 {
+        if repo.get_outbox_job(job_id).await.unwrap().status == "done" {
+            break;
+        }
+        repo.run_fanout_batch(2).await;
+    }
+    let job = repo.get_outbox_job(job_id).await.unwrap();
+    assert_eq!((job.status.as_str(), job.processed), ("done", 5));
+    assert!(job.completed_at.is_some());
+    for user in &recipients {
+        assert_eq!(comment_count(user.id).await, 2);
+    }
+    assert_eq!(comment_count(muted.id).await, 0);
+    assert_eq!(comment_count(commenter.id).await, 0);
+}
+
+async fn comment_pages(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let author = backend.user("student").await;
+    let reporter = backend.user("student").await;
+    let p = repo.create_project(project(&new_tag(), "Long thread"), owner.id).await;
+    let first = repo.add_comment(p.id, author.id, "Draft".to_string(), 500).await.unwrap().unwrap().comment;
+    let page = |hidden, before, limit| async move {
+        let comments = repo.get_comments_before(p.id, hidden, before, limit).await;
+        comments.into_iter().map(|c| c.id).collect::<Vec<_>>()
+    };
+    // Drafts have no thread to page through, except for their owner and moderators.
+    assert!(page(HiddenComments::Exclude, None, 10).await.is_empty());
+    assert!(page(HiddenComments::AuthoredBy(author.id), None, 10).await.is_empty());
+    assert_eq!(page(HiddenComments::Include, None, 10).await, vec![first.id]);
+
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    let mut ids = vec![first.id];
+    for n in 1..5 {
+        ids.push(repo.add_comment(p.id, author.id, format!("Reply {n}"), 500).await.unwrap().unwrap().comment.id);
+    }
+    let hidden = ids[3];
+    repo.create_report(Some(p.id), hidden, reporter.id, None, 1).await.unwrap();
+
+    // Newest first, below the cursor, skipping what the viewer may not see.
+    let exclude = HiddenComments::Exclude;
+    assert_eq!(page(exclude, None, 2).await, vec![ids[4], ids[2]]);
+    assert_eq!(page(exclude, Some(CommentCursor::Id(ids[2])), 10).await, vec![ids[1], ids[0]]);
+    assert!(page(exclude, Some(CommentCursor::Id(ids[0])), 10).await.is_empty());
+    assert_eq!(page(HiddenComments::AuthoredBy(author.id), None, 2).await, vec![ids[4], hidden]);
+    assert_eq!(page(HiddenComments::Include, Some(CommentCursor::Id(ids[4])), 1).await, vec![hidden]);
+
+    // A time cursor is exclusive as well.
+    let created = repo.get_comments(p.id, CommentOrder::Oldest, HiddenComments::Include).await;
+    let before = |c: &fyp_portal::models::Comment| Some(CommentCursor::Time(c.created_at));
+    assert!(page(exclude, before(&created[0]), 10).await.is_empty());
+    let later = Some(CommentCursor::Time(created[4].created_at + chrono::Duration::seconds(1)));
+    assert_eq!(page(exclude, later, 10).await, vec![ids[4], ids[2], ids[1], ids[0]]);
+
+    // Paging forwards mirrors it: oldest first, above the cursor.
+    let forward = |hidden, after, limit| async move {
+        let comments = repo.get_comments_after(p.id, hidden, after, limit).await;
+        comments.into_iter().map(|c| c.id).collect::<Vec<_>>()
+    };
+    assert_eq!(forward(exclude, None, 2).await, vec![ids[0], ids[1]]);
+    assert_eq!(forward(exclude, Some(CommentCursor::Id(ids[1])), 10).await, vec![ids[2], ids[4]]);
+    assert!(forward(exclude, Some(CommentCursor::Id(ids[4])), 10).await.is_empty());
+    assert_eq!(forward(HiddenComments::AuthoredBy(author.id), Some(CommentCursor::Id(ids[2])), 1).await, vec![hidden]);
+    assert_eq!(forward(exclude, before(&created[0]), 10).await, vec![ids[1], ids[2], ids[4]]);
+}
+
+async fn comment_edits(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let author = backend.user("student").await;
+    let admin = backend.user("admin").await;
+    let p = repo.create_project(project(&new_tag(), "Typos"), owner.id).await;
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    let posted = repo.add_comment(p.id, author.id, "Frist".to_string(), 500).await.unwrap().unwrap().comment;
+    repo.add_comment(p.id, owner.id, "Thanks".to_string(), 500).await.unwrap().unwrap();
+    assert_eq!((posted.edited_at, posted.edited), (None, false));
+    let edit = |text: &str| UpdateCommentRequest { text: text.to_string() };
+
+    // Only the author: not the project owner, not an admin, and not a missing id.
+    assert!(repo.update_comment(posted.id, owner.id, edit("Mine now")).await.is_none());
+    assert!(repo.update_comment(posted.id, admin.id, edit("Moderated")).await.is_none());
+    assert!(repo.update_comment(i64::MAX, author.id, edit("First")).await.is_none());
+
+    let Some(CommentEditOutcome::Edited(edited)) = repo.update_comment(posted.id, author.id, edit("First")).await else {
+        panic!("the author's edit was refused");
+    };
+    assert_eq!((edited.id, edited.comment.as_str(), edited.edited), (posted.id, "First", true));
+    assert_eq!(edited.author_email.as_deref(), Some(author.email.as_str()));
+    assert!(edited.edited_at.is_some_and(|at| at >= posted.created_at));
+
+    // It keeps its place in the thread, and listings carry the flag.
+    let thread = repo.get_comments(p.id, CommentOrder::Oldest, HiddenComments::Exclude).await;
+    assert_eq!(thread.iter().map(|c| c.edited).collect::<Vec<_>>(), vec![true, false]);
+    assert_eq!((thread[0].id, thread[0].comment.as_str()), (posted.id, "First"));
+    assert_eq!(thread[0].edited_at, edited.edited_at);
+    let page = repo.get_comments_before(p.id, HiddenComments::Exclude, None, 10).await;
+    assert!(page.iter().any(|c| c.id == posted.id && c.edited));
+    // The edit counts towards the rate limit like the comment itself.
+    assert_eq!(repo.get_recent_comments(author.id).await.last_minute, 2);
+
+    // A locked discussion, or a comment held for review, keeps its text; refusals do not count.
+    repo.set_comments_locked(p.id, owner.id, true).await.unwrap();
+    assert!(matches!(repo.update_comment(posted.id, author.id, edit("Locked")).await, Some(CommentEditOutcome::Locked)));
+    repo.set_comments_locked(p.id, owner.id, false).await.unwrap();
+    repo.create_report(None, posted.id, owner.id, None, 1).await.unwrap();
+    let refused = repo.update_comment(posted.id, author.id, edit("Nothing to see")).await;
+    assert!(matches!(refused, Some(CommentEditOutcome::HiddenPendingReview)));
+    let thread = repo.get_comments(p.id, CommentOrder::Oldest, HiddenComments::Include).await;
+    assert_eq!(thread[0].comment, "First");
+    assert_eq!(repo.get_recent_comments(author.id).await.last_minute, 2);
+}
+
+async fn reported_comment_queue(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let author = backend.user("student").await;
+    let reporters = [backend.user("student").await, backend.user("student").await];
+    let tag = new_tag();
+    let draft = repo.create_project(project(&tag, "Unpublished"), owner.id).await;
+    let hidden = repo.add_comment(draft.id, author.id, "Early".to_string(), 500).await.unwrap().unwrap().comment;
+    let p = repo.create_project(project(&tag, "Heated"), owner.id).await;
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    let rude = repo.add_comment(p.id, author.id, "Rude".to_string(), 500).await.unwrap().unwrap().comment;
+    let mild = repo.add_comment(p.id, author.id, "Meh".to_string(), 500).await.unwrap().unwrap().comment;
+    let report = |comment: i64, reporter: Uuid, reason: Option<&str>| {
+        repo.create_report(None, comment, reporter, reason.map(str::to_string), 100)
+    };
+    // Other tests' reports share the database, so only this test's comments are looked at.
+    let queue = || async {
+        repo.get_reported_comments()
+            .await
+            .into_iter()
+            .filter(|r| [rude.id, mild.id, hidden.id].contains(&r.comment.id))
+            .collect::<Vec<_>>()
+    };
+
+    // By id alone, and idempotent per reporter however the comment is addressed.
+    assert!(report(hidden.id, reporters[0].id, None).await.is_none());
+    assert_eq!(report(rude.id, reporters[0].id, Some("insulting")).await.unwrap().pending_reports, 1);
+    assert_eq!(report(rude.id, reporters[0].id, Some("again")).await.unwrap().pending_reports, 1);
+    let scoped = repo.create_report(Some(p.id), rude.id, reporters[1].id, None, 100).await;
+    assert_eq!(scoped.unwrap().pending_reports, 2);
+    assert_eq!(report(mild.id, reporters[1].id, Some("off-topic")).await.unwrap().pending_reports, 1);
+
+    let listed = queue().await;
+    let summary: Vec<_> = listed.iter().map(|r| (r.comment.id, r.report_count, r.reasons.clone())).collect();
+    assert_eq!(
+        summary,
+        vec![(rude.id, 2, vec!["insulting".to_string()]), (mild.id, 1, vec!["off-topic".to_string()])]
+    );
+    assert_eq!(listed[0].project_title, format!("Heated {tag}"));
+    assert_eq!(listed[0].comment.author_email.as_deref(), Some(author.email.as_str()));
+    assert!(listed[0].last_reported_at >= rude.created_at);
+
+    // Dismissing (restoring) or deleting takes a comment out of the queue.
+    assert!(repo.restore_comment(mild.id).await);
+    assert_eq!(queue().await.iter().map(|r| r.comment.id).collect::<Vec<_>>(), vec![rude.id]);
+    assert!(repo.delete_comment_admin(rude.id).await);
+    assert!(queue().await.is_empty());
+}
+
+async fn admin_comment_listing(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let authors = [backend.user("student").await, backend.user("student").await];
+    let tag = new_tag();
+    let draft = repo.create_project(project(&tag, "Draft"), owner.id).await;
+    let p = repo.create_project(project(&tag, "Listed"), owner.id).await;
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    // A unique word per run, since other tests' comments share the database.
+    let word = format!("Marker{}", Uuid::new_v4().simple());
+    let mut ids = vec![];
+    for (project, author, text) in [
+        (p.id, &authors[0], format!("first {word}")),
+        (p.id, &authors[1], format!("second {}", word.to_uppercase())),
+        (draft.id, &authors[0], format!("third {word}")),
+        (p.id, &authors[0], "unrelated".to_string()),
+    ] {
+        ids.push(repo.add_comment(project, author.id, text, 500).await.unwrap().unwrap().comment.id);
+    }
+    let list = |project_id: Option<Uuid>, user_id: Option<Uuid>, limit: i64, offset: i64| {
+        let filter = AdminCommentFilter { project_id, user_id, search: Some(word.to_lowercase()) };
+        async move {
+            repo.get_admin_comments(&filter, limit, offset)
+                .await
+                .into_iter()
+                .map(|c| c.comment.id)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // Newest first, on drafts too, with the search ignoring case.
+    assert_eq!(list(None, None, 10, 0).await, vec![ids[2], ids[1], ids[0]]);
+    assert_eq!(list(Some(p.id), None, 10, 0).await, vec![ids[1], ids[0]]);
+    assert_eq!(list(None, Some(authors[0].id), 10, 0).await, vec![ids[2], ids[0]]);
+    assert_eq!(list(Some(p.id), Some(authors[0].id), 10, 0).await, vec![ids[0]]);
+    assert_eq!(list(None, None, 1, 1).await, vec![ids[1]]);
+    assert!(list(None, None, 10, 3).await.is_empty());
+
+    let filter = AdminCommentFilter { project_id: Some(p.id), ..AdminCommentFilter::default() };
+    let all = repo.get_admin_comments(&filter, 10, 0).await;
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].comment.id, ids[3]);
+    assert_eq!(all[0].project_title, format!("Listed {tag}"));
+    assert_eq!(all[0].comment.author_email.as_deref(), Some(authors[0].email.as_str()));
+}
+
+async fn reports_hide_and_restore(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let author = backend.user("student").await;
+    let admin = backend.user("admin").await;
+    let reporters = [backend.user("student").await, backend.user("student").await];
+    let p = repo.create_project(project(&new_tag(), "Reported"), owner.id).await;
+    let comment = repo.add_comment(p.id, author.id, "Spam".to_string(), 500).await.unwrap().unwrap().comment;
+
+    // Drafts cannot be reported.
+    assert!(repo.create_report(Some(p.id), comment.id, reporters[0].id, None, 2).await.is_none());
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    assert!(repo.create_report(Some(Uuid::new_v4()), comment.id, reporters[0].id, None, 2).await.is_none());
+
+    let outcome = repo.create_report(Some(p.id), comment.id, reporters[0].id, None, 2).await.unwrap();
+    assert_eq!((outcome.pending_reports, outcome.hidden), (1, false));
+    let repeat = repo.create_report(Some(p.id), comment.id, reporters[0].id, None, 2).await.unwrap();
+    assert_eq!((repeat.pending_reports, repeat.hidden), (1, false));
+    let tripped = repo.create_report(Some(p.id), comment.id, reporters[1].id, Some("spam".to_string()), 2).await.unwrap();
+    assert_eq!((tripped.pending_reports, tripped.hidden), (2, true));
+
+    assert!(repo.get_comments(p.id, CommentOrder::Oldest, HiddenComments::Exclude).await.is_empty());
+    let own = repo.get_comments(p.id, CommentOrder::Oldest, HiddenComments::AuthoredBy(author.id)).await;
+    assert!(own[0].hidden_pending_review);
+    assert_eq!(repo.count_comments(p.id).await, Some(0));
+    // Every admin is alerted, so other tests' alerts may reach this admin too.
+    let alerts: Vec<_> = repo
+        .get_notifications(admin.id)
+        .await
+        .into_iter()
+        .filter(|n| n.project_id == p.id)
+        .collect();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].notification_type, "comment_hidden");
+
+    assert!(repo.restore_comment(comment.id).await);
+    assert_eq!(repo.count_comments(p.id).await, Some(1));
+    // Restoring resolves the pending reports, so the count starts again.
+    let fresh = repo.create_report(Some(p.id), comment.id, reporters[0].id, None, 2).await.unwrap();
+    assert_eq!(fresh.pending_reports, 1);
+    assert!(!repo.restore_comment(i64::MAX).await);
+}
+
+async fn project_comment_counts(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let commenter = backend.user("student").await;
+    let reporters = [backend.user("student").await, backend.user("student").await];
+    let tag = new_tag();
+    let p = repo.create_project(project(&tag, "Counted"), owner.id).await;
+    assert_eq!(p.comment_count, 0);
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+
+    let listed = |projects: Vec<fyp_portal::models::Project>| {
+        projects.into_iter().find(|q| q.id == p.id).map(|q| q.comment_count)
+    };
+    let owned = |summaries: Vec<fyp_portal::models::MyProjectSummary>| summaries.into_iter().map(|s| s.project).collect();
+    let first = repo.add_comment(p.id, commenter.id, "First".to_string(), 500).await.unwrap().unwrap().comment;
+    let second = repo.add_comment(p.id, commenter.id, "Second".to_string(), 500).await.unwrap().unwrap().comment;
+    assert_eq!(listed(repo.get_projects(tagged(&tag), PageRequest::default()).await.0), Some(2));
+    assert_eq!(repo.get_project(p.id).await.unwrap().comment_count, 2);
+    assert_eq!(repo.get_public_project(p.id).await.unwrap().comment_count, 2);
+
+    // A comment held for review drops out of public counts but not the owner's.
+    for reporter in &reporters {
+        repo.create_report(Some(p.id), second.id, reporter.id, None, 2).await.unwrap();
+    }
+    assert_eq!(listed(repo.get_projects(tagged(&tag), PageRequest::default()).await.0), Some(1));
+    assert_eq!(repo.get_public_project(p.id).await.unwrap().comment_count, 1);
+    assert_eq!(listed(owned(repo.get_my_project_summaries(owner.id).await)), Some(2));
+
+    assert!(repo.delete_comment_admin(first.id).await);
+    assert_eq!(listed(repo.get_projects(tagged(&tag), PageRequest::default()).await.0), Some(0));
+    assert_eq!(repo.get_project(p.id).await.unwrap().comment_count, 1);
+    assert_eq!(listed(owned(repo.get_my_project_summaries(owner.id).await)), Some(1));
+}
+
+async fn notifications_are_enriched_and_owned(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let admin = backend.user("admin").await;
+    let p = repo.create_project(project(&new_tag(), "Reviewed"), owner.id).await;
+
+    assert!(!repo.notify_status_change(p.id, owner.id, None).await);
+    assert!(!repo.notify_status_change(Uuid::new_v4(), admin.id, None).await);
+    assert!(repo.notify_status_change(p.id, admin.id, Some("Approved".to_string())).await);
+
+    let notifications = repo.get_notifications(owner.id).await;
+    assert_eq!(notifications.len(), 1);
+    let n = &notifications[0];
+    assert_eq!(n.notification_type, "status_change");
+    assert_eq!(n.message.as_deref(), Some("Approved"));
+    assert_eq!(n.actor_email, admin.email);
+    assert_eq!(n.project_title, p.title);
+    assert!(!n.is_read);
+
+    assert!(!repo.mark_notification_read(n.id, admin.id).await);
+    assert!(repo.mark_notification_read(n.id, owner.id).await);
+    assert!(repo.get_notifications(owner.id).await[0].is_read);
+
+    // The generic path: written as given, except that nobody is told about their own action.
+    let created = repo
+        .create_notification(NewNotification::new(owner.id, admin.id, p.id, "feedback").with_message("Hello"))
+        .await
+        .unwrap();
+    assert_eq!((created.user_id, created.actor_id, created.project_id), (owner.id, admin.id, p.id));
+    assert_eq!(created.message.as_deref(), Some("Hello"));
+    assert!(!created.is_read);
+    assert!(repo.create_notification(NewNotification::new(owner.id, owner.id, p.id, "feedback")).await.is_none());
+    assert_eq!(repo.get_notifications(owner.id).await.len(), 2);
+}
+
+async fn users_by_email_prefix(backend: &Backend) {
+    let repo = &*backend.repo;
+    let mut users = [backend.user("student").await, backend.user("admin").await];
+    backend.user("student").await;
+    users.sort_by(|a, b| a.email.cmp(&b.email));
+    let local = |user: &User| user.email.split('@').next().unwrap().to_string();
+
+    // Only whole local parts match: neither the domain nor a truncated one does.
+    let truncated = local(&users[0])[..20].to_string();
+    let prefixes = [local(&users[0]), local(&users[1]), "test.com".to_string(), truncated, new_tag()];
+    let found = repo.get_users_by_email_prefix(&prefixes).await;
+    assert_eq!(found.iter().map(|u| u.id).collect::<Vec<_>>(), users.iter().map(|u| u.id).collect::<Vec<_>>());
+    assert_eq!(found[1].role, users[1].role);
+    assert!(repo.get_users_by_email_prefix(&[]).await.is_empty());
+}
+
+async fn project_scoped_comment_delete(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let author = backend.user("student").await;
+    let tag = new_tag();
+    let p = repo.create_project(project(&tag, "Here"), owner.id).await;
+    let other = repo.create_project(project(&tag, "There"), owner.id).await;
+    let comment = repo.add_comment(p.id, author.id, "Hi".to_string(), 500).await.unwrap().unwrap().comment;
+
+    assert!(!repo.delete_project_comment(other.id, comment.id, None).await);
+    assert!(!repo.delete_project_comment(p.id, comment.id, Some(owner.id)).await);
+    assert!(!repo.delete_comment(comment.id, owner.id).await);
+    assert!(repo.delete_project_comment(p.id, comment.id, Some(author.id)).await);
+    assert!(!repo.delete_comment_admin(comment.id).await);
+}
+
+async fn soft_deleted_comments(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let author = backend.user("student").await;
+    let reporter = backend.user("student").await;
+    let tag = new_tag();
+    let p = repo.create_project(project(&tag, "Soft"), owner.id).await;
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    let first = repo.add_comment(p.id, author.id, "First".to_string(), 500).await.unwrap().unwrap().comment;
+    let middle = repo.add_comment(p.id, author.id, "Middle".to_string(), 500).await.unwrap().unwrap().comment;
+    let last = repo.add_comment(p.id, owner.id, "Last".to_string(), 500).await.unwrap().unwrap().comment;
+    repo.create_report(None, middle.id, reporter.id, None, 5).await.unwrap();
+    let edit = UpdateCommentRequest { text: "Middle, edited".to_string() };
+    repo.update_comment(middle.id, author.id, edit).await.unwrap();
+
+    assert!(repo.delete_comment(middle.id, author.id).await);
+    let thread = repo.get_comments(p.id, CommentOrder::Oldest, HiddenComments::Exclude).await;
+    let shown: Vec<_> = thread.iter().map(|c| (c.id, c.comment.as_str(), c.author_email.is_some(), c.is_deleted)).collect();
+    assert_eq!(shown, vec![(first.id, "First", true, false), (middle.id, "[deleted]", false, true), (last.id, "Last", true, false)]);
+    // The placeholder says nothing about who wrote it or when it was last touched.
+    let placeholder = &thread[1];
+    assert_eq!(placeholder.user_id, None);
+    assert_eq!(placeholder.edited_at, None);
+    assert!(!placeholder.edited && !placeholder.hidden_pending_review);
+    assert_eq!(thread[0].user_id, Some(author.id));
+    assert_eq!(repo.count_comments(p.id).await, Some(2));
+    assert_eq!(repo.get_project(p.id).await.unwrap().comment_count, 2);
+    // Deleted comments leave the moderation queue and cannot be reported again.
+    assert!(repo.get_reported_comments().await.iter().all(|r| r.comment.id != middle.id));
+    assert!(repo.create_report(None, middle.id, owner.id, None, 5).await.is_none());
+    // Admins still see what was written.
+    let filter = AdminCommentFilter { project_id: Some(p.id), ..AdminCommentFilter::default() };
+    let listed = repo.get_admin_comments(&filter, 10, 0).await;
+    let deleted = listed.iter().find(|c| c.comment.id == middle.id).unwrap();
+    assert!(deleted.comment.is_deleted);
+    assert_eq!(deleted.comment.comment, "Middle, edited");
+
+    // Without a later comment still standing, the placeholder goes too.
+    assert!(repo.delete_comment_admin(last.id).await);
+    let ids: Vec<i64> = repo.get_comments(p.id, CommentOrder::Newest, HiddenComments::Include).await.iter().map(|c| c.id).collect();
+    assert_eq!(ids, vec![first.id]);
+
+    // A later comment held for review only keeps a placeholder for viewers who can see it.
+    let held = repo.add_comment(p.id, reporter.id, "Held".to_string(), 500).await.unwrap().unwrap().comment;
+    repo.create_report(None, held.id, owner.id, None, 1).await.unwrap();
+    assert!(repo.delete_comment(first.id, author.id).await);
+    let ids = async |hidden| repo.get_comments(p.id, CommentOrder::Oldest, hidden).await.iter().map(|c| c.id).collect::<Vec<_>>();
+    assert_eq!(ids(HiddenComments::Exclude).await, Vec::<i64>::new());
+    assert_eq!(ids(HiddenComments::AuthoredBy(author.id)).await, Vec::<i64>::new());
+    assert_eq!(ids(HiddenComments::AuthoredBy(reporter.id)).await, vec![first.id, middle.id, last.id, held.id]);
+    assert_eq!(ids(HiddenComments::Include).await, vec![first.id, middle.id, last.id, held.id]);
+}
+
+async fn discussion_lock(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let stranger = backend.user("student").await;
+    let p = repo.create_project(project(&new_tag(), "Locked"), owner.id).await;
+
+    assert!(!repo.are_comments_locked(p.id).await);
+    assert!(repo.set_comments_locked(p.id, stranger.id, true).await.is_none());
+    assert!(repo.set_comments_locked(p.id, owner.id, true).await.unwrap().comments_locked);
+    assert!(repo.are_comments_locked(p.id).await);
+    // The insert checks the lock itself, so nothing gets in while it holds.
+    assert!(repo.add_comment(p.id, stranger.id, "Too late".to_string(), 500).await.unwrap().is_none());
+    assert!(repo.get_comments(p.id, CommentOrder::Oldest, HiddenComments::Include).await.is_empty());
+    assert!(!repo.set_comments_locked_admin(p.id, false).await.unwrap().comments_locked);
+    assert!(repo.add_comment(p.id, stranger.id, "Reopened".to_string(), 500).await.unwrap().is_some());
+    assert!(!repo.are_comments_locked(Uuid::new_v4()).await);
+
+    let submitted = repo.mark_project_submitted(p.id).await.unwrap();
+    assert!(submitted.submitted_at.is_some());
+    assert!(submitted.updated_at >= p.updated_at);
+    // Only a draft is submitted: a second submit, or one after a decision, changes nothing.
+    assert!(repo.mark_project_submitted(p.id).await.is_none());
+    repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+    assert!(repo.mark_project_submitted(p.id).await.is_none());
+    assert_eq!(repo.get_project(p.id).await.unwrap().status, ProjectStatus::Approved);
+}
+
+async fn upload_counts(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let key = format!("covers/{}", Uuid::new_v4());
+    let expires = Utc::now() + chrono::Duration::minutes(15);
+
+    assert!(repo.record_upload(owner.id, &key, UploadPurpose::CoverImage, expires).await);
+    let counts = repo.get_upload_counts(owner.id, UploadPurpose::CoverImage).await;
+    assert_eq!((counts.last_hour, counts.outstanding), (1, 1));
+    assert_eq!(repo.get_upload_counts(owner.id, UploadPurpose::Video).await.outstanding, 0);
+
+    // Ownership is per user and per purpose.
+    assert!(repo.is_own_upload(owner.id, &key, UploadPurpose::CoverImage).await);
+    assert!(!repo.is_own_upload(owner.id, &key, UploadPurpose::Report).await);
+    assert!(!repo.is_own_upload(Uuid::new_v4(), &key, UploadPurpose::CoverImage).await);
+
+    // Referenced by a project, the upload is confirmed rather than outstanding.
+    let request = CreateProjectRequest {
+        cover_image_key: key,
+        ..project(&new_tag(), "Uploaded")
+    };
+    repo.create_project(request, owner.id).await;
+    let counts = repo.get_upload_counts(owner.id, UploadPurpose::CoverImage).await;
+    assert_eq!((counts.last_hour, counts.outstanding), (1, 0));
+}
+
+async fn programme_membership(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let suffix = new_tag()[..8].to_uppercase();
+    let code = format!("CF-{suffix}");
+    let programme = repo.create_programme(&code, "Conformance").await.unwrap();
+    assert!(repo.create_programme(&code, "Again").await.is_none());
+    assert!(repo.get_programmes().await.contains(&programme));
+    let unknown = format!("CX-{suffix}");
+    assert_eq!(repo.unknown_programme_codes(&[code.clone(), unknown.clone()]).await, vec![unknown]);
+
+    let request = CreateProjectRequest {
+        programme_codes: Some(vec![code.clone()]),
+        ..project(&suffix, "Member")
+    };
+    let member = repo.create_project(request, owner.id).await;
+    repo.set_project_status(member.id, ProjectStatus::Approved).await.unwrap();
+    assert_eq!(repo.get_project_programmes(member.id).await, vec![code.clone()]);
+
+    let filter = ProjectListFilter {
+        programme: Some(code),
+        ..ProjectListFilter::default()
+    };
+    let (items, total) = repo.get_projects(filter, PageRequest::default()).await;
+    assert_eq!(total, 1);
+    assert_eq!(items[0].id, member.id);
+}
+
+async fn project_tags(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    // Tags are global, so this test's tags carry part of its unique tag.
+    let [ml, web] = [format!("ml-{}", &tag[..8]), format!("web-{}", &tag[..8])];
+
+    let request = CreateProjectRequest {
+        tags: vec![web.clone(), ml.clone()],
+        ..project(&tag, "Tagged")
+    };
+    let tagged_project = repo.create_project(request, owner.id).await;
+    assert_eq!(tagged_project.tags, vec![ml.clone(), web.clone()]);
+    let plain = repo.create_project(project(&tag, "Plain"), owner.id).await;
+    assert!(plain.tags.is_empty());
+
+    // Drafts' tags are not counted.
+    let mine = |counts: Vec<TagCount>| counts.into_iter().filter(|c| c.tag == ml || c.tag == web).collect::<Vec<_>>();
+    assert!(mine(repo.get_tag_counts().await).is_empty());
+
+    repo.set_project_status(tagged_project.id, ProjectStatus::Approved).await.unwrap();
+    repo.set_project_status(plain.id, ProjectStatus::Approved).await.unwrap();
+    let with_tag = |t: &String| ProjectListFilter { tag: Some(t.clone()), ..tagged(&tag) };
+    let (items, total) = repo.get_projects(with_tag(&ml), PageRequest::default()).await;
+    assert_eq!(total, 1);
+    assert_eq!(items[0].id, tagged_project.id);
+    assert_eq!(items[0].tags, vec![ml.clone(), web.clone()]);
+
+    let update = |tags| UpdateProjectRequest { tags: Some(tags), ..UpdateProjectRequest::default() };
+    let updated = repo.update_project(plain.id, owner.id, update(vec![ml.clone()])).await.unwrap().project;
+    assert_eq!(updated.tags, vec![ml.clone()]);
+    let (_, total) = repo.get_projects(with_tag(&ml), PageRequest::default()).await;
+    assert_eq!(total, 2);
+    assert_eq!(
+        mine(repo.get_tag_counts().await),
+        vec![TagCount { tag: ml.clone(), count: 2 }, TagCount { tag: web.clone(), count: 1 }]
+    );
+
+    // A non-owner's update leaves the tags alone; an empty list clears them.
+    assert!(repo.update_project(plain.id, Uuid::new_v4(), update(vec![])).await.is_none());
+    assert_eq!(repo.get_project(plain.id).await.unwrap().tags, vec![ml.clone()]);
+    assert!(repo.update_project(plain.id, owner.id, update(vec![])).await.unwrap().project.tags.is_empty());
+    let (_, total) = repo.get_projects(with_tag(&ml), PageRequest::default()).await;
+    assert_eq!(total, 1);
+}
+
+async fn supervisor_filter(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    let supervised = |title, supervisor: Option<&str>| CreateProjectRequest {
+        supervisor: supervisor.map(str::to_string),
+        ..project(&tag, title)
+    };
+    let mut ids = Vec::new();
+    for request in [
+        supervised("Mine", Some("Dr Jane Smith")),
+        supervised("Padded", Some("  dr jane smith ")),
+        supervised("Other", Some("Dr Jane Smithson")),
+        supervised("Unassigned", None),
+    ] {
+        let created = repo.create_project(request, owner.id).await;
+        repo.set_project_status(created.id, ProjectStatus::Approved).await.unwrap();
+        ids.push(created.id);
+    }
+    let hidden = repo.create_project(supervised("Hidden", Some("Dr Jane Smith")), owner.id).await;
+    assert_eq!(hidden.supervisor.as_deref(), Some("Dr Jane Smith"));
+
+    let by = |name: &str| ProjectListFilter { supervisor: Some(name.to_string()), ..tagged(&tag) };
+    let (items, total) = repo.get_projects(by("DR JANE SMITH"), PageRequest::default()).await;
+    assert_eq!(total, 2);
+    assert_eq!(items.iter().map(|p| p.id).collect::<Vec<_>>(), vec![ids[1], ids[0]]);
+    assert_eq!(repo.get_projects(by("Dr Jane"), PageRequest::default()).await.1, 0);
+
+    // Updates keep the supervisor unless a new one is given.
+    let update = |supervisor: Option<&str>| UpdateProjectRequest {
+        supervisor: supervisor.map(str::to_string),
+        ..UpdateProjectRequest::default()
+    };
+    let kept = repo.update_project(ids[0], owner.id, update(None)).await.unwrap().project;
+    assert_eq!(kept.supervisor.as_deref(), Some("Dr Jane Smith"));
+    repo.update_project(ids[0], owner.id, update(Some("Prof Alan Byrne"))).await.unwrap();
+    assert_eq!(repo.get_projects(by("dr jane smith"), PageRequest::default()).await.1, 1);
+}
+
+async fn author_filter(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    // Names carry part of the unique tag, so other rows never share them.
+    let [jane, janet] = [format!("Jane {}", &tag[..8]), format!("Janet {}", &tag[..8])];
+    let mut ids = Vec::new();
+    for (title, author, year) in [("First", &jane, 2023), ("Second", &jane.to_uppercase(), 2024), ("Other", &janet, 2024)] {
+        let request = CreateProjectRequest { author_name: author.clone(), year, ..project(&tag, title) };
+        let created = repo.create_project(request, owner.id).await;
+        repo.set_project_status(created.id, ProjectStatus::Approved).await.unwrap();
+        ids.push(created.id);
+    }
+    let draft = CreateProjectRequest { author_name: jane.clone(), ..project(&tag, "Draft") };
+    repo.create_project(draft, owner.id).await;
+
+    // `search` matches part of a name; `author` only the whole name, in any case.
+    let partial = ProjectListFilter { search: Some(format!("Jane {}", &tag[..4])), ..ProjectListFilter::default() };
+    assert_eq!(repo.get_projects(partial, PageRequest::default()).await.1, 2);
+    let by = |name: &str| ProjectListFilter { author: Some(name.to_string()), ..ProjectListFilter::default() };
+    assert_eq!(repo.get_projects(by("Jane"), PageRequest::default()).await.1, 0);
+    assert_eq!(repo.get_projects(by(&format!("Jane {}", &tag[..4])), PageRequest::default()).await.1, 0);
+    let (items, total) = repo.get_projects(by(&jane.to_lowercase()), PageRequest::default()).await;
+    assert_eq!(total, 2);
+    assert_eq!(items.iter().map(|p| p.id).collect::<Vec<_>>(), vec![ids[1], ids[0]]);
+
+    // Combines with `year` and `search`.
+    let in_year = ProjectListFilter { year: Some(2024), ..by(&jane) };
+    let (items, _) = repo.get_projects(in_year, PageRequest::default()).await;
+    assert_eq!(items.iter().map(|p| p.id).collect::<Vec<_>>(), vec![ids[1]]);
+    assert_eq!(repo.get_projects(ProjectListFilter { search: Some("First".to_string()), ..by(&jane) }, PageRequest::default()).await.1, 1);
+    assert_eq!(repo.get_projects(ProjectListFilter { search: Some("Other".to_string()), ..by(&jane) }, PageRequest::default()).await.1, 0);
+}
+
+async fn department_filter(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    // The repository stores what the handler validated; unique names keep the counts local.
+    let [ee, bio] = [format!("EE-{}", &tag[..8]), format!("Bio-{}", &tag[..8])];
+    for (title, department) in [("One", &ee), ("Two", &ee), ("Three", &bio)] {
+        let request = CreateProjectRequest { department: Some(department.clone()), ..project(&tag, title) };
+        let created = repo.create_project(request, owner.id).await;
+        repo.set_project_status(created.id, ProjectStatus::Approved).await.unwrap();
+    }
+    let draft = CreateProjectRequest { department: Some(bio.clone()), ..project(&tag, "Draft") };
+    let draft = repo.create_project(draft, owner.id).await;
+    assert_eq!(draft.department.as_deref(), Some(bio.as_str()));
+
+    let by = |name: &str| ProjectListFilter { department: Some(name.to_string()), ..ProjectListFilter::default() };
+    assert_eq!(repo.get_projects(by(&ee.to_lowercase()), PageRequest::default()).await.1, 2);
+    assert_eq!(repo.get_projects(by(&bio), PageRequest::default()).await.1, 1);
+    assert_eq!(repo.get_projects(by(&tag[..8]), PageRequest::default()).await.1, 0);
+
+    // Counts cover public projects only, most projects first.
+    let counts: Vec<_> = repo
+        .get_department_counts()
+        .await
+        .into_iter()
+        .filter(|c| c.department.ends_with(&tag[..8]))
+        .map(|c| (c.department, c.count))
+        .collect();
+    assert_eq!(counts, vec![(ee.clone(), 2), (bio.clone(), 1)]);
+
+    // An update moves the project between departments; omitting the field keeps it.
+    let update = UpdateProjectRequest { department: Some(ee.clone()), ..UpdateProjectRequest::default() };
+    let result = repo.update_project(draft.id, owner.id, update).await.unwrap();
+    assert_eq!(result.project.department.as_deref(), Some(ee.as_str()));
+    assert!(result.changed_fields.contains(&"department".to_string()));
+    let rename = UpdateProjectRequest { title: Some("Renamed".to_string()), ..UpdateProjectRequest::default() };
+    let result = repo.update_project(draft.id, owner.id, rename).await.unwrap();
+    assert_eq!(result.project.department.as_deref(), Some(ee.as_str()));
+}
+
+async fn technology_filter(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    // Unique names keep the counts local; the handler has already lower-cased and sorted them.
+    let [rust, postgres] = [format!("rust-{}", &tag[..8]), format!("postgres-{}", &tag[..8])];
+    let mut ids = Vec::new();
+    for (title, technologies) in [("Both", vec![postgres.clone(), rust.clone()]), ("Rust", vec![rust.clone()])] {
+        let request = CreateProjectRequest { technologies, ..project(&tag, title) };
+        let created = repo.create_project(request, owner.id).await;
+        repo.set_project_status(created.id, ProjectStatus::Approved).await.unwrap();
+        ids.push(created.id);
+    }
+    let draft = CreateProjectRequest { technologies: vec![postgres.clone()], ..project(&tag, "Draft") };
+    let draft = repo.create_project(draft, owner.id).await;
+    assert_eq!(draft.technologies, vec![postgres.clone()]);
+
+    // Several technologies narrow the listing: a project has to use every one of them.
+    let by = |technologies: &[&String]| ProjectListFilter {
+        technologies: technologies.iter().map(|t| t.to_string()).collect(),
+        ..ProjectListFilter::default()
+    };
+    let (both, total) = repo.get_projects(by(&[&rust, &postgres]), PageRequest::default()).await;
+    assert_eq!(total, 1);
+    assert_eq!(both[0].id, ids[0]);
+    assert_eq!(repo.get_projects(by(&[&rust]), PageRequest::default()).await.1, 2);
+    assert_eq!(repo.get_projects(by(&[&postgres]), PageRequest::default()).await.1, 1);
+
+    // Counts cover public projects only, most used first.
+    let counts: Vec<_> = repo
+        .get_technology_counts()
+        .await
+        .into_iter()
+        .filter(|c| c.technology.ends_with(&tag[..8]))
+        .map(|c| (c.technology, c.count))
+        .collect();
+    assert_eq!(counts, vec![(rust.clone(), 2), (postgres.clone(), 1)]);
+
+    // An update replaces the list; omitting the field keeps it.
+    let update = UpdateProjectRequest { technologies: Some(vec![rust.clone()]), ..UpdateProjectRequest::default() };
+    let result = repo.update_project(draft.id, owner.id, update).await.unwrap();
+    assert_eq!(result.project.technologies, vec![rust.clone()]);
+    assert!(result.changed_fields.contains(&"technologies".to_string()));
+    let rename = UpdateProjectRequest { title: Some("Renamed".to_string()), ..UpdateProjectRequest::default() };
+    let result = repo.update_project(draft.id, owner.id, rename).await.unwrap();
+    assert_eq!(result.project.technologies, vec![rust]);
+}
+
+async fn project_detail_owner(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let stranger = backend.user("student").await;
+    let tag = new_tag();
+    let expected = ProjectOwner::new(owner.id, owner.email.clone());
+
+    // A draft: its owner and admins see it, with the owner's profile; nobody else does.
+    let draft = repo.create_project(project(&tag, "Detail"), owner.id).await;
+    assert!(repo.get_project_detail(draft.id, ProjectViewer::Public).await.is_none());
+    assert!(repo.get_project_detail(draft.id, ProjectViewer::User(stranger.id)).await.is_none());
+    for viewer in [ProjectViewer::User(owner.id), ProjectViewer::Admin] {
+        let (project, found) = repo.get_project_detail(draft.id, viewer).await.unwrap();
+        assert_eq!(project.id, draft.id);
+        assert_eq!(found.as_ref(), Some(&expected));
+    }
+    repo.set_project_status(draft.id, ProjectStatus::Approved).await.unwrap();
+    let (_, found) = repo.get_project_detail(draft.id, ProjectViewer::Public).await.unwrap();
+    assert_eq!(found, Some(expected));
+
+    // An owner without a profile row still has a visible project, just no `owner`.
+    let orphan_id = Uuid::new_v4();
+    if let Some(pool) = &backend.pool {
+        sqlx::query!("INSERT INTO auth.users (id, email) VALUES ($1, $2)", orphan_id, format!("orphan-{orphan_id}@test.com"))
+            .execute(pool)
+            .await
+            .expect("Failed to seed auth user");
+    }
+    let orphaned = repo.create_project(project(&tag, "Orphaned"), orphan_id).await;
+    repo.set_project_status(orphaned.id, ProjectStatus::Approved).await.unwrap();
+    let (project, found) = repo.get_project_detail(orphaned.id, ProjectViewer::Public).await.unwrap();
+    assert_eq!(project.id, orphaned.id);
+    assert!(found.is_none());
+}
+
+async fn project_years(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    // Years far outside real cohorts, unique to this run, so other rows never count here.
+    let year = 5000 + (Uuid::new_v4().as_u128() % 100_000) as i32 * 3;
+    let mine = async || {
+        let years = repo.get_project_years().await;
+        years.into_iter().filter(|y| (year..year + 3).contains(&y.year)).collect::<Vec<_>>()
+    };
+
+    for (offset, publish, hide) in [(0, true, false), (0, true, false), (1, true, false), (0, true, true), (2, false, false)] {
+        let req = CreateProjectRequest { year: year + offset, ..project(&tag, "Cohort") };
+        let p = repo.create_project(req, owner.id).await;
+        if publish {
+            repo.set_project_status(p.id, ProjectStatus::Approved).await.unwrap();
+        }
+        if hide {
+            repo.set_project_status(p.id, ProjectStatus::Rejected).await.unwrap();
+        }
+    }
+
+    // Newest first; the hidden project and the draft-only year are not counted.
+    assert_eq!(
+        mine().await,
+        vec![YearCount { year: year + 1, count: 1 }, YearCount { year, count: 2 }]
+    );
+}
+
+async fn invalid_years(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    // Negative years, unique to this run; no real cohort or other check uses them.
+    let bad = -3 * (1 + (Uuid::new_v4().as_u128() % 100_000) as i32);
+    let (typo, other, fixed) = (bad, bad + 1, bad + 2);
+
+    let mut ids = vec![];
+    for (title, year) in [("Typo B", typo), ("Typo A", typo), ("Other", other)] {
+        let req = CreateProjectRequest { year, ..project(&tag, title) };
+        ids.push(repo.create_project(req, owner.id).await.id);
+    }
+    let mine = async |min, max| {
+        let outside = repo.get_projects_outside_years(min, max).await;
+        outside
+            .into_iter()
+            .filter(|p| ids.contains(&p.project_id))
+            .map(|p| (p.title, p.year))
+            .collect::<Vec<_>>()
+    };
+
+    // By year, then title; the bounds themselves are inside.
+    assert_eq!(
+        mine(fixed, i32::MAX).await,
+        vec![(format!("Typo A {tag}"), typo), (format!("Typo B {tag}"), typo), (format!("Other {tag}"), other)]
+    );
+    assert_eq!(mine(other, i32::MAX).await.len(), 2);
+
+    assert_eq!(repo.remap_project_year(typo, fixed).await, 2);
+    assert_eq!(repo.remap_project_year(typo, fixed).await, 0);
+    assert_eq!(mine(fixed, i32::MAX).await, vec![(format!("Other {tag}"), other)]);
+
+    // Each moved project gets a slug for its new year and keeps answering to the old one.
+    let moved = repo.get_project(ids[0]).await.unwrap();
+    assert_eq!((moved.year, moved.slug.clone()), (fixed, format!("typo-b-{tag}-{fixed}")));
+    assert_eq!(repo.resolve_project_slug(&format!("typo-b-{tag}-{typo}")).await, Some(ids[0]));
+    assert_eq!(repo.get_project(ids[2]).await.unwrap().year, other);
+}
+
+async fn user_activity(backend: &Backend) {
+    let repo = &*backend.repo;
+    let user = backend.user("student").await;
+    let other = backend.user("student").await;
+    let tag = new_tag();
+    let own = repo.create_project(project(&tag, "Own"), user.id).await;
+    let theirs = repo.create_project(project(&tag, "Theirs"), other.id).await;
+    repo.set_project_status(theirs.id, ProjectStatus::Approved).await.unwrap();
+    let page = |page: i64, per_page: i64| PageRequest { page, per_page, after: None };
+    let kinds = |items: &[ActivityItem]| {
+        items
+            .iter()
+            .map(|item| match item {
+                ActivityItem::Commented { comment, .. } => format!("commented {comment}"),
+                ActivityItem::Liked { project_title, .. } => format!("liked {project_title}"),
+                ActivityItem::StatusChanged { is_public, .. } => format!("status {is_public}"),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // The three sources, interleaved in time.
+    repo.set_project_status(own.id, ProjectStatus::Approved).await.unwrap();
+    repo.like_project(Like { user_id: user.id, project_id: theirs.id }).await.unwrap();
+    repo.add_comment(theirs.id, user.id, "First".to_string(), 500).await.unwrap().unwrap();
+    // Setting the status it already has is not a change.
+    repo.set_project_status(own.id, ProjectStatus::Approved).await.unwrap();
+    repo.set_project_status(own.id, ProjectStatus::Rejected).await.unwrap();
+    repo.add_comment(theirs.id, user.id, "Second".to_string(), 500).await.unwrap().unwrap();
+
+    let (items, total) = repo.get_user_activity(user.id, page(1, 20)).await;
+    assert_eq!(total, 5);
+    assert_eq!(
+        kinds(&items),
+        [
+            "commented Second".to_string(),
+            "status false".to_string(),
+            "commented First".to_string(),
+            format!("liked {}", theirs.title),
+            "status true".to_string(),
+        ]
+    );
+    assert!(items.windows(2).all(|pair| pair[0].at() >= pair[1].at()));
+    assert!(matches!(&items[3], ActivityItem::Liked { project_id, .. } if *project_id == theirs.id));
+    assert!(matches!(&items[4], ActivityItem::StatusChanged { project_title, .. } if *project_title == own.title));
+
+    // Pages split the same order.
+    let mut paged = Vec::new();
+    for n in 1..=3 {
+        let (items, total) = repo.get_user_activity(user.id, page(n, 2)).await;
+        assert_eq!(total, 5);
+        assert_eq!(items.len(), if n == 3 { 1 } else { 2 });
+        paged.extend(items);
+    }
+    assert_eq!(paged, items);
+
+    // Status changes of someone else's project are theirs, not the liker's.
+    let (their_items, _) = repo.get_user_activity(other.id, page(1, 20)).await;
+    assert_eq!(kinds(&their_items), ["status true"]);
+
+    // Once the project is hidden, the user's comments and likes on it drop out.
+    repo.set_project_status(theirs.id, ProjectStatus::Rejected).await.unwrap();
+    let (items, total) = repo.get_user_activity(user.id, page(1, 20)).await;
+    assert_eq!(total, 2);
+    assert_eq!(kinds(&items), ["status false", "status true"]);
+
+    // Nothing older than the 90-day window (only Postgres rows can be backdated).
+    if let Some(pool) = &backend.pool {
+        sqlx::query!(
+            "UPDATE project_status_history SET changed_at = NOW() - INTERVAL '91 days' WHERE project_id = $1 AND is_public",
+            own.id
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        let (items, total) = repo.get_user_activity(user.id, page(1, 20)).await;
+        assert_eq!(total, 1);
+        assert_eq!(kinds(&items), ["status false"]);
+    }
+}
+
+async fn settings_upsert(backend: &Backend) {
+    let repo = &*backend.repo;
+    let admin = backend.user("admin").await;
+    // The table is shared, so this test only touches its own flag.
+    let key = format!("flag.conformance_{}", new_tag());
+    let mine = async || repo.get_settings().await.unwrap().into_iter().filter(|s| s.key == key).collect::<Vec<_>>();
+    assert!(mine().await.is_empty());
+
+    let first = repo.put_setting(&key, serde_json::json!(true), admin.id).await.unwrap();
+    assert_eq!(first.value, serde_json::json!(true));
+    assert_eq!(first.updated_by, Some(admin.id));
+    assert!(first.updated_at.is_some());
+
+    let second = repo.put_setting(&key, serde_json::json!(false), admin.id).await.unwrap();
+    assert!(second.updated_at >= first.updated_at);
+    assert_eq!(mine().await, vec![second]);
+}
+
+async fn anonymous_feedback(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    let public = repo.create_project(project(&tag, "Public"), owner.id).await;
+    repo.set_project_status(public.id, ProjectStatus::Approved).await.unwrap();
+    let private = repo.create_project(project(&tag, "Private"), owner.id).await;
+
+    assert!(repo.add_feedback(private.id, "Nice work on this!", None).await.is_none());
+    assert!(repo.add_feedback(Uuid::new_v4(), "Nice work on this!", None).await.is_none());
+    assert!(repo.get_feedback(Some(private.id)).await.is_empty());
+
+    let first = repo.add_feedback(public.id, "Nice work on this!", None).await.unwrap();
+    let second = repo.add_feedback(public.id, "Could you share the dataset?", Some("v@example.com")).await.unwrap();
+    assert_eq!(second.project_id, public.id);
+    assert_eq!(second.contact_email.as_deref(), Some("v@example.com"));
+    assert_eq!(repo.get_feedback(Some(public.id)).await, vec![second.clone(), first.clone()]);
+    // The unscoped listing includes other tests' feedback, so only look for this test's rows.
+    let all: Vec<_> = repo.get_feedback(None).await.into_iter().filter(|f| f.project_id == public.id).collect();
+    assert_eq!(all, vec![second, first]);
+
+    let notifications = repo.get_notifications(owner.id).await;
+    assert_eq!(notifications.len(), 2);
+    assert!(notifications.iter().all(|n| n.notification_type == "feedback" && n.project_id == public.id));
+}
+
+async fn stale_draft_sweeps(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let tag = new_tag();
+    let draft = repo.create_project(project(&tag, "Draft"), owner.id).await;
+    let submitted = repo.create_project(project(&tag, "Submitted"), owner.id).await;
+    repo.mark_project_submitted(submitted.id).await.unwrap();
+    let published = repo.create_project(project(&tag, "Published"), owner.id).await;
+    repo.set_project_status(published.id, ProjectStatus::Approved).await.unwrap();
+
+    // Other tests' drafts share the database, so only this test's projects are considered.
+    let mine = [draft.id, submitted.id, published.id];
+    let stale = |drafts: Vec<fyp_portal::models::StaleDraft>| {
+        drafts.into_iter().map(|d| d.project_id).filter(|id| mine.contains(id)).collect::<Vec<_>>()
+    };
+    let later = Utc::now() + chrono::Duration::days(8);
+    assert!(stale(repo.get_stale_drafts(DraftSweep::Reminder, draft.updated_at).await).is_empty());
+    assert_eq!(stale(repo.get_stale_drafts(DraftSweep::Reminder, later).await), vec![draft.id]);
+
+    assert!(repo.mark_stale_draft(DraftSweep::Reminder, draft.id, later).await);
+    assert!(!repo.mark_stale_draft(DraftSweep::Reminder, draft.id, later).await);
+    assert!(!repo.mark_stale_draft(DraftSweep::Reminder, submitted.id, later).await);
+    assert!(stale(repo.get_stale_drafts(DraftSweep::Reminder, later).await).is_empty());
+    let notifications = repo.get_notifications(owner.id).await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].notification_type, "draft_reminder");
+    assert_eq!(notifications[0].project_id, draft.id);
+
+    // The cleanup flag is tracked separately from the reminder.
+    assert_eq!(stale(repo.get_stale_drafts(DraftSweep::Cleanup, later).await), vec![draft.id]);
+    assert!(repo.mark_stale_draft(DraftSweep::Cleanup, draft.id, later).await);
+    assert!(stale(repo.get_stale_drafts(DraftSweep::Cleanup, later).await).is_empty());
+    assert_eq!(repo.get_notifications(owner.id).await.len(), 1);
+}
+
+/// Every public read path applies the same visibility rule: of one project per status, only the
+/// approved one is returned, counted or has its comments shown.
+async fn public_read_paths_share_one_visibility_rule(backend: &Backend) {
+    let repo = &*backend.repo;
+    let owner = backend.user("student").await;
+    let commenter = backend.user("student").await;
+    let tag = new_tag();
+    let label = format!("vis-{}", &tag[..8]);
+    // A cohort year unique to this run, above the range `project_years` uses.
+    let year = 400_000 + (Uuid::new_v4().as_u128() % 100_000) as i32;
+    let since = Utc::now() - chrono::Duration::minutes(1);
+
+    let mut ids = Vec::new();
+    let mut approved = None;
+    for status in [ProjectStatus::Draft, ProjectStatus::Pending, ProjectStatus::Approved, ProjectStatus::Rejected] {
+        let request = CreateProjectRequest { year, tags: vec![label.clone()], ..project(&tag, status.as_str()) };
+        let p = repo.create_project(request, owner.id).await;
+        repo.add_comment(p.id, commenter.id, "Visible?".to_string(), 500).await.unwrap().unwrap();
+        if status != ProjectStatus::Draft {
+            repo.set_project_status(p.id, status).await.unwrap();
+        }
+        if status == ProjectStatus::Approved {
+            approved = Some(p.id);
+        }
+        ids.push(p.id);
+    }
+    let approved = approved.unwrap();
+    let only_approved = |found: Vec<Uuid>| assert_eq!(found, vec![approved]);
+
+    let (items, total) = repo.get_projects(tagged(&tag), PageRequest::default()).await;
+    assert_eq!(total, 1);
+    only_approved(items.into_iter().map(|p| p.id).collect());
+    let with_label = ProjectListFilter { tag: Some(label.clone()), ..ProjectListFilter::default() };
+    only_approved(repo.get_projects(with_label, PageRequest::default()).await.0.into_iter().map(|p| p.id).collect());
+    only_approved(repo.get_top_projects(10, 0, Some(year)).await.into_iter().map(|p| p.id).collect());
+    only_approved(repo.get_year_archive(year).await.into_iter().map(|p| p.id).collect());
+    only_approved(repo.get_changed_project_ids(since, Some(ids.clone())).await);
+    assert_eq!(
+        repo.get_tag_counts().await.into_iter().filter(|c| c.tag == label).collect::<Vec<_>>(),
+        vec![TagCount { tag: label.clone(), count: 1 }]
+    );
+    assert_eq!(
+        repo.get_project_years().await.into_iter().filter(|y| y.year == year).collect::<Vec<_>>(),
+        vec![YearCount { year, count: 1 }]
+    );
+
+    for id in ids {
+        let public = id == approved;
+        assert_eq!(repo.get_public_project(id).await.is_some(), public);
+        let comments = repo.get_comments(id, CommentOrder::Oldest, HiddenComments::Exclude).await;
+        assert_eq!(comments.len(), usize::from(public));
+        assert_eq!(repo.count_comments(id).await, public.then_some(1));
+    }
+}
+
+/// Filters, pagination, the outbox fallback and retention of the audit log. One check, because
+/// the purge deletes every older entry in the shared database.
+async fn audit_log(backend: &Backend) {
+    use futures::TryStreamExt;
+    let repo = &*backend.repo;
+    // Random actors, so other rows never match the filters.
+    let (admin, other) = (Uuid::new_v4(), Uuid::new_v4());
+    let entry = |actor, action, target_type| NewAuditEntry::new(Some(actor), action, target_type, new_tag());
+    for (actor, action, target_type) in [
+        (admin, "project.status", "project"),
+        (other, "project.status", "project"),
+        (admin, "setting.put", "setting"),
+    ] {
+        repo.record_audit(&entry(actor, action, target_type)).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    let by = |actor| AuditFilter { actor_id: Some(actor), ..AuditFilter::default() };
+    let (mine, total) = repo.get_audit_log(&by(admin), PageRequest::default()).await;
+    assert_eq!(total, 2);
+    assert_eq!(mine.iter().map(|e| e.action.as_str()).collect::<Vec<_>>(), ["setting.put", "project.status"]);
+    let (page, total) = repo.get_audit_log(&by(admin), PageRequest { page: 2, per_page: 1, after: None }).await;
+    assert_eq!((page[0].id, total), (mine[1].id, 2));
+    let filter = AuditFilter { action: Some("setting.put".to_string()), target_type: Some("setting".to_string()), ..by(admin) };
+    assert_eq!(repo.get_audit_log(&filter, PageRequest::default()).await.1, 1);
+    let filter = AuditFilter { target_type: Some("project".to_string()), ..by(other) };
+    assert_eq!(repo.get_audit_log(&filter, PageRequest::default()).await.1, 1);
+
+    // `from` is inclusive, `to` exclusive.
+    let range = |from, to| AuditFilter { from: Some(from), to: Some(to), ..by(admin) };
+    let (older, newer) = (mine[1].created_at, mine[0].created_at);
+    let ids = |(entries, _): (Vec<fyp_portal::models::AuditEntry>, i64)| entries.into_iter().map(|e| e.id).collect::<Vec<_>>();
+    assert_eq!(ids(repo.get_audit_log(&range(older, newer), PageRequest::default()).await), [mine[1].id]);
+    assert_eq!(ids(repo.get_audit_log(&range(newer, Utc::now()), PageRequest::default()).await), [mine[0].id]);
+
+    // The CSV stream has the same entries in the same order, without a page.
+    let streamed: Vec<_> = repo.stream_audit_log(by(admin)).try_collect().await.unwrap();
+    assert_eq!(streamed, mine);
+
+    // A queued entry is written by the flush, dated when it was queued, and only once.
+    let queued = entry(admin, "comment.restore", "comment");
+    let job_id = repo.queue_audit(&queued).await.unwrap();
+    let job = repo.get_outbox_job(job_id).await.unwrap();
+    assert_eq!((job.kind.as_str(), job.status.as_str()), ("audit_entry", "pending"));
+    assert!(repo.flush_audit_outbox().await.unwrap() >= 1);
+    assert_eq!(repo.get_outbox_job(job_id).await.unwrap().status, "done");
+    repo.flush_audit_outbox().await.unwrap();
+    let filter = AuditFilter { action: Some("comment.restore".to_string()), ..by(admin) };
+    let (flushed, total) = repo.get_audit_log(&filter, PageRequest::default()).await;
+    assert_eq!(total, 1);
+    assert_eq!((flushed[0].target_id.clone(), flushed[0].created_at), (queued.target_id, job.created_at));
+
+    // Entries created exactly at the cutoff are kept; older ones are purged.
+    let oldest = mine[1].created_at;
+    repo.purge_audit_log(oldest).await.unwrap();
+    assert_eq!(repo.get_audit_log(&by(admin), PageRequest::default()).await.1, 3);
+    assert!(repo.purge_audit_log(oldest + chrono::Duration::microseconds(1)).await.unwrap() >= 1);
+    assert_eq!(ids(repo.get_audit_log(&by(admin), PageRequest::default()).await), [flushed[0].id, mine[0].id]);
+}
@@ -1,9 +1,13 @@
 use chrono::Utc;
-use dotenv; // Added import for dotenv
 use fyp_portal::{
-    models::{CreateProjectRequest, Project, UpdateProjectRequest, User},
-    repository::{PostgresRepository, Repository},
+    models::{ProjectStatus, 
+        CommentOrder, CreateProjectRequest, DELETED_COMMENT_TEXT, Project, PublicStats, SubscriptionState,
+        UpdateProjectRequest, UploadCounts, UploadPurpose, User, OwnerTransferOutcome, Like, LikeOutcome, NewNotification,
+    },
+    pagination::{MAX_PER_PAGE, PageRequest},
+    repository::{HiddenComments, PostgresRepository, ProjectListFilter, Repository, query_public_stats},
 };
+use futures::StreamExt;
 use sqlx::PgPool;
 use tokio::test;
 use uuid::Uuid;
@@ -46,7 +50,7 @@ async fn create_test_user(pool: &PgPool, id: Uuid, role: &str) -> User {
     let email = format!("{}@test.com", role);
 
     // Use a CTE to ensure both inserts happen atomically
-    let created_user = sqlx::query_as!(
+    sqlx::query_as!(
         User,
         r#"
         WITH auth_user AS (
@@ -66,9 +70,7 @@ async fn create_test_user(pool: &PgPool, id: Uuid, role: &str) -> User {
     )
     .fetch_one(pool)
     .await
-    .expect("Failed to create test user");
-
-    created_user
+    .expect("Failed to create test user")
 }
 
 /// Inserts a mock project into the database directly.
@@ -94,14 +96,15 @@ async fn create_test_project(
         r#"INSERT INTO public.projects (
              id, user_id, author, title, abstract, cover_image, 
              video, report, 
-             year, is_public, report_is_public, created_at, updated_at
+             year, status, report_is_public, created_at, updated_at, slug
            )
-           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, CASE WHEN $10 THEN 'approved' ELSE 'draft' END, $11, $12, $13, $14)
            RETURNING 
              id, user_id, author, title, abstract as abstract_text, cover_image, 
              video, report, 
-             is_public, report_is_public, year, created_at, updated_at"#,
-        // --- 13 PARAMETERS LISTED HERE ---
+             status AS "status: ProjectStatus", is_public, report_is_public, comments_locked, links, video_waived, submitted_at, supervisor, department, technologies, view_count, slug, year, created_at, updated_at,
+             0::bigint AS "comment_count!", ARRAY[]::text[] AS "tags!""#,
+        // --- 14 PARAMETERS LISTED HERE ---
         project_uuid,    // $1: id (Uuid)
         user_id,         // $2: user_id (Uuid)
         author_name,     // $3: author (&str)
@@ -111,10 +114,11 @@ async fn create_test_project(
         video_key as _,  // $7: video (Option<String>)
         report_key as _, // $8: report (Option<String>)
         year,            // $9: year (i32)
-        is_public,       // $10: is_public (bool)
+        is_public,       // $10: status, approved or draft (bool)
         report_pub,      // $11: report_is_public (bool)
         created,         // $12: created_at (DateTime<Utc>)
         updated,         // $13: updated_at (DateTime<Utc>)
+        format!("test-{project_uuid}"), // $14: slug (String)
     )
     // REMOVE all .bind() calls after the macro
     .fetch_one(pool)
@@ -138,6 +142,13 @@ async fn test_create_and_get_project() {
         cover_image_key: "key1".to_string(),
         video_key: None,
         report_key: None,
+        links: None,
+        supervisor: None,
+        department: None,
+        technologies: vec![],
+        programme_codes: None,
+        tags: vec![],
+        force: false,
     };
 
     // 1. Test Create
@@ -167,8 +178,16 @@ async fn test_get_projects_with_filters() {
     create_test_project(&ctx.pool, user.id, "Search Rust Query", 2024, true).await;
     create_test_project(&ctx.pool, user.id, "Hidden Project", 2024, false).await; // Private
 
+    // Newest first, so a full page always includes the rows created above.
+    let wide = PageRequest { page: 1, per_page: MAX_PER_PAGE, after: None };
+    let filter = |year: Option<i32>, search: Option<&str>| ProjectListFilter {
+        year,
+        search: search.map(str::to_string),
+        ..ProjectListFilter::default()
+    };
+
     // Test 1: No filter (Should only return public projects)
-    let all_projects = repo.get_projects(None, None).await;
+    let all_projects = repo.get_projects(ProjectListFilter::default(), wide).await.0;
     let our_projects: Vec<_> = all_projects
         .iter()
         .filter(|p| p.user_id == user.id)
@@ -180,7 +199,7 @@ async fn test_get_projects_with_filters() {
     );
 
     // Test 2: Filter by year (2024)
-    let year_projects = repo.get_projects(Some(2024), None).await;
+    let year_projects = repo.get_projects(filter(Some(2024), None), wide).await.0;
     let our_2024: Vec<_> = year_projects
         .iter()
         .filter(|p| p.user_id == user.id)
@@ -192,7 +211,7 @@ async fn test_get_projects_with_filters() {
     );
 
     // Test 3: Filter by search term ("Rust")
-    let search_projects = repo.get_projects(None, Some("Rust".to_string())).await;
+    let search_projects = repo.get_projects(filter(None, Some("Rust")), wide).await.0;
     let our_rust: Vec<_> = search_projects
         .iter()
         .filter(|p| p.user_id == user.id)
@@ -205,8 +224,9 @@ async fn test_get_projects_with_filters() {
 
     // Test 4: Filter by year and search
     let filtered_projects = repo
-        .get_projects(Some(2024), Some("Backend".to_string()))
-        .await;
+        .get_projects(filter(Some(2024), Some("Backend")), wide)
+        .await
+        .0;
     let our_filtered: Vec<_> = filtered_projects
         .iter()
         .filter(|p| p.user_id == user.id)
@@ -218,6 +238,42 @@ async fn test_get_projects_with_filters() {
     );
 }
 
+#[test]
+async fn test_get_projects_pages_share_the_filtered_total() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let user = create_test_user(&ctx.pool, Uuid::new_v4(), "student").await;
+    let tag = Uuid::new_v4().simple().to_string();
+
+    for i in 0..5 {
+        create_test_project(&ctx.pool, user.id, &format!("Paged {tag} {i}"), 2024, true).await;
+    }
+    create_test_project(&ctx.pool, user.id, &format!("Paged {tag} old"), 2023, true).await;
+    create_test_project(&ctx.pool, user.id, &format!("Paged {tag} private"), 2024, false).await;
+
+    let filter = ProjectListFilter {
+        year: Some(2024),
+        search: Some(tag),
+        ..ProjectListFilter::default()
+    };
+    let page = |page| PageRequest { page, per_page: 2, after: None };
+
+    let mut seen = Vec::new();
+    for n in 1..=3 {
+        let (items, total) = repo.get_projects(filter.clone(), page(n)).await;
+        assert_eq!(total, 5, "total_count must count every match, not just page {n}");
+        seen.extend(items.into_iter().map(|p| p.id));
+    }
+    assert_eq!(seen.len(), 5);
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), 5, "pages must not overlap");
+
+    let (beyond, total) = repo.get_projects(filter, page(4)).await;
+    assert!(beyond.is_empty());
+    assert_eq!(total, 5);
+}
+
 #[test]
 async fn test_update_and_delete_project_ownership() {
     let ctx = DbTestContext::setup().await;
@@ -233,6 +289,15 @@ async fn test_update_and_delete_project_ownership() {
         cover_image_key: None,
         video_key: None,
         report_key: None,
+        author_name: Some("  Corrected Author ".to_string()),
+        year: Some(2024),
+        links: None,
+        video_waived: None,
+        supervisor: None,
+        department: None,
+        technologies: None,
+        programme_codes: None,
+        tags: None,
     };
     let updated_project_fail = repo
         .update_project(project.id, non_owner.id, update_req.clone())
@@ -241,11 +306,21 @@ async fn test_update_and_delete_project_ownership() {
         updated_project_fail.is_none(),
         "Non-owner should not be able to update."
     );
+    let untouched = repo.get_project(project.id).await.unwrap();
+    assert_eq!((untouched.year, untouched.author.as_str()), (2023, project.author.as_str()));
 
     // Test 2: Update by Owner (Should succeed)
     let updated_project_success = repo.update_project(project.id, owner.id, update_req).await;
     assert!(updated_project_success.is_some());
-    assert_eq!(updated_project_success.unwrap().title, "New Title");
+    let result = updated_project_success.unwrap();
+    assert_eq!(result.project.title, "New Title");
+    // The author is stored trimmed, and the new year shows in the slug.
+    assert_eq!(result.project.author, "Corrected Author");
+    assert_eq!(result.project.year, 2024);
+    assert!(result.project.slug.ends_with("-2024"), "{}", result.project.slug);
+    for field in ["title", "author_name", "year"] {
+        assert!(result.changed_fields.contains(&field.to_string()), "{field}");
+    }
 
     // Test 3: Delete by Non-Owner (Should fail)
     let delete_fail = repo.delete_project(project.id, non_owner.id).await;
@@ -271,12 +346,15 @@ async fn test_comment_lifecycle_and_deletion() {
     // 1. Add comment
     let comment_text = "This is a great project!";
     let comment = repo
-        .add_comment(project.id, user.id, comment_text.to_string())
-        .await;
+        .add_comment(project.id, user.id, comment_text.to_string(), 500)
+        .await
+        .unwrap()
+        .unwrap()
+        .comment;
     assert_eq!(comment.comment, comment_text);
 
     // 2. Retrieve comments
-    let comments = repo.get_comments(project.id).await;
+    let comments = repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Exclude).await;
     assert_eq!(comments.len(), 1);
     assert_eq!(comments[0].author_email.as_ref().unwrap(), &user.email);
 
@@ -290,7 +368,7 @@ async fn test_comment_lifecycle_and_deletion() {
     assert!(delete_success_admin);
 
     // Verify deletion
-    let comments_after_delete = repo.get_comments(project.id).await;
+    let comments_after_delete = repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Exclude).await;
     assert!(comments_after_delete.is_empty());
 }
 
@@ -302,43 +380,957 @@ async fn test_notification_and_read_status() {
     let actor = create_test_user(&ctx.pool, Uuid::new_v4(), "actor").await;
     let project = create_test_project(&ctx.pool, recipient.id, "Notif Project", 2024, true).await;
 
-    // Directly insert a notification (simulating a complex trigger like a comment)
-    let notification_id = Uuid::new_v4();
-    sqlx::query(
-        r#"INSERT INTO public.notifications (id, user_id, actor_id, project_id, type, is_read, created_at) 
-          VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
-    )
-    .bind(notification_id)
-    .bind(recipient.id)
-    .bind(actor.id)
-    .bind(project.id)
-    .bind("comment")
-    .bind(false)
-    .bind(Utc::now())
-    .execute(&ctx.pool)
-    .await
-    .expect("Failed to create test notification");
-
-    // 1. Get notifications
+    // 1. A like notifies the owner once, however often it is repeated; the owner's own
+    // (refused) like and comment notify nobody.
+    let like = |user_id| Like { user_id, project_id: project.id };
+    assert_eq!(repo.like_project(like(actor.id)).await, Some(LikeOutcome::Created(1)));
+    assert_eq!(repo.like_project(like(actor.id)).await, Some(LikeOutcome::AlreadyExists(1)));
+    assert_eq!(repo.like_project(like(recipient.id)).await, Some(LikeOutcome::OwnProject(1)));
+    repo.add_comment(project.id, recipient.id, "Thanks all".to_string(), 500).await.unwrap().unwrap();
     let notifs = repo.get_notifications(recipient.id).await;
     assert_eq!(notifs.len(), 1);
+    assert_eq!(notifs[0].notification_type, "like");
     assert!(!notifs[0].is_read);
     assert_eq!(notifs[0].project_title, project.title);
     assert_eq!(notifs[0].actor_email, actor.email);
 
-    // 2. Mark as read
-    let mark_success = repo
-        .mark_notification_read(notification_id, recipient.id)
-        .await;
-    assert!(mark_success);
+    // 2. A comment by someone else notifies the owner in the same way.
+    repo.add_comment(project.id, actor.id, "Great work".to_string(), 500).await.unwrap().unwrap();
+    let notifs = repo.get_notifications(recipient.id).await;
+    assert_eq!(notifs.iter().filter(|n| n.notification_type == "comment").count(), 1);
+
+    // 3. `create_notification` writes the row directly, and skips self-notifications.
+    let created = repo
+        .create_notification(NewNotification::new(recipient.id, actor.id, project.id, "status_change").with_message("Approved"))
+        .await
+        .expect("notification is written");
+    assert_eq!(created.notification_type, "status_change");
+    assert_eq!(created.message.as_deref(), Some("Approved"));
+    assert!(repo.create_notification(NewNotification::new(actor.id, actor.id, project.id, "like")).await.is_none());
+    assert!(repo.get_notifications(actor.id).await.is_empty());
+
+    // 4. Mark as read, through the owner only.
+    assert!(!repo.mark_notification_read(created.id, actor.id).await);
+    assert!(repo.mark_notification_read(created.id, recipient.id).await);
+    let notifs = repo.get_notifications(recipient.id).await;
+    assert_eq!(notifs.len(), 3);
+    assert!(notifs.iter().find(|n| n.id == created.id).unwrap().is_read);
+    assert_eq!(notifs.iter().filter(|n| !n.is_read).count(), 2);
+}
+
+#[test]
+async fn test_comments_lock_owner_and_cohort() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "lockowner").await;
+    let other = create_test_user(&ctx.pool, Uuid::new_v4(), "lockother").await;
+
+    // A cohort year no other test uses, so the bulk count is deterministic.
+    let year = 3000 + (Uuid::new_v4().as_u128() % 100_000) as i32;
+    let first = create_test_project(&ctx.pool, owner.id, "Lock A", year, true).await;
+    let second = create_test_project(&ctx.pool, owner.id, "Lock B", year, true).await;
+    let _third = create_test_project(&ctx.pool, owner.id, "Lock C", year, false).await;
+    assert!(!first.comments_locked);
+
+    let comment = repo
+        .add_comment(first.id, other.id, "Before the lock".to_string(), 500)
+        .await
+        .unwrap()
+        .unwrap()
+        .comment;
 
-    // 3. Verify read status (direct SQL check)
-    let is_read: bool =
-        sqlx::query_scalar("SELECT is_read FROM public.notifications WHERE id = $1")
-            .bind(notification_id)
-            .fetch_one(&ctx.pool)
+    // 1. Non-owner cannot lock; owner can.
+    assert!(repo.set_comments_locked(first.id, other.id, true).await.is_none());
+    let locked = repo.set_comments_locked(first.id, owner.id, true).await;
+    assert!(locked.unwrap().comments_locked);
+    assert!(repo.are_comments_locked(first.id).await);
+    assert!(!repo.are_comments_locked(second.id).await);
+
+    // 2. Bulk lock only counts projects that were still open.
+    assert_eq!(repo.lock_comments_for_year(year).await, 2);
+    assert!(repo.are_comments_locked(second.id).await);
+    assert_eq!(repo.lock_comments_for_year(year).await, 0);
+
+    // 3. Existing comments remain readable.
+    let comments = repo.get_comments(first.id, CommentOrder::Oldest, HiddenComments::Exclude).await;
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].id, comment.id);
+
+    // 4. Admin override can reopen discussion.
+    let reopened = repo.set_comments_locked_admin(first.id, false).await;
+    assert!(!reopened.unwrap().comments_locked);
+}
+
+#[test]
+async fn test_bulk_status_single_update_and_history() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "bulkowner").await;
+    let year = 3000 + (Uuid::new_v4().as_u128() % 100_000) as i32;
+    let draft = create_test_project(&ctx.pool, owner.id, "Bulk A", year, false).await;
+    let other = create_test_project(&ctx.pool, owner.id, "Bulk B", year, false).await;
+    let published = create_test_project(&ctx.pool, owner.id, "Bulk C", year, true).await;
+    let unknown = Uuid::new_v4();
+    let history = async |id: Uuid| {
+        sqlx::query_scalar!("SELECT is_public FROM project_status_history WHERE project_id = $1 ORDER BY id", id)
+            .fetch_all(&ctx.pool)
             .await
-            .expect("Failed to fetch notification read status");
+            .unwrap()
+    };
+    let status = async |id: Uuid| repo.get_project(id).await.unwrap().status;
+
+    // The already-approved project is found but not counted as changed.
+    let outcome = repo.set_projects_status_bulk(&[draft.id, unknown, published.id, other.id], true).await;
+    let mut changed = outcome.changed.clone();
+    changed.sort();
+    let mut expected = vec![draft.id, other.id];
+    expected.sort();
+    assert_eq!(changed, expected);
+    assert_eq!(outcome.not_found, vec![unknown]);
+    assert_eq!(status(draft.id).await, ProjectStatus::Approved);
+    assert!(repo.get_public_project(other.id).await.is_some());
+    // The status history trigger fires per changed row, not for the unchanged one.
+    assert_eq!(history(draft.id).await, vec![true]);
+    assert!(history(published.id).await.is_empty());
+
+    // Hiding rejects; an empty batch touches nothing.
+    let outcome = repo.set_projects_status_bulk(&[published.id], false).await;
+    assert_eq!(outcome.changed, vec![published.id]);
+    assert_eq!(status(published.id).await, ProjectStatus::Rejected);
+    assert_eq!(history(published.id).await, vec![false]);
+    assert_eq!(repo.set_projects_status_bulk(&[], true).await, Default::default());
+}
+
+#[test]
+async fn test_links_waiver_and_submission_persist() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let user = create_test_user(&ctx.pool, Uuid::new_v4(), "submitter").await;
+
+    let req = CreateProjectRequest {
+        title: "Submission Test".to_string(),
+        abstract_text: "Abstract".to_string(),
+        author_name: "Submitter".to_string(),
+        year: 2025,
+        cover_image_key: "cover".to_string(),
+        video_key: None,
+        report_key: None,
+        links: Some([("repository".to_string(), "https://example.com/repo".to_string())].into()),
+        supervisor: None,
+        department: None,
+        technologies: vec![],
+        programme_codes: None,
+        tags: vec![],
+        force: false,
+    };
+    let created = repo.create_project(req, user.id).await;
+    assert_eq!(created.links["repository"], "https://example.com/repo");
+    assert!(!created.video_waived);
+    assert!(created.submitted_at.is_none());
+
+    let updated = repo
+        .update_project(
+            created.id,
+            user.id,
+            UpdateProjectRequest {
+                video_waived: Some(true),
+                ..UpdateProjectRequest::default()
+            },
+        )
+        .await
+        .unwrap()
+        .project;
+    assert!(updated.video_waived);
+    // Links are untouched when not supplied.
+    assert_eq!(updated.links["repository"], "https://example.com/repo");
+
+    let submitted = repo.mark_project_submitted(created.id).await.unwrap();
+    assert!(submitted.submitted_at.is_some());
+}
+
+#[test]
+async fn test_supervisor_report_aggregates_and_unassigned_bucket() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "supervised").await;
+    let liker_a = create_test_user(&ctx.pool, Uuid::new_v4(), "likera").await;
+    let liker_b = create_test_user(&ctx.pool, Uuid::new_v4(), "likerb").await;
+
+    // Unique year and supervisor names keep this test independent of shared DB state.
+    let year = 3000 + (Uuid::new_v4().as_u128() % 100_000) as i32;
+    let tag = Uuid::new_v4().simple().to_string();
+    let dr_x = format!("Dr. X {}", tag);
+
+    let set_supervisor = |id: Uuid, name: Option<String>| {
+        let pool = ctx.pool.clone();
+        async move {
+            sqlx::query("UPDATE projects SET supervisor = $1 WHERE id = $2")
+                .bind(name)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+    };
+
+    let a = create_test_project(&ctx.pool, owner.id, "Sup A", year, true).await;
+    // B is liked while approved and taken down afterwards: likes are only accepted on public
+    // projects, but a hidden project keeps the ones it already has.
+    let b = create_test_project(&ctx.pool, owner.id, "Sup B", year, true).await;
+    let c = create_test_project(&ctx.pool, owner.id, "Sup C", year, true).await;
+    let d = create_test_project(&ctx.pool, owner.id, "Sup D", year, true).await;
+    set_supervisor(a.id, Some(dr_x.clone())).await;
+    set_supervisor(b.id, Some(dr_x.clone())).await;
+    set_supervisor(c.id, Some("   ".to_string())).await;
+    set_supervisor(d.id, None).await;
+
+    // Dr. X: 3 likes across 2 projects (avg 1.5), 1 comment.
+    for (project, liker) in [(a.id, liker_a.id), (a.id, liker_b.id), (b.id, liker_a.id)] {
+        assert!(matches!(
+            repo.like_project(fyp_portal::models::Like {
+                user_id: liker,
+                project_id: project,
+            })
+            .await,
+            Some(fyp_portal::models::LikeOutcome::Created(_))
+        ));
+    }
+    sqlx::query("UPDATE projects SET status = 'draft' WHERE id = $1")
+        .bind(b.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    repo.add_comment(a.id, liker_a.id, "Nice".to_string(), 500).await.unwrap().unwrap();
+    // Unassigned: 1 comment, no likes.
+    repo.add_comment(c.id, liker_b.id, "Hi".to_string(), 500).await.unwrap().unwrap();
+
+    let report = repo.get_supervisor_report(Some(year)).await;
+    assert_eq!(report.len(), 2, "Only Dr. X and Unassigned exist in this year");
+
+    let x = report.iter().find(|r| r.supervisor == dr_x).unwrap();
+    assert_eq!(x.project_count, 2);
+    assert_eq!(x.public_count, 1);
+    assert!((x.avg_likes - 1.5).abs() < f64::EPSILON);
+    assert_eq!(x.total_comments, 1);
+
+    // NULL and blank supervisors share the bucket, which is listed last.
+    let unassigned = report.last().unwrap();
+    assert_eq!(unassigned.supervisor, "Unassigned");
+    assert_eq!(unassigned.project_count, 2);
+    assert_eq!(unassigned.public_count, 2);
+    assert_eq!(unassigned.avg_likes, 0.0);
+    assert_eq!(unassigned.total_comments, 1);
+}
+
+#[test]
+async fn test_status_change_notification_carries_reason() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "statusowner").await;
+    let admin = create_test_user(&ctx.pool, Uuid::new_v4(), "statusadmin").await;
+    let project = create_test_project(&ctx.pool, owner.id, "Status Project", 2024, true).await;
+
+    assert!(
+        repo.notify_status_change(project.id, admin.id, Some("Needs a new cover".to_string()))
+            .await
+    );
+
+    let notifs = repo.get_notifications(owner.id).await;
+    assert_eq!(notifs.len(), 1);
+    assert_eq!(notifs[0].notification_type, "status_change");
+    assert_eq!(notifs[0].message.as_deref(), Some("Needs a new cover"));
+    assert_eq!(notifs[0].actor_email, admin.email);
+
+    // An owner acting on their own project is not notified.
+    assert!(!repo.notify_status_change(project.id, owner.id, None).await);
+}
+
+#[test]
+async fn test_reassign_project_owner_notifies_both_owners() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let previous = create_test_user(&ctx.pool, Uuid::new_v4(), "transferfrom").await;
+    let target = create_test_user(&ctx.pool, Uuid::new_v4(), "transferto").await;
+    let admin = create_test_user(&ctx.pool, Uuid::new_v4(), "transferadmin").await;
+    let project = create_test_project(&ctx.pool, previous.id, "Transferred Project", 2024, false).await;
+
+    // A missing project or target account changes nothing.
+    assert!(repo.reassign_project_owner(Uuid::new_v4(), target.id, admin.id).await.is_none());
+    assert!(matches!(
+        repo.reassign_project_owner(project.id, Uuid::new_v4(), admin.id).await,
+        Some(OwnerTransferOutcome::UnknownOwner)
+    ));
+    assert_eq!(repo.get_project(project.id).await.unwrap().user_id, previous.id);
+
+    let Some(OwnerTransferOutcome::Transferred { project: moved, previous_owner }) =
+        repo.reassign_project_owner(project.id, target.id, admin.id).await
+    else {
+        panic!("expected the project to be transferred");
+    };
+    assert_eq!(previous_owner, previous.id);
+    assert_eq!(moved.user_id, target.id);
+    assert_eq!(repo.get_project(project.id).await.unwrap().user_id, target.id);
+
+    let old_notifs = repo.get_notifications(previous.id).await;
+    assert_eq!(old_notifs.len(), 1);
+    assert_eq!(old_notifs[0].notification_type, "ownership_change");
+    assert_eq!(old_notifs[0].actor_email, admin.email);
+    assert_eq!(old_notifs[0].message.as_deref(), Some("Transferred to transferto@test.com by an admin."));
+    let new_notifs = repo.get_notifications(target.id).await;
+    assert_eq!(new_notifs.len(), 1);
+    assert_eq!(new_notifs[0].notification_type, "ownership_change");
+    assert_eq!(new_notifs[0].project_id, project.id);
+
+    // Reassigning to the current owner sends nothing more.
+    assert!(repo.reassign_project_owner(project.id, target.id, admin.id).await.is_some());
+    assert_eq!(repo.get_notifications(target.id).await.len(), 1);
+}
+
+#[test]
+async fn test_like_unlike_like_cycle_cleans_up_notifications() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "unlikeowner").await;
+    let fan = create_test_user(&ctx.pool, Uuid::new_v4(), "unlikefan").await;
+    let project = create_test_project(&ctx.pool, owner.id, "Unlike Project", 2024, true).await;
+    let like = || Like { user_id: fan.id, project_id: project.id };
+
+    assert_eq!(repo.like_project(like()).await, Some(LikeOutcome::Created(1)));
+    assert_eq!(repo.get_notifications(owner.id).await.len(), 1);
+
+    // Unliking removes the like and the owner's notification; a second unlike finds nothing.
+    assert!(repo.unlike_project(like()).await);
+    assert_eq!(repo.get_notifications(owner.id).await.len(), 0);
+    assert!(!repo.unlike_project(like()).await);
+    assert!(!repo.unlike_project(Like { user_id: fan.id, project_id: Uuid::new_v4() }).await);
+
+    // Liking again counts as a new like and notifies again.
+    assert_eq!(repo.like_project(like()).await, Some(LikeOutcome::Created(1)));
+    assert_eq!(repo.get_notifications(owner.id).await.len(), 1);
+}
+
+#[test]
+async fn test_project_stats_bucket_likes_by_day() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "statsowner").await;
+    let project = create_test_project(&ctx.pool, owner.id, "Stats Project", 2024, true).await;
+
+    // Likes today, yesterday (twice), ten days ago, and one outside the 30-day window.
+    let today = Utc::now().date_naive();
+    for days_ago in [0, 1, 1, 10, 40] {
+        let fan = create_test_user(&ctx.pool, Uuid::new_v4(), "statsfan").await;
+        // Noon UTC, so the like cannot drift into a neighbouring day.
+        let liked_at = (today - chrono::Duration::days(days_ago)).and_hms_opt(12, 0, 0).unwrap().and_utc();
+        sqlx::query("INSERT INTO public.project_likes (user_id, project_id, liked_at) VALUES ($1, $2, $3)")
+            .bind(fan.id)
+            .bind(project.id)
+            .bind(liked_at)
+            .execute(&ctx.pool)
+            .await
+            .expect("Failed to insert like");
+    }
+    repo.increment_view(project.id).await;
+    repo.increment_view(project.id).await;
+
+    let stats = repo.get_project_stats(project.id, owner.id).await.expect("owner sees stats");
+    assert_eq!(stats.like_count, 5);
+    assert_eq!(stats.comment_count, 0);
+    assert_eq!(stats.view_count, 2);
+    assert_eq!(stats.likes_per_day.len(), 30);
+    assert_eq!(stats.likes_per_day.first().unwrap().day, today - chrono::Duration::days(29));
+    let by_day: Vec<(i64, i64)> = stats
+        .likes_per_day
+        .iter()
+        .filter(|d| d.count > 0)
+        .map(|d| ((today - d.day).num_days(), d.count))
+        .collect();
+    assert_eq!(by_day, vec![(10, 1), (1, 2), (0, 1)]);
+
+    // Ownership is part of the query: anyone else, or a missing project, gets nothing.
+    let other = create_test_user(&ctx.pool, Uuid::new_v4(), "statsother").await;
+    assert!(repo.get_project_stats(project.id, other.id).await.is_none());
+    assert!(repo.get_project_stats(Uuid::new_v4(), owner.id).await.is_none());
+}
+
+#[test]
+async fn test_pending_reviews_are_oldest_first_with_owner_email() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "queueowner").await;
+    let late = create_test_project(&ctx.pool, owner.id, "Queue Late", 2024, false).await;
+    let early = create_test_project(&ctx.pool, owner.id, "Queue Early", 2024, false).await;
+    let draft = create_test_project(&ctx.pool, owner.id, "Queue Draft", 2024, false).await;
+
+    // Submitted long ago (so ahead of anything other tests leave in the queue), in the
+    // opposite order to creation.
+    let base = chrono::DateTime::parse_from_rfc3339("2001-02-03T04:05:06Z").unwrap().with_timezone(&Utc);
+    for (project, submitted) in [(&late, base + chrono::Duration::hours(2)), (&early, base)] {
+        sqlx::query("UPDATE public.projects SET status = 'pending', submitted_at = $2 WHERE id = $1")
+            .bind(project.id)
+            .bind(submitted)
+            .execute(&ctx.pool)
+            .await
+            .expect("Failed to queue project");
+    }
+
+    let queue = repo.get_pending_reviews(200).await;
+    let ours: Vec<_> = queue
+        .iter()
+        .filter(|entry| entry.project.user_id == owner.id)
+        .collect();
+    assert_eq!(ours.iter().map(|e| e.project.id).collect::<Vec<_>>(), vec![early.id, late.id]);
+    assert!(queue.iter().all(|entry| entry.project.id != draft.id));
+    assert_eq!(ours[0].owner_email.as_deref(), Some(owner.email.as_str()));
+    assert_eq!(ours[0].waiting_since, base);
+    assert!(ours[0].waiting_seconds > ours[1].waiting_seconds);
+    assert!(repo.get_pending_reviews(1).await.len() <= 1);
+
+    // Leave the shared queue as it was found.
+    sqlx::query("UPDATE public.projects SET status = 'draft' WHERE user_id = $1")
+        .bind(owner.id)
+        .execute(&ctx.pool)
+        .await
+        .expect("Failed to reset projects");
+}
+
+/// Comment notifications addressed to `user_id` on `project_id`.
+async fn comment_notification_count(pool: &PgPool, user_id: Uuid, project_id: Uuid) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM public.notifications WHERE user_id = $1 AND project_id = $2 AND type = 'comment'",
+    )
+    .bind(user_id)
+    .bind(project_id)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to count notifications")
+}
+
+#[test]
+async fn test_comment_subscriptions_fan_out_and_mute() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "subowner").await;
+    let alice = create_test_user(&ctx.pool, Uuid::new_v4(), "subalice").await;
+    let bob = create_test_user(&ctx.pool, Uuid::new_v4(), "subbob").await;
+    let project = create_test_project(&ctx.pool, owner.id, "Subscriptions", 2024, true).await;
+
+    // Defaults: the owner is subscribed, a bystander is not.
+    assert_eq!(
+        repo.get_subscription(project.id, owner.id).await,
+        Some(SubscriptionState::Subscribed)
+    );
+    assert_eq!(repo.get_subscription(project.id, alice.id).await, None);
+
+    // 1. Alice comments: only the owner is notified, never Alice herself.
+    repo.add_comment(project.id, alice.id, "First".to_string(), 500).await.unwrap().unwrap();
+    assert_eq!(comment_notification_count(&ctx.pool, owner.id, project.id).await, 1);
+    assert_eq!(comment_notification_count(&ctx.pool, alice.id, project.id).await, 0);
 
-    assert!(is_read);
+    // 2. Having commented, Alice is auto-subscribed and hears about Bob's reply.
+    assert_eq!(
+        repo.get_subscription(project.id, alice.id).await,
+        Some(SubscriptionState::Subscribed)
+    );
+    repo.add_comment(project.id, bob.id, "Reply".to_string(), 500).await.unwrap().unwrap();
+    assert_eq!(comment_notification_count(&ctx.pool, alice.id, project.id).await, 1);
+    assert_eq!(comment_notification_count(&ctx.pool, owner.id, project.id).await, 2);
+
+    // 3. Muting stops further notifications for Alice and the owner.
+    assert!(repo.set_subscription(project.id, alice.id, SubscriptionState::Muted).await);
+    assert!(repo.set_subscription(project.id, owner.id, SubscriptionState::Muted).await);
+    repo.add_comment(project.id, bob.id, "Another".to_string(), 500).await.unwrap().unwrap();
+    assert_eq!(comment_notification_count(&ctx.pool, alice.id, project.id).await, 1);
+    assert_eq!(comment_notification_count(&ctx.pool, owner.id, project.id).await, 2);
+    assert_eq!(
+        repo.get_subscription(project.id, alice.id).await,
+        Some(SubscriptionState::Muted)
+    );
+
+    // 4. The owner commenting on their own project notifies Bob but not the owner.
+    repo.add_comment(project.id, owner.id, "Thanks".to_string(), 500).await.unwrap().unwrap();
+    assert_eq!(comment_notification_count(&ctx.pool, bob.id, project.id).await, 1);
+    assert_eq!(comment_notification_count(&ctx.pool, owner.id, project.id).await, 2);
+
+    // Unknown projects are rejected.
+    assert!(!repo.set_subscription(Uuid::new_v4(), alice.id, SubscriptionState::Subscribed).await);
+}
+
+#[test]
+async fn test_project_scoped_comment_delete_rejects_mismatch() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "scopeowner").await;
+    let commenter = create_test_user(&ctx.pool, Uuid::new_v4(), "scopecommenter").await;
+    let first = create_test_project(&ctx.pool, owner.id, "Scope A", 2024, true).await;
+    let second = create_test_project(&ctx.pool, owner.id, "Scope B", 2024, true).await;
+    let comment = repo
+        .add_comment(first.id, commenter.id, "Scoped".to_string(), 500)
+        .await
+        .unwrap()
+        .unwrap()
+        .comment;
+
+    // 1. Wrong project: not deleted, even for the author or an admin.
+    assert!(!repo.delete_project_comment(second.id, comment.id, Some(commenter.id)).await);
+    assert!(!repo.delete_project_comment(second.id, comment.id, None).await);
+    // 2. Right project, wrong user.
+    assert!(!repo.delete_project_comment(first.id, comment.id, Some(owner.id)).await);
+    assert_eq!(repo.get_comments(first.id, CommentOrder::Oldest, HiddenComments::Exclude).await.len(), 1);
+
+    // 3. Right project and author.
+    assert!(repo.delete_project_comment(first.id, comment.id, Some(commenter.id)).await);
+    assert!(repo.get_comments(first.id, CommentOrder::Oldest, HiddenComments::Exclude).await.is_empty());
+}
+
+/// Inserts a project with a supervisor inside `tx` (the shared helper only accepts a pool).
+async fn insert_supervised_project(
+    tx: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    year: i32,
+    supervisor: &str,
+    is_public: bool,
+) -> Uuid {
+    sqlx::query_scalar(
+        r#"INSERT INTO public.projects (id, user_id, author, title, abstract, cover_image, year, status, supervisor, slug)
+           VALUES ($1, $2, 'Stats Author', 'Stats Project', 'Abstract', 'cover', $3, CASE WHEN $4 THEN 'approved' ELSE 'draft' END, $5, 'test-' || $1)
+           RETURNING id"#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(user_id)
+    .bind(year)
+    .bind(is_public)
+    .bind(supervisor)
+    .fetch_one(tx)
+    .await
+    .expect("Failed to insert project")
+}
+
+#[test]
+async fn test_public_stats_count_only_public_projects() {
+    let ctx = DbTestContext::setup().await;
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "statsowner").await;
+    let liker = create_test_user(&ctx.pool, Uuid::new_v4(), "statsliker").await;
+    let year = 3000 + (Uuid::new_v4().as_u128() % 100_000) as i32;
+    let supervisor = format!("Dr. Stats {}", Uuid::new_v4());
+
+    // A REPEATABLE READ snapshot hides rows committed by concurrently running tests, so the
+    // deltas below are exact. Everything is rolled back at the end.
+    let mut tx = ctx.pool.begin().await.unwrap();
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    let before = query_public_stats(&mut *tx).await.unwrap();
+
+    // 1. A public project in a new year with a new supervisor, liked once.
+    let public_id = insert_supervised_project(&mut tx, owner.id, year, &supervisor, true).await;
+    sqlx::query("INSERT INTO public.project_likes (user_id, project_id) VALUES ($1, $2)")
+        .bind(liker.id)
+        .bind(public_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    let after_public = query_public_stats(&mut *tx).await.unwrap();
+    assert_eq!(
+        after_public,
+        PublicStats {
+            public_projects: before.public_projects + 1,
+            distinct_years: before.distinct_years + 1,
+            distinct_supervisors: before.distinct_supervisors + 1,
+            total_likes: before.total_likes + 1,
+        }
+    );
+
+    // 2. A private project (another new year and supervisor, also liked) changes nothing.
+    let private_id =
+        insert_supervised_project(&mut tx, owner.id, year + 1, "Dr. Hidden", false).await;
+    sqlx::query("INSERT INTO public.project_likes (user_id, project_id) VALUES ($1, $2)")
+        .bind(liker.id)
+        .bind(private_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    assert_eq!(query_public_stats(&mut *tx).await.unwrap(), after_public);
+
+    tx.rollback().await.unwrap();
+}
+
+#[test]
+async fn test_public_stats_are_cached() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "cacheowner").await;
+
+    let first = repo.get_public_stats().await;
+    create_test_project(&ctx.pool, owner.id, "Cached", 2024, true).await;
+
+    // Served from the in-process cache: the new project is not counted yet.
+    assert_eq!(repo.get_public_stats().await, first);
+    // A fresh repository (cold cache) sees it.
+    let fresh = ctx.repository().get_public_stats().await;
+    assert!(fresh.public_projects > first.public_projects);
+}
+
+#[test]
+async fn test_status_changes_invalidate_public_stats_cache() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "cachehider").await;
+    let project = create_test_project(&ctx.pool, owner.id, "Hidden later", 2024, false).await;
+
+    let before = repo.get_public_stats().await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    // Recomputed rather than served from the cache, so the newly public project counts.
+    let published = repo.get_public_stats().await;
+    assert_ne!(published, before);
+
+    repo.set_project_status(project.id, ProjectStatus::Rejected).await.unwrap();
+    assert_ne!(repo.get_public_stats().await, published);
+}
+
+#[test]
+async fn test_deleted_comment_with_replies_stays_as_placeholder() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let author = create_test_user(&ctx.pool, Uuid::new_v4(), "softauthor").await;
+    let replier = create_test_user(&ctx.pool, Uuid::new_v4(), "softreplier").await;
+    let project = create_test_project(&ctx.pool, author.id, "Soft Delete Replies", 2024, true).await;
+
+    let first = repo.add_comment(project.id, author.id, "Question about the results".to_string(), 500).await.unwrap().unwrap().comment;
+    let reply = repo.add_comment(project.id, replier.id, "See section 4".to_string(), 500).await.unwrap().unwrap().comment;
+    assert!(repo.delete_comment(first.id, author.id).await);
+    // Deleting is soft, but only once.
+    assert!(!repo.delete_comment(first.id, author.id).await);
+    assert!(!repo.delete_comment_admin(first.id).await);
+
+    for order in [CommentOrder::Oldest, CommentOrder::Newest] {
+        let thread = repo.get_comments(project.id, order, HiddenComments::Exclude).await;
+        let placeholder = thread.iter().find(|c| c.id == first.id).expect("placeholder kept");
+        assert!(placeholder.is_deleted);
+        assert_eq!(placeholder.comment, DELETED_COMMENT_TEXT);
+        assert_eq!(placeholder.author_email, None);
+        assert_eq!(thread.len(), 2);
+    }
+    let page = repo.get_comments_before(project.id, HiddenComments::Exclude, None, 10).await;
+    assert_eq!(page.iter().map(|c| (c.id, c.is_deleted)).collect::<Vec<_>>(), vec![(reply.id, false), (first.id, true)]);
+
+    // The placeholder is not counted, cannot be edited and drops out with its last reply.
+    assert_eq!(repo.count_comments(project.id).await, Some(1));
+    let edit = fyp_portal::models::UpdateCommentRequest { text: "Back again".to_string() };
+    assert!(repo.update_comment(first.id, author.id, edit).await.is_none());
+    assert!(repo.delete_project_comment(project.id, reply.id, Some(replier.id)).await);
+    assert!(repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Exclude).await.is_empty());
+    assert_eq!(repo.count_comments(project.id).await, Some(0));
+}
+
+#[test]
+async fn test_deleted_comment_without_replies_is_omitted() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let author = create_test_user(&ctx.pool, Uuid::new_v4(), "softlast").await;
+    let project = create_test_project(&ctx.pool, author.id, "Soft Delete Last", 2024, true).await;
+
+    let kept = repo.add_comment(project.id, author.id, "Kept".to_string(), 500).await.unwrap().unwrap().comment;
+    let last = repo.add_comment(project.id, author.id, "Last word".to_string(), 500).await.unwrap().unwrap().comment;
+    assert!(repo.delete_comment_admin(last.id).await);
+
+    let thread = repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Include).await;
+    assert_eq!(thread.iter().map(|c| c.id).collect::<Vec<_>>(), vec![kept.id]);
+    assert!(!thread[0].is_deleted);
+    assert_eq!(repo.get_comments_before(project.id, HiddenComments::Include, None, 10).await.len(), 1);
+
+    // The row itself is kept, stamped.
+    let deleted_at = sqlx::query_scalar!("SELECT deleted_at FROM project_comments WHERE id = $1", last.id)
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert!(deleted_at.is_some());
+}
+
+#[test]
+async fn test_comment_ordering_and_count() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "orderowner").await;
+    let project = create_test_project(&ctx.pool, owner.id, "Ordering", 2024, true).await;
+    let hidden = create_test_project(&ctx.pool, owner.id, "Ordering Hidden", 2024, false).await;
+
+    let mut posted = vec![];
+    for text in ["first", "second", "third"] {
+        posted.push(repo.add_comment(project.id, owner.id, text.to_string(), 500).await.unwrap().unwrap().comment.id);
+    }
+    repo.add_comment(hidden.id, owner.id, "hidden".to_string(), 500).await.unwrap().unwrap();
+
+    let ids = |comments: Vec<fyp_portal::models::Comment>| {
+        comments.into_iter().map(|c| c.id).collect::<Vec<_>>()
+    };
+    assert_eq!(ids(repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Exclude).await), posted);
+    let mut reversed = posted.clone();
+    reversed.reverse();
+    assert_eq!(ids(repo.get_comments(project.id, CommentOrder::Newest, HiddenComments::Exclude).await), reversed);
+
+    assert_eq!(repo.count_comments(project.id).await, Some(3));
+    // Private threads are invisible, so they answer like missing projects.
+    assert_eq!(repo.count_comments(hidden.id).await, None);
+    assert_eq!(repo.count_comments(Uuid::new_v4()).await, None);
+}
+
+#[test]
+async fn test_upload_counts_track_pending_and_confirmed() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let user = create_test_user(&ctx.pool, Uuid::new_v4(), "uploader").await;
+    let soon = Utc::now() + chrono::Duration::minutes(10);
+    let key = |n: u32| format!("uploads/{}-{}.mp4", user.id, n);
+
+    assert!(repo.record_upload(user.id, &key(1), UploadPurpose::Video, soon).await);
+    assert!(repo.record_upload(user.id, &key(2), UploadPurpose::Video, soon).await);
+    assert!(repo.record_upload(user.id, &key(3), UploadPurpose::Report, soon).await);
+    // Already expired: counts towards the hourly total but is no longer pending.
+    let expired = Utc::now() - chrono::Duration::minutes(1);
+    assert!(repo.record_upload(user.id, &key(4), UploadPurpose::Video, expired).await);
+
+    let counts = repo.get_upload_counts(user.id, UploadPurpose::Video).await;
+    assert_eq!(counts, UploadCounts { last_hour: 4, outstanding: 2 });
+
+    // Referencing a key from a project confirms it.
+    let project = create_test_project(&ctx.pool, user.id, "Uploads", 2024, false).await;
+    sqlx::query("UPDATE public.projects SET video = $1 WHERE id = $2")
+        .bind(key(1))
+        .bind(project.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let counts = repo.get_upload_counts(user.id, UploadPurpose::Video).await;
+    assert_eq!(counts, UploadCounts { last_hour: 4, outstanding: 1 });
+    assert_eq!(
+        repo.get_upload_counts(user.id, UploadPurpose::Report).await.outstanding,
+        1
+    );
+}
+
+#[test]
+async fn test_changed_project_ids_by_edit_or_comment() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "changedowner").await;
+    let edited = create_test_project(&ctx.pool, owner.id, "Changed Edited", 2024, true).await;
+    let commented = create_test_project(&ctx.pool, owner.id, "Changed Commented", 2024, true).await;
+    let stale = create_test_project(&ctx.pool, owner.id, "Changed Stale", 2024, true).await;
+    let private = create_test_project(&ctx.pool, owner.id, "Changed Private", 2024, false).await;
+    let all = vec![edited.id, commented.id, stale.id, private.id];
+
+    // Everything starts out untouched for ten days; the stale project's only comment is old too.
+    let long_ago = Utc::now() - chrono::Duration::days(10);
+    sqlx::query("UPDATE public.projects SET updated_at = $1 WHERE id = ANY($2)")
+        .bind(long_ago)
+        .bind(&all)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    let old_comment = repo.add_comment(stale.id, owner.id, "old news".to_string(), 500).await.unwrap().unwrap().comment;
+    sqlx::query("UPDATE public.project_comments SET created_at = $1 WHERE id = $2")
+        .bind(long_ago)
+        .bind(old_comment.id)
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    sqlx::query("UPDATE public.projects SET updated_at = NOW() WHERE id = ANY($1)")
+        .bind(vec![edited.id, private.id])
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+    repo.add_comment(commented.id, owner.id, "fresh".to_string(), 500).await.unwrap().unwrap();
+
+    let since = Utc::now() - chrono::Duration::days(5);
+    let mut expected = vec![edited.id, commented.id];
+    expected.sort();
+
+    // Scoped: exactly the changed public projects among the given ids.
+    assert_eq!(repo.get_changed_project_ids(since, Some(all.clone())).await, expected);
+    assert!(repo.get_changed_project_ids(since, Some(vec![stale.id])).await.is_empty());
+    assert!(repo.get_changed_project_ids(since, Some(vec![])).await.is_empty());
+
+    // Unscoped: other tests' projects may appear, but ours must be classified the same way.
+    let unscoped = repo.get_changed_project_ids(since, None).await;
+    assert!(unscoped.contains(&edited.id) && unscoped.contains(&commented.id));
+    assert!(!unscoped.contains(&stale.id) && !unscoped.contains(&private.id));
+
+    // An earlier cutoff picks up the stale project's old comment and edit.
+    let earlier = long_ago - chrono::Duration::days(1);
+    assert_eq!(repo.get_changed_project_ids(earlier, Some(vec![stale.id])).await, vec![stale.id]);
+}
+
+#[test]
+async fn test_stream_all_projects_includes_private_rows() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "streamowner").await;
+    let public = create_test_project(&ctx.pool, owner.id, "Streamed Public", 2024, true).await;
+    let private = create_test_project(&ctx.pool, owner.id, "Streamed Private", 2024, false).await;
+
+    let streamed: Vec<Uuid> = repo
+        .stream_all_projects()
+        .map(|row| row.expect("stream row").id)
+        .collect()
+        .await;
+    assert!(streamed.contains(&public.id) && streamed.contains(&private.id));
+}
+
+#[test]
+async fn test_programme_membership_and_filtering() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "programmeowner").await;
+
+    // Unique codes keep this test independent of other runs.
+    let suffix = Uuid::new_v4().simple().to_string()[..8].to_uppercase();
+    let cs = repo.create_programme(&format!("CS-{suffix}"), "Computer Science").await.unwrap();
+    let ms = repo.create_programme(&format!("MS-{suffix}"), "Management Science").await.unwrap();
+    assert!(repo.create_programme(&cs.code, "Duplicate").await.is_none());
+    assert!(repo.get_programmes().await.contains(&ms));
+
+    let unknown = format!("XX-{suffix}");
+    assert_eq!(
+        repo.unknown_programme_codes(&[cs.code.clone(), unknown.clone()]).await,
+        vec![unknown]
+    );
+
+    let create = |title: &str, codes: Vec<String>| CreateProjectRequest {
+        title: title.to_string(),
+        abstract_text: "Abstract".to_string(),
+        author_name: "Author".to_string(),
+        year: 2024,
+        cover_image_key: "cover".to_string(),
+        programme_codes: Some(codes),
+        ..CreateProjectRequest::default()
+    };
+    let joint = repo.create_project(create("Joint", vec![cs.code.clone(), ms.code.clone()]), owner.id).await;
+    let single = repo.create_project(create("Single", vec![cs.code.clone()]), owner.id).await;
+    for id in [joint.id, single.id] {
+        repo.set_project_status(id, ProjectStatus::Approved).await.unwrap();
+    }
+    assert_eq!(repo.get_project_programmes(joint.id).await, vec![cs.code.clone(), ms.code.clone()]);
+
+    let ids = |(projects, _): (Vec<Project>, i64)| projects.into_iter().map(|p| p.id).collect::<Vec<_>>();
+    let in_programme = |code: &str| ProjectListFilter {
+        programme: Some(code.to_string()),
+        ..ProjectListFilter::default()
+    };
+    let in_cs = ids(repo.get_projects(in_programme(&cs.code), PageRequest::default()).await);
+    assert_eq!(in_cs.len(), 2);
+    assert!(in_cs.contains(&joint.id) && in_cs.contains(&single.id));
+    assert_eq!(ids(repo.get_projects(in_programme(&ms.code), PageRequest::default()).await), vec![joint.id]);
+
+    // Updating replaces the whole set; omitting the field leaves it alone.
+    let update = |codes: Option<Vec<String>>| UpdateProjectRequest {
+        programme_codes: codes,
+        ..UpdateProjectRequest::default()
+    };
+    repo.update_project(single.id, owner.id, update(Some(vec![ms.code.clone()]))).await.unwrap();
+    repo.update_project(single.id, owner.id, update(None)).await.unwrap();
+    assert_eq!(repo.get_project_programmes(single.id).await, vec![ms.code.clone()]);
+    assert_eq!(ids(repo.get_projects(in_programme(&cs.code), PageRequest::default()).await), vec![joint.id]);
+
+    // A non-owner's update changes neither the row nor its programmes.
+    assert!(repo.update_project(single.id, Uuid::new_v4(), update(Some(vec![]))).await.is_none());
+    assert_eq!(repo.get_project_programmes(single.id).await, vec![ms.code]);
+}
+
+#[test]
+async fn test_reports_auto_hide_comment_and_restore_clears_them() {
+    let ctx = DbTestContext::setup().await;
+    let repo = ctx.repository();
+    let owner = create_test_user(&ctx.pool, Uuid::new_v4(), "reportowner").await;
+    let author = create_test_user(&ctx.pool, Uuid::new_v4(), "reportauthor").await;
+    let admin = create_test_user(&ctx.pool, Uuid::new_v4(), "admin").await;
+    let project = create_test_project(&ctx.pool, owner.id, "Reported", 2024, true).await;
+    let comment = repo.add_comment(project.id, author.id, "spam".to_string(), 500).await.unwrap().unwrap().comment;
+    let mut reporters = vec![];
+    for _ in 0..4 {
+        reporters.push(create_test_user(&ctx.pool, Uuid::new_v4(), "reporter").await.id);
+    }
+    let report = |reporter: Uuid| repo.create_report(Some(project.id), comment.id, reporter, None, 3);
+
+    // Two distinct reporters (one reporting twice) stay below the threshold.
+    assert_eq!(report(reporters[0]).await.unwrap().pending_reports, 1);
+    assert_eq!(report(reporters[0]).await.unwrap().pending_reports, 1);
+    let second = report(reporters[1]).await.unwrap();
+    assert_eq!((second.pending_reports, second.hidden), (2, false));
+    assert_eq!(repo.get_comments(project.id, CommentOrder::Oldest, HiddenComments::Exclude).await.len(), 1);
+
+    // The third distinct reporter hides it; later reports do not hide it "again".
+    let third = report(reporters[2]).await.unwrap();
+    assert_eq!((third.pending_reports, third.hidden), (3, true));
+    assert!(!report(reporters[3]).await.unwrap().hidden);
+
+    let listing = |hidden| repo.get_comments(project.id, CommentOrder::Oldest, hidden);
+    assert!(listing(HiddenComments::Exclude).await.is_empty());
+    assert!(listing(HiddenComments::AuthoredBy(reporters[0])).await.is_empty());
+    let own = listing(HiddenComments::AuthoredBy(author.id)).await;
+    assert_eq!(own.len(), 1);
+    assert!(own[0].hidden_pending_review);
+    assert!(listing(HiddenComments::Include).await[0].hidden_pending_review);
+    assert_eq!(repo.count_comments(project.id).await, Some(0));
+
+    let moderator_notifications = repo
+        .get_notifications(admin.id)
+        .await
+        .into_iter()
+        .filter(|n| n.project_id == project.id && n.notification_type == "comment_hidden")
+        .count();
+    assert_eq!(moderator_notifications, 1);
+
+    // Restoring unhides it and resolves every pending report, so counting starts over.
+    assert!(repo.restore_comment(comment.id).await);
+    let visible = listing(HiddenComments::Exclude).await;
+    assert_eq!(visible.len(), 1);
+    assert!(!visible[0].hidden_pending_review);
+    let pending: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM comment_reports WHERE comment_id = $1 AND resolved_at IS NULL",
+    )
+    .bind(comment.id)
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(pending, 0);
+    assert_eq!(report(reporters[0]).await.unwrap().pending_reports, 1);
+
+    // Unknown comments and comments addressed through the wrong project are not reportable.
+    assert!(repo.create_report(Some(Uuid::new_v4()), comment.id, reporters[0], None, 3).await.is_none());
+    assert!(!repo.restore_comment(i64::MAX).await);
+}
+
+#[test]
+async fn test_search_and_year_listing_use_their_indexes() {
+    let ctx = DbTestContext::setup().await;
+    // Test tables are small enough that a sequential scan always wins on cost; turning it off
+    // shows which indexes the planner *can* use for the listing queries.
+    let mut tx = ctx.pool.begin().await.unwrap();
+    sqlx::query("SET LOCAL enable_seqscan = off").execute(&mut *tx).await.unwrap();
+    let mut plan = async |sql: &str| -> String {
+        let rows: Vec<String> = sqlx::query_scalar(sql).fetch_all(&mut *tx).await.unwrap();
+        rows.join("\n")
+    };
+
+    // Without the status predicate: once the shared database holds enough approved rows the
+    // planner may rightly narrow by the listing index first and filter the ILIKEs.
+    let search = plan(
+        "EXPLAIN SELECT id FROM projects \
+         WHERE title ILIKE '%robot%' OR abstract ILIKE '%robot%' OR author ILIKE '%robot%'",
+    )
+    .await;
+    for index in ["idx_projects_title_trgm", "idx_projects_abstract_trgm", "idx_projects_author_trgm"] {
+        assert!(search.contains(index), "{index} missing from:\n{search}");
+    }
+
+    let year = plan(
+        "EXPLAIN SELECT id FROM projects WHERE status = 'approved' AND year = 2024 \
+         ORDER BY created_at DESC, id DESC LIMIT 20",
+    )
+    .await;
+    assert!(year.contains("idx_projects_public_year_listing"), "{year}");
+    assert!(!year.contains("Sort"), "the index should supply the order:\n{year}");
 }
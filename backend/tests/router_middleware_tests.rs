@@ -0,0 +1,643 @@
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Request, StatusCode},
+};
+use fyp_portal::{
+    AppConfig, AppStateBuilder, build_info,
+    config::Env,
+    casing::to_camel_case_keys,
+    create_router,
+    normalize::normalize_path,
+    error::ErrorResponse,
+    models::{CreateProjectRequest, ProjectStatus, User},
+    rate_limit::{MAX_TRACKED_CLIENTS, RateLimiter},
+    repository::{Repository, RepositoryState, counting::CountingRepository, memory::InMemoryRepository},
+    security_headers::{DEFAULT_HSTS, DEFAULT_HTML_CSP, DEFAULT_SWAGGER_CSP, SecurityHeaders},
+};
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+// --- Helpers ---
+
+// Router-level tests only care about middleware behaviour. They run against an in-memory
+// repository holding one approved project, which resolves on the detail, preview and embed
+// routes (any other id is a 404) and gives the casing tests real keys to inspect.
+async fn seeded_repo() -> (Arc<InMemoryRepository>, Uuid) {
+    let repo = Arc::new(InMemoryRepository::new());
+    let owner = repo
+        .create_user(User { id: Uuid::new_v4(), email: "owner@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let request = CreateProjectRequest {
+        title: "Router Showcase".to_string(),
+        author_name: "Ada Lovelace".to_string(),
+        abstract_text: "A project for the middleware tests.".to_string(),
+        year: 2024,
+        ..CreateProjectRequest::default()
+    };
+    let project = repo.create_project(request, owner.id).await;
+    repo.set_project_status(project.id, ProjectStatus::Approved).await.unwrap();
+    (repo, project.id)
+}
+
+fn app_with(config: AppConfig, repo: RepositoryState) -> axum::Router {
+    create_router(AppStateBuilder::test_default().repo(repo).config(config).build())
+}
+
+async fn app(config: AppConfig) -> axum::Router {
+    app_with(config, seeded_repo().await.0)
+}
+
+/// The socket peer the rate limiter keys on, as `into_make_service_with_connect_info` sets it.
+fn peer(client_ip: &str) -> ConnectInfo<SocketAddr> {
+    ConnectInfo(SocketAddr::new(client_ip.parse().unwrap(), 40_000))
+}
+
+fn get(uri: &str, client_ip: &str) -> Request<Body> {
+    Request::builder()
+        .uri(uri)
+        .extension(peer(client_ip))
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn body_json(response: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+fn header_u64(response: &axum::response::Response, name: &str) -> u64 {
+    response
+        .headers()
+        .get(name)
+        .unwrap_or_else(|| panic!("missing {name}"))
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+// --- Rate Limit Headers ---
+
+#[tokio::test]
+async fn test_rate_limit_headers_decrease_monotonically() {
+    let app = app(AppConfig {
+        rate_limit_per_minute: 10,
+        ..AppConfig::default()
+    }).await;
+
+    let mut previous = u64::MAX;
+    for _ in 0..5 {
+        let response = app.clone().oneshot(get("/projects", "10.0.0.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(header_u64(&response, "x-ratelimit-limit"), 10);
+
+        let remaining = header_u64(&response, "x-ratelimit-remaining");
+        assert!(remaining < previous, "remaining must strictly decrease");
+        previous = remaining;
+
+        let reset = header_u64(&response, "x-ratelimit-reset");
+        let now = unix_now();
+        assert!(reset >= now && reset <= now + 61, "reset {reset} is not a sane epoch");
+    }
+    assert_eq!(previous, 5);
+}
+
+#[tokio::test]
+async fn test_rate_limit_exhaustion_returns_429_with_body() {
+    let app = app(AppConfig {
+        rate_limit_per_minute: 2,
+        ..AppConfig::default()
+    }).await;
+
+    for _ in 0..2 {
+        let response = app.clone().oneshot(get("/projects", "10.0.0.2")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = app.clone().oneshot(get("/projects", "10.0.0.2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    // Two tokens a minute: the next one is back in 30 seconds, the full bucket only in 60.
+    assert_eq!(header_u64(&response, "retry-after"), 30);
+    assert!(header_u64(&response, "x-ratelimit-reset") >= unix_now() + 59);
+    assert_eq!(header_u64(&response, "x-ratelimit-remaining"), 0);
+
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: ErrorResponse = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body.code, "rate_limited");
+    let details = body.details.unwrap();
+    assert_eq!(details["limit"], 2);
+    assert_eq!(details["remaining"], 0);
+    assert!(details["reset"].as_u64().unwrap() >= unix_now());
+
+    // A different client still has its own full bucket.
+    let other = app.oneshot(get("/projects", "10.0.0.3")).await.unwrap();
+    assert_eq!(other.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_rate_limit_ignores_forwarded_for_unless_proxies_are_trusted() {
+    let forwarded = |client_ip: &str, chain: &str| {
+        let mut request = get("/projects", client_ip);
+        request.headers_mut().insert("x-forwarded-for", chain.parse().unwrap());
+        request
+    };
+
+    // Without trusted proxies a client cannot pick a fresh bucket by inventing hops.
+    let app = app(AppConfig {
+        rate_limit_per_minute: 1,
+        ..AppConfig::default()
+    }).await;
+    let response = app.clone().oneshot(forwarded("10.0.1.1", "203.0.113.1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app.oneshot(forwarded("10.0.1.1", "203.0.113.2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // Behind one proxy, the hop it appended counts; anything the client prepended does not.
+    let app = app_behind_proxy(1).await;
+    let response = app.clone().oneshot(forwarded("10.0.1.2", "1.1.1.1, 203.0.113.3")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app.clone().oneshot(forwarded("10.0.1.2", "2.2.2.2, 203.0.113.3")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let response = app.oneshot(forwarded("10.0.1.2", "203.0.113.4")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+async fn app_behind_proxy(hops: usize) -> axum::Router {
+    app(AppConfig {
+        rate_limit_per_minute: 1,
+        trusted_proxy_hops: hops,
+        ..AppConfig::default()
+    }).await
+}
+
+#[test]
+fn test_rate_limiter_forgets_refilled_and_least_recent_clients() {
+    // Buckets that have refilled are swept once a window has passed.
+    let limiter = RateLimiter::new(1, Duration::from_millis(1));
+    limiter.check("10.0.2.1");
+    std::thread::sleep(Duration::from_millis(5));
+    limiter.check("10.0.2.2");
+    assert_eq!(limiter.tracked_clients(), 1);
+
+    // Past the cap, the client seen longest ago makes room and starts over with a full bucket.
+    let limiter = RateLimiter::new(2, Duration::from_secs(60));
+    for i in 0..=MAX_TRACKED_CLIENTS {
+        limiter.check(&format!("client-{i}"));
+    }
+    assert_eq!(limiter.tracked_clients(), MAX_TRACKED_CLIENTS);
+    assert_eq!(limiter.check("client-0").remaining, 1);
+    assert_eq!(limiter.tracked_clients(), MAX_TRACKED_CLIENTS);
+}
+
+#[tokio::test]
+async fn test_rate_limit_skips_exempt_paths() {
+    let app = app(AppConfig {
+        rate_limit_per_minute: 1,
+        ..AppConfig::default()
+    }).await;
+
+    for _ in 0..3 {
+        let response = app.clone().oneshot(get("/health", "10.0.0.4")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key("x-ratelimit-limit"));
+    }
+}
+
+#[tokio::test]
+async fn test_metrics_are_exposed_in_prometheus_format() {
+    let app = app(AppConfig {
+        rate_limit_per_minute: 1,
+        ..AppConfig::default()
+    }).await;
+
+    // Scrapes are exempt from the limiter, like health checks.
+    for _ in 0..3 {
+        let response = app.clone().oneshot(get("/metrics", "10.0.0.5")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/plain"));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("# TYPE auth_provider_requests_total counter"));
+        assert!(body.contains("auth_provider_requests_total{outcome=\"timeout\"}"));
+    }
+}
+
+fn post_feedback(project: Uuid, client_ip: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(format!("/projects/{project}/feedback"))
+        .extension(peer(client_ip))
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"message":"Loved the demo video!"}"#))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_feedback_has_its_own_stricter_rate_limit() {
+    let (repo, project) = seeded_repo().await;
+    let app = app_with(
+        AppConfig {
+            feedback_per_hour: 2,
+            ..AppConfig::default()
+        },
+        repo,
+    );
+
+    for remaining in [1, 0] {
+        let response = app.clone().oneshot(post_feedback(project, "10.0.0.6")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        // The feedback budget is reported, not the (larger) global one.
+        assert_eq!(header_u64(&response, "x-ratelimit-limit"), 2);
+        assert_eq!(header_u64(&response, "x-ratelimit-remaining"), remaining);
+    }
+
+    let response = app.clone().oneshot(post_feedback(project, "10.0.0.6")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key("retry-after"));
+    assert_eq!(body_json(response).await["code"], "rate_limited");
+
+    // Other routes still use the global budget, and other clients have their own.
+    let listing = app.clone().oneshot(get("/projects", "10.0.0.6")).await.unwrap();
+    assert_eq!(listing.status(), StatusCode::OK);
+    assert_eq!(header_u64(&listing, "x-ratelimit-limit"), 120);
+    let other = app.oneshot(post_feedback(project, "10.0.0.7")).await.unwrap();
+    assert_eq!(other.status(), StatusCode::CREATED);
+}
+
+// --- JSON Casing ---
+
+#[tokio::test]
+async fn test_unbufferable_json_body_becomes_a_500_when_recasing() {
+    use axum::{http::header, middleware, routing::get};
+
+    // Just over the 16 MB the middleware buffers.
+    let huge = || async { ([(header::CONTENT_TYPE, "application/json")], vec![b' '; 16 * 1024 * 1024 + 1]) };
+    let app = axum::Router::new()
+        .route("/huge", get(huge))
+        .layer(middleware::from_fn(fyp_portal::casing::json_casing));
+
+    let snake = app.clone().oneshot(Request::get("/huge").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(snake.status(), StatusCode::OK);
+    let camel = app.oneshot(Request::get("/huge?case=camel").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(camel.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body_json(camel).await["code"], "casing_failed");
+}
+
+#[tokio::test]
+async fn test_same_endpoint_serves_both_casings() {
+    let app = app(AppConfig::default()).await;
+
+    let snake = body_json(app.clone().oneshot(get("/projects/featured", "10.1.0.1")).await.unwrap()).await;
+    let project = &snake[0];
+    assert!(project.get("abstract_text").is_some());
+    assert!(project.get("is_public").is_some());
+    assert!(project.get("abstractText").is_none());
+
+    let mut request = get("/projects/featured", "10.1.0.1");
+    request
+        .headers_mut()
+        .insert("x-api-case", "camel".parse().unwrap());
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let declared: usize = response.headers()["content-length"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(declared, bytes.len(), "content-length must match the rewritten body");
+    let camel: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    let project = &camel[0];
+    assert!(project.get("abstractText").is_some());
+    assert!(project.get("reportIsPublic").is_some());
+    assert!(project.get("createdAt").is_some());
+    assert!(project.get("abstract_text").is_none());
+
+    // The query parameter form is equivalent to the header.
+    let via_query = body_json(
+        app.oneshot(get("/projects/featured?case=camel", "10.1.0.1"))
+            .await
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(via_query, camel);
+}
+
+#[tokio::test]
+async fn test_camel_case_leaves_non_json_responses_alone() {
+    let app = app(AppConfig::default()).await;
+    let response = app
+        .oneshot(get("/health?case=camel", "10.1.0.2"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&bytes[..], b"ok");
+}
+
+#[test]
+fn test_camel_case_preserves_free_form_fields() {
+    let input = serde_json::json!({
+        "cover_image": "a.png",
+        "links": { "demo_url": "https://x", "source_code": "https://y" },
+        "crop": { "offset_x": 1, "offset_y": 2 },
+        "metadata": [{ "raw_key": true }],
+        "nested_items": [{ "user_id": 1, "details": { "keep_me": 1 } }]
+    });
+
+    let output = to_camel_case_keys(input);
+    assert_eq!(
+        output,
+        serde_json::json!({
+            "coverImage": "a.png",
+            "links": { "demo_url": "https://x", "source_code": "https://y" },
+            "crop": { "offset_x": 1, "offset_y": 2 },
+            "metadata": [{ "raw_key": true }],
+            "nestedItems": [{ "userId": 1, "details": { "keep_me": 1 } }]
+        })
+    );
+}
+
+// --- Build Metadata ---
+
+#[tokio::test]
+async fn test_version_endpoint_shape_and_server_header() {
+    let app = app(AppConfig::default()).await;
+    let response = app.oneshot(get("/version", "10.2.0.1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let server = response.headers()["server"].to_str().unwrap().to_string();
+    assert_eq!(server, build_info::server_header());
+    assert!(server.starts_with(&format!("fyp-portal/{}", env!("CARGO_PKG_VERSION"))));
+
+    let body = body_json(response).await;
+    let mut keys: Vec<_> = body.as_object().unwrap().keys().cloned().collect();
+    keys.sort();
+    assert_eq!(keys, vec!["built_at", "commit", "env", "version"]);
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(body["env"], "local");
+    assert!(!body["commit"].as_str().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_version_endpoint_can_be_admin_only() {
+    let app = app(AppConfig {
+        version_admin_only: true,
+        ..AppConfig::default()
+    }).await;
+    let response = app.oneshot(get("/version", "10.2.0.2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    // The header would otherwise hand out the commit the endpoint withholds.
+    assert_eq!(response.headers()["server"], "fyp-portal");
+}
+
+#[test]
+fn test_missing_build_metadata_degrades_to_unknown() {
+    assert_eq!(build_info::or_unknown(None), "unknown");
+    assert_eq!(build_info::or_unknown(Some("abc123")), "abc123");
+}
+
+// --- Path Normalization ---
+
+#[tokio::test]
+async fn test_trailing_and_duplicate_slashes_reach_the_same_handlers() {
+    let (repo, project) = seeded_repo().await;
+    let app = app_with(AppConfig::default(), repo);
+
+    for uri in ["/projects/", "//projects", "/projects//", "///projects///"] {
+        let response = app.clone().oneshot(get(uri, "10.3.0.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        assert_eq!(body_json(response).await["items"][0]["id"], project.to_string(), "{uri}");
+    }
+
+    for uri in [
+        format!("/projects//{project}"),
+        format!("//projects/{project}/"),
+    ] {
+        let response = app.clone().oneshot(get(&uri, "10.3.0.1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        assert_eq!(body_json(response).await["id"], project.to_string());
+    }
+}
+
+#[tokio::test]
+async fn test_normalization_keeps_query_string() {
+    let app = app(AppConfig::default()).await;
+
+    // `case=camel` only takes effect if the query survived the rewrite.
+    let response = app
+        .oneshot(get("//projects/featured/?case=camel", "10.3.0.2"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = body_json(response).await;
+    assert!(body[0].get("abstractText").is_some());
+}
+
+#[tokio::test]
+async fn test_path_normalization_can_be_disabled() {
+    let app = app(AppConfig {
+        normalize_paths: false,
+        ..AppConfig::default()
+    }).await;
+
+    let response = app.clone().oneshot(get("/projects/", "10.3.0.3")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = app.oneshot(get("/projects", "10.3.0.3")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[test]
+fn test_normalize_path_rules() {
+    assert_eq!(normalize_path("/projects"), None);
+    assert_eq!(normalize_path("/"), None);
+    assert_eq!(normalize_path("//"), Some("/".to_string()));
+    assert_eq!(normalize_path("/a//b/"), Some("/a/b".to_string()));
+    // Encoded slashes are data; Swagger UI needs its trailing slash.
+    assert_eq!(normalize_path("/a%2F%2Fb"), None);
+    assert_eq!(normalize_path("/swagger-ui/"), None);
+    assert_eq!(normalize_path("//swagger-ui/"), Some("/swagger-ui/".to_string()));
+}
+
+// --- Embed ---
+
+#[tokio::test]
+async fn test_embed_route_is_frameable() {
+    let app = app(AppConfig::default()).await;
+
+    // Any other id is unknown to the repository, so this is the 404 shell; it must still be frameable.
+    let uri = format!("/embed/projects/{}", Uuid::from_u128(0xdead));
+    let response = app.oneshot(get(&uri, "10.5.0.1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers().get("x-frame-options").is_none());
+    let csp = response.headers()["content-security-policy"].to_str().unwrap();
+    assert!(csp.contains("frame-ancestors *"));
+}
+
+// --- Security Headers ---
+
+fn header<'a>(response: &'a axum::response::Response, name: &str) -> Option<&'a str> {
+    response.headers().get(name).map(|v| v.to_str().unwrap())
+}
+
+#[tokio::test]
+async fn test_security_headers_on_json_responses() {
+    let app = app(AppConfig::default()).await;
+
+    let response = app.oneshot(get("/projects", "10.6.0.1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header(&response, "x-content-type-options"), Some("nosniff"));
+    assert_eq!(header(&response, "referrer-policy"), Some("strict-origin-when-cross-origin"));
+    assert_eq!(header(&response, "x-frame-options"), Some("DENY"));
+    // JSON is not rendered, so it gets no CSP; HSTS is Production-only.
+    assert_eq!(header(&response, "content-security-policy"), None);
+    assert_eq!(header(&response, "strict-transport-security"), None);
+}
+
+#[tokio::test]
+async fn test_security_headers_on_html_responses() {
+    let (repo, project) = seeded_repo().await;
+    let app = app_with(AppConfig::default(), repo);
+
+    let response = app.clone().oneshot(get(&format!("/projects/{project}/og"), "10.6.0.2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header(&response, "content-security-policy"), Some(DEFAULT_HTML_CSP));
+    assert_eq!(header(&response, "x-frame-options"), Some("DENY"));
+
+    // Swagger UI needs its own allowances (same-origin scripts, inline styles, data: icons).
+    let response = app.oneshot(get("/swagger-ui/", "10.6.0.2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let csp = header(&response, "content-security-policy").unwrap();
+    assert_eq!(csp, DEFAULT_SWAGGER_CSP);
+    assert!(csp.contains("script-src 'self'"));
+    assert!(csp.contains("style-src 'self' 'unsafe-inline'"));
+    assert!(csp.contains("img-src 'self' data:"));
+}
+
+#[tokio::test]
+async fn test_embed_route_is_exempt_from_frame_options() {
+    let (repo, project) = seeded_repo().await;
+    let app = app_with(AppConfig::default(), repo);
+
+    let response = app.oneshot(get(&format!("/embed/projects/{project}"), "10.6.0.3")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header(&response, "x-frame-options"), None);
+    assert_eq!(header(&response, "content-security-policy"), Some(fyp_portal::embed::EMBED_CSP));
+    assert_eq!(header(&response, "x-content-type-options"), Some("nosniff"));
+}
+
+#[tokio::test]
+async fn test_hsts_only_in_production() {
+    let app = app(AppConfig {
+        env: Env::Production,
+        ..AppConfig::default()
+    }).await;
+
+    let response = app.oneshot(get("/projects", "10.6.0.4")).await.unwrap();
+    assert_eq!(header(&response, "strict-transport-security"), Some(DEFAULT_HSTS));
+}
+
+#[tokio::test]
+async fn test_security_headers_are_configurable() {
+    let (repo, project) = seeded_repo().await;
+    let app = app_with(
+        AppConfig {
+            env: Env::Production,
+            security_headers: SecurityHeaders {
+                referrer_policy: "no-referrer".to_string(),
+                frame_options: "SAMEORIGIN".to_string(),
+                hsts: String::new(),
+                html_csp: "default-src 'none'".to_string(),
+                embed_csp: "default-src 'none'; frame-ancestors https://example.com".to_string(),
+                ..SecurityHeaders::default()
+            },
+            ..AppConfig::default()
+        },
+        repo,
+    );
+
+    let response = app.clone().oneshot(get(&format!("/projects/{project}/og"), "10.6.0.5")).await.unwrap();
+    assert_eq!(header(&response, "referrer-policy"), Some("no-referrer"));
+    assert_eq!(header(&response, "x-frame-options"), Some("SAMEORIGIN"));
+    assert_eq!(header(&response, "content-security-policy"), Some("default-src 'none'"));
+    // An empty value switches the header off, even in Production.
+    assert_eq!(header(&response, "strict-transport-security"), None);
+
+    let response = app.oneshot(get(&format!("/embed/projects/{project}"), "10.6.0.5")).await.unwrap();
+    assert_eq!(
+        header(&response, "content-security-policy"),
+        Some("default-src 'none'; frame-ancestors https://example.com")
+    );
+}
+
+// --- DB Call Counting ---
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn test_db_calls_header_counts_repository_calls_per_request() {
+    let (inner, _) = seeded_repo().await;
+    let user = inner
+        .create_user(User { id: Uuid::new_v4(), email: "counted@ul.ie".to_string(), role: "student".to_string() })
+        .await;
+    let repo = Arc::new(CountingRepository::new(inner));
+    let app = app_with(AppConfig::default(), repo.clone());
+
+    let response = app.clone().oneshot(get("/projects", "10.4.0.1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header_u64(&response, "x-db-calls"), 1);
+
+    // The auth middleware and the handler's user lookup each hit the repository.
+    let request = Request::builder()
+        .uri("/me")
+        .extension(peer("10.4.0.1"))
+        .header("x-user-id", user.id.to_string())
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header_u64(&response, "x-db-calls"), 2);
+    assert!(logs_contain("get_user=2"));
+
+    let totals = repo.totals();
+    assert_eq!(totals.get("get_projects"), 1);
+    assert_eq!(totals.get("get_user"), 2);
+    assert_eq!(totals.total(), 3);
+}
+
+#[tokio::test]
+async fn test_db_calls_header_is_local_only() {
+    let repo = Arc::new(CountingRepository::new(seeded_repo().await.0));
+    let app = app_with(
+        AppConfig {
+            env: Env::Production,
+            ..AppConfig::default()
+        },
+        repo.clone(),
+    );
+
+    let response = app.oneshot(get("/projects", "10.4.0.2")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("x-db-calls").is_none());
+    assert_eq!(repo.totals().get("get_projects"), 1);
+}
@@ -0,0 +1,94 @@
+//! The settings service: per-key validation, typed getters and change propagation through
+//! its watch channel, against the in-memory repository.
+
+use axum::http::StatusCode;
+use chrono::{TimeZone, Utc};
+use fyp_portal::{
+    models::MaintenanceMode,
+    repository::{Repository, memory::InMemoryRepository},
+    settings::{MAINTENANCE_MODE, SUBMISSION_DEADLINE, SettingsService},
+};
+use serde_json::json;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_unset_settings_read_as_defaults() {
+    let settings = SettingsService::new();
+
+    assert_eq!(settings.deadline(), None);
+    assert_eq!(settings.maintenance(), MaintenanceMode::Off);
+    assert!(!settings.flag("new_editor"));
+    let setting = settings.get(MAINTENANCE_MODE).unwrap();
+    assert_eq!(setting.value, json!("off"));
+    assert!(setting.updated_at.is_none());
+    assert_eq!(settings.get("flag.new_editor").unwrap().value, json!(false));
+}
+
+#[tokio::test]
+async fn test_values_are_validated_per_key() {
+    let repo = InMemoryRepository::new();
+    let settings = SettingsService::new();
+    let admin = Uuid::new_v4();
+
+    for (key, value) in [
+        (SUBMISSION_DEADLINE, json!("next friday")),
+        (SUBMISSION_DEADLINE, json!(1735689600)),
+        (MAINTENANCE_MODE, json!("panic")),
+        ("flag.new_editor", json!("yes")),
+    ] {
+        let err = settings.set(&repo, key, value.clone(), admin).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY, "{key} = {value}");
+        assert_eq!(err.body.code, "invalid_setting");
+        assert_eq!(err.body.details.unwrap()["key"], key);
+    }
+    for key in ["theme", "flag.", "flag.New-Editor", "maintenance_mode.extra"] {
+        let err = settings.set(&repo, key, json!(true), admin).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND, "{key}");
+        assert_eq!(err.body.code, "unknown_setting");
+    }
+    // Nothing invalid reached the table.
+    assert!(repo.get_settings().await.unwrap().is_empty());
+
+    let stored = settings
+        .set(&repo, SUBMISSION_DEADLINE, json!("2025-04-30T17:00:00+01:00"), admin)
+        .await
+        .unwrap();
+    assert_eq!(stored.updated_by, Some(admin));
+    assert_eq!(settings.deadline(), Some(Utc.with_ymd_and_hms(2025, 4, 30, 16, 0, 0).unwrap()));
+    settings.set(&repo, SUBMISSION_DEADLINE, json!(null), admin).await.unwrap();
+    assert_eq!(settings.deadline(), None);
+    settings.set(&repo, "flag.new_editor", json!(true), admin).await.unwrap();
+    assert!(settings.flag("new_editor"));
+}
+
+#[tokio::test]
+async fn test_changes_reach_subscribers() {
+    let repo = InMemoryRepository::new();
+    let settings = SettingsService::new();
+    let mut rx = settings.subscribe();
+    assert!(!rx.has_changed().unwrap());
+
+    settings.set(&repo, MAINTENANCE_MODE, json!("read_only"), Uuid::new_v4()).await.unwrap();
+    rx.changed().await.unwrap();
+    assert_eq!(rx.borrow_and_update().maintenance(), MaintenanceMode::ReadOnly);
+}
+
+#[tokio::test]
+async fn test_refresh_picks_up_other_instances_changes() {
+    let repo = InMemoryRepository::new();
+    let (here, elsewhere) = (SettingsService::new(), SettingsService::new());
+    let mut rx = here.subscribe();
+
+    elsewhere.set(&repo, "flag.new_editor", json!(true), Uuid::new_v4()).await.unwrap();
+    assert!(!here.flag("new_editor"));
+
+    here.refresh(&repo).await.unwrap();
+    assert!(rx.has_changed().unwrap());
+    assert!(rx.borrow_and_update().flag("new_editor"));
+
+    // A refresh that finds nothing new does not wake subscribers.
+    here.refresh(&repo).await.unwrap();
+    assert!(!rx.has_changed().unwrap());
+    assert_eq!(here.snapshot(), elsewhere.snapshot());
+    assert_eq!(repo.get_settings().await.unwrap().len(), 1);
+}
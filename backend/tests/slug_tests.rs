@@ -0,0 +1,30 @@
+use fyp_portal::slug::{base, first_free};
+
+#[test]
+fn test_base_normalises_the_title() {
+    assert_eq!(base("Smart Greenhouse!", 2024), "smart-greenhouse-2024");
+    assert_eq!(base("  --AI/ML: Vision & Speech--  ", 2025), "ai-ml-vision-speech-2025");
+    assert_eq!(base("Café Über Système", 2024), "caf-ber-syst-me-2024");
+}
+
+#[test]
+fn test_base_falls_back_for_titles_without_letters_or_digits() {
+    assert_eq!(base("", 2024), "project-2024");
+    assert_eq!(base("???", 2024), "project-2024");
+    assert_eq!(base("日本語", 2023), "project-2023");
+}
+
+#[test]
+fn test_base_caps_the_title_part() {
+    let long = "a".repeat(59) + " b" + &"c".repeat(40);
+    // Cut at 60 characters, which leaves a trailing separator to drop.
+    assert_eq!(base(&long, 2024), format!("{}-2024", "a".repeat(59)));
+    assert_eq!(base(&"x".repeat(200), 2024).len(), 60 + "-2024".len());
+}
+
+#[test]
+fn test_first_free_counts_up_from_two() {
+    assert_eq!(first_free("demo-2024", |_| false), "demo-2024");
+    let taken = ["demo-2024", "demo-2024-2", "demo-2024-4"];
+    assert_eq!(first_free("demo-2024", |s| taken.contains(&s)), "demo-2024-3");
+}
@@ -0,0 +1,113 @@
+//! The deployment smoke test run in-process against the full router, over the in-memory
+//! repository, mock storage and a mocked Supabase signup endpoint.
+
+use fyp_portal::{
+    AppConfig, AppStateBuilder, create_router,
+    repository::{Repository, memory::InMemoryRepository},
+    smoke::{self, SmokeOptions},
+};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use uuid::Uuid;
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+/// Serves the router on an ephemeral port with signups going to `auth_provider`.
+async fn serve(repo: Arc<InMemoryRepository>, auth_provider: &MockServer) -> (String, AppConfig) {
+    let config = AppConfig {
+        supabase_url: Some(auth_provider.uri()),
+        supabase_key: Some("test-anon-key".to_string()),
+        ..AppConfig::default()
+    };
+    let state = AppStateBuilder::test_default()
+        .repo(repo)
+        .config(config.clone())
+        .build();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, create_router(state)).await.unwrap();
+    });
+    (address, config)
+}
+
+async fn signup_succeeds(server: &MockServer, user_id: Uuid) {
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/auth/v1/signup"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": user_id })))
+        .mount(server)
+        .await;
+}
+
+fn options(base_url: String, env: &str, allow_writes: bool, jwt_secret: Option<String>) -> SmokeOptions {
+    SmokeOptions { base_url, env: env.to_string(), allow_writes, jwt_secret }
+}
+
+fn step_names(report: &smoke::SmokeReport) -> Vec<&str> {
+    report.steps.iter().map(|s| s.name).collect()
+}
+
+#[tokio::test]
+async fn test_full_sequence_passes_and_cleans_up() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let auth_provider = MockServer::start().await;
+    let user_id = Uuid::new_v4();
+    signup_succeeds(&auth_provider, user_id).await;
+    let (address, config) = serve(repo.clone(), &auth_provider).await;
+
+    let report = smoke::run(&options(address, "staging", true, Some(config.jwt_secret))).await;
+
+    assert!(report.passed(), "{report}");
+    assert_eq!(
+        step_names(&report),
+        ["health", "public_projects", "register_user", "create_draft", "request_presign", "cleanup"]
+    );
+    assert!(report.to_string().ends_with("smoke test passed (6 steps)"));
+    // The draft is gone; the throwaway user stays, as there is no way to delete one.
+    assert!(repo.get_user(user_id).await.is_some());
+    assert!(repo.get_my_project_summaries(user_id).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_writes_need_staging_and_the_flag() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let auth_provider = MockServer::start().await;
+    let (address, _) = serve(repo, &auth_provider).await;
+
+    for (env, allow_writes) in [("production", true), ("staging", false)] {
+        let report = smoke::run(&options(address.clone(), env, allow_writes, None)).await;
+        assert!(report.passed(), "{report}");
+        assert_eq!(step_names(&report), ["health", "public_projects"], "{env}");
+    }
+    assert!(auth_provider.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_stops_at_the_first_failure() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let auth_provider = MockServer::start().await;
+    Mock::given(matchers::path("/auth/v1/signup"))
+        .respond_with(ResponseTemplate::new(422))
+        .mount(&auth_provider)
+        .await;
+    let (address, _) = serve(repo, &auth_provider).await;
+
+    let report = smoke::run(&options(address, "staging", true, None)).await;
+
+    assert!(!report.passed());
+    assert_eq!(step_names(&report), ["health", "public_projects", "register_user"]);
+    let failure = report.first_failure().unwrap();
+    assert_eq!(failure.name, "register_user");
+    assert!(failure.result.as_ref().unwrap_err().to_string().contains("got 400 Bad Request"));
+    assert!(report.to_string().ends_with("smoke test failed at register_user"));
+}
+
+#[tokio::test]
+async fn test_unreachable_target_fails_the_health_check() {
+    // Bound and dropped, so nothing is listening on it.
+    let address = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+
+    let report = smoke::run(&options(format!("http://{address}"), "staging", true, None)).await;
+
+    assert_eq!(step_names(&report), ["health"]);
+    assert!(matches!(report.steps[0].result, Err(smoke::SmokeError::Http(_))));
+}
@@ -4,13 +4,14 @@ use axum::{
     http::{Request, StatusCode},
 };
 use fyp_portal::{
-    AppConfig, AppState, create_router,
+    AppConfig, AppStateBuilder, create_router,
     models::{
         AdminDashboardStats, Comment, CreateProjectRequest, Like, NotificationResponse,
-        PresignedUrlRequest, PresignedUrlResponse, Project, UpdateProjectRequest, User,
+        PresignedUrlRequest, PresignedUrlResponse, Project, SupervisorStats, UpdateProjectRequest,
+        User,
     },
     repository::{Repository, RepositoryState},
-    storage::MockStorageService,
+    storage::{MockStorageService, PresignedUrl, StorageService},
 };
 use std::sync::Arc;
 use tower::util::ServiceExt;
@@ -20,27 +21,52 @@ struct StubRepository;
 
 #[async_trait]
 impl Repository for StubRepository {
-    async fn get_projects(&self, _y: Option<i32>, _s: Option<String>) -> Vec<Project> {
-        vec![]
+    async fn get_projects(
+        &self,
+        _f: fyp_portal::repository::ProjectListFilter,
+        _p: fyp_portal::pagination::PageRequest,
+    ) -> (Vec<Project>, i64) {
+        (vec![], 0)
     }
     async fn get_all_projects(&self) -> Vec<Project> {
         vec![]
     }
-    async fn get_top_projects(&self, _l: i64) -> Vec<Project> {
+    async fn get_pending_reviews(&self, _limit: i64) -> Vec<fyp_portal::models::PendingReview> {
+        vec![]
+    }
+    async fn get_top_projects(&self, _l: i64, _m: i64, _y: Option<i32>) -> Vec<Project> {
+        vec![]
+    }
+    async fn get_year_archive(&self, _y: i32) -> Vec<Project> {
         vec![]
     }
+    async fn get_random_public_project(&self, _seed: Option<i64>) -> Option<Project> {
+        None
+    }
     async fn get_project(&self, _id: Uuid) -> Option<Project> {
         None
     }
     async fn create_project(&self, _r: CreateProjectRequest, _u: Uuid) -> Project {
         panic!("Stub called")
     }
-    async fn like_project(&self, _l: Like) -> bool {
+    async fn like_project(&self, _l: Like) -> Option<fyp_portal::models::LikeOutcome> {
+        None
+    }
+    async fn unlike_project(&self, _l: Like) -> bool {
         false
     }
-    async fn set_project_status(&self, _id: Uuid, _p: bool) -> Option<Project> {
+    async fn get_liked_project_ids(&self, _u: Uuid, _ids: &[Uuid]) -> Vec<Uuid> {
+        vec![]
+    }
+    async fn get_project_likers(&self, _p: Uuid, _page: fyp_portal::pagination::PageRequest) -> (Vec<fyp_portal::models::ProjectLiker>, i64) {
+        (vec![], 0)
+    }
+    async fn set_project_status(&self, _id: Uuid, _status: fyp_portal::models::ProjectStatus) -> Option<Project> {
         None
     }
+    async fn set_projects_status_bulk(&self, _ids: &[Uuid], _is_public: bool) -> fyp_portal::models::BulkStatusOutcome {
+        fyp_portal::models::BulkStatusOutcome::default()
+    }
     async fn get_user(&self, id: Uuid) -> Option<User> {
         // Return a valid test user for any UUID
         Some(User {
@@ -52,6 +78,9 @@ impl Repository for StubRepository {
     async fn create_user(&self, _u: User) -> User {
         panic!("Stub called")
     }
+    async fn get_users_by_email_prefix(&self, _prefixes: &[String]) -> Vec<User> {
+        Vec::new()
+    }
     async fn get_stats(&self) -> AdminDashboardStats {
         AdminDashboardStats {
             total_projects: 0,
@@ -61,7 +90,7 @@ impl Repository for StubRepository {
         }
     }
 
-    async fn get_my_projects(&self, _user_id: Uuid) -> Vec<Project> {
+    async fn get_my_project_summaries(&self, _user_id: Uuid) -> Vec<fyp_portal::models::MyProjectSummary> {
         vec![]
     }
 
@@ -74,21 +103,58 @@ impl Repository for StubRepository {
         _id: Uuid,
         _user_id: Uuid,
         _req: UpdateProjectRequest,
-    ) -> Option<Project> {
+    ) -> Option<fyp_portal::models::ProjectUpdateResult> {
         None
     }
 
-    async fn add_comment(&self, _p_id: Uuid, _u_id: Uuid, _text: String) -> Comment {
+    async fn add_comment(&self, _p_id: Uuid, _u_id: Uuid, _text: String, _t: i64) -> Result<Option<fyp_portal::models::PostedComment>, fyp_portal::repository::RepositoryError> {
         panic!("Stub called")
     }
 
-    async fn get_comments(&self, _project_id: Uuid) -> Vec<Comment> {
+    async fn get_comments(
+        &self,
+        _project_id: Uuid,
+        _o: fyp_portal::models::CommentOrder,
+        _h: fyp_portal::repository::HiddenComments,
+    ) -> Vec<Comment> {
         vec![]
     }
+    async fn get_comments_before(
+        &self,
+        _project_id: Uuid,
+        _h: fyp_portal::repository::HiddenComments,
+        _before: Option<fyp_portal::repository::CommentCursor>,
+        _limit: i64,
+    ) -> Vec<fyp_portal::models::Comment> {
+        vec![]
+    }
+    async fn get_comments_after(
+        &self,
+        _project_id: Uuid,
+        _h: fyp_portal::repository::HiddenComments,
+        _after: Option<fyp_portal::repository::CommentCursor>,
+        _limit: i64,
+    ) -> Vec<fyp_portal::models::Comment> {
+        vec![]
+    }
+    async fn get_recent_comments(&self, _user_id: Uuid) -> fyp_portal::models::RecentComments {
+        fyp_portal::models::RecentComments::default()
+    }
+    async fn count_comments(&self, _project_id: Uuid) -> Option<i64> {
+        None
+    }
 
     async fn delete_project_admin(&self, _id: Uuid) -> bool {
         false
     }
+    async fn update_comment(
+        &self,
+        _id: i64,
+        _user_id: Uuid,
+        _req: fyp_portal::models::UpdateCommentRequest,
+    ) -> Option<fyp_portal::models::CommentEditOutcome> {
+        None
+    }
     async fn delete_comment(&self, _id: i64, _user_id: Uuid) -> bool {
         false
     }
@@ -103,6 +169,9 @@ impl Repository for StubRepository {
     async fn mark_notification_read(&self, _notification_id: Uuid, _user_id: Uuid) -> bool {
         false
     }
+    async fn create_notification(&self, _n: fyp_portal::models::NewNotification) -> Option<fyp_portal::models::Notification> {
+        None
+    }
 
     async fn get_project_authorized(&self, id: Uuid, user_id: Uuid) -> Option<Project> {
         self.get_project(id)
@@ -113,6 +182,255 @@ impl Repository for StubRepository {
     async fn get_public_project(&self, id: Uuid) -> Option<Project> {
         self.get_project(id).await.filter(|p| p.is_public)
     }
+    async fn get_project_detail(&self, id: Uuid, viewer: fyp_portal::repository::ProjectViewer) -> Option<(Project, Option<fyp_portal::models::ProjectOwner>)> {
+        let project = match viewer {
+            fyp_portal::repository::ProjectViewer::Public => self.get_public_project(id).await,
+            fyp_portal::repository::ProjectViewer::User(user_id) => self.get_project_authorized(id, user_id).await,
+            fyp_portal::repository::ProjectViewer::Admin => self.get_project(id).await,
+        }?;
+        Some((project, None))
+    }
+    async fn set_comments_locked(&self, _id: Uuid, _user_id: Uuid, _l: bool) -> Option<Project> {
+        None
+    }
+    async fn set_comments_locked_admin(&self, _id: Uuid, _l: bool) -> Option<Project> {
+        None
+    }
+    async fn lock_comments_for_year(&self, _year: i32) -> i64 {
+        0
+    }
+    async fn are_comments_locked(&self, _project_id: Uuid) -> bool {
+        false
+    }
+    async fn mark_project_submitted(&self, _id: Uuid) -> Option<Project> {
+        None
+    }
+    async fn resubmit_project(&self, _id: Uuid, _owner_id: Uuid, _max_attempts: i32) -> Option<fyp_portal::models::ResubmitOutcome> {
+        None
+    }
+    async fn reassign_project_owner(&self, _project_id: Uuid, _new_owner_id: Uuid, _actor_id: Uuid) -> Option<fyp_portal::models::OwnerTransferOutcome> {
+        None
+    }
+    async fn get_supervisor_report(&self, _year: Option<i32>) -> Vec<SupervisorStats> {
+        vec![]
+    }
+    async fn notify_status_change(&self, _p: Uuid, _a: Uuid, _r: Option<String>) -> bool {
+        false
+    }
+    async fn set_subscription(&self, _p: Uuid, _u: Uuid, _s: fyp_portal::models::SubscriptionState) -> bool {
+        false
+    }
+    async fn get_subscription(&self, _p: Uuid, _u: Uuid) -> Option<fyp_portal::models::SubscriptionState> {
+        None
+    }
+    async fn delete_project_comment(&self, _p: Uuid, _id: i64, _u: Option<Uuid>) -> bool {
+        false
+    }
+    async fn get_public_stats(&self) -> fyp_portal::models::PublicStats {
+        fyp_portal::models::PublicStats::default()
+    }
+    async fn record_upload(
+        &self,
+        _u: Uuid,
+        _k: &str,
+        _p: fyp_portal::models::UploadPurpose,
+        _e: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        true
+    }
+    async fn get_upload_counts(&self, _u: Uuid, _p: fyp_portal::models::UploadPurpose) -> fyp_portal::models::UploadCounts {
+        fyp_portal::models::UploadCounts::default()
+    }
+    async fn is_own_upload(&self, _u: Uuid, _k: &str, _p: fyp_portal::models::UploadPurpose) -> bool {
+        false
+    }
+    async fn get_changed_project_ids(
+        &self,
+        _since: chrono::DateTime<chrono::Utc>,
+        _ids: Option<Vec<Uuid>>,
+    ) -> Vec<Uuid> {
+        vec![]
+    }
+    async fn get_public_events(
+        &self,
+        _since: chrono::DateTime<chrono::Utc>,
+        _limit: i64,
+    ) -> Vec<fyp_portal::models::PublicEvent> {
+        vec![]
+    }
+    async fn increment_view(&self, _id: Uuid) -> Option<i64> {
+        None
+    }
+    async fn get_like_count(&self, _id: Uuid) -> Option<i64> {
+        None
+    }
+    fn stream_all_projects(
+        &self,
+    ) -> futures::stream::BoxStream<
+        'static,
+        Result<fyp_portal::models::Project, fyp_portal::repository::RepositoryError>,
+    > {
+        Box::pin(futures::stream::empty())
+    }
+    fn stream_project_export(
+        &self,
+    ) -> futures::stream::BoxStream<
+        'static,
+        Result<fyp_portal::models::ProjectExportRow, fyp_portal::repository::RepositoryError>,
+    > {
+        Box::pin(futures::stream::empty())
+    }
+    async fn get_programmes(&self) -> Vec<fyp_portal::models::Programme> {
+        vec![]
+    }
+    async fn create_programme(
+        &self,
+        _code: &str,
+        _name: &str,
+    ) -> Option<fyp_portal::models::Programme> {
+        None
+    }
+    async fn unknown_programme_codes(&self, _codes: &[String]) -> Vec<String> {
+        vec![]
+    }
+    async fn get_project_programmes(&self, _project_id: Uuid) -> Vec<String> {
+        vec![]
+    }
+    async fn get_tag_counts(&self) -> Vec<fyp_portal::models::TagCount> {
+        vec![]
+    }
+    async fn get_project_years(&self) -> Vec<fyp_portal::models::YearCount> {
+        vec![]
+    }
+    async fn get_department_counts(&self) -> Vec<fyp_portal::models::DepartmentCount> {
+        vec![]
+    }
+    async fn get_technology_counts(&self) -> Vec<fyp_portal::models::TechnologyCount> {
+        vec![]
+    }
+    async fn add_feedback(&self, _project_id: Uuid, _message: &str, _contact_email: Option<&str>) -> Option<fyp_portal::models::ProjectFeedback> {
+        None
+    }
+    async fn get_feedback(&self, _project_id: Option<Uuid>) -> Vec<fyp_portal::models::ProjectFeedback> {
+        vec![]
+    }
+    async fn get_settings(
+        &self,
+    ) -> Result<Vec<fyp_portal::models::Setting>, fyp_portal::repository::RepositoryError> {
+        Ok(vec![])
+    }
+    async fn put_setting(
+        &self,
+        _key: &str,
+        _value: serde_json::Value,
+        _by: Uuid,
+    ) -> Option<fyp_portal::models::Setting> {
+        None
+    }
+    async fn get_stale_drafts(
+        &self,
+        _s: fyp_portal::repository::DraftSweep,
+        _before: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<fyp_portal::models::StaleDraft> {
+        vec![]
+    }
+    async fn mark_stale_draft(&self, _s: fyp_portal::repository::DraftSweep, _p: Uuid, _at: chrono::DateTime<chrono::Utc>) -> bool {
+        false
+    }
+    async fn get_projects_outside_years(&self, _min: i32, _max: i32) -> Vec<fyp_portal::models::InvalidYearProject> {
+        vec![]
+    }
+    async fn remap_project_year(&self, _from: i32, _to: i32) -> i64 {
+        0
+    }
+    async fn run_fanout_batch(&self, _batch_size: i64) -> Option<fyp_portal::models::OutboxJob> {
+        None
+    }
+    async fn get_outbox_job(&self, _id: i64) -> Option<fyp_portal::models::OutboxJob> {
+        None
+    }
+    async fn record_audit(&self, _entry: &fyp_portal::models::NewAuditEntry) -> Result<(), fyp_portal::repository::RepositoryError> {
+        Ok(())
+    }
+    async fn queue_audit(&self, _entry: &fyp_portal::models::NewAuditEntry) -> Result<i64, fyp_portal::repository::RepositoryError> {
+        Ok(0)
+    }
+    async fn get_audit_log(&self, _filter: &fyp_portal::repository::AuditFilter, _page: fyp_portal::pagination::PageRequest) -> (Vec<fyp_portal::models::AuditEntry>, i64) {
+        (vec![], 0)
+    }
+    fn stream_audit_log(
+        &self,
+        _filter: fyp_portal::repository::AuditFilter,
+    ) -> futures::stream::BoxStream<
+        'static,
+        Result<fyp_portal::models::AuditEntry, fyp_portal::repository::RepositoryError>,
+    > {
+        Box::pin(futures::stream::empty())
+    }
+    async fn purge_audit_log(&self, _before: chrono::DateTime<chrono::Utc>) -> Result<u64, fyp_portal::repository::RepositoryError> {
+        Ok(0)
+    }
+    async fn flush_audit_outbox(&self) -> Result<u64, fyp_portal::repository::RepositoryError> {
+        Ok(0)
+    }
+    async fn resolve_project_slug(&self, _slug: &str) -> Option<Uuid> {
+        None
+    }
+    async fn find_project_by_title_year(&self, _title: &str, _year: i32) -> Option<Uuid> {
+        None
+    }
+    async fn count_user_projects_in_year(&self, _user_id: Uuid, _year: i32) -> i64 {
+        0
+    }
+    async fn get_user_activity(
+        &self,
+        _u: Uuid,
+        _p: fyp_portal::pagination::PageRequest,
+    ) -> (Vec<fyp_portal::models::ActivityItem>, i64) {
+        (vec![], 0)
+    }
+    async fn get_project_stats(&self, _id: Uuid, _owner_id: Uuid) -> Option<fyp_portal::models::ProjectStats> {
+        None
+    }
+    async fn record_project_review(
+        &self,
+        _p: Uuid,
+        _r: Uuid,
+        _s: fyp_portal::models::ProjectStatus,
+        _reason: Option<String>,
+    ) -> bool {
+        false
+    }
+    async fn get_latest_reviews(&self, _o: Uuid) -> Vec<fyp_portal::models::ProjectReview> {
+        vec![]
+    }
+    async fn ping(&self) -> Result<(), fyp_portal::repository::RepositoryError> {
+        Ok(())
+    }
+    async fn create_report(
+        &self,
+        _p: Option<Uuid>,
+        _c: i64,
+        _u: Uuid,
+        _r: Option<String>,
+        _t: i64,
+    ) -> Option<fyp_portal::models::ReportOutcome> {
+        None
+    }
+    async fn restore_comment(&self, _id: i64) -> bool {
+        false
+    }
+    async fn get_reported_comments(&self) -> Vec<fyp_portal::models::ReportedComment> {
+        vec![]
+    }
+    async fn get_admin_comments(
+        &self,
+        _: &fyp_portal::repository::AdminCommentFilter,
+        _: i64,
+        _: i64,
+    ) -> Vec<fyp_portal::models::AdminComment> {
+        vec![]
+    }
 }
 
 //#[cfg(test)]
@@ -132,11 +450,11 @@ fn app(mock_storage: MockStorageService) -> axum::Router {
     let storage = Arc::new(mock_storage);
     let config = AppConfig::load();
 
-    let state = AppState {
-        repo,
-        storage,
-        config,
-    };
+    let state = AppStateBuilder::test_default()
+        .repo(repo)
+        .storage(storage)
+        .config(config)
+        .build();
     create_router(state)
 }
 
@@ -148,6 +466,7 @@ async fn test_presigned_url_success() {
     let payload = PresignedUrlRequest {
         filename: "test_video.mp4".to_string(),
         file_type: "video/mp4".to_string(),
+        purpose: None,
     };
 
     let response = app
@@ -183,6 +502,7 @@ async fn test_presigned_url_sanitization() {
     let payload = PresignedUrlRequest {
         filename: "../../etc/passwd.exe".to_string(),
         file_type: "application/binary".to_string(),
+        purpose: None,
     };
 
     let response = app
@@ -217,6 +537,7 @@ async fn test_presigned_url_storage_failure() {
     let payload = PresignedUrlRequest {
         filename: "valid.mp4".to_string(),
         file_type: "video/mp4".to_string(),
+        purpose: None,
     };
 
     let response = app
@@ -234,3 +555,70 @@ async fn test_presigned_url_storage_failure() {
 
     assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
 }
+
+/// Storage backend whose presign failure quotes the signed request URL, as SDK errors can.
+struct LeakyStorage;
+
+const LEAKED_URL: &str = "https://storage.example.com/fyp/uploads/a.mp4?X-Amz-Credential=AKIAEXAMPLEKEY%2F20240101&X-Amz-Signature=deadbeefcafe";
+
+#[async_trait]
+impl StorageService for LeakyStorage {
+    async fn ensure_bucket_exists(&self) {}
+
+    async fn get_presigned_upload_url(
+        &self,
+        _key: &str,
+        _content_type: &str,
+    ) -> Result<PresignedUrl, String> {
+        Err(format!("dispatch failure: error sending request for url ({})", LEAKED_URL))
+    }
+
+    async fn object_exists(&self, _key: &str) -> Result<bool, String> {
+        Ok(true)
+    }
+
+    async fn get_media_url(&self, _key: &str) -> Result<PresignedUrl, String> {
+        Err(format!("dispatch failure: error sending request for url ({})", LEAKED_URL))
+    }
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn test_presigned_url_storage_error_log_strips_signature() {
+    setup_test_environment();
+    let state = AppStateBuilder::test_default()
+        .repo(Arc::new(StubRepository))
+        .storage(Arc::new(LeakyStorage))
+        .config(AppConfig::load())
+        .build();
+    let app = create_router(state);
+
+    let payload = PresignedUrlRequest {
+        filename: "valid.mp4".to_string(),
+        file_type: "video/mp4".to_string(),
+        purpose: None,
+    };
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/upload/presigned")
+                .header("Content-Type", "application/json")
+                .header("x-user-id", Uuid::new_v4().to_string())
+                .body(Body::from(serde_json::to_string(&payload).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(!String::from_utf8_lossy(&body).contains("X-Amz"));
+
+    assert!(logs_contain("Storage Error"));
+    assert!(logs_contain("https://storage.example.com/fyp/uploads/a.mp4?[REDACTED]"));
+    assert!(!logs_contain("X-Amz-Signature"));
+    assert!(!logs_contain("AKIAEXAMPLEKEY"));
+}
@@ -0,0 +1,95 @@
+//! Supervised background tasks: restarts with backoff, and their health in GET /health/deep.
+
+use axum::{extract::State, http::StatusCode};
+use fyp_portal::{
+    AppState, AppStateBuilder,
+    clock::MockClock,
+    handlers,
+    models::HealthStatus,
+    repository::RepositoryError,
+    supervisor::{self, MAX_BACKOFF, TaskMonitor},
+};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+fn state(tasks: Arc<TaskMonitor>) -> AppState {
+    AppStateBuilder::test_default()
+        .tasks(tasks)
+        .build()
+}
+
+#[test]
+fn test_backoff_doubles_up_to_the_cap() {
+    let waits: Vec<_> = (1..=4).map(supervisor::backoff).collect();
+    assert_eq!(waits, [1, 2, 4, 8].map(Duration::from_secs));
+    assert_eq!(supervisor::backoff(10), MAX_BACKOFF);
+    assert_eq!(supervisor::backoff(u32::MAX), MAX_BACKOFF);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_failing_passes_are_restarted_until_they_recover() {
+    let monitor = Arc::new(TaskMonitor::default());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    // The first pass fails (as on a dropped connection), the second panics, then it recovers.
+    let handle = supervisor::spawn_supervised(monitor.clone(), "flaky", Duration::from_secs(60), move || {
+        let call = counter.fetch_add(1, Ordering::SeqCst);
+        async move {
+            match call {
+                0 => Err(RepositoryError("connection reset".to_string())),
+                1 => panic!("pool closed"),
+                _ => Ok(()),
+            }
+        }
+    });
+    // Backoff is 1s then 2s, well within one interval.
+    tokio::time::sleep(Duration::from_secs(10)).await;
+
+    let report = monitor.report();
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(report.len(), 1);
+    let task = &report[0];
+    assert_eq!((task.name.as_str(), task.interval_secs), ("flaky", 60));
+    assert_eq!((task.restarts, task.consecutive_failures), (2, 0));
+    assert!(task.last_success.is_some());
+    assert!(!task.stale);
+
+    // The loop survived the panic and keeps its schedule.
+    tokio::time::sleep(Duration::from_secs(60)).await;
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+    assert!(!handle.is_finished());
+    handle.abort();
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stuck_task_turns_deep_health_amber() {
+    let clock = Arc::new(MockClock::new());
+    let monitor = Arc::new(TaskMonitor::new(clock.clone()));
+    let healthy = supervisor::spawn_supervised(monitor.clone(), "healthy", Duration::from_secs(10), || async { Ok(()) });
+    // A pass that never returns, like a dispatcher blocked on a dead connection.
+    let stuck = supervisor::spawn_supervised(monitor.clone(), "stuck", Duration::from_secs(10), std::future::pending);
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let (code, body) = handlers::get_deep_health(State(state(monitor.clone()))).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(body.status, HealthStatus::Green);
+    assert!(body.database);
+
+    // Three intervals without a success make the stuck task stale; the other keeps passing.
+    clock.advance(chrono::Duration::seconds(31));
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    let (code, body) = handlers::get_deep_health(State(state(monitor))).await;
+    assert_eq!(code, StatusCode::OK);
+    assert_eq!(body.status, HealthStatus::Amber);
+    let stale: Vec<_> = body.tasks.iter().map(|t| (t.name.as_str(), t.stale)).collect();
+    assert_eq!(stale, [("healthy", false), ("stuck", true)]);
+    assert!(body.tasks[1].last_success.is_none());
+
+    healthy.abort();
+    stuck.abort();
+}
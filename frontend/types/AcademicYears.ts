@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AcademicYears
+ *
+ * The cohort years projects may be filed under, inclusive (the `academic_years` setting).
+ */
+export type AcademicYears = { min: number, max: number, };
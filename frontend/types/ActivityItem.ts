@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ActivityItem
+ *
+ * One entry of the caller's activity feed (GET /me/activity), tagged by `kind`: a comment
+ * they wrote, a project they liked, or a visibility change of a project they own.
+ */
+export type ActivityItem = { "kind": "commented", at: string, project_id: string, project_title: string, comment_id: bigint, comment: string, } | { "kind": "liked", at: string, project_id: string, project_title: string, } | { "kind": "status_changed", at: string, project_id: string, project_title: string, is_public: boolean, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AdminComment
+ *
+ * A row of the moderators' comment listing (GET /admin/comments): any comment, hidden ones
+ * included, with the title of the project it was left on.
+ */
+export type AdminComment = { project_title: string, id: bigint, user_id: string | null, project_id: string, comment: string, created_at: string, author_email: string | null, hidden_pending_review: boolean, edited_at: string | null, edited: boolean, is_deleted: boolean, };
@@ -1,7 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
 /**
- * --- Dashboard & Profile Schemas (Output) ---
  * AdminDashboardStats
  *
  * Output schema for the administrative statistics dashboard (GET /admin/stats).
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * AuditEntry
+ *
+ * One admin action in the audit log (GET /admin/audit): who (`actor_id`, null for the system
+ * itself) did `action` to which record, with action-specific `details`.
+ */
+export type AuditEntry = { id: bigint, actor_id: string | null, action: string, target_type: string, target_id: string | null, details: unknown, created_at: string, };
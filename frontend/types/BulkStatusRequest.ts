@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * BulkStatusRequest
+ *
+ * Admin payload for publishing or hiding many projects at once (POST /admin/projects/bulk_status).
+ * `is_public: true` approves every listed project and `false` rejects them, like the legacy
+ * boolean body of the single-project endpoint.
+ */
+export type BulkStatusRequest = { ids: Array<string>, is_public: boolean, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * BulkStatusResponse
+ *
+ * Output schema for POST /admin/projects/bulk_status: how many projects changed status, and
+ * the requested ids that matched no project.
+ */
+export type BulkStatusResponse = { updated: bigint, not_found: Array<string>, };
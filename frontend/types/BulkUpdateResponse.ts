@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * BulkUpdateResponse
+ *
+ * Output schema for admin bulk actions, reporting how many rows were changed.
+ */
+export type BulkUpdateResponse = { updated: bigint, };
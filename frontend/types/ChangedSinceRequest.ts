@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ChangedSinceRequest
+ *
+ * Input for POST /projects/changed-since. The client sends the time of its last visit and,
+ * optionally, the projects it is currently showing (at most 200).
+ */
+export type ChangedSinceRequest = { since: string, 
+/**
+ * Restricts the check to these projects; omitted means every public project.
+ */
+ids: Array<string> | null, };
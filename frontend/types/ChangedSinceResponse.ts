@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ChangedSinceResponse
+ *
+ * Public projects edited or commented on after `since`. `since` echoes the cutoff that was
+ * actually applied, which is clamped to at most 90 days back.
+ */
+export type ChangedSinceResponse = { since: string, ids: Array<string>, };
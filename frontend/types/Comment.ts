@@ -6,4 +6,4 @@
  * Represents a comment record from the `public.project_comments` table, augmented with
  * the author's email (a join operation).
  */
-export type Comment = { id: bigint, user_id: string, project_id: string, comment: string, created_at: string, author_email: string | null, };
+export type Comment = { id: bigint, user_id: string | null, project_id: string, comment: string, created_at: string, author_email: string | null, hidden_pending_review: boolean, edited_at: string | null, edited: boolean, is_deleted: boolean, };
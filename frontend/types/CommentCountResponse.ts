@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * CommentCountResponse
+ *
+ * Body of GET /projects/{id}/comments/count: how many comments the public thread shows.
+ */
+export type CommentCountResponse = { count: bigint, };
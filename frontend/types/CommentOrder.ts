@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * CommentOrder
+ *
+ * Sort order for a project's comment thread (GET /projects/{id}/comments?order=...).
+ */
+export type CommentOrder = "oldest" | "newest";
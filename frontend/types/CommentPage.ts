@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Comment } from "./Comment";
+
+/**
+ * CommentPage
+ *
+ * Response of GET /projects/{id}/comments. Without `limit` or `before` it holds the whole
+ * thread; a page holds older comments oldest first, and `has_more` says that a request with
+ * `before` set to its first comment's id returns more.
+ */
+export type CommentPage = { comments: Array<Comment>, has_more: boolean, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * CommentsLockRequest
+ *
+ * Input payload for locking or unlocking discussion on a project (PUT /projects/{id}/comments-lock).
+ */
+export type CommentsLockRequest = { locked: boolean, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * CreateFeedbackRequest
+ *
+ * Anonymous feedback on a public project (POST /projects/{id}/feedback). The message must be
+ * 10-1000 characters once trimmed. `captcha_token` is required only when a CAPTCHA secret is
+ * configured.
+ */
+export type CreateFeedbackRequest = { message: string, contact_email: string | null, captcha_token: string | null, };
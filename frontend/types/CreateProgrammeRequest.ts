@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * CreateProgrammeRequest
+ *
+ * Admin payload for adding a programme (POST /admin/programmes). The code is upper-cased;
+ * it must be 2-16 letters, digits or dashes.
+ */
+export type CreateProgrammeRequest = { code: string, name: string, };
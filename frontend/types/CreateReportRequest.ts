@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * CreateReportRequest
+ *
+ * Input payload for reporting a comment (POST /projects/{id}/comments/{comment_id}/reports, or
+ * POST /comments/{id}/report).
+ */
+export type CreateReportRequest = { reason: string | null, };
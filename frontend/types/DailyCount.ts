@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * DailyCount
+ *
+ * A count for one UTC calendar day, e.g. the likes a project received that day.
+ */
+export type DailyCount = { day: string, count: bigint, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HealthStatus } from "./HealthStatus";
+import type { TaskHealth } from "./TaskHealth";
+
+/**
+ * DeepHealth
+ *
+ * Response of GET /health/deep: the database round trip and every background task.
+ */
+export type DeepHealth = { status: HealthStatus, database: boolean, tasks: Array<TaskHealth>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * DepartmentCount
+ *
+ * One entry of GET /departments: a department and the number of public projects in it.
+ */
+export type DepartmentCount = { department: string, count: bigint, };
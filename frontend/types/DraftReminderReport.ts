@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StaleDraft } from "./StaleDraft";
+
+/**
+ * DraftReminderReport
+ *
+ * Outcome of one stale-draft maintenance pass (POST /admin/maintenance/draft-reminders).
+ * With `dry_run` the lists show what would happen and nothing is written.
+ */
+export type DraftReminderReport = { dry_run: boolean, 
+/**
+ * Owners notified with a "draft_reminder" (once per draft, ever).
+ */
+reminded: Array<StaleDraft>, 
+/**
+ * Very old drafts flagged for the orphaned-upload cleanup.
+ */
+flagged_for_cleanup: Array<StaleDraft>, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * EmbedDocument
+ *
+ * oEmbed-style description of a project's embeddable card (GET /embed/projects/{id}?format=json).
+ * `html` is ready-to-paste iframe markup.
+ */
+export type EmbedDocument = { type: string, version: string, title: string, author_name: string, provider_name: string, provider_url: string, url: string, thumbnail_url: string | null, html: string, width: number, height: number, };
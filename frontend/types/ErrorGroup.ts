@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ErrorGroup
+ *
+ * Errors of one route and code within the requested window.
+ */
+export type ErrorGroup = { route: string, code: string, status: number, count: bigint, last_seen: string, };
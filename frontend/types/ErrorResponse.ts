@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ErrorResponse
+ *
+ * The JSON body returned for every structured API error. `code` is a stable, machine-readable
+ * identifier the frontend can branch on; `message` is a human-readable explanation.
+ * `details` carries optional, error-specific context (e.g. rate limit counters, failed checks).
+ */
+export type ErrorResponse = { code: string, message: string, details: unknown, };
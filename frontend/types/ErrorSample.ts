@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ErrorSample
+ *
+ * One 5xx or 429 response kept by the in-process error log. Only the route template is
+ * recorded (never the concrete path, query or body), so samples carry no personal data.
+ */
+export type ErrorSample = { at: string, route: string, status: number, code: string, request_id: string | null, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ErrorGroup } from "./ErrorGroup";
+import type { ErrorSample } from "./ErrorSample";
+import type { ErrorWindow } from "./ErrorWindow";
+
+/**
+ * ErrorSummary
+ *
+ * Response of GET /admin/errors: error counts per route and code since `since`, most
+ * frequent first, and the newest samples of that window.
+ */
+export type ErrorSummary = { window: ErrorWindow, since: string, total: bigint, groups: Array<ErrorGroup>, recent: Array<ErrorSample>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ErrorWindow
+ *
+ * How far back GET /admin/errors looks: the last 15 minutes, hour (default) or day.
+ */
+export type ErrorWindow = "15m" | "1h" | "24h";
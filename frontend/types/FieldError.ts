@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * FieldError
+ *
+ * One failed check in a 422 `validation_failed` response, which lists every failing field of
+ * the payload in `details.errors` so a form can mark them all at once.
+ */
+export type FieldError = { field: string, code: string, message: string, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * HealthStatus
+ *
+ * Overall verdict of GET /health/deep: `red` when the database is unreachable, `amber` when a
+ * background task has gone too long without a successful pass, `green` otherwise.
+ */
+export type HealthStatus = "green" | "amber" | "red";
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * InvalidYearProject
+ *
+ * A project whose year lies outside the configured academic years.
+ */
+export type InvalidYearProject = { project_id: string, owner_id: string, title: string, year: number, };
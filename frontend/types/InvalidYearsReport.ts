@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AcademicYears } from "./AcademicYears";
+import type { InvalidYearProject } from "./InvalidYearProject";
+
+/**
+ * InvalidYearsReport
+ *
+ * Response of GET /admin/maintenance/invalid-years: the allowed range and every project
+ * outside it, by year.
+ */
+export type InvalidYearsReport = { allowed: AcademicYears, projects: Array<InvalidYearProject>, };
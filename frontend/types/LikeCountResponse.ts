@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * LikeCountResponse
+ *
+ * Body of GET /projects/{id}/likes: the project's current like total.
+ */
+export type LikeCountResponse = { count: bigint, };
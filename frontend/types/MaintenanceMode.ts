@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * MaintenanceMode
+ *
+ * Value of the `maintenance_mode` setting. `read_only` is meant for migrations and exports:
+ * reads keep working while writes are refused.
+ */
+export type MaintenanceMode = "off" | "read_only";
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectReview } from "./ProjectReview";
+import type { ProjectStatus } from "./ProjectStatus";
+import type { ReviewState } from "./ReviewState";
+
+/**
+ * MyProjectSummary
+ *
+ * An entry of GET /me/projects: the project, flattened (with its `comment_count`), plus its
+ * like count, its `review_state` and the latest admin review so the owner can see why a
+ * project was rejected or hidden.
+ */
+export type MyProjectSummary = { like_count: bigint, review_state: ReviewState, latest_review?: ProjectReview, id: string, user_id: string, author: string, title: string, 
+/**
+ * Maps SQL column "abstract" to Rust field "abstract_text".
+ * This renaming is necessary because `abstract` is a reserved keyword in Rust.
+ */
+abstract_text: string, cover_image: string, video: string | null, report: string | null, status: ProjectStatus, is_public: boolean, report_is_public: boolean, comments_locked: boolean, links: Record<string, string>, video_waived: boolean, submitted_at: string | null, supervisor: string | null, department: string | null, technologies: Array<string>, view_count: bigint, slug: string, year: number, comment_count: bigint, tags: Array<string>, created_at: string, updated_at: string, };
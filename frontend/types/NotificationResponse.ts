@@ -6,4 +6,4 @@
  * Enriched response structure for the Frontend (UI Ready).
  * This is the result of joining the internal `Notification` row with user and project details.
  */
-export type NotificationResponse = { id: string, actor_email: string, project_id: string, project_title: string, type: string, is_read: boolean, created_at: string, };
+export type NotificationResponse = { id: string, actor_email: string, project_id: string, project_title: string, type: string, message: string | null, is_read: boolean, created_at: string, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * OutboxJob
+ *
+ * A queued background job (GET /admin/outbox/{id}). A `pending_fanout` job writes one
+ * notification per recipient in batches; `processed` counts the ones written so far against
+ * the `total` counted when it was queued (recipients who join or mute meanwhile can make the
+ * final figure differ). An `audit_entry` job holds one audit entry whose direct insert failed.
+ */
+export type OutboxJob = { id: bigint, kind: string, status: string, payload: unknown, total: bigint, processed: bigint, created_at: string, updated_at: string, completed_at: string | null, };
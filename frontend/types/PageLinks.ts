@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * PageLinks
+ *
+ * Absolute URLs for navigating a paginated listing. Each keeps the caller's original query
+ * parameters (filters, `fields`, ...) and swaps only `page`. `next` is omitted on the last
+ * page and `prev` on the first.
+ */
+export type PageLinks = { self: string, next?: string, prev?: string, };
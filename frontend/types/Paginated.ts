@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PageLinks } from "./PageLinks";
+
+/**
+ * Paginated
+ *
+ * Envelope for a single page of a listing. `page` is 1-based; `total_count` counts every
+ * matching item, not just this page. `next_cursor` continues after the last item (keyset
+ * pagination) and is `null` once a page comes back short.
+ */
+export type Paginated<T> = { items: Array<T>, total_count: bigint, page: bigint, per_page: bigint, links: PageLinks, next_cursor: string | null, };
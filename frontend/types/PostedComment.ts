@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * PostedComment
+ *
+ * Response of POST /projects/{id}/comments: the new comment and, when its notifications were
+ * too many to write in the request, the outbox job that sends them (see GET /admin/outbox/{id}).
+ */
+export type PostedComment = { fanout_job_id?: bigint, id: bigint, user_id: string | null, project_id: string, comment: string, created_at: string, author_email: string | null, hidden_pending_review: boolean, edited_at: string | null, edited: boolean, is_deleted: boolean, };
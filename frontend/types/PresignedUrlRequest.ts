@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UploadPurpose } from "./UploadPurpose";
 
 /**
  * PresignedUrlRequest
@@ -14,4 +15,8 @@ filename: string,
 /**
  * The MIME type, used to constrain the S3 upload to the allowed type (security).
  */
-file_type: string, };
+file_type: string, 
+/**
+ * What the file will be attached to. Inferred from `file_type` when omitted.
+ */
+purpose?: UploadPurpose, };
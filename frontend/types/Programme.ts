@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Programme
+ *
+ * A degree programme from the admin-managed `programmes` lookup. Projects reference
+ * programmes by `code` through the `project_programmes` join table.
+ */
+export type Programme = { code: string, name: string, };
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectStatus } from "./ProjectStatus";
 
 /**
  * Project
@@ -11,4 +12,4 @@ export type Project = { id: string, user_id: string, author: string, title: stri
  * Maps SQL column "abstract" to Rust field "abstract_text".
  * This renaming is necessary because `abstract` is a reserved keyword in Rust.
  */
-abstract_text: string, cover_image: string, video: string | null, report: string | null, is_public: boolean, report_is_public: boolean, year: number, created_at: string, updated_at: string, };
+abstract_text: string, cover_image: string, video: string | null, report: string | null, status: ProjectStatus, is_public: boolean, report_is_public: boolean, comments_locked: boolean, links: Record<string, string>, video_waived: boolean, submitted_at: string | null, supervisor: string | null, department: string | null, technologies: Array<string>, view_count: bigint, slug: string, year: number, comment_count: bigint, tags: Array<string>, created_at: string, updated_at: string, };
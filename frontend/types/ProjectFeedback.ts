@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ProjectFeedback
+ *
+ * One piece of anonymous feedback, as the project owner or an admin reads it
+ * (GET /me/projects/{id}/feedback, GET /admin/feedback).
+ */
+export type ProjectFeedback = { id: bigint, project_id: string, message: string, contact_email: string | null, created_at: string, };
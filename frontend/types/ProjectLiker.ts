@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ProjectLiker
+ *
+ * One entry of GET /projects/{id}/likes/users: a user who liked the project.
+ */
+export type ProjectLiker = { id: string, email: string | null, liked_at: string, };
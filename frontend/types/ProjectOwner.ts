@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ProjectOwner
+ *
+ * The owner of a project as shown on its detail page ("by ..."), from their `profiles` row.
+ * Listings carry only `user_id`.
+ */
+export type ProjectOwner = { id: string, email: string, avatar_url: string | null, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectStatus } from "./ProjectStatus";
+
+/**
+ * ProjectReview
+ *
+ * An admin status decision from `project_reviews`, as shown to the project owner. The
+ * reviewer is recorded but not exposed. A resubmission by the owner is recorded as a
+ * `pending` review without a reason.
+ */
+export type ProjectReview = { project_id: string, status: ProjectStatus, reason: string | null, reviewed_at: string, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ProjectSort
+ *
+ * Order of the public project listing (GET /projects?sort=...). Ties fall back to newest
+ * first, then id, so page boundaries are stable between requests.
+ */
+export type ProjectSort = "newest" | "oldest" | "most_liked" | "title" | "most_viewed";
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DailyCount } from "./DailyCount";
+
+/**
+ * ProjectStats
+ *
+ * Engagement figures for one of the caller's projects (GET /me/projects/{id}/stats), e.g.
+ * for the slides of a final presentation.
+ */
+export type ProjectStats = { project_id: string, like_count: bigint, comment_count: bigint, view_count: bigint, likes_per_day: Array<DailyCount>, };
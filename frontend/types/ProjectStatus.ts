@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ProjectStatus
+ *
+ * Where a project is in the review flow, stored as lower-case text in `projects.status`.
+ * New projects start as `draft`, submitting moves them to `pending`, and an admin approves
+ * (publishes) or rejects them.
+ */
+export type ProjectStatus = "draft" | "pending" | "approved" | "rejected";
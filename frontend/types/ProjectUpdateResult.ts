@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Project } from "./Project";
+
+/**
+ * ProjectUpdateResult
+ *
+ * Response of PUT /projects/{id}: the project after the update and the request fields that
+ * actually changed it, spelled as in `UpdateProjectRequest`. An empty list means the update
+ * was a no-op (`updated_at` is left alone).
+ * `truncated` and `abstract_length` are filled in by the handler (see `?truncate=true`).
+ */
+export type ProjectUpdateResult = { project: Project, changed_fields: Array<string>, truncated: boolean, abstract_length: number, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Project } from "./Project";
+import type { PublicEventKind } from "./PublicEventKind";
+
+/**
+ * PublicEvent
+ *
+ * One entry of the publication feed (GET /events/public). `project` is the public project
+ * as it is now, so it is only present on `project.published` events whose project is still
+ * public; hidden or deleted projects are never disclosed through older events.
+ */
+export type PublicEvent = { id: bigint, kind: PublicEventKind, project_id: string, at: string, project: Project | null, };
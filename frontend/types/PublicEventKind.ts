@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * PublicEventKind
+ *
+ * What happened to a project's public visibility. Deleting a public project also counts as
+ * unpublishing it.
+ */
+export type PublicEventKind = "project.published" | "project.unpublished";
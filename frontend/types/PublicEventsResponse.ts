@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PublicEvent } from "./PublicEvent";
+
+/**
+ * PublicEventsResponse
+ *
+ * A page of the publication feed, oldest first. Pass `next_since` as `since` on the next
+ * call; `has_more` means that call will return more events straight away.
+ */
+export type PublicEventsResponse = { events: Array<PublicEvent>, next_since: string, has_more: boolean, };
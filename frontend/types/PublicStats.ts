@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * PublicStats
+ *
+ * Landing-page counters (GET /stats/public). Computed over public projects only, so unlike
+ * `AdminDashboardStats` it carries nothing about pending reviews or users.
+ */
+export type PublicStats = { public_projects: bigint, distinct_years: bigint, distinct_supervisors: bigint, 
+/**
+ * Likes on public projects only.
+ */
+total_likes: bigint, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ReadinessCheck
+ *
+ * A single, named rule from the submission checklist. `detail` explains a failure
+ * (or confirms a pass) in words the student can act on.
+ */
+export type ReadinessCheck = { name: string, passed: boolean, detail: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReadinessCheck } from "./ReadinessCheck";
+
+/**
+ * ReadinessReport
+ *
+ * Output schema for the submission checklist (GET /me/projects/{id}/readiness).
+ * `ready` is true only when every check passed.
+ */
+export type ReadinessReport = { ready: boolean, checks: Array<ReadinessCheck>, };
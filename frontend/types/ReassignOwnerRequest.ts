@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ReassignOwnerRequest
+ *
+ * Input payload for PUT /admin/projects/{id}/owner.
+ */
+export type ReassignOwnerRequest = { new_owner_id: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ReportedComment
+ *
+ * An entry of the moderation queue (GET /admin/comments/reported): a comment with pending
+ * reports, how many distinct users filed them, when the latest came in and the reasons given.
+ * It leaves the queue once a moderator restores (dismisses) or deletes the comment.
+ */
+export type ReportedComment = { project_title: string, report_count: bigint, last_reported_at: string, reasons: Array<string>, id: bigint, user_id: string | null, project_id: string, comment: string, created_at: string, author_email: string | null, hidden_pending_review: boolean, edited_at: string | null, edited: boolean, is_deleted: boolean, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ReviewState
+ *
+ * The owner dashboard's short answer to "where is my project?": `approved` (public),
+ * `hidden` (rejected) or `pending` (neither yet, whether still a draft or awaiting review;
+ * `status` tells those two apart).
+ */
+export type ReviewState = "pending" | "approved" | "hidden";
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Setting
+ *
+ * One runtime setting (GET/PUT /admin/settings/{key}). Keys that were never set report their
+ * default value with no `updated_at`.
+ */
+export type Setting = { key: string, value: unknown, updated_at: string | null, updated_by: string | null, };
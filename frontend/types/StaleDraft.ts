@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * StaleDraft
+ *
+ * A draft (never submitted, not public) found by a stale-draft sweep.
+ */
+export type StaleDraft = { project_id: string, owner_id: string, title: string, updated_at: string, };
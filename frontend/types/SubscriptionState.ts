@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * SubscriptionState
+ *
+ * A user's comment-notification preference for a project (PUT /projects/{id}/subscription).
+ */
+export type SubscriptionState = "subscribed" | "muted";
@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * SupervisorStats
+ *
+ * One row of the staff supervisor report (GET /admin/reports/supervisors).
+ * Projects with no supervisor are grouped under "Unassigned".
+ */
+export type SupervisorStats = { supervisor: string, project_count: bigint, 
+/**
+ * Number of those projects currently visible to the public.
+ */
+public_count: bigint, 
+/**
+ * Mean like count per project (0 when the supervisor's projects have no likes).
+ */
+avg_likes: number, total_comments: bigint, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * TagCount
+ *
+ * One entry of GET /tags: a tag and the number of public projects carrying it.
+ */
+export type TagCount = { tag: string, count: bigint, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * TaskHealth
+ *
+ * One supervised background task as reported by GET /health/deep. Error messages are only
+ * logged, never exposed here.
+ */
+export type TaskHealth = { name: string, interval_secs: bigint, last_success: string | null, consecutive_failures: number, restarts: bigint, stale: boolean, };
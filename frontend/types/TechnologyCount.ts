@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * TechnologyCount
+ *
+ * One entry of GET /technologies: a technology and the number of public projects using it.
+ */
+export type TechnologyCount = { technology: string, count: bigint, };
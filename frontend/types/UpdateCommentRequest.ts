@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * UpdateCommentRequest
+ *
+ * Input payload for correcting one's own comment (PUT /comments/{id}).
+ */
+export type UpdateCommentRequest = { text: string, };
@@ -4,8 +4,37 @@
  * UpdateProjectRequest
  *
  * Partial update payload for modifying an existing project (PUT /projects/{id}).
+ * `user_id` is fixed at creation; a payload naming it is rejected (see `extract::ProjectPatch`).
  *
  * *Optimization*: Uses `Option<T>` for all fields and `#[serde(skip_serializing_if = "Option::is_none")]`
  * to efficiently handle partial updates, ensuring only provided fields are included in the JSON payload.
  */
-export type UpdateProjectRequest = { title: string | null, abstract_text: string | null, cover_image_key: string | null, video_key: string | null, report_key: string | null, };
+export type UpdateProjectRequest = { title: string | null, abstract_text: string | null, cover_image_key: string | null, video_key: string | null, report_key: string | null, 
+/**
+ * Corrects the author's name (also accepted as `author`); must not be blank.
+ */
+author_name: string | null, 
+/**
+ * Moves the project to another cohort year, within the `academic_years` setting.
+ */
+year: number | null, 
+/**
+ * Replaces the whole link map when provided.
+ */
+links: { [key in string]?: string } | null, video_waived: boolean | null, supervisor: string | null, 
+/**
+ * One of the configured DEPARTMENTS, matched case-insensitively.
+ */
+department: string | null, 
+/**
+ * Replaces the project's whole programme set when provided.
+ */
+programme_codes: Array<string> | null, 
+/**
+ * Replaces the project's whole tag set when provided (same limits as on create).
+ */
+tags: Array<string> | null, 
+/**
+ * Replaces the project's technologies when provided (same limits as on create).
+ */
+technologies: Array<string> | null, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * UpdateSettingRequest
+ *
+ * Admin payload for PUT /admin/settings/{key}. `value` is validated against the key's
+ * registered rule before it is stored.
+ */
+export type UpdateSettingRequest = { value: unknown, };
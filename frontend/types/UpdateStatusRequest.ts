@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectStatus } from "./ProjectStatus";
+
+/**
+ * UpdateStatusRequest
+ *
+ * Admin payload for moving a project through review (PUT /admin/projects/{id}/status).
+ * A bare JSON boolean is still accepted for older clients (see `extract::StatusUpdate`).
+ */
+export type UpdateStatusRequest = { status: ProjectStatus, 
+/**
+ * Explanation shown to the owner (e.g. why a project was rejected). Stored with the
+ * review and sent to the owner in a `status_change` notification.
+ */
+reason: string | null, 
+/**
+ * When true, the owner is notified even if no reason is given.
+ */
+notify_owner: boolean, };
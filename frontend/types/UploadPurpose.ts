@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * UploadPurpose
+ *
+ * The project slot an upload is destined for. Pending-upload limits are counted per purpose.
+ */
+export type UploadPurpose = "cover_image" | "video" | "report";
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * VersionInfo
+ *
+ * Build metadata of the running binary (GET /version). Fields that were unavailable at
+ * compile time are reported as "unknown".
+ */
+export type VersionInfo = { version: string, commit: string, built_at: string, env: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * ViewResponse
+ *
+ * Body of POST /projects/{id}/view: the project's view count including this view.
+ */
+export type ViewResponse = { view_count: bigint, };
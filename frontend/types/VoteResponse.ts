@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * VoteResponse
+ *
+ * Body of POST /projects/{id}/vote, for both a new like (200) and a repeated one (409), so
+ * the client can render the heart and counter without another request.
+ */
+export type VoteResponse = { already_liked: boolean, like_count: bigint, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * YearCount
+ *
+ * One entry of GET /projects/years: a cohort year and the number of public projects in it.
+ */
+export type YearCount = { year: number, count: bigint, };
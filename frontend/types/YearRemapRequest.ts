@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * YearRemapRequest
+ *
+ * Admin payload for POST /admin/maintenance/invalid-years/remap: moves every project filed
+ * under `from` to `to`, which must be an allowed academic year.
+ */
+export type YearRemapRequest = { from: number, to: number, };